@@ -23,14 +23,56 @@ use std::{
 pub type Result<T, E = Error> = core::result::Result<T, E>;
 
 /// A generic wrapper for any error.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 #[repr(transparent)]
-pub struct Error(Arc<dyn error::Error + Send + Sync>);
+pub struct Error(Arc<ErrorInner>);
+
+/// The wrapped error, along with the components it occurred in, if known.
+#[derive(Clone)]
+struct ErrorInner {
+    error: Arc<dyn error::Error + Send + Sync>,
+    component_stack: Option<Arc<[&'static str]>>,
+}
 
 impl Error {
     /// Converts the wrapper into the inner reference-counted error.
     pub fn into_inner(self) -> Arc<dyn error::Error + Send + Sync> {
-        Arc::clone(&self.0)
+        Arc::clone(&self.0.error)
+    }
+
+    /// Attaches the names of the components in which the error occurred, outermost first.
+    pub fn with_component_stack(
+        mut self,
+        stack: impl Into<Arc<[&'static str]>>,
+    ) -> Self {
+        Arc::make_mut(&mut self.0).component_stack = Some(stack.into());
+        self
+    }
+
+    /// Returns the names of the components in which the error occurred, outermost first.
+    ///
+    /// This is empty unless a stack was attached with [`Error::with_component_stack`].
+    pub fn component_stack(&self) -> &[&'static str] {
+        self.0.component_stack.as_deref().unwrap_or_default()
+    }
+
+    /// Describes the components in which the error occurred, like `<PostList> → <PostRow>`, or
+    /// returns `None` if no component stack is attached.
+    pub fn component_trace(&self) -> Option<String> {
+        let stack = self.component_stack();
+        (!stack.is_empty()).then(|| {
+            stack
+                .iter()
+                .map(|name| format!("<{name}>"))
+                .collect::<Vec<_>>()
+                .join(" → ")
+        })
+    }
+}
+
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Error").field(&self.0.error).finish()
     }
 }
 
@@ -38,13 +80,13 @@ impl ops::Deref for Error {
     type Target = Arc<dyn error::Error + Send + Sync>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.0.error
     }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.0.error)
     }
 }
 
@@ -53,7 +95,10 @@ where
     T: error::Error + Send + Sync + 'static,
 {
     fn from(value: T) -> Self {
-        Error(Arc::new(value))
+        Error(Arc::new(ErrorInner {
+            error: Arc::new(value),
+            component_stack: None,
+        }))
     }
 }
 
@@ -163,3 +208,26 @@ where
         this.inner.poll(cx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+    use std::io;
+
+    #[test]
+    fn errors_describe_the_components_they_occurred_in() {
+        let error = Error::from(io::Error::other("oops"));
+        assert!(error.component_stack().is_empty());
+        assert_eq!(error.component_trace(), None);
+
+        let traced = error.clone().with_component_stack(["App", "PostList"]);
+        assert_eq!(traced.component_stack(), ["App", "PostList"]);
+        assert_eq!(
+            traced.component_trace().as_deref(),
+            Some("<App> → <PostList>")
+        );
+        assert_eq!(traced.to_string(), "oops");
+        // the stack is attached to the new error only
+        assert!(error.component_stack().is_empty());
+    }
+}
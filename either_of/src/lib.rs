@@ -133,3 +133,76 @@ tuples!(EitherOf13 + EitherOf13Future + EitherOf13FutureProj => A, B, C, D, E, F
 tuples!(EitherOf14 + EitherOf14Future + EitherOf14FutureProj => A, B, C, D, E, F, G, H, I, J, K, L, M, N);
 tuples!(EitherOf15 + EitherOf15Future + EitherOf15FutureProj => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
 tuples!(EitherOf16 + EitherOf16Future + EitherOf16FutureProj => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
+
+/// Matches an expression against a list of patterns, wrapping the result of each arm in the
+/// variant of [`Either`] or `EitherOfN` that corresponds to its position.
+///
+/// This allows a `match` whose arms have different types to stay statically typed, without
+/// boxing each arm or nesting `Either`s by hand. Between 2 and 16 arms are supported.
+///
+/// ```
+/// use either_of::{either, Either, EitherOf3};
+///
+/// let two = either!(1 + 1,
+///     2 => "two",
+///     _ => 0,
+/// );
+/// assert!(matches!(two, Either::Left("two")));
+///
+/// let size = |n: usize| {
+///     either!(n,
+///         0 => "none",
+///         1 | 2 => 'a',
+///         _ => n,
+///     )
+/// };
+/// assert_eq!(size(0), EitherOf3::A("none"));
+/// assert_eq!(size(2), EitherOf3::B('a'));
+/// assert_eq!(size(10), EitherOf3::C(10));
+/// ```
+#[macro_export]
+macro_rules! either {
+    ($match:expr, $left_pat:pat => $left_expr:expr, $right_pat:pat => $right_expr:expr $(,)?) => {
+        match $match {
+            $left_pat => $crate::Either::Left($left_expr),
+            $right_pat => $crate::Either::Right($right_expr),
+        }
+    };
+    ($match:expr, $($pat:pat => $expr:expr),+ $(,)?) => {
+        $crate::either!(
+            @collect $match;
+            [];
+            [A B C D E F G H I J K L M N O P Q];
+            $($pat => $expr,)+
+        )
+    };
+    // assigns each arm the next variant name
+    (@collect $match:expr; [$($done:tt)*]; [$var:ident $($vars:ident)*]; $pat:pat => $expr:expr, $($rest:tt)*) => {
+        $crate::either!(
+            @collect $match;
+            [$($done)* ($var, $pat, $expr)];
+            [$($vars)*];
+            $($rest)*
+        )
+    };
+    // the first unused variant name tells us how many arms there are
+    (@collect $match:expr; [$(($var:ident, $pat:pat, $expr:expr))*]; [$next:ident $($vars:ident)*];) => {
+        match $match {
+            $($pat => $crate::either!(@variant $next $var $expr),)*
+        }
+    };
+    (@variant D $var:ident $expr:expr) => { $crate::EitherOf3::$var($expr) };
+    (@variant E $var:ident $expr:expr) => { $crate::EitherOf4::$var($expr) };
+    (@variant F $var:ident $expr:expr) => { $crate::EitherOf5::$var($expr) };
+    (@variant G $var:ident $expr:expr) => { $crate::EitherOf6::$var($expr) };
+    (@variant H $var:ident $expr:expr) => { $crate::EitherOf7::$var($expr) };
+    (@variant I $var:ident $expr:expr) => { $crate::EitherOf8::$var($expr) };
+    (@variant J $var:ident $expr:expr) => { $crate::EitherOf9::$var($expr) };
+    (@variant K $var:ident $expr:expr) => { $crate::EitherOf10::$var($expr) };
+    (@variant L $var:ident $expr:expr) => { $crate::EitherOf11::$var($expr) };
+    (@variant M $var:ident $expr:expr) => { $crate::EitherOf12::$var($expr) };
+    (@variant N $var:ident $expr:expr) => { $crate::EitherOf13::$var($expr) };
+    (@variant O $var:ident $expr:expr) => { $crate::EitherOf14::$var($expr) };
+    (@variant P $var:ident $expr:expr) => { $crate::EitherOf15::$var($expr) };
+    (@variant Q $var:ident $expr:expr) => { $crate::EitherOf16::$var($expr) };
+}
@@ -122,7 +122,6 @@ pub fn ContactList() -> impl IntoView {
 
 #[derive(Params, PartialEq, Clone, Debug)]
 pub struct ContactParams {
-    // Params isn't implemented for usize, only Option<usize>
     id: Option<usize>,
 }
 
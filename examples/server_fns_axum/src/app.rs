@@ -361,9 +361,17 @@ pub fn FileUpload() -> impl IntoView {
         Ok(count)
     }
 
-    let upload_action = Action::new_local(|data: &FormData| {
+    // the fraction of the file that has been sent so far
+    let (progress, set_progress) = signal(0.0);
+
+    let upload_action = Action::new_local(move |data: &FormData| {
         // `MultipartData` implements `From<FormData>`
-        file_length(data.clone().into())
+        let data = MultipartData::from(data.clone())
+            // reports how much of the file the browser has sent
+            .with_progress(move |sent, total| {
+                set_progress.set(sent as f64 / total as f64)
+            });
+        file_length(data)
     });
 
     view! {
@@ -384,7 +392,7 @@ pub fn FileUpload() -> impl IntoView {
                 {
                     "Upload a file.".to_string()
                 } else if upload_action.pending().get() {
-                    "Uploading...".to_string()
+                    format!("Uploading... {:.0}%", progress.get() * 100.0)
                 } else if let Some(Ok(value)) = upload_action.value().get() {
                     value.to_string()
                 } else {
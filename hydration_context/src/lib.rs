@@ -21,7 +21,7 @@ mod hydrate;
 mod ssr;
 #[cfg(feature = "browser")]
 pub use csr::*;
-use futures::Stream;
+use futures::{future::Shared, Stream};
 #[cfg(feature = "browser")]
 pub use hydrate::*;
 use serde::{Deserialize, Serialize};
@@ -35,6 +35,9 @@ pub type PinnedFuture<T> = Pin<Box<dyn Future<Output = T> + Send + Sync>>;
 pub type PinnedLocalFuture<T> = Pin<Box<dyn Future<Output = T>>>;
 /// Type alias for a boxed [`Stream`].
 pub type PinnedStream<T> = Pin<Box<dyn Stream<Item = T> + Send + Sync>>;
+/// A [`Future`] that resolves when server rendering should stop waiting for async data. See
+/// [`SharedContext::set_deadline`].
+pub type Deadline = Shared<PinnedFuture<()>>;
 
 #[derive(
     Clone, Debug, PartialEq, Eq, Hash, Default, Deserialize, Serialize,
@@ -154,4 +157,20 @@ pub trait SharedContext: Debug {
 
     /// Checks whether this chunk is being sent from the server before all its data have loaded.
     fn get_incomplete_chunk(&self, id: &SerializedDataId) -> bool;
+
+    /// Sets a deadline for rendering on the server. Once it resolves, any `<Suspense/>` that has
+    /// not resolved yet is sent in its fallback state, and the data that has not loaded yet is
+    /// no longer waited for, so that both are loaded by the client instead.
+    ///
+    /// By default, and in browser implementations, this is a no-op.
+    fn set_deadline(&self, deadline: PinnedFuture<()>) {
+        _ = deadline;
+    }
+
+    /// Returns the deadline set with [`set_deadline`](Self::set_deadline), if any.
+    ///
+    /// By default, and in browser implementations, this returns `None`.
+    fn deadline(&self) -> Option<Deadline> {
+        None
+    }
 }
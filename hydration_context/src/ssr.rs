@@ -1,9 +1,9 @@
 use super::{SerializedDataId, SharedContext};
-use crate::{PinnedFuture, PinnedStream};
+use crate::{Deadline, PinnedFuture, PinnedStream};
 use futures::{
     future::join_all,
     stream::{self, once},
-    Stream, StreamExt,
+    FutureExt, Stream, StreamExt,
 };
 use or_poisoned::OrPoisoned;
 use std::{
@@ -34,6 +34,7 @@ pub struct SsrSharedContext {
     sealed_error_boundaries: SealedErrors,
     deferred: Mutex<Vec<PinnedFuture<()>>>,
     incomplete: Arc<Mutex<Vec<SerializedDataId>>>,
+    deadline: Arc<Mutex<Option<Deadline>>>,
 }
 
 impl SsrSharedContext {
@@ -173,10 +174,15 @@ impl SharedContext for SsrSharedContext {
         // resolvers
         initial_chunk.push_str("__RESOURCE_RESOLVERS=[];");
 
+        // incomplete chunks are only known once the stream has finished, but the page can be
+        // hydrated before that
+        initial_chunk.push_str("__INCOMPLETE_CHUNKS=[];");
+
         let async_data = AsyncDataStream {
             async_buf: Arc::clone(&self.async_buf),
             errors: Arc::clone(&self.errors),
             sealed_error_boundaries: Arc::clone(&self.sealed_error_boundaries),
+            deadline: Arc::clone(&self.deadline),
         };
 
         let incomplete = Arc::clone(&self.incomplete);
@@ -227,12 +233,21 @@ impl SharedContext for SsrSharedContext {
             .iter()
             .any(|entry| entry == id)
     }
+
+    fn set_deadline(&self, deadline: PinnedFuture<()>) {
+        *self.deadline.lock().or_poisoned() = Some(deadline.shared());
+    }
+
+    fn deadline(&self) -> Option<Deadline> {
+        self.deadline.lock().or_poisoned().clone()
+    }
 }
 
 struct AsyncDataStream {
     async_buf: AsyncDataBuf,
     errors: ErrorBuf,
     sealed_error_boundaries: SealedErrors,
+    deadline: Arc<Mutex<Option<Deadline>>>,
 }
 
 impl Stream for AsyncDataStream {
@@ -242,6 +257,11 @@ impl Stream for AsyncDataStream {
         self: Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<Option<Self::Item>> {
+        let deadline_passed = match &mut *self.deadline.lock().or_poisoned() {
+            Some(deadline) => deadline.poll_unpin(cx).is_ready(),
+            None => false,
+        };
+
         let mut resolved = String::new();
         let mut async_buf = self.async_buf.write().or_poisoned();
         let data = mem::take(&mut *async_buf);
@@ -261,6 +281,10 @@ impl Stream for AsyncDataStream {
                 }
             }
         }
+        // once the deadline has passed, data that has not loaded yet is loaded by the client
+        if deadline_passed {
+            async_buf.clear();
+        }
         let sealed = self.sealed_error_boundaries.read().or_poisoned();
         for error in mem::take(&mut *self.errors.write().or_poisoned()) {
             if !sealed.contains(&error.0) {
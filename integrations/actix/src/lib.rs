@@ -5,6 +5,16 @@
 //! For more details on how to use the integrations, see the
 //! [`examples`](https://github.com/leptos-rs/leptos/tree/main/examples)
 //! directory in the Leptos repository.
+//!
+//! Along with the context types listed for each handler, every handler provides a
+//! [`RequestMeta`] and a [`ResponseMeta`](leptos::response::ResponseMeta), which do the same as
+//! [`HttpRequest`](actix_web::HttpRequest) and [`ResponseOptions`] without depending on Actix,
+//! so that server functions and components can be shared with other integrations.
+//!
+//! When a page is streamed, its shell, including the `<head>` with the preload hints for the
+//! app's JS and WASM, is sent as soon as it has rendered, before any `Suspense` content has
+//! loaded. Middleware that buffers the response body, like `actix_web`'s `Compress`, may hold
+//! the shell back until it has collected more of the page.
 
 use actix_http::header::{HeaderName, HeaderValue, ACCEPT, LOCATION, REFERER};
 use actix_web::{
@@ -18,17 +28,24 @@ use futures::{stream::once, Stream, StreamExt};
 use http::StatusCode;
 use hydration_context::SsrSharedContext;
 use leptos::{
+    config::LeptosOptions,
     context::{provide_context, use_context},
     reactive_graph::{computed::ScopedFuture, owner::Owner},
+    request::{provide_request_meta, RequestContext, RequestMeta},
+    response::{provide_response_meta, ResponseMetaParts},
     IntoView, *,
 };
 use leptos_integration_utils::{
     BoxedFnOnce, ExtendResponse, PinnedFuture, PinnedStream,
 };
+pub use leptos_integration_utils::{
+    CacheKey, CachedPage, MemoryCache, SsrCacheOptions, StaticCache,
+};
 use leptos_meta::ServerMetaContext;
 use leptos_router::{
     components::provide_server_redirect, location::RequestUrl, PathSegment,
-    RouteList, RouteListing, SsrMode, StaticDataMap, StaticMode, *,
+    RouteList, RouteListing, SsrMode, StaticDataMap, StaticMode, StaticRoute,
+    *,
 };
 use parking_lot::RwLock;
 use send_wrapper::SendWrapper;
@@ -39,8 +56,12 @@ use std::{
     fmt::{Debug, Display},
     ops::{Deref, DerefMut},
     sync::Arc,
+    time::Duration,
 };
 
+mod websocket;
+pub use websocket::*;
+
 /// This struct lets you define headers and override the status of the Response from an Element or a Server Function
 /// Typically contained inside of a ResponseOptions. Setting this is useful for cookies and custom responses.
 #[derive(Debug, Clone, Default)]
@@ -99,6 +120,10 @@ impl DerefMut for Request {
 }
 
 /// Allows you to override details of the HTTP response like the status code and add Headers/Cookies.
+///
+/// To set the status and headers from code that does not depend on Actix, use
+/// [`ResponseMeta`](leptos::response::ResponseMeta) instead. Anything set with `ResponseOptions`
+/// is applied after it, so it takes precedence.
 #[derive(Debug, Clone, Default)]
 pub struct ResponseOptions(pub Arc<RwLock<ResponseParts>>);
 
@@ -167,6 +192,42 @@ impl ExtendResponse for ActixResponse {
         }
     }
 
+    fn set_status(&mut self, status: u16) {
+        if let Ok(status) = StatusCode::from_u16(status) {
+            *self.0.status_mut() = status;
+        }
+    }
+
+    fn add_header(&mut self, name: &str, value: &str, replace: bool) -> bool {
+        let (Ok(name), Ok(value)) =
+            (HeaderName::try_from(name), HeaderValue::try_from(value))
+        else {
+            return false;
+        };
+        let headers = self.0.headers_mut();
+        if replace {
+            headers.remove(&name);
+        }
+        headers.append(name, value);
+        true
+    }
+
+    fn response_options_meta(
+        res_options: &Self::ResponseOptions,
+    ) -> ResponseMetaParts {
+        let res_options = res_options.0.read();
+        ResponseMetaParts {
+            status: res_options.status.map(|status| status.as_u16()),
+            headers: res_options
+                .headers
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect(),
+        }
+    }
+
     fn set_default_content_type(&mut self, content_type: &str) {
         let headers = self.0.headers_mut();
         if !headers.contains_key(header::CONTENT_TYPE) {
@@ -310,10 +371,16 @@ pub fn handle_server_fns_with_context(
                 owner
                     .with(|| {
                         ScopedFuture::new(async move {
+                            provide_request_meta(request_meta(&req));
+                            provide_request_context(&req);
+                            // as when rendering, the additional context is provided after the request's
+                            // context, so that it can override it
                             additional_context();
                             provide_context(Request::new(&req));
                             let res_options = ResponseOptions::default();
                             provide_context(res_options.clone());
+                            let res_meta = provide_response_meta();
+                            provide_server_redirect(redirect);
 
                             // store Accepts and Referer in case we need them for redirect (below)
                             let accepts_html = req
@@ -349,6 +416,7 @@ pub fn handle_server_fns_with_context(
                             }
 
                             // apply status code and headers if used changed them
+                            res.apply_response_meta(res_meta.take());
                             res.extend_response(&res_options);
                             res.0
                         })
@@ -621,12 +689,7 @@ where
     IV: IntoView + 'static,
 {
     _ = replace_blocks; // TODO
-    handle_response(method, additional_context, app_fn, |app, chunks| {
-        Box::pin(async move {
-            Box::pin(app.to_html_stream_out_of_order().chain(chunks()))
-                as PinnedStream<String>
-        })
-    })
+    handle_response(method, additional_context, app_fn, out_of_order_stream)
 }
 
 /// Returns an Actix [struct@Route](actix_web::Route) that listens for a `GET` request and tries
@@ -650,12 +713,7 @@ pub fn render_app_to_stream_in_order_with_context<IV>(
 where
     IV: IntoView + 'static,
 {
-    handle_response(method, additional_context, app_fn, |app, chunks| {
-        Box::pin(async move {
-            Box::pin(app.to_html_stream_in_order().chain(chunks()))
-                as PinnedStream<String>
-        })
-    })
+    handle_response(method, additional_context, app_fn, in_order_stream)
 }
 
 /// Returns an Actix [struct@Route](actix_web::Route) that listens for a `GET` request and tries
@@ -680,13 +738,158 @@ pub fn render_app_async_with_context<IV>(
 where
     IV: IntoView + 'static,
 {
-    handle_response(method, additional_context, app_fn, |app, chunks| {
-        Box::pin(async move {
-            let app = app.to_html_stream_in_order().collect::<String>().await;
-            let chunks = chunks();
-            Box::pin(once(async move { app }).chain(chunks))
-                as PinnedStream<String>
-        })
+    handle_response(method, additional_context, app_fn, async_stream)
+}
+
+/// Returns an Actix [struct@Route](actix_web::Route) that listens for a `GET` request and tries
+/// to route it using [leptos_router], choosing how to render the HTML for each request.
+///
+/// `mode` is called with the incoming request, and the [SsrMode] it returns determines whether
+/// the shell is streamed immediately with placeholders for pending `<Suspense/>` boundaries
+/// ([SsrMode::OutOfOrder] and [SsrMode::PartiallyBlocked]), streamed in order, pausing at each
+/// boundary ([SsrMode::InOrder]), or sent only once every boundary has resolved
+/// ([SsrMode::Async]). This is useful, for example, to serve fully-resolved HTML to crawlers
+/// while streaming to browsers.
+///
+/// ```
+/// use actix_web::{App, HttpServer};
+/// use leptos::prelude::*;
+/// use leptos_router::{Method, SsrMode};
+///
+/// #[component]
+/// fn MyApp() -> impl IntoView {
+///     view! { <main>"Hello, world!"</main> }
+/// }
+///
+/// # if false { // don't actually try to run a server in a doctest...
+/// #[actix_web::main]
+/// async fn main() -> std::io::Result<()> {
+///     let conf = get_configuration(Some("Cargo.toml")).unwrap();
+///     let addr = conf.leptos_options.site_addr.clone();
+///     HttpServer::new(move || {
+///         App::new().route(
+///             "/{tail:.*}",
+///             leptos_actix::render_app_with_mode(
+///                 |req| {
+///                     let is_bot = req
+///                         .headers()
+///                         .get("user-agent")
+///                         .and_then(|ua| ua.to_str().ok())
+///                         .map(|ua| ua.contains("bot"))
+///                         .unwrap_or(false);
+///                     if is_bot {
+///                         SsrMode::Async
+///                     } else {
+///                         SsrMode::OutOfOrder
+///                     }
+///                 },
+///                 MyApp,
+///                 Method::Get,
+///             ),
+///         )
+///     })
+///     .bind(&addr)?
+///     .run()
+///     .await
+/// }
+/// # }
+/// ```
+///
+/// ## Provided Context Types
+/// This function always provides context values including the following types:
+/// - [ResponseOptions]
+/// - [HttpRequest](actix_web::HttpRequest)
+/// - [MetaContext](leptos_meta::MetaContext)
+/// - [RouterIntegrationContext](leptos_router::RouterIntegrationContext)
+#[tracing::instrument(level = "trace", fields(error), skip_all)]
+pub fn render_app_with_mode<IV>(
+    mode: impl Fn(&HttpRequest) -> SsrMode + Clone + Send + 'static,
+    app_fn: impl Fn() -> IV + Clone + Send + 'static,
+    method: Method,
+) -> Route
+where
+    IV: IntoView + 'static,
+{
+    render_app_with_mode_and_context(mode, || {}, app_fn, method)
+}
+
+/// Returns an Actix [struct@Route](actix_web::Route) that listens for a `GET` request and tries
+/// to route it using [leptos_router], choosing how to render the HTML for each request.
+///
+/// See [render_app_with_mode] for how `mode` is used.
+///
+/// This function allows you to provide additional information to Leptos for your route.
+/// It could be used to pass in Path Info, Connection Info, or anything your heart desires.
+///
+/// ## Provided Context Types
+/// This function always provides context values including the following types:
+/// - [ResponseOptions]
+/// - [HttpRequest](actix_web::HttpRequest)
+/// - [MetaContext](leptos_meta::MetaContext)
+/// - [RouterIntegrationContext](leptos_router::RouterIntegrationContext)
+#[tracing::instrument(level = "trace", fields(error), skip_all)]
+pub fn render_app_with_mode_and_context<IV>(
+    mode: impl Fn(&HttpRequest) -> SsrMode + Clone + Send + 'static,
+    additional_context: impl Fn() + 'static + Clone + Send,
+    app_fn: impl Fn() -> IV + Clone + Send + 'static,
+    method: Method,
+) -> Route
+where
+    IV: IntoView + 'static,
+{
+    handle_response_with_mode(method, additional_context, app_fn, move |req| {
+        match mode(req) {
+            SsrMode::OutOfOrder | SsrMode::PartiallyBlocked => {
+                out_of_order_stream
+            }
+            SsrMode::InOrder => in_order_stream,
+            SsrMode::Async => async_stream,
+        }
+    })
+}
+
+type StreamBuilder<IV> = fn(
+    IV,
+    BoxedFnOnce<PinnedStream<String>>,
+) -> PinnedFuture<PinnedStream<String>>;
+
+fn out_of_order_stream<IV>(
+    app: IV,
+    chunks: BoxedFnOnce<PinnedStream<String>>,
+) -> PinnedFuture<PinnedStream<String>>
+where
+    IV: IntoView + 'static,
+{
+    Box::pin(async move {
+        Box::pin(app.to_html_stream_out_of_order().chain(chunks()))
+            as PinnedStream<String>
+    })
+}
+
+fn in_order_stream<IV>(
+    app: IV,
+    chunks: BoxedFnOnce<PinnedStream<String>>,
+) -> PinnedFuture<PinnedStream<String>>
+where
+    IV: IntoView + 'static,
+{
+    Box::pin(async move {
+        Box::pin(app.to_html_stream_in_order().chain(chunks()))
+            as PinnedStream<String>
+    })
+}
+
+fn async_stream<IV>(
+    app: IV,
+    chunks: BoxedFnOnce<PinnedStream<String>>,
+) -> PinnedFuture<PinnedStream<String>>
+where
+    IV: IntoView + 'static,
+{
+    Box::pin(async move {
+        let app = app.to_html_stream_in_order().collect::<String>().await;
+        let chunks = chunks();
+        Box::pin(once(async move { app }).chain(chunks)) as PinnedStream<String>
     })
 }
 
@@ -701,11 +904,77 @@ fn provide_contexts(
     provide_context(RequestUrl::new(&path));
     provide_context(meta_context.clone());
     provide_context(res_options.clone());
+    provide_request_meta(request_meta(&req));
+    provide_request_context(&req);
     provide_context(req);
     provide_server_redirect(redirect);
+    provide_response_meta();
     leptos::nonce::provide_nonce();
 }
 
+/// The framework-agnostic [`RequestMeta`] for a request.
+fn request_meta(req: &HttpRequest) -> RequestMeta {
+    let headers = req.headers().iter().filter_map(|(name, value)| {
+        Some((name.to_string(), value.to_str().ok()?.to_string()))
+    });
+    RequestMeta::new(
+        req.method().as_str(),
+        req.uri().to_string(),
+        headers,
+        req.peer_addr(),
+    )
+}
+
+/// Provides the values in the [`RequestContext`] in the request's extensions, if there is one.
+fn provide_request_context(req: &HttpRequest) {
+    if let Some(cx) = req.extensions().get::<RequestContext>() {
+        cx.provide();
+    }
+}
+
+/// Sets a deadline for rendering the current request. Once `timeout` has passed, any `Suspense`
+/// boundaries that are still pending are sent with their fallbacks, and load their data in the
+/// browser instead.
+///
+/// By default, this uses the `ssr_timeout` of the [`LeptosOptions`] in context, if any. It can
+/// be called in the additional context of a handler to set a different deadline for a request.
+pub fn set_render_timeout(timeout: Duration) {
+    if let Some(sc) = Owner::current_shared_context() {
+        sc.set_deadline(Box::pin(rt::time::sleep(timeout)));
+    }
+}
+
+/// Sets the deadline from the [`LeptosOptions`] in context, unless one has already been set.
+fn set_default_render_timeout() {
+    let timeout = use_context::<LeptosOptions>()
+        .and_then(|options| options.ssr_timeout)
+        .map(Duration::from_millis);
+    if let (Some(timeout), Some(sc)) =
+        (timeout, Owner::current_shared_context())
+    {
+        if sc.deadline().is_none() {
+            set_render_timeout(timeout);
+        }
+    }
+}
+
+/// For a `GET` request, a function that returns the key the page is cached under, if an
+/// [`SsrCacheOptions`] is provided in the additional context.
+fn ssr_cache_key(
+    req: &HttpRequest,
+) -> Option<impl FnOnce(&SsrCacheOptions) -> CacheKey + Send + 'static> {
+    if req.method() != http::Method::GET {
+        return None;
+    }
+    let path = req.uri().path_and_query()?.as_str().to_string();
+    let headers = req.headers().clone();
+    Some(move |options: &SsrCacheOptions| {
+        options.key(&path, |name| {
+            Some(headers.get(name)?.to_str().ok()?.to_string())
+        })
+    })
+}
+
 fn leptos_corrected_path(req: &HttpRequest) -> String {
     let path = req.path();
     let query = req.query_string();
@@ -720,10 +989,24 @@ fn handle_response<IV>(
     method: Method,
     additional_context: impl Fn() + 'static + Clone + Send,
     app_fn: impl Fn() -> IV + Clone + Send + 'static,
-    stream_builder: fn(
-        IV,
-        BoxedFnOnce<PinnedStream<String>>,
-    ) -> PinnedFuture<PinnedStream<String>>,
+    stream_builder: StreamBuilder<IV>,
+) -> Route
+where
+    IV: IntoView + 'static,
+{
+    handle_response_with_mode(method, additional_context, app_fn, move |_| {
+        stream_builder
+    })
+}
+
+fn handle_response_with_mode<IV>(
+    method: Method,
+    additional_context: impl Fn() + 'static + Clone + Send,
+    app_fn: impl Fn() -> IV + Clone + Send + 'static,
+    select_stream_builder: impl Fn(&HttpRequest) -> StreamBuilder<IV>
+        + Clone
+        + Send
+        + 'static,
 ) -> Route
 where
     IV: IntoView + 'static,
@@ -731,6 +1014,8 @@ where
     let handler = move |req: HttpRequest| {
         let app_fn = app_fn.clone();
         let add_context = additional_context.clone();
+        let stream_builder = select_stream_builder(&req);
+        let cache_key = ssr_cache_key(&req);
 
         async move {
             let res_options = ResponseOptions::default();
@@ -743,17 +1028,33 @@ where
                 move || {
                     provide_contexts(req, &meta_context, &res_options);
                     add_context();
+                    set_default_render_timeout();
                 }
             };
 
-            let res = ActixResponse::from_app(
-                app_fn,
-                meta_output,
-                additional_context,
-                res_options,
-                stream_builder,
-            )
-            .await;
+            let res = match cache_key {
+                Some(cache_key) => {
+                    ActixResponse::from_app_with_cache(
+                        cache_key,
+                        app_fn,
+                        meta_output,
+                        additional_context,
+                        res_options,
+                        stream_builder,
+                    )
+                    .await
+                }
+                None => {
+                    ActixResponse::from_app(
+                        app_fn,
+                        meta_output,
+                        additional_context,
+                        res_options,
+                        stream_builder,
+                    )
+                    .await
+                }
+            };
 
             res.0
         }
@@ -861,7 +1162,7 @@ pub struct ActixRouteListing {
     path: String,
     mode: SsrMode,
     methods: Vec<leptos_router::Method>,
-    static_mode: Option<(StaticMode, StaticDataMap)>,
+    static_route: Option<StaticRoute>,
 }
 
 impl From<RouteListing> for ActixRouteListing {
@@ -874,12 +1175,12 @@ impl From<RouteListing> for ActixRouteListing {
         };
         let mode = value.mode();
         let methods = value.methods().collect();
-        let static_mode = value.into_static_parts();
+        let static_route = value.into_static_route();
         Self {
             path,
             mode,
             methods,
-            static_mode,
+            static_route,
         }
     }
 }
@@ -890,13 +1191,13 @@ impl ActixRouteListing {
         path: String,
         mode: SsrMode,
         methods: impl IntoIterator<Item = leptos_router::Method>,
-        static_mode: Option<(StaticMode, StaticDataMap)>,
+        static_route: Option<StaticRoute>,
     ) -> Self {
         Self {
             path,
             mode,
             methods: methods.into_iter().collect(),
-            static_mode,
+            static_route,
         }
     }
 
@@ -918,7 +1219,7 @@ impl ActixRouteListing {
     /// Whether this route is statically rendered.
     #[inline(always)]
     pub fn static_mode(&self) -> Option<StaticMode> {
-        self.static_mode.as_ref().map(|n| n.0)
+        self.static_route.as_ref().map(StaticRoute::mode)
     }
 }
 
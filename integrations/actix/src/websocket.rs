@@ -0,0 +1,202 @@
+use actix_codec::{Decoder, Encoder};
+use actix_http::ws::{handshake, Codec};
+pub use actix_http::ws::{
+    CloseCode, CloseReason, Frame, Message, ProtocolError,
+};
+use actix_web::{
+    body::BodyStream,
+    web::{Bytes, BytesMut, Payload},
+    HttpRequest, HttpResponse,
+};
+use futures::{channel::mpsc, Stream, StreamExt};
+use std::{
+    fmt::{Debug, Display},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Upgrades an HTTP request to a WebSocket connection.
+///
+/// Returns the response that completes the handshake, which should be returned from the
+/// handler, along with a [`WebSocketSession`] used to send messages and a [`WebSocketStream`]
+/// of the frames sent by the client. The stream is not `Send`, so it should be read in a task
+/// spawned with [`actix_web::rt::spawn`].
+///
+/// The connection is not kept alive automatically: reply to a [`Frame::Ping`] with
+/// [`WebSocketSession::pong`] if the client expects it.
+///
+/// ```
+/// use actix_web::{rt, web, Error, HttpRequest, HttpResponse};
+/// use futures::StreamExt;
+/// use leptos_actix::{upgrade_websocket, Frame};
+///
+/// async fn echo(
+///     req: HttpRequest,
+///     body: web::Payload,
+/// ) -> Result<HttpResponse, Error> {
+///     let (res, session, mut stream) = upgrade_websocket(&req, body)?;
+///     rt::spawn(async move {
+///         while let Some(Ok(frame)) = stream.next().await {
+///             let sent = match frame {
+///                 Frame::Text(text) => session.text(String::from_utf8_lossy(&text)),
+///                 Frame::Ping(bytes) => session.pong(bytes),
+///                 Frame::Close(reason) => {
+///                     _ = session.close(reason);
+///                     break;
+///                 }
+///                 _ => Ok(()),
+///             };
+///             if sent.is_err() {
+///                 break;
+///             }
+///         }
+///     });
+///     Ok(res)
+/// }
+///
+/// # if false { // don't actually try to run a server in a doctest...
+/// let app = actix_web::App::new().route("/ws", web::get().to(echo));
+/// # }
+/// ```
+pub fn upgrade_websocket(
+    req: &HttpRequest,
+    body: Payload,
+) -> Result<(HttpResponse, WebSocketSession, WebSocketStream), actix_web::Error>
+{
+    let res = handshake(req.head())?.finish();
+    let (tx, rx) = mpsc::unbounded();
+    let res = HttpResponse::from(res)
+        .set_body(BodyStream::new(rx))
+        .map_into_boxed_body();
+    Ok((
+        res,
+        WebSocketSession { tx },
+        WebSocketStream {
+            body,
+            buf: BytesMut::new(),
+            codec: Codec::new(),
+            done: false,
+        },
+    ))
+}
+
+/// Sends messages on a WebSocket connection created with [`upgrade_websocket`].
+///
+/// The connection is closed once every clone of the session has been dropped.
+#[derive(Debug, Clone)]
+pub struct WebSocketSession {
+    tx: mpsc::UnboundedSender<Result<Bytes, ProtocolError>>,
+}
+
+/// The error returned when sending a message on a WebSocket connection that has been closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WebSocketClosed;
+
+impl Display for WebSocketClosed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("the WebSocket connection is closed")
+    }
+}
+
+impl std::error::Error for WebSocketClosed {}
+
+impl WebSocketSession {
+    /// Sends a message to the client.
+    pub fn send(&self, message: Message) -> Result<(), WebSocketClosed> {
+        let mut buf = BytesMut::new();
+        Codec::new()
+            .encode(message, &mut buf)
+            .map_err(|_| WebSocketClosed)?;
+        self.tx
+            .unbounded_send(Ok(buf.freeze()))
+            .map_err(|_| WebSocketClosed)
+    }
+
+    /// Sends a text message.
+    pub fn text(&self, text: impl Into<String>) -> Result<(), WebSocketClosed> {
+        self.send(Message::Text(text.into().into()))
+    }
+
+    /// Sends a binary message.
+    pub fn binary(
+        &self,
+        bytes: impl Into<Bytes>,
+    ) -> Result<(), WebSocketClosed> {
+        self.send(Message::Binary(bytes.into()))
+    }
+
+    /// Sends a ping.
+    pub fn ping(&self, bytes: impl Into<Bytes>) -> Result<(), WebSocketClosed> {
+        self.send(Message::Ping(bytes.into()))
+    }
+
+    /// Replies to a ping.
+    pub fn pong(&self, bytes: impl Into<Bytes>) -> Result<(), WebSocketClosed> {
+        self.send(Message::Pong(bytes.into()))
+    }
+
+    /// Sends a close frame and ends the connection.
+    pub fn close(
+        self,
+        reason: Option<CloseReason>,
+    ) -> Result<(), WebSocketClosed> {
+        let res = self.send(Message::Close(reason));
+        self.tx.close_channel();
+        res
+    }
+}
+
+/// The frames sent by the client on a WebSocket connection created with [`upgrade_websocket`].
+pub struct WebSocketStream {
+    body: Payload,
+    buf: BytesMut,
+    codec: Codec,
+    done: bool,
+}
+
+impl Debug for WebSocketStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebSocketStream")
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Stream for WebSocketStream {
+    type Item = Result<Frame, ProtocolError>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            // decode any complete frames that have already been received
+            match this.codec.decode(&mut this.buf) {
+                Ok(Some(frame)) => return Poll::Ready(Some(Ok(frame))),
+                Ok(None) if this.done => return Poll::Ready(None),
+                Ok(None) => {}
+                Err(e) => {
+                    this.done = true;
+                    this.buf.clear();
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+
+            match this.body.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(bytes))) => {
+                    this.buf.extend_from_slice(&bytes)
+                }
+                Poll::Ready(Some(Err(e))) => {
+                    this.done = true;
+                    this.buf.clear();
+                    return Poll::Ready(Some(Err(ProtocolError::Io(
+                        std::io::Error::other(e.to_string()),
+                    ))));
+                }
+                Poll::Ready(None) => this.done = true,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
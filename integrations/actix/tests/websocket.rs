@@ -0,0 +1,81 @@
+use actix_codec::{Decoder, Encoder};
+use actix_http::ws::{Codec, Frame, Message};
+use actix_web::{
+    body::to_bytes,
+    http::{header, StatusCode},
+    test::TestRequest,
+    web::{self, BytesMut},
+    FromRequest,
+};
+use futures::StreamExt;
+use leptos_actix::upgrade_websocket;
+
+#[actix_web::test]
+async fn websocket_requests_are_upgraded() {
+    // frames sent by a client are masked
+    let mut client = Codec::new().client_mode();
+    let mut sent = BytesMut::new();
+    client
+        .encode(Message::Text("hello".into()), &mut sent)
+        .unwrap();
+    client
+        .encode(Message::Ping("beat".into()), &mut sent)
+        .unwrap();
+
+    let (req, mut payload) = TestRequest::get()
+        .insert_header((header::UPGRADE, "websocket"))
+        .insert_header((header::CONNECTION, "upgrade"))
+        .insert_header((header::SEC_WEBSOCKET_VERSION, "13"))
+        .insert_header((header::SEC_WEBSOCKET_KEY, "dGhlIHNhbXBsZSBub25jZQ=="))
+        .set_payload(sent.freeze())
+        .to_http_parts();
+    let body = web::Payload::from_request(&req, &mut payload)
+        .await
+        .unwrap();
+
+    let (res, session, mut stream) = upgrade_websocket(&req, body).unwrap();
+    assert_eq!(res.status(), StatusCode::SWITCHING_PROTOCOLS);
+    assert_eq!(
+        res.headers().get(header::SEC_WEBSOCKET_ACCEPT).unwrap(),
+        "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+    );
+
+    assert_eq!(
+        stream.next().await.unwrap().unwrap(),
+        Frame::Text("hello".into())
+    );
+    assert_eq!(
+        stream.next().await.unwrap().unwrap(),
+        Frame::Ping("beat".into())
+    );
+    assert!(stream.next().await.is_none());
+
+    session.text("hi!").unwrap();
+    session.pong("beat").unwrap();
+    session.close(None).unwrap();
+
+    let mut received =
+        BytesMut::from(&to_bytes(res.into_body()).await.unwrap()[..]);
+    let mut frames = Vec::new();
+    while let Some(frame) = client.decode(&mut received).unwrap() {
+        frames.push(frame);
+    }
+    assert_eq!(
+        frames,
+        [
+            Frame::Text("hi!".into()),
+            Frame::Pong("beat".into()),
+            Frame::Close(None)
+        ]
+    );
+}
+
+#[actix_web::test]
+async fn other_requests_are_not_upgraded() {
+    let (req, mut payload) = TestRequest::get().to_http_parts();
+    let body = web::Payload::from_request(&req, &mut payload)
+        .await
+        .unwrap();
+    let err = upgrade_websocket(&req, body).unwrap_err();
+    assert_eq!(err.error_response().status(), StatusCode::BAD_REQUEST);
+}
@@ -25,6 +25,32 @@
 //! `
 //! If you are not using the `wasm` feature, do not set `default-features = false` on this package.
 //!
+//! ## Request Context
+//! Each request is rendered in its own reactive root, which is disposed of once the response has
+//! been sent. The handlers in this crate provide the request's [`Parts`] via context, so that any
+//! component or server function can read its headers (including cookies), URI, and
+//! [extensions](axum::http::Extensions), along with [`ResponseOptions`], which can be used to set
+//! the status and headers of the response. Any other data, like Axum state, can be provided with
+//! the `_with_context` version of each handler, or for each request by adding a
+//! [`RequestContext`] to the request's extensions in a layer or middleware.
+//!
+//! Every handler also provides a [`RequestMeta`] and a
+//! [`ResponseMeta`](leptos::response::ResponseMeta), which do the same as `Parts` and
+//! `ResponseOptions` without depending on Axum, so that server functions and components can
+//! be shared with other integrations.
+//!
+//! ## Streaming
+//! When a page is streamed, its shell, including the `<head>` with the preload hints for the
+//! app's JS and WASM, is sent as soon as it has rendered, before any `Suspense` content has
+//! loaded. Layers that buffer the response body, like `tower_http`'s `CompressionLayer`, may
+//! hold the shell back until they have collected more of the page, so it only reaches the
+//! browser early if the response is not compressed, or is compressed by something (like a
+//! reverse proxy) that flushes each chunk.
+//!
+//! ## Caching
+//! Server-rendered pages can be cached by providing an [`SsrCacheOptions`] in the additional
+//! context of a handler. Pages are stored in a [`StaticCache`], keyed by their path and query and
+//! the request headers they vary on, until their time-to-live has passed or they are invalidated.
 //!
 //! ## More information
 //!
@@ -55,20 +81,28 @@ use leptos::{
     context::{provide_context, use_context},
     prelude::*,
     reactive_graph::{computed::ScopedFuture, owner::Owner},
+    request::{provide_request_meta, RequestContext, RequestMeta},
+    response::{provide_response_meta, ResponseMetaParts},
     IntoView,
 };
 use leptos_integration_utils::{
-    BoxedFnOnce, ExtendResponse, PinnedFuture, PinnedStream,
+    render_static_html, BoxedFnOnce, ExtendResponse, PinnedFuture, PinnedStream,
+};
+pub use leptos_integration_utils::{
+    CacheKey, CachedPage, FileCache, MemoryCache, SsrCacheOptions, StaticCache,
 };
 use leptos_meta::ServerMetaContext;
 use leptos_router::{
     components::provide_server_redirect, location::RequestUrl, PathSegment,
-    RouteList, RouteListing, SsrMode, StaticDataMap, StaticMode,
+    RouteList, RouteListing, Sitemap, SitemapRoute, SsrMode, StaticDataMap,
+    StaticMode, StaticRoute,
 };
 use parking_lot::RwLock;
 use server_fn::{redirect::REDIRECT_HEADER, ServerFnError};
 use std::{fmt::Debug, io, pin::Pin, sync::Arc};
 #[cfg(feature = "default")]
+use std::{net::SocketAddr, time::Duration};
+#[cfg(feature = "default")]
 use tower::ServiceExt;
 #[cfg(feature = "default")]
 use tower_http::services::ServeDir;
@@ -102,6 +136,10 @@ impl ResponseParts {
 ///
 /// If you provide your own handler, you will need to provide `ResponseOptions` via context
 /// yourself if you want to access it via context.
+///
+/// To set the status and headers from code that does not depend on Axum, use
+/// [`ResponseMeta`](leptos::response::ResponseMeta) instead. Anything set with `ResponseOptions`
+/// is applied after it, so it takes precedence.
 /// ```
 /// use leptos::prelude::*;
 ///
@@ -165,6 +203,42 @@ impl ExtendResponse for AxumResponse {
             .extend(std::mem::take(&mut res_options.headers));
     }
 
+    fn set_status(&mut self, status: u16) {
+        if let Ok(status) = StatusCode::from_u16(status) {
+            *self.0.status_mut() = status;
+        }
+    }
+
+    fn add_header(&mut self, name: &str, value: &str, replace: bool) -> bool {
+        let (Ok(name), Ok(value)) =
+            (HeaderName::try_from(name), HeaderValue::try_from(value))
+        else {
+            return false;
+        };
+        let headers = self.0.headers_mut();
+        if replace {
+            headers.remove(&name);
+        }
+        headers.append(name, value);
+        true
+    }
+
+    fn response_options_meta(
+        res_options: &Self::ResponseOptions,
+    ) -> ResponseMetaParts {
+        let res_options = res_options.0.read();
+        ResponseMetaParts {
+            status: res_options.status.map(|status| status.as_u16()),
+            headers: res_options
+                .headers
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect(),
+        }
+    }
+
     fn set_default_content_type(&mut self, content_type: &str) {
         let headers = self.0.headers_mut();
         if !headers.contains_key(header::CONTENT_TYPE) {
@@ -219,6 +293,62 @@ pub fn redirect(path: &str) {
     }
 }
 
+/// The framework-agnostic [`RequestMeta`] for a request.
+fn request_meta(parts: &Parts) -> RequestMeta {
+    let headers = parts.headers.iter().filter_map(|(name, value)| {
+        Some((name.to_string(), value.to_str().ok()?.to_string()))
+    });
+    // connection info is only available when serving with Tokio
+    #[cfg(feature = "default")]
+    let remote_addr = parts
+        .extensions
+        .get::<axum::extract::ConnectInfo<SocketAddr>>()
+        .map(|info| info.0);
+    #[cfg(not(feature = "default"))]
+    let remote_addr = None;
+    RequestMeta::new(
+        parts.method.as_str(),
+        parts.uri.to_string(),
+        headers,
+        remote_addr,
+    )
+}
+
+/// Provides the values in the [`RequestContext`] in the request's extensions, if there is one.
+fn provide_request_context(parts: &Parts) {
+    if let Some(cx) = parts.extensions.get::<RequestContext>() {
+        cx.provide();
+    }
+}
+
+/// Sets a deadline for rendering the current request. Once `timeout` has passed, any `Suspense`
+/// boundaries that are still pending are sent with their fallbacks, and load their data in the
+/// browser instead.
+///
+/// By default, this uses the `ssr_timeout` of the [`LeptosOptions`] in context, if any. It can
+/// be called in the additional context of a handler to set a different deadline for a request.
+#[cfg(feature = "default")]
+pub fn set_render_timeout(timeout: Duration) {
+    if let Some(sc) = Owner::current_shared_context() {
+        sc.set_deadline(Box::pin(tokio::time::sleep(timeout)));
+    }
+}
+
+/// Sets the deadline from the [`LeptosOptions`] in context, unless one has already been set.
+#[cfg(feature = "default")]
+fn set_default_render_timeout() {
+    let timeout = use_context::<LeptosOptions>()
+        .and_then(|options| options.ssr_timeout)
+        .map(Duration::from_millis);
+    if let (Some(timeout), Some(sc)) =
+        (timeout, Owner::current_shared_context())
+    {
+        if sc.deadline().is_none() {
+            set_render_timeout(timeout);
+        }
+    }
+}
+
 /// Decomposes an HTTP request into its parts, allowing you to read its headers
 /// and other data without consuming the body. Creates a new Request from the
 /// original parts for further processing
@@ -332,10 +462,16 @@ async fn handle_server_fns_inner(
         owner
             .with(|| {
                 ScopedFuture::new(async move {
+                    provide_request_meta(request_meta(&parts));
+                    provide_request_context(&parts);
+                    // as when rendering, the additional context is provided after the request's
+                    // context, so that it can override it
                     additional_context();
                     provide_context(parts);
                     let res_options = ResponseOptions::default();
                     provide_context(res_options.clone());
+                    let res_meta = provide_response_meta();
+                    provide_server_redirect(redirect);
 
                     // store Accepts and Referer in case we need them for redirect (below)
                     let accepts_html = req
@@ -363,6 +499,7 @@ async fn handle_server_fns_inner(
                     }
 
                     // apply status code and headers if used changed them
+                    res.apply_response_meta(res_meta.take());
                     res.extend_response(&res_options);
                     Ok(res.0)
                 })
@@ -611,6 +748,133 @@ pub fn render_route_with_context<IV>(
        + 'static
 where
     IV: IntoView + 'static,
+{
+    render_app_with_mode_and_context(
+        move |req| {
+            // 1. Process route to match the values in routeListing
+            let path = req
+                .extensions()
+                .get::<MatchedPath>()
+                .expect("Failed to get Axum router rule")
+                .as_str();
+            // 2. Find RouteListing in paths. This should probably be optimized, we probably don't want to
+            // search for this every time
+            let listing: &AxumRouteListing =
+                paths.iter().find(|r| r.path() == path).unwrap_or_else(|| {
+                    panic!(
+                        "Failed to find the route {path} requested by the \
+                         user. This suggests that the routing rules in the \
+                         Router that call this handler needs to be edited!"
+                    )
+                });
+            // 3. Use the listing's mode to choose how to render
+            listing.mode()
+        },
+        additional_context,
+        app_fn,
+    )
+}
+
+/// Returns an Axum [Handler](axum::handler::Handler) that listens for a `GET` request and tries
+/// to route it using [leptos_router], choosing how to render the HTML for each request.
+///
+/// `mode` is called with the incoming request, and the [`SsrMode`] it returns determines whether
+/// the shell is streamed immediately with placeholders for pending `<Suspense/>` boundaries
+/// ([`SsrMode::OutOfOrder`] and [`SsrMode::PartiallyBlocked`]), streamed in order, pausing at
+/// each boundary ([`SsrMode::InOrder`]), or sent only once every boundary has resolved
+/// ([`SsrMode::Async`]). This is useful, for example, to serve fully-resolved HTML to crawlers
+/// while streaming to browsers.
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use leptos::prelude::*;
+/// use leptos_router::SsrMode;
+///
+/// #[component]
+/// fn MyApp() -> impl IntoView {
+///     view! { <main>"Hello, world!"</main> }
+/// }
+///
+/// # if false { // don't actually try to run a server in a doctest...
+/// #[tokio::main]
+/// async fn main() {
+///     let conf = get_configuration(Some("Cargo.toml")).unwrap();
+///     let addr = conf.leptos_options.site_addr.clone();
+///
+///     // build our application with a route
+///     let app = Router::new().fallback(leptos_axum::render_app_with_mode(
+///         |req| {
+///             let is_bot = req
+///                 .headers()
+///                 .get("user-agent")
+///                 .and_then(|ua| ua.to_str().ok())
+///                 .map(|ua| ua.contains("bot"))
+///                 .unwrap_or(false);
+///             if is_bot {
+///                 SsrMode::Async
+///             } else {
+///                 SsrMode::OutOfOrder
+///             }
+///         },
+///         || view! { <MyApp/> },
+///     ));
+///
+///     // run our app with hyper
+///     let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
+///     axum::serve(listener, app.into_make_service())
+///         .await
+///         .unwrap();
+/// }
+/// # }
+/// ```
+///
+/// ## Provided Context Types
+/// This function always provides context values including the following types:
+/// - [`Parts`]
+/// - [`ResponseOptions`]
+/// - [`ServerMetaContext`](leptos_meta::ServerMetaContext)
+/// - [`RouterIntegrationContext`](leptos_router::RouterIntegrationContext)
+#[tracing::instrument(level = "trace", fields(error), skip_all)]
+pub fn render_app_with_mode<IV>(
+    mode: impl Fn(&Request<Body>) -> SsrMode + Clone + Send + 'static,
+    app_fn: impl Fn() -> IV + Clone + Send + 'static,
+) -> impl Fn(
+    Request<Body>,
+) -> Pin<Box<dyn Future<Output = Response<Body>> + Send + 'static>>
+       + Clone
+       + Send
+       + 'static
+where
+    IV: IntoView + 'static,
+{
+    render_app_with_mode_and_context(mode, || {}, app_fn)
+}
+
+/// Returns an Axum [Handler](axum::handler::Handler) that listens for a `GET` request and tries
+/// to route it using [leptos_router], choosing how to render the HTML for each request.
+/// It allows you to pass in a context function with additional info to be made available to the app.
+///
+/// See [`render_app_with_mode`] for how `mode` is used.
+///
+/// ## Provided Context Types
+/// This function always provides context values including the following types:
+/// - [`Parts`]
+/// - [`ResponseOptions`]
+/// - [`ServerMetaContext`](leptos_meta::ServerMetaContext)
+/// - [`RouterIntegrationContext`](leptos_router::RouterIntegrationContext)
+#[tracing::instrument(level = "trace", fields(error), skip_all)]
+pub fn render_app_with_mode_and_context<IV>(
+    mode: impl Fn(&Request<Body>) -> SsrMode + Clone + Send + 'static,
+    additional_context: impl Fn() + 'static + Clone + Send,
+    app_fn: impl Fn() -> IV + Clone + Send + 'static,
+) -> impl Fn(
+    Request<Body>,
+) -> Pin<Box<dyn Future<Output = Response<Body>> + Send + 'static>>
+       + Clone
+       + Send
+       + 'static
+where
+    IV: IntoView + 'static,
 {
     let ooo = render_app_to_stream_with_context(
         additional_context.clone(),
@@ -630,30 +894,11 @@ where
         app_fn.clone(),
     );
 
-    move |req| {
-        // 1. Process route to match the values in routeListing
-        let path = req
-            .extensions()
-            .get::<MatchedPath>()
-            .expect("Failed to get Axum router rule")
-            .as_str();
-        // 2. Find RouteListing in paths. This should probably be optimized, we probably don't want to
-        // search for this every time
-        let listing: &AxumRouteListing =
-            paths.iter().find(|r| r.path() == path).unwrap_or_else(|| {
-                panic!(
-                    "Failed to find the route {path} requested by the user. \
-                     This suggests that the routing rules in the Router that \
-                     call this handler needs to be edited!"
-                )
-            });
-        // 3. Match listing mode against known, and choose function
-        match listing.mode() {
-            SsrMode::OutOfOrder => ooo(req),
-            SsrMode::PartiallyBlocked => pb(req),
-            SsrMode::InOrder => io(req),
-            SsrMode::Async => asyn(req),
-        }
+    move |req| match mode(&req) {
+        SsrMode::OutOfOrder => ooo(req),
+        SsrMode::PartiallyBlocked => pb(req),
+        SsrMode::InOrder => io(req),
+        SsrMode::Async => asyn(req),
     }
 }
 
@@ -799,6 +1044,7 @@ where
 {
     Box::pin(async move {
         let add_context = additional_context.clone();
+        let cache_key = ssr_cache_key(&req);
         let res_options = ResponseOptions::default();
         let (meta_context, meta_output) = ServerMetaContext::new();
 
@@ -819,22 +1065,56 @@ where
                     res_options.clone(),
                 );
                 add_context();
+                #[cfg(feature = "default")]
+                set_default_render_timeout();
             }
         };
 
-        let res = AxumResponse::from_app(
-            app_fn,
-            meta_output,
-            additional_context,
-            res_options,
-            stream_builder,
-        )
-        .await;
+        let res = match cache_key {
+            Some(cache_key) => {
+                AxumResponse::from_app_with_cache(
+                    cache_key,
+                    app_fn,
+                    meta_output,
+                    additional_context,
+                    res_options,
+                    stream_builder,
+                )
+                .await
+            }
+            None => {
+                AxumResponse::from_app(
+                    app_fn,
+                    meta_output,
+                    additional_context,
+                    res_options,
+                    stream_builder,
+                )
+                .await
+            }
+        };
 
         res.0
     })
 }
 
+/// For a `GET` request, a function that returns the key the page is cached under, if an
+/// [`SsrCacheOptions`] is provided in the additional context.
+fn ssr_cache_key(
+    req: &Request<Body>,
+) -> Option<impl FnOnce(&SsrCacheOptions) -> CacheKey + Send + 'static> {
+    if req.method() != Method::GET {
+        return None;
+    }
+    let path = req.uri().path_and_query()?.as_str().to_string();
+    let headers = req.headers().clone();
+    Some(move |options: &SsrCacheOptions| {
+        options.key(&path, |name| {
+            Some(headers.get(name)?.to_str().ok()?.to_string())
+        })
+    })
+}
+
 #[tracing::instrument(level = "trace", fields(error), skip_all)]
 fn provide_contexts(
     path: &str,
@@ -844,9 +1124,12 @@ fn provide_contexts(
 ) {
     provide_context(RequestUrl::new(path));
     provide_context(meta_context.clone());
+    provide_request_meta(request_meta(&parts));
+    provide_request_context(&parts);
     provide_context(parts);
     provide_context(default_res_options);
     provide_server_redirect(redirect);
+    provide_response_meta();
     leptos::nonce::provide_nonce();
 }
 
@@ -1086,30 +1369,261 @@ where
     generate_route_list_with_exclusions_and_ssg(app_fn, excluded_routes).0
 }
 
-/// TODO docs
-#[allow(unused)]
+/// Renders each static route in the app to an HTML file in the site root, returning the URL paths
+/// that were rendered.
+///
+/// Routes are marked as static with the `static_route` prop on a `<Route/>`; see
+/// [`StaticRoute`]. Each page is rendered after all of its async data has loaded, with any meta
+/// tags from [`leptos_meta`] injected into its `<head>`, and written to
+/// `{site_root}/{path}/index.html`. When a static route is registered with
+/// [`.leptos_routes`](LeptosRoutes::leptos_routes), requests to it are served from that file if
+/// it exists, and rendered as usual otherwise. Routes marked as
+/// [`incremental`](StaticRoute::incremental) are skipped, as they are rendered on the first
+/// request to each path instead.
+///
+/// ## Caching
+/// Pages are stored with a [`FileCache`] in the site root by default. To store them somewhere
+/// else, provide an `Arc<dyn StaticCache>` as context in the additional context passed to
+/// [`build_static_routes_with_additional_context`] and
+/// [`.leptos_routes_with_context`](LeptosRoutes::leptos_routes_with_context). Create the cache
+/// once and clone it into the context, as that function runs for every request:
+/// ```
+/// use leptos::prelude::*;
+/// use leptos_axum::{MemoryCache, StaticCache};
+/// use std::sync::Arc;
+///
+/// let cache: Arc<dyn StaticCache> = Arc::new(MemoryCache::new());
+/// let additional_context = move || provide_context(cache.clone());
+/// ```
+///
+/// This is typically called once, when the server starts or as a separate build step:
+/// ```
+/// use leptos::{config::get_configuration, prelude::*};
+/// use leptos_axum::{build_static_routes, generate_route_list};
+///
+/// # fn App() -> impl IntoView {}
+/// async fn prerender() {
+///     let conf = get_configuration(None).unwrap();
+///     let routes = generate_route_list(App);
+///     build_static_routes(&conf.leptos_options, App, &routes)
+///         .await
+///         .expect("could not write static routes");
+/// }
+/// ```
+///
+/// ## Provided Context Types
+/// This function always provides context values including the following types:
+/// - [`Parts`]
+/// - [`ResponseOptions`]
+/// - [`ServerMetaContext`](leptos_meta::ServerMetaContext)
+/// - [`RequestUrl`]
 pub async fn build_static_routes<IV>(
     options: &LeptosOptions,
     app_fn: impl Fn() -> IV + 'static + Send + Clone,
-    routes: &[RouteListing],
-    static_data_map: StaticDataMap,
-) where
+    routes: &[AxumRouteListing],
+) -> io::Result<Vec<String>>
+where
     IV: IntoView + 'static,
 {
-    todo!()
-    /*
-    let options = options.clone();
-    let routes = routes.to_owned();
-    spawn_task!(async move {
-        leptos_router::build_static_routes(
-            &options,
-            app_fn,
-            &routes,
-            &static_data_map,
-        )
+    build_static_routes_with_additional_context(options, app_fn, || {}, routes)
         .await
-        .expect("could not build static routes")
-    });*/
+}
+
+/// Renders each static route in the app to an HTML file in the site root, returning the URL paths
+/// that were rendered. See [`build_static_routes`].
+///
+/// This version allows you to provide additional context to the app, which should match the
+/// context provided when it is rendered on each request.
+pub async fn build_static_routes_with_additional_context<IV>(
+    options: &LeptosOptions,
+    app_fn: impl Fn() -> IV + 'static + Send + Clone,
+    additional_context: impl Fn() + 'static + Send + Clone,
+    routes: &[AxumRouteListing],
+) -> io::Result<Vec<String>>
+where
+    IV: IntoView + 'static,
+{
+    init_executor();
+
+    let cache = static_cache(Some(options), additional_context.clone())
+        .expect("a cache is always created when LeptosOptions are given");
+    let mut rendered = Vec::new();
+    for listing in routes {
+        let Some(static_route) = &listing.static_route else {
+            continue;
+        };
+        if static_route.mode() == StaticMode::Incremental {
+            continue;
+        }
+        for path in static_route.to_paths(&listing.segments).await {
+            let page = render_static_path(
+                path.clone(),
+                app_fn.clone(),
+                additional_context.clone(),
+            )
+            .await;
+            if !page.is_cacheable() {
+                tracing::warn!(
+                    "static page {path} was not written, because it \
+                     redirected or set a status other than 2xx"
+                );
+                continue;
+            }
+            cache
+                .set(path.as_str().into(), CachedPage::new(page.html))
+                .await?;
+            rendered.push(path);
+        }
+    }
+    Ok(rendered)
+}
+
+/// Creates a [`Sitemap`] of the site at `base_url`, like `https://leptos.dev`, from the route
+/// list of the app.
+///
+/// Routes without parameters are listed, along with every path of a [`StaticRoute`] that is
+/// prerendered, unless they have been excluded with a [`SitemapRoute`].
+pub async fn generate_sitemap(
+    base_url: &str,
+    routes: &[AxumRouteListing],
+) -> Sitemap {
+    let mut sitemap = Sitemap::new(base_url);
+    for listing in routes {
+        sitemap
+            .add_route(
+                &listing.segments,
+                listing.static_route.as_ref(),
+                listing.sitemap.as_ref(),
+            )
+            .await;
+    }
+    sitemap
+}
+
+/// Writes a `sitemap.xml` and a `robots.txt` for the routes of the app into the site root,
+/// where they are served along with the other static files, and returns the sitemap. See
+/// [`generate_sitemap`].
+///
+/// This is usually called along with [`build_static_routes`], before the server is started.
+/// Alternatively, the sitemap can be served without writing any files with [`sitemap_router`].
+pub async fn build_sitemap(
+    options: &LeptosOptions,
+    base_url: &str,
+    routes: &[AxumRouteListing],
+) -> io::Result<Sitemap> {
+    let sitemap = generate_sitemap(base_url, routes).await;
+    let site_root = std::path::Path::new(&*options.site_root);
+    std::fs::create_dir_all(site_root)?;
+    std::fs::write(site_root.join("sitemap.xml"), sitemap.to_xml())?;
+    std::fs::write(site_root.join("robots.txt"), sitemap.robots_txt())?;
+    Ok(sitemap)
+}
+
+/// Returns a router that serves the sitemap at `/sitemap.xml`, and a `robots.txt` that points
+/// to it at `/robots.txt`. This can be merged into the router of the app.
+///
+/// ```rust,ignore
+/// let routes = generate_route_list(App);
+/// let sitemap = generate_sitemap("https://leptos.dev", &routes).await;
+/// let app = Router::new()
+///     .leptos_routes(&leptos_options, routes, App)
+///     .merge(sitemap_router(sitemap))
+///     .with_state(leptos_options);
+/// ```
+pub fn sitemap_router<S>(sitemap: Sitemap) -> axum::Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let xml = sitemap.to_xml();
+    let robots = sitemap.robots_txt();
+    axum::Router::new()
+        .route(
+            "/sitemap.xml",
+            axum::routing::get(move || async move {
+                ([(header::CONTENT_TYPE, "application/xml")], xml)
+            }),
+        )
+        .route(
+            "/robots.txt",
+            axum::routing::get(move || async move {
+                ([(header::CONTENT_TYPE, "text/plain")], robots)
+            }),
+        )
+}
+
+/// The cache provided in the additional context, or a [`FileCache`] in the site root.
+fn static_cache(
+    options: Option<&LeptosOptions>,
+    additional_context: impl FnOnce(),
+) -> Option<Arc<dyn StaticCache>> {
+    let (options_cx, cache) = Owner::new().with(|| {
+        additional_context();
+        (
+            use_context::<LeptosOptions>(),
+            use_context::<Arc<dyn StaticCache>>(),
+        )
+    });
+    cache.or_else(|| {
+        let options = options.cloned().or(options_cx)?;
+        Some(Arc::new(FileCache::new(&*options.site_root)) as _)
+    })
+}
+
+/// The HTML rendered for a static route, along with the status and headers it set.
+struct StaticPage {
+    html: String,
+    res_options: ResponseOptions,
+}
+
+impl StaticPage {
+    /// Whether the page can be stored: pages that redirect or set a status other than `2xx` are
+    /// not.
+    fn is_cacheable(&self) -> bool {
+        let res_options = self.res_options.0.read();
+        res_options
+            .status
+            .map_or(true, |status| status.is_success())
+            && !res_options.headers.contains_key(header::LOCATION)
+            && !res_options.headers.contains_key(REDIRECT_HEADER)
+    }
+
+    fn into_response(self) -> Response<Body> {
+        let mut res = AxumResponse(Response::new(Body::from(self.html)));
+        res.extend_response(&self.res_options);
+        res.set_default_content_type("text/html; charset=utf-8");
+        res.0
+    }
+}
+
+async fn render_static_path<IV>(
+    path: String,
+    app_fn: impl FnOnce() -> IV + 'static + Send,
+    additional_context: impl FnOnce() + 'static + Send,
+) -> StaticPage
+where
+    IV: IntoView + 'static,
+{
+    let (meta_context, meta_output) = ServerMetaContext::new();
+    let res_options = ResponseOptions::default();
+    let html = render_static_html(app_fn, meta_output, {
+        let res_options = res_options.clone();
+        move || {
+            let (parts, _) = Request::builder()
+                .uri(&path)
+                .body(Body::empty())
+                .unwrap_or_default()
+                .into_parts();
+            provide_contexts(
+                &format!("http://leptos.dev{path}"),
+                &meta_context,
+                parts,
+                res_options,
+            );
+            additional_context();
+        }
+    })
+    .await;
+    StaticPage { html, res_options }
 }
 
 /// Generates a list of all routes defined in Leptos's Router in your app. We can then use this to automatically
@@ -1137,7 +1651,9 @@ pub struct AxumRouteListing {
     path: String,
     mode: SsrMode,
     methods: Vec<leptos_router::Method>,
-    static_mode: Option<(StaticMode, StaticDataMap)>,
+    static_route: Option<StaticRoute>,
+    sitemap: Option<SitemapRoute>,
+    segments: Vec<PathSegment>,
 }
 
 impl From<RouteListing> for AxumRouteListing {
@@ -1150,12 +1666,16 @@ impl From<RouteListing> for AxumRouteListing {
         };
         let mode = value.mode();
         let methods = value.methods().collect();
-        let static_mode = value.into_static_parts();
+        let segments = value.path().to_vec();
+        let sitemap = value.sitemap().cloned();
+        let static_route = value.into_static_route();
         Self {
             path,
             mode,
             methods,
-            static_mode,
+            static_route,
+            sitemap,
+            segments,
         }
     }
 }
@@ -1166,13 +1686,28 @@ impl AxumRouteListing {
         path: String,
         mode: SsrMode,
         methods: impl IntoIterator<Item = leptos_router::Method>,
-        static_mode: Option<(StaticMode, StaticDataMap)>,
+        static_route: Option<StaticRoute>,
     ) -> Self {
+        let segments = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| {
+                if let Some(param) = segment.strip_prefix(':') {
+                    PathSegment::Param(param.to_string().into())
+                } else if let Some(splat) = segment.strip_prefix('*') {
+                    PathSegment::Splat(splat.to_string().into())
+                } else {
+                    PathSegment::Static(segment.to_string().into())
+                }
+            })
+            .collect();
         Self {
             path,
             mode,
             methods: methods.into_iter().collect(),
-            static_mode,
+            static_route,
+            sitemap: None,
+            segments,
         }
     }
 
@@ -1194,7 +1729,7 @@ impl AxumRouteListing {
     /// Whether this route is statically rendered.
     #[inline(always)]
     pub fn static_mode(&self) -> Option<StaticMode> {
-        self.static_mode.as_ref().map(|n| n.0)
+        self.static_route.as_ref().map(StaticRoute::mode)
     }
 }
 
@@ -1290,106 +1825,12 @@ where
         H: axum::handler::Handler<T, S>,
         T: 'static;
 }
-/*
-#[cfg(feature = "default")]
-fn handle_static_response<IV>(
-    path: String,
-    options: LeptosOptions,
-    app_fn: impl Fn() -> IV + Clone + Send + 'static,
-    additional_context: impl Fn() + Clone + Send + 'static,
-    res: StaticResponse,
-) -> Pin<Box<dyn Future<Output = Response<String>> + 'static>>
-where
-    IV: IntoView + 'static,
-{
-    Box::pin(async move {
-        match res {
-            StaticResponse::ReturnResponse {
-                body,
-                status,
-                content_type,
-            } => {
-                let mut res = Response::new(body);
-                if let Some(v) = content_type {
-                    res.headers_mut().insert(
-                        HeaderName::from_static("content-type"),
-                        HeaderValue::from_static(v),
-                    );
-                }
-                *res.status_mut() = match status {
-                    StaticStatusCode::Ok => StatusCode::OK,
-                    StaticStatusCode::NotFound => StatusCode::NOT_FOUND,
-                    StaticStatusCode::InternalServerError => {
-                        StatusCode::INTERNAL_SERVER_ERROR
-                    }
-                };
-                res
-            }
-            StaticResponse::RenderDynamic => {
-                let res = render_dynamic(
-                    &path,
-                    &options,
-                    app_fn.clone(),
-                    additional_context.clone(),
-                )
-                .await;
-                handle_static_response(
-                    path,
-                    options,
-                    app_fn,
-                    additional_context,
-                    res,
-                )
-                .await
-            }
-            StaticResponse::RenderNotFound => {
-                let res = not_found_page(
-                    tokio::fs::read_to_string(not_found_path(&options)).await,
-                );
-                handle_static_response(
-                    path,
-                    options,
-                    app_fn,
-                    additional_context,
-                    res,
-                )
-                .await
-            }
-            StaticResponse::WriteFile { body, path } => {
-                if let Some(path) = path.parent() {
-                    if let Err(e) = std::fs::create_dir_all(path) {
-                        tracing::error!(
-                            "encountered error {} writing directories {}",
-                            e,
-                            path.display()
-                        );
-                    }
-                }
-                if let Err(e) = std::fs::write(&path, &body) {
-                    tracing::error!(
-                        "encountered error {} writing file {}",
-                        e,
-                        path.display()
-                    );
-                }
-                handle_static_response(
-                    path.to_str().unwrap().to_string(),
-                    options,
-                    app_fn,
-                    additional_context,
-                    StaticResponse::ReturnResponse {
-                        body,
-                        status: StaticStatusCode::Ok,
-                        content_type: Some("text/html"),
-                    },
-                )
-                .await
-            }
-        }
-    })
-}*/
-
-#[allow(unused)] // TODO
+/// Serves a static route from the [`StaticCache`], rendering and storing pages as needed.
+///
+/// Pages that have not been rendered yet are rendered as usual, unless the route is incremental
+/// or revalidated and [caches the path](StaticRoute::caches_path), in which case they are
+/// rendered and stored. Expired pages are served while they are rendered again in the
+/// background. Pages that redirect or set a status other than `2xx` are never stored.
 #[cfg(feature = "default")]
 fn static_route<IV, S>(
     router: axum::Router<S>,
@@ -1397,101 +1838,119 @@ fn static_route<IV, S>(
     app_fn: impl Fn() -> IV + Clone + Send + 'static,
     additional_context: impl Fn() + Clone + Send + 'static,
     method: leptos_router::Method,
-    mode: StaticMode,
+    static_route: StaticRoute,
+    segments: Vec<PathSegment>,
 ) -> axum::Router<S>
 where
     IV: IntoView + 'static,
     S: Clone + Send + Sync + 'static,
 {
-    todo!()
-    /*match mode {
-        StaticMode::Incremental => {
-            let handler = move |req: Request<Body>| {
-                Box::pin({
-                    let path = req.uri().path().to_string();
-                    let options = options.clone();
-                    let app_fn = app_fn.clone();
-                    let additional_context = additional_context.clone();
-
-                    async move {
-                        let (tx, rx) = futures::channel::oneshot::channel();
-                        spawn_task!(async move {
-                            let res = incremental_static_route(
-                                tokio::fs::read_to_string(static_file_path(
-                                    &options, &path,
-                                ))
-                                .await,
-                            );
-                            let res = handle_static_response(
+    // paths that are currently being regenerated in the background
+    let regenerating = Arc::new(parking_lot::Mutex::new(
+        std::collections::HashSet::<String>::new(),
+    ));
+    let segments: Arc<[PathSegment]> = segments.into();
+    let handler = move |req: Request<Body>| {
+        let app_fn = app_fn.clone();
+        let additional_context = additional_context.clone();
+        let static_route = static_route.clone();
+        let segments = Arc::clone(&segments);
+        let regenerating = Arc::clone(&regenerating);
+        async move {
+            // the site root is found in the LeptosOptions provided via context, which is usually
+            // the state of the router
+            let Some(cache) = static_cache(None, additional_context.clone())
+            else {
+                return render_app_async_with_context(
+                    additional_context,
+                    app_fn,
+                )(req)
+                .await;
+            };
+            let path = req.uri().path().to_string();
+
+            let renders_on_request = static_route.mode()
+                == StaticMode::Incremental
+                || static_route.ttl().is_some();
+            match cache.get(&path.as_str().into()).await {
+                Some(page) => {
+                    let expired =
+                        static_route.ttl().is_some_and(|ttl| page.age() >= ttl);
+                    if expired && regenerating.lock().insert(path.clone()) {
+                        tokio::spawn(async move {
+                            let page = render_static_path(
                                 path.clone(),
-                                options,
                                 app_fn,
                                 additional_context,
-                                res,
                             )
                             .await;
-
-                            let _ = tx.send(res);
+                            if page.is_cacheable() {
+                                if let Err(e) = cache
+                                    .set(
+                                        path.as_str().into(),
+                                        CachedPage::new(page.html),
+                                    )
+                                    .await
+                                {
+                                    tracing::error!(
+                                        "failed to regenerate static page \
+                                         {path}: {e}"
+                                    );
+                                }
+                            }
+                            regenerating.lock().remove(&path);
                         });
-                        rx.await.expect("to complete HTML rendering")
                     }
-                })
-            };
-            router.route(
-                path,
-                match method {
-                    leptos_router::Method::Get => get(handler),
-                    leptos_router::Method::Post => post(handler),
-                    leptos_router::Method::Put => put(handler),
-                    leptos_router::Method::Delete => delete(handler),
-                    leptos_router::Method::Patch => patch(handler),
-                },
-            )
-        }
-        StaticMode::Upfront => {
-            let handler = move |req: Request<Body>| {
-                Box::pin({
-                    let path = req.uri().path().to_string();
-                    let options = options.clone();
-                    let app_fn = app_fn.clone();
-                    let additional_context = additional_context.clone();
-
-                    async move {
-                        let (tx, rx) = futures::channel::oneshot::channel();
-                        spawn_task!(async move {
-                            let res = upfront_static_route(
-                                tokio::fs::read_to_string(static_file_path(
-                                    &options, &path,
-                                ))
-                                .await,
-                            );
-                            let res = handle_static_response(
-                                path.clone(),
-                                options,
-                                app_fn,
-                                additional_context,
-                                res,
+                    let mut res = Response::new(Body::from(page.html));
+                    res.headers_mut().insert(
+                        header::CONTENT_TYPE,
+                        HeaderValue::from_static("text/html; charset=utf-8"),
+                    );
+                    res
+                }
+                None if renders_on_request
+                    && static_route.caches_path(&segments, &path).await =>
+                {
+                    let page = render_static_path(
+                        path.clone(),
+                        app_fn,
+                        additional_context,
+                    )
+                    .await;
+                    if page.is_cacheable() {
+                        if let Err(e) = cache
+                            .set(
+                                path.as_str().into(),
+                                CachedPage::new(page.html.clone()),
                             )
-                            .await;
-
-                            let _ = tx.send(res);
-                        });
-                        rx.await.expect("to complete HTML rendering")
+                            .await
+                        {
+                            tracing::error!(
+                                "failed to store static page {path}: {e}"
+                            );
+                        }
                     }
-                })
-            };
-            router.route(
-                path,
-                match method {
-                    leptos_router::Method::Get => get(handler),
-                    leptos_router::Method::Post => post(handler),
-                    leptos_router::Method::Put => put(handler),
-                    leptos_router::Method::Delete => delete(handler),
-                    leptos_router::Method::Patch => patch(handler),
-                },
-            )
+                    page.into_response()
+                }
+                None => {
+                    render_app_async_with_context(additional_context, app_fn)(
+                        req,
+                    )
+                    .await
+                }
+            }
         }
-    }*/
+    };
+    router.route(
+        path,
+        match method {
+            leptos_router::Method::Get => get(handler),
+            leptos_router::Method::Post => post(handler),
+            leptos_router::Method::Put => put(handler),
+            leptos_router::Method::Delete => delete(handler),
+            leptos_router::Method::Patch => patch(handler),
+        },
+    )
 }
 
 trait AxumPath {
@@ -1600,7 +2059,7 @@ where
                     provide_context(method);
                     cx_with_state();
                 };
-                router = if let Some(static_mode) = listing.static_mode() {
+                router = if let Some(static_config) = &listing.static_route {
                     #[cfg(feature = "default")]
                     {
                         static_route(
@@ -1609,12 +2068,13 @@ where
                             app_fn.clone(),
                             cx_with_state_and_method.clone(),
                             method,
-                            static_mode,
+                            static_config.clone(),
+                            listing.segments.clone(),
                         )
                     }
                     #[cfg(not(feature = "default"))]
                     {
-                        _ = static_mode;
+                        _ = static_config;
                         panic!(
                             "Static site generation is not currently \
                              supported on WASM32 server targets."
@@ -1724,6 +2184,9 @@ where
 ///
 /// Any error that occurs during extraction is converted to a [`ServerFnError`].
 ///
+/// To read cookies, headers, or the client's address in a server function that should work
+/// with any server integration, use [`leptos::request::extract`] instead.
+///
 /// ```rust
 /// use leptos::prelude::*;
 ///
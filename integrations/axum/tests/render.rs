@@ -0,0 +1,339 @@
+use axum::{
+    body::Body,
+    http::{
+        header::{
+            ACCEPT_LANGUAGE, CACHE_CONTROL, CONTENT_TYPE, COOKIE, SET_COOKIE,
+        },
+        request::Parts,
+        HeaderValue, Request, StatusCode,
+    },
+};
+use http_body_util::BodyExt;
+use leptos::{
+    prelude::*, request::RequestContext, response::use_response_meta,
+    spawn::Executor,
+};
+use leptos_axum::{
+    render_app_to_stream, render_app_to_stream_with_context, MemoryCache,
+    ResponseOptions, SsrCacheOptions, StaticCache,
+};
+use leptos_meta::{provide_meta_context, MetaTags, Title};
+use leptos_router::{
+    components::{
+        ErrorTemplate, Outlet, ParentRoute, Route, RouteErrorBoundary, Router,
+        Routes,
+    },
+    path,
+};
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+#[derive(Debug, Clone)]
+struct RequestId(u32);
+
+static DISPOSED: AtomicUsize = AtomicUsize::new(0);
+
+fn app() -> impl IntoView {
+    // the request is available in context, including its headers and extensions
+    let parts = expect_context::<Parts>();
+    let cookie = parts
+        .headers
+        .get(COOKIE)
+        .and_then(|cookie| cookie.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let id = parts.extensions.get::<RequestId>().unwrap().0;
+
+    let res = expect_context::<ResponseOptions>();
+    res.set_status(StatusCode::ACCEPTED);
+    res.insert_header(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+
+    on_cleanup(|| {
+        DISPOSED.fetch_add(1, Ordering::Relaxed);
+    });
+
+    view! { <p>{format!("request {id}: {cookie}")}</p> }
+}
+
+#[tokio::test]
+async fn apps_are_rendered_with_the_request_in_context() {
+    let handler = render_app_to_stream(app);
+
+    for id in 0..2 {
+        let mut req = Request::builder()
+            .uri("/")
+            .header(COOKIE, "theme=dark")
+            .body(Body::empty())
+            .unwrap();
+        req.extensions_mut().insert(RequestId(id));
+
+        let res = handler(req).await;
+        assert_eq!(res.status(), StatusCode::ACCEPTED);
+        assert_eq!(res.headers()[CACHE_CONTROL], "no-store");
+        assert_eq!(res.headers()[CONTENT_TYPE], "text/html; charset=utf-8");
+
+        // each request has its own reactive owner, which is disposed once the response is sent
+        assert_eq!(DISPOSED.load(Ordering::Relaxed), id as usize);
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        let body = std::str::from_utf8(&body).unwrap();
+        assert!(body.contains(&format!("<p>request {id}: theme=dark</p>")));
+        assert_eq!(DISPOSED.load(Ordering::Relaxed), id as usize + 1);
+    }
+}
+
+fn not_found() -> impl IntoView {
+    // components can set the status and headers without using any Axum types
+    let res = use_response_meta().unwrap();
+    res.set_status(404);
+    res.set_cookie("visited=1; Path=/");
+    res.set_cookie("theme=dark; Path=/");
+    res.insert_header("content-type", "text/plain; charset=utf-8");
+    res.insert_header("cache-control", "no-store");
+    view! { <p>"Not found."</p> }
+}
+
+#[tokio::test]
+async fn response_meta_is_applied_to_the_response() {
+    let handler = render_app_to_stream(not_found);
+    let req = Request::builder().uri("/").body(Body::empty()).unwrap();
+    let res = handler(req).await;
+
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    assert_eq!(res.headers()[CACHE_CONTROL], "no-store");
+    assert_eq!(res.headers()[CONTENT_TYPE], "text/plain; charset=utf-8");
+    let cookies = res
+        .headers()
+        .get_all(SET_COOKIE)
+        .iter()
+        .map(|cookie| cookie.to_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(cookies, ["visited=1; Path=/", "theme=dark; Path=/"]);
+}
+
+fn failing_app() -> impl IntoView {
+    provide_meta_context();
+    let error_template = ErrorTemplate::new(|errors: Errors| {
+        let count = errors.iter().count();
+        view! {
+            <Title text="Error"/>
+            <h1>{format!("Error page: {count} error")}</h1>
+        }
+    });
+    view! {
+        <!DOCTYPE html>
+        <html>
+            <head>
+                <MetaTags/>
+            </head>
+            <body>
+                <main>
+                    <Router error_template>
+                        <Routes fallback=|| "Not found.">
+                            <Route path=path!("/") view=|| "Home"/>
+                            <Route path=path!("/broken") view=Broken/>
+                        </Routes>
+                    </Router>
+                </main>
+            </body>
+        </html>
+    }
+}
+
+#[component]
+fn Broken() -> impl IntoView {
+    let count: Result<i32, std::num::ParseIntError> = "four".parse();
+    view! {
+        <RouteErrorBoundary>
+            <p>"Count: " {count}</p>
+        </RouteErrorBoundary>
+    }
+}
+
+#[tokio::test]
+async fn route_errors_show_the_error_page_in_place_of_the_route() {
+    let handler = render_app_to_stream(failing_app);
+
+    let req = Request::builder()
+        .uri("/broken")
+        .body(Body::empty())
+        .unwrap();
+    let res = handler(req).await;
+    assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert!(body.contains("<title>Error</title>"));
+    assert!(body.contains("<main><h1>Error page: 1 error</h1></main>"));
+
+    let req = Request::builder().uri("/").body(Body::empty()).unwrap();
+    let res = handler(req).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    assert!(std::str::from_utf8(&body)
+        .unwrap()
+        .contains("<main>Home</main>"));
+}
+
+#[derive(Clone)]
+struct Session(&'static str);
+
+fn nested_app() -> impl IntoView {
+    view! {
+        <Router>
+            <Routes fallback=|| "Not found.">
+                <ParentRoute path=path!("/account") view=|| view! { <Outlet/> }>
+                    <Route path=path!("settings") view=Settings/>
+                </ParentRoute>
+            </Routes>
+        </Router>
+    }
+}
+
+#[component]
+fn Settings() -> impl IntoView {
+    let Session(user) = expect_context::<Session>();
+    view! { <p>{format!("Settings for {user}")}</p> }
+}
+
+#[tokio::test]
+async fn request_context_is_provided_to_nested_routes() {
+    let handler = render_app_to_stream(nested_app);
+    let mut req = Request::builder()
+        .uri("/account/settings")
+        .body(Body::empty())
+        .unwrap();
+    req.extensions_mut()
+        .insert(RequestContext::new().with(Session("greg")));
+
+    let res = handler(req).await;
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    assert!(std::str::from_utf8(&body)
+        .unwrap()
+        .starts_with("<p>Settings for greg</p>"));
+}
+
+fn slow_app() -> impl IntoView {
+    let weather = Resource::new(
+        || (),
+        |_| async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            "Sunny".to_string()
+        },
+    );
+    view! {
+        <p>"Forecast"</p>
+        <Suspense fallback=|| "Loading...">
+            {move || Suspend::new(async move { weather.await })}
+        </Suspense>
+    }
+}
+
+#[tokio::test]
+async fn pending_suspense_is_flushed_after_the_ssr_timeout() {
+    _ = Executor::init_tokio();
+    let options = LeptosOptions::builder().ssr_timeout(Some(50)).build();
+    let handler = render_app_to_stream_with_context(
+        move || provide_context(options.clone()),
+        slow_app,
+    );
+    let req = Request::builder().uri("/").body(Body::empty()).unwrap();
+
+    let body = tokio::time::timeout(Duration::from_secs(5), async {
+        handler(req).await.into_body().collect().await
+    })
+    .await
+    .expect("the response should finish once the deadline has passed")
+    .unwrap()
+    .to_bytes();
+    let body = std::str::from_utf8(&body).unwrap();
+    assert!(body.contains("Loading..."));
+    assert!(!body.contains("Sunny"));
+}
+
+static RENDERS: AtomicUsize = AtomicUsize::new(0);
+
+fn expensive_app() -> impl IntoView {
+    let render = RENDERS.fetch_add(1, Ordering::Relaxed) + 1;
+    let language = expect_context::<Parts>()
+        .headers
+        .get(ACCEPT_LANGUAGE)
+        .and_then(|language| language.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    use_response_meta()
+        .unwrap()
+        .insert_header("content-language", language.clone());
+    view! { <p>{format!("render {render}: {language}")}</p> }
+}
+
+#[tokio::test]
+async fn rendered_pages_are_cached_until_they_are_invalidated() {
+    let cache = MemoryCache::new();
+    let options = SsrCacheOptions::new(cache.clone(), Duration::from_secs(60))
+        .vary("Accept-Language");
+    let handler = render_app_to_stream_with_context(
+        move || provide_context(options.clone()),
+        expensive_app,
+    );
+    let get = |language: &'static str| {
+        let handler = handler.clone();
+        async move {
+            let req = Request::builder()
+                .uri("/pricing?plan=pro")
+                .header(ACCEPT_LANGUAGE, language)
+                .body(Body::empty())
+                .unwrap();
+            let res = handler(req).await;
+            assert_eq!(res.headers()["content-language"], language);
+            let body = res.into_body().collect().await.unwrap().to_bytes();
+            String::from_utf8(body.to_vec()).unwrap()
+        }
+    };
+
+    let first = get("en").await;
+    assert!(first.starts_with("<p>render 1: en</p>"));
+    assert_eq!(get("en").await, first);
+    assert!(get("fr").await.starts_with("<p>render 2: fr</p>"));
+
+    // every query of the path is invalidated
+    cache.invalidate("/pricing").await.unwrap();
+    assert!(get("en").await.starts_with("<p>render 3: en</p>"));
+    assert_eq!(RENDERS.load(Ordering::Relaxed), 3);
+}
+
+static LOGINS: AtomicUsize = AtomicUsize::new(0);
+static CONTEXT_RUNS: AtomicUsize = AtomicUsize::new(0);
+
+fn login_app() -> impl IntoView {
+    let render = LOGINS.fetch_add(1, Ordering::Relaxed) + 1;
+    expect_context::<ResponseOptions>()
+        .insert_header(SET_COOKIE, HeaderValue::from_static("session=1"));
+    view! { <p>{format!("login {render}")}</p> }
+}
+
+#[tokio::test]
+async fn pages_that_set_cookies_with_response_options_are_not_cached() {
+    let options =
+        SsrCacheOptions::new(MemoryCache::new(), Duration::from_secs(60));
+    let handler = render_app_to_stream_with_context(
+        move || {
+            CONTEXT_RUNS.fetch_add(1, Ordering::Relaxed);
+            provide_context(options.clone());
+        },
+        login_app,
+    );
+    for render in 1..=2 {
+        let req = Request::builder()
+            .uri("/login")
+            .body(Body::empty())
+            .unwrap();
+        let res = handler(req).await;
+        assert_eq!(res.headers()[SET_COOKIE], "session=1");
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        let body = std::str::from_utf8(&body).unwrap();
+        assert!(body.starts_with(&format!("<p>login {render}</p>")));
+    }
+    // the additional context is only run once for each request, including to find the cache
+    assert_eq!(CONTEXT_RUNS.load(Ordering::Relaxed), 2);
+}
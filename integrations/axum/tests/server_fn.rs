@@ -0,0 +1,450 @@
+use axum::{
+    body::Body,
+    http::{
+        header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, COOKIE, LOCATION},
+        Method, Request, Response, StatusCode,
+    },
+    response::IntoResponse,
+};
+use futures::future::BoxFuture;
+use http_body_util::BodyExt;
+use leptos::{
+    prelude::*,
+    request::{extract, ConnectInfo, Cookies, RequestContext, RequestMeta},
+};
+use leptos_axum::{handle_server_fns, handle_server_fns_with_context};
+use leptos_router::components::redirect;
+use server_fn::{
+    codec::{Cbor, Json, JsonStream, StreamingJson, StreamingText, TextStream},
+    error::{ServerFnErrorSerde, TypedError},
+    redirect::REDIRECT_HEADER,
+    serde::{Deserialize, Serialize},
+    ServerFn,
+};
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    task::{Context, Poll},
+};
+use tower::{layer::util::Stack, Layer, Service};
+
+#[server]
+async fn add_url(a: i32, b: i32) -> Result<i32, ServerFnError> {
+    Ok(a + b)
+}
+
+#[server(input = Json)]
+async fn add_json(a: i32, b: i32) -> Result<i32, ServerFnError> {
+    Ok(a + b)
+}
+
+#[server(input = Cbor, output = Cbor)]
+async fn add_cbor(a: i32, b: i32) -> Result<i32, ServerFnError> {
+    Ok(a + b)
+}
+
+#[server(output = StreamingJson)]
+async fn count_to(n: u32) -> Result<JsonStream<u32>, ServerFnError> {
+    Ok(JsonStream::from(futures::stream::iter(1..=n)))
+}
+
+#[server(output = StreamingText)]
+async fn spell(word: String) -> Result<TextStream, ServerFnError> {
+    let letters = word.chars().map(String::from).collect::<Vec<_>>();
+    Ok(TextStream::from(futures::stream::iter(letters)))
+}
+
+/// Rejects requests without an `Authorization` header.
+#[derive(Clone)]
+struct RequireAuth;
+
+impl<S> Layer<S> for RequireAuth {
+    type Service = RequireAuthService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequireAuthService(inner)
+    }
+}
+
+struct RequireAuthService<S>(S);
+
+impl<S> Service<Request<Body>> for RequireAuthService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>>,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response<Body>, S::Error>>;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), S::Error>> {
+        self.0.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        if req.headers().contains_key(AUTHORIZATION) {
+            Box::pin(self.0.call(req))
+        } else {
+            Box::pin(async {
+                Ok(Response::builder()
+                    .status(StatusCode::UNAUTHORIZED)
+                    .body(Body::empty())
+                    .unwrap())
+            })
+        }
+    }
+}
+
+static ADMIN_REQUESTS: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts every request that reaches it.
+#[derive(Clone)]
+struct CountAdminRequests;
+
+impl<S> Layer<S> for CountAdminRequests {
+    type Service = CountAdminRequestsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CountAdminRequestsService(inner)
+    }
+}
+
+struct CountAdminRequestsService<S>(S);
+
+impl<S> Service<Request<Body>> for CountAdminRequestsService<S>
+where
+    S: Service<Request<Body>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), S::Error>> {
+        self.0.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        ADMIN_REQUESTS.fetch_add(1, Ordering::Relaxed);
+        self.0.call(req)
+    }
+}
+
+/// The layers shared by every admin server function: requests are counted, then checked.
+fn admin() -> Stack<RequireAuth, CountAdminRequests> {
+    Stack::new(RequireAuth, CountAdminRequests)
+}
+
+#[server]
+#[middleware(admin())]
+async fn ban_user(id: u32) -> Result<u32, ServerFnError> {
+    Ok(id)
+}
+
+#[server]
+#[middleware(admin())]
+async fn unban_user(id: u32) -> Result<u32, ServerFnError> {
+    Ok(id)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "server_fn::serde")]
+pub enum LookupError {
+    NotFound,
+    Forbidden { reason: String },
+}
+
+#[server]
+async fn look_up(
+    id: u32,
+) -> Result<String, ServerFnError<TypedError<LookupError>>> {
+    match id {
+        0 => Err(LookupError::Forbidden {
+            reason: "reserved | internal".to_string(),
+        })?,
+        1 => Ok("ferris".to_string()),
+        _ => Err(LookupError::NotFound)?,
+    }
+}
+
+#[server]
+async fn log_out() -> Result<(), ServerFnError> {
+    // the request's context is still available after an `.await`
+    tokio::task::yield_now().await;
+    redirect("/login");
+    Ok(())
+}
+
+#[server]
+async fn whoami() -> Result<String, ServerFnError> {
+    let cookies: Cookies = extract()?;
+    let ConnectInfo(addr) = extract()?;
+    let req: RequestMeta = extract()?;
+    Ok(format!(
+        "{} {} {} {addr} {}",
+        req.method(),
+        req.path(),
+        cookies.get("session").unwrap_or_default(),
+        req.header("x-client").unwrap_or_default()
+    ))
+}
+
+async fn call(path: &str, content_type: &str, body: Vec<u8>) -> Vec<u8> {
+    let req = Request::builder()
+        .method(Method::POST)
+        .uri(path)
+        .header(CONTENT_TYPE, content_type)
+        .body(Body::from(body))
+        .unwrap();
+    let res = handle_server_fns(req).await.into_response();
+    assert_eq!(res.status(), StatusCode::OK);
+    res.into_body().collect().await.unwrap().to_bytes().to_vec()
+}
+
+#[tokio::test]
+async fn server_fns_run_their_body_on_the_server() {
+    assert_eq!(add_url(1, 2).await.unwrap(), 3);
+}
+
+#[test]
+fn server_fns_are_registered_automatically() {
+    let paths = server_fn::axum::server_fn_paths()
+        .map(|(path, _)| path)
+        .collect::<Vec<_>>();
+    for path in [AddUrl::PATH, AddJson::PATH, AddCbor::PATH] {
+        assert!(path.starts_with("/api/"));
+        assert!(paths.contains(&path));
+    }
+}
+
+#[tokio::test]
+async fn server_fns_decode_each_input_encoding() {
+    let res = call(
+        AddUrl::PATH,
+        "application/x-www-form-urlencoded",
+        b"a=1&b=2".to_vec(),
+    )
+    .await;
+    assert_eq!(res, b"3");
+
+    let res = call(
+        AddJson::PATH,
+        "application/json",
+        br#"{"a":2,"b":3}"#.to_vec(),
+    )
+    .await;
+    assert_eq!(res, b"5");
+
+    let mut body = Vec::new();
+    ciborium::into_writer(&AddCbor { a: 3, b: 4 }, &mut body).unwrap();
+    let res = call(AddCbor::PATH, "application/cbor", body).await;
+    assert_eq!(ciborium::from_reader::<i32, _>(res.as_slice()).unwrap(), 7);
+}
+
+#[tokio::test]
+async fn unknown_server_fn_paths_are_rejected() {
+    let req = Request::builder()
+        .method(Method::POST)
+        .uri("/api/not_a_server_fn")
+        .body(Body::empty())
+        .unwrap();
+    let res = handle_server_fns(req).await.into_response();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn streaming_server_fns_send_each_item() {
+    let res = call(
+        CountTo::PATH,
+        "application/x-www-form-urlencoded",
+        b"n=3".to_vec(),
+    )
+    .await;
+    assert_eq!(res, b"1\n2\n3\n");
+
+    let res = call(
+        Spell::PATH,
+        "application/x-www-form-urlencoded",
+        b"word=abc".to_vec(),
+    )
+    .await;
+    assert_eq!(res, b"abc");
+}
+
+#[tokio::test]
+async fn middleware_is_applied_to_each_server_fn_in_a_group() {
+    for path in [BanUser::PATH, UnbanUser::PATH] {
+        let req = |auth: bool| {
+            let req = Request::builder()
+                .method(Method::POST)
+                .uri(path)
+                .header(CONTENT_TYPE, "application/x-www-form-urlencoded");
+            let req = if auth {
+                req.header(AUTHORIZATION, "Bearer admin")
+            } else {
+                req
+            };
+            req.body(Body::from("id=7")).unwrap()
+        };
+
+        let res = handle_server_fns(req(false)).await.into_response();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+        let res = handle_server_fns(req(true)).await.into_response();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body, "7");
+    }
+    assert_eq!(ADMIN_REQUESTS.load(Ordering::Relaxed), 4);
+}
+
+#[tokio::test]
+async fn typed_errors_are_sent_back_to_the_client() {
+    for (id, expected) in [
+        (
+            "id=0",
+            LookupError::Forbidden {
+                reason: "reserved | internal".to_string(),
+            },
+        ),
+        ("id=2", LookupError::NotFound),
+    ] {
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(LookUp::PATH)
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(id))
+            .unwrap();
+        let res = handle_server_fns(req).await.into_response();
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let body = res.into_body().collect().await.unwrap().to_bytes();
+        let err = ServerFnError::<TypedError<LookupError>>::de(
+            std::str::from_utf8(&body).unwrap(),
+        );
+        assert_eq!(
+            err,
+            ServerFnError::WrappedServerError(TypedError(expected))
+        );
+    }
+}
+
+#[tokio::test]
+async fn server_fns_can_redirect_without_framework_types() {
+    let req = |accept: &str| {
+        Request::builder()
+            .method(Method::POST)
+            .uri(LogOut::PATH)
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .header(ACCEPT, accept)
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    // a plain form submission is redirected by the browser
+    let res = handle_server_fns(req("text/html")).await.into_response();
+    assert_eq!(res.status(), StatusCode::FOUND);
+    assert_eq!(res.headers()[LOCATION], "/login");
+
+    // a call from the server fn client is redirected by the client, so that it gets the response
+    let res = handle_server_fns(req("application/json"))
+        .await
+        .into_response();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()[LOCATION], "/login");
+    assert!(res.headers().contains_key(REDIRECT_HEADER));
+}
+
+#[tokio::test]
+async fn server_fns_can_read_the_request_without_framework_types() {
+    let addr = std::net::SocketAddr::from(([127, 0, 0, 1], 8080));
+    let mut req = Request::builder()
+        .method(Method::POST)
+        .uri(format!("{}?debug", Whoami::PATH))
+        .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+        .header(COOKIE, "theme=dark; session=\"abc\"")
+        .header("X-Client", "tests")
+        .body(Body::empty())
+        .unwrap();
+    req.extensions_mut()
+        .insert(axum::extract::ConnectInfo(addr));
+
+    let res = handle_server_fns(req).await.into_response();
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(
+        String::from_utf8(body.to_vec()).unwrap(),
+        format!("\"POST {} abc 127.0.0.1:8080 tests\"", Whoami::PATH)
+    );
+}
+
+#[derive(Clone)]
+struct Pool(&'static str);
+
+#[derive(Clone)]
+struct Session(String);
+
+#[server]
+async fn current_user() -> Result<String, ServerFnError> {
+    let Pool(pool) = use_context::<Pool>().unwrap();
+    let Session(user) = use_context::<Session>().unwrap();
+    Ok(format!("{user} from {pool}"))
+}
+
+async fn load_session(
+    mut req: Request<Body>,
+    next: axum::middleware::Next,
+) -> Response<Body> {
+    let user = req.headers()["x-user"].to_str().unwrap().to_string();
+    req.extensions_mut()
+        .get_or_insert_default::<RequestContext>()
+        .insert(Session(user));
+    next.run(req).await
+}
+
+#[tokio::test]
+async fn request_context_from_layers_is_provided_to_server_fns() {
+    let app = axum::Router::new()
+        .route(CurrentUser::PATH, axum::routing::post(handle_server_fns))
+        .layer(axum::middleware::from_fn(load_session))
+        .layer(axum::Extension(
+            RequestContext::new().with(Pool("postgres")),
+        ));
+
+    let req = Request::builder()
+        .method(Method::POST)
+        .uri(CurrentUser::PATH)
+        .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+        .header("x-user", "greg")
+        .body(Body::empty())
+        .unwrap();
+    let res = tower::ServiceExt::oneshot(app, req).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(body, "\"greg from postgres\"");
+}
+
+#[tokio::test]
+async fn additional_context_overrides_request_context_for_server_fns() {
+    let mut req = Request::builder()
+        .method(Method::POST)
+        .uri(CurrentUser::PATH)
+        .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+        .body(Body::empty())
+        .unwrap();
+    req.extensions_mut().insert(
+        RequestContext::new()
+            .with(Pool("postgres"))
+            .with(Session("greg".into())),
+    );
+
+    let res =
+        handle_server_fns_with_context(|| provide_context(Pool("sqlite")), req)
+            .await
+            .into_response();
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(body, "\"greg from sqlite\"");
+}
@@ -0,0 +1,274 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use http_body_util::BodyExt;
+use leptos::{config::LeptosOptions, prelude::*};
+use leptos_axum::{
+    build_sitemap, build_static_routes, generate_route_list, generate_sitemap,
+    sitemap_router, LeptosRoutes, MemoryCache, ResponseOptions, StaticCache,
+};
+use leptos_meta::{provide_meta_context, MetaTags, Title};
+use leptos_router::{
+    components::{Route, Router, Routes},
+    hooks::use_params_map,
+    path, StaticParamsMap, StaticRoute,
+};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tower::ServiceExt;
+
+static RENDERS: AtomicUsize = AtomicUsize::new(0);
+
+fn shell() -> impl IntoView {
+    provide_meta_context();
+    view! {
+        <!DOCTYPE html>
+        <html>
+            <head>
+                <MetaTags/>
+            </head>
+            <body>
+                <App/>
+            </body>
+        </html>
+    }
+}
+
+#[component]
+fn App() -> impl IntoView {
+    view! {
+        <Router>
+            <Routes fallback=|| "Not found.">
+                <Route path=path!("/") view=|| "Home" static_route=StaticRoute::new()/>
+                <Route path=path!("/dynamic") view=|| "Dynamic"/>
+                <Route
+                    path=path!("/count")
+                    view=|| RENDERS.fetch_add(1, Ordering::Relaxed) + 1
+                    static_route=StaticRoute::new()
+                        .incremental()
+                        .revalidate(Duration::from_millis(100))
+                />
+                <Route
+                    path=path!("/tags/:tag")
+                    view=|| "Tag"
+                    static_route=StaticRoute::new()
+                        .incremental()
+                        .prerender_params(|| async {
+                            [("tag", ["rust"])]
+                                .into_iter()
+                                .collect::<StaticParamsMap>()
+                        })
+                />
+                <Route
+                    path=path!("/gone")
+                    view=|| {
+                        expect_context::<ResponseOptions>()
+                            .set_status(StatusCode::GONE);
+                        "Gone"
+                    }
+                    static_route=StaticRoute::new().incremental()
+                />
+                <Route
+                    path=path!("/posts/:slug")
+                    view=Post
+                    static_route=StaticRoute::new()
+                        .prerender_params(|| async {
+                            [("slug", ["first", "second"])]
+                                .into_iter()
+                                .collect::<StaticParamsMap>()
+                        })
+                />
+            </Routes>
+        </Router>
+    }
+}
+
+#[component]
+fn Post() -> impl IntoView {
+    let params = use_params_map();
+    let slug = move || params.read().get("slug").unwrap_or_default();
+    // static pages are only written once all of their async data has loaded
+    let title = Resource::new(slug, |slug| async move {
+        tokio::task::yield_now().await;
+        format!("Post: {slug}")
+    });
+    view! {
+        <Suspense fallback=|| "Loading...">
+            {move || Suspend::new(async move {
+                let title = title.await;
+                view! {
+                    <Title text=title.clone()/>
+                    <h1>{title}</h1>
+                }
+            })}
+        </Suspense>
+    }
+}
+
+#[tokio::test]
+async fn static_routes_are_written_to_the_site_root() {
+    let site_root = std::env::temp_dir()
+        .join(format!("leptos_axum_static_routes_{}", std::process::id()));
+    let options = LeptosOptions::builder()
+        .output_name("static_routes")
+        .site_root(site_root.to_string_lossy())
+        .build();
+
+    let routes = generate_route_list(App);
+    let paths = build_static_routes(&options, shell, &routes).await.unwrap();
+    // incremental routes are rendered on the first request instead
+    assert_eq!(paths, ["/", "/posts/first", "/posts/second"]);
+
+    let home = std::fs::read_to_string(site_root.join("index.html")).unwrap();
+    assert!(home.starts_with("<!DOCTYPE html>"));
+    assert!(home.contains("<body>Home</body>"));
+
+    let post =
+        std::fs::read_to_string(site_root.join("posts/second/index.html"))
+            .unwrap();
+    assert!(post.contains("<title>Post: second</title>"));
+    assert!(post.contains("<h1>Post: second</h1>"));
+    assert!(!post.contains("Loading..."));
+
+    std::fs::remove_dir_all(site_root).unwrap();
+}
+
+#[tokio::test]
+async fn incremental_routes_are_rendered_on_request_and_revalidated() {
+    let options = LeptosOptions::builder().output_name("isr").build();
+    let cache: Arc<dyn StaticCache> = Arc::new(MemoryCache::new());
+    let app = {
+        let cache = cache.clone();
+        Router::new()
+            .leptos_routes_with_context(
+                &options,
+                generate_route_list(App),
+                move || provide_context(cache.clone()),
+                shell,
+            )
+            .with_state(options)
+    };
+    let get = |path: &'static str| {
+        let app = app.clone();
+        async move {
+            let req = Request::get(path).body(Body::empty()).unwrap();
+            let res = app.oneshot(req).await.unwrap();
+            let body = res.into_body().collect().await.unwrap().to_bytes();
+            String::from_utf8(body.to_vec()).unwrap()
+        }
+    };
+
+    // the page is rendered and stored on the first request, then served from the cache
+    assert!(cache.get(&"/count".into()).await.is_none());
+    assert!(get("/count").await.contains("<body>1</body>"));
+    assert!(get("/count").await.contains("<body>1</body>"));
+    assert!(cache
+        .get(&"/count".into())
+        .await
+        .unwrap()
+        .html
+        .contains("<body>1</body>"));
+
+    // once it expires, the old page is served while the new one is rendered
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert!(get("/count").await.contains("<body>1</body>"));
+    for _ in 0..100 {
+        if cache
+            .get(&"/count".into())
+            .await
+            .unwrap()
+            .html
+            .contains("<body>2</body>")
+        {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert!(get("/count").await.contains("<body>2</body>"));
+    assert_eq!(RENDERS.load(Ordering::Relaxed), 2);
+}
+
+#[tokio::test]
+async fn only_listed_successful_pages_are_cached() {
+    let options = LeptosOptions::builder().output_name("isr_listed").build();
+    let cache: Arc<dyn StaticCache> = Arc::new(MemoryCache::new());
+    let app = {
+        let cache = cache.clone();
+        Router::new()
+            .leptos_routes_with_context(
+                &options,
+                generate_route_list(App),
+                move || provide_context(cache.clone()),
+                shell,
+            )
+            .with_state(options)
+    };
+    let get = |path: &'static str| {
+        let app = app.clone();
+        async move {
+            let req = Request::get(path).body(Body::empty()).unwrap();
+            app.oneshot(req).await.unwrap()
+        }
+    };
+
+    // only the paths generated from the static params are stored
+    assert_eq!(get("/tags/rust").await.status(), StatusCode::OK);
+    assert!(cache.get(&"/tags/rust".into()).await.is_some());
+    assert_eq!(get("/tags/go").await.status(), StatusCode::OK);
+    assert!(cache.get(&"/tags/go".into()).await.is_none());
+
+    // pages that set an error status are sent with it, but not stored
+    assert_eq!(get("/gone").await.status(), StatusCode::GONE);
+    assert!(cache.get(&"/gone".into()).await.is_none());
+}
+
+#[tokio::test]
+async fn sitemaps_are_written_and_served() {
+    let routes = generate_route_list(App);
+    let sitemap = generate_sitemap("https://leptos.dev", &routes).await;
+    assert_eq!(
+        sitemap.paths().collect::<Vec<_>>(),
+        [
+            "/",
+            "/dynamic",
+            "/count",
+            "/tags/rust",
+            "/gone",
+            "/posts/first",
+            "/posts/second"
+        ]
+    );
+
+    let app: Router = sitemap_router(sitemap);
+    let req = Request::get("/robots.txt").body(Body::empty()).unwrap();
+    let res = app.oneshot(req).await.unwrap();
+    assert_eq!(res.headers()["content-type"], "text/plain");
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(
+        body,
+        "User-agent: *\nAllow: /\n\nSitemap: https://leptos.dev/sitemap.xml\n"
+    );
+
+    let site_root = std::env::temp_dir()
+        .join(format!("leptos_axum_sitemap_{}", std::process::id()));
+    let options = LeptosOptions::builder()
+        .output_name("sitemap")
+        .site_root(site_root.to_string_lossy())
+        .build();
+    build_sitemap(&options, "https://leptos.dev", &routes)
+        .await
+        .unwrap();
+    let xml = std::fs::read_to_string(site_root.join("sitemap.xml")).unwrap();
+    assert!(
+        xml.contains("<url><loc>https://leptos.dev/posts/first</loc></url>")
+    );
+    assert!(site_root.join("robots.txt").exists());
+    std::fs::remove_dir_all(site_root).unwrap();
+}
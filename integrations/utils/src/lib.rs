@@ -2,11 +2,21 @@ use futures::{stream::once, Stream, StreamExt};
 use hydration_context::{SharedContext, SsrSharedContext};
 use leptos::{
     nonce::use_nonce,
-    reactive_graph::owner::{Owner, Sandboxed},
+    reactive_graph::owner::{use_context, Owner, Sandboxed},
+    response::{use_response_meta, ResponseMetaParts},
     IntoView,
 };
 use leptos_meta::ServerMetaContextOutput;
-use std::{future::Future, pin::Pin, sync::Arc};
+use std::{
+    future::Future,
+    io, mem,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+mod static_cache;
+pub use static_cache::*;
 
 pub type PinnedStream<T> = Pin<Box<dyn Stream<Item = T> + Send>>;
 pub type PinnedFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
@@ -20,6 +30,36 @@ pub trait ExtendResponse: Sized {
 
     fn extend_response(&mut self, opt: &Self::ResponseOptions);
 
+    /// Sets the status code of the response, unless it is not a valid one.
+    fn set_status(&mut self, status: u16);
+
+    /// Adds a header to the response, first removing any header with the same name if `replace`
+    /// is `true`. Returns `false`, and leaves the response as it was, if the name or the value is
+    /// not valid.
+    fn add_header(&mut self, name: &str, value: &str, replace: bool) -> bool;
+
+    /// Applies the status and headers set with a
+    /// [`ResponseMeta`](leptos::response::ResponseMeta). The first header with each
+    /// name replaces any header with that name already on the response.
+    fn apply_response_meta(&mut self, meta: ResponseMetaParts) {
+        if let Some(status) = meta.status {
+            self.set_status(status);
+        }
+        // header names are case-insensitive
+        let mut replaced = Vec::new();
+        for (name, value) in meta.headers {
+            let lowercase = name.to_ascii_lowercase();
+            let replace = !replaced.contains(&lowercase);
+            if self.add_header(&name, &value, replace) && replace {
+                replaced.push(lowercase);
+            }
+        }
+    }
+
+    /// The status and headers that have been set with the integration's `ResponseOptions` so
+    /// far. These are checked before a page is cached, and stored along with it.
+    fn response_options_meta(opt: &Self::ResponseOptions) -> ResponseMetaParts;
+
     fn set_default_content_type(&mut self, content_type: &str);
 
     fn from_app<IV>(
@@ -35,43 +75,162 @@ pub trait ExtendResponse: Sized {
     where
         IV: IntoView + 'static,
     {
-        async move {
-            let (owner, stream) =
-                build_response(app_fn, additional_context, stream_builder);
+        render_app(
+            app_fn,
+            meta_context,
+            additional_context,
+            res_options,
+            stream_builder,
+            None::<fn(&SsrCacheOptions) -> CacheKey>,
+        )
+    }
 
-            let stream = stream.await.ready_chunks(32).map(|n| n.join(""));
+    /// Renders the app like [`from_app`](Self::from_app), unless the additional context provides
+    /// an [`SsrCacheOptions`] and the cache holds a page for the key that `cache_key` returns
+    /// which has not expired, in which case that page is sent. Otherwise, the page is stored in
+    /// the cache once the whole response has been sent, if it can be cached.
+    fn from_app_with_cache<IV>(
+        cache_key: impl FnOnce(&SsrCacheOptions) -> CacheKey + Send + 'static,
+        app_fn: impl FnOnce() -> IV + Send + 'static,
+        meta_context: ServerMetaContextOutput,
+        additional_context: impl FnOnce() + Send + 'static,
+        res_options: Self::ResponseOptions,
+        stream_builder: fn(
+            IV,
+            BoxedFnOnce<PinnedStream<String>>,
+        ) -> PinnedFuture<PinnedStream<String>>,
+    ) -> impl Future<Output = Self> + Send
+    where
+        IV: IntoView + 'static,
+    {
+        render_app(
+            app_fn,
+            meta_context,
+            additional_context,
+            res_options,
+            stream_builder,
+            Some(cache_key),
+        )
+    }
+}
 
-            let sc = owner.shared_context().unwrap();
-            while let Some(pending) = sc.await_deferred() {
-                pending.await;
-            }
+async fn render_app<R, IV>(
+    app_fn: impl FnOnce() -> IV + Send + 'static,
+    meta_context: ServerMetaContextOutput,
+    additional_context: impl FnOnce() + Send + 'static,
+    res_options: R::ResponseOptions,
+    stream_builder: fn(
+        IV,
+        BoxedFnOnce<PinnedStream<String>>,
+    ) -> PinnedFuture<PinnedStream<String>>,
+    cache_key: Option<impl FnOnce(&SsrCacheOptions) -> CacheKey>,
+) -> R
+where
+    R: ExtendResponse,
+    IV: IntoView + 'static,
+{
+    let owner = response_owner();
+    owner.with(additional_context);
 
-            let mut stream =
-                Box::pin(meta_context.inject_meta_context(stream).await);
+    // the cache is read from the context provided for this render
+    let cache = cache_key.and_then(|cache_key| {
+        let options = owner.with(use_context::<SsrCacheOptions>)?;
+        let key = cache_key(&options);
+        Some((options, key))
+    });
+    if let Some((options, key)) = &cache {
+        if let Some(CachedPage { html, meta, .. }) = options.get(key).await {
+            let mut res = R::from_stream(once(async move { html }));
+            res.apply_response_meta(meta);
+            res.set_default_content_type("text/html; charset=utf-8");
+            return res;
+        }
+    }
 
-            // wait for the first chunk of the stream, then set the status and headers
-            let first_chunk = stream.next().await.unwrap_or_default();
+    let stream = render_in_owner(&owner, app_fn, stream_builder);
 
-            let mut res = Self::from_stream(Sandboxed::new(
-                once(async move { first_chunk })
-                    .chain(stream)
-                    // drop the owner, cleaning up the reactive runtime,
-                    // once the stream is over
-                    .chain(once(async move {
-                        drop(owner);
-                        Default::default()
-                    })),
-            ));
+    let stream = stream.await.ready_chunks(32).map(|n| n.join(""));
 
-            res.extend_response(&res_options);
+    let sc = owner.shared_context().unwrap();
+    while let Some(pending) = sc.await_deferred() {
+        pending.await;
+    }
 
-            // Set the Content Type headers on all responses. This makes Firefox show the page source
-            // without complaining
-            res.set_default_content_type("text/html; charset=utf-8");
+    let mut stream = Box::pin(meta_context.inject_meta_context(stream).await);
+
+    // wait for the first chunk of the stream, then set the status and headers
+    let first_chunk = stream.next().await.unwrap_or_default();
+    let meta = owner.with(use_response_meta).map(|meta| meta.take());
+
+    let mut stream = Box::pin(once(async move { first_chunk }).chain(stream))
+        as PinnedStream<String>;
 
-            res
+    // the status and headers set with `ResponseOptions` are stored along with the page, after
+    // those set with a `ResponseMeta`, as that is the order they are applied in
+    if let Some((cache, key)) = cache {
+        let mut stored = meta.clone().unwrap_or_default();
+        let options_meta = R::response_options_meta(&res_options);
+        if SsrCacheOptions::is_cacheable(&stored)
+            && SsrCacheOptions::is_cacheable(&options_meta)
+        {
+            stored.status = options_meta.status.or(stored.status);
+            stored.headers.extend(options_meta.headers);
+            stream = cache_stream(stream, sc, cache, key, stored);
         }
     }
+
+    let mut res = R::from_stream(Sandboxed::new(
+        stream
+            // drop the owner, cleaning up the reactive runtime,
+            // once the stream is over
+            .chain(once(async move {
+                drop(owner);
+                Default::default()
+            })),
+    ));
+
+    if let Some(meta) = meta {
+        res.apply_response_meta(meta);
+    }
+    res.extend_response(&res_options);
+
+    // Set the Content Type headers on all responses. This makes Firefox show the page source
+    // without complaining
+    res.set_default_content_type("text/html; charset=utf-8");
+
+    res
+}
+
+/// Passes the stream through, storing the whole page in the cache once it is over.
+fn cache_stream(
+    stream: PinnedStream<String>,
+    shared_context: Arc<dyn SharedContext + Send + Sync>,
+    cache: SsrCacheOptions,
+    key: CacheKey,
+    meta: ResponseMetaParts,
+) -> PinnedStream<String> {
+    let html = Arc::new(Mutex::new(String::new()));
+    Box::pin(
+        stream
+            .inspect({
+                let html = Arc::clone(&html);
+                move |chunk| html.lock().unwrap().push_str(chunk)
+            })
+            .chain(once(async move {
+                // pages that were sent with fallbacks because the render deadline passed are
+                // not cached
+                let deadline_passed = shared_context
+                    .deadline()
+                    .is_some_and(|deadline| deadline.peek().is_some());
+                if !deadline_passed {
+                    let html = mem::take(&mut *html.lock().unwrap());
+                    if let Err(e) = cache.set(key, html, meta).await {
+                        tracing::error!("failed to cache rendered page: {e}");
+                    }
+                }
+                String::new()
+            })),
+    )
 }
 
 pub fn build_response<IV>(
@@ -85,50 +244,153 @@ pub fn build_response<IV>(
 where
     IV: IntoView + 'static,
 {
-    let shared_context = Arc::new(SsrSharedContext::new())
-        as Arc<dyn SharedContext + Send + Sync>;
-    let owner = Owner::new_root(Some(Arc::clone(&shared_context)));
-    let stream = Box::pin(Sandboxed::new({
+    let owner = response_owner();
+    let stream = Box::pin({
         let owner = owner.clone();
         async move {
-            let stream = owner.with(|| {
-                additional_context();
-
-                // run app
-                let app = app_fn();
-
-                let nonce = use_nonce()
-                    .as_ref()
-                    .map(|nonce| format!(" nonce=\"{nonce}\""))
-                    .unwrap_or_default();
-
-                let shared_context = Owner::current_shared_context().unwrap();
-
-                let chunks = Box::new({
-                    let shared_context = shared_context.clone();
-                    move || {
-                        Box::pin(shared_context.pending_data().unwrap().map(
-                            move |chunk| {
-                                format!("<script{nonce}>{chunk}</script>")
-                            },
-                        ))
-                            as Pin<Box<dyn Stream<Item = String> + Send>>
-                    }
+            owner.with(additional_context);
+            render_in_owner(&owner, app_fn, stream_builder).await
+        }
+    });
+    (owner, stream)
+}
+
+/// Creates the root owner that a response is rendered in.
+fn response_owner() -> Owner {
+    let shared_context = Arc::new(SsrSharedContext::new())
+        as Arc<dyn SharedContext + Send + Sync>;
+    let owner = Owner::new_root(Some(shared_context));
+    // the app is rendered inside `owner.with()`, so this thread doesn't need to hold on to it as
+    // the current owner, which would keep it alive after the response has been sent
+    owner.unset();
+    owner
+}
+
+/// Renders the app in an owner that the additional context has already been provided to.
+fn render_in_owner<IV>(
+    owner: &Owner,
+    app_fn: impl FnOnce() -> IV + Send + 'static,
+    stream_builder: fn(
+        IV,
+        BoxedFnOnce<PinnedStream<String>>,
+    ) -> PinnedFuture<PinnedStream<String>>,
+) -> PinnedFuture<PinnedStream<String>>
+where
+    IV: IntoView + 'static,
+{
+    // the owner's arena is entered, so that the future is sandboxed in it
+    owner.with(|| {
+        Box::pin(Sandboxed::new({
+            let owner = owner.clone();
+            async move {
+                let stream = owner.with(|| {
+                    // run app
+                    let app = app_fn();
+
+                    let nonce = use_nonce()
+                        .as_ref()
+                        .map(|nonce| format!(" nonce=\"{nonce}\""))
+                        .unwrap_or_default();
+
+                    let shared_context =
+                        Owner::current_shared_context().unwrap();
+
+                    let chunks = Box::new({
+                        let shared_context = shared_context.clone();
+                        move || {
+                            Box::pin(
+                                shared_context.pending_data().unwrap().map(
+                                    move |chunk| {
+                                        format!(
+                                            "<script{nonce}>{chunk}</script>"
+                                        )
+                                    },
+                                ),
+                            )
+                                as Pin<Box<dyn Stream<Item = String> + Send>>
+                        }
+                    });
+
+                    // convert app to appropriate response type
+                    // and chain the app stream, followed by chunks
+                    // in theory, we could select here, and intersperse them
+                    // the problem is that during the DOM walk, that would be mean random <script> tags
+                    // interspersed where we expect other children
+                    //
+                    // we also don't actually start hydrating until after the whole stream is complete,
+                    // so it's not useful to send those scripts down earlier.
+                    stream_builder(app, chunks)
                 });
 
-                // convert app to appropriate response type
-                // and chain the app stream, followed by chunks
-                // in theory, we could select here, and intersperse them
-                // the problem is that during the DOM walk, that would be mean random <script> tags
-                // interspersed where we expect other children
-                //
-                // we also don't actually start hydrating until after the whole stream is complete,
-                // so it's not useful to send those scripts down earlier.
-                stream_builder(app, chunks)
-            });
-
-            stream.await
+                stream.await
+            }
+        })) as PinnedFuture<PinnedStream<String>>
+    })
+}
+
+/// Renders the app to a complete HTML document, after all of its async data has loaded.
+///
+/// This is used to render static routes. `additional_context` should provide the
+/// [`ServerMetaContext`](leptos_meta::ServerMetaContext) that `meta_context` was created with,
+/// along with any other context the app needs.
+pub async fn render_static_html<IV>(
+    app_fn: impl FnOnce() -> IV + Send + 'static,
+    meta_context: ServerMetaContextOutput,
+    additional_context: impl FnOnce() + Send + 'static,
+) -> String
+where
+    IV: IntoView + 'static,
+{
+    let (owner, stream) =
+        build_response(app_fn, additional_context, |app, chunks| {
+            Box::pin(async move {
+                let app =
+                    app.to_html_stream_in_order().collect::<String>().await;
+                Box::pin(once(async move { app }).chain(chunks()))
+                    as PinnedStream<String>
+            })
+        });
+
+    Sandboxed::new(async move {
+        let stream = stream.await;
+        let sc = owner.shared_context().unwrap();
+        while let Some(pending) = sc.await_deferred() {
+            pending.await;
         }
-    }));
-    (owner, stream)
+        let html = meta_context
+            .inject_meta_context(stream)
+            .await
+            .collect::<String>()
+            .await;
+        drop(owner);
+        html
+    })
+    .await
+}
+
+/// The file that the static HTML for a URL path is written to: `/posts/1` is written to
+/// `{site_root}/posts/1/index.html`.
+pub fn static_file_path(site_root: impl AsRef<Path>, path: &str) -> PathBuf {
+    let mut file = site_root.as_ref().to_path_buf();
+    file.extend(
+        path.split('/')
+            .filter(|segment| !segment.is_empty() && *segment != ".."),
+    );
+    file.push("index.html");
+    file
+}
+
+/// Writes the static HTML for a URL path into the site root, creating any directories that are
+/// needed, and returns the path of the file. See [`static_file_path`].
+pub fn write_static_file(
+    site_root: impl AsRef<Path>,
+    path: &str,
+    html: &str,
+) -> io::Result<PathBuf> {
+    let file = static_file_path(site_root, path);
+    if let Some(dir) = file.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(&file, html)?;
+    Ok(file)
 }
@@ -0,0 +1,391 @@
+use crate::{static_file_path, write_static_file, PinnedFuture};
+use leptos::response::ResponseMetaParts;
+use or_poisoned::OrPoisoned;
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    io,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime},
+};
+
+/// The key a page is stored under in a [`StaticCache`].
+///
+/// Static routes are keyed by their path alone. Server-rendered pages that are cached with an
+/// [`SsrCacheOptions`] are also keyed by their query and by the request headers they vary on.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    /// The path of the page, without its query.
+    pub path: String,
+    /// The query of the request, without the leading `?`, if it had one.
+    pub query: Option<String>,
+    /// The value of each header named in [`SsrCacheOptions::vary`], in the same order.
+    pub vary: Vec<Option<String>>,
+}
+
+impl CacheKey {
+    /// The key for a path and query, such as `/posts?page=2`, that does not vary on any
+    /// headers.
+    pub fn new(path_and_query: &str) -> Self {
+        let (path, query) = match path_and_query.split_once('?') {
+            Some((path, query)) => (path, Some(query.to_string())),
+            None => (path_and_query, None),
+        };
+        Self {
+            path: path.to_string(),
+            query,
+            vary: Vec::new(),
+        }
+    }
+
+    /// Whether the page is keyed by its path alone.
+    pub fn is_path_only(&self) -> bool {
+        self.query.is_none() && self.vary.is_empty()
+    }
+}
+
+impl From<&str> for CacheKey {
+    fn from(path_and_query: &str) -> Self {
+        Self::new(path_and_query)
+    }
+}
+
+/// A page of HTML that has been rendered and stored in a [`StaticCache`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedPage {
+    /// The HTML for the page.
+    pub html: String,
+    /// When the page was rendered.
+    pub rendered_at: SystemTime,
+    /// The status and headers that were set while the page was rendered, which are sent along
+    /// with it. Static pages are only stored if they do not set any.
+    pub meta: ResponseMetaParts,
+    /// When the page should no longer be served from the cache, if ever.
+    pub expires_at: Option<SystemTime>,
+}
+
+impl CachedPage {
+    /// A page that has just been rendered, with no status or headers, and no expiry.
+    pub fn new(html: String) -> Self {
+        Self {
+            html,
+            rendered_at: SystemTime::now(),
+            meta: ResponseMetaParts::default(),
+            expires_at: None,
+        }
+    }
+
+    /// How long ago the page was rendered.
+    pub fn age(&self) -> Duration {
+        self.rendered_at.elapsed().unwrap_or_default()
+    }
+
+    /// Whether the page has outlived its time-to-live.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| SystemTime::now() >= expires_at)
+    }
+}
+
+/// Stores the HTML rendered for static routes and, when they are cached with an
+/// [`SsrCacheOptions`], for server-rendered pages.
+///
+/// By default, the server integrations store static pages as files in the site root with a
+/// [`FileCache`]. A different cache can be used by providing it as an `Arc<dyn StaticCache>`
+/// in the additional context passed to the integration.
+///
+/// Expired pages are never served, so an implementation does not need to check
+/// [`CachedPage::is_expired`] itself.
+pub trait StaticCache: Send + Sync + 'static {
+    /// Loads the page stored for a key, if any.
+    fn get(&self, key: &CacheKey) -> PinnedFuture<Option<CachedPage>>;
+
+    /// Stores a page, replacing any previous version.
+    fn set(
+        &self,
+        key: CacheKey,
+        page: CachedPage,
+    ) -> PinnedFuture<io::Result<()>>;
+
+    /// Removes every page stored for a path, whatever its query and the headers it varied on,
+    /// so that it is rendered again on the next request.
+    fn invalidate(&self, path: &str) -> PinnedFuture<io::Result<()>>;
+}
+
+/// Stores static pages as `index.html` files in a directory, usually the site root. The age of
+/// each page is taken from the modification time of its file.
+///
+/// See [`static_file_path`] for where each path is written. Only pages that are keyed by their
+/// path alone, and have no status, headers or expiry, can be stored as files; storing any other
+/// page fails with [`io::ErrorKind::Unsupported`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileCache {
+    root: PathBuf,
+}
+
+impl FileCache {
+    /// Creates a cache that stores its files in `root`.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl StaticCache for FileCache {
+    fn get(&self, key: &CacheKey) -> PinnedFuture<Option<CachedPage>> {
+        if !key.is_path_only() {
+            return Box::pin(async { None });
+        }
+        let file = static_file_path(&self.root, &key.path);
+        Box::pin(async move {
+            // file system access blocks, so it is moved off the async runtime
+            tokio::task::spawn_blocking(move || {
+                let html = std::fs::read_to_string(&file).ok()?;
+                let rendered_at = std::fs::metadata(&file)
+                    .and_then(|meta| meta.modified())
+                    .unwrap_or_else(|_| SystemTime::now());
+                Some(CachedPage {
+                    rendered_at,
+                    ..CachedPage::new(html)
+                })
+            })
+            .await
+            .ok()
+            .flatten()
+        })
+    }
+
+    fn set(
+        &self,
+        key: CacheKey,
+        page: CachedPage,
+    ) -> PinnedFuture<io::Result<()>> {
+        if !key.is_path_only()
+            || page.meta != ResponseMetaParts::default()
+            || page.expires_at.is_some()
+        {
+            return Box::pin(async {
+                Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "only static pages can be stored as files",
+                ))
+            });
+        }
+        let root = self.root.clone();
+        Box::pin(async move {
+            tokio::task::spawn_blocking(move || {
+                write_static_file(&root, &key.path, &page.html).map(|_| ())
+            })
+            .await
+            .map_err(io::Error::other)?
+        })
+    }
+
+    fn invalidate(&self, path: &str) -> PinnedFuture<io::Result<()>> {
+        let file = static_file_path(&self.root, path);
+        Box::pin(async move {
+            tokio::task::spawn_blocking(move || {
+                match std::fs::remove_file(file) {
+                    Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+                    _ => Ok(()),
+                }
+            })
+            .await
+            .map_err(io::Error::other)?
+        })
+    }
+}
+
+/// Stores pages in memory. Pages are lost when the server restarts.
+///
+/// Clones share the same pages, so a clone can be kept to invalidate pages, for example from a
+/// server function that changes the data they show.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryCache {
+    pages: Arc<RwLock<MemoryPages>>,
+}
+
+#[derive(Debug, Default)]
+struct MemoryPages {
+    // grouped by path, so that every version of a path can be invalidated at once
+    by_path: HashMap<String, HashMap<CacheKey, CachedPage>>,
+    len: usize,
+    // expired pages are dropped once this many pages are stored
+    purge_at: usize,
+}
+
+impl MemoryPages {
+    // how many pages are stored before expired pages are first dropped
+    const MIN_PURGE_AT: usize = 64;
+
+    fn insert(&mut self, key: CacheKey, page: CachedPage) {
+        let pages = self.by_path.entry(key.path.clone()).or_default();
+        if pages.insert(key, page).is_none() {
+            self.len += 1;
+        }
+        // dropping expired pages scans the whole cache, so it is only done each time the
+        // cache has doubled in size since it was last done
+        if self.len >= self.purge_at {
+            self.by_path.retain(|_, pages| {
+                pages.retain(|_, page| !page.is_expired());
+                !pages.is_empty()
+            });
+            self.len = self.by_path.values().map(HashMap::len).sum();
+            self.purge_at = (self.len * 2).max(Self::MIN_PURGE_AT);
+        }
+    }
+}
+
+impl MemoryCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StaticCache for MemoryCache {
+    fn get(&self, key: &CacheKey) -> PinnedFuture<Option<CachedPage>> {
+        let page = self
+            .pages
+            .read()
+            .or_poisoned()
+            .by_path
+            .get(&key.path)
+            .and_then(|pages| pages.get(key))
+            .cloned();
+        Box::pin(async move { page })
+    }
+
+    fn set(
+        &self,
+        key: CacheKey,
+        page: CachedPage,
+    ) -> PinnedFuture<io::Result<()>> {
+        self.pages.write().or_poisoned().insert(key, page);
+        Box::pin(async { Ok(()) })
+    }
+
+    fn invalidate(&self, path: &str) -> PinnedFuture<io::Result<()>> {
+        let mut pages = self.pages.write().or_poisoned();
+        if let Some(removed) = pages.by_path.remove(path) {
+            pages.len -= removed.len();
+        }
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Enables caching of server-rendered pages, when it is provided in the additional context passed
+/// to a server integration.
+///
+/// Only `GET` requests are cached. A page is not cached if its route throws an error, if it
+/// redirects or sets a status other than `2xx`, a cookie, or a `cache-control` header that
+/// contains `no-store` or `private`, or if the render deadline passes before all of its data has
+/// loaded. This applies to the status and headers set with a
+/// [`ResponseMeta`](leptos::response::ResponseMeta) and with an integration's own
+/// `ResponseOptions` alike, as both are stored and sent along with a cached page.
+///
+/// ```rust,ignore
+/// let cache = MemoryCache::new();
+/// let options =
+///     SsrCacheOptions::new(cache.clone(), Duration::from_secs(60)).vary("accept-language");
+/// let app = Router::new().leptos_routes_with_context(
+///     &leptos_options,
+///     routes,
+///     move || provide_context(options.clone()),
+///     App,
+/// );
+///
+/// // later, once a post has changed
+/// cache.invalidate("/posts/1").await?;
+/// ```
+#[derive(Clone)]
+pub struct SsrCacheOptions {
+    cache: Arc<dyn StaticCache>,
+    ttl: Duration,
+    vary: Vec<String>,
+}
+
+impl SsrCacheOptions {
+    /// Caches pages in `cache` for `ttl` after they are rendered.
+    pub fn new(cache: impl StaticCache, ttl: Duration) -> Self {
+        Self {
+            cache: Arc::new(cache),
+            ttl,
+            vary: Vec::new(),
+        }
+    }
+
+    /// Caches a separate version of each page for every value of a request header.
+    pub fn vary(mut self, header: impl Into<String>) -> Self {
+        self.vary.push(header.into().to_ascii_lowercase());
+        self
+    }
+
+    /// The cache that pages are stored in.
+    pub fn cache(&self) -> &Arc<dyn StaticCache> {
+        &self.cache
+    }
+
+    /// How long pages are cached for.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// The key for a request, given its path and query and a function that returns the value of
+    /// a request header.
+    pub fn key(
+        &self,
+        path_and_query: &str,
+        header: impl Fn(&str) -> Option<String>,
+    ) -> CacheKey {
+        CacheKey {
+            vary: self.vary.iter().map(|name| header(name)).collect(),
+            ..CacheKey::new(path_and_query)
+        }
+    }
+
+    /// Loads the page stored for a key, unless it has expired.
+    pub async fn get(&self, key: &CacheKey) -> Option<CachedPage> {
+        self.cache.get(key).await.filter(|page| !page.is_expired())
+    }
+
+    /// Stores a page that has just been rendered.
+    pub async fn set(
+        &self,
+        key: CacheKey,
+        html: String,
+        meta: ResponseMetaParts,
+    ) -> io::Result<()> {
+        let page = CachedPage {
+            meta,
+            expires_at: Some(SystemTime::now() + self.ttl),
+            ..CachedPage::new(html)
+        };
+        self.cache.set(key, page).await
+    }
+
+    /// Whether a page that was rendered with these response parts can be cached: it must not
+    /// set a status other than `2xx`, redirect, set a cookie, or forbid shared caching.
+    pub fn is_cacheable(meta: &ResponseMetaParts) -> bool {
+        let success = meta
+            .status
+            .map_or(true, |status| (200..300).contains(&status));
+        success
+            && meta.headers.iter().all(|(name, value)| {
+                !name.eq_ignore_ascii_case("set-cookie")
+                    && !name.eq_ignore_ascii_case("location")
+                    && !(name.eq_ignore_ascii_case("cache-control")
+                        && (value.contains("no-store")
+                            || value.contains("private")))
+            })
+    }
+}
+
+impl Debug for SsrCacheOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SsrCacheOptions")
+            .field("ttl", &self.ttl)
+            .field("vary", &self.vary)
+            .finish_non_exhaustive()
+    }
+}
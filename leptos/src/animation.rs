@@ -0,0 +1,538 @@
+use leptos_dom::helpers::{
+    document, is_browser, request_animation_frame_with_handle, window,
+    AnimationFrameRequestHandle,
+};
+use reactive_graph::{
+    effect::Effect,
+    owner::Owner,
+    signal::RwSignal,
+    traits::{Get, GetUntracked, Set},
+    untrack,
+    wrappers::read::Signal,
+};
+use send_wrapper::SendWrapper;
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    rc::Rc,
+    sync::Arc,
+    time::Duration,
+};
+use wasm_bindgen::{prelude::Closure, JsCast};
+
+/// The current time in milliseconds, on the same clock as `requestAnimationFrame` timestamps.
+fn now() -> f64 {
+    window()
+        .performance()
+        .map(|performance| performance.now())
+        .unwrap_or_else(js_sys::Date::now)
+}
+
+/// Calls `cb` on every animation frame, with the current time in milliseconds, using
+/// [`requestAnimationFrame`](https://developer.mozilla.org/en-US/docs/Web/API/window/requestAnimationFrame).
+///
+/// The loop pauses while the tab is hidden, and stops for good when the current owner is
+/// disposed. It can also be paused and resumed with the returned [`RafHandle`]. On the server,
+/// this does nothing.
+///
+/// ```rust
+/// use leptos::{animation::use_raf, prelude::*};
+///
+/// #[component]
+/// fn Spinner() -> impl IntoView {
+///     let (angle, set_angle) = signal(0.0);
+///     use_raf(move |timestamp| set_angle.set(timestamp / 10.0 % 360.0));
+///     view! {
+///         <div style:transform=move || format!("rotate({}deg)", angle.get())>"*"</div>
+///     }
+/// }
+/// ```
+pub fn use_raf(cb: impl Fn(f64) + 'static) -> RafHandle {
+    RafHandle::new(
+        move |timestamp| {
+            cb(timestamp);
+            true
+        },
+        true,
+    )
+}
+
+/// Controls an animation frame loop started with [`use_raf`].
+#[derive(Clone)]
+pub struct RafHandle {
+    // only set in the browser, so that the wrapper is never dropped on another thread
+    inner: Option<Arc<SendWrapper<Rc<RafLoop>>>>,
+}
+
+impl fmt::Debug for RafHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RafHandle")
+            .field("is_paused", &self.is_paused())
+            .finish()
+    }
+}
+
+type FrameFn = Box<dyn Fn(f64) -> bool>;
+
+struct RafLoop {
+    // returns whether the loop should keep running
+    cb: FrameFn,
+    frame: Cell<Option<AnimationFrameRequestHandle>>,
+    paused: Cell<bool>,
+    hidden: Cell<bool>,
+}
+
+impl RafLoop {
+    fn schedule(self: &Rc<Self>) {
+        if self.paused.get() || self.hidden.get() || self.frame.get().is_some()
+        {
+            return;
+        }
+        let this = Rc::clone(self);
+        let frame = request_animation_frame_with_handle(move || {
+            this.frame.set(None);
+            if (this.cb)(now()) {
+                this.schedule();
+            } else {
+                this.paused.set(true);
+            }
+        });
+        self.frame.set(frame.ok());
+    }
+
+    fn cancel(&self) {
+        if let Some(frame) = self.frame.take() {
+            frame.cancel();
+        }
+    }
+}
+
+impl RafHandle {
+    fn new(cb: impl Fn(f64) -> bool + 'static, running: bool) -> Self {
+        if !is_browser() {
+            return Self { inner: None };
+        }
+
+        let raf = Rc::new(RafLoop {
+            cb: Box::new(cb),
+            frame: Cell::new(None),
+            paused: Cell::new(!running),
+            hidden: Cell::new(document().hidden()),
+        });
+        raf.schedule();
+
+        let on_visibility_change = Closure::<dyn Fn()>::new({
+            let raf = Rc::downgrade(&raf);
+            move || {
+                if let Some(raf) = raf.upgrade() {
+                    raf.hidden.set(document().hidden());
+                    if raf.hidden.get() {
+                        raf.cancel();
+                    } else {
+                        raf.schedule();
+                    }
+                }
+            }
+        });
+        _ = document().add_event_listener_with_callback(
+            "visibilitychange",
+            on_visibility_change.as_ref().unchecked_ref(),
+        );
+
+        let inner = Arc::new(SendWrapper::new(raf));
+        Owner::on_cleanup({
+            let inner = Arc::clone(&inner);
+            let on_visibility_change = SendWrapper::new(on_visibility_change);
+            move || {
+                inner.paused.set(true);
+                inner.cancel();
+                _ = document().remove_event_listener_with_callback(
+                    "visibilitychange",
+                    on_visibility_change.as_ref().unchecked_ref(),
+                );
+            }
+        });
+        Self { inner: Some(inner) }
+    }
+
+    fn with(&self, fun: impl FnOnce(&Rc<RafLoop>)) {
+        // the loop can only be used on the thread that created it
+        if let Some(inner) = self.inner.as_ref().filter(|inner| inner.valid()) {
+            fun(inner);
+        }
+    }
+
+    /// Stops calling the callback until [`resume`](RafHandle::resume) is called.
+    pub fn pause(&self) {
+        self.with(|raf| {
+            raf.paused.set(true);
+            raf.cancel();
+        });
+    }
+
+    /// Starts calling the callback on every frame again, after it was paused.
+    pub fn resume(&self) {
+        self.with(|raf| {
+            raf.paused.set(false);
+            raf.schedule();
+        });
+    }
+
+    /// Whether the loop has been paused. This is always `true` on the server.
+    pub fn is_paused(&self) -> bool {
+        let mut paused = true;
+        self.with(|raf| paused = raf.paused.get());
+        paused
+    }
+}
+
+/// An easing function, which controls how an animation progresses over time.
+#[derive(Debug, Default, Clone, Copy)]
+pub enum Easing {
+    /// Moves at a constant speed.
+    Linear,
+    /// Starts slowly and speeds up.
+    EaseIn,
+    /// Starts quickly and slows down.
+    #[default]
+    EaseOut,
+    /// Starts and ends slowly.
+    EaseInOut,
+    /// Maps the fraction of the duration that has passed, from `0.0` to `1.0`, to the fraction of
+    /// the distance that has been covered.
+    Custom(fn(f64) -> f64),
+}
+
+impl Easing {
+    /// Returns the fraction of the distance that has been covered once `t`, from `0.0` to `1.0`,
+    /// of the duration has passed.
+    pub fn apply(self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::Custom(f) => f(t),
+        }
+    }
+}
+
+struct Tween {
+    from: f64,
+    to: f64,
+    start: f64,
+}
+
+/// Creates a signal that follows `target`, animating from its old value to each new one over
+/// `duration` with the given [`Easing`].
+///
+/// If the target changes while an animation is running, the new animation starts from the
+/// current value. On the server, and until hydration is complete, the signal simply has the
+/// target's value.
+///
+/// ```rust
+/// use leptos::{
+///     animation::{create_animated_signal, Easing},
+///     prelude::*,
+/// };
+/// use std::time::Duration;
+///
+/// #[component]
+/// fn Progress() -> impl IntoView {
+///     let (progress, set_progress) = signal(0.0);
+///     let width = create_animated_signal(
+///         progress,
+///         Duration::from_millis(300),
+///         Easing::EaseInOut,
+///     );
+///     view! {
+///         <div style:width=move || format!("{}%", width.get())/>
+///         <button on:click=move |_| set_progress.set(100.0)>"Finish"</button>
+///     }
+/// }
+/// ```
+pub fn create_animated_signal(
+    target: impl Get<Value = f64> + 'static,
+    duration: Duration,
+    easing: Easing,
+) -> Signal<f64> {
+    let value = RwSignal::new(untrack(|| target.get()));
+
+    let tween = Rc::new(RefCell::new(None::<Tween>));
+    let duration = duration.as_secs_f64() * 1000.0;
+    let raf = RafHandle::new(
+        {
+            let tween = Rc::clone(&tween);
+            move |now| {
+                let mut tween = tween.borrow_mut();
+                let Some(Tween { from, to, start }) = *tween else {
+                    return false;
+                };
+                let progress = if duration > 0.0 {
+                    (now - start) / duration
+                } else {
+                    1.0
+                };
+                if progress >= 1.0 {
+                    value.set(to);
+                    *tween = None;
+                    false
+                } else {
+                    value.set(from + (to - from) * easing.apply(progress));
+                    true
+                }
+            }
+        },
+        false,
+    );
+
+    Effect::watch(
+        move || target.get(),
+        move |to, _, _| {
+            *tween.borrow_mut() = Some(Tween {
+                from: value.get_untracked(),
+                to: *to,
+                start: now(),
+            });
+            raf.resume();
+        },
+        false,
+    );
+
+    value.read_only().into()
+}
+
+/// The physical properties of a spring used by [`create_spring`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpringConfig {
+    /// How strongly the spring pulls the value towards its target.
+    pub stiffness: f64,
+    /// How strongly the spring resists motion. Lower values make it bounce more.
+    pub damping: f64,
+    /// The mass of the value being moved. Higher values make it slower to start and stop.
+    pub mass: f64,
+    /// The animation stops once the value is within this distance of its target and moving more
+    /// slowly than this speed (per second).
+    pub precision: f64,
+}
+
+/// The default spring moves quickly without overshooting its target.
+impl Default for SpringConfig {
+    fn default() -> Self {
+        Self {
+            stiffness: 170.0,
+            damping: 26.0,
+            mass: 1.0,
+            precision: 0.01,
+        }
+    }
+}
+
+impl SpringConfig {
+    /// A slower, softer spring.
+    pub fn gentle() -> Self {
+        Self {
+            stiffness: 120.0,
+            damping: 14.0,
+            ..Self::default()
+        }
+    }
+
+    /// A spring that overshoots its target and bounces.
+    pub fn wobbly() -> Self {
+        Self {
+            stiffness: 180.0,
+            damping: 12.0,
+            ..Self::default()
+        }
+    }
+
+    /// A fast spring that settles quickly.
+    pub fn stiff() -> Self {
+        Self {
+            stiffness: 210.0,
+            damping: 20.0,
+            ..Self::default()
+        }
+    }
+
+    /// Advances a value with the given `velocity` towards `target` by `dt` seconds, returning
+    /// the new value and velocity.
+    fn step(
+        &self,
+        mut value: f64,
+        mut velocity: f64,
+        target: f64,
+        dt: f64,
+    ) -> (f64, f64) {
+        // small fixed steps keep the simulation stable when frames are slow
+        const MAX_STEP: f64 = 1.0 / 120.0;
+        let mut remaining = dt;
+        while remaining > 0.0 {
+            let dt = remaining.min(MAX_STEP);
+            let force =
+                -self.stiffness * (value - target) - self.damping * velocity;
+            velocity += force / self.mass * dt;
+            value += velocity * dt;
+            remaining -= dt;
+        }
+        (value, velocity)
+    }
+
+    fn is_at_rest(&self, value: f64, velocity: f64, target: f64) -> bool {
+        (value - target).abs() < self.precision
+            && velocity.abs() < self.precision
+    }
+}
+
+struct Spring {
+    velocity: f64,
+    target: f64,
+    last_frame: Option<f64>,
+}
+
+/// Creates a signal that follows `target` with the motion of a spring, for animations that
+/// feel physical and can be interrupted smoothly.
+///
+/// When the target changes, the value accelerates towards it and settles according to the
+/// [`SpringConfig`], keeping its velocity if the target changes again mid-animation. On the
+/// server, and until hydration is complete, the signal simply has the target's value.
+///
+/// ```rust
+/// use leptos::{
+///     animation::{create_spring, SpringConfig},
+///     prelude::*,
+/// };
+///
+/// #[component]
+/// fn Drawer() -> impl IntoView {
+///     let (open, set_open) = signal(false);
+///     let offset = create_spring(
+///         Signal::derive(move || if open.get() { 0.0 } else { -300.0 }),
+///         SpringConfig::wobbly(),
+///     );
+///     view! {
+///         <button on:click=move |_| set_open.update(|open| *open = !*open)>"Toggle"</button>
+///         <nav style:transform=move || format!("translateX({}px)", offset.get())/>
+///     }
+/// }
+/// ```
+pub fn create_spring(
+    target: impl Get<Value = f64> + 'static,
+    config: SpringConfig,
+) -> Signal<f64> {
+    let initial = untrack(|| target.get());
+    let value = RwSignal::new(initial);
+
+    let spring = Rc::new(RefCell::new(Spring {
+        velocity: 0.0,
+        target: initial,
+        last_frame: None,
+    }));
+    let raf = RafHandle::new(
+        {
+            let spring = Rc::clone(&spring);
+            move |now| {
+                let mut spring = spring.borrow_mut();
+                // avoid a large jump after the tab has been hidden
+                let dt = spring
+                    .last_frame
+                    .map(|last| ((now - last) / 1000.0).clamp(0.0, 0.1))
+                    .unwrap_or(1.0 / 60.0);
+                spring.last_frame = Some(now);
+
+                let (new_value, velocity) = config.step(
+                    value.get_untracked(),
+                    spring.velocity,
+                    spring.target,
+                    dt,
+                );
+                if config.is_at_rest(new_value, velocity, spring.target) {
+                    value.set(spring.target);
+                    spring.velocity = 0.0;
+                    spring.last_frame = None;
+                    false
+                } else {
+                    value.set(new_value);
+                    spring.velocity = velocity;
+                    true
+                }
+            }
+        },
+        false,
+    );
+
+    Effect::watch(
+        move || target.get(),
+        move |new_target, _, _| {
+            let mut spring = spring.borrow_mut();
+            spring.target = *new_target;
+            if raf.is_paused() {
+                spring.last_frame = None;
+            }
+            raf.resume();
+        },
+        false,
+    );
+
+    value.read_only().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Easing, SpringConfig};
+
+    #[test]
+    fn easings_start_at_zero_and_end_at_one() {
+        for easing in [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+        ] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+            // progress is clamped
+            assert_eq!(easing.apply(2.0), 1.0);
+        }
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+        assert!(Easing::EaseIn.apply(0.5) < 0.5);
+        assert!(Easing::EaseOut.apply(0.5) > 0.5);
+    }
+
+    #[test]
+    fn springs_settle_at_their_target() {
+        for config in [
+            SpringConfig::default(),
+            SpringConfig::gentle(),
+            SpringConfig::wobbly(),
+            SpringConfig::stiff(),
+        ] {
+            let (mut value, mut velocity) = (0.0, 0.0);
+            let mut frames = 0;
+            while !config.is_at_rest(value, velocity, 100.0) {
+                (value, velocity) =
+                    config.step(value, velocity, 100.0, 1.0 / 60.0);
+                frames += 1;
+                assert!(frames < 600, "{config:?} did not settle in 10s");
+            }
+        }
+
+        // an underdamped spring overshoots its target
+        let config = SpringConfig::wobbly();
+        let (mut value, mut velocity) = (0.0, 0.0);
+        let mut max: f64 = 0.0;
+        for _ in 0..60 {
+            (value, velocity) = config.step(value, velocity, 100.0, 1.0 / 60.0);
+            max = max.max(value);
+        }
+        assert!(max > 100.0);
+    }
+}
@@ -0,0 +1,252 @@
+//! Announcing messages to screen reader users, with
+//! [ARIA live regions](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/ARIA_Live_Regions).
+//!
+//! Changes that are obvious to sighted users, like an item being added to a cart or a form
+//! being saved, are often not noticed by screen reader users unless they are announced. The
+//! [`Announcer`] reads a message aloud by placing it in a visually-hidden live region.
+//!
+//! Screen readers only watch live regions that were already on the page when it loaded, so
+//! [`<LiveAnnouncer/>`](LiveAnnouncer) should be rendered once near the root of the app. It is
+//! rendered on the server, so the regions exist before the app is hydrated. Announcements made
+//! without it still work, but the regions are then added when the first message is announced,
+//! and some screen readers miss that message.
+//!
+//! ```rust
+//! use leptos::{
+//!     announce::{use_announcer, LiveAnnouncer, Politeness},
+//!     prelude::*,
+//! };
+//!
+//! #[component]
+//! fn App() -> impl IntoView {
+//!     view! {
+//!         <LiveAnnouncer>
+//!             <AddToCart/>
+//!         </LiveAnnouncer>
+//!     }
+//! }
+//!
+//! #[component]
+//! fn AddToCart() -> impl IntoView {
+//!     let announcer = use_announcer();
+//!     view! {
+//!         <button on:click=move |_| {
+//!             announcer.announce("Added to cart", Politeness::Polite)
+//!         }>"Add to cart"</button>
+//!     }
+//! }
+//! ```
+
+use crate::{children::Children, component, prelude::*, IntoView};
+use leptos_dom::helpers::{
+    document, is_browser, request_animation_frame, set_timeout,
+};
+use std::{
+    sync::{Arc, Mutex, OnceLock},
+    time::Duration,
+};
+
+/// Styles that hide an element visually, while leaving it in the accessibility tree.
+pub(crate) const VISUALLY_HIDDEN: &str =
+    "position:absolute;width:1px;height:1px;padding:0;margin:-1px;\
+     overflow:hidden;clip:rect(0,0,0,0);white-space:nowrap;border:0";
+
+/// Messages announced within this time of each other are read together.
+const DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// How urgently a message is announced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Politeness {
+    /// The message is read once the screen reader has finished what it is reading. This should
+    /// be used for most messages.
+    #[default]
+    Polite,
+    /// The message interrupts whatever the screen reader is reading. This should only be used
+    /// for messages that need attention right away, like errors.
+    Assertive,
+}
+
+impl Politeness {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Politeness::Polite => "polite",
+            Politeness::Assertive => "assertive",
+        }
+    }
+
+    fn region_id(&self) -> &'static str {
+        match self {
+            Politeness::Polite => "leptos-announcer-polite",
+            Politeness::Assertive => "leptos-announcer-assertive",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Pending {
+    polite: Vec<String>,
+    assertive: Vec<String>,
+    scheduled: bool,
+}
+
+/// Announces messages to screen reader users, as described in the
+/// [module documentation](self).
+///
+/// This is provided as context by [`<LiveAnnouncer/>`](LiveAnnouncer), and can be accessed
+/// with [`use_announcer`].
+#[derive(Debug, Clone)]
+pub struct Announcer {
+    pending: Arc<Mutex<Pending>>,
+}
+
+impl Announcer {
+    /// The announcer shared by the whole app.
+    pub fn global() -> Self {
+        static GLOBAL: OnceLock<Announcer> = OnceLock::new();
+        GLOBAL
+            .get_or_init(|| Announcer {
+                pending: Default::default(),
+            })
+            .clone()
+    }
+
+    /// Announces the message to screen reader users.
+    ///
+    /// Messages that are announced in quick succession, for example when several items are
+    /// updated at once, are combined into one announcement, leaving out repeated messages. The
+    /// same message can be announced again later, and it will be read again.
+    ///
+    /// This does nothing on the server.
+    pub fn announce(&self, message: impl Into<String>, politeness: Politeness) {
+        if !is_browser() {
+            return;
+        }
+        let message = message.into();
+        let mut pending =
+            self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        let queue = match politeness {
+            Politeness::Polite => &mut pending.polite,
+            Politeness::Assertive => &mut pending.assertive,
+        };
+        if !queue.contains(&message) {
+            queue.push(message);
+        }
+        if !pending.scheduled {
+            pending.scheduled = true;
+            let this = self.clone();
+            set_timeout(move || this.flush(), DEBOUNCE);
+        }
+    }
+
+    fn flush(&self) {
+        let (polite, assertive) = {
+            let mut pending =
+                self.pending.lock().unwrap_or_else(|e| e.into_inner());
+            pending.scheduled = false;
+            (
+                std::mem::take(&mut pending.polite),
+                std::mem::take(&mut pending.assertive),
+            )
+        };
+        for (politeness, messages) in [
+            (Politeness::Polite, polite),
+            (Politeness::Assertive, assertive),
+        ] {
+            if !messages.is_empty() {
+                set_region_text(politeness, messages.join(" "));
+            }
+        }
+    }
+}
+
+fn set_region_text(politeness: Politeness, message: String) {
+    let document = document();
+    let region = match document.get_element_by_id(politeness.region_id()) {
+        Some(region) => region,
+        None => {
+            let Ok(region) = document.create_element("div") else {
+                return;
+            };
+            region.set_id(politeness.region_id());
+            _ = region.set_attribute("aria-live", politeness.as_str());
+            _ = region.set_attribute("aria-atomic", "true");
+            _ = region.set_attribute("style", VISUALLY_HIDDEN);
+            if let Some(body) = document.body() {
+                _ = body.append_child(&region);
+            }
+            region
+        }
+    };
+    // screen readers only announce changes, so the region is cleared first in case it already
+    // contains the same message
+    region.set_text_content(None);
+    request_animation_frame(move || region.set_text_content(Some(&message)));
+}
+
+/// Returns the [`Announcer`] provided by [`<LiveAnnouncer/>`](LiveAnnouncer), or the
+/// [global](Announcer::global) one if there is none.
+pub fn use_announcer() -> Announcer {
+    use_context().unwrap_or_else(Announcer::global)
+}
+
+/// Renders the live regions used by the [`Announcer`], and provides it to its children.
+///
+/// This should be rendered once, near the root of the app, so that the regions are part of
+/// the HTML rendered on the server. It can be used either around the rest of the app or on its
+/// own.
+#[component]
+pub fn LiveAnnouncer(
+    /// The rest of the app.
+    #[prop(optional)]
+    children: Option<Children>,
+) -> impl IntoView {
+    provide_context(Announcer::global());
+    let region = |politeness: Politeness| {
+        view! {
+            <div
+                id=politeness.region_id()
+                aria-live=politeness.as_str()
+                aria-atomic="true"
+                style=VISUALLY_HIDDEN
+            ></div>
+        }
+    };
+    view! {
+        {children.map(|children| children())}
+        {region(Politeness::Polite)}
+        {region(Politeness::Assertive)}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tachys::{html::element::HtmlElement, view::RenderHtml};
+
+    #[test]
+    fn renders_regions_on_the_server() {
+        Owner::new().with(|| {
+            let app: HtmlElement<_, _, _, Dom> = view! {
+                <main>
+                    <LiveAnnouncer/>
+                </main>
+            };
+            let html = app.to_html();
+            let region = |politeness: &str| {
+                format!(
+                    r#"<div id="leptos-announcer-{politeness}" aria-live="{politeness}" aria-atomic="true" style="{VISUALLY_HIDDEN};"></div>"#
+                )
+            };
+            assert_eq!(
+                html,
+                format!(
+                    "<main><!>{}{}</main>",
+                    region("polite"),
+                    region("assertive")
+                )
+            );
+            // announcing on the server does nothing
+            use_announcer().announce("Saved", Politeness::Polite);
+        });
+    }
+}
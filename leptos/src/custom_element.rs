@@ -0,0 +1,211 @@
+use crate::{mount, IntoView};
+use reactive_graph::{
+    signal::{ArcReadSignal, ArcRwSignal},
+    traits::Set,
+};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::HtmlElement;
+
+#[wasm_bindgen(inline_js = "
+    export function define_custom_element(tag, observed, connected, disconnected, changed) {
+        customElements.define(tag, class extends HTMLElement {
+            static get observedAttributes() {
+                return observed;
+            }
+            connectedCallback() {
+                if (this.__leptos_id === undefined) {
+                    this.__leptos_id = connected(this);
+                }
+            }
+            disconnectedCallback() {
+                // an element that is only being moved is reconnected in the same task
+                queueMicrotask(() => {
+                    if (!this.isConnected && this.__leptos_id !== undefined) {
+                        const id = this.__leptos_id;
+                        this.__leptos_id = undefined;
+                        disconnected(id);
+                    }
+                });
+            }
+            attributeChangedCallback(name, _old, value) {
+                changed(this.__leptos_id, name, value);
+            }
+        });
+    }
+")]
+extern "C" {
+    #[wasm_bindgen(js_name = define_custom_element)]
+    fn define_element_class(
+        tag: &str,
+        observed: Box<[JsValue]>,
+        connected: &Closure<dyn Fn(HtmlElement) -> u32>,
+        disconnected: &Closure<dyn Fn(u32)>,
+        changed: &Closure<dyn Fn(Option<u32>, String, Option<String>)>,
+    );
+}
+
+/// The attributes and host element of a custom element instance, passed to the component
+/// registered with [`define_custom_element`].
+#[derive(Debug, Clone)]
+pub struct CustomElementAttributes {
+    host: HtmlElement,
+    attributes: Vec<(&'static str, ArcRwSignal<Option<String>>)>,
+}
+
+impl CustomElementAttributes {
+    /// Returns a signal that tracks the current value of an observed attribute.
+    ///
+    /// Returns `None` if `name` was not one of the observed attributes passed to
+    /// [`define_custom_element`].
+    pub fn attribute(
+        &self,
+        name: &str,
+    ) -> Option<ArcReadSignal<Option<String>>> {
+        self.attributes
+            .iter()
+            .find(|(attr, _)| *attr == name)
+            .map(|(_, signal)| signal.read_only())
+    }
+
+    /// The custom element itself, whose shadow root the component is mounted into.
+    pub fn host(&self) -> &HtmlElement {
+        &self.host
+    }
+}
+
+struct Instance {
+    attributes: CustomElementAttributes,
+    _handle: Box<dyn Any>,
+}
+
+thread_local! {
+    static INSTANCES: RefCell<HashMap<u32, Instance>> = Default::default();
+    static NEXT_ID: Cell<u32> = const { Cell::new(0) };
+}
+
+/// Registers a [custom element](https://developer.mozilla.org/en-US/docs/Web/API/Web_components/Using_custom_elements)
+/// that renders a Leptos view into its shadow root.
+///
+/// Each time an element with the given `tag` is connected to the document, an open shadow root is
+/// attached to it and the view returned by `component` is mounted into it. Every attribute in
+/// `observed_attributes` is exposed to the component as a signal through
+/// [`CustomElementAttributes::attribute`], and updated whenever the attribute changes. The view
+/// is unmounted, and its reactive owner cleaned up, when the element is removed from the document.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos::custom_element::define_custom_element;
+/// # if false {
+/// define_custom_element("hello-name", &["name"], |attrs| {
+///     let name = attrs.attribute("name").unwrap();
+///     view! { <p>"Hello, " {move || name.get().unwrap_or_default()} "!"</p> }
+/// });
+/// # }
+/// ```
+///
+/// ## Panics
+/// Panics if called outside the browser, or if `tag` is not a valid custom element name or has
+/// already been defined.
+pub fn define_custom_element<F, N>(
+    tag: &str,
+    observed_attributes: &[&'static str],
+    component: F,
+) where
+    F: Fn(CustomElementAttributes) -> N + 'static,
+    N: IntoView + 'static,
+{
+    let observed = observed_attributes.to_vec();
+    let component = Rc::new(component);
+
+    let connected = Closure::<dyn Fn(HtmlElement) -> u32>::new({
+        let observed = observed.clone();
+        move |host: HtmlElement| {
+            let id = NEXT_ID.get();
+            NEXT_ID.set(id.wrapping_add(1));
+
+            let attributes = CustomElementAttributes {
+                attributes: observed
+                    .iter()
+                    .map(|name| {
+                        (*name, ArcRwSignal::new(host.get_attribute(name)))
+                    })
+                    .collect(),
+                host: host.clone(),
+            };
+            let render_root = host
+                .shadow_root()
+                .or_else(|| {
+                    host.attach_shadow(&web_sys::ShadowRootInit::new(
+                        web_sys::ShadowRootMode::Open,
+                    ))
+                    .ok()
+                })
+                .expect("could not attach a shadow root to custom element");
+
+            let handle = mount::mount_to(render_root.unchecked_into(), {
+                let component = Rc::clone(&component);
+                let attributes = attributes.clone();
+                move || component(attributes)
+            });
+            INSTANCES.with_borrow_mut(|instances| {
+                instances.insert(
+                    id,
+                    Instance {
+                        attributes,
+                        _handle: Box::new(handle),
+                    },
+                )
+            });
+            id
+        }
+    });
+
+    let disconnected = Closure::<dyn Fn(u32)>::new(|id| {
+        // dropped outside the borrow, as unmounting may disconnect nested custom elements
+        let instance =
+            INSTANCES.with_borrow_mut(|instances| instances.remove(&id));
+        drop(instance);
+    });
+
+    let changed = Closure::<dyn Fn(Option<u32>, String, Option<String>)>::new(
+        |id: Option<u32>, name: String, value: Option<String>| {
+            let signal = id.and_then(|id| {
+                INSTANCES.with_borrow(|instances| {
+                    instances.get(&id).and_then(|instance| {
+                        instance
+                            .attributes
+                            .attributes
+                            .iter()
+                            .find(|(attr, _)| *attr == name)
+                            .map(|(_, signal)| signal.clone())
+                    })
+                })
+            });
+            if let Some(signal) = signal {
+                signal.set(value);
+            }
+        },
+    );
+
+    define_element_class(
+        tag,
+        observed
+            .iter()
+            .map(|name| JsValue::from_str(name))
+            .collect(),
+        &connected,
+        &disconnected,
+        &changed,
+    );
+
+    // the element class lives as long as the page, so its callbacks do too
+    connected.forget();
+    disconnected.forget();
+    changed.forget();
+}
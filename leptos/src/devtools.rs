@@ -0,0 +1,341 @@
+//! A bridge between the reactive graph and a browser devtools extension.
+//!
+//! Calling [`init`] in the browser forwards every change to the reactive graph to the page as a
+//! [`postMessage`](https://developer.mozilla.org/en-US/docs/Web/API/Window/postMessage), where
+//! a content script can pass it on to an extension. Each message is numbered, so that an
+//! extension can record them and step backwards and forwards through the history of the graph.
+//!
+//! # Protocol
+//! Every message posted by the bridge is an object of this form:
+//! ```json
+//! {
+//!   "source": "leptos-devtools",
+//!   "version": 1,
+//!   "seq": 42,
+//!   "timestamp": 1718000000000.0,
+//!   "event": { "type": "node_updated", "id": 1234 }
+//! }
+//! ```
+//! `seq` counts up from zero, and `timestamp` is in milliseconds since the Unix epoch. `event`
+//! is one of:
+//!
+//! | `type` | Other fields | Sent when |
+//! |---|---|---|
+//! | `node_created` | `id`, `kind`, `defined_at` | A signal, trigger, memo, async derived or effect is created. |
+//! | `node_updated` | `id` | A node notifies its subscribers that it has changed. |
+//! | `node_ran` | `id`, `kind` | A memo, async derived or effect starts running. |
+//! | `owner_disposed` | `owner` | An owner is cleaned up, disposing of everything it owns. |
+//! | `snapshot` | `nodes` | In reply to a snapshot request. |
+//! | `leaks` | `nodes`, `owners`, `counts` | In reply to a leak report request. |
+//!
+//! `kind` is one of `"signal"`, `"trigger"`, `"memo"`, `"async_derived"` or `"effect"`, and
+//! `defined_at` is a `"file:line:column"` string, or `null` in release builds. Each entry in
+//! `nodes` has the fields `id`, `kind`, `defined_at`, `last_updated` (a timestamp or `null`)
+//! and `sources`, the IDs of the nodes it read from the last time it ran.
+//!
+//! An extension that connects after the page has loaded can ask for the current state of the
+//! graph by posting this message to the page:
+//! ```json
+//! { "source": "leptos-devtools-extension", "type": "request_snapshot" }
+//! ```
+//!
+//! Similarly, it can ask for the nodes and owners that have outlived their owners, as found by
+//! [`leaks`](reactive_graph::introspection::leaks), by posting a message with the type
+//! `request_leaks`. Each entry in the `nodes` of the reply has the fields `id`, `kind`,
+//! `defined_at` and `components`, the names of the components it was created in, and each
+//! entry in `owners` has the fields `id` and `components`. `counts` has the fields
+//! `nodes_created`, `live_nodes`, `owners_created` and `live_owners`. To log the same report to
+//! the console instead, call [`warn_about_leaks`].
+//!
+//! IDs are the addresses of nodes and owners, so they may be reused once a node is dropped.
+
+use reactive_graph::introspection::{
+    add_listener, leaks, nodes, GraphEvent, LeakReport, NodeId, NodeInfo,
+    NodeKind,
+};
+use serde_json::{json, Value};
+use std::{
+    panic::Location,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+};
+
+/// The `source` field of every message posted by the bridge.
+pub const SOURCE: &str = "leptos-devtools";
+
+/// The `source` field of messages the bridge accepts from an extension.
+pub const EXTENSION_SOURCE: &str = "leptos-devtools-extension";
+
+/// The version of the protocol, which is increased whenever it changes incompatibly.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Starts posting reactive graph events to the page, as described in the
+/// [module documentation](self).
+///
+/// This should be called once, before the app is mounted, so that the extension sees every node
+/// being created. Calling it again does nothing, as does calling it outside the browser.
+///
+/// ```rust,no_run
+/// # use leptos::prelude::*;
+/// # #[component] fn App() -> impl IntoView {}
+/// leptos::devtools::init();
+/// mount_to_body(App);
+/// ```
+pub fn init() {
+    static INITIALIZED: AtomicBool = AtomicBool::new(false);
+    if !cfg!(target_arch = "wasm32")
+        || INITIALIZED.swap(true, Ordering::Relaxed)
+    {
+        return;
+    }
+
+    add_listener(|event| post(event_to_json(event)));
+    listen_for_requests();
+}
+
+/// Logs a warning to the console that lists any reactive nodes and owners that are still alive
+/// after their owner has been cleaned up, which usually means that something is keeping them in
+/// memory. Returns `false` if there are none, in which case nothing is logged.
+///
+/// As nodes may be dropped a little after the part of the UI that owns them is removed, this is
+/// best called some time after navigating away from a page, or from a button in a debug panel.
+pub fn warn_about_leaks() -> bool {
+    let report = leaks();
+    if report.is_empty() {
+        return false;
+    }
+    crate::logging::warn!("possible reactive leaks: {report}");
+    true
+}
+
+fn post(event: Value) {
+    static SEQ: AtomicU64 = AtomicU64::new(0);
+    let message = json!({
+        "source": SOURCE,
+        "version": PROTOCOL_VERSION,
+        "seq": SEQ.fetch_add(1, Ordering::Relaxed),
+        "timestamp": js_sys::Date::now(),
+        "event": event,
+    });
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    // messages are posted as objects, rather than strings, so that they can be inspected
+    // directly in the console
+    if let Ok(message) = js_sys::JSON::parse(&message.to_string()) {
+        _ = window.post_message(&message, "*");
+    }
+}
+
+fn listen_for_requests() {
+    use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let on_message = Closure::<dyn Fn(web_sys::MessageEvent)>::new(
+        |ev: web_sys::MessageEvent| {
+            let data = ev.data();
+            let field = |name: &str| {
+                js_sys::Reflect::get(&data, &JsValue::from_str(name))
+                    .ok()
+                    .and_then(|value| value.as_string())
+            };
+            if field("source").as_deref() != Some(EXTENSION_SOURCE) {
+                return;
+            }
+            match field("type").as_deref() {
+                Some("request_snapshot") => post(snapshot_to_json(nodes())),
+                Some("request_leaks") => post(leaks_to_json(leaks())),
+                _ => {}
+            }
+        },
+    );
+    _ = window.add_event_listener_with_callback(
+        "message",
+        on_message.as_ref().unchecked_ref(),
+    );
+    // the bridge listens for as long as the page is open
+    on_message.forget();
+}
+
+fn event_to_json(event: &GraphEvent) -> Value {
+    match event {
+        GraphEvent::NodeCreated {
+            id,
+            kind,
+            defined_at,
+        } => json!({
+            "type": "node_created",
+            "id": id.0,
+            "kind": kind_name(*kind),
+            "defined_at": location(*defined_at),
+        }),
+        GraphEvent::NodeUpdated { id } => json!({
+            "type": "node_updated",
+            "id": id.0,
+        }),
+        GraphEvent::NodeRan { id, kind } => json!({
+            "type": "node_ran",
+            "id": id.0,
+            "kind": kind_name(*kind),
+        }),
+        GraphEvent::OwnerDisposed { owner } => json!({
+            "type": "owner_disposed",
+            "owner": owner,
+        }),
+    }
+}
+
+fn snapshot_to_json(nodes: Vec<NodeInfo>) -> Value {
+    let nodes = nodes
+        .into_iter()
+        .map(|node| {
+            json!({
+                "id": node.id.0,
+                "kind": kind_name(node.kind),
+                "defined_at": location(node.defined_at),
+                "last_updated": node.last_updated.and_then(|time| {
+                    time.duration_since(web_time::SystemTime::UNIX_EPOCH)
+                        .ok()
+                        .map(|elapsed| elapsed.as_secs_f64() * 1000.0)
+                }),
+                "sources": node.sources.iter().map(|NodeId(id)| id).collect::<Vec<_>>(),
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "type": "snapshot",
+        "nodes": nodes,
+    })
+}
+
+fn leaks_to_json(report: LeakReport) -> Value {
+    let nodes = report
+        .nodes
+        .iter()
+        .map(|node| {
+            json!({
+                "id": node.id.0,
+                "kind": kind_name(node.kind),
+                "defined_at": location(node.defined_at),
+                "components": &*node.component_stack,
+            })
+        })
+        .collect::<Vec<_>>();
+    let owners = report
+        .owners
+        .iter()
+        .map(|owner| {
+            json!({
+                "id": owner.id,
+                "components": &*owner.component_stack,
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "type": "leaks",
+        "nodes": nodes,
+        "owners": owners,
+        "counts": {
+            "nodes_created": report.nodes_created,
+            "live_nodes": report.live_nodes,
+            "owners_created": report.owners_created,
+            "live_owners": report.live_owners,
+        },
+    })
+}
+
+fn kind_name(kind: NodeKind) -> &'static str {
+    match kind {
+        NodeKind::Signal => "signal",
+        NodeKind::Trigger => "trigger",
+        NodeKind::Memo => "memo",
+        NodeKind::AsyncDerived => "async_derived",
+        NodeKind::Effect => "effect",
+    }
+}
+
+fn location(location: Option<&'static Location<'static>>) -> Option<String> {
+    location.map(ToString::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reactive_graph::introspection::{LeakedNode, LeakedOwner};
+    use std::sync::Arc;
+
+    #[test]
+    fn encodes_events() {
+        assert_eq!(
+            event_to_json(&GraphEvent::NodeRan {
+                id: NodeId(7),
+                kind: NodeKind::AsyncDerived,
+            }),
+            json!({ "type": "node_ran", "id": 7, "kind": "async_derived" })
+        );
+        assert_eq!(
+            event_to_json(&GraphEvent::OwnerDisposed { owner: 3 }),
+            json!({ "type": "owner_disposed", "owner": 3 })
+        );
+        let snapshot = snapshot_to_json(vec![NodeInfo {
+            id: NodeId(1),
+            kind: NodeKind::Memo,
+            defined_at: None,
+            last_updated: None,
+            sources: vec![NodeId(2)],
+        }]);
+        assert_eq!(
+            snapshot,
+            json!({
+                "type": "snapshot",
+                "nodes": [{
+                    "id": 1,
+                    "kind": "memo",
+                    "defined_at": null,
+                    "last_updated": null,
+                    "sources": [2],
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn encodes_leaks() {
+        let report = LeakReport {
+            nodes_created: 4,
+            live_nodes: 2,
+            owners_created: 2,
+            live_owners: 1,
+            nodes: vec![LeakedNode {
+                id: NodeId(5),
+                kind: NodeKind::Signal,
+                defined_at: None,
+                component_stack: Arc::from(["App", "Row"]),
+            }],
+            owners: vec![LeakedOwner {
+                id: 9,
+                component_stack: Arc::from([]),
+            }],
+        };
+        assert_eq!(
+            leaks_to_json(report),
+            json!({
+                "type": "leaks",
+                "nodes": [{
+                    "id": 5,
+                    "kind": "signal",
+                    "defined_at": null,
+                    "components": ["App", "Row"],
+                }],
+                "owners": [{ "id": 9, "components": [] }],
+                "counts": {
+                    "nodes_created": 4,
+                    "live_nodes": 2,
+                    "owners_created": 2,
+                    "live_owners": 1,
+                },
+            })
+        );
+    }
+}
@@ -0,0 +1,275 @@
+//! Support for right-to-left languages.
+//!
+//! The [`Direction`] of the text is provided via context with [`provide_direction`]. If it has
+//! not been provided, but there is an [`I18n`] in context, it follows the locale, so that
+//! switching to Arabic or Hebrew switches the page to right-to-left. `<Dir/>` in `leptos_meta`
+//! provides it and sets the `dir` attribute of the `<html>` element, during server rendering
+//! and in the browser.
+//!
+//! Most styles should use [CSS logical properties](https://developer.mozilla.org/en-US/docs/Web/CSS/CSS_logical_properties_and_values),
+//! like `margin-inline-start`, which follow the direction of the text on their own. For cases
+//! where that is not enough, such as utility classes that only exist for one side, or values
+//! like `float: inline-start` that not every browser supports, [`logical_style`] and
+//! [`directional_class`] switch between the two sides as the direction changes.
+
+use crate::{
+    context::{provide_context, use_context},
+    i18n::{primary_subtag, I18n},
+};
+use reactive_graph::{
+    traits::{Get, With},
+    wrappers::read::Signal,
+};
+use std::{borrow::Cow, sync::Arc};
+
+/// The direction of the text of a page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Direction {
+    /// Left to right, as in English.
+    #[default]
+    Ltr,
+    /// Right to left, as in Arabic or Hebrew.
+    Rtl,
+}
+
+impl Direction {
+    /// The value of the `dir` attribute for the direction.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Direction::Ltr => "ltr",
+            Direction::Rtl => "rtl",
+        }
+    }
+
+    /// Whether the direction is right to left.
+    pub fn is_rtl(&self) -> bool {
+        matches!(self, Direction::Rtl)
+    }
+
+    /// The direction in which a locale is written, like [`Direction::Rtl`] for `ar` or `he-IL`.
+    ///
+    /// A script subtag takes precedence over the language, so `az-Arab` is right to left.
+    pub fn of_locale(locale: &str) -> Self {
+        let mut subtags = locale.split(['-', '_']).skip(1);
+        if let Some(script) = subtags.find(|subtag| subtag.len() == 4) {
+            return match script.to_ascii_lowercase().as_str() {
+                "arab" | "hebr" | "thaa" | "syrc" | "nkoo" | "adlm"
+                | "rohg" => Direction::Rtl,
+                _ => Direction::Ltr,
+            };
+        }
+        match primary_subtag(locale).to_ascii_lowercase().as_str() {
+            "ar" | "arc" | "ckb" | "dv" | "fa" | "he" | "iw" | "ks" | "ps"
+            | "sd" | "syr" | "ug" | "ur" | "yi" => Direction::Rtl,
+            _ => Direction::Ltr,
+        }
+    }
+
+    /// The physical side for the start of a line, `left` or `right`.
+    pub fn start(&self) -> &'static str {
+        match self {
+            Direction::Ltr => "left",
+            Direction::Rtl => "right",
+        }
+    }
+
+    /// The physical side for the end of a line, `right` or `left`.
+    pub fn end(&self) -> &'static str {
+        match self {
+            Direction::Ltr => "right",
+            Direction::Rtl => "left",
+        }
+    }
+
+    /// Maps a logical CSS property to the physical property for this direction, like
+    /// `margin-inline-start` to `margin-right` for [`Direction::Rtl`]. Other properties are
+    /// returned as they are.
+    pub fn physical_property<'a>(&self, property: &'a str) -> Cow<'a, str> {
+        let (start, end) = (self.start(), self.end());
+        match property {
+            "inset-inline-start" => return start.into(),
+            "inset-inline-end" => return end.into(),
+            "border-start-start-radius" => {
+                return format!("border-top-{start}-radius").into()
+            }
+            "border-start-end-radius" => {
+                return format!("border-top-{end}-radius").into()
+            }
+            "border-end-start-radius" => {
+                return format!("border-bottom-{start}-radius").into()
+            }
+            "border-end-end-radius" => {
+                return format!("border-bottom-{end}-radius").into()
+            }
+            _ => {}
+        }
+        if property.contains("-inline-start") {
+            property
+                .replacen("-inline-start", &format!("-{start}"), 1)
+                .into()
+        } else if property.contains("-inline-end") {
+            property
+                .replacen("-inline-end", &format!("-{end}"), 1)
+                .into()
+        } else {
+            property.into()
+        }
+    }
+
+    /// Maps a logical value of `text-align`, `float` or `clear`, like `start` or
+    /// `inline-end`, to the physical side for this direction. Other values are returned as they
+    /// are.
+    pub fn physical_value<'a>(
+        &self,
+        property: &str,
+        value: &'a str,
+    ) -> Cow<'a, str> {
+        if !matches!(property, "text-align" | "float" | "clear") {
+            return value.into();
+        }
+        match value {
+            "start" | "inline-start" => self.start().into(),
+            "end" | "inline-end" => self.end().into(),
+            _ => value.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct DirectionContext(Signal<Direction>);
+
+/// Provides the direction of the text via context, and returns it.
+pub fn provide_direction(
+    direction: impl Into<Signal<Direction>>,
+) -> Signal<Direction> {
+    let direction = direction.into();
+    provide_context(DirectionContext(direction));
+    direction
+}
+
+/// Returns the direction of the text.
+///
+/// This is the direction provided with [`provide_direction`], or else the direction of the
+/// locale of the [`I18n`] in context, or else [`Direction::Ltr`].
+pub fn use_direction() -> Signal<Direction> {
+    if let Some(DirectionContext(direction)) = use_context() {
+        return direction;
+    }
+    match use_context::<I18n>() {
+        Some(i18n) => {
+            let locale = i18n.locale();
+            Signal::derive(move || locale.with(|l| Direction::of_locale(l)))
+        }
+        None => Signal::from(Direction::Ltr),
+    }
+}
+
+/// A style that sets a logical property, or a property with a logical value, as the physical
+/// property and value for the current direction, updating them when the direction changes.
+///
+/// ```rust
+/// use leptos::{direction::logical_style, prelude::*};
+///
+/// #[component]
+/// fn Badge() -> impl IntoView {
+///     view! {
+///         <span style=logical_style("float", "inline-end")>"New"</span>
+///         <p style=logical_style("padding-inline-start", "1rem")>"Details"</p>
+///     }
+/// }
+/// ```
+pub fn logical_style(
+    property: &'static str,
+    value: impl Into<Arc<str>>,
+) -> impl Fn() -> (Arc<str>, Arc<str>) + Send + Sync + 'static {
+    let value = value.into();
+    let direction = use_direction();
+    move || {
+        let direction = direction.get();
+        let name = direction.physical_property(property);
+        let value = direction.physical_value(property, &value);
+        (Arc::from(name.as_ref()), Arc::from(value.as_ref()))
+    }
+}
+
+/// A class that is `ltr` when the text is left to right and `rtl` when it is right to left,
+/// for CSS that has separate classes for each side.
+///
+/// ```rust
+/// use leptos::{direction::directional_class, prelude::*};
+///
+/// #[component]
+/// fn Icon() -> impl IntoView {
+///     view! { <span class=directional_class("ml-2", "mr-2")>"→"</span> }
+/// }
+/// ```
+pub fn directional_class(
+    ltr: &'static str,
+    rtl: &'static str,
+) -> impl Fn() -> &'static str + Send + Sync + 'static {
+    let direction = use_direction();
+    move || if direction.get().is_rtl() { rtl } else { ltr }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::{provide_i18n, Translations};
+    use reactive_graph::owner::Owner;
+
+    #[test]
+    fn finds_the_direction_of_locales() {
+        assert_eq!(Direction::of_locale("en-US"), Direction::Ltr);
+        assert_eq!(Direction::of_locale("ar"), Direction::Rtl);
+        assert_eq!(Direction::of_locale("he_IL"), Direction::Rtl);
+        assert_eq!(Direction::of_locale("az-Arab"), Direction::Rtl);
+        assert_eq!(Direction::of_locale("ku-Latn-TR"), Direction::Ltr);
+    }
+
+    #[test]
+    fn maps_logical_properties() {
+        let rtl = Direction::Rtl;
+        assert_eq!(
+            rtl.physical_property("margin-inline-start"),
+            "margin-right"
+        );
+        assert_eq!(
+            rtl.physical_property("border-inline-end-width"),
+            "border-left-width"
+        );
+        assert_eq!(rtl.physical_property("inset-inline-start"), "right");
+        assert_eq!(
+            Direction::Ltr.physical_property("border-start-end-radius"),
+            "border-top-right-radius"
+        );
+        assert_eq!(
+            rtl.physical_property("margin-block-start"),
+            "margin-block-start"
+        );
+        assert_eq!(rtl.physical_value("text-align", "start"), "right");
+        assert_eq!(rtl.physical_value("margin", "start"), "start");
+    }
+
+    #[test]
+    fn follows_the_locale() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let translations =
+                Translations::from_static(&[("en", "{}"), ("ar", "{}")]);
+            let i18n = provide_i18n(translations, "en");
+            let style = logical_style("margin-inline-start", "1rem");
+            let class = directional_class("ml-2", "mr-2");
+            assert_eq!(use_direction().get(), Direction::Ltr);
+            assert_eq!(style().0.as_ref(), "margin-left");
+            assert_eq!(class(), "ml-2");
+
+            i18n.set_locale("ar");
+            assert_eq!(use_direction().get(), Direction::Rtl);
+            assert_eq!(style().0.as_ref(), "margin-right");
+            assert_eq!(class(), "mr-2");
+
+            provide_direction(Direction::Ltr);
+            assert_eq!(use_direction().get(), Direction::Ltr);
+        });
+    }
+}
@@ -46,6 +46,11 @@ use throw_error::{Error, ErrorHook, ErrorId};
 /// }
 /// ```
 ///
+/// In debug builds, each error that is caught remembers the components it occurred in, which
+/// can be shown in the fallback or in logs with
+/// [`Error::component_trace`](throw_error::Error::component_trace), like
+/// `<PostList> → <PostRow>`.
+///
 /// ## Beginner's Tip: ErrorBoundary Requires Your Error To Implement std::error::Error.
 /// `ErrorBoundary` requires your `Result<T,E>` to implement [IntoView](https://docs.rs/leptos/latest/leptos/trait.IntoView.html).
 /// `Result<T,E>` only implements `IntoView` if `E` implements [std::error::Error](https://doc.rust-lang.org/std/error/trait.Error.html).
@@ -452,6 +457,11 @@ impl ErrorBoundaryErrorHook {
 
 impl ErrorHook for ErrorBoundaryErrorHook {
     fn throw(&self, error: Error) -> ErrorId {
+        // remember the components the error was thrown in, for fallbacks and logs
+        #[cfg(debug_assertions)]
+        let error = error
+            .with_component_stack(reactive_graph::owner::component_stack());
+
         // generate a unique ID
         let key: ErrorId = Owner::current_shared_context()
             .map(|sc| sc.next_id())
@@ -464,6 +474,13 @@ impl ErrorHook for ErrorBoundaryErrorHook {
             sc.register_error(self.id.clone(), key.clone(), error.clone());
         }
 
+        #[cfg(debug_assertions)]
+        crate::error_overlay::show_error(
+            &error.to_string(),
+            None,
+            error.component_trace().as_deref(),
+        );
+
         // add it to the reactive map of errors
         self.errors.update(|map| {
             map.insert(key.clone(), error);
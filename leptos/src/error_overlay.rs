@@ -0,0 +1,144 @@
+//! A full-screen overlay that shows errors in development builds.
+//!
+//! In debug builds in the browser, panics and errors thrown to an
+//! [`ErrorBoundary`](crate::error::ErrorBoundary) are shown on top of the page, with their
+//! message and, if known, the source location and component stack, rather than only being
+//! logged to the console. Errors are added to the same overlay until it is dismissed, with its
+//! button or the Escape key.
+//!
+//! The panic hook is installed when the app is mounted or hydrated. It calls the hook that was
+//! set before it, so a hook like `console_error_panic_hook` still logs the panic.
+//!
+//! In release builds, and outside the browser, nothing is shown.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use wasm_bindgen::{prelude::Closure, JsCast};
+use web_sys::{Document, Element, Event, HtmlElement, KeyboardEvent};
+
+const OVERLAY_ID: &str = "leptos-error-overlay";
+
+const OVERLAY_STYLE: &str = "position:fixed;inset:0;z-index:2147483647;\
+                             overflow:auto;padding:2rem;box-sizing:border-box;\
+                             background:rgba(24,24,27,0.95);color:#fafafa;\
+                             font:14px/1.5 ui-monospace,monospace;";
+
+const BUTTON_STYLE: &str = "float:right;padding:0.25rem 0.75rem;\
+                            font:inherit;cursor:pointer;";
+
+const STACK_STYLE: &str = "margin:0.5rem 0 0;white-space:pre-wrap;\
+                           color:#a1a1aa;";
+
+/// Shows an error in the overlay, below any errors that are already shown.
+///
+/// `location` is the place in the source the error came from, like `src/app.rs:12:5`, and
+/// `component_stack` lists the components it occurred in.
+pub fn show_error(
+    message: &str,
+    location: Option<&str>,
+    component_stack: Option<&str>,
+) {
+    if !cfg!(all(debug_assertions, target_arch = "wasm32")) {
+        return;
+    }
+    let Some(document) = web_sys::window().and_then(|w| w.document()) else {
+        return;
+    };
+    let list = match document.get_element_by_id(OVERLAY_ID) {
+        Some(overlay) => overlay.query_selector("ol").ok().flatten(),
+        None => create_overlay(&document),
+    };
+    let Some(list) = list else {
+        return;
+    };
+
+    let Ok(entry) = document.create_element("li") else {
+        return;
+    };
+    if let Ok(el) = document.create_element("strong") {
+        el.set_text_content(Some(message));
+        _ = entry.append_child(&el);
+    }
+    if let Some(location) = location {
+        if let Ok(el) = document.create_element("div") {
+            el.set_text_content(Some(&format!("at {location}")));
+            _ = entry.append_child(&el);
+        }
+    }
+    if let Some(component_stack) = component_stack {
+        if let Ok(el) = document.create_element("pre") {
+            el.set_text_content(Some(component_stack));
+            _ = el.set_attribute("style", STACK_STYLE);
+            _ = entry.append_child(&el);
+        }
+    }
+    _ = list.append_child(&entry);
+}
+
+/// Shows panics in the overlay, after calling the panic hook that is currently set.
+///
+/// This is called when the app is mounted. Calling it again does nothing.
+pub fn install_panic_hook() {
+    static INSTALLED: AtomicBool = AtomicBool::new(false);
+    if !cfg!(all(debug_assertions, target_arch = "wasm32"))
+        || INSTALLED.swap(true, Ordering::Relaxed)
+    {
+        return;
+    }
+
+    let prev = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        prev(info);
+        let payload = info.payload();
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(ToString::to_string)
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| String::from("Box<dyn Any>"));
+        let location = info.location().map(ToString::to_string);
+        show_error(&format!("panicked: {message}"), location.as_deref(), None);
+    }));
+}
+
+fn create_overlay(document: &Document) -> Option<Element> {
+    let body = document.body()?;
+    let overlay = document.create_element("div").ok()?;
+    overlay.set_id(OVERLAY_ID);
+    _ = overlay.set_attribute("role", "alertdialog");
+    _ = overlay.set_attribute("tabindex", "-1");
+    _ = overlay.set_attribute("style", OVERLAY_STYLE);
+
+    let close = document.create_element("button").ok()?;
+    close.set_text_content(Some("Dismiss (Esc)"));
+    _ = close.set_attribute("style", BUTTON_STYLE);
+    let heading = document.create_element("h2").ok()?;
+    heading.set_text_content(Some("Errors"));
+    let list = document.create_element("ol").ok()?;
+    _ = overlay.append_child(&close);
+    _ = overlay.append_child(&heading);
+    _ = overlay.append_child(&list);
+
+    let dismiss = Closure::<dyn Fn(Event)>::new({
+        let overlay = overlay.clone();
+        move |ev: Event| {
+            let escape =
+                ev.dyn_ref::<KeyboardEvent>().map(|ev| ev.key() == "Escape");
+            // clicks on the button dismiss it, as does pressing Escape
+            if escape != Some(false) {
+                overlay.remove();
+            }
+        }
+    });
+    _ = close.add_event_listener_with_callback(
+        "click",
+        dismiss.as_ref().unchecked_ref(),
+    );
+    _ = overlay.add_event_listener_with_callback(
+        "keydown",
+        dismiss.as_ref().unchecked_ref(),
+    );
+    dismiss.forget();
+
+    _ = body.append_child(&overlay);
+    _ = overlay.unchecked_ref::<HtmlElement>().focus();
+    Some(list)
+}
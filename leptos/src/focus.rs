@@ -0,0 +1,346 @@
+use crate::announce::{Announcer, Politeness};
+use leptos_dom::helpers::document;
+use reactive_graph::{
+    effect::Effect,
+    owner::Owner,
+    signal::RwSignal,
+    traits::{Get, GetUntracked, Set},
+    wrappers::read::Signal,
+};
+use send_wrapper::SendWrapper;
+use tachys::{html::element::ElementType, reactive_graph::node_ref::NodeRef};
+use wasm_bindgen::{prelude::Closure, JsCast};
+use web_sys::{Element, Event, HtmlElement, KeyboardEvent};
+
+/// The elements that can be focused with the Tab key.
+const FOCUSABLE: &str = "a[href], area[href], button:not([disabled]), \
+                         input:not([disabled]):not([type=\"hidden\"]), \
+                         select:not([disabled]), textarea:not([disabled]), \
+                         iframe, [contenteditable], \
+                         [tabindex]:not([tabindex=\"-1\"])";
+
+fn query_all(root: &Element, selector: &str) -> Vec<HtmlElement> {
+    let Ok(list) = root.query_selector_all(selector) else {
+        return Vec::new();
+    };
+    (0..list.length())
+        .filter_map(|idx| list.item(idx))
+        .filter_map(|node| node.dyn_into::<HtmlElement>().ok())
+        .collect()
+}
+
+fn active_element() -> Option<Element> {
+    document().active_element()
+}
+
+/// Adds an event listener to `el` that is removed when the current owner is cleaned up.
+fn on_cleanup_remove(
+    el: Element,
+    name: &'static str,
+    listener: Closure<dyn Fn(Event)>,
+) {
+    _ = el.add_event_listener_with_callback(
+        name,
+        listener.as_ref().unchecked_ref(),
+    );
+    let listener = SendWrapper::new((el, listener));
+    Owner::on_cleanup(move || {
+        let (el, listener) = listener.take();
+        _ = el.remove_event_listener_with_callback(
+            name,
+            listener.as_ref().unchecked_ref(),
+        );
+    });
+}
+
+/// Keeps keyboard focus inside the element loaded into `node_ref`, for as long as it is mounted,
+/// as a modal dialog or menu should.
+///
+/// When the element is mounted, the first focusable element inside it is focused, unless focus
+/// is already inside it. Pressing Tab on the last focusable element moves focus back to the
+/// first, and Shift+Tab on the first moves it to the last. When the element is removed, focus
+/// returns to the element that had it before.
+///
+/// ```rust
+/// use leptos::{focus::use_focus_trap, html, prelude::*};
+///
+/// #[component]
+/// fn Dialog(on_close: Callback<()>) -> impl IntoView {
+///     let dialog = NodeRef::<html::Div>::new();
+///     use_focus_trap(dialog);
+///     view! {
+///         <div node_ref=dialog role="dialog" aria-modal="true">
+///             <input/>
+///             <button on:click=move |_| on_close.call(())>"Close"</button>
+///         </div>
+///     }
+/// }
+/// ```
+pub fn use_focus_trap<E>(node_ref: NodeRef<E>)
+where
+    E: ElementType + 'static,
+    E::Output: JsCast + Clone + 'static,
+{
+    if !cfg!(target_arch = "wasm32") {
+        return;
+    }
+    // effects run after the view has been mounted, and again if another element is loaded
+    Effect::new(move |_| {
+        let Some(el) = node_ref.get() else {
+            return;
+        };
+        let el = el.unchecked_into::<Element>();
+        let previous = active_element();
+        let focus_is_inside = previous
+            .as_ref()
+            .is_some_and(|active| el.contains(Some(active)));
+        if !focus_is_inside {
+            match query_all(&el, FOCUSABLE).first() {
+                Some(first) => _ = first.focus(),
+                // make sure that focus leaves whatever was behind the trap
+                None => {
+                    if !el.has_attribute("tabindex") {
+                        _ = el.set_attribute("tabindex", "-1");
+                    }
+                    _ = el.unchecked_ref::<HtmlElement>().focus();
+                }
+            }
+        }
+
+        let on_keydown = Closure::<dyn Fn(Event)>::new({
+            let el = el.clone();
+            move |ev: Event| {
+                let Some(ev) = ev.dyn_ref::<KeyboardEvent>() else {
+                    return;
+                };
+                if ev.key() != "Tab" {
+                    return;
+                }
+                let focusable = query_all(&el, FOCUSABLE);
+                let (Some(first), Some(last)) =
+                    (focusable.first(), focusable.last())
+                else {
+                    ev.prevent_default();
+                    return;
+                };
+                let active = active_element();
+                let at = |target: &HtmlElement| {
+                    active.as_ref().is_some_and(|active| {
+                        active == target.unchecked_ref::<Element>()
+                    })
+                };
+                if ev.shift_key() && (at(first) || active.as_ref() == Some(&el))
+                {
+                    ev.prevent_default();
+                    _ = last.focus();
+                } else if !ev.shift_key() && at(last) {
+                    ev.prevent_default();
+                    _ = first.focus();
+                }
+            }
+        });
+        on_cleanup_remove(el, "keydown", on_keydown);
+
+        // return focus to where it was when the trap is removed
+        let previous = SendWrapper::new(previous);
+        Owner::on_cleanup(move || {
+            if let Some(previous) = previous
+                .take()
+                .and_then(|el| el.dyn_into::<HtmlElement>().ok())
+            {
+                if previous.is_connected() {
+                    _ = previous.focus();
+                }
+            }
+        });
+    });
+}
+
+/// The arrow keys that move focus with [`use_roving_tabindex`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Orientation {
+    /// The left and right arrow keys, as in a toolbar or a tab list.
+    #[default]
+    Horizontal,
+    /// The up and down arrow keys, as in a menu or a list box.
+    Vertical,
+    /// All four arrow keys, as in a grid of options.
+    Both,
+}
+
+/// Makes the items inside the element loaded into `node_ref` a single stop when tabbing through
+/// the page, with the arrow keys moving focus between them, as recommended for composite
+/// widgets like toolbars, menus and tab lists.
+///
+/// The items are the elements inside the container that match `items`, a CSS selector. Only
+/// the active item has `tabindex="0"`, and the rest have `tabindex="-1"`. The arrow keys for the
+/// [`Orientation`] move to the previous and next items, wrapping around, and Home and End move
+/// to the first and last. An item also becomes active when it is focused in another way, like
+/// being clicked.
+///
+/// Returns the index of the active item.
+///
+/// ```rust
+/// use leptos::{
+///     focus::{use_roving_tabindex, Orientation},
+///     html,
+///     prelude::*,
+/// };
+///
+/// #[component]
+/// fn Toolbar() -> impl IntoView {
+///     let toolbar = NodeRef::<html::Div>::new();
+///     let active = use_roving_tabindex(toolbar, "button", Orientation::Horizontal);
+///     view! {
+///         <div node_ref=toolbar role="toolbar">
+///             <button>"Bold"</button>
+///             <button>"Italic"</button>
+///             <button>"Underline"</button>
+///         </div>
+///         <p>"Active: " {active}</p>
+///     }
+/// }
+/// ```
+pub fn use_roving_tabindex<E>(
+    node_ref: NodeRef<E>,
+    items: &'static str,
+    orientation: Orientation,
+) -> Signal<usize>
+where
+    E: ElementType + 'static,
+    E::Output: JsCast + Clone + 'static,
+{
+    let active = RwSignal::new(0);
+    if !cfg!(target_arch = "wasm32") {
+        return active.read_only().into();
+    }
+
+    // items may be added or removed at any time, so their tab indices are set whenever focus
+    // moves among them
+    let update = move |container: &Element, index: usize| {
+        let all = query_all(container, items);
+        let index = index.min(all.len().saturating_sub(1));
+        for (idx, item) in all.iter().enumerate() {
+            _ = item.set_attribute(
+                "tabindex",
+                if idx == index { "0" } else { "-1" },
+            );
+        }
+        if active.get_untracked() != index {
+            active.set(index);
+        }
+        all
+    };
+
+    Effect::new(move |_| {
+        let Some(el) = node_ref.get() else {
+            return;
+        };
+        let el = el.unchecked_into::<Element>();
+        update(&el, active.get_untracked());
+
+        let on_keydown = Closure::<dyn Fn(Event)>::new({
+            let el = el.clone();
+            move |ev: Event| {
+                let Some(ev) = ev.dyn_ref::<KeyboardEvent>() else {
+                    return;
+                };
+                let (prev, next) = match orientation {
+                    Orientation::Horizontal => {
+                        (&["ArrowLeft"][..], &["ArrowRight"][..])
+                    }
+                    Orientation::Vertical => {
+                        (&["ArrowUp"][..], &["ArrowDown"][..])
+                    }
+                    Orientation::Both => (
+                        &["ArrowLeft", "ArrowUp"][..],
+                        &["ArrowRight", "ArrowDown"][..],
+                    ),
+                };
+                let all = query_all(&el, items);
+                if all.is_empty() {
+                    return;
+                }
+                let current = active.get_untracked().min(all.len() - 1);
+                let key = ev.key();
+                let target = if prev.contains(&key.as_str()) {
+                    (current + all.len() - 1) % all.len()
+                } else if next.contains(&key.as_str()) {
+                    (current + 1) % all.len()
+                } else if key == "Home" {
+                    0
+                } else if key == "End" {
+                    all.len() - 1
+                } else {
+                    return;
+                };
+                ev.prevent_default();
+                update(&el, target);
+                _ = all[target].focus();
+            }
+        });
+        on_cleanup_remove(el.clone(), "keydown", on_keydown);
+
+        let on_focusin = Closure::<dyn Fn(Event)>::new({
+            let el = el.clone();
+            move |ev: Event| {
+                let Some(target) =
+                    ev.target().and_then(|t| t.dyn_into::<Element>().ok())
+                else {
+                    return;
+                };
+                let all = query_all(&el, items);
+                if let Some(index) = all.iter().position(|item| {
+                    item.unchecked_ref::<Element>().contains(Some(&target))
+                }) {
+                    update(&el, index);
+                }
+            }
+        });
+        on_cleanup_remove(el, "focusin", on_focusin);
+    });
+    active.read_only().into()
+}
+
+/// Moves focus to the main heading of the page, and announces it to screen readers, as should
+/// happen after navigating to a new page without a full page load.
+///
+/// The heading is the first `<h1>` inside `<main>`, or else the first `<h1>` on the page. It is
+/// given `tabindex="-1"` so that it can be focused, without adding it to the tab order. If there
+/// is no heading, the `<body>` is focused instead, so that tabbing starts again from the top of
+/// the page. The announcement is the text of the heading, or else the title of the document,
+/// and is made with the [`Announcer`].
+///
+/// The router calls this after each navigation when
+/// `leptos_router::hooks::use_focus_on_navigate` is used.
+pub fn focus_page_heading() {
+    if !cfg!(target_arch = "wasm32") {
+        return;
+    }
+    let document = document();
+    let heading = document
+        .query_selector("main h1")
+        .ok()
+        .flatten()
+        .or_else(|| document.query_selector("h1").ok().flatten())
+        .and_then(|el| el.dyn_into::<HtmlElement>().ok());
+    let message = match &heading {
+        Some(heading) => {
+            if !heading.has_attribute("tabindex") {
+                _ = heading.set_attribute("tabindex", "-1");
+            }
+            _ = heading.focus();
+            heading.text_content().unwrap_or_default()
+        }
+        None => {
+            if let Some(body) = document.body() {
+                if !body.has_attribute("tabindex") {
+                    _ = body.set_attribute("tabindex", "-1");
+                }
+                _ = body.focus();
+            }
+            document.title()
+        }
+    };
+    Announcer::global().announce(message.trim(), Politeness::Assertive);
+}
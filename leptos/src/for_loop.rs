@@ -9,6 +9,10 @@ use tachys::{reactive_graph::OwnedView, view::keyed::keyed};
 /// This is much more efficient than naively iterating over nodes with `.iter().map(|n| view! { ... })...`,
 /// as it avoids re-creating DOM nodes that are not being changed.
 ///
+/// Each item is rendered with its own reactive [`Owner`]. When an item is removed from the list,
+/// its owner is disposed of, running any [`on_cleanup`](reactive_graph::owner::on_cleanup)
+/// functions registered while rendering it.
+///
 /// ```
 /// # use leptos::prelude::*;
 ///
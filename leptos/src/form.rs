@@ -1,4 +1,10 @@
-use crate::{children::Children, component, prelude::*, IntoView};
+use crate::{
+    callback::{Callable, Callback},
+    children::Children,
+    component,
+    prelude::*,
+    IntoView,
+};
 use leptos_dom::helpers::window;
 use leptos_server::{ServerAction, ServerMultiAction};
 use serde::de::DeserializeOwned;
@@ -20,10 +26,25 @@ use web_sys::{
     SubmitEvent,
 };
 
+mod validation;
+pub use validation::*;
+
 /// Automatically turns a server [Action](leptos_server::Action) into an HTML
 /// [`form`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/form)
 /// progressively enhanced to use client-side routing.
 ///
+/// ## Progressive Enhancement
+/// The form is rendered as an ordinary `<form method="post">` whose `action` is the server
+/// function's URL, so it can be submitted before (or without) WASM loading. In that case the
+/// server function runs, and the server redirects back to the page the form was submitted from.
+/// If the server function returned an error, it is encoded in that page's URL, and the
+/// [`ServerAction`] for that server function is created with the error as its `value` when
+/// rendered inside a `<Router/>`.
+///
+/// Once the app has hydrated, submitting the form is intercepted: the form fields are
+/// deserialized into the server function's arguments and dispatched to the `action`, so its
+/// `input`, `pending`, and `value` signals update as usual.
+///
 /// ## Encoding
 /// **Note:** `<ActionForm/>` only works with server functions that use the
 /// default `Url` encoding. This is to ensure that `<ActionForm/>` works correctly
@@ -82,6 +103,11 @@ pub fn ActionForm<ServFn>(
     /// A [`NodeRef`] in which the `<form>` element should be stored.
     #[prop(optional)]
     node_ref: Option<NodeRef<Form>>,
+    /// Checks the server function's arguments in the browser before they are sent, for example
+    /// using [`Validate`](trait@Validate). If this returns an error, the action is not dispatched
+    /// and the error is set as its `value` instead.
+    #[prop(optional, into)]
+    validate: Option<Callback<ServFn, Result<(), ServFn::Error>>>,
     /// Component children; should include the HTML of the form elements.
     children: Children,
 ) -> impl IntoView
@@ -119,7 +145,20 @@ where
 
             match ServFn::from_event(&ev) {
                 Ok(new_input) => {
-                    action.dispatch(new_input);
+                    let invalid = validate.and_then(|validate| {
+                        validate.call(new_input.clone()).err()
+                    });
+                    match invalid {
+                        Some(err) => {
+                            value.set(Some(Err(
+                                ServerFnError::WrappedServerError(err),
+                            )));
+                            version.update(|n| *n += 1);
+                        }
+                        None => {
+                            action.dispatch(new_input);
+                        }
+                    }
                 }
                 Err(err) => {
                     crate::logging::error!(
@@ -0,0 +1,343 @@
+pub use leptos_macro::Validate;
+use leptos_server::ServerAction;
+use reactive_graph::{traits::With, wrappers::read::Signal};
+use serde::{Deserialize, Serialize};
+use server_fn::{error::TypedError, ServerFn, ServerFnError};
+use std::{collections::BTreeMap, fmt};
+
+/// Validation errors for the fields of a form, keyed by field name.
+///
+/// This is usually created by the [`Validate`](trait@Validate) implementation derived for a
+/// struct. It can be returned from a server function as a [`TypedError`], so that the same errors
+/// are available whether they were found on the client before submitting or on the server; see
+/// [`field_errors`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FieldErrors(BTreeMap<String, Vec<String>>);
+
+impl FieldErrors {
+    /// Creates an empty set of errors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an error message for the given field.
+    pub fn add(
+        &mut self,
+        field: impl Into<String>,
+        message: impl Into<String>,
+    ) {
+        self.0.entry(field.into()).or_default().push(message.into());
+    }
+
+    /// All the error messages for the given field, in the order they were added.
+    pub fn get(&self, field: &str) -> &[String] {
+        self.0.get(field).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// The first error message for the given field, if any.
+    pub fn first(&self, field: &str) -> Option<&str> {
+        self.get(field).first().map(String::as_str)
+    }
+
+    /// Whether there are no errors for any field.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterates over each field that has errors, along with its error messages.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[String])> {
+        self.0
+            .iter()
+            .map(|(field, messages)| (field.as_str(), messages.as_slice()))
+    }
+
+    /// Returns `Ok(())` if there are no errors, and `Err(self)` otherwise.
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl fmt::Display for FieldErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, (field, messages)) in self.iter().enumerate() {
+            if idx > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{field}: {}", messages.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for FieldErrors {}
+
+/// Checks a value against a set of validation rules.
+///
+/// This can be derived for a struct with named fields, using `#[validate(...)]` attributes to
+/// list the rules for each field:
+/// - `required`: the field is `Some(_)` (for an `Option`) or not empty, ignoring leading and
+///   trailing whitespace
+/// - `min_length = N` and `max_length = N`: the number of characters (or items) in the field,
+///   including any leading and trailing whitespace
+/// - `min = N` and `max = N`: the value of the field
+/// - `email`: the field looks like an email address
+/// - `custom = path`: calls a function `fn(&T) -> Result<(), String>` with the field
+///
+/// Any rule other than `required` is skipped for an `Option` field that is `None`. The default
+/// error message for each rule in an attribute can be replaced with `message = "..."`.
+///
+/// There is no separate derive for reading a struct from a form:
+/// [`FromFormData`](crate::form::FromFormData) is implemented for any type that implements
+/// [`Deserialize`], so deriving `Deserialize` alongside `Validate` is enough to read the struct
+/// from a `submit` event and validate it before it is sent.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// use leptos::form::{FieldErrors, Validate};
+///
+/// #[derive(Validate)]
+/// struct SignUp {
+///     #[validate(required, max_length = 32)]
+///     name: String,
+///     #[validate(required, email, message = "Please enter your email.")]
+///     email: String,
+///     #[validate(min = 13)]
+///     age: Option<u8>,
+/// }
+///
+/// let errors = SignUp {
+///     name: "  ".into(),
+///     email: "not an email".into(),
+///     age: Some(12),
+/// }
+/// .validate()
+/// .unwrap_err();
+/// assert_eq!(errors.get("name"), ["This field is required."]);
+/// assert_eq!(errors.first("email"), Some("Please enter your email."));
+/// assert_eq!(errors.first("age"), Some("Must be at least 13."));
+/// ```
+pub trait Validate {
+    /// Checks each field, returning all the errors that were found.
+    fn validate(&self) -> Result<(), FieldErrors>;
+}
+
+/// The rules used by `#[derive(Validate)]`.
+///
+/// Each returns the default error message if the value does not match.
+pub mod rules {
+    use std::fmt::Display;
+
+    /// A value whose length can be checked.
+    pub trait FieldLength {
+        /// The length of the value. For strings, this is the number of characters.
+        fn field_len(&self) -> usize;
+
+        /// Whether the value counts as empty for the `required` rule. For strings, whitespace
+        /// alone counts as empty.
+        fn is_blank(&self) -> bool {
+            self.field_len() == 0
+        }
+
+        /// What the length counts, used in error messages.
+        const UNIT: &'static str;
+    }
+
+    impl FieldLength for str {
+        const UNIT: &'static str = "characters";
+
+        fn field_len(&self) -> usize {
+            self.chars().count()
+        }
+
+        fn is_blank(&self) -> bool {
+            self.trim().is_empty()
+        }
+    }
+
+    impl FieldLength for String {
+        const UNIT: &'static str = "characters";
+
+        fn field_len(&self) -> usize {
+            self.as_str().field_len()
+        }
+
+        fn is_blank(&self) -> bool {
+            self.as_str().is_blank()
+        }
+    }
+
+    impl<T> FieldLength for [T] {
+        const UNIT: &'static str = "items";
+
+        fn field_len(&self) -> usize {
+            self.len()
+        }
+    }
+
+    impl<T> FieldLength for Vec<T> {
+        const UNIT: &'static str = "items";
+
+        fn field_len(&self) -> usize {
+            self.len()
+        }
+    }
+
+    /// The value is not empty. A string that only contains whitespace counts as empty.
+    pub fn required<T: FieldLength + ?Sized>(value: &T) -> Result<(), String> {
+        if value.is_blank() {
+            Err("This field is required.".into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The optional value is `Some(_)`.
+    pub fn required_some<T>(value: &Option<T>) -> Result<(), String> {
+        if value.is_some() {
+            Ok(())
+        } else {
+            Err("This field is required.".into())
+        }
+    }
+
+    /// The value has at least `min` characters or items.
+    pub fn min_length<T: FieldLength + ?Sized>(
+        value: &T,
+        min: usize,
+    ) -> Result<(), String> {
+        if value.field_len() < min {
+            Err(format!("Must have at least {min} {}.", T::UNIT))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The value has at most `max` characters or items.
+    pub fn max_length<T: FieldLength + ?Sized>(
+        value: &T,
+        max: usize,
+    ) -> Result<(), String> {
+        if value.field_len() > max {
+            Err(format!("Must have at most {max} {}.", T::UNIT))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The value is at least `min`.
+    pub fn min<T: PartialOrd + Display>(
+        value: &T,
+        min: T,
+    ) -> Result<(), String> {
+        if *value < min {
+            Err(format!("Must be at least {min}."))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The value is at most `max`.
+    pub fn max<T: PartialOrd + Display>(
+        value: &T,
+        max: T,
+    ) -> Result<(), String> {
+        if *value > max {
+            Err(format!("Must be at most {max}."))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The value looks like an email address: some text, an `@`, and a domain containing a `.`,
+    /// with no whitespace.
+    ///
+    /// This is only a basic check; the only way to be sure an address exists is to send an email
+    /// to it.
+    pub fn email<T: AsRef<str> + ?Sized>(value: &T) -> Result<(), String> {
+        let value = value.as_ref().trim();
+        let valid = match value.split_once('@') {
+            Some((user, domain)) => {
+                !user.is_empty()
+                    && !domain.contains('@')
+                    && !value.contains(char::is_whitespace)
+                    && domain.contains('.')
+                    && domain.split('.').all(|part| !part.is_empty())
+            }
+            None => false,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err("Must be a valid email address.".into())
+        }
+    }
+}
+
+/// Returns the [`FieldErrors`] returned by the most recent call to a server action, or an empty
+/// set of errors if it succeeded or has not been called.
+///
+/// The server function should return its validation errors as a
+/// `ServerFnError<TypedError<FieldErrors>>`. Because the server action is initialized with any
+/// error returned from a form submitted without JS/WASM (see
+/// [`ActionForm`](crate::form::ActionForm)), this can be used to render errors inline both during
+/// server rendering and in the browser.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// use leptos::form::{field_errors, ActionForm, FieldErrors, Validate};
+/// use server_fn::error::TypedError;
+///
+/// #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Validate)]
+/// struct NewUser {
+///     #[validate(required, max_length = 32)]
+///     name: String,
+/// }
+///
+/// #[server]
+/// async fn add_user(
+///     user: NewUser,
+/// ) -> Result<(), ServerFnError<TypedError<FieldErrors>>> {
+///     user.validate()?;
+///     Ok(())
+/// }
+///
+/// #[component]
+/// fn AddUserForm() -> impl IntoView {
+///     let add_user = ServerAction::<AddUser>::new();
+///     let errors = field_errors(add_user);
+///     view! {
+///         <ActionForm
+///             action=add_user
+///             validate=|args: AddUser| args.user.validate().map_err(TypedError)
+///         >
+///             <input type="text" name="user[name]"/>
+///             <p class="error">{move || errors.with(|e| e.first("name").map(str::to_string))}</p>
+///             <input type="submit"/>
+///         </ActionForm>
+///     }
+/// }
+/// ```
+pub fn field_errors<S>(action: ServerAction<S>) -> Signal<FieldErrors>
+where
+    S: ServerFn<Error = TypedError<FieldErrors>>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+    S::Output: Send + Sync + 'static,
+{
+    let value = action.value();
+    Signal::derive(move || {
+        value.with(|value| match value {
+            Some(Err(ServerFnError::WrappedServerError(TypedError(
+                errors,
+            )))) => errors.clone(),
+            _ => FieldErrors::default(),
+        })
+    })
+}
@@ -0,0 +1,710 @@
+//! Reactive formatting of numbers, dates and relative times for the current locale.
+//!
+//! In the browser, values are formatted with the
+//! [`Intl`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl)
+//! API. It is not available on the server, so values are formatted there with a small
+//! implementation in Rust, which covers the most widely used locales. The browser uses the same
+//! implementation until hydration is complete, so that the page it hydrates matches the HTML
+//! from the server, and then switches to `Intl`.
+//!
+//! The locale is the one of the [`I18n`] in context, or `en-US` if there is none, unless it is
+//! set on the format.
+
+use crate::{
+    i18n::{primary_subtag, I18n, PluralCategory},
+    media::after_hydration,
+};
+use leptos_dom::helpers::{is_browser, set_interval_with_handle};
+use reactive_graph::{
+    computed::Memo,
+    owner::{use_context, Owner},
+    signal::RwSignal,
+    traits::{Get, Set, With},
+    wrappers::read::Signal,
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use wasm_bindgen::{JsCast, JsValue};
+
+const DEFAULT_LOCALE: &str = "en-US";
+
+/// How often [`use_relative_time`] updates.
+const RELATIVE_TIME_INTERVAL: Duration = Duration::from_secs(10);
+
+fn use_locale(locale: Option<&str>) -> Signal<String> {
+    match (locale, use_context::<I18n>()) {
+        (Some(locale), _) => Signal::from(locale.to_string()),
+        (None, Some(i18n)) => i18n.locale().into(),
+        (None, None) => Signal::from(DEFAULT_LOCALE.to_string()),
+    }
+}
+
+/// Returns a signal that becomes `true` in the browser once hydration is complete, when it is
+/// safe to use `Intl`.
+fn use_intl() -> Signal<bool> {
+    let ready = RwSignal::new(false);
+    if is_browser() {
+        after_hydration(move || ready.set(true));
+    }
+    ready.read_only().into()
+}
+
+fn intl_options(options: &[(&str, JsValue)]) -> js_sys::Object {
+    let object = js_sys::Object::new();
+    for (key, value) in options {
+        _ = js_sys::Reflect::set(&object, &JsValue::from_str(key), value);
+    }
+    object
+}
+
+/// Calls the `format` function of an `Intl` formatter.
+fn intl_format(format: js_sys::Function, value: &JsValue) -> Option<String> {
+    format.call1(&JsValue::NULL, value).ok()?.as_string()
+}
+
+/// The symbols used to write numbers in a locale.
+struct NumberSymbols {
+    group: &'static str,
+    decimal: &'static str,
+    /// The number of digits before the decimal point needed to use grouping separators.
+    min_grouping: usize,
+    percent: &'static str,
+}
+
+impl NumberSymbols {
+    fn of(locale: &str) -> Self {
+        let language = primary_subtag(locale).to_ascii_lowercase();
+        let (group, decimal, min_grouping) = match language.as_str() {
+            "de" | "nl" | "it" | "pt" | "id" | "tr" | "da" => (".", ",", 4),
+            "es" => (".", ",", 5),
+            "fr" => ("\u{202f}", ",", 4),
+            "ru" | "uk" | "cs" | "sk" | "sv" | "nb" | "no" | "fi" => {
+                ("\u{a0}", ",", 4)
+            }
+            "pl" => ("\u{a0}", ",", 5),
+            _ => (",", ".", 4),
+        };
+        let percent = match language.as_str() {
+            "fr" => "\u{202f}%",
+            "de" | "es" | "ru" | "uk" | "cs" | "sk" | "sv" | "nb" | "no"
+            | "fi" | "da" => "\u{a0}%",
+            _ => "%",
+        };
+        Self {
+            group,
+            decimal,
+            min_grouping,
+            percent,
+        }
+    }
+}
+
+/// How to format a number, for [`use_number_format`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NumberFormat {
+    locale: Option<String>,
+    percent: bool,
+    fraction_digits: Option<(u8, u8)>,
+    no_grouping: bool,
+}
+
+impl NumberFormat {
+    /// Formats numbers as decimals, with up to three fraction digits and grouping separators,
+    /// like `1,234.568` in English.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Formats numbers in this locale, rather than the current one.
+    pub fn locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Formats numbers as percentages, so that `0.25` is `25%` in English. Percentages have no
+    /// fraction digits, unless they are set with [`fraction_digits`](Self::fraction_digits).
+    pub fn percent(mut self) -> Self {
+        self.percent = true;
+        self
+    }
+
+    /// Sets the minimum and maximum number of digits after the decimal point. Numbers are
+    /// rounded half away from zero.
+    pub fn fraction_digits(mut self, min: u8, max: u8) -> Self {
+        self.fraction_digits = Some((min.min(20), max.clamp(min, 20)));
+        self
+    }
+
+    /// Sets whether digits are grouped, like the thousands in `1,234` in English.
+    pub fn grouping(mut self, grouping: bool) -> Self {
+        self.no_grouping = !grouping;
+        self
+    }
+
+    fn digits(&self) -> (u8, u8) {
+        self.fraction_digits.unwrap_or(if self.percent {
+            (0, 0)
+        } else {
+            (0, 3)
+        })
+    }
+
+    /// Formats the number in the locale, without `Intl`.
+    pub fn format(&self, locale: &str, value: f64) -> String {
+        let value = if self.percent { value * 100.0 } else { value };
+        if value.is_nan() {
+            return "NaN".to_string();
+        }
+        let symbols = NumberSymbols::of(locale);
+        let sign = if value.is_sign_negative() { "-" } else { "" };
+        let mut out = sign.to_string();
+        if value.is_infinite() {
+            out.push('∞');
+        } else {
+            let (min, max) = self.digits();
+            let factor = 10f64.powi(max.into());
+            let rounded = (value.abs() * factor).round() / factor;
+            if rounded == 0.0 {
+                out.clear();
+            }
+            let digits = format!("{rounded:.0$}", max as usize);
+            let (int, frac) = digits.split_once('.').unwrap_or((&digits, ""));
+            let frac = frac.trim_end_matches('0');
+            let frac = if frac.len() < min as usize {
+                &digits[int.len() + 1..int.len() + 1 + min as usize]
+            } else {
+                frac
+            };
+
+            if self.no_grouping || int.len() < symbols.min_grouping {
+                out.push_str(int);
+            } else {
+                for (idx, digit) in int.chars().enumerate() {
+                    if idx > 0 && (int.len() - idx) % 3 == 0 {
+                        out.push_str(symbols.group);
+                    }
+                    out.push(digit);
+                }
+            }
+            if !frac.is_empty() {
+                out.push_str(symbols.decimal);
+                out.push_str(frac);
+            }
+        }
+        if self.percent {
+            out.push_str(symbols.percent);
+        }
+        out
+    }
+
+    fn format_intl(&self, locale: &str, value: f64) -> String {
+        let (min, max) = self.digits();
+        let style = if self.percent { "percent" } else { "decimal" };
+        let options = intl_options(&[
+            ("style", style.into()),
+            ("minimumFractionDigits", min.into()),
+            ("maximumFractionDigits", max.into()),
+            ("useGrouping", (!self.no_grouping).into()),
+        ]);
+        let locales = js_sys::Array::of1(&JsValue::from_str(locale));
+        let format = js_sys::Intl::NumberFormat::new(&locales, &options);
+        intl_format(format.format(), &JsValue::from_f64(value))
+            .unwrap_or_else(|| self.format(locale, value))
+    }
+}
+
+/// Returns a signal with the number formatted for the current locale.
+///
+/// ```rust
+/// use leptos::{
+///     format::{use_number_format, NumberFormat},
+///     prelude::*,
+/// };
+///
+/// #[component]
+/// fn Progress(done: ReadSignal<f64>) -> impl IntoView {
+///     let percent = use_number_format(done, NumberFormat::new().percent());
+///     view! { <p>{percent}" done"</p> }
+/// }
+/// ```
+pub fn use_number_format(
+    value: impl Into<Signal<f64>>,
+    format: NumberFormat,
+) -> Signal<String> {
+    let value = value.into();
+    let locale = use_locale(format.locale.as_deref());
+    let intl = use_intl();
+    Memo::new(move |_| {
+        let value = value.get();
+        locale.with(|locale| {
+            if intl.get() {
+                format.format_intl(locale, value)
+            } else {
+                format.format(locale, value)
+            }
+        })
+    })
+    .into()
+}
+
+/// The date and time of a [`SystemTime`], in UTC.
+struct Civil {
+    year: i64,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+}
+
+fn millis_since_epoch(time: SystemTime) -> f64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(since) => since.as_millis() as f64,
+        Err(e) => -(e.duration().as_millis() as f64),
+    }
+}
+
+impl Civil {
+    fn from_millis(millis: f64) -> Self {
+        let millis = millis as i64;
+        let days = millis.div_euclid(86_400_000);
+        let minutes = millis.rem_euclid(86_400_000) / 60_000;
+        // https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = yoe + era * 400 + i64::from(month <= 2);
+        Self {
+            year,
+            month,
+            day,
+            hour: (minutes / 60) as u32,
+            minute: (minutes % 60) as u32,
+        }
+    }
+}
+
+/// How to format a date and time, for [`use_datetime_format`].
+///
+/// Dates are written with numbers, like `1/2/2024` in American English, and times with hours
+/// and minutes, like `1:05 PM`. Both are in UTC, so that they are the same on the server and in
+/// the browser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateTimeFormat {
+    locale: Option<String>,
+    date: bool,
+    time: bool,
+}
+
+impl Default for DateTimeFormat {
+    fn default() -> Self {
+        Self::date()
+    }
+}
+
+impl DateTimeFormat {
+    /// Formats the date only.
+    pub fn date() -> Self {
+        Self {
+            locale: None,
+            date: true,
+            time: false,
+        }
+    }
+
+    /// Formats the time only.
+    pub fn time() -> Self {
+        Self {
+            locale: None,
+            date: false,
+            time: true,
+        }
+    }
+
+    /// Formats both the date and the time.
+    pub fn date_time() -> Self {
+        Self {
+            locale: None,
+            date: true,
+            time: true,
+        }
+    }
+
+    /// Formats dates in this locale, rather than the current one.
+    pub fn locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Formats the time in the locale, without `Intl`.
+    pub fn format(&self, locale: &str, time: SystemTime) -> String {
+        self.format_millis(locale, millis_since_epoch(time))
+    }
+
+    fn format_millis(&self, locale: &str, millis: f64) -> String {
+        let Civil {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+        } = Civil::from_millis(millis);
+        let language = primary_subtag(locale).to_ascii_lowercase();
+        let us_english = language == "en"
+            && matches!(
+                locale.to_ascii_lowercase().as_str(),
+                "en" | "en-us" | "en_us"
+            );
+
+        let date = match language.as_str() {
+            "en" if us_english => format!("{month}/{day}/{year}"),
+            "en" | "fr" | "pt" => format!("{day:02}/{month:02}/{year}"),
+            "es" | "it" => format!("{day}/{month}/{year}"),
+            "de" => format!("{day}.{month}.{year}"),
+            "ru" | "uk" => format!("{day:02}.{month:02}.{year}"),
+            "pl" => format!("{day}.{month:02}.{year}"),
+            "nl" => format!("{day}-{month}-{year}"),
+            "ja" | "zh" => format!("{year}/{month}/{day}"),
+            "ko" => format!("{year}. {month}. {day}."),
+            _ => format!("{year}-{month:02}-{day:02}"),
+        };
+        let time = if us_english {
+            let period = if hour < 12 { "AM" } else { "PM" };
+            let hour = match hour % 12 {
+                0 => 12,
+                hour => hour,
+            };
+            format!("{hour}:{minute:02}\u{202f}{period}")
+        } else {
+            format!("{hour:02}:{minute:02}")
+        };
+        match (self.date, self.time) {
+            (true, true) => {
+                let separator = match language.as_str() {
+                    "fr" | "ja" | "zh" | "ko" | "sv" => " ",
+                    _ => ", ",
+                };
+                format!("{date}{separator}{time}")
+            }
+            (false, true) => time,
+            _ => date,
+        }
+    }
+
+    fn format_intl(&self, locale: &str, millis: f64) -> String {
+        let mut options = vec![("timeZone", "UTC")];
+        if self.date {
+            options.extend([
+                ("year", "numeric"),
+                ("month", "numeric"),
+                ("day", "numeric"),
+            ]);
+        }
+        if self.time {
+            options.extend([("hour", "numeric"), ("minute", "2-digit")]);
+        }
+        let options = options
+            .into_iter()
+            .map(|(key, value)| (key, JsValue::from_str(value)))
+            .collect::<Vec<_>>();
+        let locales = js_sys::Array::of1(&JsValue::from_str(locale));
+        let format = js_sys::Intl::DateTimeFormat::new(
+            &locales,
+            &intl_options(&options),
+        );
+        let date = js_sys::Date::new(&JsValue::from_f64(millis));
+        intl_format(format.format(), date.unchecked_ref())
+            .unwrap_or_else(|| self.format_millis(locale, millis))
+    }
+}
+
+/// Returns a signal with the date or time formatted for the current locale.
+///
+/// In the browser, [`SystemTime::now`] is not available, but times can be created from a
+/// timestamp by adding it to [`UNIX_EPOCH`].
+///
+/// ```rust
+/// use leptos::{
+///     format::{use_datetime_format, DateTimeFormat},
+///     prelude::*,
+/// };
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// #[component]
+/// fn Posted(timestamp: u64) -> impl IntoView {
+///     let posted = UNIX_EPOCH + Duration::from_secs(timestamp);
+///     let posted = use_datetime_format(posted, DateTimeFormat::date_time());
+///     view! { <time>{posted}</time> }
+/// }
+/// ```
+pub fn use_datetime_format(
+    time: impl Into<Signal<SystemTime>>,
+    format: DateTimeFormat,
+) -> Signal<String> {
+    let time = time.into();
+    let locale = use_locale(format.locale.as_deref());
+    let intl = use_intl();
+    Memo::new(move |_| {
+        let millis = millis_since_epoch(time.get());
+        locale.with(|locale| {
+            if intl.get() {
+                format.format_intl(locale, millis)
+            } else {
+                format.format_millis(locale, millis)
+            }
+        })
+    })
+    .into()
+}
+
+const UNITS: [(&str, f64); 7] = [
+    ("second", 1.0),
+    ("minute", 60.0),
+    ("hour", 3_600.0),
+    ("day", 86_400.0),
+    ("week", 604_800.0),
+    ("month", 2_629_800.0),
+    ("year", 31_557_600.0),
+];
+
+/// Chooses the unit for a difference in seconds, returning it and the rounded value in it.
+fn relative_unit(seconds: f64) -> (&'static str, f64) {
+    let (unit, size) = UNITS
+        .iter()
+        .zip(UNITS.iter().skip(1))
+        .find(|(_, (_, next))| seconds.abs() < *next)
+        .map_or(UNITS[UNITS.len() - 1], |(unit, _)| *unit);
+    (unit, (seconds / size).round())
+}
+
+/// Formats a difference in seconds between a time and now, like `3 minutes ago` or `in 2 days`,
+/// without `Intl`.
+///
+/// This covers English, French, German and Spanish. Other locales use English.
+pub fn format_relative_time(locale: &str, seconds: f64) -> String {
+    let (unit, value) = relative_unit(seconds);
+    let language = primary_subtag(locale).to_ascii_lowercase();
+    let count = value.abs();
+    let one = PluralCategory::of(&language, count) == PluralCategory::One;
+    let number = NumberFormat::new().format(locale, count);
+    let past = value.is_sign_negative();
+    let pick = |forms: [(&'static str, &'static str); 7]| {
+        let idx = UNITS.iter().position(|(name, _)| *name == unit);
+        let (singular, plural) = forms[idx.unwrap_or_default()];
+        if one {
+            singular
+        } else {
+            plural
+        }
+    };
+    match language.as_str() {
+        "de" => {
+            let unit = pick([
+                ("Sekunde", "Sekunden"),
+                ("Minute", "Minuten"),
+                ("Stunde", "Stunden"),
+                ("Tag", "Tagen"),
+                ("Woche", "Wochen"),
+                ("Monat", "Monaten"),
+                ("Jahr", "Jahren"),
+            ]);
+            if past {
+                format!("vor {number} {unit}")
+            } else {
+                format!("in {number} {unit}")
+            }
+        }
+        "fr" => {
+            let unit = pick([
+                ("seconde", "secondes"),
+                ("minute", "minutes"),
+                ("heure", "heures"),
+                ("jour", "jours"),
+                ("semaine", "semaines"),
+                ("mois", "mois"),
+                ("an", "ans"),
+            ]);
+            if past {
+                format!("il y a {number} {unit}")
+            } else {
+                format!("dans {number} {unit}")
+            }
+        }
+        "es" => {
+            let unit = pick([
+                ("segundo", "segundos"),
+                ("minuto", "minutos"),
+                ("hora", "horas"),
+                ("día", "días"),
+                ("semana", "semanas"),
+                ("mes", "meses"),
+                ("año", "años"),
+            ]);
+            if past {
+                format!("hace {number} {unit}")
+            } else {
+                format!("dentro de {number} {unit}")
+            }
+        }
+        _ => {
+            let unit = pick([
+                ("second", "seconds"),
+                ("minute", "minutes"),
+                ("hour", "hours"),
+                ("day", "days"),
+                ("week", "weeks"),
+                ("month", "months"),
+                ("year", "years"),
+            ]);
+            if past {
+                format!("{number} {unit} ago")
+            } else {
+                format!("in {number} {unit}")
+            }
+        }
+    }
+}
+
+fn format_relative_time_intl(locale: &str, seconds: f64) -> String {
+    let (unit, value) = relative_unit(seconds);
+    let locales = js_sys::Array::of1(&JsValue::from_str(locale));
+    js_sys::Intl::RelativeTimeFormat::new(&locales, &js_sys::Object::new())
+        .format(value, unit)
+        .into()
+}
+
+fn now_millis() -> f64 {
+    if cfg!(target_arch = "wasm32") {
+        js_sys::Date::now()
+    } else {
+        millis_since_epoch(SystemTime::now())
+    }
+}
+
+/// Returns a signal with the time relative to now, like `3 minutes ago` or `in 2 days`,
+/// formatted for the current locale.
+///
+/// The unit is the largest one in which the difference is at least one, from seconds up to
+/// years. In the browser, the signal is updated every few seconds as time passes, until the
+/// current owner is disposed.
+///
+/// ```rust
+/// use leptos::{format::use_relative_time, prelude::*};
+/// use std::time::SystemTime;
+///
+/// #[component]
+/// fn LastSaved(saved: ReadSignal<SystemTime>) -> impl IntoView {
+///     let ago = use_relative_time(saved);
+///     view! { <p>"Saved " {ago}</p> }
+/// }
+/// ```
+pub fn use_relative_time(
+    time: impl Into<Signal<SystemTime>>,
+) -> Signal<String> {
+    let time = time.into();
+    let locale = use_locale(None);
+    let intl = use_intl();
+    let now = RwSignal::new(now_millis());
+    if is_browser() {
+        after_hydration(move || {
+            if let Ok(handle) = set_interval_with_handle(
+                move || now.set(now_millis()),
+                RELATIVE_TIME_INTERVAL,
+            ) {
+                Owner::on_cleanup(move || handle.clear());
+            }
+        });
+    }
+    Memo::new(move |_| {
+        let seconds = (millis_since_epoch(time.get()) - now.get()) / 1000.0;
+        locale.with(|locale| {
+            if intl.get() {
+                format_relative_time_intl(locale, seconds)
+            } else {
+                format_relative_time(locale, seconds)
+            }
+        })
+    })
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_numbers() {
+        let decimal = NumberFormat::new();
+        assert_eq!(decimal.format("en-US", 1234567.891), "1,234,567.891");
+        assert_eq!(decimal.format("en", -0.0004), "0");
+        assert_eq!(decimal.format("de", 1234.5), "1.234,5");
+        assert_eq!(decimal.format("es", 1234.5), "1234,5");
+        assert_eq!(decimal.format("fr", 12345.0), "12\u{202f}345");
+        assert_eq!(
+            NumberFormat::new()
+                .fraction_digits(2, 2)
+                .format("en", 0.125),
+            "0.13"
+        );
+        assert_eq!(
+            NumberFormat::new().grouping(false).format("en", 12345.0),
+            "12345"
+        );
+        let percent = NumberFormat::new().percent();
+        assert_eq!(percent.format("en", 0.256), "26%");
+        assert_eq!(percent.format("de", 0.256), "26\u{a0}%");
+    }
+
+    #[test]
+    fn formats_dates() {
+        // 2024-01-02T13:05:00Z
+        let time = UNIX_EPOCH + Duration::from_secs(1_704_200_700);
+        let date_time = DateTimeFormat::date_time();
+        assert_eq!(date_time.format("en-US", time), "1/2/2024, 1:05\u{202f}PM");
+        assert_eq!(date_time.format("de-DE", time), "2.1.2024, 13:05");
+        assert_eq!(date_time.format("fr", time), "02/01/2024 13:05");
+        assert_eq!(DateTimeFormat::date().format("en-GB", time), "02/01/2024");
+        assert_eq!(DateTimeFormat::date().format("ja", time), "2024/1/2");
+        assert_eq!(DateTimeFormat::time().format("ru", time), "13:05");
+        assert_eq!(
+            DateTimeFormat::date()
+                .format("en", UNIX_EPOCH - Duration::from_secs(1)),
+            "12/31/1969"
+        );
+    }
+
+    #[test]
+    fn formats_relative_times() {
+        assert_eq!(format_relative_time("en", -30.0), "30 seconds ago");
+        assert_eq!(format_relative_time("en", 60.0), "in 1 minute");
+        assert_eq!(format_relative_time("en", -3.0 * 86_400.0), "3 days ago");
+        assert_eq!(format_relative_time("de", -2.0 * 3_600.0), "vor 2 Stunden");
+        assert_eq!(format_relative_time("fr", -86_400.0), "il y a 1 jour");
+        assert_eq!(
+            format_relative_time("es", 2.0 * 604_800.0),
+            "dentro de 2 semanas"
+        );
+        assert_eq!(format_relative_time("en", 400.0 * 86_400.0), "in 1 year");
+    }
+
+    #[test]
+    fn signals_use_the_locale_in_context() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let translations =
+                crate::i18n::Translations::from_static(&[("de", "{}")]);
+            crate::i18n::provide_i18n(translations, "de");
+            let value = RwSignal::new(1234.5);
+            let formatted = use_number_format(value, NumberFormat::new());
+            assert_eq!(formatted.get(), "1.234,5");
+            value.set(2.0);
+            assert_eq!(formatted.get(), "2");
+            let english =
+                use_number_format(1234.5, NumberFormat::new().locale("en"));
+            assert_eq!(english.get(), "1,234.5");
+        });
+    }
+}
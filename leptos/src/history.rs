@@ -0,0 +1,332 @@
+use reactive_graph::{
+    effect::Effect,
+    owner::StoredValue,
+    signal::RwSignal,
+    traits::{Get, Set},
+    untrack,
+    wrappers::read::Signal,
+};
+use std::{collections::VecDeque, mem, sync::Arc, time::Duration};
+
+/// Options for [`create_history`].
+#[derive(Debug, Clone)]
+pub struct HistoryOptions {
+    depth: usize,
+    batch: Duration,
+}
+
+impl Default for HistoryOptions {
+    fn default() -> Self {
+        Self {
+            depth: 100,
+            batch: Duration::ZERO,
+        }
+    }
+}
+
+impl HistoryOptions {
+    /// The maximum number of changes that can be undone. Once it is reached, the oldest change
+    /// is forgotten. Defaults to 100.
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Changes made within this interval of the previous change are combined with it, so that
+    /// they are undone together. This is useful for text inputs, where each keystroke would
+    /// otherwise be a separate change. Defaults to zero, which does not combine any changes.
+    pub fn batch(mut self, interval: Duration) -> Self {
+        self.batch = interval;
+        self
+    }
+}
+
+/// The undo and redo history of a signal, as returned by [`create_history`].
+///
+/// This is `Copy`, and can be moved into as many closures as needed.
+pub struct History<T>
+where
+    T: Send + Sync + 'static,
+{
+    inner: StoredValue<HistoryInner<T>>,
+    can_undo: RwSignal<bool>,
+    can_redo: RwSignal<bool>,
+}
+
+impl<T> Clone for History<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for History<T> where T: Send + Sync + 'static {}
+
+type Setter<T> = Arc<dyn Fn(T) + Send + Sync>;
+
+struct HistoryInner<T> {
+    stack: Stack<T>,
+    set: Setter<T>,
+}
+
+/// Records changes to `signal`, so that they can be undone and redone.
+///
+/// This works with any signal that can be both read and set, including a store. Each time its
+/// value changes, the previous value is pushed onto the undo stack, and the redo stack is
+/// cleared. Calling [`undo`](History::undo) or [`redo`](History::redo) sets the signal back to
+/// a previous or later value.
+///
+/// Changes are recorded by an effect, so they are only recorded in the browser.
+///
+/// ```rust
+/// use leptos::{
+///     history::{create_history, HistoryOptions},
+///     prelude::*,
+/// };
+/// use std::time::Duration;
+///
+/// #[component]
+/// fn Editor() -> impl IntoView {
+///     let text = RwSignal::new(String::new());
+///     let history = create_history(
+///         text,
+///         HistoryOptions::default().batch(Duration::from_millis(500)),
+///     );
+///     view! {
+///         <textarea
+///             prop:value=move || text.get()
+///             on:input=move |ev| text.set(event_target_value(&ev))
+///         ></textarea>
+///         <button on:click=move |_| history.undo() disabled=move || !history.can_undo().get()>
+///             "Undo"
+///         </button>
+///         <button on:click=move |_| history.redo() disabled=move || !history.can_redo().get()>
+///             "Redo"
+///         </button>
+///     }
+/// }
+/// ```
+pub fn create_history<S>(
+    signal: S,
+    options: HistoryOptions,
+) -> History<<S as Get>::Value>
+where
+    S: Get + Set<Value = <S as Get>::Value> + Send + Sync + Clone + 'static,
+    <S as Get>::Value: PartialEq + Clone + Send + Sync + 'static,
+{
+    let HistoryOptions { depth, batch } = options;
+    let current = untrack(|| signal.get());
+    let history = History {
+        inner: StoredValue::new(HistoryInner {
+            stack: Stack::new(current, depth, batch),
+            set: Arc::new({
+                let signal = signal.clone();
+                move |value| signal.set(value)
+            }),
+        }),
+        can_undo: RwSignal::new(false),
+        can_redo: RwSignal::new(false),
+    };
+
+    Effect::watch(
+        move || signal.get(),
+        move |value, _, _| {
+            let now = now();
+            // values set by undo() and redo() are already the current value, so are ignored
+            let recorded = history.inner.try_update_value(|inner| {
+                inner.stack.record(value.clone(), now)
+            });
+            if recorded == Some(true) {
+                history.update_flags();
+            }
+        },
+        false,
+    );
+
+    history
+}
+
+impl<T> History<T>
+where
+    T: PartialEq + Clone + Send + Sync + 'static,
+{
+    /// Sets the signal back to the value it had before the last change, if there is one.
+    pub fn undo(&self) {
+        self.apply(Stack::undo);
+    }
+
+    /// Sets the signal to the value it had before the last call to [`undo`](History::undo), if
+    /// nothing has changed since.
+    pub fn redo(&self) {
+        self.apply(Stack::redo);
+    }
+
+    /// Forgets all the changes that have been recorded.
+    pub fn clear(&self) {
+        self.inner.update_value(|inner| inner.stack.clear());
+        self.update_flags();
+    }
+
+    /// Returns a signal that is `true` when there is a change that can be undone.
+    pub fn can_undo(&self) -> Signal<bool> {
+        self.can_undo.read_only().into()
+    }
+
+    /// Returns a signal that is `true` when there is a change that can be redone.
+    pub fn can_redo(&self) -> Signal<bool> {
+        self.can_redo.read_only().into()
+    }
+
+    fn apply(&self, step: fn(&mut Stack<T>) -> Option<T>) {
+        // the value is set after the stored value has been released, as setting it may run
+        // other code that reads the history
+        let step = self.inner.try_update_value(|inner| {
+            step(&mut inner.stack).map(|value| (value, Arc::clone(&inner.set)))
+        });
+        if let Some(Some((value, set))) = step {
+            set(value);
+            self.update_flags();
+        }
+    }
+
+    fn update_flags(&self) {
+        if let Some((can_undo, can_redo)) = self.inner.try_with_value(|inner| {
+            (inner.stack.can_undo(), inner.stack.can_redo())
+        }) {
+            self.can_undo.set(can_undo);
+            self.can_redo.set(can_redo);
+        }
+    }
+}
+
+/// The current time in milliseconds, used to decide which changes are batched together.
+fn now() -> f64 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        js_sys::Date::now()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs_f64() * 1000.0)
+            .unwrap_or_default()
+    }
+}
+
+struct Stack<T> {
+    current: T,
+    undo: VecDeque<T>,
+    redo: Vec<T>,
+    depth: usize,
+    batch: f64,
+    last_change: Option<f64>,
+}
+
+impl<T> Stack<T>
+where
+    T: PartialEq + Clone,
+{
+    fn new(current: T, depth: usize, batch: Duration) -> Self {
+        Self {
+            current,
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            depth,
+            batch: batch.as_secs_f64() * 1000.0,
+            last_change: None,
+        }
+    }
+
+    /// Records a new value, returning `false` if it is already the current one.
+    fn record(&mut self, value: T, now: f64) -> bool {
+        if value == self.current {
+            return false;
+        }
+        let batched = !self.undo.is_empty()
+            && self.last_change.is_some_and(|last| now - last < self.batch);
+        let previous = mem::replace(&mut self.current, value);
+        if !batched {
+            self.undo.push_back(previous);
+            if self.undo.len() > self.depth {
+                self.undo.pop_front();
+            }
+        }
+        self.redo.clear();
+        self.last_change = Some(now);
+        true
+    }
+
+    fn undo(&mut self) -> Option<T> {
+        let previous = self.undo.pop_back()?;
+        self.redo.push(mem::replace(&mut self.current, previous));
+        self.last_change = None;
+        Some(self.current.clone())
+    }
+
+    fn redo(&mut self) -> Option<T> {
+        let next = self.redo.pop()?;
+        self.undo.push_back(mem::replace(&mut self.current, next));
+        self.last_change = None;
+        Some(self.current.clone())
+    }
+
+    fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.last_change = None;
+    }
+
+    fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Stack;
+    use std::time::Duration;
+
+    #[test]
+    fn undoes_and_redoes_changes() {
+        let mut stack = Stack::new(0, 100, Duration::ZERO);
+        assert!(stack.record(1, 0.0));
+        assert!(stack.record(2, 1.0));
+        assert!(!stack.record(2, 2.0));
+        assert_eq!(stack.undo(), Some(1));
+        assert_eq!(stack.undo(), Some(0));
+        assert_eq!(stack.undo(), None);
+        assert_eq!(stack.redo(), Some(1));
+
+        // a new change clears the redo stack
+        stack.record(5, 3.0);
+        assert_eq!(stack.redo(), None);
+        assert_eq!(stack.undo(), Some(1));
+    }
+
+    #[test]
+    fn limits_depth_and_batches_changes() {
+        let mut stack = Stack::new(0, 2, Duration::from_millis(100));
+        stack.record(1, 0.0);
+        stack.record(2, 50.0);
+        stack.record(3, 120.0);
+        // 2 and 3 are batched with 1, as each came within 100ms of the last
+        assert_eq!(stack.undo(), Some(0));
+        assert_eq!(stack.undo(), None);
+
+        stack.redo();
+        stack.record(4, 500.0);
+        stack.record(5, 1000.0);
+        stack.record(6, 1500.0);
+        assert_eq!(stack.undo(), Some(5));
+        assert_eq!(stack.undo(), Some(4));
+        // the oldest change has been forgotten
+        assert_eq!(stack.undo(), None);
+    }
+}
@@ -33,20 +33,76 @@
 
 		return { el: null, id: null, children: tree };
 	}
+	const hydrated = new WeakSet();
 	function hydrateIsland(el, id, mod) {
+		if (hydrated.has(el)) {
+			return;
+		}
+		hydrated.add(el);
 		const islandFn = mod[`_island_${id}`];
 		if (islandFn) {
 			islandFn(el);
 		} else {
-			console.warn(`Could not find WASM function for the island ${l}.`);
+			console.warn(`Could not find WASM function for the island ${id}.`);
+		}
+	}
+	function onVisible(el, c) {
+		if ("IntersectionObserver" in window) {
+			const observer = new IntersectionObserver((entries) => {
+				if (entries.some(entry => entry.isIntersecting)) {
+					observer.disconnect();
+					c();
+				}
+			});
+			observer.observe(el);
+		} else {
+			c();
+		}
+	}
+	function onInteraction(el, c) {
+		const events = ["pointerover", "pointerdown", "focusin", "keydown"];
+		let done = false;
+		function listener() {
+			if (!done) {
+				done = true;
+				for (const ev of events) {
+					el.removeEventListener(ev, listener);
+				}
+				c();
+			}
+		}
+		for (const ev of events) {
+			el.addEventListener(ev, listener, { passive: true });
+		}
+	}
+	function whenReady(el, c) {
+		switch (el.dataset.hydrate) {
+			case "idle":
+				idle(c);
+				break;
+			case "visible":
+				onVisible(el, c);
+				break;
+			case "interaction":
+				onInteraction(el, c);
+				break;
+			default:
+				c();
 		}
 	}
 	function hydrateIslands(entry, mod) {
-		if(entry.node) {
-			hydrateIsland(entry.node, entry.id, mod);
+		function hydrateChildren() {
+			for (const island of entry.children) {
+				hydrateIslands(island, mod);
+			}
 		}
-		for (const island of entry.children) {
-			hydrateIslands(island, mod);
+		if(entry.node) {
+			whenReady(entry.node, () => {
+				hydrateIsland(entry.node, entry.id, mod);
+				hydrateChildren();
+			});
+		} else {
+			hydrateChildren();
 		}
 	}
 	idle(() => {
@@ -55,6 +111,10 @@
 				mod.default(`/${pkg_path}/${wasm_output_name}.wasm`).then(() => {
 					mod.hydrate();
 					hydrateIslands(islandTree(document.body, null), mod);
+					// islands that were kept across a server-driven navigation are skipped
+					window.addEventListener("leptos:navigated", () => {
+						hydrateIslands(islandTree(document.body, null), mod);
+					});
 				});
 			})
 	});
@@ -0,0 +1,63 @@
+(() => {
+	// the request for the page that is loading, which is canceled if another navigation starts
+	let pending;
+	async function navigate(url, push) {
+		// the page is patched by the app's WASM, so it is loaded in full until that is ready
+		const morph = window.__leptos_morph_document;
+		if (!morph) {
+			window.location.href = url.href;
+			return;
+		}
+		pending?.abort();
+		const controller = pending = new AbortController();
+		let doc;
+		try {
+			const res = await fetch(url, { headers: { "Accept": "text/html" }, signal: controller.signal });
+			if (!res.ok || !(res.headers.get("Content-Type") || "").includes("text/html")) {
+				throw new Error(`unexpected response ${res.status}`);
+			}
+			doc = new DOMParser().parseFromString(await res.text(), "text/html");
+		} catch (e) {
+			if (controller.signal.aborted) {
+				return;
+			}
+			// fall back to a full page load
+			window.location.href = url.href;
+			return;
+		}
+		pending = undefined;
+		if (push) {
+			window.history.pushState(null, "", url.href);
+		}
+		morph(doc);
+		if (url.hash) {
+			document.getElementById(decodeURIComponent(url.hash.slice(1)))?.scrollIntoView();
+		} else if (push) {
+			window.scrollTo(0, 0);
+		}
+		window.dispatchEvent(new CustomEvent("leptos:navigated", { detail: { url: url.href } }));
+	}
+	document.addEventListener("click", (ev) => {
+		if (ev.defaultPrevented || ev.button !== 0 || ev.metaKey || ev.altKey || ev.ctrlKey || ev.shiftKey) {
+			return;
+		}
+		const a = ev.composedPath().find(el => el instanceof Node && el.nodeName.toUpperCase() === "A");
+		if (!a || !a.href || a.hasAttribute("download") || a.getAttribute("rel") === "external"
+			|| (a.target && a.target !== "_self")) {
+			return;
+		}
+		const url = new URL(a.href);
+		if (url.origin !== window.location.origin) {
+			return;
+		}
+		// let the browser jump to anchors on the same page
+		if (url.pathname === window.location.pathname && url.search === window.location.search && url.hash) {
+			return;
+		}
+		ev.preventDefault();
+		navigate(url, true);
+	});
+	window.addEventListener("popstate", () => {
+		navigate(new URL(window.location.href), false);
+	});
+})()
@@ -33,6 +33,17 @@ pub fn AutoReload(
 pub fn HydrationScripts(
     options: LeptosOptions,
     #[prop(optional)] islands: bool,
+    /// In islands mode, navigates between pages by fetching the new page's HTML and patching it
+    /// into the current document with
+    /// [`morph_document`](tachys::morph::morph_document), instead of doing a full page load.
+    /// Islands that are in the same place on both pages with the same props are kept alive, along
+    /// with their state.
+    ///
+    /// The `<head>` is patched like the rest of the document. `<script>` elements that are
+    /// inserted by patching are not executed. Until the WASM has loaded, links do a full page
+    /// load.
+    #[prop(optional)]
+    islands_router: bool,
 ) -> impl IntoView {
     let pkg_path = &options.site_pkg_dir;
     let output_name = &options.output_name;
@@ -62,8 +73,12 @@ pub fn HydrationScripts(
             r#type="application/wasm"
             crossorigin=nonce.clone().unwrap_or_default()
         />
-        <script type="module" nonce=nonce>
+        <script type="module" nonce=nonce.clone()>
             {format!("{script}({pkg_path:?}, {output_name:?}, {wasm_output_name:?})")}
         </script>
+        {(islands && islands_router)
+            .then(|| {
+                view! { <script nonce=nonce>{include_str!("./islands_routing.js")}</script> }
+            })}
     }
 }
@@ -18,6 +18,14 @@ ws.onmessage = (ev) => {
 	if(msg.view) {
 		patch(msg.view);
 	}
+	if (msg.morph) {
+		// patch the running app in place, keeping its state
+		if (window.__leptos_morph) {
+			window.__leptos_morph(msg.morph);
+		} else {
+			window.location.reload();
+		}
+	}
 };
 ws.onclose = () => console.warn('Live-reload stopped. Manual reload necessary.');
 })
@@ -0,0 +1,712 @@
+//! Translating an app into multiple languages, with a reactive locale.
+//!
+//! Translations are JSON files, one per locale, named like `en.json` or `pt-BR.json`. Nested
+//! objects are flattened into keys joined with `.`, and messages can contain `{placeholders}`
+//! that are filled in with named arguments. An object whose keys are all plural categories
+//! (`zero`, `one`, `two`, `few`, `many` and `other`) holds the plural forms of one message,
+//! chosen by the `count` argument with the plural rules of the locale.
+//!
+//! ```json
+//! {
+//!     "greeting": "Hello, {name}!",
+//!     "cart": {
+//!         "title": "Your cart",
+//!         "items": { "zero": "Your cart is empty", "one": "{count} item", "other": "{count} items" }
+//!     }
+//! }
+//! ```
+//!
+//! [`include_translations!`] embeds the files in the `locales` directory of the crate, and
+//! [`provide_i18n`] provides an [`I18n`] via context, whose locale is a signal. Messages are
+//! looked up with [`t!`], which checks the key and its arguments against the files when the
+//! crate is compiled.
+//!
+//! ```rust,ignore
+//! use leptos::{i18n::*, prelude::*};
+//!
+//! #[component]
+//! fn App() -> impl IntoView {
+//!     let i18n = provide_i18n(include_translations!(), "en");
+//!     let (items, _) = signal(3);
+//!     view! {
+//!         <h1>{move || t!(i18n, "greeting", name = "Ferris")}</h1>
+//!         <p>{move || t!(i18n, "cart.items", count = items.get())}</p>
+//!         <button on:click=move |_| i18n.set_locale("fr")>"Français"</button>
+//!     }
+//! }
+//! ```
+//!
+//! During server rendering, the locale is negotiated from the `Accept-Language` header of the
+//! request, and serialized with the page, so that the app hydrates in the same locale it was
+//! rendered in. Otherwise, in the browser, it is read from the `lang` attribute of the `<html>`
+//! element, which is updated when the locale changes. The shell should set it to
+//! [`I18n::locale`] when rendering on the server.
+
+use crate::{
+    context::{provide_context, use_context},
+    request::RequestMeta,
+};
+pub use leptos_macro::{include_translations, t};
+use reactive_graph::{
+    effect::Effect,
+    owner::{Owner, StoredValue},
+    signal::{ReadSignal, RwSignal},
+    traits::{Get, GetUntracked, Set, With},
+};
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{Display, Write},
+};
+use thiserror::Error;
+
+/// An error in a translation file.
+#[derive(Debug, Error)]
+pub enum TranslationsError {
+    /// The file is not valid JSON.
+    #[error("invalid JSON in the translations for {locale}: {source}")]
+    Json {
+        /// The locale of the file.
+        locale: String,
+        /// The error from parsing the file.
+        source: serde_json::Error,
+    },
+    /// A value in the file is not a message.
+    #[error("invalid translation for {key} in {locale}: {reason}")]
+    Invalid {
+        /// The locale of the file.
+        locale: String,
+        /// The key of the value.
+        key: String,
+        /// What is wrong with it.
+        reason: String,
+    },
+}
+
+/// The plural categories of the [Unicode CLDR](https://cldr.unicode.org/index/cldr-spec/plural-rules).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PluralCategory {
+    /// Used by some languages for 0.
+    Zero,
+    /// Used for 1, and in some languages for other numbers like 21.
+    One,
+    /// Used by some languages for 2.
+    Two,
+    /// Used by some languages for small numbers, like 2 to 4.
+    Few,
+    /// Used by some languages for larger numbers, like 5 to 20.
+    Many,
+    /// Used for every other number.
+    Other,
+}
+
+impl PluralCategory {
+    /// The name of the category, as used in translation files.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PluralCategory::Zero => "zero",
+            PluralCategory::One => "one",
+            PluralCategory::Two => "two",
+            PluralCategory::Few => "few",
+            PluralCategory::Many => "many",
+            PluralCategory::Other => "other",
+        }
+    }
+
+    fn from_str(name: &str) -> Option<Self> {
+        Some(match name {
+            "zero" => PluralCategory::Zero,
+            "one" => PluralCategory::One,
+            "two" => PluralCategory::Two,
+            "few" => PluralCategory::Few,
+            "many" => PluralCategory::Many,
+            "other" => PluralCategory::Other,
+            _ => return None,
+        })
+    }
+
+    /// Returns the plural category of `count` in the locale.
+    ///
+    /// This covers the cardinal rules for whole numbers in the most widely used languages.
+    /// Other languages, and numbers with a fractional part, use the English rules.
+    pub fn of(locale: &str, count: f64) -> Self {
+        let language = primary_subtag(locale).to_ascii_lowercase();
+        if count.fract() != 0.0 || count < 0.0 {
+            return PluralCategory::Other;
+        }
+        let n = count as u64;
+        let (n10, n100) = (n % 10, n % 100);
+        match language.as_str() {
+            "ja" | "ko" | "zh" | "th" | "vi" | "id" | "ms" => {
+                PluralCategory::Other
+            }
+            "fr" | "pt" if n < 2 => PluralCategory::One,
+            "fr" | "pt" => PluralCategory::Other,
+            "ru" | "uk" | "be" | "sr" | "hr" | "bs" => {
+                if n10 == 1 && n100 != 11 {
+                    PluralCategory::One
+                } else if (2..=4).contains(&n10) && !(12..=14).contains(&n100) {
+                    PluralCategory::Few
+                } else {
+                    PluralCategory::Many
+                }
+            }
+            "pl" => {
+                if n == 1 {
+                    PluralCategory::One
+                } else if (2..=4).contains(&n10) && !(12..=14).contains(&n100) {
+                    PluralCategory::Few
+                } else {
+                    PluralCategory::Many
+                }
+            }
+            "cs" | "sk" => match n {
+                1 => PluralCategory::One,
+                2..=4 => PluralCategory::Few,
+                _ => PluralCategory::Other,
+            },
+            "ar" => match (n, n100) {
+                (0, _) => PluralCategory::Zero,
+                (1, _) => PluralCategory::One,
+                (2, _) => PluralCategory::Two,
+                (_, 3..=10) => PluralCategory::Few,
+                (_, 11..=99) => PluralCategory::Many,
+                _ => PluralCategory::Other,
+            },
+            _ if n == 1 => PluralCategory::One,
+            _ => PluralCategory::Other,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Text(String),
+    Plural(HashMap<PluralCategory, String>),
+}
+
+/// The messages for each locale, usually loaded with [`include_translations!`].
+#[derive(Debug, Clone, Default)]
+pub struct Translations {
+    locales: BTreeMap<String, HashMap<String, Message>>,
+}
+
+impl Translations {
+    /// Creates an empty set of translations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the messages for a locale from the contents of a JSON translation file, in the
+    /// format described in the [module documentation](self).
+    pub fn add_json(
+        &mut self,
+        locale: impl Into<String>,
+        json: &str,
+    ) -> Result<(), TranslationsError> {
+        let locale = locale.into();
+        let value = serde_json::from_str(json).map_err(|source| {
+            TranslationsError::Json {
+                locale: locale.clone(),
+                source,
+            }
+        })?;
+        let mut messages = HashMap::new();
+        flatten("", &value, &mut messages).map_err(|(key, reason)| {
+            TranslationsError::Invalid {
+                locale: locale.clone(),
+                key,
+                reason,
+            }
+        })?;
+        self.locales.entry(locale).or_default().extend(messages);
+        Ok(())
+    }
+
+    /// Creates translations from pairs of locales and the contents of their JSON files.
+    ///
+    /// This is used by [`include_translations!`], which checks the files when the crate is
+    /// compiled.
+    ///
+    /// # Panics
+    /// Panics if one of the files is invalid.
+    pub fn from_static(files: &[(&str, &str)]) -> Self {
+        let mut translations = Self::new();
+        for (locale, json) in files {
+            if let Err(e) = translations.add_json(*locale, json) {
+                panic!("{e}");
+            }
+        }
+        translations
+    }
+
+    /// The locales that have translations, in alphabetical order.
+    pub fn locales(&self) -> impl Iterator<Item = &str> {
+        self.locales.keys().map(String::as_str)
+    }
+
+    /// Returns the message for `key` in the locale, choosing the plural form for `count` if
+    /// it has plural forms.
+    pub fn get(
+        &self,
+        locale: &str,
+        key: &str,
+        count: Option<f64>,
+    ) -> Option<&str> {
+        match self.locales.get(locale)?.get(key)? {
+            Message::Text(text) => Some(text),
+            Message::Plural(forms) => {
+                let count = count.unwrap_or_default();
+                // an explicit `zero` form is used for 0, even in languages without a zero
+                // category, for messages like "Your cart is empty"
+                let category = if count == 0.0
+                    && forms.contains_key(&PluralCategory::Zero)
+                {
+                    PluralCategory::Zero
+                } else {
+                    PluralCategory::of(locale, count)
+                };
+                forms
+                    .get(&category)
+                    .or_else(|| forms.get(&PluralCategory::Other))
+                    .map(String::as_str)
+            }
+        }
+    }
+}
+
+fn flatten(
+    prefix: &str,
+    value: &Value,
+    out: &mut HashMap<String, Message>,
+) -> Result<(), (String, String)> {
+    match value {
+        Value::String(text) => {
+            out.insert(prefix.to_string(), Message::Text(text.clone()));
+        }
+        Value::Object(map)
+            if !prefix.is_empty()
+                && !map.is_empty()
+                && map.iter().all(|(key, value)| {
+                    PluralCategory::from_str(key).is_some() && value.is_string()
+                }) =>
+        {
+            let forms = map
+                .iter()
+                .filter_map(|(key, value)| {
+                    Some((
+                        PluralCategory::from_str(key)?,
+                        value.as_str()?.into(),
+                    ))
+                })
+                .collect::<HashMap<_, _>>();
+            if !forms.contains_key(&PluralCategory::Other) {
+                return Err((
+                    prefix.to_string(),
+                    "plural forms need an `other` form".into(),
+                ));
+            }
+            out.insert(prefix.to_string(), Message::Plural(forms));
+        }
+        Value::Object(map) => {
+            for (key, value) in map {
+                let key = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(&key, value, out)?;
+            }
+        }
+        _ => {
+            return Err((
+                prefix.to_string(),
+                format!("expected a string or an object, found `{value}`"),
+            ))
+        }
+    }
+    Ok(())
+}
+
+/// Fills in the `{placeholders}` in a message. `{{` and `}}` are escaped braces, and
+/// placeholders without an argument are left as they are.
+fn format_message(message: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut out = String::with_capacity(message.len());
+    let mut rest = message;
+    while let Some(idx) = rest.find(['{', '}']) {
+        out.push_str(&rest[..idx]);
+        let brace = &rest[idx..idx + 1];
+        rest = &rest[idx + 1..];
+        if let Some(after) = rest.strip_prefix(brace) {
+            out.push_str(brace);
+            rest = after;
+            continue;
+        }
+        let end = match (brace, rest.find('}')) {
+            ("{", Some(end)) => end,
+            _ => {
+                out.push_str(brace);
+                continue;
+            }
+        };
+        let name = rest[..end].trim();
+        match args.iter().find(|(arg, _)| *arg == name) {
+            Some((_, value)) => _ = write!(out, "{value}"),
+            None => {
+                out.push('{');
+                out.push_str(&rest[..=end]);
+            }
+        }
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+pub(crate) fn primary_subtag(tag: &str) -> &str {
+    tag.split(['-', '_']).next().unwrap_or(tag)
+}
+
+/// Chooses the best of the `available` locales for an `Accept-Language` header, or `None` if
+/// none of them were asked for.
+///
+/// Languages are tried in order of their quality values. A language matches a locale with the
+/// same tag, or otherwise one with the same primary language, so that `en-US` matches `en` and
+/// `en` matches `en-GB`.
+///
+/// ```rust
+/// # use leptos::i18n::negotiate_locale;
+/// let available = ["de", "en-GB", "fr"];
+/// assert_eq!(
+///     negotiate_locale("fr-CH, fr;q=0.9, en;q=0.8", available),
+///     Some("fr")
+/// );
+/// assert_eq!(negotiate_locale("en-US,es;q=0.5", available), Some("en-GB"));
+/// assert_eq!(negotiate_locale("ja", available), None);
+/// ```
+pub fn negotiate_locale<'a>(
+    accept_language: &str,
+    available: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let available = available.into_iter().collect::<Vec<_>>();
+    let mut requested = accept_language
+        .split(',')
+        .filter_map(|part| {
+            let mut params = part.split(';');
+            let tag = params.next()?.trim();
+            let quality = params
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())?;
+            (!tag.is_empty() && tag != "*" && quality > 0.0)
+                .then_some((tag, quality))
+        })
+        .collect::<Vec<_>>();
+    // a stable sort keeps the order of languages with the same quality
+    requested.sort_by(|a, b| b.1.total_cmp(&a.1));
+    requested
+        .into_iter()
+        .find_map(|(tag, _)| match_locale(tag, &available))
+}
+
+fn match_locale<'a>(tag: &str, available: &[&'a str]) -> Option<&'a str> {
+    let same = |a: &str, b: &str| a.replace('_', "-").eq_ignore_ascii_case(b);
+    available
+        .iter()
+        .find(|locale| same(locale, tag))
+        .or_else(|| {
+            available.iter().find(|locale| {
+                primary_subtag(locale).eq_ignore_ascii_case(primary_subtag(tag))
+            })
+        })
+        .copied()
+}
+
+struct I18nInner {
+    translations: Translations,
+    default_locale: String,
+}
+
+/// The current locale and the translations for each locale, provided via context by
+/// [`provide_i18n`].
+///
+/// Messages are looked up with [`t!`], or with [`I18n::translate`].
+#[derive(Clone, Copy)]
+pub struct I18n {
+    locale: RwSignal<String>,
+    inner: StoredValue<I18nInner>,
+}
+
+impl I18n {
+    /// The current locale.
+    pub fn locale(&self) -> ReadSignal<String> {
+        self.locale.read_only()
+    }
+
+    /// Changes the locale to the best match for `locale` among those with translations, or
+    /// to the default locale if there is none.
+    pub fn set_locale(&self, locale: &str) {
+        let locale = self.inner.with_value(|inner| {
+            let available = inner.translations.locales().collect::<Vec<_>>();
+            match_locale(locale, &available)
+                .unwrap_or(&inner.default_locale)
+                .to_string()
+        });
+        if self.locale.get_untracked() != locale {
+            self.locale.set(locale);
+        }
+    }
+
+    /// The locales that have translations.
+    pub fn locales(&self) -> Vec<String> {
+        self.inner.with_value(|inner| {
+            inner.translations.locales().map(String::from).collect()
+        })
+    }
+
+    /// Looks up the message for `key` in the current locale and fills in its placeholders,
+    /// choosing the plural form for `count` if it has plural forms.
+    ///
+    /// Messages that are missing from the current locale are taken from the default locale,
+    /// and if they are missing there too, the key itself is returned. This reads the locale,
+    /// so it is reactive.
+    pub fn translate(
+        &self,
+        key: &str,
+        count: Option<f64>,
+        args: &[(&str, &dyn Display)],
+    ) -> String {
+        self.locale.with(|locale| {
+            self.inner.with_value(|inner| {
+                let message = inner
+                    .translations
+                    .get(locale, key, count)
+                    .or_else(|| {
+                        inner.translations.get(
+                            &inner.default_locale,
+                            key,
+                            count,
+                        )
+                    })
+                    .unwrap_or(key);
+                format_message(message, args)
+            })
+        })
+    }
+}
+
+impl std::fmt::Debug for I18n {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("I18n")
+            .field("locale", &self.locale)
+            .finish()
+    }
+}
+
+/// Provides an [`I18n`] via context, and returns it.
+///
+/// The initial locale is negotiated from the `Accept-Language` header during server rendering,
+/// and hydrated from the locale chosen on the server in the browser, as described in the
+/// [module documentation](self). If neither matches one of the locales with translations,
+/// `default_locale` is used.
+pub fn provide_i18n(
+    translations: Translations,
+    default_locale: impl Into<String>,
+) -> I18n {
+    let default_locale = default_locale.into();
+    // the locale chosen on the server is serialized, so that hydration uses the same one
+    let sc = Owner::current_shared_context()
+        .filter(|sc| !sc.is_browser() || sc.during_hydration());
+    let id = sc.as_ref().map(|sc| sc.next_id());
+    let initial = {
+        let available = translations.locales().collect::<Vec<_>>();
+        let from_server = sc
+            .as_ref()
+            .zip(id.as_ref())
+            .filter(|(sc, _)| sc.is_browser())
+            .and_then(|(sc, id)| sc.read_data(id))
+            .and_then(|locale| match_locale(&locale, &available));
+        from_server
+            .or_else(|| initial_locale(&available))
+            .unwrap_or(&default_locale)
+            .to_string()
+    };
+    if let Some((sc, id)) = sc.zip(id) {
+        if !sc.is_browser() {
+            let locale = initial.clone();
+            sc.write_async(id, Box::pin(async move { locale }));
+        }
+    }
+    let i18n = I18n {
+        locale: RwSignal::new(initial),
+        inner: StoredValue::new(I18nInner {
+            translations,
+            default_locale,
+        }),
+    };
+    if cfg!(target_arch = "wasm32") {
+        Effect::new(move |_| {
+            let locale = i18n.locale.get();
+            if let Some(html) =
+                leptos_dom::helpers::document().document_element()
+            {
+                if html.get_attribute("lang").as_deref() != Some(&locale) {
+                    _ = html.set_attribute("lang", &locale);
+                }
+            }
+        });
+    }
+    provide_context(i18n);
+    i18n
+}
+
+fn initial_locale<'a>(available: &[&'a str]) -> Option<&'a str> {
+    if let Some(req) = use_context::<RequestMeta>() {
+        let accept_language = req.header("accept-language")?;
+        return negotiate_locale(accept_language, available.iter().copied());
+    }
+    if !cfg!(target_arch = "wasm32") {
+        return None;
+    }
+    let document = leptos_dom::helpers::document();
+    document
+        .document_element()
+        .and_then(|html| html.get_attribute("lang"))
+        .and_then(|lang| match_locale(&lang, available))
+        .or_else(|| {
+            let language =
+                leptos_dom::helpers::window().navigator().language()?;
+            match_locale(&language, available)
+        })
+}
+
+/// Returns the [`I18n`] provided by [`provide_i18n`].
+///
+/// # Panics
+/// Panics if there is no `I18n` in context.
+#[track_caller]
+pub fn use_i18n() -> I18n {
+    use_context()
+        .expect("Tried to access I18n outside of a call to `provide_i18n`.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reactive_graph::owner::Owner;
+
+    const EN: &str = r#"{
+        "greeting": "Hello, {name}!",
+        "cart": {
+            "items": { "zero": "Empty", "one": "{count} item", "other": "{count} items" }
+        },
+        "braces": "{{literal}} {missing}"
+    }"#;
+    const RU: &str = r#"{
+        "cart": {
+            "items": { "one": "{count} товар", "few": "{count} товара", "many": "{count} товаров", "other": "{count} товара" }
+        }
+    }"#;
+
+    #[test]
+    fn chooses_plural_categories() {
+        assert_eq!(PluralCategory::of("en-US", 1.0), PluralCategory::One);
+        assert_eq!(PluralCategory::of("en", 2.0), PluralCategory::Other);
+        assert_eq!(PluralCategory::of("fr", 0.0), PluralCategory::One);
+        assert_eq!(PluralCategory::of("ru", 21.0), PluralCategory::One);
+        assert_eq!(PluralCategory::of("ru", 23.0), PluralCategory::Few);
+        assert_eq!(PluralCategory::of("ru", 12.0), PluralCategory::Many);
+        assert_eq!(PluralCategory::of("pl", 22.0), PluralCategory::Few);
+        assert_eq!(PluralCategory::of("ja", 1.0), PluralCategory::Other);
+        assert_eq!(PluralCategory::of("ar", 2.0), PluralCategory::Two);
+    }
+
+    #[test]
+    fn rejects_invalid_translations() {
+        let mut translations = Translations::new();
+        assert!(matches!(
+            translations.add_json("en", "{"),
+            Err(TranslationsError::Json { .. })
+        ));
+        assert!(matches!(
+            translations.add_json("en", r#"{ "count": { "one": "1" } }"#),
+            Err(TranslationsError::Invalid { key, .. }) if key == "count"
+        ));
+        assert!(matches!(
+            translations.add_json("en", r#"{ "a": { "b": 1 } }"#),
+            Err(TranslationsError::Invalid { key, .. }) if key == "a.b"
+        ));
+    }
+
+    #[test]
+    fn translates_reactively() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let translations =
+                Translations::from_static(&[("en", EN), ("ru", RU)]);
+            let i18n = provide_i18n(translations, "en");
+            assert_eq!(i18n.locale().get_untracked(), "en");
+            assert_eq!(i18n.locales(), ["en", "ru"]);
+
+            assert_eq!(t!(i18n, "greeting", name = "Ferris"), "Hello, Ferris!");
+            assert_eq!(t!(i18n, "cart.items", count = 0), "Empty");
+            assert_eq!(t!(i18n, "cart.items", count = 1), "1 item");
+            assert_eq!(t!(i18n, "braces"), "{literal} {missing}");
+            assert_eq!(t!(i18n, "unknown"), "unknown");
+
+            use_i18n().set_locale("ru-RU");
+            assert_eq!(i18n.locale().get_untracked(), "ru");
+            assert_eq!(t!(i18n, "cart.items", count = 3), "3 товара");
+            assert_eq!(t!(i18n, "cart.items", count = 5), "5 товаров");
+            // falls back to the default locale
+            assert_eq!(t!(i18n, "greeting", name = "Ferris"), "Hello, Ferris!");
+
+            i18n.set_locale("de");
+            assert_eq!(i18n.locale().get_untracked(), "en");
+        });
+    }
+
+    #[test]
+    fn negotiates_the_locale_of_the_request() {
+        let owner = Owner::new();
+        owner.with(|| {
+            provide_context(RequestMeta::new(
+                "GET",
+                "/",
+                [("Accept-Language".into(), "de;q=0.5, ru".into())],
+                None,
+            ));
+            let translations =
+                Translations::from_static(&[("en", EN), ("ru", RU)]);
+            assert_eq!(
+                provide_i18n(translations, "en").locale().get_untracked(),
+                "ru"
+            );
+        });
+    }
+
+    #[test]
+    fn serializes_the_locale_chosen_on_the_server() {
+        use futures::StreamExt;
+        use hydration_context::{SharedContext, SsrSharedContext};
+        use std::sync::Arc;
+
+        let sc: Arc<dyn SharedContext + Send + Sync> =
+            Arc::new(SsrSharedContext::new());
+        let owner = Owner::new_root(Some(Arc::clone(&sc)));
+        owner.with(|| {
+            provide_context(RequestMeta::new(
+                "GET",
+                "/",
+                [("Accept-Language".into(), "ru".into())],
+                None,
+            ));
+            let translations =
+                Translations::from_static(&[("en", EN), ("ru", RU)]);
+            provide_i18n(translations, "en");
+        });
+        let data = futures::executor::block_on(
+            sc.pending_data().unwrap().collect::<String>(),
+        );
+        assert!(data.contains(r#""ru""#), "{data}");
+    }
+}
@@ -0,0 +1,72 @@
+use reactive_graph::owner::Owner;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The prefix of every ID generated by [`use_id`].
+pub const ID_PREFIX: &str = "leptos-id-";
+
+/// Generates an ID that is unique within the page, for attributes that refer to other
+/// elements, like `for`, `aria-labelledby` or `aria-describedby`.
+///
+/// IDs are generated in the order in which components are created, which is the same during
+/// server rendering and hydration, so the ID a component is given on the server is the one it
+/// gets again when it is hydrated. This avoids the hydration mismatches caused by random IDs.
+/// Components that are created later in the browser are given IDs from a separate sequence,
+/// so they never clash with the IDs from the server.
+///
+/// Call it once in the body of a component, rather than in a closure in the view, so that the
+/// ID stays the same when the view updates.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// # use leptos::id::use_id;
+/// #[component]
+/// fn Field(label: &'static str) -> impl IntoView {
+///     let id = use_id();
+///     view! {
+///         <label for=id.clone()>{label}</label>
+///         <input id=id/>
+///     }
+/// }
+/// ```
+pub fn use_id() -> String {
+    // IDs for components created in the browser after hydration
+    static CLIENT_IDS: AtomicUsize = AtomicUsize::new(0);
+
+    match Owner::current_shared_context() {
+        Some(sc) if !sc.is_browser() || sc.during_hydration() => {
+            let id = throw_error::ErrorId::from(sc.next_id());
+            format!("{ID_PREFIX}{id}")
+        }
+        _ => {
+            let id = CLIENT_IDS.fetch_add(1, Ordering::Relaxed);
+            format!("{ID_PREFIX}c{id}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hydration_context::{SharedContext, SsrSharedContext};
+    use std::sync::Arc;
+
+    #[test]
+    fn ids_follow_the_order_of_creation() {
+        let server = || {
+            let sc: Arc<dyn SharedContext + Send + Sync> =
+                Arc::new(SsrSharedContext::new());
+            let owner = Owner::new_root(Some(sc));
+            let ids = owner.with(|| (use_id(), use_id()));
+            owner.unset();
+            ids
+        };
+        let first = server();
+        assert_ne!(first.0, first.1);
+        // a new request gets the same IDs again
+        assert_eq!(server(), first);
+
+        let client = (use_id(), use_id());
+        assert!(client.0.starts_with("leptos-id-c"));
+        assert_ne!(client.0, client.1);
+    }
+}
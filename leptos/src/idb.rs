@@ -0,0 +1,447 @@
+use futures::channel::oneshot;
+use leptos_dom::helpers::window;
+use leptos_server::LocalResource;
+use reactive_graph::{
+    actions::Action,
+    signal::ArcTrigger,
+    traits::{Track, Trigger},
+};
+use send_wrapper::SendWrapper;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    future::Future,
+    rc::Rc,
+    sync::Arc,
+};
+use thiserror::Error;
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+use web_sys::{
+    EventTarget, IdbDatabase, IdbFactory, IdbObjectStore, IdbRequest,
+    IdbTransaction, IdbTransactionMode,
+};
+
+/// An error that occurred while using an [`IdbStore`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum IdbError {
+    /// IndexedDB cannot be used here, for example because this is running on the server.
+    #[error("IndexedDB is not available")]
+    Unavailable,
+    /// A request to IndexedDB failed.
+    #[error("IndexedDB request failed: {0}")]
+    Request(String),
+    /// A value could not be serialized or deserialized.
+    #[error("could not serialize or deserialize the value: {0}")]
+    Serialization(String),
+}
+
+// the database and object store names
+type StoreKey = (Arc<str>, Arc<str>);
+
+thread_local! {
+    // every store with the same database and name shares a trigger, so that a write through one
+    // `IdbStore` refetches the resources created by any other
+    static CHANGED: RefCell<HashMap<StoreKey, ArcTrigger>> =
+        Default::default();
+}
+
+/// Returns an [`IdbStore`] for the object store `store_name` in the IndexedDB database
+/// `db_name`.
+///
+/// The database and object store are created the first time they are used.
+///
+/// ```rust
+/// use leptos::{idb::use_idb_store, prelude::*};
+///
+/// #[component]
+/// fn Draft() -> impl IntoView {
+///     let drafts = use_idb_store("my-app", "drafts");
+///     let draft = drafts.resource::<String>(|| "post".to_string());
+///     let save = drafts.put_action::<String>();
+///
+///     view! {
+///         <Suspense fallback=|| "Loading...">
+///             {move || Suspend::new(async move {
+///                 let text = draft.await.ok().flatten().unwrap_or_default();
+///                 view! {
+///                     <textarea on:change=move |ev| {
+///                         save.dispatch(("post".to_string(), event_target_value(&ev)));
+///                     }>{text}</textarea>
+///                 }
+///             })}
+///         </Suspense>
+///     }
+/// }
+/// ```
+pub fn use_idb_store(
+    db_name: impl Into<String>,
+    store_name: impl Into<String>,
+) -> IdbStore {
+    IdbStore::new(db_name, store_name)
+}
+
+/// A key-value store backed by an [IndexedDB](https://developer.mozilla.org/en-US/docs/Web/API/IndexedDB_API)
+/// object store, for data that is too large for `localStorage` or needs to be available offline.
+///
+/// Keys are strings, and values are stored as JSON. Values can be read directly with
+/// [`get`](IdbStore::get), or reactively with [`resource`](IdbStore::resource), which is reloaded
+/// whenever the store is written to. Writes can be made directly or through the actions returned
+/// by [`put_action`](IdbStore::put_action) and [`delete_action`](IdbStore::delete_action).
+///
+/// IndexedDB is only available in the browser. On the server, every method returns
+/// [`IdbError::Unavailable`], and resources never resolve, so that their `<Suspense/>` fallback
+/// is rendered.
+#[derive(Debug, Clone)]
+pub struct IdbStore {
+    db_name: Arc<str>,
+    store_name: Arc<str>,
+    changed: ArcTrigger,
+}
+
+impl IdbStore {
+    /// Creates a store for the object store `store_name` in the database `db_name`.
+    pub fn new(
+        db_name: impl Into<String>,
+        store_name: impl Into<String>,
+    ) -> Self {
+        let db_name: Arc<str> = db_name.into().into();
+        let store_name: Arc<str> = store_name.into().into();
+        let changed = CHANGED.with(|changed| {
+            changed
+                .borrow_mut()
+                .entry((Arc::clone(&db_name), Arc::clone(&store_name)))
+                .or_default()
+                .clone()
+        });
+        Self {
+            db_name,
+            store_name,
+            changed,
+        }
+    }
+
+    /// Loads the value stored under `key`, if there is one.
+    pub async fn get<T>(&self, key: &str) -> Result<Option<T>, IdbError>
+    where
+        T: DeserializeOwned,
+    {
+        let key = JsValue::from_str(key);
+        let value = self
+            .run(IdbTransactionMode::Readonly, |store| store.get(&key))
+            .await?;
+        match value.as_string() {
+            Some(json) => serde_json::from_str(&json)
+                .map(Some)
+                .map_err(|e| IdbError::Serialization(e.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// Loads every key in the store.
+    pub async fn keys(&self) -> Result<Vec<String>, IdbError> {
+        let keys = self
+            .run(IdbTransactionMode::Readonly, |store| store.get_all_keys())
+            .await?;
+        Ok(js_sys::Array::from(&keys)
+            .iter()
+            .filter_map(|key| key.as_string())
+            .collect())
+    }
+
+    /// Stores `value` under `key`, replacing any previous value.
+    pub async fn put<T>(&self, key: &str, value: &T) -> Result<(), IdbError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let json = serde_json::to_string(value)
+            .map_err(|e| IdbError::Serialization(e.to_string()))?;
+        self.put_json(key, json).await
+    }
+
+    async fn put_json(&self, key: &str, json: String) -> Result<(), IdbError> {
+        let key = JsValue::from_str(key);
+        let value = JsValue::from_str(&json);
+        self.run(IdbTransactionMode::Readwrite, |store| {
+            store.put_with_key(&value, &key)
+        })
+        .await?;
+        self.changed.trigger();
+        Ok(())
+    }
+
+    /// Removes the value stored under `key`, if there is one.
+    pub async fn delete(&self, key: &str) -> Result<(), IdbError> {
+        let key = JsValue::from_str(key);
+        self.run(IdbTransactionMode::Readwrite, |store| store.delete(&key))
+            .await?;
+        self.changed.trigger();
+        Ok(())
+    }
+
+    /// Removes every value in the store.
+    pub async fn clear(&self) -> Result<(), IdbError> {
+        self.run(IdbTransactionMode::Readwrite, |store| store.clear())
+            .await?;
+        self.changed.trigger();
+        Ok(())
+    }
+
+    /// Creates a resource that loads the value stored under the key returned by `key`.
+    ///
+    /// The resource is reloaded when `key` changes, and whenever a value is written to or
+    /// deleted from this object store.
+    #[track_caller]
+    pub fn resource<T>(
+        &self,
+        key: impl Fn() -> String + 'static,
+    ) -> LocalResource<Result<Option<T>, IdbError>>
+    where
+        T: DeserializeOwned + 'static,
+    {
+        let this = self.clone();
+        LocalResource::new(move || {
+            this.changed.track();
+            let key = key();
+            let this = this.clone();
+            SendWrapper::new(async move { this.get(&key).await })
+        })
+    }
+
+    /// Creates an action that stores a value under a key, with a `(key, value)` pair as its
+    /// input.
+    #[track_caller]
+    pub fn put_action<T>(&self) -> Action<(String, T), Result<(), IdbError>>
+    where
+        T: Serialize + Send + Sync + 'static,
+    {
+        let this = self.clone();
+        Action::new_unsync(move |(key, value): &(String, T)| {
+            let this = this.clone();
+            let key = key.clone();
+            let json = serde_json::to_string(value)
+                .map_err(|e| IdbError::Serialization(e.to_string()));
+            async move { this.put_json(&key, json?).await }
+        })
+    }
+
+    /// Creates an action that removes the value stored under a key, with the key as its input.
+    #[track_caller]
+    pub fn delete_action(&self) -> Action<String, Result<(), IdbError>> {
+        let this = self.clone();
+        Action::new_unsync(move |key: &String| {
+            let this = this.clone();
+            let key = key.clone();
+            async move { this.delete(&key).await }
+        })
+    }
+
+    async fn run(
+        &self,
+        mode: IdbTransactionMode,
+        op: impl FnOnce(&IdbObjectStore) -> Result<IdbRequest, JsValue>,
+    ) -> Result<JsValue, IdbError> {
+        let factory = factory()?;
+        let db = open(&factory, &self.db_name, &self.store_name).await?;
+        let result = async {
+            let tx = db
+                .transaction_with_str_and_mode(&self.store_name, mode)
+                .map_err(js_error)?;
+            let store = tx.object_store(&self.store_name).map_err(js_error)?;
+            let req = op(&store).map_err(js_error)?;
+            // listen for the transaction to finish before awaiting the request, so that its
+            // `complete` event cannot be missed
+            let done = transaction_done(&tx);
+            let value = request_result(&req).await?;
+            done.await?;
+            Ok(value)
+        }
+        .await;
+        db.close();
+        result
+    }
+}
+
+fn factory() -> Result<IdbFactory, IdbError> {
+    if !cfg!(target_arch = "wasm32") {
+        return Err(IdbError::Unavailable);
+    }
+    window()
+        .indexed_db()
+        .ok()
+        .flatten()
+        .ok_or(IdbError::Unavailable)
+}
+
+async fn open(
+    factory: &IdbFactory,
+    db_name: &str,
+    store_name: &str,
+) -> Result<IdbDatabase, IdbError> {
+    let db = open_with_version(factory, db_name, store_name, None).await?;
+    if db.object_store_names().contains(store_name) {
+        return Ok(db);
+    }
+    // the database already exists without this object store, so it has to be upgraded
+    let version = db.version() as u32 + 1;
+    db.close();
+    open_with_version(factory, db_name, store_name, Some(version)).await
+}
+
+async fn open_with_version(
+    factory: &IdbFactory,
+    db_name: &str,
+    store_name: &str,
+    version: Option<u32>,
+) -> Result<IdbDatabase, IdbError> {
+    let req = match version {
+        Some(version) => factory.open_with_u32(db_name, version),
+        None => factory.open(db_name),
+    }
+    .map_err(js_error)?;
+
+    let mut listeners = Listeners::new(&req);
+    listeners.add("upgradeneeded", {
+        let req = req.clone();
+        let store_name = store_name.to_string();
+        move || {
+            if let Ok(db) = req.result() {
+                let db = db.unchecked_into::<IdbDatabase>();
+                if !db.object_store_names().contains(&store_name) {
+                    _ = db.create_object_store(&store_name);
+                }
+            }
+        }
+    });
+    let db = request_result(&req).await;
+    drop(listeners);
+    db.map(JsCast::unchecked_into)
+}
+
+fn request_result(
+    req: &IdbRequest,
+) -> impl Future<Output = Result<JsValue, IdbError>> {
+    let on_success = req.clone();
+    let on_error = req.clone();
+    first_event(
+        req,
+        vec![
+            (
+                "success",
+                Box::new(move || on_success.result().map_err(js_error)),
+            ),
+            (
+                "error",
+                Box::new(move || {
+                    Err(IdbError::Request(
+                        on_error
+                            .error()
+                            .ok()
+                            .flatten()
+                            .map(|e| e.message())
+                            .unwrap_or_else(|| "unknown error".to_string()),
+                    ))
+                }),
+            ),
+        ],
+    )
+}
+
+fn transaction_done(
+    tx: &IdbTransaction,
+) -> impl Future<Output = Result<(), IdbError>> {
+    let error = {
+        let tx = tx.clone();
+        move || {
+            Err(IdbError::Request(
+                tx.error()
+                    .map(|e| e.message())
+                    .unwrap_or_else(|| "the transaction was aborted".into()),
+            ))
+        }
+    };
+    first_event(
+        tx,
+        vec![
+            ("complete", Box::new(|| Ok(()))),
+            ("error", Box::new(error.clone())),
+            ("abort", Box::new(error)),
+        ],
+    )
+}
+
+type Outcome<T> = Box<dyn Fn() -> Result<T, IdbError>>;
+
+/// Resolves with the outcome of whichever of `events` fires first on `target`.
+///
+/// The listeners are added immediately, rather than when the future is first polled.
+fn first_event<T: 'static>(
+    target: &EventTarget,
+    events: Vec<(&'static str, Outcome<T>)>,
+) -> impl Future<Output = Result<T, IdbError>> {
+    let (tx, rx) = oneshot::channel();
+    let tx = Rc::new(Cell::new(Some(tx)));
+    let mut listeners = Listeners::new(target);
+    for (event, outcome) in events {
+        let tx = Rc::clone(&tx);
+        listeners.add(event, move || {
+            if let Some(tx) = tx.take() {
+                _ = tx.send(outcome());
+            }
+        });
+    }
+    async move {
+        let result = rx.await.unwrap_or_else(|_| {
+            Err(IdbError::Request("the request was cancelled".into()))
+        });
+        drop(listeners);
+        result
+    }
+}
+
+type Listener = (&'static str, Closure<dyn FnMut()>);
+
+/// Event listeners that are removed when this is dropped, so that they are never called after
+/// their closures have been freed.
+struct Listeners {
+    target: EventTarget,
+    closures: Vec<Listener>,
+}
+
+impl Listeners {
+    fn new(target: &EventTarget) -> Self {
+        Self {
+            target: target.clone(),
+            closures: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, event: &'static str, cb: impl FnMut() + 'static) {
+        let closure = Closure::<dyn FnMut()>::new(cb);
+        _ = self.target.add_event_listener_with_callback(
+            event,
+            closure.as_ref().unchecked_ref(),
+        );
+        self.closures.push((event, closure));
+    }
+}
+
+impl Drop for Listeners {
+    fn drop(&mut self) {
+        for (event, closure) in &self.closures {
+            _ = self.target.remove_event_listener_with_callback(
+                event,
+                closure.as_ref().unchecked_ref(),
+            );
+        }
+    }
+}
+
+fn js_error(err: JsValue) -> IdbError {
+    IdbError::Request(
+        err.dyn_ref::<js_sys::Error>()
+            .map(|e| String::from(e.message()))
+            .or_else(|| err.as_string())
+            .unwrap_or_else(|| format!("{err:?}")),
+    )
+}
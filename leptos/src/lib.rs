@@ -163,6 +163,7 @@ pub mod prelude {
         pub use crate::{
             callback::*, children::*, component::*, control_flow::*, error::*,
             form::*, hydration::*, into_view::*, mount::*, suspense::*,
+            timing::*,
         };
         pub use leptos_config::*;
         pub use leptos_dom::{helpers::*, *};
@@ -183,6 +184,12 @@ pub mod prelude {
     pub use export_types::*;
 }
 
+/// Animation frame loops and animated signals.
+pub mod animation;
+
+/// Announcing messages to screen reader users with ARIA live regions.
+pub mod announce;
+
 /// Components used for working with HTML forms, like `<ActionForm>`.
 pub mod form;
 
@@ -213,17 +220,67 @@ mod show;
 /// A component that allows rendering a component somewhere else.
 pub mod portal;
 
+/// Define custom elements (web components) that render Leptos views.
+pub mod custom_element;
+
+/// Undo and redo history for signals.
+pub mod history;
+
+/// A bridge that posts reactive graph events to a browser devtools extension.
+#[cfg(feature = "devtools")]
+pub mod devtools;
+
+/// Text direction, for languages that are written right to left.
+pub mod direction;
+
+/// Shows panics and errors in an overlay in development builds.
+pub mod error_overlay;
+
+/// Moving keyboard focus for dialogs, composite widgets and navigation.
+pub mod focus;
+
+/// Formatting numbers, dates and relative times for the current locale.
+pub mod format;
+
 /// Components to enable server-side rendering and client-side hydration.
 pub mod hydration;
 
+/// Translations and a reactive locale.
+pub mod i18n;
+
+/// Generating IDs that are the same on the server and the client.
+pub mod id;
+
+/// An async key-value store backed by IndexedDB.
+pub mod idb;
+
+/// Signals that track media queries and the size of elements.
+pub mod media;
+
 /// Utilities for exporting nonces to be used for a Content Security Policy.
 #[cfg(feature = "nonce")]
 pub mod nonce;
 
+/// Reading the HTTP request in server functions and during server rendering.
+pub mod request;
+
+/// Setting the status code and headers of the HTTP response during server rendering.
+pub mod response;
+
+/// Reactive values updated by server-sent events.
+pub mod sse;
+
+/// Signals that are persisted to `localStorage` or `sessionStorage`.
+pub mod storage;
+
+/// Debouncing and throttling signals and effects.
+pub mod timing;
+
 /// Components to load asynchronous data.
 pub mod suspense {
-    pub use crate::{suspense_component::*, transition::*};
+    pub use crate::{await_::*, suspense_component::*, transition::*};
 }
+mod await_;
 
 #[macro_use]
 mod suspense_component;
@@ -231,6 +288,9 @@ mod suspense_component;
 /// Types for reactive string properties for components.
 pub mod text_prop;
 mod transition;
+
+/// Wrapping updates in the browser's View Transitions API.
+pub mod view_transition;
 pub use leptos_macro::*;
 pub use server_fn;
 #[doc(hidden)]
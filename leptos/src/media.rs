@@ -0,0 +1,156 @@
+use leptos_dom::helpers::{is_browser, window};
+use reactive_graph::{
+    effect::Effect,
+    owner::Owner,
+    signal::RwSignal,
+    traits::{Get, Set},
+    wrappers::read::Signal,
+};
+use send_wrapper::SendWrapper;
+use tachys::{html::element::ElementType, reactive_graph::node_ref::NodeRef};
+use wasm_bindgen::{prelude::Closure, JsCast};
+use web_sys::{MediaQueryListEvent, ResizeObserver, ResizeObserverEntry};
+
+/// Runs `f` now, unless the app is hydrating, in which case it runs once hydration is complete so
+/// that the first render in the browser matches the server-rendered HTML.
+pub(crate) fn after_hydration(f: impl FnOnce() + 'static) {
+    let hydrating = Owner::current_shared_context()
+        .map(|sc| sc.during_hydration())
+        .unwrap_or(false);
+    if hydrating {
+        let mut f = Some(f);
+        Effect::new(move |_| {
+            if let Some(f) = f.take() {
+                f();
+            }
+        });
+    } else {
+        f();
+    }
+}
+
+/// Returns a signal that is `true` while the [media query](https://developer.mozilla.org/en-US/docs/Web/CSS/CSS_media_queries/Using_media_queries)
+/// matches, such as `"(max-width: 600px)"` or `"(prefers-color-scheme: dark)"`.
+///
+/// On the server, and until hydration is complete, the signal is `false`. The listener on the
+/// media query is removed when the current owner is disposed.
+///
+/// ```rust
+/// use leptos::{media::use_media_query, prelude::*};
+///
+/// #[component]
+/// fn Nav() -> impl IntoView {
+///     let is_mobile = use_media_query("(max-width: 600px)");
+///     view! {
+///         <Show when=move || is_mobile.get() fallback=|| view! { <nav>"Full menu"</nav> }>
+///             <button>"Menu"</button>
+///         </Show>
+///     }
+/// }
+/// ```
+pub fn use_media_query(query: impl Into<String>) -> Signal<bool> {
+    let matches = RwSignal::new(false);
+    if is_browser() {
+        let query = query.into();
+        after_hydration(move || {
+            let Ok(Some(list)) = window().match_media(&query) else {
+                return;
+            };
+            matches.set(list.matches());
+
+            let on_change = Closure::<dyn Fn(MediaQueryListEvent)>::new(
+                move |ev: MediaQueryListEvent| matches.set(ev.matches()),
+            );
+            _ = list.add_event_listener_with_callback(
+                "change",
+                on_change.as_ref().unchecked_ref(),
+            );
+            let listener = SendWrapper::new((list, on_change));
+            Owner::on_cleanup(move || {
+                let (list, on_change) = listener.take();
+                _ = list.remove_event_listener_with_callback(
+                    "change",
+                    on_change.as_ref().unchecked_ref(),
+                );
+            });
+        });
+    }
+    matches.read_only().into()
+}
+
+/// The size of an element, as returned by [`use_element_size`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ElementSize {
+    /// The width of the element's content box, in CSS pixels.
+    pub width: Signal<f64>,
+    /// The height of the element's content box, in CSS pixels.
+    pub height: Signal<f64>,
+}
+
+/// Returns signals with the size of the element loaded into `node_ref`, updated by a
+/// [`ResizeObserver`](https://developer.mozilla.org/en-US/docs/Web/API/ResizeObserver) whenever
+/// it changes.
+///
+/// On the server, and until the element has been mounted, both dimensions are `0.0`. If another
+/// element is loaded into `node_ref`, that element is observed instead. The observer is
+/// disconnected when the current owner is disposed.
+///
+/// ```rust
+/// use leptos::{html, media::use_element_size, prelude::*};
+///
+/// #[component]
+/// fn Panel() -> impl IntoView {
+///     let panel = NodeRef::<html::Div>::new();
+///     let size = use_element_size(panel);
+///     view! {
+///         <div node_ref=panel>
+///             {move || format!("{} x {}", size.width.get(), size.height.get())}
+///         </div>
+///     }
+/// }
+/// ```
+pub fn use_element_size<E>(node_ref: NodeRef<E>) -> ElementSize
+where
+    E: ElementType + 'static,
+    E::Output: JsCast + Clone + 'static,
+{
+    let width = RwSignal::new(0.0);
+    let height = RwSignal::new(0.0);
+    if is_browser() {
+        // effects run after the view has been mounted, and again if another element is loaded
+        Effect::new(move |_| {
+            let Some(el) = node_ref.get() else {
+                return;
+            };
+            let on_resize = Closure::<dyn Fn(js_sys::Array)>::new(
+                move |entries: js_sys::Array| {
+                    if let Some(entry) =
+                        entries.iter().last().and_then(|entry| {
+                            entry.dyn_into::<ResizeObserverEntry>().ok()
+                        })
+                    {
+                        let rect = entry.content_rect();
+                        width.set(rect.width());
+                        height.set(rect.height());
+                    }
+                },
+            );
+            let Ok(observer) =
+                ResizeObserver::new(on_resize.as_ref().unchecked_ref())
+            else {
+                return;
+            };
+            // the observer reports the element's size as soon as it starts observing it
+            observer.observe(el.unchecked_ref());
+            let observer = SendWrapper::new((observer, on_resize));
+            Owner::on_cleanup(move || {
+                let (observer, _on_resize) = observer.take();
+                observer.disconnect();
+            });
+        });
+    }
+    ElementSize {
+        width: width.read_only().into(),
+        height: height.read_only().into(),
+    }
+}
@@ -1,6 +1,10 @@
 use crate::{logging, IntoView};
 use any_spawner::Executor;
-use reactive_graph::owner::Owner;
+use reactive_graph::{
+    effect::{defer_effects, Effect},
+    owner::Owner,
+    untrack,
+};
 #[cfg(debug_assertions)]
 use std::cell::Cell;
 use std::marker::PhantomData;
@@ -65,16 +69,20 @@ where
             );
         }
         FIRST_CALL.set(false);
+        crate::error_overlay::install_panic_hook();
     }
 
     // create a new reactive owner and use it as the root node to run the app
     let owner = Owner::new_root(Some(Arc::new(HydrateSharedContext::new())));
+    // effects created while hydrating the view wait until it has been hydrated
     let mountable = owner.with(move || {
-        let view = f().into_view();
-        view.hydrate::<true>(
-            &Cursor::new(parent.unchecked_into()),
-            &PositionState::default(),
-        )
+        defer_effects(move || {
+            let view = f().into_view();
+            view.hydrate::<true>(
+                &Cursor::new(parent.unchecked_into()),
+                &PositionState::default(),
+            )
+        })
     });
 
     if let Some(sc) = Owner::current_shared_context() {
@@ -125,15 +133,19 @@ where
             );
         }
         FIRST_CALL.set(false);
+        crate::error_overlay::install_panic_hook();
     }
 
     // create a new reactive owner and use it as the root node to run the app
     let owner = Owner::new();
+    // effects created while building the view wait until it has been mounted
     let mountable = owner.with(move || {
-        let view = f().into_view();
-        let mut mountable = view.build();
-        mountable.mount(&parent, None);
-        mountable
+        defer_effects(move || {
+            let view = f().into_view();
+            let mut mountable = view.build();
+            mountable.mount(&parent, None);
+            mountable
+        })
     });
 
     // returns a handle that owns the owner
@@ -162,11 +174,14 @@ where
 
     // create a new reactive owner and use it as the root node to run the app
     let owner = Owner::new();
+    // effects created while building the view wait until it has been mounted
     let mountable = owner.with(move || {
-        let view = f();
-        let mut mountable = view.build();
-        mountable.mount(parent, None);
-        mountable
+        defer_effects(move || {
+            let view = f();
+            let mut mountable = view.build();
+            mountable.mount(parent, None);
+            mountable
+        })
     });
 
     // returns a handle that owns the owner
@@ -190,7 +205,10 @@ pub fn hydrate_islands() {
     _ = Executor::init_wasm_bindgen();
 
     #[cfg(debug_assertions)]
-    FIRST_CALL.set(false);
+    {
+        FIRST_CALL.set(false);
+        crate::error_overlay::install_panic_hook();
+    }
 
     // create a new reactive owner and use it as the root node to run the app
     let sc = HydrateSharedContext::new();
@@ -198,6 +216,91 @@ pub fn hydrate_islands() {
     let owner = Owner::new_root(Some(Arc::new(sc)));
     owner.set();
     std::mem::forget(owner);
+
+    register_islands_navigation();
+    #[cfg(debug_assertions)]
+    register_hot_patching();
+}
+
+/// Lets the islands router script patch the page it has fetched into the document, with the same
+/// [`morph_document`](tachys::morph::morph_document) that is used everywhere else.
+#[cfg(feature = "hydrate")]
+fn register_islands_navigation() {
+    use wasm_bindgen::{prelude::Closure, JsValue};
+
+    let morph =
+        Closure::<dyn Fn(web_sys::Document)>::new(|doc: web_sys::Document| {
+            tachys::morph::morph_document(&doc)
+        });
+    _ = js_sys::Reflect::set(
+        &tachys::dom::window(),
+        &JsValue::from_str("__leptos_morph_document"),
+        morph.as_ref(),
+    );
+    morph.forget();
+}
+
+/// Lets the live-reload script patch the page with new server-rendered HTML, without reloading
+/// the WASM and losing the state of the app.
+///
+/// This is only done for islands apps, in which everything outside of the islands is static HTML
+/// that can be patched without touching any state. If the islands on the page would change, the
+/// page is reloaded instead. In an app that hydrates the whole `<body>`, the DOM belongs to the
+/// view tree, so it is always reloaded.
+#[cfg(all(feature = "hydrate", debug_assertions))]
+fn register_hot_patching() {
+    use wasm_bindgen::{prelude::Closure, JsValue};
+
+    let morph = Closure::<dyn Fn(String)>::new(|html: String| {
+        if !tachys::morph::morph_static(&body(), &html) {
+            _ = tachys::dom::window().location().reload();
+        }
+    });
+    _ = js_sys::Reflect::set(
+        &tachys::dom::window(),
+        &JsValue::from_str("__leptos_morph"),
+        morph.as_ref(),
+    );
+    morph.forget();
+}
+
+/// Runs `fun` once, after the view that is being built has been mounted.
+///
+/// This is the counterpart of [`on_cleanup`](reactive_graph::owner::on_cleanup), which runs when
+/// the current [`Owner`] is disposed of. Together they can set up and tear down anything that
+/// needs the mounted DOM, like an observer or a subscription:
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// #[component]
+/// fn Resizable() -> impl IntoView {
+///     let el = NodeRef::<leptos::html::Div>::new();
+///     on_mount(move || {
+///         // the element is in the document, so it can be measured or observed here
+///         let _el = el.get_untracked();
+///         on_cleanup(|| {
+///             // stop observing the element
+///         });
+///     });
+///     view! { <div node_ref=el></div> }
+/// }
+/// ```
+///
+/// Reactive values read inside `fun` are not tracked, so it never runs again. Cleanups
+/// registered inside it run when the component that called `on_mount` is disposed of, which
+/// includes removing the row of a [`For`](crate::control_flow::For) it is part of.
+///
+/// Like an [`Effect`](reactive_graph::effect::Effect), this only runs in the browser.
+#[track_caller]
+pub fn on_mount(fun: impl FnOnce() + 'static) {
+    let mut fun = Some(fun);
+    // the effect's owner is a child of the current owner, so cleanups registered while it runs
+    // are run when the current owner is cleaned up
+    Effect::new(move |_| {
+        if let Some(fun) = fun.take() {
+            untrack(fun);
+        }
+    });
 }
 
 /// On drop, this will clean up the reactive [`Owner`] and unmount the view created by
@@ -1,8 +1,10 @@
-use crate::{children::TypedChildrenFn, mount, IntoView};
+use crate::{children::TypedChildrenFn, IntoView};
 use leptos_dom::helpers::document;
 use leptos_macro::component;
-use reactive_graph::{effect::Effect, owner::Owner, untrack};
-use std::sync::Arc;
+use reactive_graph::{owner::Owner, untrack};
+use send_wrapper::SendWrapper;
+use tachys::{renderer::dom::Dom, view::portal::portal};
+use wasm_bindgen::JsCast;
 
 /// Renders components somewhere else in the DOM.
 ///
@@ -10,6 +12,9 @@ use std::sync::Arc;
 /// If no mount point is given, the portal is inserted in `document.body`;
 /// it is wrapped in a `<div>` unless  `is_svg` is `true` in which case it's wrappend in a `<g>`.
 /// Setting `use_shadow` to `true` places the element in a shadow root to isolate styles.
+///
+/// This is a [`portal`] from `tachys`, so it leaves a placeholder where it appears in the view.
+/// The children are only rendered in the browser.
 #[cfg_attr(
     any(debug_assertions, feature = "ssr"),
     tracing::instrument(level = "trace", skip_all)
@@ -31,54 +36,45 @@ pub fn Portal<V>(
 where
     V: IntoView + 'static,
 {
-    if cfg!(target_arch = "wasm32")
-        && Owner::current_shared_context()
-            .map(|sc| sc.is_browser())
-            .unwrap_or(true)
-    {
-        use send_wrapper::SendWrapper;
-        use wasm_bindgen::JsCast;
+    // an element can only be created in the browser, and the target is only used there
+    let mount = mount.map(SendWrapper::new);
+    let children = children.into_inner();
 
-        let mount = mount.unwrap_or_else(|| {
+    let target = move || {
+        let mount = mount.map(SendWrapper::take).unwrap_or_else(|| {
             document().body().expect("body to exist").unchecked_into()
         });
-        let children = children.into_inner();
+        let tag = if is_svg { "g" } else { "div" };
 
-        Effect::new(move |_| {
-            let tag = if is_svg { "g" } else { "div" };
+        let container = document()
+            .create_element(tag)
+            .expect("element creation to work");
+        // the container is not part of the server-rendered HTML, so it is kept if the page
+        // is patched with new HTML
+        _ = container.set_attribute(tachys::morph::CLIENT_ONLY_ATTR, "");
 
-            let container = document()
-                .create_element(tag)
-                .expect("element creation to work");
+        let render_root = if use_shadow {
+            container
+                .attach_shadow(&web_sys::ShadowRootInit::new(
+                    web_sys::ShadowRootMode::Open,
+                ))
+                .map(|root| root.unchecked_into())
+                .unwrap_or(container.clone())
+        } else {
+            container.clone()
+        };
 
-            let render_root = if use_shadow {
-                container
-                    .attach_shadow(&web_sys::ShadowRootInit::new(
-                        web_sys::ShadowRootMode::Open,
-                    ))
-                    .map(|root| root.unchecked_into())
-                    .unwrap_or(container.clone())
-            } else {
-                container.clone()
-            };
+        _ = mount.append_child(&container);
+        // the portal removes its children from the container, and the container is removed
+        // along with the component
+        let container = SendWrapper::new((mount, container));
+        Owner::on_cleanup(move || {
+            let (mount, container) = container.take();
+            _ = mount.remove_child(&container);
+        });
 
-            let _ = mount.append_child(&container);
-            let handle = SendWrapper::new((
-                mount::mount_to(render_root.unchecked_into(), {
-                    let children = Arc::clone(&children);
-                    move || untrack(|| children())
-                }),
-                mount.clone(),
-                container,
-            ));
+        render_root
+    };
 
-            Owner::on_cleanup({
-                move || {
-                    let (handle, mount, container) = handle.take();
-                    drop(handle);
-                    let _ = mount.remove_child(&container);
-                }
-            })
-        });
-    }
+    portal::<_, _, Dom>(target, move || untrack(|| children()))
 }
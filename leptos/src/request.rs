@@ -0,0 +1,266 @@
+use crate::context::{provide_context, use_context};
+use server_fn::ServerFnError;
+use std::{net::SocketAddr, sync::Arc};
+
+/// The parts of the current HTTP request that server functions and components commonly need, in
+/// a way that does not depend on the server framework.
+///
+/// The server integrations provide a `RequestMeta` via context during server rendering and
+/// while running a server function. Use [`extract`] to read typed values from it, such as
+/// [`Cookies`] or the client's address with [`ConnectInfo`], so that server functions work with
+/// any integration. In the browser it is not provided.
+///
+/// ```rust
+/// use leptos::{
+///     prelude::*,
+///     request::{extract, ConnectInfo, Cookies, RequestMeta},
+/// };
+///
+/// #[server]
+/// pub async fn whoami() -> Result<String, ServerFnError> {
+///     let cookies: Cookies = extract()?;
+///     let ConnectInfo(addr) = extract()?;
+///     let req: RequestMeta = extract()?;
+///     let agent = req.header("user-agent").unwrap_or("unknown");
+///     Ok(format!(
+///         "{:?} from {addr} using {agent}",
+///         cookies.get("session")
+///     ))
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct RequestMeta(Arc<RequestMetaInner>);
+
+#[derive(Debug)]
+struct RequestMetaInner {
+    method: String,
+    uri: String,
+    headers: Vec<(String, String)>,
+    remote_addr: Option<SocketAddr>,
+}
+
+impl RequestMeta {
+    /// Creates the metadata for a request. This is used by the server integrations.
+    pub fn new(
+        method: impl Into<String>,
+        uri: impl Into<String>,
+        headers: impl IntoIterator<Item = (String, String)>,
+        remote_addr: Option<SocketAddr>,
+    ) -> Self {
+        Self(Arc::new(RequestMetaInner {
+            method: method.into(),
+            uri: uri.into(),
+            headers: headers.into_iter().collect(),
+            remote_addr,
+        }))
+    }
+
+    /// The HTTP method, such as `GET`.
+    pub fn method(&self) -> &str {
+        &self.0.method
+    }
+
+    /// The request URI, as sent by the client: usually the path and query.
+    pub fn uri(&self) -> &str {
+        &self.0.uri
+    }
+
+    /// The path of the request URI, without the query.
+    pub fn path(&self) -> &str {
+        let uri = self.uri();
+        uri.split_once('?').map_or(uri, |(path, _)| path)
+    }
+
+    /// The first value of a header, with a case-insensitive name.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.0
+            .headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Every value of a header, with a case-insensitive name.
+    pub fn headers<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a str> + 'a {
+        self.0
+            .headers
+            .iter()
+            .filter(move |(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The address of the client, if it is known.
+    pub fn remote_addr(&self) -> Option<SocketAddr> {
+        self.0.remote_addr
+    }
+}
+
+/// Returns the [`RequestMeta`] for the current request, if it has been provided by a server
+/// integration.
+pub fn use_request_meta() -> Option<RequestMeta> {
+    use_context::<RequestMeta>()
+}
+
+/// Provides a [`RequestMeta`] via context.
+///
+/// This is called by the server integrations, and only needs to be used in a custom handler.
+pub fn provide_request_meta(meta: RequestMeta) {
+    provide_context(meta);
+}
+
+/// Values, like a database pool or the user's session, that are provided via context for a
+/// single request, both while rendering and while running a server function.
+///
+/// Server middleware adds a `RequestContext` to the extensions of the request, and the server
+/// integrations provide each of its values via context before the app or server function runs,
+/// so that they can be read with [`use_context`] anywhere, including in nested routes. As they
+/// are stored in the request itself, this works however the server's routers are nested. They
+/// are provided before the additional context of a `_with_context` handler, so a value of the
+/// same type in the additional context takes precedence.
+///
+/// With Axum, values that are shared by every request can be added with an `Extension` layer,
+/// and values for each request with middleware:
+///
+/// ```rust,ignore
+/// use axum::{extract::Request, middleware::Next, Extension};
+/// use leptos::request::RequestContext;
+///
+/// async fn session(mut req: Request, next: Next) -> Response {
+///     let session = load_session(&req).await;
+///     req.extensions_mut()
+///         .get_or_insert_default::<RequestContext>()
+///         .insert(session);
+///     next.run(req).await
+/// }
+///
+/// let app = Router::new()
+///     .leptos_routes(&leptos_options, routes, App)
+///     .layer(axum::middleware::from_fn(session))
+///     .layer(Extension(RequestContext::new().with(pool)));
+/// ```
+#[derive(Clone, Default)]
+pub struct RequestContext(Vec<Arc<dyn Fn() + Send + Sync>>);
+
+impl RequestContext {
+    /// Creates an empty set of values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a value, which will be provided via context.
+    pub fn insert<T>(&mut self, value: T)
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        self.0
+            .push(Arc::new(move || provide_context(value.clone())));
+    }
+
+    /// Adds a value, which will be provided via context, and returns the set of values.
+    pub fn with<T>(mut self, value: T) -> Self
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        self.insert(value);
+        self
+    }
+
+    /// Provides each of the values via context, in the order they were added.
+    ///
+    /// This is called by the server integrations, and only needs to be used in a custom handler.
+    pub fn provide(&self) {
+        for provide in &self.0 {
+            provide();
+        }
+    }
+}
+
+impl std::fmt::Debug for RequestContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestContext")
+            .field("len", &self.0.len())
+            .finish()
+    }
+}
+
+/// A value that can be read from the current request with [`extract`].
+pub trait FromRequestMeta: Sized {
+    /// Reads the value from the request.
+    fn from_request_meta(req: &RequestMeta) -> Result<Self, ServerFnError>;
+}
+
+/// Reads a typed value from the current request, such as [`Cookies`] or [`ConnectInfo`].
+///
+/// This works with any server integration. Returns an error if it is not called on the server
+/// with a [`RequestMeta`] provided, or if the value is not in the request.
+pub fn extract<T: FromRequestMeta>() -> Result<T, ServerFnError> {
+    let req = use_request_meta().ok_or_else(|| {
+        ServerFnError::new(
+            "should have had RequestMeta provided by the server integration",
+        )
+    })?;
+    T::from_request_meta(&req)
+}
+
+impl FromRequestMeta for RequestMeta {
+    fn from_request_meta(req: &RequestMeta) -> Result<Self, ServerFnError> {
+        Ok(req.clone())
+    }
+}
+
+impl<T: FromRequestMeta> FromRequestMeta for Option<T> {
+    fn from_request_meta(req: &RequestMeta) -> Result<Self, ServerFnError> {
+        Ok(T::from_request_meta(req).ok())
+    }
+}
+
+/// The cookies sent with the request, from every `Cookie` header.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Cookies(pub Vec<(String, String)>);
+
+impl Cookies {
+    /// The value of the first cookie with this name.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl FromRequestMeta for Cookies {
+    fn from_request_meta(req: &RequestMeta) -> Result<Self, ServerFnError> {
+        Ok(Cookies(
+            req.headers("cookie")
+                .flat_map(|header| header.split(';'))
+                .filter_map(|cookie| {
+                    let (name, value) = cookie.trim().split_once('=')?;
+                    let value = value.trim();
+                    let value = value
+                        .strip_prefix('"')
+                        .and_then(|value| value.strip_suffix('"'))
+                        .unwrap_or(value);
+                    Some((name.trim().to_string(), value.to_string()))
+                })
+                .collect(),
+        ))
+    }
+}
+
+/// The address of the client that made the request.
+///
+/// With Axum, this is only known if the app is served with
+/// `into_make_service_with_connect_info::<SocketAddr>()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConnectInfo(pub SocketAddr);
+
+impl FromRequestMeta for ConnectInfo {
+    fn from_request_meta(req: &RequestMeta) -> Result<Self, ServerFnError> {
+        req.remote_addr().map(ConnectInfo).ok_or_else(|| {
+            ServerFnError::new("the address of the client is not known")
+        })
+    }
+}
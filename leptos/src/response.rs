@@ -0,0 +1,107 @@
+use crate::context::{provide_context, use_context};
+use or_poisoned::OrPoisoned;
+use std::sync::{Arc, RwLock};
+
+/// The status code and headers that should be set on the HTTP response, in a way that does not
+/// depend on the server framework.
+///
+/// During server rendering, and while running a server function, the server integrations
+/// provide a `ResponseMeta` via context and apply it to the response once the app's shell has
+/// been rendered. This means that components and server functions can set the status, add
+/// headers, and set cookies without using any types from Axum or Actix. In the browser it is
+/// not provided, so [`use_response_meta`] returns `None`.
+///
+/// Changes made after the first chunk of a streamed response has been sent, for example inside
+/// a `<Suspense/>` that resolves later, cannot be applied. To set the status from async data,
+/// use `SsrMode::Async` or render it before the `<Suspense/>`.
+///
+/// ```rust
+/// use leptos::{prelude::*, response::use_response_meta};
+///
+/// #[component]
+/// fn NotFound() -> impl IntoView {
+///     if let Some(res) = use_response_meta() {
+///         res.set_status(404);
+///         res.insert_header("cache-control", "no-store");
+///     }
+///     view! { <h1>"Not Found"</h1> }
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ResponseMeta(Arc<RwLock<ResponseMetaParts>>);
+
+/// The contents of a [`ResponseMeta`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResponseMetaParts {
+    /// The status code, if it has been set.
+    pub status: Option<u16>,
+    /// Headers to add to the response, in the order they were added. A header appears more than
+    /// once if it was appended.
+    pub headers: Vec<(String, String)>,
+}
+
+impl ResponseMeta {
+    /// Sets the status code of the response.
+    pub fn set_status(&self, status: u16) {
+        self.0.write().or_poisoned().status = Some(status);
+    }
+
+    /// The status code that has been set, if any.
+    pub fn status(&self) -> Option<u16> {
+        self.0.read().or_poisoned().status
+    }
+
+    /// Inserts a header, replacing any previous values with the same (case-insensitive) name.
+    pub fn insert_header(
+        &self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) {
+        let name = name.into();
+        let mut parts = self.0.write().or_poisoned();
+        parts
+            .headers
+            .retain(|(existing, _)| !existing.eq_ignore_ascii_case(&name));
+        parts.headers.push((name, value.into()));
+    }
+
+    /// Appends a header, leaving any previous values with the same name intact.
+    pub fn append_header(
+        &self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) {
+        self.0
+            .write()
+            .or_poisoned()
+            .headers
+            .push((name.into(), value.into()));
+    }
+
+    /// Adds a `Set-Cookie` header, such as `"theme=dark; Path=/; HttpOnly"`.
+    pub fn set_cookie(&self, cookie: impl Into<String>) {
+        self.append_header("set-cookie", cookie);
+    }
+
+    /// Removes and returns the status and headers that have been set.
+    ///
+    /// This is used by the server integrations to apply them to the response.
+    pub fn take(&self) -> ResponseMetaParts {
+        std::mem::take(&mut *self.0.write().or_poisoned())
+    }
+}
+
+/// Returns the [`ResponseMeta`] for the current response, if it has been provided by a server
+/// integration.
+pub fn use_response_meta() -> Option<ResponseMeta> {
+    use_context::<ResponseMeta>()
+}
+
+/// Provides a new [`ResponseMeta`] via context, and returns it.
+///
+/// This is called by the server integrations, and only needs to be used in a custom handler.
+pub fn provide_response_meta() -> ResponseMeta {
+    let meta = ResponseMeta::default();
+    provide_context(meta.clone());
+    meta
+}
@@ -3,8 +3,23 @@ use crate::{
     IntoView,
 };
 use leptos_macro::component;
-use reactive_graph::{computed::ArcMemo, traits::Get};
-use tachys::either::Either;
+use reactive_graph::{
+    computed::ArcMemo,
+    effect::RenderEffect,
+    traits::{Get, GetUntracked},
+};
+use std::marker::PhantomData;
+use tachys::{
+    either::Either,
+    html::attribute::Attribute,
+    hydration::Cursor,
+    renderer::{CastFrom, Renderer},
+    ssr::StreamBuilder,
+    view::{
+        add_attr::AddAnyAttr, Mountable, Position, PositionState, Render,
+        RenderHtml,
+    },
+};
 
 #[component]
 pub fn Show<W, C>(
@@ -15,6 +30,14 @@ pub fn Show<W, C>(
     /// A closure that returns what gets rendered if the when statement is false. By default this is the empty view.
     #[prop(optional, into)]
     fallback: ViewFn,
+    /// If `true`, both the children and the fallback are created once and kept alive, and the
+    /// inactive one is taken out of the DOM rather than dropped. This preserves state like the
+    /// contents of form inputs when toggling back and forth.
+    ///
+    /// Like other control-flow components, the position of the branches is marked with an empty
+    /// comment, rather than a wrapper element.
+    #[prop(optional)]
+    keep_alive: bool,
 ) -> impl IntoView
 where
     W: Fn() -> bool + Send + Sync + 'static,
@@ -23,8 +46,359 @@ where
     let memoized_when = ArcMemo::new(move |_| when());
     let children = children.into_inner();
 
-    move || match memoized_when.get() {
-        true => Either::Left(children()),
-        false => Either::Right(fallback.run()),
+    if keep_alive {
+        Either::Left(KeepAlive {
+            when: memoized_when,
+            children: children(),
+            fallback: fallback.run(),
+            rndr: PhantomData,
+        })
+    } else {
+        Either::Right(move || match memoized_when.get() {
+            true => Either::Left(children()),
+            false => Either::Right(fallback.run()),
+        })
+    }
+}
+
+struct KeepAlive<Chil, Fal, Rndr> {
+    when: ArcMemo<bool>,
+    children: Chil,
+    fallback: Fal,
+    rndr: PhantomData<Rndr>,
+}
+
+struct KeepAliveState<Chil, Fal, Rndr>
+where
+    Rndr: Renderer,
+{
+    // both branches are always present, but only the one that is shown is mounted
+    shown: bool,
+    children: Chil,
+    fallback: Fal,
+    marker: Rndr::Placeholder,
+}
+
+impl<Chil, Fal, Rndr> KeepAliveState<Chil, Fal, Rndr>
+where
+    Chil: Mountable<Rndr>,
+    Fal: Mountable<Rndr>,
+    Rndr: Renderer,
+{
+    fn show(&mut self, shown: bool) {
+        if self.shown == shown {
+            return;
+        }
+        self.shown = shown;
+
+        // if the view itself isn't mounted, the new branch is mounted along with it later
+        let Some(parent) = Rndr::get_parent(self.marker.as_ref())
+            .and_then(Rndr::Element::cast_from)
+        else {
+            return;
+        };
+        let marker = Some(self.marker.as_ref());
+        if shown {
+            self.fallback.unmount();
+            self.children.mount(&parent, marker);
+        } else {
+            self.children.unmount();
+            self.fallback.mount(&parent, marker);
+        }
+    }
+}
+
+impl<Chil, Fal, Rndr> Mountable<Rndr> for KeepAliveState<Chil, Fal, Rndr>
+where
+    Chil: Mountable<Rndr>,
+    Fal: Mountable<Rndr>,
+    Rndr: Renderer,
+{
+    fn unmount(&mut self) {
+        if self.shown {
+            self.children.unmount();
+        } else {
+            self.fallback.unmount();
+        }
+        self.marker.unmount();
+    }
+
+    fn mount(&mut self, parent: &Rndr::Element, marker: Option<&Rndr::Node>) {
+        if self.shown {
+            self.children.mount(parent, marker);
+        } else {
+            self.fallback.mount(parent, marker);
+        }
+        self.marker.mount(parent, marker);
+    }
+
+    fn insert_before_this(&self, child: &mut dyn Mountable<Rndr>) -> bool {
+        let inserted = if self.shown {
+            self.children.insert_before_this(child)
+        } else {
+            self.fallback.insert_before_this(child)
+        };
+        inserted || self.marker.insert_before_this(child)
+    }
+}
+
+impl<Chil, Fal, Rndr> Render<Rndr> for KeepAlive<Chil, Fal, Rndr>
+where
+    Chil: Render<Rndr> + 'static,
+    Fal: Render<Rndr> + 'static,
+    Rndr: Renderer,
+{
+    type State = RenderEffect<KeepAliveState<Chil::State, Fal::State, Rndr>>;
+
+    fn build(self) -> Self::State {
+        let KeepAlive {
+            when,
+            children,
+            fallback,
+            ..
+        } = self;
+        let mut views = Some((children, fallback));
+        RenderEffect::new(
+            move |prev: Option<
+                KeepAliveState<Chil::State, Fal::State, Rndr>,
+            >| {
+                let shown = when.get();
+                if let Some(mut state) = prev {
+                    state.show(shown);
+                    state
+                } else {
+                    let (children, fallback) = views.take().unwrap();
+                    KeepAliveState {
+                        shown,
+                        children: children.build(),
+                        fallback: fallback.build(),
+                        marker: Rndr::create_placeholder(),
+                    }
+                }
+            },
+        )
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        let new = self.build();
+        let mut old = std::mem::replace(state, new);
+        old.insert_before_this(state);
+        old.unmount();
+    }
+}
+
+impl<Chil, Fal, Rndr> AddAnyAttr<Rndr> for KeepAlive<Chil, Fal, Rndr>
+where
+    Chil: RenderHtml<Rndr> + 'static,
+    Fal: RenderHtml<Rndr> + 'static,
+    Rndr: Renderer,
+{
+    type Output<SomeNewAttr: Attribute<Rndr>> = KeepAlive<
+        Chil::Output<SomeNewAttr::CloneableOwned>,
+        Fal::Output<SomeNewAttr::CloneableOwned>,
+        Rndr,
+    >;
+
+    fn add_any_attr<NewAttr: Attribute<Rndr>>(
+        self,
+        attr: NewAttr,
+    ) -> Self::Output<NewAttr>
+    where
+        Self::Output<NewAttr>: RenderHtml<Rndr>,
+    {
+        let KeepAlive {
+            when,
+            children,
+            fallback,
+            rndr,
+        } = self;
+        let attr = attr.into_cloneable_owned();
+        KeepAlive {
+            when,
+            children: children.add_any_attr(attr.clone()),
+            fallback: fallback.add_any_attr(attr),
+            rndr,
+        }
+    }
+}
+
+impl<Chil, Fal, Rndr> RenderHtml<Rndr> for KeepAlive<Chil, Fal, Rndr>
+where
+    Chil: RenderHtml<Rndr> + 'static,
+    Fal: RenderHtml<Rndr> + 'static,
+    Rndr: Renderer,
+{
+    type AsyncOutput = KeepAlive<Chil::AsyncOutput, Fal::AsyncOutput, Rndr>;
+
+    const MIN_LENGTH: usize = 0;
+
+    fn dry_resolve(&mut self) {
+        self.children.dry_resolve();
+        self.fallback.dry_resolve();
+    }
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        let KeepAlive {
+            when,
+            children,
+            fallback,
+            rndr,
+        } = self;
+        let (children, fallback) =
+            futures::join!(children.resolve(), fallback.resolve());
+        KeepAlive {
+            when,
+            children,
+            fallback,
+            rndr,
+        }
+    }
+
+    fn to_html_with_buf(
+        self,
+        buf: &mut String,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: bool,
+    ) {
+        // only the branch that is shown is rendered; the other one is built when hydrating
+        if self.when.get_untracked() {
+            self.children.to_html_with_buf(
+                buf,
+                position,
+                escape,
+                mark_branches,
+            );
+        } else {
+            self.fallback.to_html_with_buf(
+                buf,
+                position,
+                escape,
+                mark_branches,
+            );
+        }
+        buf.push_str("<!>");
+    }
+
+    fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+        self,
+        buf: &mut StreamBuilder,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: bool,
+    ) where
+        Self: Sized,
+    {
+        if self.when.get_untracked() {
+            self.children.to_html_async_with_buf::<OUT_OF_ORDER>(
+                buf,
+                position,
+                escape,
+                mark_branches,
+            );
+        } else {
+            self.fallback.to_html_async_with_buf::<OUT_OF_ORDER>(
+                buf,
+                position,
+                escape,
+                mark_branches,
+            );
+        }
+        buf.push_sync("<!>");
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        cursor: &Cursor<Rndr>,
+        position: &PositionState,
+    ) -> Self::State {
+        let KeepAlive {
+            when,
+            children,
+            fallback,
+            ..
+        } = self;
+        let mut views = Some((children, fallback));
+        let cursor = cursor.to_owned();
+        let position = position.to_owned();
+        RenderEffect::new(
+            move |prev: Option<
+                KeepAliveState<Chil::State, Fal::State, Rndr>,
+            >| {
+                let shown = when.get();
+                if let Some(mut state) = prev {
+                    state.show(shown);
+                    state
+                } else {
+                    let (children, fallback) = views.take().unwrap();
+                    let (children, fallback) = if shown {
+                        (
+                            children.hydrate::<FROM_SERVER>(&cursor, &position),
+                            fallback.build(),
+                        )
+                    } else {
+                        (
+                            children.build(),
+                            fallback.hydrate::<FROM_SERVER>(&cursor, &position),
+                        )
+                    };
+                    let marker = cursor.next_placeholder(&position);
+                    KeepAliveState {
+                        shown,
+                        children,
+                        fallback,
+                        marker,
+                    }
+                }
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeepAlive;
+    use any_spawner::Executor;
+    use reactive_graph::{computed::ArcMemo, signal::RwSignal, traits::*};
+    use std::marker::PhantomData;
+    use tachys::{
+        html::element::input,
+        renderer::mock_dom::MockDom,
+        testing::{find_by_role, mount_to, test_dom},
+    };
+
+    #[tokio::test]
+    async fn keep_alive_keeps_the_hidden_branch() {
+        _ = Executor::init_tokio();
+        tokio::task::LocalSet::new()
+            .run_until(async {
+                let open = RwSignal::new(true);
+                let view = KeepAlive::<_, _, MockDom> {
+                    when: ArcMemo::new(move |_| open.get()),
+                    children: input(),
+                    fallback: "closed",
+                    rndr: PhantomData,
+                };
+                let mounted = mount_to(&test_dom(), ("before", view));
+                assert_eq!(
+                    mounted.html(),
+                    "<body>before<input></input><!></body>"
+                );
+                let el = find_by_role(mounted.root(), "textbox").unwrap();
+
+                open.set(false);
+                Executor::tick().await;
+                assert_eq!(mounted.html(), "<body>beforeclosed<!></body>");
+
+                // the same element is mounted again, rather than a new one
+                open.set(true);
+                Executor::tick().await;
+                assert_eq!(
+                    mounted.html(),
+                    "<body>before<input></input><!></body>"
+                );
+                assert_eq!(find_by_role(mounted.root(), "textbox"), Some(el));
+            })
+            .await;
     }
 }
@@ -0,0 +1,350 @@
+use leptos_dom::helpers::{is_browser, set_timeout_with_handle, TimeoutHandle};
+use reactive_graph::{
+    owner::Owner,
+    signal::{ArcReadSignal, ArcRwSignal},
+    traits::{GetUntracked, Set},
+};
+use send_wrapper::SendWrapper;
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    rc::{Rc, Weak},
+    sync::Arc,
+    time::Duration,
+};
+use wasm_bindgen::{prelude::Closure, JsCast};
+use web_sys::{EventSource, EventSourceInit, MessageEvent};
+
+/// The state of the connection used by an [`SseSignal`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SseState {
+    /// Connecting, or reconnecting after the connection was lost.
+    #[default]
+    Connecting,
+    /// Connected and receiving events.
+    Open,
+    /// Not connected. The signal will try to reconnect after a delay, unless it has been closed.
+    Closed,
+}
+
+/// Options for an [`SseSignal`].
+#[derive(Debug, Clone)]
+pub struct SseOptions {
+    event: String,
+    reconnect_delay: Option<Duration>,
+    with_credentials: bool,
+}
+
+impl Default for SseOptions {
+    fn default() -> Self {
+        Self {
+            event: "message".to_string(),
+            reconnect_delay: Some(Duration::from_secs(3)),
+            with_credentials: false,
+        }
+    }
+}
+
+impl SseOptions {
+    /// The type of event to listen for. Defaults to `"message"`, which is used for events sent
+    /// without an `event:` field.
+    pub fn event(mut self, event: impl Into<String>) -> Self {
+        self.event = event.into();
+        self
+    }
+
+    /// How long to wait before reconnecting if the server closes the connection with an error,
+    /// or `None` to stay disconnected. Defaults to three seconds.
+    pub fn reconnect_delay(mut self, delay: Option<Duration>) -> Self {
+        self.reconnect_delay = delay;
+        self
+    }
+
+    /// Whether to send cookies with cross-origin requests. Defaults to `false`.
+    pub fn with_credentials(mut self, with_credentials: bool) -> Self {
+        self.with_credentials = with_credentials;
+        self
+    }
+}
+
+/// A reactive value that is updated by [server-sent events](https://developer.mozilla.org/en-US/docs/Web/API/Server-sent_events).
+///
+/// Each event is parsed into a `T` by the function passed to [`SseSignal::new`]; events that
+/// fail to parse are ignored. The connection is opened in the browser when the signal is
+/// created, and closed when its owner is disposed or [`SseSignal::close`] is called.
+///
+/// On the server, and while hydrating, the value is `None` until the first event arrives, so
+/// the server-rendered HTML always matches the first render in the browser.
+///
+/// ## Reconnection
+/// The browser reconnects automatically if the connection drops, and sends the ID of the last
+/// event it received in the `Last-Event-ID` header. If the server instead closes the connection
+/// with an error, the signal reconnects itself after the
+/// [`reconnect_delay`](SseOptions::reconnect_delay). As headers cannot be set on an
+/// `EventSource`, the last event ID is then sent in a `lastEventId` query parameter.
+///
+/// ```rust
+/// use leptos::{prelude::*, sse::SseSignal};
+///
+/// #[component]
+/// fn Price() -> impl IntoView {
+///     let price = SseSignal::new("/api/prices", |data| data.parse::<f64>().ok());
+///     let value = price.value();
+///     view! {
+///         <p>
+///             {move || match value.get() {
+///                 Some(price) => format!("${price:.2}"),
+///                 None => "Loading...".to_string(),
+///             }}
+///         </p>
+///     }
+/// }
+/// ```
+pub struct SseSignal<T: 'static> {
+    value: ArcReadSignal<Option<T>>,
+    state: ArcReadSignal<SseState>,
+    last_event_id: ArcReadSignal<Option<String>>,
+    // only set in the browser, so that the wrapper is never dropped on another thread
+    connection: Option<Arc<SendWrapper<Rc<Connection<T>>>>>,
+}
+
+impl<T> Clone for SseSignal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            state: self.state.clone(),
+            last_event_id: self.last_event_id.clone(),
+            connection: self.connection.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for SseSignal<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SseSignal")
+            .field("state", &self.state)
+            .field("last_event_id", &self.last_event_id)
+            .finish_non_exhaustive()
+    }
+}
+
+type ParseFn<T> = Box<dyn Fn(&str) -> Option<T>>;
+
+struct Connection<T: 'static> {
+    url: String,
+    options: SseOptions,
+    parse: ParseFn<T>,
+    value: ArcRwSignal<Option<T>>,
+    state: ArcRwSignal<SseState>,
+    last_event_id: ArcRwSignal<Option<String>>,
+    source: RefCell<Option<EventSource>>,
+    retry: Cell<Option<TimeoutHandle>>,
+    closed: Cell<bool>,
+}
+
+impl<T> Drop for Connection<T> {
+    fn drop(&mut self) {
+        disconnect(self);
+    }
+}
+
+impl<T> SseSignal<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Connects to the event stream at `url`, parsing the data of each event with `parse`.
+    pub fn new(
+        url: impl Into<String>,
+        parse: impl Fn(&str) -> Option<T> + 'static,
+    ) -> Self {
+        Self::new_with_options(url, SseOptions::default(), parse)
+    }
+
+    /// Connects to the event stream at `url` with the given options, parsing the data of each
+    /// event with `parse`.
+    pub fn new_with_options(
+        url: impl Into<String>,
+        options: SseOptions,
+        parse: impl Fn(&str) -> Option<T> + 'static,
+    ) -> Self {
+        let value = ArcRwSignal::new(None);
+        let state = ArcRwSignal::new(SseState::Connecting);
+        let last_event_id = ArcRwSignal::new(None);
+
+        let connection = is_browser().then(|| {
+            let connection = Rc::new(Connection {
+                url: url.into(),
+                options,
+                parse: Box::new(parse),
+                value: value.clone(),
+                state: state.clone(),
+                last_event_id: last_event_id.clone(),
+                source: RefCell::new(None),
+                retry: Cell::new(None),
+                closed: Cell::new(false),
+            });
+            connect(&connection);
+            Arc::new(SendWrapper::new(connection))
+        });
+        let this = Self {
+            value: value.read_only(),
+            state: state.read_only(),
+            last_event_id: last_event_id.read_only(),
+            connection,
+        };
+
+        Owner::on_cleanup({
+            let this = this.clone();
+            move || this.close()
+        });
+        this
+    }
+}
+
+impl<T> SseSignal<T> {
+    /// The value parsed from the most recent event, if any have been received.
+    pub fn value(&self) -> ArcReadSignal<Option<T>> {
+        self.value.clone()
+    }
+
+    /// The state of the connection.
+    pub fn state(&self) -> ArcReadSignal<SseState> {
+        self.state.clone()
+    }
+
+    /// The ID of the most recent event that had one.
+    pub fn last_event_id(&self) -> ArcReadSignal<Option<String>> {
+        self.last_event_id.clone()
+    }
+
+    /// Closes the connection. It will not be reopened.
+    pub fn close(&self) {
+        // the connection can only be used on the thread that created it
+        if let Some(connection) = self
+            .connection
+            .as_ref()
+            .filter(|connection| connection.valid())
+        {
+            connection.closed.set(true);
+            disconnect(connection);
+            connection.state.set(SseState::Closed);
+        }
+    }
+}
+
+fn disconnect<T>(connection: &Connection<T>) {
+    if let Some(retry) = connection.retry.take() {
+        retry.clear();
+    }
+    if let Some(source) = connection.source.take() {
+        source.close();
+    }
+}
+
+fn connect<T>(connection: &Rc<Connection<T>>)
+where
+    T: Send + Sync + 'static,
+{
+    if connection.closed.get() {
+        return;
+    }
+    disconnect(connection);
+
+    let url = match connection.last_event_id.get_untracked() {
+        Some(id) => {
+            let sep = if connection.url.contains('?') {
+                '&'
+            } else {
+                '?'
+            };
+            format!(
+                "{}{sep}lastEventId={}",
+                connection.url,
+                js_sys::encode_uri_component(&id)
+            )
+        }
+        None => connection.url.clone(),
+    };
+    let init = EventSourceInit::new();
+    init.set_with_credentials(connection.options.with_credentials);
+    let Ok(source) = EventSource::new_with_event_source_init_dict(&url, &init)
+    else {
+        connection.state.set(SseState::Closed);
+        return;
+    };
+    connection.state.set(SseState::Connecting);
+
+    let weak = Rc::downgrade(connection);
+    let on_open = Closure::<dyn Fn()>::new({
+        let weak = Weak::clone(&weak);
+        move || {
+            if let Some(connection) = weak.upgrade() {
+                connection.state.set(SseState::Open);
+            }
+        }
+    })
+    .into_js_value();
+    let on_event = Closure::<dyn Fn(MessageEvent)>::new({
+        let weak = Weak::clone(&weak);
+        move |ev: MessageEvent| {
+            let Some(connection) = weak.upgrade() else {
+                return;
+            };
+            let id = ev.last_event_id();
+            if !id.is_empty() {
+                connection.last_event_id.set(Some(id));
+            }
+            if let Some(value) = ev
+                .data()
+                .as_string()
+                .and_then(|data| (connection.parse)(&data))
+            {
+                connection.value.set(Some(value));
+            }
+        }
+    })
+    .into_js_value();
+    let on_error = Closure::<dyn Fn()>::new({
+        let weak = Weak::clone(&weak);
+        move || {
+            let Some(connection) = weak.upgrade() else {
+                return;
+            };
+            let closed = connection
+                .source
+                .borrow()
+                .as_ref()
+                .map(|source| source.ready_state() == EventSource::CLOSED)
+                .unwrap_or(true);
+            if !closed {
+                // the browser is reconnecting by itself
+                connection.state.set(SseState::Connecting);
+                return;
+            }
+            connection.state.set(SseState::Closed);
+            if let Some(delay) = connection.options.reconnect_delay {
+                let weak = Rc::downgrade(&connection);
+                let retry = set_timeout_with_handle(
+                    move || {
+                        if let Some(connection) = weak.upgrade() {
+                            connect(&connection);
+                        }
+                    },
+                    delay,
+                );
+                connection.retry.set(retry.ok());
+            }
+        }
+    })
+    .into_js_value();
+
+    _ = source
+        .add_event_listener_with_callback("open", on_open.unchecked_ref());
+    _ = source.add_event_listener_with_callback(
+        &connection.options.event,
+        on_event.unchecked_ref(),
+    );
+    _ = source
+        .add_event_listener_with_callback("error", on_error.unchecked_ref());
+    *connection.source.borrow_mut() = Some(source);
+}
@@ -0,0 +1,233 @@
+use crate::ev;
+use leptos_dom::helpers::{
+    is_browser, set_timeout_with_handle, window, window_event_listener,
+    window_event_listener_untyped, TimeoutHandle,
+};
+use reactive_graph::{
+    effect::Effect,
+    owner::Owner,
+    signal::{signal, ReadSignal, WriteSignal},
+    traits::{Set, With},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// Which [Web Storage](https://developer.mozilla.org/en-US/docs/Web/API/Web_Storage_API) area a
+/// persistent signal is saved to.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StorageKind {
+    /// `localStorage`, which is shared between tabs and kept when the browser is closed.
+    #[default]
+    Local,
+    /// `sessionStorage`, which is kept for the lifetime of a single tab.
+    Session,
+}
+
+impl StorageKind {
+    fn storage(self) -> Option<web_sys::Storage> {
+        let window = window();
+        match self {
+            StorageKind::Local => window.local_storage(),
+            StorageKind::Session => window.session_storage(),
+        }
+        .ok()
+        .flatten()
+    }
+}
+
+/// Options for [`create_persistent_signal_with_options`].
+#[derive(Debug, Clone)]
+pub struct PersistOptions {
+    storage: StorageKind,
+    debounce: Duration,
+}
+
+impl Default for PersistOptions {
+    fn default() -> Self {
+        Self {
+            storage: StorageKind::Local,
+            debounce: Duration::from_millis(100),
+        }
+    }
+}
+
+impl PersistOptions {
+    /// The storage area to use. Defaults to [`StorageKind::Local`].
+    pub fn storage(mut self, storage: StorageKind) -> Self {
+        self.storage = storage;
+        self
+    }
+
+    /// How long to wait after the last change before writing the value to storage. Defaults to
+    /// 100 milliseconds.
+    pub fn debounce(mut self, delay: Duration) -> Self {
+        self.debounce = delay;
+        self
+    }
+}
+
+/// Creates a signal whose value is saved to `localStorage` under `key`, as JSON.
+///
+/// See [`create_persistent_signal_with_options`] for details.
+///
+/// ```rust
+/// use leptos::{prelude::*, storage::create_persistent_signal};
+///
+/// #[component]
+/// fn ThemeToggle() -> impl IntoView {
+///     let (dark, set_dark) = create_persistent_signal("dark-mode", false);
+///     view! {
+///         <button on:click=move |_| set_dark.update(|dark| *dark = !*dark)>
+///             {move || if dark.get() { "Light mode" } else { "Dark mode" }}
+///         </button>
+///     }
+/// }
+/// ```
+pub fn create_persistent_signal<T>(
+    key: impl Into<String>,
+    default: T,
+) -> (ReadSignal<T>, WriteSignal<T>)
+where
+    T: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    create_persistent_signal_with_options(
+        key,
+        default,
+        PersistOptions::default(),
+    )
+}
+
+/// Creates a signal whose value is saved to Web Storage under `key`, as JSON.
+///
+/// The signal starts with `default` on the server and during hydration, so that the
+/// server-rendered HTML always matches the first render in the browser. Once the app has
+/// hydrated (or immediately, in a client-side rendered app), the stored value is loaded, if there
+/// is one that can be deserialized. Changes are written back after the
+/// [`debounce`](PersistOptions::debounce) delay, and any pending write is flushed when the
+/// signal's owner is disposed or the page is hidden.
+///
+/// With [`StorageKind::Local`], changes made in other tabs are applied to the signal as they
+/// happen. If the key is removed in another tab, the signal is reset to `default`.
+pub fn create_persistent_signal_with_options<T>(
+    key: impl Into<String>,
+    default: T,
+    options: PersistOptions,
+) -> (ReadSignal<T>, WriteSignal<T>)
+where
+    T: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    let (value, set_value) = signal(default.clone());
+
+    if !is_browser() {
+        return (value, set_value);
+    }
+
+    let key = key.into();
+    let PersistOptions { storage, debounce } = options;
+
+    // effects only run once hydration is complete, so the stored value is loaded after the first
+    // render rather than during it
+    Effect::new({
+        let key = key.clone();
+        move |_| {
+            if let Some(stored) = storage
+                .storage()
+                .and_then(|area| area.get_item(&key).ok().flatten())
+                .and_then(|json| serde_json::from_str::<T>(&json).ok())
+            {
+                set_value.set(stored);
+            }
+
+            let handle = window_event_listener(ev::storage, {
+                let key = key.clone();
+                let default = default.clone();
+                move |ev| {
+                    if ev.storage_area() != storage.storage() {
+                        return;
+                    }
+                    match ev.key() {
+                        Some(changed) if changed == key => {
+                            match ev.new_value() {
+                                Some(json) => {
+                                    if let Ok(new_value) =
+                                        serde_json::from_str::<T>(&json)
+                                    {
+                                        set_value.set(new_value);
+                                    }
+                                }
+                                None => set_value.set(default.clone()),
+                            }
+                        }
+                        // the whole storage area was cleared
+                        None => set_value.set(default.clone()),
+                        Some(_) => {}
+                    }
+                }
+            });
+            Owner::on_cleanup(move || handle.remove());
+        }
+    });
+
+    let writer = Arc::new(Writer {
+        key,
+        storage,
+        pending: Mutex::new(None),
+        timer: Mutex::new(None),
+    });
+    Effect::watch(
+        move || value.with(|value| serde_json::to_string(value).ok()),
+        {
+            let writer = Arc::clone(&writer);
+            move |json: &Option<String>, _, _| {
+                if let Some(json) = json {
+                    writer.schedule(json.clone(), debounce);
+                }
+            }
+        },
+        false,
+    );
+
+    let handle = window_event_listener_untyped("pagehide", {
+        let writer = Arc::clone(&writer);
+        move |_| writer.flush()
+    });
+    Owner::on_cleanup(move || {
+        handle.remove();
+        writer.flush();
+    });
+
+    (value, set_value)
+}
+
+struct Writer {
+    key: String,
+    storage: StorageKind,
+    pending: Mutex<Option<String>>,
+    timer: Mutex<Option<TimeoutHandle>>,
+}
+
+impl Writer {
+    fn schedule(self: &Arc<Self>, json: String, delay: Duration) {
+        *self.pending.lock().unwrap() = Some(json);
+        let mut timer = self.timer.lock().unwrap();
+        if let Some(timer) = timer.take() {
+            timer.clear();
+        }
+        let this = Arc::clone(self);
+        *timer = set_timeout_with_handle(move || this.flush(), delay).ok();
+    }
+
+    fn flush(&self) {
+        if let Some(timer) = self.timer.lock().unwrap().take() {
+            timer.clear();
+        }
+        if let Some(json) = self.pending.lock().unwrap().take() {
+            if let Some(area) = self.storage.storage() {
+                _ = area.set_item(&self.key, &json);
+            }
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use leptos_dom::helpers::{set_timeout_with_handle, TimeoutHandle};
+use reactive_graph::{
+    effect::Effect,
+    owner::{LocalStorage, Owner},
+    signal::RwSignal,
+    traits::{Get, Set},
+    untrack,
+    wrappers::read::Signal,
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// Creates an effect that calls `handler` with the value returned by `deps` once it has stopped
+/// changing for `delay`.
+///
+/// Like [`Effect::watch`], `deps` is tracked, and `handler` is not. It is only called for
+/// changes, not with the initial value. Any pending call is cancelled when the current owner is
+/// disposed. As with other effects, this does nothing on the server.
+///
+/// ```rust
+/// use leptos::{prelude::*, timing::watch_debounced};
+/// use std::time::Duration;
+///
+/// #[component]
+/// fn Search() -> impl IntoView {
+///     let (query, set_query) = signal(String::new());
+///     watch_debounced(
+///         move || query.get(),
+///         |query| leptos::logging::log!("searching for {query}"),
+///         Duration::from_millis(300),
+///     );
+///     view! { <input on:input=move |ev| set_query.set(event_target_value(&ev))/> }
+/// }
+/// ```
+pub fn watch_debounced<D>(
+    deps: impl FnMut() -> D + 'static,
+    handler: impl Fn(&D) + 'static,
+    delay: Duration,
+) -> Effect<LocalStorage>
+where
+    D: Clone + 'static,
+{
+    let timer = cancel_on_cleanup();
+    let handler = Rc::new(handler);
+    Effect::watch(
+        deps,
+        move |value: &D, _, _| {
+            let mut timer = timer.lock().unwrap();
+            if let Some(timer) = timer.take() {
+                timer.clear();
+            }
+            let value = value.clone();
+            let handler = Rc::clone(&handler);
+            *timer =
+                set_timeout_with_handle(move || handler(&value), delay).ok();
+        },
+        false,
+    )
+}
+
+/// Creates an effect that calls `handler` with the value returned by `deps` when it changes, at
+/// most once every `interval`.
+///
+/// The first change is handled immediately. Changes made during the following `interval` are
+/// handled together, with the latest value, once it has passed. Like [`Effect::watch`], `deps`
+/// is tracked, and `handler` is not. Any pending call is cancelled when the current owner is
+/// disposed. As with other effects, this does nothing on the server.
+pub fn watch_throttled<D>(
+    deps: impl FnMut() -> D + 'static,
+    handler: impl Fn(&D) + 'static,
+    interval: Duration,
+) -> Effect<LocalStorage>
+where
+    D: Clone + 'static,
+{
+    let throttle = Rc::new(Throttle {
+        handler: Box::new(handler),
+        interval,
+        pending: RefCell::new(None),
+        waiting: Cell::new(false),
+        timer: cancel_on_cleanup(),
+    });
+    Effect::watch(deps, move |value: &D, _, _| throttle.call(value), false)
+}
+
+fn cancel_on_cleanup() -> Arc<Mutex<Option<TimeoutHandle>>> {
+    let timer = Arc::new(Mutex::new(None::<TimeoutHandle>));
+    Owner::on_cleanup({
+        let timer = Arc::clone(&timer);
+        move || {
+            if let Some(timer) = timer.lock().unwrap().take() {
+                timer.clear();
+            }
+        }
+    });
+    timer
+}
+
+type Handler<D> = Box<dyn Fn(&D)>;
+
+struct Throttle<D> {
+    handler: Handler<D>,
+    interval: Duration,
+    pending: RefCell<Option<D>>,
+    waiting: Cell<bool>,
+    timer: Arc<Mutex<Option<TimeoutHandle>>>,
+}
+
+impl<D: Clone + 'static> Throttle<D> {
+    fn call(self: &Rc<Self>, value: &D) {
+        if self.waiting.get() {
+            *self.pending.borrow_mut() = Some(value.clone());
+            return;
+        }
+        (self.handler)(value);
+
+        self.waiting.set(true);
+        let this = Rc::clone(self);
+        *self.timer.lock().unwrap() = set_timeout_with_handle(
+            move || {
+                this.waiting.set(false);
+                let pending = this.pending.take();
+                if let Some(value) = pending {
+                    this.call(&value);
+                }
+            },
+            self.interval,
+        )
+        .ok();
+    }
+}
+
+/// Debounces and throttles signals.
+///
+/// This is implemented for every signal type, and returns a new read-only signal that follows
+/// the original one more slowly. It starts with the original signal's current value. On the
+/// server, where effects do not run, it keeps that value.
+///
+/// ```rust
+/// use leptos::{prelude::*, timing::SignalTimingExt};
+/// use std::time::Duration;
+///
+/// #[component]
+/// fn Search() -> impl IntoView {
+///     let (query, set_query) = signal(String::new());
+///     // only search once the user has stopped typing for 300ms
+///     let debounced = query.debounce(Duration::from_millis(300));
+///     let results = LocalResource::new(move || {
+///         let query = debounced.get();
+///         async move { format!("results for {query}") }
+///     });
+///     view! {
+///         <input on:input=move |ev| set_query.set(event_target_value(&ev))/>
+///         <Suspense>{move || Suspend::new(async move { results.await })}</Suspense>
+///     }
+/// }
+/// ```
+pub trait SignalTimingExt: Get
+where
+    Self::Value: Send + Sync + 'static,
+{
+    /// Returns a signal that only takes the value of this one once it has stopped changing for
+    /// `delay`.
+    fn debounce(self, delay: Duration) -> Signal<Self::Value>;
+
+    /// Returns a signal that takes the value of this one at most once every `interval`.
+    ///
+    /// See [`watch_throttled`] for exactly when it is updated.
+    fn throttle(self, interval: Duration) -> Signal<Self::Value>;
+}
+
+impl<S> SignalTimingExt for S
+where
+    S: Get + 'static,
+    S::Value: Send + Sync + 'static,
+{
+    fn debounce(self, delay: Duration) -> Signal<Self::Value> {
+        let value = RwSignal::new(untrack(|| self.get()));
+        watch_debounced(
+            move || self.get(),
+            move |new_value| value.set(new_value.clone()),
+            delay,
+        );
+        value.read_only().into()
+    }
+
+    fn throttle(self, interval: Duration) -> Signal<Self::Value> {
+        let value = RwSignal::new(untrack(|| self.get()));
+        watch_throttled(
+            move || self.get(),
+            move |new_value| value.set(new_value.clone()),
+            interval,
+        );
+        value.read_only().into()
+    }
+}
@@ -1,19 +1,14 @@
 use crate::{
     children::{TypedChildren, ViewFnOnce},
-    suspense_component::SuspenseBoundary,
     IntoView,
 };
 use leptos_macro::component;
 use reactive_graph::{
-    computed::{suspense::SuspenseContext, ArcMemo},
     effect::Effect,
-    owner::{provide_context, Owner},
-    signal::ArcRwSignal,
-    traits::{Get, Set, Track, With},
+    traits::{Get, Set},
     wrappers::write::SignalSetter,
 };
-use slotmap::{DefaultKey, SlotMap};
-use tachys::reactive_graph::OwnedView;
+use tachys::reactive_graph::{OwnedView, SuspenseBoundary};
 
 /// If any [`Resource`](leptos_reactive::Resource) is read in the `children` of this
 /// component, it will show the `fallback` while they are loading. Once all are resolved,
@@ -85,41 +80,15 @@ pub fn Transition<Chil>(
 where
     Chil: IntoView + Send + 'static,
 {
-    let (starts_local, id) = {
-        Owner::current_shared_context()
-            .map(|sc| {
-                let id = sc.next_id();
-                (sc.get_incomplete_chunk(&id), id)
-            })
-            .unwrap_or_else(|| (false, Default::default()))
-    };
     let fallback = fallback.run();
-    let children = children.into_inner()();
-    let tasks = ArcRwSignal::new(SlotMap::<DefaultKey, ()>::new());
-    provide_context(SuspenseContext {
-        tasks: tasks.clone(),
-    });
-    let none_pending = ArcMemo::new(move |prev: Option<&bool>| {
-        tasks.track();
-        if prev.is_none() && starts_local {
-            false
-        } else {
-            tasks.with(SlotMap::is_empty)
-        }
-    });
+    let boundary =
+        SuspenseBoundary::<true, _, _>::new(fallback, children.into_inner());
     if let Some(set_pending) = set_pending {
-        Effect::new_isomorphic({
-            let none_pending = none_pending.clone();
-            move |_| {
-                set_pending.set(!none_pending.get());
-            }
+        let none_pending = boundary.none_pending();
+        Effect::new_isomorphic(move |_| {
+            set_pending.set(!none_pending.get());
         });
     }
 
-    OwnedView::new(SuspenseBoundary::<true, _, _> {
-        id,
-        none_pending,
-        fallback,
-        children,
-    })
+    OwnedView::new(boundary)
 }
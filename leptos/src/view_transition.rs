@@ -0,0 +1,77 @@
+use any_spawner::Executor;
+use leptos_dom::helpers::is_browser;
+use reactive_graph::{
+    signal::ArcRwSignal,
+    traits::{Get, Update},
+    wrappers::read::Signal,
+};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(inline_js = "
+    export function start_view_transition(update, done) {
+        if (!document.startViewTransition) {
+            update(() => {});
+            done();
+            return;
+        }
+        const transition = document.startViewTransition(
+            () => new Promise((resolve) => update(resolve))
+        );
+        transition.finished.finally(done);
+    }
+")]
+extern "C" {
+    #[wasm_bindgen(js_name = start_view_transition)]
+    fn start_view_transition_js(update: JsValue, done: JsValue);
+}
+
+thread_local! {
+    // the number of view transitions that have started but not yet finished
+    static RUNNING: ArcRwSignal<usize> = ArcRwSignal::new(0);
+}
+
+/// Runs `update` inside a [view transition](https://developer.mozilla.org/en-US/docs/Web/API/View_Transitions_API),
+/// if the browser supports them.
+///
+/// The browser captures the current state of the page, and then calls `update`. Any signals it
+/// changes are allowed to update the DOM before the transition animates from the old state of the
+/// page to the new one. If the browser does not support view transitions, or this is called on the
+/// server, `update` simply runs immediately.
+///
+/// Only effects that run in response to the changes made by `update` are captured by the
+/// transition. Anything that waits for async data, such as a `<Suspense/>` fallback being replaced,
+/// will update the page after the transition has taken its snapshot of the new state.
+///
+/// The [`Router`](https://docs.rs/leptos_router) can wrap every navigation in a view transition
+/// when its `transition` prop is set.
+pub fn start_view_transition(update: impl FnOnce() + 'static) {
+    if !is_browser() {
+        update();
+        return;
+    }
+
+    let running = RUNNING.with(Clone::clone);
+    running.update(|n| *n += 1);
+
+    let update = Closure::once_into_js(move |resolve: js_sys::Function| {
+        update();
+        // resolve once the effects that depend on the changed signals have updated the DOM
+        Executor::spawn_local(async move {
+            Executor::tick().await;
+            _ = resolve.call0(&JsValue::UNDEFINED);
+        });
+    });
+    let done = Closure::once_into_js(move || {
+        running.update(|n| *n = n.saturating_sub(1))
+    });
+    start_view_transition_js(update, done);
+}
+
+/// Returns a signal that is `true` while a view transition started by
+/// [`start_view_transition`] is running.
+///
+/// This can be used, for example, to disable some interactions while the page is animating.
+pub fn use_view_transition() -> Signal<bool> {
+    let running = RUNNING.with(Clone::clone);
+    Signal::derive(move || running.get() > 0)
+}
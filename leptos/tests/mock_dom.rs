@@ -0,0 +1,34 @@
+use leptos::{
+    prelude::*,
+    tachys::testing::{check, click, find_by_role, input, mount_to, test_dom},
+};
+
+#[test]
+fn view_output_can_be_tested_in_the_mock_dom() {
+    let (name, set_name) = signal(String::new());
+    let (subscribed, set_subscribed) = signal(false);
+    let (submitted, set_submitted) = signal(0);
+    let view = view! {
+        <form>
+            <input type="text" on:input=move |ev| set_name.set(event_target_value(&ev))/>
+            <input
+                type="checkbox"
+                on:change=move |ev| set_subscribed.set(event_target_checked(&ev))
+            />
+            <button on:click=move |_| *set_submitted.write() += 1>"Sign up"</button>
+        </form>
+    };
+    let mounted = mount_to(&test_dom(), view);
+
+    input(&find_by_role(mounted.root(), "textbox").unwrap(), "Ferris");
+    assert_eq!(name.get_untracked(), "Ferris");
+
+    let checkbox = find_by_role(mounted.root(), "checkbox").unwrap();
+    check(&checkbox, true);
+    assert!(subscribed.get_untracked());
+    check(&checkbox, false);
+    assert!(!subscribed.get_untracked());
+
+    click(&find_by_role(mounted.root(), "button").unwrap());
+    assert_eq!(submitted.get_untracked(), 1);
+}
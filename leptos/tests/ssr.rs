@@ -119,6 +119,168 @@ fn ssr_with_styles() {
     );
 }
 
+#[test]
+fn ssr_with_bindings() {
+    use leptos::prelude::*;
+
+    let name = RwSignal::new("Ferris \"the crab\"".to_string());
+    let agreed = signal(true);
+    let rendered: HtmlElement<_, _, _, Dom> = view! {
+        <form>
+            <input type="text" bind:value=name/>
+            <input type="checkbox" bind:checked=agreed/>
+        </form>
+    };
+
+    assert_eq!(
+        rendered.to_html(),
+        "<form><input type=\"text\" value=\"Ferris &quot;the \
+         crab&quot;\"><input type=\"checkbox\" checked></form>"
+    );
+}
+
+#[test]
+fn ssr_with_textarea_and_select_bindings() {
+    use leptos::prelude::*;
+
+    let bio = RwSignal::new("<b>rusty</b>".to_string());
+    let color = RwSignal::new("green".to_string());
+    let sizes = RwSignal::new(vec!["s".to_string(), "l".to_string()]);
+    let rendered: HtmlElement<_, _, _, Dom> = view! {
+        <form>
+            <textarea bind:value=bio></textarea>
+            <select bind:value=color>
+                <option value="red">"Red"</option>
+                <option value="green">"Green"</option>
+            </select>
+            <select multiple bind:value=sizes>
+                <option value="s">"S"</option>
+                <option value="m">"M"</option>
+                <option value="l">"L"</option>
+            </select>
+            <datalist>
+                <option value="green"></option>
+            </datalist>
+        </form>
+    };
+
+    assert_eq!(
+        rendered.to_html(),
+        "<form><textarea>&lt;b&gt;rusty&lt;/b&gt;</textarea><select><option \
+         value=\"red\">Red</option><option value=\"green\" \
+         selected>Green</option></select><select multiple><option \
+         value=\"s\" selected>S</option><option value=\"m\">M</option><option \
+         value=\"l\" selected>L</option></select><datalist><option \
+         value=\"green\"></option></datalist></form>"
+    );
+}
+
+#[test]
+fn ssr_with_group_bindings() {
+    use leptos::prelude::*;
+
+    let plan = RwSignal::new("pro".to_string());
+    let toppings = RwSignal::new(vec!["cheese".to_string()]);
+    let rendered: HtmlElement<_, _, _, Dom> = view! {
+        <form>
+            <input type="radio" value="free" bind:group=plan/>
+            <input type="radio" bind:group=plan value="pro"/>
+            <input type="checkbox" value="cheese" bind:group=toppings/>
+            <input type="checkbox" value="ham" bind:group=toppings/>
+        </form>
+    };
+
+    assert_eq!(
+        rendered.to_html(),
+        "<form><input type=\"radio\" value=\"free\"><input type=\"radio\" \
+         value=\"pro\" checked><input type=\"checkbox\" value=\"cheese\" \
+         checked><input type=\"checkbox\" value=\"ham\"></form>"
+    );
+}
+
+#[test]
+fn ssr_with_controlled_inputs() {
+    use leptos::prelude::*;
+    use leptos::tachys::reactive_graph::bind::{
+        controlled, uncontrolled, Value,
+    };
+
+    let (zip, set_zip) = signal("12345".to_string());
+    let note = RwSignal::new("draft".to_string());
+    let rendered: HtmlElement<_, _, _, Dom> = view! {
+        <form>
+            <input {..controlled(Value, zip, move |value: String| {
+                if value.chars().all(|c| c.is_ascii_digit()) {
+                    set_zip.set(value);
+                }
+            })}/>
+            <input {..uncontrolled(Value, note)}/>
+        </form>
+    };
+
+    assert_eq!(
+        rendered.to_html(),
+        "<form><input value=\"12345\"><input value=\"draft\"></form>"
+    );
+}
+
+#[test]
+fn ssr_with_render_prop_children() {
+    use leptos::prelude::*;
+
+    #[derive(Clone)]
+    struct Row {
+        name: &'static str,
+        age: u8,
+    }
+
+    #[component]
+    fn DataTable<F, IV>(rows: Vec<Row>, children: F) -> impl IntoView
+    where
+        F: Fn(Row) -> IV + Send + 'static,
+        IV: IntoView + 'static,
+    {
+        view! { <table>{rows.into_iter().map(children).collect::<Vec<_>>()}</table> }
+    }
+
+    let rows = vec![
+        Row {
+            name: "Alice",
+            age: 30,
+        },
+        Row {
+            name: "Bob",
+            age: 25,
+        },
+    ];
+    let rendered = view! {
+        <DataTable rows=rows>
+            {|row| view! { <tr><td>{row.name}</td><td>{row.age}</td></tr> }}
+        </DataTable>
+    };
+
+    assert_eq!(
+        rendered.to_html(),
+        "<table><tr><td>Alice</td><td>30</td></tr><tr><td>Bob</td><td>25</\
+         td></tr><!></table>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_show_keep_alive() {
+    use leptos::prelude::*;
+
+    let (open, _) = signal(false);
+    let rendered = view! {
+        <Show when=move || open.get() fallback=|| "closed" keep_alive=true>
+            <input/>
+        </Show>
+    };
+
+    assert_eq!(rendered.to_html(), "closed<!>");
+}
+
 #[test]
 fn ssr_option() {
     use leptos::prelude::*;
@@ -128,3 +290,119 @@ fn ssr_option() {
 
     assert_eq!(rendered.to_html(), "<option></option>");
 }
+
+#[test]
+fn ssr_portal_renders_its_placeholder() {
+    use leptos::{portal::Portal, prelude::*};
+
+    let rendered = view! {
+        <div>
+            <Portal>
+                <p>"In the portal"</p>
+            </Portal>
+            <p>"After the portal"</p>
+        </div>
+    };
+
+    assert_eq!(rendered.to_html(), "<div><!><p>After the portal</p></div>");
+}
+
+#[test]
+fn ssr_stream_min_flush_size() {
+    use futures::{executor::block_on, future::poll_fn, StreamExt};
+    use leptos::prelude::*;
+    use std::task::Poll;
+
+    // resolves on its second poll, so it can't be rendered synchronously
+    async fn yield_once() {
+        let mut yielded = false;
+        poll_fn(move |cx| {
+            if yielded {
+                Poll::Ready(())
+            } else {
+                yielded = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        })
+        .await
+    }
+
+    fn view() -> impl IntoView {
+        view! {
+            <main>
+                "Before"
+                {Suspend::new(async {
+                    yield_once().await;
+                    "First"
+                })}
+                "Between"
+                {Suspend::new(async {
+                    yield_once().await;
+                    "Second"
+                })}
+                "After"
+            </main>
+        }
+    }
+
+    let chunks = block_on(
+        view()
+            .into_view()
+            .to_html_stream_in_order()
+            .collect::<Vec<_>>(),
+    );
+    assert!(chunks.len() > 1);
+
+    let chunks = block_on(
+        view()
+            .into_view()
+            .to_html_stream_in_order()
+            .with_min_flush_size(1024)
+            .collect::<Vec<_>>(),
+    );
+    assert_eq!(chunks.len(), 1);
+    assert!(chunks[0].starts_with("<main>Before"));
+    assert!(chunks[0].contains("First"));
+    assert!(chunks[0].contains("Second"));
+    assert!(chunks[0].ends_with("After</main>"));
+}
+
+#[tokio::test]
+async fn ssr_stream_min_flush_size_sends_buffered_html_before_pending_suspense()
+{
+    use any_spawner::Executor;
+    use futures::{channel::oneshot, FutureExt, StreamExt};
+    use leptos::prelude::*;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+    let (tx, rx) = oneshot::channel::<()>();
+    let view = view! {
+        <main>
+            "Before"
+            <Suspense fallback=|| "Loading">
+                {Suspend::new(async move {
+                    _ = rx.await;
+                    "Resolved"
+                })}
+            </Suspense>
+            "After"
+        </main>
+    };
+    let mut stream = view
+        .into_view()
+        .to_html_stream_in_order()
+        .with_min_flush_size(1024);
+
+    // the HTML before the Suspense is sent while it is still pending, even though it is shorter
+    // than the minimum flush size
+    let first = stream.next().now_or_never().flatten();
+    assert_eq!(first.as_deref(), Some("<main>Before"));
+
+    tx.send(()).unwrap();
+    let rest = stream.collect::<String>().await;
+    assert!(rest.contains("Resolved"));
+    assert!(rest.ends_with("After</main>"));
+}
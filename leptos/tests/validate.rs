@@ -0,0 +1,111 @@
+use leptos::form::{FieldErrors, Validate};
+use server_fn::{
+    error::{NoCustomError, ServerFnErrorSerde, TypedError},
+    ServerFnError,
+};
+
+fn not_admin(value: &String) -> Result<(), String> {
+    if value == "admin" {
+        Err("That name is reserved.".into())
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Validate)]
+struct Profile {
+    #[validate(required, min_length = 3, max_length = 8)]
+    #[validate(custom = not_admin)]
+    name: String,
+    #[validate(email)]
+    email: Option<String>,
+    #[validate(required)]
+    country: Option<String>,
+    #[validate(min = 1, max = 3, message = "Pick one to three tags.")]
+    tag_count: usize,
+    #[validate(max_length = 2)]
+    links: Vec<String>,
+}
+
+fn valid_profile() -> Profile {
+    Profile {
+        name: "greg".into(),
+        email: None,
+        country: Some("US".into()),
+        tag_count: 2,
+        links: vec![],
+    }
+}
+
+#[test]
+fn valid_values_pass() {
+    assert_eq!(valid_profile().validate(), Ok(()));
+}
+
+#[test]
+fn each_failing_rule_adds_an_error_for_its_field() {
+    let profile = Profile {
+        name: "admin".into(),
+        email: Some("nope".into()),
+        country: None,
+        tag_count: 0,
+        links: vec!["a".into(), "b".into(), "c".into()],
+    };
+    let errors = profile.validate().unwrap_err();
+    assert_eq!(errors.get("name"), ["That name is reserved."]);
+    assert_eq!(
+        errors.first("email"),
+        Some("Must be a valid email address.")
+    );
+    assert_eq!(errors.first("country"), Some("This field is required."));
+    assert_eq!(errors.first("tag_count"), Some("Pick one to three tags."));
+    assert_eq!(errors.first("links"), Some("Must have at most 2 items."));
+
+    let profile = Profile {
+        name: " ".into(),
+        ..valid_profile()
+    };
+    let errors = profile.validate().unwrap_err();
+    assert_eq!(
+        errors.get("name"),
+        [
+            "This field is required.",
+            "Must have at least 3 characters."
+        ]
+    );
+    assert_eq!(errors.iter().count(), 1);
+
+    // whitespace only counts as empty for `required`, not towards the length
+    let profile = Profile {
+        name: "   greg   ".into(),
+        ..valid_profile()
+    };
+    let errors = profile.validate().unwrap_err();
+    assert_eq!(errors.get("name"), ["Must have at most 8 characters."]);
+}
+
+#[test]
+fn field_errors_round_trip_as_a_server_fn_error() {
+    let errors = Profile {
+        tag_count: 4,
+        ..valid_profile()
+    }
+    .validate()
+    .unwrap_err();
+    let error: ServerFnError<TypedError<FieldErrors>> = errors.clone().into();
+
+    let encoded = error.ser().unwrap();
+    assert_eq!(
+        ServerFnError::<TypedError<FieldErrors>>::de(&encoded),
+        ServerFnError::WrappedServerError(TypedError(errors))
+    );
+
+    // other server fn errors are not mistaken for field errors
+    let other = ServerFnError::<NoCustomError>::ServerError("oops".into())
+        .ser()
+        .unwrap();
+    assert_eq!(
+        ServerFnError::<TypedError<FieldErrors>>::de(&other),
+        ServerFnError::ServerError("oops".into())
+    );
+}
@@ -76,6 +76,20 @@ pub struct LeptosOptions {
     #[builder(default = default_hash_files())]
     #[serde(default = "default_hash_files")]
     pub hash_files: bool,
+    /// The number of milliseconds server rendering waits for `Suspense` boundaries to resolve.
+    /// Once it has passed, any boundaries that are still pending are sent with their fallbacks,
+    /// and load their data in the browser instead. Defaults to `None`, which waits indefinitely.
+    #[builder(
+        default,
+        setter(
+            doc = "Sets the number of milliseconds server rendering waits for `Suspense` \
+                   boundaries to resolve, after which pending boundaries are sent with their \
+                   fallbacks. Defaults to `None`, which waits indefinitely. This can also be \
+                   set with `LEPTOS_SSR_TIMEOUT`."
+        )
+    )]
+    #[serde(default)]
+    pub ssr_timeout: Option<u64>,
 }
 
 impl LeptosOptions {
@@ -116,6 +130,10 @@ impl LeptosOptions {
             not_found_path: env_w_default("LEPTOS_NOT_FOUND_PATH", "/404")?,
             hash_file: env_w_default("LEPTOS_HASH_FILE_NAME", "hash.txt")?,
             hash_files: env_w_default("LEPTOS_HASH_FILES", "false")?.parse()?,
+            ssr_timeout: match env_wo_default("LEPTOS_SSR_TIMEOUT")? {
+                Some(val) => Some(val.parse()?),
+                None => None,
+            },
         })
     }
 }
@@ -173,6 +173,7 @@ async fn get_config_from_env() {
             ("LEPTOS_SITE_ADDR", Some("0.0.0.0:80")),
             ("LEPTOS_RELOAD_PORT", Some("8080")),
             ("LEPTOS_RELOAD_EXTERNAL_PORT", Some("8080")),
+            ("LEPTOS_SSR_TIMEOUT", Some("500")),
         ],
         async { get_configuration(None).unwrap().leptos_options },
     )
@@ -187,6 +188,7 @@ async fn get_config_from_env() {
     );
     assert_eq!(config.reload_port, 8080);
     assert_eq!(config.reload_external_port, Some(8080));
+    assert_eq!(config.ssr_timeout, Some(500));
 
     // Test default config values
     let config = temp_env::async_with_vars(
@@ -197,6 +199,7 @@ async fn get_config_from_env() {
             ("LEPTOS_SITE_ADDR", None::<&str>),
             ("LEPTOS_RELOAD_PORT", None::<&str>),
             ("LEPTOS_RELOAD_EXTERNAL_PORT", None::<&str>),
+            ("LEPTOS_SSR_TIMEOUT", None::<&str>),
         ],
         async { get_configuration(None).unwrap().leptos_options },
     )
@@ -210,6 +213,7 @@ async fn get_config_from_env() {
     );
     assert_eq!(config.reload_port, 3001);
     assert_eq!(config.reload_external_port, None);
+    assert_eq!(config.ssr_timeout, None);
 }
 
 #[test]
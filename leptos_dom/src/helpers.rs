@@ -17,6 +17,18 @@ thread_local! {
     pub(crate) static DOCUMENT: web_sys::Document = web_sys::window().unwrap_throw().document().unwrap_throw();
 }
 
+/// Returns `true` when the app is running in the browser, and `false` when it is being rendered on
+/// the server, including a server that runs on `wasm32`.
+///
+/// Browser APIs, like timers, event listeners and [`window`], should only be used when this is
+/// `true`.
+pub fn is_browser() -> bool {
+    cfg!(target_arch = "wasm32")
+        && Owner::current_shared_context()
+            .map(|sc| sc.is_browser())
+            .unwrap_or(true)
+}
+
 /// Returns the [`Window`](https://developer.mozilla.org/en-US/docs/Web/API/Window).
 ///
 /// This is cached as a thread-local variable, so calling `window()` multiple times
@@ -95,26 +107,21 @@ where
 /// Helper function to extract `event.target.value` from an event.
 ///
 /// This is useful in the `on:input` or `on:change` listeners for an `<input>` element.
+///
+/// Outside the browser, this reads the target of an event dispatched in the mock DOM. See
+/// [`tachys::dom::event_target_value`].
 pub fn event_target_value<T>(event: &T) -> String
 where
     T: JsCast,
 {
-    event
-        .unchecked_ref::<web_sys::Event>()
-        .target()
-        .unwrap_throw()
-        .unchecked_into::<web_sys::HtmlInputElement>()
-        .value()
+    tachys::dom::event_target_value(event)
 }
 
 /// Helper function to extract `event.target.checked` from an event.
 ///
 /// This is useful in the `on:change` listeners for an `<input type="checkbox">` element.
 pub fn event_target_checked(ev: &web_sys::Event) -> bool {
-    ev.target()
-        .unwrap()
-        .unchecked_into::<web_sys::HtmlInputElement>()
-        .checked()
+    tachys::dom::event_target_checked(ev)
 }
 
 /// Handle that is generated by [request_animation_frame_with_handle] and can
@@ -312,6 +319,25 @@ pub fn set_timeout_with_handle(
     st(closure_once(cb), duration)
 }
 
+/// Executes the given function after the given duration of time has passed, unless the current
+/// [`Owner`] is disposed first.
+///
+/// This is the same as [`set_timeout_with_handle`], except that the timeout is cleared
+/// automatically when the component (or other reactive scope) that created it is unmounted, so
+/// the callback never runs after its component is gone. On the server, this does nothing and
+/// returns `None`.
+pub fn use_timeout(
+    duration: Duration,
+    cb: impl FnOnce() + 'static,
+) -> Option<TimeoutHandle> {
+    if !is_browser() {
+        return None;
+    }
+    let handle = set_timeout_with_handle(cb, duration).ok()?;
+    Owner::on_cleanup(move || handle.clear());
+    Some(handle)
+}
+
 /// "Debounce" a callback function. This will cause it to wait for a period of `delay`
 /// after it is called. If it is called again during that period, it will wait
 /// `delay` before running, and so on. This can be used, for example, to wrap event
@@ -451,6 +477,40 @@ pub fn set_interval_with_handle(
     si(Box::new(cb), duration)
 }
 
+/// Repeatedly calls the given function, with a delay of the given duration between calls, until
+/// the current [`Owner`] is disposed.
+///
+/// This is the same as [`set_interval_with_handle`], except that the interval is cleared
+/// automatically when the component (or other reactive scope) that created it is unmounted. The
+/// returned handle can be used to clear it earlier. On the server, this does nothing and returns
+/// `None`.
+///
+/// ```
+/// use leptos::{leptos_dom::helpers::use_interval, prelude::*};
+/// use std::time::Duration;
+///
+/// #[component]
+/// fn Clock() -> impl IntoView {
+///     let (seconds, set_seconds) = signal(0);
+///     // stops ticking when <Clock/> is unmounted
+///     use_interval(Duration::from_secs(1), move || {
+///         set_seconds.update(|n| *n += 1)
+///     });
+///     view! { <p>{seconds}" seconds"</p> }
+/// }
+/// ```
+pub fn use_interval(
+    duration: Duration,
+    cb: impl Fn() + 'static,
+) -> Option<IntervalHandle> {
+    if !is_browser() {
+        return None;
+    }
+    let handle = set_interval_with_handle(cb, duration).ok()?;
+    Owner::on_cleanup(move || handle.clear());
+    Some(handle)
+}
+
 /// Adds an event listener to the `Window`, typed as a generic `Event`,
 /// returning a cancelable handle.
 #[cfg_attr(
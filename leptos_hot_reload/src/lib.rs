@@ -26,6 +26,28 @@ pub mod parsing;
 
 pub const HOT_RELOAD_JS: &str = include_str!("patch.js");
 
+/// A message asking the browser to patch the running app to match newly server-rendered HTML.
+///
+/// The dev server sends this over the live-reload WebSocket as `{"morph":"..."}`, containing the
+/// new contents of the page's `<body>`. A debug build of an islands app patches the static HTML
+/// around its islands in place with `tachys::morph::morph_static`, so the WASM is not reloaded
+/// and the islands keep their state. The page is reloaded instead if the islands themselves
+/// changed, or if the app hydrates its whole `<body>`, as that HTML belongs to the view tree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MorphMessage {
+    /// The new inner HTML of the `<body>`.
+    pub morph: String,
+}
+
+impl MorphMessage {
+    #[must_use]
+    pub fn new(body_html: impl Into<String>) -> Self {
+        Self {
+            morph: body_html.into(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct ViewMacros {
     // keyed by original location identifier
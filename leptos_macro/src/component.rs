@@ -18,6 +18,7 @@ use syn::{
 
 pub struct Model {
     is_island: bool,
+    hydrate: Option<LitStr>,
     docs: Docs,
     vis: Visibility,
     name: Ident,
@@ -60,6 +61,7 @@ impl Parse for Model {
 
         Ok(Self {
             is_island: false,
+            hydrate: None,
             docs,
             vis: item.vis.clone(),
             name: convert_from_snake_case(&item.sig.ident),
@@ -99,6 +101,7 @@ impl ToTokens for Model {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let Self {
             is_island,
+            hydrate,
             docs,
             vis,
             name,
@@ -232,6 +235,28 @@ impl ToTokens for Model {
         } else {
             quote! {}
         };
+        let island_hydration_strategy = match hydrate {
+            None => quote! {},
+            Some(strategy) => {
+                let variant = match strategy.value().as_str() {
+                    "load" => "Load",
+                    "idle" => "Idle",
+                    "visible" => "Visible",
+                    "interaction" => "Interaction",
+                    _ => abort!(
+                        strategy,
+                        "unknown hydration strategy for an island";
+                        help = r#"expected one of "load", "idle", "visible", or "interaction""#
+                    ),
+                };
+                let variant = Ident::new(variant, strategy.span());
+                quote! {
+                    .with_hydration_strategy(
+                        ::leptos::tachys::html::islands::HydrationStrategy::#variant
+                    )
+                }
+            }
+        };
 
         let body_name = unmodified_fn_name_from_fn_name(&body_name);
         let body_expr = if *is_island {
@@ -273,6 +298,7 @@ impl ToTokens for Model {
                                 #component
                             )
                              #island_serialized_props
+                             #island_hydration_strategy
                         )
                     }
                 }
@@ -281,6 +307,15 @@ impl ToTokens for Model {
             component
         };
 
+        // in debug builds, remember which component this is while it runs, so that errors can
+        // report the components they occurred in
+        let component = quote! {
+            ::leptos::reactive_graph::owner::with_component(
+                #component_id,
+                move || #component
+            )
+        };
+
         let props_arg = if no_props {
             quote! {}
         } else {
@@ -481,6 +516,20 @@ impl Model {
 
         self
     }
+
+    pub fn with_island_opts(mut self, opts: IslandOpts) -> Self {
+        self.hydrate = opts.hydrate;
+
+        self
+    }
+}
+
+/// Arguments to the `#[island]` macro.
+#[derive(Clone, Debug, Default, FromAttr)]
+#[attribute(ident = island)]
+pub struct IslandOpts {
+    #[attribute(example = "\"visible\"")]
+    hydrate: Option<LitStr>,
 }
 
 /// A model that is more lenient in case of a syntax error in the function body,
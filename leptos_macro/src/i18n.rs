@@ -0,0 +1,437 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use serde_json::Value;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+use syn::{
+    parse::{Parse, ParseStream},
+    Expr, Ident, LitStr, Token,
+};
+
+const PLURAL_CATEGORIES: [&str; 6] =
+    ["zero", "one", "two", "few", "many", "other"];
+
+/// The arguments of `t!(i18n, "key", name = value, ...)`.
+pub struct TranslateInput {
+    i18n: Expr,
+    key: LitStr,
+    args: Vec<(Ident, Expr)>,
+}
+
+impl Parse for TranslateInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let i18n = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let key = input.parse()?;
+        let mut args = Vec::new();
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let name: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let value = input.parse()?;
+            if args.iter().any(|(other, _)| *other == name) {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("`{name}` is given more than once"),
+                ));
+            }
+            args.push((name, value));
+        }
+        Ok(Self { i18n, key, args })
+    }
+}
+
+enum Message {
+    Text(String),
+    Plural(BTreeMap<String, String>),
+}
+
+impl Message {
+    fn texts(&self) -> Vec<&str> {
+        match self {
+            Message::Text(text) => vec![text.as_str()],
+            Message::Plural(forms) => {
+                forms.values().map(String::as_str).collect()
+            }
+        }
+    }
+}
+
+struct LocaleFile {
+    locale: String,
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    messages: BTreeMap<String, Message>,
+}
+
+/// The translation files that have been parsed, by directory, so that they are only parsed once
+/// for all of the `t!` calls in a crate rather than once per call. They are parsed again if any
+/// of them has changed, which matters for long-running hosts like rust-analyzer.
+static LOCALES: Mutex<BTreeMap<PathBuf, Arc<Vec<LocaleFile>>>> =
+    Mutex::new(BTreeMap::new());
+
+/// The directory containing the translation files, relative to the crate root. This is
+/// `locales` unless the `LEPTOS_I18N_DIR` environment variable is set.
+fn locales_dir() -> PathBuf {
+    let dir = std::env::var("LEPTOS_I18N_DIR")
+        .unwrap_or_else(|_| "locales".to_string());
+    let root = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    Path::new(&root).join(dir)
+}
+
+/// Reads every `<locale>.json` file in the directory, or returns the files that were already
+/// parsed if none of them has changed since.
+fn read_locales(dir: &Path) -> Result<Arc<Vec<LocaleFile>>, String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("could not read {}: {e}", dir.display()))?;
+    let mut paths = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let modified = entry.metadata().and_then(|meta| meta.modified()).ok();
+        paths.push((path, modified));
+    }
+    paths.sort();
+
+    let mut cache = LOCALES.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(files) = cache.get(dir) {
+        let unchanged = files.len() == paths.len()
+            && files.iter().zip(&paths).all(|(file, (path, modified))| {
+                file.path == *path
+                    && file.modified.is_some()
+                    && file.modified == *modified
+            });
+        if unchanged {
+            return Ok(Arc::clone(files));
+        }
+    }
+    let files = Arc::new(parse_locales(paths)?);
+    cache.insert(dir.to_path_buf(), Arc::clone(&files));
+    Ok(files)
+}
+
+fn parse_locales(
+    paths: Vec<(PathBuf, Option<SystemTime>)>,
+) -> Result<Vec<LocaleFile>, String> {
+    let mut files = Vec::new();
+    for (path, modified) in paths {
+        let Some(locale) = path.file_stem().and_then(|stem| stem.to_str())
+        else {
+            continue;
+        };
+        let invalid = |e: String| format!("{}: {e}", path.display());
+        let contents =
+            fs::read_to_string(&path).map_err(|e| invalid(e.to_string()))?;
+        let value: Value = serde_json::from_str(&contents)
+            .map_err(|e| invalid(e.to_string()))?;
+        let mut messages = BTreeMap::new();
+        flatten("", &value, &mut messages).map_err(invalid)?;
+        files.push(LocaleFile {
+            locale: locale.to_string(),
+            path,
+            modified,
+            messages,
+        });
+    }
+    Ok(files)
+}
+
+/// Flattens nested objects into keys joined with `.`. Objects whose keys are all plural
+/// categories are the plural forms of one message.
+fn flatten(
+    prefix: &str,
+    value: &Value,
+    out: &mut BTreeMap<String, Message>,
+) -> Result<(), String> {
+    let join = |key: &str| {
+        if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{prefix}.{key}")
+        }
+    };
+    match value {
+        Value::String(text) => {
+            out.insert(prefix.to_string(), Message::Text(text.clone()));
+        }
+        Value::Object(map)
+            if !prefix.is_empty()
+                && !map.is_empty()
+                && map.iter().all(|(key, value)| {
+                    PLURAL_CATEGORIES.contains(&key.as_str())
+                        && value.is_string()
+                }) =>
+        {
+            if !map.contains_key("other") {
+                return Err(format!(
+                    "the plural forms of `{prefix}` need an `other` form"
+                ));
+            }
+            let forms = map
+                .iter()
+                .map(|(key, value)| {
+                    (key.clone(), value.as_str().unwrap_or_default().into())
+                })
+                .collect();
+            out.insert(prefix.to_string(), Message::Plural(forms));
+        }
+        Value::Object(map) => {
+            for (key, value) in map {
+                flatten(&join(key), value, out)?;
+            }
+        }
+        _ => {
+            return Err(format!(
+                "`{prefix}` should be a string or an object, not `{value}`"
+            ))
+        }
+    }
+    Ok(())
+}
+
+/// The names of the `{placeholders}` in a message. `{{` and `}}` are escaped braces.
+fn placeholders(text: &str) -> Vec<&str> {
+    let mut found = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        if let Some(after) = rest.strip_prefix('{') {
+            rest = after;
+            continue;
+        }
+        if let Some(end) = rest.find('}') {
+            found.push(rest[..end].trim());
+            rest = &rest[end + 1..];
+        }
+    }
+    found
+}
+
+/// Checks that every locale in `dir` has a message for `key`, and that all of the arguments its
+/// messages need are `given`.
+fn check_key(
+    dir: &Path,
+    files: &[LocaleFile],
+    key: &str,
+    given: impl Fn(&str) -> bool,
+) -> Result<(), String> {
+    let (found, missing): (Vec<_>, Vec<_>) = files
+        .iter()
+        .partition(|file| file.messages.contains_key(key));
+    if found.is_empty() {
+        return Err(format!(
+            "there is no translation for `{key}` in {}",
+            dir.display()
+        ));
+    }
+    if !missing.is_empty() {
+        let missing = missing
+            .iter()
+            .map(|file| file.path.display().to_string())
+            .collect::<Vec<_>>();
+        return Err(format!("`{key}` is missing from {}", missing.join(", ")));
+    }
+    for file in found {
+        let message = &file.messages[key];
+        if matches!(message, Message::Plural(_)) && !given("count") {
+            return Err(format!(
+                "`{key}` has plural forms, so it needs a `count` argument"
+            ));
+        }
+        for text in message.texts() {
+            if let Some(missing) =
+                placeholders(text).into_iter().find(|name| !given(name))
+            {
+                return Err(format!(
+                    "`{key}` uses `{{{missing}}}`, so it needs a `{missing}` \
+                     argument"
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn translate_impl(input: TranslateInput) -> syn::Result<TokenStream> {
+    let TranslateInput { i18n, key, args } = input;
+    let key_str = key.value();
+
+    // checks the key and arguments against the translation files, if there are any
+    let dir = locales_dir();
+    let mut tracking = Vec::new();
+    if dir.is_dir() {
+        let files = read_locales(&dir)
+            .map_err(|e| syn::Error::new(Span::call_site(), e))?;
+        let given = |name: &str| args.iter().any(|(arg, _)| arg == name);
+        check_key(&dir, &files, &key_str, given)
+            .map_err(|e| syn::Error::new(key.span(), e))?;
+        // rebuilds the crate when the translations change
+        for file in files.iter() {
+            let path = file.path.display().to_string();
+            tracking.push(quote! { const _: &str = include_str!(#path); });
+        }
+    }
+
+    let vars = args
+        .iter()
+        .map(|(name, _)| format_ident!("__{}", name, span = Span::mixed_site()))
+        .collect::<Vec<_>>();
+    let values = args.iter().map(|(_, value)| value);
+    let names = args.iter().map(|(name, _)| name.to_string());
+    let count = match args.iter().position(|(name, _)| name == "count") {
+        Some(idx) => {
+            let var = &vars[idx];
+            quote! { ::std::option::Option::Some(#var as f64) }
+        }
+        None => quote! { ::std::option::Option::None },
+    };
+    Ok(quote! {
+        {
+            #(#tracking)*
+            #(let #vars = #values;)*
+            ::leptos::i18n::I18n::translate(
+                &#i18n,
+                #key,
+                #count,
+                &[#((#names, &#vars as &dyn ::std::fmt::Display)),*],
+            )
+        }
+    })
+}
+
+pub fn include_translations_impl(
+    input: TokenStream,
+) -> syn::Result<TokenStream> {
+    if !input.is_empty() {
+        return Err(syn::Error::new_spanned(
+            input,
+            "`include_translations!` does not take any arguments",
+        ));
+    }
+    let dir = locales_dir();
+    if !dir.is_dir() {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            format!("there are no translations at {}", dir.display()),
+        ));
+    }
+    let files = read_locales(&dir)
+        .map_err(|e| syn::Error::new(Span::call_site(), e))?;
+    let files = files.iter().map(|file| {
+        let locale = &file.locale;
+        let path = file.path.display().to_string();
+        quote! { (#locale, include_str!(#path)) }
+    });
+    Ok(quote! {
+        ::leptos::i18n::Translations::from_static(&[#(#files),*])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locale(locale: &str, json: &str) -> LocaleFile {
+        let mut messages = BTreeMap::new();
+        flatten("", &serde_json::from_str(json).unwrap(), &mut messages)
+            .unwrap();
+        LocaleFile {
+            locale: locale.to_string(),
+            path: PathBuf::from(format!("locales/{locale}.json")),
+            modified: None,
+            messages,
+        }
+    }
+
+    fn flattened(json: &str) -> Result<Vec<String>, String> {
+        let mut messages = BTreeMap::new();
+        flatten("", &serde_json::from_str(json).unwrap(), &mut messages)?;
+        Ok(messages.into_keys().collect())
+    }
+
+    #[test]
+    fn flattens_nested_keys() {
+        assert_eq!(
+            flattened(
+                r#"{
+                    "title": "Title",
+                    "cart": {
+                        "empty": "Empty",
+                        "items": { "one": "{count} item", "other": "{count} items" }
+                    }
+                }"#
+            ),
+            Ok(vec![
+                "cart.empty".to_string(),
+                "cart.items".to_string(),
+                "title".to_string()
+            ])
+        );
+        assert_eq!(
+            flattened(r#"{ "items": { "one": "1 item" } }"#),
+            Err("the plural forms of `items` need an `other` form".into())
+        );
+        assert!(flattened(r#"{ "count": 1 }"#).is_err());
+    }
+
+    #[test]
+    fn finds_placeholders() {
+        assert_eq!(placeholders("Hello, { name }!"), ["name"]);
+        assert_eq!(
+            placeholders("{{escaped}} {count} of {total}"),
+            ["count", "total"]
+        );
+    }
+
+    #[test]
+    fn checks_keys_and_arguments() {
+        let dir = Path::new("locales");
+        let files = [
+            locale(
+                "en",
+                r#"{ "greeting": "Hello, {name}!", "items": { "one": "1 item", "other": "{count} items" }, "only_en": "" }"#,
+            ),
+            locale(
+                "fr",
+                r#"{ "greeting": "Bonjour, {name} !", "items": { "one": "1 article", "other": "{count} articles" } }"#,
+            ),
+        ];
+        let given = |names: &'static [&'static str]| {
+            move |name: &str| names.contains(&name)
+        };
+
+        assert_eq!(
+            check_key(dir, &files, "greeting", given(&["name"])),
+            Ok(())
+        );
+        assert_eq!(
+            check_key(dir, &files, "greeting", given(&[])),
+            Err("`greeting` uses `{name}`, so it needs a `name` argument"
+                .into())
+        );
+        assert_eq!(check_key(dir, &files, "items", given(&["count"])), Ok(()));
+        assert_eq!(
+            check_key(dir, &files, "items", given(&[])),
+            Err("`items` has plural forms, so it needs a `count` argument"
+                .into())
+        );
+        assert_eq!(
+            check_key(dir, &files, "only_en", given(&[])),
+            Err("`only_en` is missing from locales/fr.json".into())
+        );
+        assert_eq!(
+            check_key(dir, &files, "unknown", given(&[])),
+            Err("there is no translation for `unknown` in locales".into())
+        );
+    }
+}
@@ -9,13 +9,16 @@
 #[macro_use]
 extern crate proc_macro_error;
 
-use component::DummyModel;
+use attribute_derive::FromAttr;
+use component::{DummyModel, IslandOpts};
 use proc_macro::TokenStream;
 use proc_macro2::{Span, TokenTree};
 use quote::{quote, ToTokens};
 use syn::{parse_macro_input, spanned::Spanned, token::Pub, Visibility};
 
+mod i18n;
 mod params;
+mod validate;
 mod view;
 use crate::component::unmodified_fn_name_from_fn_name;
 mod component;
@@ -125,6 +128,28 @@ mod slot;
 /// # }
 /// ```
 ///
+/// For form elements, `bind:value`, `bind:checked`, and `bind:group` keep a property and a signal in
+/// sync in both directions. They take an `RwSignal` or a `(ReadSignal, WriteSignal)` pair.
+/// `bind:value` waits for IME composition to finish before updating a `String` signal, and binds
+/// the selected options of a `<select multiple>` to a `Vec<String>`. For explicitly controlled or
+/// uncontrolled inputs, spread `controlled(..)` or `uncontrolled(..)` from
+/// `leptos::tachys::reactive_graph::bind` onto the element instead.
+/// ```rust
+/// # use leptos::prelude::*;
+/// # fn test() -> impl IntoView {
+/// let name = RwSignal::new("Alice".to_string());
+/// let subscribed = signal(false);
+/// let plan = RwSignal::new("free".to_string());
+///
+/// view! {
+///   <input type="text" bind:value=name/>
+///   <input type="checkbox" bind:checked=subscribed/>
+///   <input type="radio" value="free" bind:group=plan/>
+///   <input type="radio" value="pro" bind:group=plan/>
+/// }
+/// # }
+/// ```
+///
 /// 7. Classes can be toggled with `class:` attributes, which take a `bool` (or a signal that returns a `bool`).
 /// ```rust
 /// # use leptos::prelude::*;
@@ -415,6 +440,30 @@ pub fn view(tokens: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// If the only child is a closure that takes arguments, it is passed to `children` as-is. This lets a component take
+/// children that render data it provides, like the rows of a table.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// #[component]
+/// fn NumberList<F, IV>(children: F) -> impl IntoView
+/// where
+///     F: Fn(usize) -> IV + Send + 'static,
+///     IV: IntoView + 'static,
+/// {
+///     view! { <ul>{(1..=3).map(children).collect::<Vec<_>>()}</ul> }
+/// }
+///
+/// #[component]
+/// fn ShowNumbers() -> impl IntoView {
+///     view! {
+///       <NumberList>
+///         {|n| view! { <li>"Item " {n}</li> }}
+///       </NumberList>
+///     }
+/// }
+/// ```
+///
 /// ## Customizing Properties
 /// You can use the `#[prop]` attribute on individual component properties (function arguments) to
 /// customize the types that component property can receive. You can use the following attributes:
@@ -457,13 +506,50 @@ pub fn view(tokens: TokenStream) -> TokenStream {
 ///     }
 /// }
 /// ```
+///
+/// * `#[prop(attrs)]`: Marks a `Vec<AnyAttribute<Dom>>` prop that holds attributes passed in by
+///   the caller, to be forwarded to an inner element with `{..attributes}`. Like `optional`, it
+///   defaults to an empty list. The caller builds the list and passes it like any other prop:
+///   unknown attributes are not collected into it automatically. (Attributes added after `{..}`
+///   when using the component are spread onto its root element instead.)
+/// ```rust
+/// # use leptos::prelude::*;
+/// use leptos::{
+///     attr::{any_attribute::AnyAttribute, custom::custom_attribute},
+///     tachys::html::class::class,
+/// };
+///
+/// #[component]
+/// pub fn MyButton(
+///     #[prop(attrs)] attributes: Vec<AnyAttribute<Dom>>,
+///     children: Children,
+/// ) -> impl IntoView {
+///     view! {
+///       <div class="wrapper">
+///         <button {..attributes}>{children()}</button>
+///       </div>
+///     }
+/// }
+///
+/// #[component]
+/// pub fn App() -> impl IntoView {
+///     view! {
+///       <MyButton attributes=vec![
+///         class("x").into_any_attr(),
+///         custom_attribute("data-test", "y").into_any_attr(),
+///       ]>
+///         "Click me"
+///       </MyButton>
+///     }
+/// }
+/// ```
 #[proc_macro_error::proc_macro_error]
 #[proc_macro_attribute]
 pub fn component(
     _args: proc_macro::TokenStream,
     s: TokenStream,
 ) -> TokenStream {
-    component_macro(s, false)
+    component_macro(s, false, IslandOpts::default())
 }
 
 /// Defines a component as an interactive island when you are using the
@@ -486,6 +572,17 @@ pub fn component(
 /// without the island needing to be able to hydrate them.
 /// 2. Props can be passed from the server to an island.
 ///
+/// ## Lazy Hydration
+/// By default, an island is hydrated as soon as the WASM bundle has loaded. You can defer
+/// hydration by passing a strategy to the macro:
+/// - `#[island(hydrate = "idle")]` hydrates once the browser is idle (`requestIdleCallback`).
+/// - `#[island(hydrate = "visible")]` hydrates once the island scrolls into view
+///   (`IntersectionObserver`).
+/// - `#[island(hydrate = "interaction")]` hydrates on the first pointer, focus, or keyboard
+///   event within the island. The event that triggers hydration is not replayed.
+///
+/// Islands nested inside a deferred island are hydrated after their parent.
+///
 /// ## Present Limitations
 /// A few noteworthy limitations, at the moment:
 /// 1. `children` are completely opaque in islands. You can't iterate over `children`;
@@ -539,16 +636,28 @@ pub fn component(
 /// ```
 #[proc_macro_error::proc_macro_error]
 #[proc_macro_attribute]
-pub fn island(_args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
-    component_macro(s, true)
+pub fn island(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
+    let opts =
+        match IslandOpts::from_input(proc_macro2::TokenStream::from(args)) {
+            Ok(opts) => opts,
+            Err(e) => return e.to_compile_error().into(),
+        };
+    component_macro(s, true, opts)
 }
 
-fn component_macro(s: TokenStream, island: bool) -> TokenStream {
+fn component_macro(
+    s: TokenStream,
+    island: bool,
+    island_opts: IslandOpts,
+) -> TokenStream {
     let mut dummy = syn::parse::<DummyModel>(s.clone());
     let parse_result = syn::parse::<component::Model>(s);
 
     if let (Ok(ref mut unexpanded), Ok(model)) = (&mut dummy, parse_result) {
-        let expanded = model.is_island(island).into_token_stream();
+        let expanded = model
+            .is_island(island)
+            .with_island_opts(island_opts)
+            .into_token_stream();
         if !matches!(unexpanded.vis, Visibility::Public(_)) {
             unexpanded.vis = Visibility::Public(Pub {
                 span: unexpanded.vis.span(),
@@ -676,6 +785,41 @@ fn component_macro(s: TokenStream, island: bool) -> TokenStream {
 ///     }
 /// }
 /// ```
+///
+/// 4. A component can take several different slots, each as its own prop. Slots follow the same
+/// rules as other props: a slot that is not `Option<_>`, `Vec<_>`, or marked `#[prop(optional)]`
+/// is required, and leaving it out is a compile error.
+///
+/// ```compile_fail
+/// # use leptos::prelude::*;
+/// #[slot]
+/// struct Header {
+///     children: Children,
+/// }
+///
+/// #[slot]
+/// struct Body {
+///     children: Children,
+/// }
+///
+/// #[component]
+/// fn Modal(header: Header, body: Body) -> impl IntoView {
+///     view! {
+///         <header>{(header.children)()}</header>
+///         <section>{(body.children)()}</section>
+///     }
+/// }
+///
+/// #[component]
+/// fn App() -> impl IntoView {
+///     // ❌ the required `header` slot is missing
+///     view! {
+///         <Modal>
+///             <Body slot>"Are you sure?"</Body>
+///         </Modal>
+///     }
+/// }
+/// ```
 #[proc_macro_error::proc_macro_error]
 #[proc_macro_attribute]
 pub fn slot(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
@@ -756,6 +900,26 @@ pub fn slot(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 /// Whatever encoding is provided to `input` should implement `IntoReq` and `FromReq`. Whatever encoding is provided
 /// to `output` should implement `IntoRes` and `FromRes`.
 ///
+/// ## Middleware
+///
+/// Each `#[middleware]` attribute placed after `#[server]` adds a middleware layer to the server
+/// function's endpoint, which is applied on the server each time the function is called. With
+/// Axum, this can be any [`tower::Layer`](https://docs.rs/tower/latest/tower/trait.Layer.html)
+/// for services of `Request<Body>`. The attribute takes any expression, so a set of layers that
+/// is shared by a group of server functions, such as authentication and rate limiting for an
+/// admin API, can be returned by a function or combined with `tower::ServiceBuilder`, and added
+/// to each of them with a single attribute. When there are several attributes, the first is the
+/// innermost layer, and the last one sees each request first.
+///
+/// ```rust,ignore
+/// #[server]
+/// #[middleware(RequireAuthLayer)]
+/// #[middleware(TimeoutLayer::new(Duration::from_secs(5)))]
+/// pub async fn delete_post(id: u32) -> Result<(), ServerFnError> {
+///     todo!()
+/// }
+/// ```
+///
 /// ## Default Values for Parameters
 ///
 /// Individual function parameters can be annotated with `#[server(default)]`, which will pass
@@ -775,7 +939,10 @@ pub fn slot(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 ///   network call are fallible.
 ///     - [`ServerFnError`](../server_fn/error/enum.ServerFnError.html) can be generic over some custom error type. If so, that type should implement
 ///       [`FromStr`](std::str::FromStr) and [`Display`](std::fmt::Display), but does not need to implement [`Error`](std::error::Error). This is so the value
-///       can be easily serialized and deserialized along with the result.
+///       can be easily serialized and deserialized along with the result. A type that implements
+///       `Serialize` and `Deserialize` instead can be used by wrapping it in
+///       [`TypedError`](../server_fn/error/struct.TypedError.html), so that the client gets back
+///       the same value that the server returned and can match on it.
 /// - **Server functions are part of the public API of your application.** A server function is an
 ///   ad hoc HTTP API endpoint, not a magic formula. Any server function can be accessed by any HTTP
 ///   client. You should take care to sanitize any data being returned from the function to ensure it
@@ -816,6 +983,18 @@ pub fn server(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 
 /// Derives a trait that parses a map of string keys and values into a typed
 /// data structure, e.g., for route params.
+///
+/// Each field is parsed from the value with the same name using `leptos_router`'s `IntoParam`.
+/// A field of type `Option<T>` is `None` if the value is missing, while any other field returns
+/// an error.
+///
+/// ```rust,ignore
+/// #[derive(Params, Clone, PartialEq)]
+/// struct PostParams {
+///     id: u64,
+///     tab: Option<String>,
+/// }
+/// ```
 #[proc_macro_derive(Params, attributes(params))]
 pub fn params_derive(
     input: proc_macro::TokenStream,
@@ -826,6 +1005,56 @@ pub fn params_derive(
     }
 }
 
+/// Derives [`Validate`](https://docs.rs/leptos/latest/leptos/form/trait.Validate.html) for a
+/// struct with named fields, checking each field against the rules listed in its
+/// `#[validate(...)]` attributes.
+///
+/// The available rules are `required`, `min_length = N`, `max_length = N`, `min = N`, `max = N`,
+/// `email`, and `custom = path::to::function`. Each attribute may also set a `message = "..."`
+/// that replaces the default error message for its rules.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// use leptos::form::Validate;
+///
+/// fn no_spaces(value: &String) -> Result<(), String> {
+///     if value.contains(' ') {
+///         Err("Usernames cannot contain spaces.".into())
+///     } else {
+///         Ok(())
+///     }
+/// }
+///
+/// #[derive(Validate)]
+/// struct SignUp {
+///     #[validate(required, min_length = 3, custom = no_spaces)]
+///     username: String,
+///     #[validate(max = 120, message = "Please enter a real age.")]
+///     age: Option<u8>,
+/// }
+///
+/// let errors = SignUp {
+///     username: "a b".into(),
+///     age: Some(200),
+/// }
+/// .validate()
+/// .unwrap_err();
+/// assert_eq!(
+///     errors.get("username"),
+///     ["Usernames cannot contain spaces."]
+/// );
+/// assert_eq!(errors.first("age"), Some("Please enter a real age."));
+/// ```
+#[proc_macro_derive(Validate, attributes(validate))]
+pub fn validate_derive(
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    match syn::parse(input).and_then(|ast| validate::validate_impl(&ast)) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 /// Generates a `slice` into a struct with a default getter and setter.
 ///
 /// Can be used to access deeply nested fields within a global state object.
@@ -857,3 +1086,58 @@ pub fn params_derive(
 pub fn slice(input: TokenStream) -> TokenStream {
     slice::slice_impl(input)
 }
+
+/// Looks up a translated message for the current locale of an
+/// [`I18n`](../leptos/i18n/struct.I18n.html), filling in its `{placeholders}` with the named
+/// arguments.
+///
+/// A `count` argument chooses between the plural forms of the message, using the plural rules
+/// of the locale, and can also be used as a placeholder. The lookup reads the locale, so it
+/// updates when the locale changes if it is used in a closure in a view.
+///
+/// If the crate has a `locales` directory containing the translation files (or the directory
+/// in the `LEPTOS_I18N_DIR` environment variable), the key and arguments are checked against
+/// them when the crate is compiled. It is an error to use a key that is not in any of the
+/// files, to leave out an argument that one of the messages uses, or to leave out `count` for
+/// a message with plural forms.
+///
+/// ```rust
+/// # use leptos::{i18n::*, prelude::*};
+/// # let translations = Translations::from_static(&[(
+/// #     "en",
+/// #     r#"{ "cart": { "one": "{count} item", "other": "{count} items" } }"#,
+/// # )]);
+/// # let owner = Owner::new();
+/// # owner.with(|| {
+/// let i18n = provide_i18n(translations, "en");
+/// let items = RwSignal::new(vec!["apple"]);
+/// let label = move || t!(i18n, "cart", count = items.read().len());
+/// assert_eq!(label(), "1 item");
+/// # });
+/// ```
+#[proc_macro]
+pub fn t(input: TokenStream) -> TokenStream {
+    match syn::parse(input).and_then(i18n::translate_impl) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Embeds the translation files in the `locales` directory of the crate (or the directory in
+/// the `LEPTOS_I18N_DIR` environment variable) as
+/// [`Translations`](../leptos/i18n/struct.Translations.html).
+///
+/// Each file is named for its locale, like `en.json` or `pt-BR.json`. The files are checked
+/// when the crate is compiled, and it is rebuilt when they change.
+///
+/// ```rust,ignore
+/// # use leptos::{i18n::*, prelude::*};
+/// let i18n = provide_i18n(include_translations!(), "en");
+/// ```
+#[proc_macro]
+pub fn include_translations(input: TokenStream) -> TokenStream {
+    match i18n::include_translations_impl(input.into()) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
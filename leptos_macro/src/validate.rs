@@ -0,0 +1,147 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{spanned::Spanned, Expr, LitStr, Path};
+
+enum Rule {
+    Required,
+    MinLength(Expr),
+    MaxLength(Expr),
+    Min(Expr),
+    Max(Expr),
+    Email,
+    Custom(Path),
+}
+
+pub fn validate_impl(ast: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) =
+        ast.generics.split_for_impl();
+
+    let fields =
+        match &ast.data {
+            syn::Data::Struct(syn::DataStruct {
+                fields: syn::Fields::Named(fields),
+                ..
+            }) => &fields.named,
+            _ => return Err(syn::Error::new(
+                ast.span(),
+                "Validate can only be derived for structs with named fields",
+            )),
+        };
+
+    let mut checks = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().expect("expected named struct fields");
+        let field_name = ident.to_string();
+        let is_option = is_option(&field.ty);
+
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("validate"))
+        {
+            let mut rules = Vec::new();
+            let mut message = None::<LitStr>;
+            attr.parse_nested_meta(|meta| {
+                let rule = if meta.path.is_ident("required") {
+                    Rule::Required
+                } else if meta.path.is_ident("email") {
+                    Rule::Email
+                } else if meta.path.is_ident("min_length") {
+                    Rule::MinLength(meta.value()?.parse()?)
+                } else if meta.path.is_ident("max_length") {
+                    Rule::MaxLength(meta.value()?.parse()?)
+                } else if meta.path.is_ident("min") {
+                    Rule::Min(meta.value()?.parse()?)
+                } else if meta.path.is_ident("max") {
+                    Rule::Max(meta.value()?.parse()?)
+                } else if meta.path.is_ident("custom") {
+                    Rule::Custom(meta.value()?.parse()?)
+                } else if meta.path.is_ident("message") {
+                    message = Some(meta.value()?.parse()?);
+                    return Ok(());
+                } else {
+                    return Err(meta.error(
+                        "expected one of `required`, `min_length`, \
+                         `max_length`, `min`, `max`, `email`, `custom`, or \
+                         `message`",
+                    ));
+                };
+                rules.push(rule);
+                Ok(())
+            })?;
+
+            let rules_path = quote! { ::leptos::form::rules };
+            for rule in rules {
+                let span = attr.span();
+                let (check, skip_none) = match rule {
+                    Rule::Required if is_option => {
+                        (quote! { #rules_path::required_some(value) }, false)
+                    }
+                    Rule::Required => {
+                        (quote! { #rules_path::required(value) }, true)
+                    }
+                    Rule::MinLength(n) => {
+                        (quote! { #rules_path::min_length(value, #n) }, true)
+                    }
+                    Rule::MaxLength(n) => {
+                        (quote! { #rules_path::max_length(value, #n) }, true)
+                    }
+                    Rule::Min(n) => {
+                        (quote! { #rules_path::min(value, #n) }, true)
+                    }
+                    Rule::Max(n) => {
+                        (quote! { #rules_path::max(value, #n) }, true)
+                    }
+                    Rule::Email => (quote! { #rules_path::email(value) }, true),
+                    Rule::Custom(f) => (quote! { #f(value) }, true),
+                };
+                let check = match &message {
+                    Some(message) => quote_spanned! {span=>
+                        if #check.is_err() {
+                            errors.add(#field_name, #message);
+                        }
+                    },
+                    None => quote_spanned! {span=>
+                        if let ::std::result::Result::Err(message) = #check {
+                            errors.add(#field_name, message);
+                        }
+                    },
+                };
+                checks.push(if is_option && skip_none {
+                    quote! {
+                        if let ::std::option::Option::Some(value) = &self.#ident {
+                            #check
+                        }
+                    }
+                } else {
+                    quote! {
+                        {
+                            let value = &self.#ident;
+                            #check
+                        }
+                    }
+                });
+            }
+        }
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::leptos::form::Validate for #name #ty_generics #where_clause {
+            fn validate(&self) -> ::std::result::Result<(), ::leptos::form::FieldErrors> {
+                #[allow(unused_mut)]
+                let mut errors = ::leptos::form::FieldErrors::new();
+                #(#checks)*
+                errors.into_result()
+            }
+        }
+    })
+}
+
+fn is_option(ty: &syn::Type) -> bool {
+    if let syn::Type::Path(syn::TypePath { qself: None, path }) = ty {
+        path.segments
+            .last()
+            .map(|segment| segment.ident == "Option")
+            .unwrap_or(false)
+    } else {
+        false
+    }
+}
@@ -3,10 +3,12 @@ use crate::view::attribute_absolute;
 use proc_macro2::{Ident, TokenStream, TokenTree};
 use quote::{format_ident, quote, quote_spanned};
 use rstml::node::{
-    KeyedAttributeValue, NodeAttribute, NodeBlock, NodeElement, NodeName,
+    KeyedAttributeValue, Node, NodeAttribute, NodeBlock, NodeElement, NodeName,
 };
 use std::collections::HashMap;
-use syn::{spanned::Spanned, Expr, ExprPath, ExprRange, RangeLimits, Stmt};
+use syn::{
+    spanned::Spanned, Expr, ExprClosure, ExprPath, ExprRange, RangeLimits, Stmt,
+};
 
 pub(crate) fn component_to_tokens(
     node: &NodeElement,
@@ -66,6 +68,7 @@ pub(crate) fn component_to_tokens(
                     && !attr_key.starts_with("prop:")
                     && !attr_key.starts_with("on:")
                     && !attr_key.starts_with("use:")
+                    && !attr_key.starts_with("bind:")
             }
         })
         .map(|(_, attr)| {
@@ -181,9 +184,38 @@ pub(crate) fn component_to_tokens(
     let events_and_directives =
         events.into_iter().chain(directives).collect::<Vec<_>>(); */
 
+    // a single closure child that takes arguments, like `{|row| view! { ... }}`, is passed
+    // through as-is, so that components can take children that render data they provide
+    let render_prop = match node.children.as_slice() {
+        [Node::Block(NodeBlock::ValidBlock(block))]
+            if items_to_bind.is_empty() =>
+        {
+            match block.stmts.as_slice() {
+                [Stmt::Expr(
+                    closure @ Expr::Closure(ExprClosure { inputs, .. }),
+                    None,
+                )] if !inputs.is_empty() => Some(closure),
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+
     let mut slots = HashMap::new();
     let children = if node.children.is_empty() {
         quote! {}
+    } else if let Some(closure) = render_prop {
+        let clonables = items_to_clone.iter().map(|ident| {
+            let ident_ref = quote_spanned!(ident.span()=> &#ident);
+            quote! { let #ident = ::core::clone::Clone::clone(#ident_ref); }
+        });
+        quote_spanned! {closure.span()=>
+            .children(#[allow(unused_braces)] {
+                #(#clonables)*
+
+                #closure
+            })
+        }
     } else {
         let children = fragment_to_tokens(
             &node.children,
@@ -450,6 +450,12 @@ fn attribute_to_tokens(
                     _ => unreachable!(),
                 };
                 prop_to_tokens(node, prop.into_token_stream(), name)
+            } else if let Some(name) = name.strip_prefix("bind:") {
+                let key = bind_key(node, name);
+                let value = attribute_value(node);
+                quote! {
+                    .bind(#key, #value)
+                }
             }
             // circumstances in which we just do unchecked attributes
             // 1) custom elements, which can have any attributes
@@ -546,6 +552,12 @@ pub(crate) fn attribute_absolute(
                             Some(
                                 quote! { ::leptos::tachys::html::property::#id(#key, #value) },
                             )
+                        } else if id == "bind" {
+                            let key = &node.key.to_string();
+                            let key = bind_key(node, &key.replacen("bind:", "", 1));
+                            Some(
+                                quote! { ::leptos::tachys::reactive_graph::bind::bind(#key, #value) },
+                            )
                         } else if id == "on" {
                             let key = &node.key.to_string();
                             let key = key.replacen("on:", "", 1);
@@ -711,6 +723,22 @@ fn prop_to_tokens(
     }
 }
 
+fn bind_key(node: &KeyedAttribute, name: &str) -> TokenStream {
+    let key = match name {
+        "value" => quote! { Value },
+        "checked" => quote! { Checked },
+        "group" => quote! { Group },
+        _ => proc_macro_error::abort!(
+            node.key.span(),
+            &format!(
+                "`bind:{name}` is not supported; expected `bind:value`, \
+                 `bind:checked`, or `bind:group`"
+            )
+        ),
+    };
+    quote! { ::leptos::tachys::reactive_graph::bind::#key }
+}
+
 fn is_custom_element(tag: &str) -> bool {
     tag.contains('-')
 }
@@ -0,0 +1,56 @@
+use leptos::prelude::*;
+
+#[slot]
+struct Header {
+    title: &'static str,
+}
+
+#[slot]
+struct Body {
+    message: &'static str,
+}
+
+#[slot]
+struct Action {
+    label: &'static str,
+}
+
+#[component]
+fn Modal(
+    header: Header,
+    body: Body,
+    #[prop(default = vec![])] action: Vec<Action>,
+) -> impl IntoView {
+    view! {
+        <div class="modal">
+            <header>{header.title}</header>
+            <section>{body.message}</section>
+            <footer>
+                {action
+                    .into_iter()
+                    .map(|action| view! { <button>{action.label}</button> })
+                    .collect::<Vec<_>>()}
+            </footer>
+        </div>
+    }
+}
+
+#[test]
+fn named_slots_render_in_place() {
+    let html = view! {
+        <Modal>
+            <Body slot message="Are you sure?"/>
+            <Header slot title="Delete file"/>
+            <Action slot label="Cancel"/>
+            <Action slot label="Delete"/>
+        </Modal>
+    }
+    .to_html();
+
+    assert_eq!(
+        html,
+        "<div class=\"modal\"><header>Delete file</header><section>Are you \
+         sure?</section><footer><button>Cancel</button><button>Delete</\
+         button><!></footer></div>"
+    );
+}
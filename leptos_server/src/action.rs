@@ -6,6 +6,11 @@ use reactive_graph::{
 use server_fn::{error::ServerFnErrorSerde, ServerFn, ServerFnError};
 use std::{ops::Deref, panic::Location, sync::Arc};
 
+/// An error returned by a server function called from a form submission without JS/WASM,
+/// which has been encoded in the URL the server redirected back to.
+///
+/// This is provided as context by the router, and used to set the initial value of the
+/// [`ServerAction`] for the server function at [`path`](Self::path).
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ServerActionError {
     path: Arc<str>,
@@ -13,6 +18,7 @@ pub struct ServerActionError {
 }
 
 impl ServerActionError {
+    /// Creates an error for the server function at `path`, given the serialized error.
     pub fn new(path: &str, err: &str) -> Self {
         Self {
             path: path.into(),
@@ -20,10 +26,12 @@ impl ServerActionError {
         }
     }
 
+    /// The path of the server function that returned the error.
     pub fn path(&self) -> &str {
         &self.path
     }
 
+    /// The serialized error.
     pub fn err(&self) -> &str {
         &self.err
     }
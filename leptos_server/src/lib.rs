@@ -8,6 +8,10 @@ mod local_resource;
 pub use local_resource::*;
 mod multi_action;
 pub use multi_action::*;
+#[cfg(feature = "postcard")]
+mod postcard_codec;
+#[cfg(feature = "postcard")]
+pub use postcard_codec::PostcardCodec;
 mod resource;
 pub use resource::*;
 mod shared;
@@ -132,10 +136,16 @@ mod shared;
 //extern crate tracing;
 use base64::{engine::general_purpose::STANDARD_NO_PAD, DecodeError, Engine};
 pub use shared::*;
+/// Converts the value produced by a codec's [`Encoder`](codee::Encoder) into the string that is
+/// written into the page.
+///
+/// Strings are written as they are, and bytes are encoded as base64.
 pub trait IntoEncodedString {
     fn into_encoded_string(self) -> String;
 }
 
+/// Converts the string read from the page back into the input to a codec's
+/// [`Decoder`](codee::Decoder), reversing [`IntoEncodedString`].
 pub trait FromEncodedStr {
     type DecodedType<'a>: Borrow<Self>;
     type DecodingError;
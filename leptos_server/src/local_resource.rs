@@ -9,10 +9,14 @@ use reactive_graph::{
     },
     owner::{use_context, LocalStorage},
     signal::guards::{AsyncPlain, ReadGuard},
-    traits::{DefinedAt, ReadUntracked},
+    traits::{
+        DefinedAt, IsDisposed, ReadUntracked, Trigger, UntrackableGuard,
+        Writeable,
+    },
 };
 use std::{
     future::{pending, Future, IntoFuture},
+    ops::DerefMut,
     panic::Location,
 };
 
@@ -64,6 +68,14 @@ impl<T> ArcLocalResource<T> {
             defined_at: Location::caller(),
         }
     }
+
+    /// Re-runs the fetcher, even if none of its sources have changed.
+    pub fn refetch(&self)
+    where
+        T: 'static,
+    {
+        self.data.refetch();
+    }
 }
 
 impl<T> IntoFuture for ArcLocalResource<T>
@@ -120,6 +132,33 @@ where
     }
 }
 
+impl<T> IsDisposed for ArcLocalResource<T> {
+    #[inline(always)]
+    fn is_disposed(&self) -> bool {
+        false
+    }
+}
+
+impl<T> Trigger for ArcLocalResource<T> {
+    fn trigger(&self) {
+        self.data.trigger();
+    }
+}
+
+impl<T: 'static> Writeable for ArcLocalResource<T> {
+    type Value = Option<T>;
+
+    fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
+        self.data.try_write()
+    }
+
+    fn try_write_untracked(
+        &self,
+    ) -> Option<impl DerefMut<Target = Self::Value>> {
+        self.data.try_write_untracked()
+    }
+}
+
 impl<T: 'static> ToAnySource for ArcLocalResource<T> {
     fn to_any_source(&self) -> AnySource {
         self.data.to_any_source()
@@ -225,6 +264,14 @@ impl<T> LocalResource<T> {
             defined_at: Location::caller(),
         }
     }
+
+    /// Re-runs the fetcher, even if none of its sources have changed.
+    pub fn refetch(&self)
+    where
+        T: 'static,
+    {
+        self.data.refetch();
+    }
 }
 
 impl<T> IntoFuture for LocalResource<T>
@@ -281,6 +328,32 @@ where
     }
 }
 
+impl<T> IsDisposed for LocalResource<T> {
+    fn is_disposed(&self) -> bool {
+        self.data.is_disposed()
+    }
+}
+
+impl<T: 'static> Trigger for LocalResource<T> {
+    fn trigger(&self) {
+        self.data.trigger();
+    }
+}
+
+impl<T: 'static> Writeable for LocalResource<T> {
+    type Value = Option<T>;
+
+    fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
+        self.data.try_write()
+    }
+
+    fn try_write_untracked(
+        &self,
+    ) -> Option<impl DerefMut<Target = Self::Value>> {
+        self.data.try_write_untracked()
+    }
+}
+
 impl<T: 'static> ToAnySource for LocalResource<T>
 where
     T: Send + Sync + 'static,
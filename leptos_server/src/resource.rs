@@ -1,3 +1,5 @@
+#[cfg(feature = "postcard")]
+use crate::PostcardCodec;
 use crate::{FromEncodedStr, IntoEncodedString};
 #[cfg(feature = "rkyv")]
 use codee::binary::RkyvCodec;
@@ -25,6 +27,62 @@ use reactive_graph::{
 };
 use std::{future::IntoFuture, ops::Deref};
 
+/// A reference-counted asynchronous value, which is loaded on the server during server-side
+/// rendering and sent to the client along with the HTML, so that it does not need to be loaded
+/// again during hydration.
+///
+/// ## Encodings
+/// When the value resolves on the server, it is encoded with the codec `Ser` and written into
+/// the page, where the client decodes it with the same codec. The codec is chosen by the
+/// constructor:
+///
+/// | Constructor | Codec | Encoded as |
+/// |---|---|---|
+/// | [`new`](ArcResource::new) | [`JsonSerdeCodec`] (the default) | JSON |
+/// | [`new_str`](ArcResource::new_str) | [`FromToStringCodec`] | [`ToString`] and [`FromStr`](std::str::FromStr) |
+/// | `new_serde_wb` | `JsonSerdeWasmCodec` (`serde-wasm-bindgen` feature) | JSON |
+/// | `new_miniserde` | `MiniserdeCodec` (`miniserde` feature) | JSON |
+/// | `new_serde_lite` | `SerdeLite<JsonSerdeCodec>` (`serde-lite` feature) | JSON |
+/// | `new_rkyv` | `RkyvCodec` (`rkyv` feature) | base64-encoded binary |
+/// | `new_postcard` | `PostcardCodec` (`postcard` feature) | base64-encoded binary |
+///
+/// Binary formats are usually smaller and faster to decode, which matters for large values.
+///
+/// Any other codec can be used with [`new_with_options`](ArcResource::new_with_options), as long
+/// as it implements [`Encoder`] and [`Decoder`] for the value, and it encodes to a `String`
+/// or to bytes. (See [`IntoEncodedString`] and [`FromEncodedStr`].)
+///
+/// ```rust,no_run
+/// # use leptos_server::ArcResource;
+/// use codee::{Decoder, Encoder};
+///
+/// /// Encodes a list of numbers as a comma-separated string.
+/// struct CommaSeparated;
+///
+/// impl Encoder<Vec<u32>> for CommaSeparated {
+///     type Error = ();
+///     type Encoded = String;
+///
+///     fn encode(val: &Vec<u32>) -> Result<String, ()> {
+///         Ok(val.iter().map(u32::to_string).collect::<Vec<_>>().join(","))
+///     }
+/// }
+///
+/// impl Decoder<Vec<u32>> for CommaSeparated {
+///     type Error = std::num::ParseIntError;
+///     type Encoded = str;
+///
+///     fn decode(val: &str) -> Result<Vec<u32>, Self::Error> {
+///         val.split(',').map(str::parse).collect()
+///     }
+/// }
+///
+/// let ids = ArcResource::<_, CommaSeparated>::new_with_options(
+///     || (),
+///     |_| async { vec![1, 2, 3] },
+///     false,
+/// );
+/// ```
 pub struct ArcResource<T, Ser = JsonSerdeCodec> {
     ser: PhantomData<Ser>,
     data: ArcAsyncDerived<T>,
@@ -374,6 +432,44 @@ where
     }
 }
 
+#[cfg(feature = "postcard")]
+impl<T> ArcResource<T, PostcardCodec>
+where
+    PostcardCodec: Encoder<T> + Decoder<T>,
+    <PostcardCodec as Encoder<T>>::Error: Debug,
+    <PostcardCodec as Decoder<T>>::Error: Debug,
+    <<PostcardCodec as Decoder<T>>::Encoded as FromEncodedStr>::DecodingError:
+        Debug,
+    <PostcardCodec as Encoder<T>>::Encoded: IntoEncodedString,
+    <PostcardCodec as Decoder<T>>::Encoded: FromEncodedStr,
+{
+    #[track_caller]
+    pub fn new_postcard<S, Fut>(
+        source: impl Fn() -> S + Send + Sync + 'static,
+        fetcher: impl Fn(S) -> Fut + Send + Sync + 'static,
+    ) -> Self
+    where
+        S: PartialEq + Clone + Send + Sync + 'static,
+        T: Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        ArcResource::new_with_options(source, fetcher, false)
+    }
+
+    #[track_caller]
+    pub fn new_postcard_blocking<S, Fut>(
+        source: impl Fn() -> S + Send + Sync + 'static,
+        fetcher: impl Fn(S) -> Fut + Send + Sync + 'static,
+    ) -> Self
+    where
+        S: PartialEq + Clone + Send + Sync + 'static,
+        T: Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        ArcResource::new_with_options(source, fetcher, true)
+    }
+}
+
 impl<T, Ser> IntoFuture for ArcResource<T, Ser>
 where
     T: Clone + 'static,
@@ -395,6 +491,10 @@ where
     }
 }
 
+/// An arena-allocated asynchronous value, which is loaded on the server during server-side
+/// rendering and sent to the client along with the HTML.
+///
+/// This is the `Copy` equivalent of [`ArcResource`], which describes how the value is encoded.
 pub struct Resource<T, Ser = JsonSerdeCodec>
 where
     T: Send + Sync + 'static,
@@ -629,6 +729,43 @@ where
     }
 }
 
+#[cfg(feature = "postcard")]
+impl<T> Resource<T, PostcardCodec>
+where
+    PostcardCodec: Encoder<T> + Decoder<T>,
+    <PostcardCodec as Encoder<T>>::Error: Debug,
+    <PostcardCodec as Decoder<T>>::Error: Debug,
+    <<PostcardCodec as Decoder<T>>::Encoded as FromEncodedStr>::DecodingError:
+        Debug,
+    <PostcardCodec as Encoder<T>>::Encoded: IntoEncodedString,
+    <PostcardCodec as Decoder<T>>::Encoded: FromEncodedStr,
+    T: Send + Sync,
+{
+    pub fn new_postcard<S, Fut>(
+        source: impl Fn() -> S + Send + Sync + 'static,
+        fetcher: impl Fn(S) -> Fut + Send + Sync + 'static,
+    ) -> Self
+    where
+        S: PartialEq + Clone + Send + Sync + 'static,
+        T: Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        Resource::new_with_options(source, fetcher, false)
+    }
+
+    pub fn new_postcard_blocking<S, Fut>(
+        source: impl Fn() -> S + Send + Sync + 'static,
+        fetcher: impl Fn(S) -> Fut + Send + Sync + 'static,
+    ) -> Self
+    where
+        S: PartialEq + Clone + Send + Sync + 'static,
+        T: Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        Resource::new_with_options(source, fetcher, true)
+    }
+}
+
 impl<T, Ser> Resource<T, Ser>
 where
     Ser: Encoder<T> + Decoder<T>,
@@ -1,3 +1,5 @@
+#[cfg(feature = "postcard")]
+use crate::PostcardCodec;
 use crate::{FromEncodedStr, IntoEncodedString};
 #[cfg(feature = "rkyv")]
 use codee::binary::RkyvCodec;
@@ -130,6 +132,22 @@ where
     }
 }
 
+#[cfg(feature = "postcard")]
+impl<T> SharedValue<T, PostcardCodec>
+where
+    PostcardCodec: Encoder<T> + Decoder<T>,
+    <PostcardCodec as Encoder<T>>::Error: Debug,
+    <PostcardCodec as Decoder<T>>::Error: Debug,
+    <PostcardCodec as Encoder<T>>::Encoded: IntoEncodedString,
+    <PostcardCodec as Decoder<T>>::Encoded: FromEncodedStr,
+    <<PostcardCodec as codee::Decoder<T>>::Encoded as FromEncodedStr>::DecodingError:
+        Debug,
+{
+    pub fn new(initial: impl FnOnce() -> T) -> Self {
+        SharedValue::new_with_encoding(initial)
+    }
+}
+
 impl<T, Ser> SharedValue<T, Ser>
 where
     Ser: Encoder<T> + Decoder<T>,
@@ -1,11 +1,14 @@
 use crate::ServerMetaContext;
 use leptos::{
     attr::NextAttribute,
-    component, html,
+    component,
+    direction::{provide_direction, use_direction, Direction},
+    html,
+    prelude::{Get, Signal},
     reactive_graph::owner::use_context,
     tachys::{
         dom::document,
-        html::attribute::Attribute,
+        html::attribute::{self, Attribute},
         hydration::Cursor,
         renderer::{dom::Dom, Renderer},
         view::{
@@ -47,6 +50,46 @@ pub fn Html() -> impl IntoView {
     HtmlView { attributes: () }
 }
 
+/// Sets the `dir` attribute of the document’s `<html>` element to the direction of the text,
+/// both during server rendering and in the browser.
+///
+/// If `dir` is given, it is also provided to the rest of the app with
+/// [`provide_direction`]. Otherwise, the direction is the one returned by [`use_direction`],
+/// which follows the locale of the app if it uses [`leptos::i18n`].
+///
+/// ```
+/// use leptos::{direction::Direction, prelude::*};
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn MyApp() -> impl IntoView {
+///     provide_meta_context();
+///     let (rtl, set_rtl) = signal(false);
+///
+///     view! {
+///       <Dir dir=Signal::derive(move || {
+///           if rtl.get() { Direction::Rtl } else { Direction::Ltr }
+///       })/>
+///       <button on:click=move |_| set_rtl.update(|rtl| *rtl = !*rtl)>
+///         "Switch direction"
+///       </button>
+///     }
+/// }
+/// ```
+#[component]
+pub fn Dir(
+    /// The direction of the text.
+    #[prop(optional, into)]
+    dir: Option<Signal<Direction>>,
+) -> impl IntoView {
+    let dir = match dir {
+        Some(dir) => provide_direction(dir),
+        None => use_direction(),
+    };
+    HtmlView { attributes: () }
+        .add_any_attr(attribute::dir(move || dir.get().as_str()))
+}
+
 struct HtmlView<At> {
     attributes: At,
 }
@@ -0,0 +1,91 @@
+use crate::{attribute_key, register};
+use leptos::{
+    component,
+    oco::Oco,
+    prelude::*,
+    tachys::html::element::{script, InnerHtmlAttribute},
+    IntoView,
+};
+use serde::Serialize;
+
+/// Injects [structured data](https://developers.google.com/search/docs/appearance/structured-data/intro-structured-data)
+/// into the document head, as a `<script type="application/ld+json">` containing `data`
+/// serialized as JSON.
+///
+/// The JSON is escaped so that strings in the data, like `"</script>"`, cannot end the script
+/// early. If `data` is a signal, the script is updated when it changes.
+///
+/// ```
+/// use leptos::prelude::*;
+/// use leptos_meta::*;
+/// use serde::Serialize;
+///
+/// #[derive(Clone, Serialize)]
+/// struct Article {
+///     #[serde(rename = "@context")]
+///     context: &'static str,
+///     #[serde(rename = "@type")]
+///     ty: &'static str,
+///     headline: String,
+/// }
+///
+/// #[component]
+/// fn Post(headline: String) -> impl IntoView {
+///     let article = Article {
+///         context: "https://schema.org",
+///         ty: "Article",
+///         headline: headline.clone(),
+///     };
+///
+///     view! {
+///       <JsonLd data=article/>
+///       <h1>{headline}</h1>
+///     }
+/// }
+/// ```
+#[component]
+pub fn JsonLd<T>(
+    /// The data, which is usually a type from [schema.org](https://schema.org).
+    #[prop(into)]
+    data: Signal<T>,
+    /// An ID for the `<script>` tag. Only the most recently rendered `<JsonLd/>` with a given `id`
+    /// is added to the head; otherwise, there can be any number of them.
+    #[prop(optional, into)]
+    id: Option<Oco<'static, str>>,
+) -> impl IntoView
+where
+    T: Serialize + Send + Sync + 'static,
+{
+    let key = id.as_deref().map(|id| attribute_key("script", "id", id));
+    register(
+        key,
+        script()
+            .id(id)
+            .r#type("application/ld+json")
+            .inner_html(move || data.with(to_script_json)),
+    )
+}
+
+/// Serializes the data as JSON that can be placed inside a `<script>` tag.
+///
+/// `<`, `>` and `&` can only appear inside JSON strings, where they are replaced with
+/// equivalent escape sequences, so that the HTML parser does not see `</script>` or `<!--`.
+/// The line and paragraph separators are escaped too, as they end lines in JavaScript.
+fn to_script_json<T: Serialize>(data: &T) -> String {
+    let json = serde_json::to_string(data).unwrap_or_else(|e| {
+        tracing::error!("could not serialize <JsonLd/> data: {e}");
+        String::from("null")
+    });
+    let mut escaped = String::with_capacity(json.len());
+    for c in json.chars() {
+        match c {
+            '<' => escaped.push_str("\\u003c"),
+            '>' => escaped.push_str("\\u003e"),
+            '&' => escaped.push_str("\\u0026"),
+            '\u{2028}' => escaped.push_str("\\u2028"),
+            '\u{2029}' => escaped.push_str("\\u2029"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
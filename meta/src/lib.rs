@@ -48,6 +48,7 @@
 //! which mode your app is operating in.
 
 use futures::{Stream, StreamExt};
+use indexmap::IndexMap;
 use leptos::{
     attr::NextAttribute,
     component,
@@ -71,6 +72,8 @@ use leptos::{
 use once_cell::sync::Lazy;
 use send_wrapper::SendWrapper;
 use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
     fmt::Debug,
     sync::{
         mpsc::{channel, Receiver, Sender},
@@ -82,17 +85,23 @@ use web_sys::HtmlHeadElement;
 
 mod body;
 mod html;
+mod json_ld;
 mod link;
 mod meta_tags;
+mod scoped_style;
 mod script;
+mod social;
 mod style;
 mod stylesheet;
 mod title;
 pub use body::*;
 pub use html::*;
+pub use json_ld::*;
 pub use link::*;
 pub use meta_tags::*;
+pub use scoped_style::*;
 pub use script::*;
+pub use social::*;
 pub use style::*;
 pub use stylesheet::*;
 pub use title::*;
@@ -174,7 +183,16 @@ pub struct ServerMetaContext {
     pub(crate) body: Sender<String>,
     /// Arbitrary elements to be added to the `<head>` as HTML.
     #[allow(unused)] // used in SSR
-    pub(crate) elements: Sender<String>,
+    pub(crate) elements: Sender<HeadTag>,
+}
+
+/// An element that has been rendered to HTML to be added to the `<head>`.
+#[derive(Debug)]
+pub(crate) struct HeadTag {
+    /// A CSS selector that identifies this element; only the last tag registered with a given key
+    /// is rendered.
+    key: Option<String>,
+    html: String,
 }
 
 /// Allows you to access `<head>` content that was inserted via [`ServerMetaContext`].
@@ -185,7 +203,7 @@ pub struct ServerMetaContextOutput {
     pub(crate) title: TitleContext,
     html: Receiver<String>,
     body: Receiver<String>,
-    elements: Receiver<String>,
+    elements: Receiver<HeadTag>,
 }
 
 impl ServerMetaContext {
@@ -232,7 +250,19 @@ impl ServerMetaContextOutput {
             .unwrap_or(0);
 
         // collect all registered meta tags
-        let meta_buf = self.elements.try_iter().collect::<String>();
+        // tags with a key replace any earlier tag with the same key, and are rendered after the
+        // others so that they do not affect the order in which the others are hydrated
+        let mut meta_buf = String::new();
+        let mut keyed = IndexMap::new();
+        for tag in self.elements.try_iter() {
+            match tag.key {
+                Some(key) => {
+                    keyed.insert(key, tag.html);
+                }
+                None => meta_buf.push_str(&tag.html),
+            }
+        }
+        meta_buf.extend(keyed.into_values());
 
         // get HTML strings for `<html>` and `<body>`
         let html_attrs = self.html.try_iter().collect::<String>();
@@ -322,7 +352,13 @@ pub fn use_head() -> MetaContext {
     }
 }
 
+/// Registers an element to be added to the `<head>`.
+///
+/// If it has a `key`, a CSS selector that matches it, then only the most recently mounted element
+/// with that key is in the `<head>` at any time. For example, a `<Meta name="description"/>` in a
+/// page replaces the one in the layout around it, until the page is unmounted.
 pub(crate) fn register<E, At, Ch>(
+    key: Option<String>,
     el: HtmlElement<E, At, Ch, Dom>,
 ) -> RegisteredMetaTag<E, At, Ch>
 where
@@ -340,7 +376,11 @@ where
             false,
             false,
         );
-        _ = cx.elements.send(buf); // fails only if the receiver is already dropped
+        // fails only if the receiver is already dropped
+        _ = cx.elements.send(HeadTag {
+            key: key.clone(),
+            html: buf,
+        });
     } else {
         tracing::warn!(
             "tried to use a leptos_meta component without `ServerMetaContext` \
@@ -348,13 +388,22 @@ where
         );
     }
 
-    RegisteredMetaTag { el }
+    RegisteredMetaTag { el, key }
+}
+
+/// Returns a key that matches the element `tag` whose attribute `attr` has the given value.
+pub(crate) fn attribute_key(tag: &str, attr: &str, value: &str) -> String {
+    format!(
+        "{tag}[{attr}=\"{}\"]",
+        value.replace('\\', "\\\\").replace('"', "\\\"")
+    )
 }
 
 struct RegisteredMetaTag<E, At, Ch> {
     // this is `None` if we've already taken it out to render to HTML on the server
     // we don't render it in place in RenderHtml, so it's fine
     el: Option<HtmlElement<E, At, Ch, Dom>>,
+    key: Option<String>,
 }
 
 struct RegisteredMetaTagState<E, At, Ch>
@@ -362,6 +411,66 @@ where
     HtmlElement<E, At, Ch, Dom>: Render<Dom>,
 {
     state: <HtmlElement<E, At, Ch, Dom> as Render<Dom>>::State,
+    key: Option<(String, u64)>,
+}
+
+thread_local! {
+    /// For each key, the keyed elements that are currently mounted, with the most recent last.
+    static KEYED_TAGS: RefCell<HashMap<String, Vec<(u64, web_sys::Element)>>> =
+        Default::default();
+    static NEXT_KEYED_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+fn next_keyed_id() -> u64 {
+    let id = NEXT_KEYED_ID.get();
+    NEXT_KEYED_ID.set(id + 1);
+    id
+}
+
+/// Removes whichever element currently has this key from the `<head>`, before `el` is mounted.
+fn mount_keyed(key: &str, id: u64, el: &web_sys::Element) {
+    KEYED_TAGS.with_borrow_mut(|tags| {
+        let stack = tags.entry(key.to_string()).or_default();
+        match stack.last() {
+            Some((top, _)) if *top == id => {}
+            Some((_, top)) => top.remove(),
+            // the first time this key is used, remove any elements that were rendered on the
+            // server, or included in the HTML shell
+            None => {
+                if let Ok(existing) = document_head().query_selector_all(key) {
+                    for idx in 0..existing.length() {
+                        if let Some(node) = existing.item(idx) {
+                            if !node.is_same_node(Some(el)) {
+                                if let Some(parent) = node.parent_node() {
+                                    _ = parent.remove_child(&node);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        stack.retain(|(other, _)| *other != id);
+        stack.push((id, el.clone()));
+    });
+}
+
+/// Restores the previous element with this key to the `<head>`, after `id` is unmounted.
+fn unmount_keyed(key: &str, id: u64) {
+    KEYED_TAGS.with_borrow_mut(|tags| {
+        if let Some(stack) = tags.get_mut(key) {
+            let was_top = stack.last().map(|(top, _)| *top == id) == Some(true);
+            stack.retain(|(other, _)| *other != id);
+            if was_top {
+                if let Some((_, previous)) = stack.last() {
+                    _ = document_head().append_child(previous);
+                }
+            }
+            if stack.is_empty() {
+                tags.remove(key);
+            }
+        }
+    });
 }
 
 fn document_head() -> HtmlHeadElement {
@@ -384,11 +493,24 @@ where
 
     fn build(self) -> Self::State {
         let state = self.el.unwrap().build();
-        RegisteredMetaTagState { state }
+        RegisteredMetaTagState {
+            state,
+            key: self.key.map(|key| (key, next_keyed_id())),
+        }
     }
 
     fn rebuild(self, state: &mut Self::State) {
         self.el.unwrap().rebuild(&mut state.state);
+        // the key changes if it was read from a reactive attribute
+        if state.key.as_ref().map(|(key, _)| key) != self.key.as_ref() {
+            if let Some((key, id)) = state.key.take() {
+                unmount_keyed(&key, id);
+            }
+            state.key = self.key.map(|key| (key, next_keyed_id()));
+            if let Some((key, id)) = &state.key {
+                mount_keyed(key, *id, &state.state);
+            }
+        }
     }
 }
 
@@ -413,6 +535,7 @@ where
     {
         RegisteredMetaTag {
             el: self.el.map(|inner| inner.add_any_attr(attr)),
+            key: self.key,
         }
     }
 }
@@ -451,6 +574,14 @@ where
         _cursor: &Cursor<Dom>,
         _position: &PositionState,
     ) -> Self::State {
+        // keyed elements are rendered after all the others on the server, and only once per key,
+        // so they are created again rather than hydrated
+        if self.key.is_some() {
+            let mut state = self.build();
+            state.mount(&document_head(), None);
+            return state;
+        }
+
         let cursor = use_context::<MetaContext>()
             .expect(
                 "attempting to hydrate `leptos_meta` components without a \
@@ -461,7 +592,7 @@ where
             &*cursor,
             &PositionState::new(Position::NextChild),
         );
-        RegisteredMetaTagState { state }
+        RegisteredMetaTagState { state, key: None }
     }
 }
 
@@ -473,6 +604,9 @@ where
 {
     fn unmount(&mut self) {
         self.state.unmount();
+        if let Some((key, id)) = &self.key {
+            unmount_keyed(key, *id);
+        }
     }
 
     fn mount(
@@ -480,6 +614,9 @@ where
         _parent: &<Dom as Renderer>::Element,
         _marker: Option<&<Dom as Renderer>::Node>,
     ) {
+        if let Some((key, id)) = &self.key {
+            mount_keyed(key, *id, &self.state);
+        }
         // we always mount this to the <head>, which is the whole point
         // but this shouldn't warn about the parent being a regular element or being unused
         // because it will call "mount" with the parent where it is located in the component tree,
@@ -1,4 +1,4 @@
-use crate::register;
+use crate::{attribute_key, register};
 use leptos::{
     component, oco::Oco, prelude::GlobalAttributes,
     tachys::html::element::link, IntoView,
@@ -30,6 +30,7 @@ use leptos::{
 #[component]
 pub fn Link(
     /// The [`id`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/link#attr-id) attribute.
+    /// Only the most recently rendered `<Link/>` with a given `id` is added to the head.
     #[prop(optional, into)]
     id: Option<Oco<'static, str>>,
     /// The [`as`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/link#attr-as) attribute.
@@ -63,6 +64,7 @@ pub fn Link(
     #[prop(optional, into)]
     referrerpolicy: Option<Oco<'static, str>>,
     /// The [`rel`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/link#attr-rel) attribute.
+    /// Only the most recently rendered `<Link rel="canonical"/>` is added to the head.
     #[prop(optional, into)]
     rel: Option<Oco<'static, str>>,
     /// The [`sizes`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/link#attr-sizes) attribute.
@@ -79,7 +81,15 @@ pub fn Link(
     blocking: Option<Oco<'static, str>>,
 ) -> impl IntoView {
     // TODO additional attributes
+    let key = match (id.as_deref(), rel.as_deref()) {
+        (Some(id), _) => Some(attribute_key("link", "id", id)),
+        (None, Some("canonical")) => {
+            Some(attribute_key("link", "rel", "canonical"))
+        }
+        _ => None,
+    };
     register(
+        key,
         link()
             .id(id)
             .r#as(as_)
@@ -1,4 +1,4 @@
-use crate::register;
+use crate::{attribute_key, register};
 use leptos::{
     component,
     prelude::{CustomAttribute, GlobalAttributes},
@@ -10,6 +10,11 @@ use leptos::{
 /// Injects an [`HTMLMetaElement`](https://developer.mozilla.org/en-US/docs/Web/API/HTMLMetaElement) into the document
 /// head to set metadata
 ///
+/// Only one `<meta>` tag with a given `charset`, `name`, or `http-equiv` is added to the head at a
+/// time: one rendered later (for example, in a page) replaces an earlier one (for example, in the
+/// layout around it) for as long as it is mounted. Tags with a `property` or `itemprop` may be
+/// repeated. If `name` or `http_equiv` is reactive, the tag is moved to its new key when it changes.
+///
 /// ```
 /// use leptos::prelude::*;
 /// use leptos_meta::*;
@@ -48,13 +53,37 @@ pub fn Meta(
     #[prop(optional, into)]
     content: Option<TextProp>,
 ) -> impl IntoView {
-    register(
+    let key = {
+        let has_charset = charset.is_some();
+        let name = name.clone();
+        let http_equiv = http_equiv.clone();
+        move || {
+            if has_charset {
+                Some("meta[charset]".to_string())
+            } else if let Some(name) = &name {
+                Some(attribute_key("meta", "name", &name.get()))
+            } else {
+                http_equiv.as_ref().map(|http_equiv| {
+                    attribute_key("meta", "http-equiv", &http_equiv.get())
+                })
+            }
+        }
+    };
+    let el = move || {
         meta()
-            .charset(charset.map(|v| move || v.get()))
-            .name(name.map(|v| move || v.get()))
-            .attr("property", property.map(|v| move || v.get()))
-            .http_equiv(http_equiv.map(|v| move || v.get()))
-            .itemprop(itemprop.map(|v| move || v.get()))
-            .content(content.map(|v| move || v.get())),
-    )
+            .charset(charset.clone().map(|v| move || v.get()))
+            .name(name.clone().map(|v| move || v.get()))
+            .attr("property", property.clone().map(|v| move || v.get()))
+            .http_equiv(http_equiv.clone().map(|v| move || v.get()))
+            .itemprop(itemprop.clone().map(|v| move || v.get()))
+            .content(content.clone().map(|v| move || v.get()))
+    };
+    // the first tag is registered right away, so that tags are added to the <head> in the order
+    // their components run during server rendering
+    let mut first = Some(register(key(), el()));
+    // if `name` or `http_equiv` is reactive, the tag is registered again under its new key
+    move || {
+        let key = key();
+        first.take().unwrap_or_else(|| register(key, el()))
+    }
 }
@@ -0,0 +1,290 @@
+use crate::{attribute_key, register};
+use leptos::{
+    component, oco::Oco, prelude::*, tachys::html::element::style, IntoView,
+};
+use once_cell::sync::Lazy;
+use or_poisoned::OrPoisoned;
+use std::{collections::HashMap, sync::Mutex};
+
+/// Returns the class name used to scope the given CSS.
+///
+/// The class is generated from a hash of the CSS, so it is the same on the server and in the
+/// browser, and the same for every instance of a component. Pass it to the
+/// [`view`](leptos::view) macro as `view! { class = class, ... }` to add it to every element in
+/// the view, and render the same CSS with [`ScopedStyle`].
+///
+/// Each class name is only allocated once, so this is cheap to call every time a component
+/// renders.
+pub fn scoped_class(css: &str) -> &'static str {
+    static CLASSES: Lazy<Mutex<ClassNames>> = Lazy::new(Default::default);
+
+    // 64-bit FNV-1a, which is stable across platforms and compiler versions
+    let hash = css.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    CLASSES.lock().or_poisoned().intern(hash)
+}
+
+/// Interns the class names returned by [`scoped_class`].
+///
+/// The names live for the rest of the program, so that they can be used as a global class in the
+/// [`view`](leptos::view) macro. Rather than allocating each one separately, they are copied
+/// into blocks that hold many names at once.
+#[derive(Default)]
+struct ClassNames {
+    names: HashMap<u64, &'static str>,
+    free: &'static mut [u8],
+}
+
+impl ClassNames {
+    // `leptos-` and 16 hex digits
+    const NAME_LEN: usize = 23;
+    const BLOCK_LEN: usize = 64 * Self::NAME_LEN;
+
+    fn intern(&mut self, hash: u64) -> &'static str {
+        if let Some(name) = self.names.get(&hash) {
+            return name;
+        }
+        if self.free.len() < Self::NAME_LEN {
+            self.free = Box::leak(vec![0; Self::BLOCK_LEN].into_boxed_slice());
+        }
+        let (buf, free) =
+            std::mem::take(&mut self.free).split_at_mut(Self::NAME_LEN);
+        self.free = free;
+        buf.copy_from_slice(format!("leptos-{hash:016x}").as_bytes());
+        let name =
+            std::str::from_utf8(buf).expect("class names are always ASCII");
+        self.names.insert(hash, name);
+        name
+    }
+}
+
+/// Injects CSS into the document head that only applies to the elements of one component.
+///
+/// The selectors in the CSS are rewritten so that they only match elements with the class
+/// returned by [`scoped_class`] for the same CSS: the class is added to the last part of each
+/// selector, so `nav > a:hover` becomes `nav > a:hover.leptos-0123456789abcdef`. Rules inside
+/// `@media`, `@supports`, `@container`, and `@layer` blocks are scoped in the same way. Quoted
+/// strings and `url(...)` values are left as they are.
+///
+/// The `<style>` tag is only added to the head once, no matter how many times the component
+/// that renders it is used: during server rendering, the styles are deduplicated before they are
+/// injected into the `<head>`, and in the browser, each copy replaces the last.
+///
+/// ```
+/// use leptos::prelude::*;
+/// use leptos_meta::*;
+///
+/// const CSS: &str = "p { color: rebeccapurple; } button:hover { opacity: 0.8; }";
+///
+/// #[component]
+/// fn Card() -> impl IntoView {
+///     let class = scoped_class(CSS);
+///
+///     view! { class = class,
+///       <ScopedStyle css=CSS/>
+///       <div>
+///         // this matches `p.leptos-...`, so it's purple
+///         <p>"Hello!"</p>
+///         <button>"Click me"</button>
+///       </div>
+///     }
+/// }
+/// ```
+#[component]
+pub fn ScopedStyle(
+    /// The CSS to be scoped.
+    #[prop(into)]
+    css: Oco<'static, str>,
+    /// The [`nonce`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/style#attr-nonce) attribute.
+    #[prop(optional, into)]
+    nonce: Option<Oco<'static, str>>,
+) -> impl IntoView {
+    let class = scoped_class(&css);
+    let scoped = scope_css(&css, class);
+    let key = attribute_key("style", "id", class);
+    register(Some(key), style().id(class).nonce(nonce).child(scoped))
+}
+
+/// Adds `.{class}` to each selector in the CSS.
+fn scope_css(css: &str, class: &str) -> String {
+    let css = strip_comments(css);
+    let mut out = String::with_capacity(css.len());
+    scope_rules(&css, class, &mut out);
+    out
+}
+
+/// The characters of the CSS and their indices, skipping over the contents of quoted strings and
+/// `url(...)`, which may contain braces, commas, or comment markers that are not part of the CSS
+/// syntax. The opening quote or `u` is still yielded.
+fn code_chars(css: &str) -> impl Iterator<Item = (usize, char)> + '_ {
+    let mut chars = css.char_indices();
+    let mut prev = None;
+    std::iter::from_fn(move || {
+        let (idx, c) = chars.next()?;
+        let starts_url = matches!(c, 'u' | 'U')
+            && !prev.is_some_and(|p: char| p.is_alphanumeric() || p == '-')
+            && css[idx..]
+                .get(..4)
+                .is_some_and(|url| url.eq_ignore_ascii_case("url("));
+        if let '"' | '\'' = c {
+            skip_string(&mut chars, c);
+        } else if starts_url {
+            // `rl(`, then everything up to the closing parenthesis
+            chars.nth(2);
+            while let Some((_, c)) = chars.next() {
+                match c {
+                    '\\' => _ = chars.next(),
+                    '"' | '\'' => skip_string(&mut chars, c),
+                    ')' => break,
+                    _ => {}
+                }
+            }
+        }
+        prev = Some(c);
+        Some((idx, c))
+    })
+}
+
+/// Skips to the end of a string that was opened with `quote`.
+fn skip_string(chars: &mut impl Iterator<Item = (usize, char)>, quote: char) {
+    while let Some((_, c)) = chars.next() {
+        match c {
+            '\\' => _ = chars.next(),
+            c if c == quote => break,
+            _ => {}
+        }
+    }
+}
+
+/// The index of the first `c` in the CSS, if any, outside strings and `url(...)`.
+fn find_code(css: &str, c: char) -> Option<usize> {
+    code_chars(css)
+        .find(|(_, other)| *other == c)
+        .map(|(idx, _)| idx)
+}
+
+fn strip_comments(css: &str) -> String {
+    let mut out = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = code_chars(rest)
+        .find(|(idx, c)| *c == '/' && rest[idx + 1..].starts_with('*'))
+        .map(|(idx, _)| idx)
+    {
+        out.push_str(&rest[..start]);
+        rest = match rest[start + 2..].find("*/") {
+            Some(end) => &rest[start + 2 + end + 2..],
+            None => "",
+        };
+    }
+    out.push_str(rest);
+    out
+}
+
+fn scope_rules(css: &str, class: &str, out: &mut String) {
+    let mut rest = css;
+    while let Some(open) = find_code(rest, '{') {
+        let mut prelude = rest[..open].trim();
+        // statements like `@import` don't have a block
+        if let Some(end) = code_chars(prelude).filter(|(_, c)| *c == ';').last()
+        {
+            out.push_str(prelude[..end.0].trim());
+            out.push(';');
+            prelude = prelude[end.0 + 1..].trim();
+        }
+        let close = matching_brace(rest, open);
+        let body = &rest[open + 1..close];
+
+        if let Some(at_rule) = prelude.strip_prefix('@') {
+            out.push_str(prelude);
+            out.push('{');
+            let mut name =
+                at_rule.split(|c: char| !c.is_alphanumeric() && c != '-');
+            match name.next() {
+                Some("media" | "supports" | "container" | "layer") => {
+                    scope_rules(body, class, out)
+                }
+                _ => out.push_str(body.trim()),
+            }
+            out.push('}');
+        } else {
+            for (idx, selector) in split_top_level(prelude, ',').enumerate() {
+                if idx > 0 {
+                    out.push(',');
+                }
+                scope_selector(selector.trim(), class, out);
+            }
+            out.push('{');
+            out.push_str(body.trim());
+            out.push('}');
+        }
+
+        rest = rest.get(close + 1..).unwrap_or_default();
+    }
+    let rest = rest.trim();
+    if !rest.is_empty() {
+        out.push_str(rest);
+    }
+}
+
+/// The index of the `}` that closes the `{` at `open`, or the end of the string.
+fn matching_brace(css: &str, open: usize) -> usize {
+    let mut depth = 0;
+    for (idx, c) in code_chars(&css[open..]) {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return open + idx;
+                }
+            }
+            _ => {}
+        }
+    }
+    css.len()
+}
+
+/// Splits on `sep`, ignoring any inside `()`, `[]`, or strings.
+fn split_top_level(s: &str, sep: char) -> impl Iterator<Item = &str> {
+    let mut depth = 0;
+    let mut start = 0;
+    let mut parts = Vec::new();
+    for (idx, c) in code_chars(s) {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            c if c == sep && depth == 0 => {
+                parts.push(&s[start..idx]);
+                start = idx + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+    parts.into_iter()
+}
+
+fn scope_selector(selector: &str, class: &str, out: &mut String) {
+    // the class goes before the pseudo-element in the last compound selector, if any
+    let mut depth = 0;
+    let mut pseudo_element = None;
+    let mut prev = None;
+    for (idx, c) in code_chars(selector) {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            ' ' | '>' | '+' | '~' if depth == 0 => pseudo_element = None,
+            ':' if depth == 0 && prev == Some(':') => {
+                pseudo_element.get_or_insert(idx - 1);
+            }
+            _ => {}
+        }
+        prev = Some(c);
+    }
+    let insert_at = pseudo_element.unwrap_or(selector.len());
+    out.push_str(&selector[..insert_at]);
+    out.push('.');
+    out.push_str(class);
+    out.push_str(&selector[insert_at..]);
+}
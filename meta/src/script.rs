@@ -1,4 +1,4 @@
-use crate::register;
+use crate::{attribute_key, register};
 use leptos::{
     component, oco::Oco, prelude::*, tachys::html::element::script, IntoView,
 };
@@ -25,7 +25,8 @@ use leptos::{
 /// ```
 #[component]
 pub fn Script(
-    /// An ID for the `<script>` tag.
+    /// An ID for the `<script>` tag. Only the most recently rendered `<Script/>` with a given
+    /// `id` is added to the head.
     #[prop(optional, into)]
     id: Option<Oco<'static, str>>,
     /// The [`async`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/script#attr-async) attribute.
@@ -65,7 +66,9 @@ pub fn Script(
     #[prop(optional)]
     children: Option<Children>,
 ) -> impl IntoView {
+    let key = id.as_deref().map(|id| attribute_key("script", "id", id));
     register(
+        key,
         script()
             .id(id)
             .r#async(async_)
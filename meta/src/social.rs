@@ -0,0 +1,191 @@
+use crate::{attribute_key, register};
+use leptos::{
+    component,
+    prelude::CustomAttribute,
+    tachys::html::element::meta,
+    text_prop::TextProp,
+    IntoView,
+};
+
+/// A `<meta property=... content=...>` tag, replacing any earlier one with the same property.
+fn property_tag(property: &'static str, content: TextProp) -> impl IntoView {
+    register(
+        Some(attribute_key("meta", "property", property)),
+        meta()
+            .attr("property", property)
+            .content(move || content.get()),
+    )
+}
+
+/// A `<meta name=... content=...>` tag, replacing any earlier one with the same name.
+fn name_tag(name: &'static str, content: TextProp) -> impl IntoView {
+    register(
+        Some(attribute_key("meta", "name", name)),
+        meta().name(name).content(move || content.get()),
+    )
+}
+
+/// Sets the [Open Graph](https://ogp.me/) title of the page, which is shown when it is shared on
+/// social media.
+///
+/// Only one `og:title` is added to the head: one rendered in a page replaces the one in the
+/// layout around it, for as long as the page is mounted.
+///
+/// ```
+/// use leptos::prelude::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn Post() -> impl IntoView {
+///     view! {
+///       <OgTitle text="Signals, explained"/>
+///       <article>"..."</article>
+///     }
+/// }
+/// ```
+#[component]
+pub fn OgTitle(
+    /// The title.
+    #[prop(into)]
+    text: TextProp,
+) -> impl IntoView {
+    property_tag("og:title", text)
+}
+
+/// Sets the [Open Graph](https://ogp.me/#structured) image of the page, which is shown when it
+/// is shared on social media, along with its description and size.
+///
+/// Only one of each of these tags is added to the head: an `<OgImage/>` rendered in a page
+/// replaces the one in the layout around it, for as long as the page is mounted. As the
+/// description and size belong to the image, a page that sets its own image should set them too
+/// if the layout does.
+///
+/// ```
+/// use leptos::prelude::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn Post() -> impl IntoView {
+///     view! {
+///       <OgImage
+///         url="https://example.com/signals.png"
+///         alt="A graph of signals and effects"
+///         width=1200
+///         height=630
+///       />
+///       <article>"..."</article>
+///     }
+/// }
+/// ```
+#[component]
+pub fn OgImage(
+    /// The absolute URL of the image.
+    #[prop(into)]
+    url: TextProp,
+    /// A description of the image, for people who cannot see it.
+    #[prop(optional, into)]
+    alt: Option<TextProp>,
+    /// The width of the image, in pixels.
+    #[prop(optional)]
+    width: Option<u32>,
+    /// The height of the image, in pixels.
+    #[prop(optional)]
+    height: Option<u32>,
+) -> impl IntoView {
+    (
+        property_tag("og:image", url),
+        alt.map(|alt| property_tag("og:image:alt", alt)),
+        width.map(|width| {
+            property_tag("og:image:width", width.to_string().into())
+        }),
+        height.map(|height| {
+            property_tag("og:image:height", height.to_string().into())
+        }),
+    )
+}
+
+/// The kinds of [card](https://developer.x.com/en/docs/twitter-for-websites/cards/overview/abouts-cards)
+/// that can be shown when a page is shared on X (formerly Twitter).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TwitterCardType {
+    /// A small card, with a thumbnail image.
+    Summary,
+    /// A card with a large image above the text.
+    #[default]
+    SummaryLargeImage,
+    /// A card for a mobile app.
+    App,
+    /// A card with a video or audio player.
+    Player,
+}
+
+impl TwitterCardType {
+    /// The value of the `twitter:card` tag.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TwitterCardType::Summary => "summary",
+            TwitterCardType::SummaryLargeImage => "summary_large_image",
+            TwitterCardType::App => "app",
+            TwitterCardType::Player => "player",
+        }
+    }
+}
+
+/// Sets the `twitter:` tags that describe the card shown when the page is shared on X (formerly
+/// Twitter).
+///
+/// The title, description and image fall back to the Open Graph tags, like those set with
+/// [`OgTitle`] and [`OgImage`], so they only need to be given here if they should differ. Only
+/// one of each tag is added to the head: a `<TwitterCard/>` rendered in a page replaces the tags
+/// it sets in the layout around it, for as long as the page is mounted.
+///
+/// ```
+/// use leptos::prelude::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn MyApp() -> impl IntoView {
+///     provide_meta_context();
+///
+///     view! {
+///       <TwitterCard site="@leptos_rs"/>
+///       <OgTitle text="Leptos"/>
+///       <OgImage url="https://leptos.dev/card.png" alt="The Leptos logo"/>
+///     }
+/// }
+/// ```
+#[component]
+pub fn TwitterCard(
+    /// The kind of card.
+    #[prop(optional)]
+    card: TwitterCardType,
+    /// The `@username` of the site.
+    #[prop(optional, into)]
+    site: Option<TextProp>,
+    /// The `@username` of the author of the page.
+    #[prop(optional, into)]
+    creator: Option<TextProp>,
+    /// The title, if it differs from `og:title`.
+    #[prop(optional, into)]
+    title: Option<TextProp>,
+    /// The description, if it differs from `og:description`.
+    #[prop(optional, into)]
+    description: Option<TextProp>,
+    /// The absolute URL of the image, if it differs from `og:image`.
+    #[prop(optional, into)]
+    image: Option<TextProp>,
+    /// A description of the image, if it differs from `og:image:alt`.
+    #[prop(optional, into)]
+    image_alt: Option<TextProp>,
+) -> impl IntoView {
+    (
+        name_tag("twitter:card", card.as_str().into()),
+        site.map(|site| name_tag("twitter:site", site)),
+        creator.map(|creator| name_tag("twitter:creator", creator)),
+        title.map(|title| name_tag("twitter:title", title)),
+        description
+            .map(|description| name_tag("twitter:description", description)),
+        image.map(|image| name_tag("twitter:image", image)),
+        image_alt.map(|alt| name_tag("twitter:image:alt", alt)),
+    )
+}
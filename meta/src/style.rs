@@ -1,4 +1,4 @@
-use crate::register;
+use crate::{attribute_key, register};
 use leptos::{
     component, oco::Oco, prelude::*, tachys::html::element::style, IntoView,
 };
@@ -25,7 +25,8 @@ use leptos::{
 /// ```
 #[component]
 pub fn Style(
-    /// An ID for the `<script>` tag.
+    /// An ID for the `<style>` tag. Only the most recently rendered `<Style/>` with a given `id`
+    /// is added to the head.
     #[prop(optional, into)]
     id: Option<Oco<'static, str>>,
     /// The [`media`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/style#attr-media) attribute.
@@ -44,7 +45,9 @@ pub fn Style(
     #[prop(optional)]
     children: Option<Children>,
 ) -> impl IntoView {
+    let key = id.as_deref().map(|id| attribute_key("style", "id", id));
     register(
+        key,
         style()
             .id(id)
             .media(media)
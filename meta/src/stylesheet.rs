@@ -1,4 +1,4 @@
-use crate::register;
+use crate::{attribute_key, register};
 use leptos::{
     attr::global::GlobalAttributes, component, tachys::html::element::link,
     IntoView,
@@ -27,10 +27,16 @@ pub fn Stylesheet(
     /// The URL at which the stylesheet is located.
     #[prop(into)]
     href: String,
-    /// An ID for the stylesheet.
+    /// An ID for the stylesheet. Only one stylesheet with a given `id`, or without an `id`
+    /// but with a given `href`, is added to the head.
     #[prop(optional, into)]
     id: Option<String>,
 ) -> impl IntoView {
+    // the same stylesheet is only loaded once
+    let key = match &id {
+        Some(id) => attribute_key("link", "id", id),
+        None => attribute_key("link", "href", &href),
+    };
     // TODO additional attributes
-    register(link().id(id).rel("stylesheet").href(href))
+    register(Some(key), link().id(id).rel("stylesheet").href(href))
 }
@@ -6,6 +6,8 @@ use leptos::{
     reactive_graph::{
         effect::RenderEffect,
         owner::{use_context, Owner},
+        signal::ArcTrigger,
+        traits::{Track, Trigger},
     },
     tachys::{
         dom::document,
@@ -21,30 +23,88 @@ use leptos::{
 };
 use or_poisoned::OrPoisoned;
 use send_wrapper::SendWrapper;
-use std::sync::{Arc, RwLock};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, RwLock,
+};
 use wasm_bindgen::{JsCast, UnwrapThrowExt};
 use web_sys::HtmlTitleElement;
 
 /// Contains the current state of the document's `<title>`.
+///
+/// Each `<Title/>` that is mounted adds its text and formatter on top of those set by the
+/// `<Title/>` components that were mounted before it, and the most recent ones are used. When it
+/// is unmounted, for example when navigating away from a page, the previous ones are used again.
 #[derive(Clone, Default)]
 pub struct TitleContext {
     el: Arc<RwLock<Option<SendWrapper<HtmlTitleElement>>>>,
-    formatter: Arc<RwLock<Option<Formatter>>>,
-    text: Arc<RwLock<Option<TextProp>>>,
+    next_id: Arc<AtomicUsize>,
+    formatter: Arc<RwLock<Vec<(usize, Formatter)>>>,
+    text: Arc<RwLock<Vec<(usize, TextProp)>>>,
+    changed: ArcTrigger,
 }
 
 impl TitleContext {
     /// Converts the title into a string that can be used as the text content of a `<title>` tag.
+    ///
+    /// When called in a reactive context, this tracks the text and formatter, including any
+    /// signals they read.
     pub fn as_string(&self) -> Option<Oco<'static, str>> {
-        let title = self.text.read().or_poisoned().as_ref().map(TextProp::get);
+        self.changed.track();
+        let title = self
+            .text
+            .read()
+            .or_poisoned()
+            .last()
+            .map(|(_, text)| text.get());
         title.map(|title| {
-            if let Some(formatter) = &*self.formatter.read().or_poisoned() {
+            if let Some((_, formatter)) =
+                self.formatter.read().or_poisoned().last()
+            {
                 (formatter.0)(title.into_owned()).into()
             } else {
                 title
             }
         })
     }
+
+    fn next_id(&self) -> usize {
+        self.next_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    fn set(
+        &self,
+        id: usize,
+        formatter: Option<Formatter>,
+        text: Option<TextProp>,
+    ) {
+        if formatter.is_none() && text.is_none() {
+            return;
+        }
+        if let Some(formatter) = formatter {
+            let mut formatters = self.formatter.write().or_poisoned();
+            formatters.retain(|(other, _)| *other != id);
+            formatters.push((id, formatter));
+        }
+        if let Some(text) = text {
+            let mut texts = self.text.write().or_poisoned();
+            texts.retain(|(other, _)| *other != id);
+            texts.push((id, text));
+        }
+        self.changed.trigger();
+    }
+
+    fn remove(&self, id: usize) {
+        self.formatter
+            .write()
+            .or_poisoned()
+            .retain(|(other, _)| *other != id);
+        self.text
+            .write()
+            .or_poisoned()
+            .retain(|(other, _)| *other != id);
+        self.changed.trigger();
+    }
 }
 
 impl core::fmt::Debug for TitleContext {
@@ -54,6 +114,10 @@ impl core::fmt::Debug for TitleContext {
 }
 
 /// A function that is applied to the text value before setting `document.title`.
+///
+/// This is a template for the titles of the pages of a site, like
+/// `|text| format!("{text} — My Site")`. It can read signals, and the title is updated when
+/// they change.
 #[repr(transparent)]
 pub struct Formatter(Box<dyn Fn(String) -> String + Send + Sync>);
 
@@ -72,6 +136,11 @@ where
 /// The `title` and `formatter` can be set independently of one another. For example, you can create a root-level
 /// `<Title formatter=.../>` that will wrap each of the text values of `<Title/>` components created lower in the tree.
 ///
+/// Both can be reactive: the title is updated whenever the signals they read change, and when a
+/// `<Title/>` is unmounted, the title set by the one mounted before it is restored. During server
+/// rendering, the title is resolved once, using the values of the signals at the time the
+/// `<head>` is rendered.
+///
 /// ```
 /// use leptos::prelude::*;
 /// use leptos_meta::*;
@@ -118,19 +187,23 @@ pub fn Title(
 ) -> impl IntoView {
     let meta = use_head();
     let server_ctx = use_context::<ServerMetaContext>();
-    if let Some(cx) = server_ctx {
+    let id = if let Some(cx) = server_ctx {
         // if we are server rendering, we will not actually use these values via RenderHtml
         // instead, they'll be handled separately by the server integration
         // so it's safe to take them out of the props here
-        if let Some(formatter) = formatter.take() {
-            *cx.title.formatter.write().or_poisoned() = Some(formatter);
-        }
-        if let Some(text) = text.take() {
-            *cx.title.text.write().or_poisoned() = Some(text);
-        }
+        let id = cx.title.next_id();
+        cx.title.set(id, formatter.take(), text.take());
+        id
+    } else {
+        let id = meta.title.next_id();
+        // restores the previous title when this one is unmounted
+        let title = meta.title.clone();
+        Owner::on_cleanup(move || title.remove(id));
+        id
     };
 
     TitleView {
+        id,
         meta,
         formatter,
         text,
@@ -138,6 +211,7 @@ pub fn Title(
 }
 
 struct TitleView {
+    id: usize,
     meta: MetaContext,
     formatter: Option<Formatter>,
     text: Option<TextProp>,
@@ -184,7 +258,7 @@ impl TitleView {
 struct TitleViewState {
     // effect is stored in the view state to keep it alive until rebuild
     #[allow(dead_code)]
-    effect: RenderEffect<Oco<'static, str>>,
+    effect: RenderEffect<Option<Oco<'static, str>>>,
 }
 
 impl Render<Dom> for TitleView {
@@ -193,19 +267,18 @@ impl Render<Dom> for TitleView {
     fn build(mut self) -> Self::State {
         let el = self.el();
         let meta = self.meta;
-        if let Some(formatter) = self.formatter.take() {
-            *meta.title.formatter.write().or_poisoned() = Some(formatter);
-        }
-        if let Some(text) = self.text.take() {
-            *meta.title.text.write().or_poisoned() = Some(text);
-        }
+        meta.title
+            .set(self.id, self.formatter.take(), self.text.take());
         let effect = RenderEffect::new({
             let el = el.clone();
-            move |prev| {
-                let text = meta.title.as_string().unwrap_or_default();
+            move |prev: Option<Option<Oco<'static, str>>>| {
+                let text = meta.title.as_string();
 
-                if prev.as_ref() != Some(&text) {
-                    el.set_text_content(Some(&text));
+                // leaves the existing title alone until there is some text to format
+                if let Some(text) = &text {
+                    if prev.as_ref() != Some(&Some(text.clone())) {
+                        el.set_text_content(Some(text));
+                    }
                 }
 
                 text
@@ -262,20 +335,18 @@ impl RenderHtml<Dom> for TitleView {
     ) -> Self::State {
         let el = self.el();
         let meta = self.meta;
-        if let Some(formatter) = self.formatter.take() {
-            *meta.title.formatter.write().or_poisoned() = Some(formatter);
-        }
-        if let Some(text) = self.text.take() {
-            *meta.title.text.write().or_poisoned() = Some(text);
-        }
+        meta.title
+            .set(self.id, self.formatter.take(), self.text.take());
         let effect = RenderEffect::new({
             let el = el.clone();
-            move |prev| {
-                let text = meta.title.as_string().unwrap_or_default();
+            move |prev: Option<Option<Oco<'static, str>>>| {
+                let text = meta.title.as_string();
 
                 // don't reset the title on initial hydration
-                if prev.is_some() && prev.as_ref() != Some(&text) {
-                    el.set_text_content(Some(&text));
+                if let (Some(prev), Some(text)) = (prev, &text) {
+                    if prev.as_ref() != Some(text) {
+                        el.set_text_content(Some(text));
+                    }
                 }
 
                 text
@@ -0,0 +1,288 @@
+#![cfg(feature = "ssr")]
+
+use futures::{executor::block_on, stream, StreamExt};
+use leptos::prelude::*;
+use leptos_meta::*;
+
+#[component]
+fn Layout(children: Children) -> impl IntoView {
+    view! {
+        <Meta charset="utf-8"/>
+        <Meta name="description" content="A Leptos fan site."/>
+        <Stylesheet href="/site.css"/>
+        {children()}
+    }
+}
+
+#[component]
+fn Page() -> impl IntoView {
+    view! {
+        <Meta name="description" content="All about signals."/>
+        <Meta property="og:image" content="/one.png"/>
+        <Meta property="og:image" content="/two.png"/>
+        <Stylesheet href="/site.css"/>
+        <p>"Signals"</p>
+    }
+}
+
+fn render_document(app: impl FnOnce() -> String) -> String {
+    let (meta, output) = ServerMetaContext::new();
+    let body = Owner::new().with(|| {
+        provide_context(meta);
+        provide_meta_context();
+        app()
+    });
+    let shell =
+        format!("<html><head><!--HEAD--></head><body>{body}</body></html>");
+    block_on(async {
+        output
+            .inject_meta_context(stream::iter([shell]))
+            .await
+            .collect::<String>()
+            .await
+    })
+}
+
+fn render_head(app: impl FnOnce() -> String) -> String {
+    let html = render_document(app);
+    let start = html.find("<head>").unwrap() + "<head>".len();
+    let end = html.find("</head>").unwrap();
+    html[start..end].to_string()
+}
+
+#[test]
+fn head_tags_are_collected_during_ssr() {
+    let head = render_head(|| {
+        view! {
+            <Title text="Home"/>
+            <Meta name="author" content="Greg"/>
+            <Link rel="icon" href="/favicon.ico"/>
+        }
+        .to_html()
+    });
+    assert_eq!(
+        head,
+        "<title>Home</title><!--HEAD--><link href=\"/favicon.ico\" \
+         rel=\"icon\"><meta name=\"author\" content=\"Greg\">"
+    );
+}
+
+#[test]
+fn later_tags_replace_earlier_ones_with_the_same_key() {
+    let head = render_head(|| {
+        view! {
+            <Layout>
+                <Page/>
+            </Layout>
+        }
+        .to_html()
+    });
+    assert_eq!(
+        head,
+        "<!--HEAD--><meta property=\"og:image\" content=\"/one.png\"><meta \
+         property=\"og:image\" content=\"/two.png\"><meta \
+         charset=\"utf-8\"><meta name=\"description\" content=\"All about \
+         signals.\"><link rel=\"stylesheet\" href=\"/site.css\">"
+    );
+}
+
+#[test]
+fn tags_with_reactive_names_replace_those_with_the_same_key() {
+    let head = render_head(|| {
+        let name = RwSignal::new("description");
+        view! {
+            <Layout>
+                <Meta name=move || name.get() content="All about memos."/>
+            </Layout>
+        }
+        .to_html()
+    });
+    assert_eq!(
+        head,
+        "<!--HEAD--><meta charset=\"utf-8\"><meta name=\"description\" \
+         content=\"All about memos.\"><link rel=\"stylesheet\" \
+         href=\"/site.css\">"
+    );
+}
+
+#[test]
+fn only_the_last_canonical_link_is_rendered() {
+    let head = render_head(|| {
+        view! {
+            <Link rel="canonical" href="https://leptos.dev/"/>
+            <Link rel="canonical" href="https://leptos.dev/blog"/>
+            <Link rel="alternate" href="https://leptos.dev/fr/blog"/>
+        }
+        .to_html()
+    });
+    assert_eq!(
+        head,
+        "<!--HEAD--><link href=\"https://leptos.dev/fr/blog\" \
+         rel=\"alternate\"><link href=\"https://leptos.dev/blog\" \
+         rel=\"canonical\">"
+    );
+}
+
+const CARD_CSS: &str = "/* cards */
+    p, .title > a:hover::after { color: red; }
+    @media (min-width: 600px) { div { display: flex; } }
+    @keyframes fade { from { opacity: 0; } }";
+
+#[component]
+fn Card() -> impl IntoView {
+    let class = scoped_class(CARD_CSS);
+    view! { class = class,
+        <ScopedStyle css=CARD_CSS/>
+        <div><p>"Card"</p></div>
+    }
+}
+
+#[test]
+fn scoped_styles_are_rewritten_and_added_once() {
+    let class = scoped_class(CARD_CSS);
+    assert_eq!(class, scoped_class(CARD_CSS));
+    assert_ne!(class, scoped_class("p { color: blue; }"));
+
+    let mut body = String::new();
+    let head = render_head(|| {
+        body = view! { <Card/><Card/> }.to_html();
+        body.clone()
+    });
+    assert_eq!(
+        body,
+        format!(
+            "<div class=\"{class}\"><p class=\"{class}\">Card</p></div>\
+             <div class=\"{class}\"><p class=\"{class}\">Card</p></div>"
+        )
+    );
+    assert_eq!(
+        head,
+        format!(
+            "<!--HEAD--><style id=\"{class}\">p.{class},.title > \
+             a:hover.{class}::after{{color: red;}}@media (min-width: \
+             600px){{div.{class}{{display: flex;}}}}@keyframes \
+             fade{{from {{ opacity: 0; }}}}</style>"
+        )
+    );
+}
+
+#[test]
+fn scoped_styles_skip_strings_and_urls() {
+    const CSS: &str = r#"a[title="x, y"]::before { content: "} /* {"; }
+        div { background: url(/a{b}/*.png); }"#;
+    let class = scoped_class(CSS);
+    let head = render_head(|| view! { <ScopedStyle css=CSS/> }.to_html());
+    assert_eq!(
+        head,
+        format!(
+            "<!--HEAD--><style id=\"{class}\">a[title=\"x, \
+             y\"].{class}::before{{content: \"}} /* {{\";}}div.{class}{{background: \
+             url(/a{{b}}/*.png);}}</style>"
+        )
+    );
+}
+
+#[test]
+fn dir_sets_the_direction_of_the_html_element() {
+    let html = render_document(|| {
+        view! { <Dir dir=leptos::direction::Direction::Rtl/> }.to_html()
+    });
+    assert!(html.starts_with("<html dir=\"rtl\"><head>"), "{html}");
+
+    let html = render_document(|| view! { <Dir/> }.to_html());
+    assert!(html.starts_with("<html dir=\"ltr\"><head>"), "{html}");
+}
+
+#[test]
+fn title_templates_and_meta_values_are_resolved_once() {
+    let head = render_head(|| {
+        let site = ArcRwSignal::new("Leptos".to_string());
+        let page = ArcRwSignal::new("Home".to_string());
+        let html = view! {
+            <Title formatter={
+                let site = site.clone();
+                move |text| format!("{text} – {}", site.get())
+            }/>
+            <Title text={
+                let page = page.clone();
+                move || page.get()
+            }/>
+            <Meta name="description" content={
+                let page = page.clone();
+                move || format!("About {}", page.get())
+            }/>
+        }
+        .to_html();
+        // the title is resolved when the <head> is rendered, after the rest of the page
+        site.set("My Site".to_string());
+        page.set("Blog".to_string());
+        html
+    });
+    assert_eq!(
+        head,
+        "<title>Blog – My Site</title><!--HEAD--><meta name=\"description\" \
+         content=\"About Home\">"
+    );
+}
+
+#[derive(Clone, serde::Serialize)]
+struct Article {
+    #[serde(rename = "@type")]
+    ty: &'static str,
+    headline: String,
+}
+
+#[test]
+fn json_ld_is_escaped() {
+    let head = render_head(|| {
+        let article = Article {
+            ty: "Article",
+            headline: "</script><script>alert('&')</script>".to_string(),
+        };
+        view! { <JsonLd data=article id="article"/> }.to_html()
+    });
+    assert_eq!(
+        head,
+        concat!(
+            r#"<!--HEAD--><script id="article" type="application/ld+json">"#,
+            r#"{"@type":"Article","headline":"\u003c/script\u003e"#,
+            r#"\u003cscript\u003ealert('\u0026')\u003c/script\u003e"}"#,
+            "</script>"
+        )
+    );
+}
+
+#[component]
+fn SocialLayout(children: Children) -> impl IntoView {
+    view! {
+        <TwitterCard site="@leptos_rs" card=TwitterCardType::Summary/>
+        <OgTitle text="Leptos"/>
+        <OgImage url="https://leptos.dev/logo.png" alt="The logo"/>
+        {children()}
+    }
+}
+
+#[test]
+fn social_tags_in_pages_replace_those_in_layouts() {
+    let head = render_head(|| {
+        view! {
+            <SocialLayout>
+                <TwitterCard creator="@gbj"/>
+                <OgTitle text="Signals"/>
+                <OgImage url="https://leptos.dev/signals.png" alt="A graph" width=1200/>
+            </SocialLayout>
+        }
+        .to_html()
+    });
+    assert_eq!(
+        head,
+        "<!--HEAD--><meta name=\"twitter:card\" \
+         content=\"summary_large_image\"><meta name=\"twitter:site\" \
+         content=\"@leptos_rs\"><meta property=\"og:title\" \
+         content=\"Signals\"><meta property=\"og:image\" \
+         content=\"https://leptos.dev/signals.png\"><meta \
+         property=\"og:image:alt\" content=\"A graph\"><meta \
+         name=\"twitter:creator\" content=\"@gbj\"><meta \
+         property=\"og:image:width\" content=\"1200\">"
+    );
+}
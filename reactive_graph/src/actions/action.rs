@@ -70,6 +70,11 @@ use std::{future::Future, panic::Location, pin::Pin, sync::Arc};
 /// # });
 /// ```
 ///
+/// To reload data after a mutation, read the action's [`version`](ArcAction::version) in the
+/// source of any resource that depends on it. The resource will refetch each time the action
+/// completes, and because it holds onto its previous value while reloading, reading it inside a
+/// `<Transition/>` keeps showing the old data rather than the fallback until the new data arrives.
+///
 /// The input to the `async` function should always be a single value,
 /// but it can be of any type. The argument is always passed by reference to the
 /// function, because it is stored in [Action::input] as well.
@@ -556,6 +561,11 @@ where
 /// # });
 /// ```
 ///
+/// To reload data after a mutation, read the action's [`version`](Action::version) in the
+/// source of any resource that depends on it. The resource will refetch each time the action
+/// completes, and because it holds onto its previous value while reloading, reading it inside a
+/// `<Transition/>` keeps showing the old data rather than the fallback until the new data arrives.
+///
 /// The input to the `async` function should always be a single value,
 /// but it can be of any type. The argument is always passed by reference to the
 /// function, because it is stored in [Action::input] as well.
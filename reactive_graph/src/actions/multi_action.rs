@@ -19,6 +19,11 @@ use std::{fmt::Debug, future::Future, panic::Location, pin::Pin, sync::Arc};
 /// If you’re trying to occasionally run an `async` function in response to something
 /// like a user adding a task to a todo list, you’re in the right place.
 ///
+/// Each dispatch creates a new [`ArcSubmission`], with its own `input`, `pending`, and `value`
+/// signals. Because a submission's input is available as soon as it is dispatched, this can be
+/// used for optimistic UI: for example, rendering a row for each pending submission to a todo
+/// list before the server has confirmed it.
+///
 /// The reference-counted, `Clone` (but not `Copy` version of a `MultiAction` is an [`ArcMultiAction`].
 ///
 /// ```rust
@@ -815,3 +820,41 @@ impl<I, O, S> Clone for Submission<I, O, S> {
 }
 
 impl<I, O, S> Copy for Submission<I, O, S> {}
+
+/// Creates a new multi-action.
+///
+/// The input to the `async` function should always be a single value,
+/// but it can be of any type. The argument is always passed by reference to the
+/// function, because it is stored in [Submission::input] as well.
+///
+/// ```rust
+/// # use reactive_graph::actions::*;
+/// # use reactive_graph::prelude::*;
+/// # tokio_test::block_on(async move {
+/// # any_spawner::Executor::init_tokio();
+/// # let _guard = reactive_graph::diagnostics::SpecialNonReactiveZone::enter();
+/// # #[allow(deprecated)]
+/// let add_todo = create_multi_action(|task: &String| {
+///     let task = task.clone();
+///     async move { task.len() }
+/// });
+///
+/// add_todo.dispatch("Buy milk".to_string());
+/// add_todo.dispatch("Profit!!!".to_string());
+/// assert_eq!(add_todo.submissions().with(Vec::len), 2);
+/// # });
+/// ```
+#[inline(always)]
+#[track_caller]
+#[deprecated = "This function is being removed to conform to Rust idioms. \
+                Please use `MultiAction::new()` instead."]
+pub fn create_multi_action<I, O, Fut>(
+    action_fn: impl Fn(&I) -> Fut + Send + Sync + 'static,
+) -> MultiAction<I, O>
+where
+    I: Send + Sync + 'static,
+    O: Send + Sync + 'static,
+    Fut: Future<Output = O> + Send + 'static,
+{
+    MultiAction::new(action_fn)
+}
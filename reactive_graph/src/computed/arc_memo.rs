@@ -161,19 +161,26 @@ where
     where
         T: PartialEq,
     {
+        let defined_at = Location::caller();
         let inner = Arc::new_cyclic(|weak| {
             let subscriber = AnySubscriber(
                 weak.as_ptr() as usize,
                 Weak::clone(weak) as Weak<dyn Subscriber + Send + Sync>,
             );
 
-            RwLock::new(MemoInner::new(Arc::new(fun), subscriber))
+            RwLock::new(MemoInner::new(Arc::new(fun), subscriber, defined_at))
         });
-        Self {
+        let this = Self {
             #[cfg(debug_assertions)]
-            defined_at: Location::caller(),
+            defined_at,
             inner,
-        }
+        };
+        #[cfg(feature = "introspection")]
+        crate::introspection::register_source(
+            crate::introspection::NodeKind::Memo,
+            this.to_any_source(),
+        );
+        this
     }
 }
 
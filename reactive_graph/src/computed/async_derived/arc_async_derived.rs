@@ -12,8 +12,8 @@ use crate::{
         SubscriberSet, ToAnySource, ToAnySubscriber, WithObserver,
     },
     owner::{use_context, Owner},
-    signal::guards::{AsyncPlain, ReadGuard},
-    traits::{DefinedAt, ReadUntracked},
+    signal::guards::{AsyncPlain, ReadGuard, WriteGuard},
+    traits::{DefinedAt, IsDisposed, ReadUntracked, Trigger, Writeable},
     transition::AsyncTransition,
 };
 use any_spawner::Executor;
@@ -115,6 +115,10 @@ pub(crate) trait BlockingLock<T> {
     fn blocking_read(&self) -> async_lock::RwLockReadGuard<'_, T>;
 
     fn blocking_write(&self) -> async_lock::RwLockWriteGuard<'_, T>;
+
+    fn blocking_write_arc(
+        self: &Arc<Self>,
+    ) -> async_lock::RwLockWriteGuardArc<T>;
 }
 
 impl<T> BlockingLock<T> for AsyncRwLock<T> {
@@ -152,6 +156,19 @@ impl<T> BlockingLock<T> for AsyncRwLock<T> {
             self.write().now_or_never().unwrap()
         }
     }
+
+    fn blocking_write_arc(
+        self: &Arc<Self>,
+    ) -> async_lock::RwLockWriteGuardArc<T> {
+        #[cfg(not(target_family = "wasm"))]
+        {
+            self.write_arc_blocking()
+        }
+        #[cfg(target_family = "wasm")]
+        {
+            self.write_arc().now_or_never().unwrap()
+        }
+    }
 }
 
 impl<T> Clone for ArcAsyncDerived<T> {
@@ -219,6 +236,11 @@ macro_rules! spawn_derived {
             inner: Arc::clone(&inner),
             loading: Arc::new(AtomicBool::new(!is_ready)),
         };
+        #[cfg(feature = "introspection")]
+        crate::introspection::register_source(
+            crate::introspection::NodeKind::AsyncDerived,
+            this.to_any_source(),
+        );
         let any_subscriber = this.to_any_subscriber();
         let initial_fut = owner.with_cleanup(|| {
             any_subscriber
@@ -414,6 +436,13 @@ impl<T: 'static> ArcAsyncDerived<T> {
             wakers: Arc::clone(&self.wakers),
         }
     }
+
+    /// Re-runs the async computation, even if none of its sources have changed.
+    ///
+    /// The current value is kept until the new one is ready.
+    pub fn refetch(&self) {
+        self.inner.mark_dirty();
+    }
 }
 
 impl<T: 'static> ReadUntracked for ArcAsyncDerived<T> {
@@ -434,6 +463,50 @@ impl<T: 'static> ReadUntracked for ArcAsyncDerived<T> {
     }
 }
 
+impl<T> IsDisposed for ArcAsyncDerived<T> {
+    #[inline(always)]
+    fn is_disposed(&self) -> bool {
+        false
+    }
+}
+
+impl<T> Trigger for ArcAsyncDerived<T> {
+    fn trigger(&self) {
+        // notify reactive subscribers, but do not re-run the async computation
+        let subscribers = self.inner.read().or_poisoned().subscribers.clone();
+        for sub in subscribers {
+            sub.mark_dirty();
+        }
+
+        // notify async .awaiters
+        for waker in mem::take(&mut *self.wakers.write().or_poisoned()) {
+            waker.wake();
+        }
+    }
+}
+
+impl<T: 'static> Writeable for ArcAsyncDerived<T> {
+    type Value = Option<T>;
+
+    #[allow(refining_impl_trait)]
+    fn try_write(
+        &self,
+    ) -> Option<WriteGuard<Self, async_lock::RwLockWriteGuardArc<Option<T>>>>
+    {
+        Some(WriteGuard::new(
+            self.clone(),
+            self.value.blocking_write_arc(),
+        ))
+    }
+
+    #[allow(refining_impl_trait)]
+    fn try_write_untracked(
+        &self,
+    ) -> Option<async_lock::RwLockWriteGuardArc<Option<T>>> {
+        Some(self.value.blocking_write_arc())
+    }
+}
+
 impl<T: 'static> ToAnySource for ArcAsyncDerived<T> {
     fn to_any_source(&self) -> AnySource {
         AnySource(
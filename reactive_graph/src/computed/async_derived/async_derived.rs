@@ -5,8 +5,10 @@ use crate::{
         ToAnySource, ToAnySubscriber,
     },
     owner::{FromLocal, LocalStorage, Storage, StoredValue, SyncStorage},
-    signal::guards::{AsyncPlain, ReadGuard},
-    traits::{DefinedAt, Dispose, ReadUntracked},
+    signal::guards::{AsyncPlain, ReadGuard, WriteGuard},
+    traits::{
+        DefinedAt, Dispose, IsDisposed, ReadUntracked, Trigger, Writeable,
+    },
     unwrap_signal,
 };
 use core::fmt::Debug;
@@ -236,6 +238,15 @@ where
             .unwrap_or_else(unwrap_signal!(self));
         this.ready()
     }
+
+    /// Re-runs the async computation, even if none of its sources have changed.
+    ///
+    /// The current value is kept until the new one is ready.
+    pub fn refetch(&self) {
+        if let Some(inner) = self.inner.try_get_value() {
+            inner.refetch();
+        }
+    }
 }
 
 impl<T, S> Copy for AsyncDerived<T, S> {}
@@ -286,6 +297,53 @@ where
     }
 }
 
+impl<T, S> IsDisposed for AsyncDerived<T, S> {
+    fn is_disposed(&self) -> bool {
+        self.inner.is_disposed()
+    }
+}
+
+impl<T, S> Trigger for AsyncDerived<T, S>
+where
+    T: 'static,
+    S: Storage<ArcAsyncDerived<T>>,
+{
+    fn trigger(&self) {
+        if let Some(inner) = self.inner.try_get_value() {
+            inner.trigger();
+        }
+    }
+}
+
+impl<T, S> Writeable for AsyncDerived<T, S>
+where
+    T: 'static,
+    S: Storage<ArcAsyncDerived<T>>,
+{
+    type Value = Option<T>;
+
+    #[allow(refining_impl_trait)]
+    fn try_write(
+        &self,
+    ) -> Option<
+        WriteGuard<
+            ArcAsyncDerived<T>,
+            async_lock::RwLockWriteGuardArc<Option<T>>,
+        >,
+    > {
+        self.inner.try_get_value().and_then(|inner| inner.try_write())
+    }
+
+    #[allow(refining_impl_trait)]
+    fn try_write_untracked(
+        &self,
+    ) -> Option<async_lock::RwLockWriteGuardArc<Option<T>>> {
+        self.inner
+            .try_get_value()
+            .and_then(|inner| inner.try_write_untracked())
+    }
+}
+
 impl<T, S> ToAnySource for AsyncDerived<T, S>
 where
     T: 'static,
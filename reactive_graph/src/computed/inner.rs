@@ -8,6 +8,7 @@ use crate::{
 use or_poisoned::OrPoisoned;
 use std::{
     fmt::Debug,
+    panic::Location,
     sync::{Arc, RwLock},
 };
 
@@ -23,6 +24,8 @@ where
     pub(crate) sources: SourceSet,
     pub(crate) subscribers: SubscriberSet,
     pub(crate) any_subscriber: AnySubscriber,
+    #[cfg(debug_assertions)]
+    pub(crate) defined_at: &'static Location<'static>,
 }
 
 impl<T, S> Debug for MemoInner<T, S>
@@ -39,9 +42,11 @@ where
     S: Storage<T>,
 {
     #[allow(clippy::type_complexity)]
+    #[cfg_attr(not(debug_assertions), allow(unused_variables))]
     pub fn new(
         fun: Arc<dyn Fn(Option<T>) -> (T, bool) + Send + Sync>,
         any_subscriber: AnySubscriber,
+        defined_at: &'static Location<'static>,
     ) -> Self {
         Self {
             value: None,
@@ -51,6 +56,8 @@ where
             sources: Default::default(),
             subscribers: SubscriberSet::new(),
             any_subscriber,
+            #[cfg(debug_assertions)]
+            defined_at,
         }
     }
 }
@@ -105,6 +112,11 @@ where
 
             let any_subscriber =
                 { self.read().or_poisoned().any_subscriber.clone() };
+            #[cfg(debug_assertions)]
+            let _running = crate::graph::cycle::enter_memo(
+                any_subscriber.0,
+                Some(self.read().or_poisoned().defined_at),
+            );
             any_subscriber.clear_sources(&any_subscriber);
             let (new_value, changed) = owner.with_cleanup(|| {
                 any_subscriber
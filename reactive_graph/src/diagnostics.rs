@@ -7,6 +7,10 @@
 //!
 //! This module provides utilities to suppress those warnings by entering a
 //! [`SpecialNonReactiveZone`].
+//!
+//! It also allows a [`ReactiveObserver`] to be installed with [`System::set_observer`], which is
+//! told each time a signal is written to or an effect runs. This can be used to answer questions
+//! like “why did this effect run?” without adding logging to the app itself.
 
 /// Marks an execution block that is known not to be reactive, and suppresses warnings.
 #[derive(Debug)]
@@ -16,11 +20,18 @@ pub struct SpecialNonReactiveZone;
 #[derive(Debug)]
 pub struct SpecialNonReactiveZoneGuard;
 
+use crate::graph::SourceSet;
+use or_poisoned::OrPoisoned;
 use pin_project_lite::pin_project;
 use std::{
     cell::Cell,
     future::Future,
+    panic::Location,
     pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
     task::{Context, Poll},
 };
 
@@ -92,3 +103,141 @@ pub fn suppress_resource_load(suppress: bool) {
 pub fn is_suppressing_resource_load() -> bool {
     SUPPRESS_RESOURCE_LOAD.with(|w| w.get())
 }
+
+/// Receives notifications about activity in the reactive system, once installed with
+/// [`System::set_observer`].
+///
+/// Each method has a default implementation that does nothing, so an observer only needs to
+/// implement the ones it is interested in. Methods are called synchronously, on the thread where
+/// the activity happened, so they should return quickly.
+///
+/// Source locations are only available in debug builds, and are `None` otherwise.
+pub trait ReactiveObserver: Send + Sync {
+    /// Called when a signal or trigger notifies its subscribers that it has changed.
+    ///
+    /// `defined_at` is the location where the signal was created.
+    fn signal_written(&self, defined_at: Option<&'static Location<'static>>) {
+        _ = defined_at;
+    }
+
+    /// Called just before an effect or render effect runs.
+    ///
+    /// `defined_at` is the location where the effect was created. `sources` are the locations of
+    /// the signals and memos it read the last time it ran, one of which has changed to cause
+    /// this run. It is empty the first time an effect runs.
+    fn effect_ran(
+        &self,
+        defined_at: Option<&'static Location<'static>>,
+        sources: &[Option<&'static Location<'static>>],
+    ) {
+        _ = (defined_at, sources);
+    }
+}
+
+/// Configures the reactive system as a whole.
+#[derive(Debug)]
+pub struct System;
+
+static OBSERVER: RwLock<Option<Arc<dyn ReactiveObserver>>> = RwLock::new(None);
+
+// checked before locking the observer, so that there is no cost when none is installed
+static HAS_OBSERVER: AtomicBool = AtomicBool::new(false);
+
+impl System {
+    /// Installs an observer that is notified whenever a signal is written to or an effect runs,
+    /// replacing any observer that was installed before.
+    ///
+    /// The locations that signals and effects were defined at are only tracked in debug builds,
+    /// so in release builds the observer is still notified, but always receives `None`.
+    ///
+    /// ```rust
+    /// # use reactive_graph::diagnostics::{ReactiveObserver, System};
+    /// # use std::panic::Location;
+    /// struct LogWrites;
+    ///
+    /// impl ReactiveObserver for LogWrites {
+    ///     fn signal_written(&self, defined_at: Option<&'static Location<'static>>) {
+    ///         if let Some(location) = defined_at {
+    ///             println!("signal defined at {location} was written");
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// System::set_observer(LogWrites);
+    /// ```
+    pub fn set_observer(observer: impl ReactiveObserver + 'static) {
+        *OBSERVER.write().or_poisoned() = Some(Arc::new(observer));
+        HAS_OBSERVER.store(true, Ordering::Release);
+    }
+
+    /// Removes the observer installed with [`System::set_observer`], if any.
+    pub fn clear_observer() {
+        HAS_OBSERVER.store(false, Ordering::Release);
+        *OBSERVER.write().or_poisoned() = None;
+    }
+
+    fn observer() -> Option<Arc<dyn ReactiveObserver>> {
+        if HAS_OBSERVER.load(Ordering::Acquire) {
+            OBSERVER.read().or_poisoned().clone()
+        } else {
+            None
+        }
+    }
+}
+
+pub(crate) fn signal_written(defined_at: Option<&'static Location<'static>>) {
+    if let Some(observer) = System::observer() {
+        observer.signal_written(defined_at);
+    }
+}
+
+pub(crate) fn effect_ran(
+    defined_at: Option<&'static Location<'static>>,
+    sources: &SourceSet,
+) {
+    if let Some(observer) = System::observer() {
+        let sources = sources
+            .into_iter()
+            .map(crate::traits::DefinedAt::defined_at)
+            .collect::<Vec<_>>();
+        observer.effect_ran(defined_at, &sources);
+    }
+}
+
+/// A [`ReactiveObserver`] that emits a [`tracing`] event at the `TRACE` level for each signal
+/// write and effect run, with the locations where they were defined.
+///
+/// ```rust
+/// # use reactive_graph::diagnostics::{System, TracingObserver};
+/// System::set_observer(TracingObserver);
+/// ```
+#[cfg(feature = "tracing")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TracingObserver;
+
+#[cfg(feature = "tracing")]
+impl ReactiveObserver for TracingObserver {
+    fn signal_written(&self, defined_at: Option<&'static Location<'static>>) {
+        tracing::trace!(
+            defined_at = defined_at.map(tracing::field::display),
+            "signal written"
+        );
+    }
+
+    fn effect_ran(
+        &self,
+        defined_at: Option<&'static Location<'static>>,
+        sources: &[Option<&'static Location<'static>>],
+    ) {
+        let sources = sources
+            .iter()
+            .flatten()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        tracing::trace!(
+            defined_at = defined_at.map(tracing::field::display),
+            ?sources,
+            "effect ran"
+        );
+    }
+}
@@ -3,6 +3,8 @@
 #[allow(clippy::module_inception)]
 mod effect;
 mod inner;
+mod priority;
 mod render_effect;
 pub use effect::*;
+pub use priority::*;
 pub use render_effect::*;
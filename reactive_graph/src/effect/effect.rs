@@ -1,6 +1,6 @@
 use crate::{
     channel::{channel, Receiver},
-    effect::inner::EffectInner,
+    effect::{inner::EffectInner, EffectPriority},
     graph::{
         AnySubscriber, ReactiveNode, SourceSet, Subscriber, ToAnySubscriber,
         WithObserver,
@@ -12,8 +12,11 @@ use any_spawner::Executor;
 use futures::StreamExt;
 use or_poisoned::OrPoisoned;
 use std::{
+    cell::RefCell,
+    future::Future,
     mem,
-    sync::{Arc, RwLock},
+    panic::Location,
+    sync::{Arc, RwLock, Weak},
 };
 
 /// Effects run a certain chunk of code whenever the signals they depend on change.
@@ -67,7 +70,9 @@ use std::{
 ///
 /// 1. **Scheduling**: Effects run after synchronous work, on the next “tick” of the reactive
 ///    system. This makes them suitable for “on mount” actions: they will fire immediately after
-///    DOM rendering.
+///    DOM rendering. Effects created inside [`defer_effects`] are held back until it returns,
+///    which the `mount` functions only do once the view has been mounted. To update the view
+///    itself during rendering, use a [`RenderEffect`](super::RenderEffect) instead.
 /// 2. By default, effects do not run unless the `effects` feature is enabled. If you are using
 ///    this with a web framework, this generally means that effects **do not run on the server**.
 ///    and you can call browser-specific APIs within the effect function without causing issues.
@@ -87,24 +92,125 @@ impl<S> Dispose for Effect<S> {
     }
 }
 
-fn effect_base() -> (Receiver, Owner, Arc<RwLock<EffectInner>>) {
-    let (mut observer, rx) = channel();
-
-    // spawn the effect asynchronously
-    // we'll notify once so it runs on the next tick,
-    // to register observed values
-    observer.notify();
+#[cfg_attr(not(debug_assertions), allow(unused_variables))]
+fn effect_base(
+    defined_at: &'static Location<'static>,
+    priority: EffectPriority,
+) -> (Receiver, Owner, Arc<RwLock<EffectInner>>) {
+    let (observer, rx) = channel();
 
     let owner = Owner::new();
     let inner = Arc::new(RwLock::new(EffectInner {
         dirty: true,
         observer,
         sources: SourceSet::new(),
+        layout: priority == EffectPriority::Layout,
+        pending_layout: None,
+        #[cfg(debug_assertions)]
+        defined_at,
     }));
+    #[cfg(feature = "introspection")]
+    crate::introspection::register_subscriber(
+        crate::introspection::NodeKind::Effect,
+        inner.to_any_subscriber(),
+    );
+
+    // spawn the effect asynchronously
+    // we'll notify once so it runs on the next tick,
+    // to register observed values
+    // inside defer_effects(), this waits until it returns
+    let deferred = DEFERRED.with_borrow_mut(|effects| {
+        if let Some(effects) = effects {
+            effects.push(Arc::downgrade(&inner));
+            true
+        } else {
+            false
+        }
+    });
+    if !deferred {
+        inner.write().or_poisoned().notify();
+    }
 
     (rx, owner, inner)
 }
 
+/// Runs an effect each time it is notified, once it is its turn to run, until it is stopped.
+fn run_effect<T>(
+    mut rx: Receiver,
+    owner: Owner,
+    inner: &Arc<RwLock<EffectInner>>,
+    priority: EffectPriority,
+    mut fun: impl FnMut(Option<T>) -> T,
+) -> impl Future<Output = ()> {
+    let subscriber = inner.to_any_subscriber();
+    let inner = Arc::downgrade(inner);
+    let mut value = None;
+    let mut first_run = true;
+
+    async move {
+        while rx.next().await.is_some() {
+            priority.wait().await;
+            if priority == EffectPriority::Layout {
+                if let Some(inner) = inner.upgrade() {
+                    inner.write().or_poisoned().pending_layout.take();
+                }
+            }
+            if first_run
+                || subscriber.with_observer(|| subscriber.update_if_necessary())
+            {
+                first_run = false;
+                subscriber.clear_sources(&subscriber);
+
+                let old_value = value.take();
+                value = Some(owner.with_cleanup(|| {
+                    subscriber.with_observer(|| fun(old_value))
+                }));
+            }
+        }
+    }
+}
+
+thread_local! {
+    static DEFERRED: RefCell<Option<Vec<Weak<RwLock<EffectInner>>>>> = const { RefCell::new(None) };
+}
+
+/// Runs `fun`, holding back the first run of any [`Effect`] created inside it until it returns.
+///
+/// Effects always run after any current synchronous work is done, but an async executor that
+/// runs tasks on other threads may start them while a view is still being built. Building and
+/// mounting a view inside `defer_effects` ensures that effects only ever observe the mounted
+/// view. Effects created by a view that is built inside it, but mounted after it returns, may
+/// still run before the view has been mounted. [`RenderEffect`](super::RenderEffect)s are not
+/// affected, and still run immediately.
+///
+/// Calls may be nested, in which case the effects are released when the outermost call returns.
+pub fn defer_effects<T>(fun: impl FnOnce() -> T) -> T {
+    let outermost = DEFERRED.with_borrow_mut(|effects| {
+        if effects.is_some() {
+            false
+        } else {
+            *effects = Some(Vec::new());
+            true
+        }
+    });
+    let _release = outermost.then_some(ReleaseDeferredEffects);
+    fun()
+}
+
+/// Notifies each deferred effect when dropped, even if the view panicked while being built.
+struct ReleaseDeferredEffects;
+
+impl Drop for ReleaseDeferredEffects {
+    fn drop(&mut self) {
+        let effects = DEFERRED.take().unwrap_or_default();
+        for effect in effects {
+            if let Some(effect) = effect.upgrade() {
+                effect.write().or_poisoned().notify();
+            }
+        }
+    }
+}
+
 impl<S> Effect<S>
 where
     S: Storage<StoredEffect>,
@@ -127,39 +233,54 @@ impl Effect<LocalStorage> {
     /// This spawns a task on the local thread using
     /// [`spawn_local`](any_spawner::Executor::spawn_local). For an effect that can be spawned on
     /// any thread, use [`new_sync`](Effect::new_sync).
-    pub fn new<T>(mut fun: impl FnMut(Option<T>) -> T + 'static) -> Self
+    ///
+    /// This is the same as [`new_with_priority`](Effect::new_with_priority) with
+    /// [`EffectPriority::Normal`]: it runs after any layout effects that are waiting to run, but
+    /// otherwise does not wait.
+    #[track_caller]
+    pub fn new<T>(fun: impl FnMut(Option<T>) -> T + 'static) -> Self
     where
         T: 'static,
     {
-        let inner = cfg!(feature = "effects").then(|| {
-            let (mut rx, owner, inner) = effect_base();
-            let value = Arc::new(RwLock::new(None::<T>));
-            let mut first_run = true;
-
-            Executor::spawn_local({
-                let value = Arc::clone(&value);
-                let subscriber = inner.to_any_subscriber();
+        Self::new_with_priority(EffectPriority::Normal, fun)
+    }
 
-                async move {
-                    while rx.next().await.is_some() {
-                        if first_run
-                            || subscriber.with_observer(|| {
-                                subscriber.update_if_necessary()
-                            })
-                        {
-                            first_run = false;
-                            subscriber.clear_sources(&subscriber);
+    /// Creates a new effect that is scheduled according to the given [`EffectPriority`].
+    ///
+    /// Layout effects run as soon as they are notified, before normal effects that were
+    /// notified by the same change, and idle effects wait until the browser is idle. While an
+    /// [`AsyncTransition`](crate::transition::AsyncTransition) is running, normal effects are
+    /// demoted so that they run when the browser is idle.
+    ///
+    /// ```
+    /// # use reactive_graph::effect::{Effect, EffectPriority};
+    /// # use reactive_graph::traits::*;
+    /// # use reactive_graph::signal::signal;
+    /// # tokio_test::block_on(async move {
+    /// # tokio::task::LocalSet::new().run_until(async move {
+    /// let (page, set_page) = signal("/");
+    ///
+    /// // reporting a page view can wait until the browser has nothing better to do
+    /// Effect::new_with_priority(EffectPriority::Idle, move |_| {
+    ///     let page = page.get();
+    ///     // send_analytics(page);
+    /// });
+    /// # });
+    /// # });
+    /// ```
+    #[track_caller]
+    pub fn new_with_priority<T>(
+        priority: EffectPriority,
+        fun: impl FnMut(Option<T>) -> T + 'static,
+    ) -> Self
+    where
+        T: 'static,
+    {
+        let defined_at = Location::caller();
+        let inner = cfg!(feature = "effects").then(|| {
+            let (rx, owner, inner) = effect_base(defined_at, priority);
 
-                            let old_value =
-                                mem::take(&mut *value.write().or_poisoned());
-                            let new_value = owner.with_cleanup(|| {
-                                subscriber.with_observer(|| fun(old_value))
-                            });
-                            *value.write().or_poisoned() = Some(new_value);
-                        }
-                    }
-                }
-            });
+            Executor::spawn_local(run_effect(rx, owner, &inner, priority, fun));
 
             StoredValue::new_with_storage(Some(inner))
         });
@@ -257,6 +378,7 @@ impl Effect<LocalStorage> {
     /// # });
     /// # });
     /// ```
+    #[track_caller]
     pub fn watch<D, T>(
         mut dependency_fn: impl FnMut() -> D + 'static,
         mut handler: impl FnMut(&D, Option<&D>, Option<T>) -> T + 'static,
@@ -266,8 +388,10 @@ impl Effect<LocalStorage> {
         D: 'static,
         T: 'static,
     {
+        let defined_at = Location::caller();
         let inner = cfg!(feature = "effects").then(|| {
-            let (mut rx, owner, inner) = effect_base();
+            let (mut rx, owner, inner) =
+                effect_base(defined_at, EffectPriority::Normal);
             let mut first_run = true;
             let dep_value = Arc::new(RwLock::new(None::<D>));
             let watch_value = Arc::new(RwLock::new(None::<T>));
@@ -330,41 +454,33 @@ impl Effect<SyncStorage> {
     ///
     /// This spawns a task that can be run on any thread. For an effect that will be spawned on
     /// the current thread, use [`new`](Effect::new).
+    ///
+    /// This is the same as [`new_sync_with_priority`](Effect::new_sync_with_priority) with
+    /// [`EffectPriority::Normal`].
+    #[track_caller]
     pub fn new_sync<T>(
-        mut fun: impl FnMut(Option<T>) -> T + Send + Sync + 'static,
+        fun: impl FnMut(Option<T>) -> T + Send + Sync + 'static,
     ) -> Self
     where
         T: Send + Sync + 'static,
     {
-        let inner = cfg!(feature = "effects").then(|| {
-            let (mut rx, owner, inner) = effect_base();
-            let mut first_run = true;
-            let value = Arc::new(RwLock::new(None::<T>));
-
-            Executor::spawn({
-                let value = Arc::clone(&value);
-                let subscriber = inner.to_any_subscriber();
+        Self::new_sync_with_priority(EffectPriority::Normal, fun)
+    }
 
-                async move {
-                    while rx.next().await.is_some() {
-                        if first_run
-                            || subscriber.with_observer(|| {
-                                subscriber.update_if_necessary()
-                            })
-                        {
-                            first_run = false;
-                            subscriber.clear_sources(&subscriber);
+    /// This is to [`Effect::new_with_priority`] what [`Effect::new_sync`] is to [`Effect::new`].
+    #[track_caller]
+    pub fn new_sync_with_priority<T>(
+        priority: EffectPriority,
+        fun: impl FnMut(Option<T>) -> T + Send + Sync + 'static,
+    ) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
+        let defined_at = Location::caller();
+        let inner = cfg!(feature = "effects").then(|| {
+            let (rx, owner, inner) = effect_base(defined_at, priority);
 
-                            let old_value =
-                                mem::take(&mut *value.write().or_poisoned());
-                            let new_value = owner.with_cleanup(|| {
-                                subscriber.with_observer(|| fun(old_value))
-                            });
-                            *value.write().or_poisoned() = Some(new_value);
-                        }
-                    }
-                }
-            });
+            Executor::spawn(run_effect(rx, owner, &inner, priority, fun));
 
             StoredValue::new_with_storage(Some(inner))
         });
@@ -376,39 +492,24 @@ impl Effect<SyncStorage> {
     /// that are read inside it change.
     ///
     /// This will run whether the `effects` feature is enabled or not.
+    #[track_caller]
     pub fn new_isomorphic<T>(
-        mut fun: impl FnMut(Option<T>) -> T + Send + Sync + 'static,
+        fun: impl FnMut(Option<T>) -> T + Send + Sync + 'static,
     ) -> Self
     where
         T: Send + Sync + 'static,
     {
-        let (mut rx, owner, inner) = effect_base();
-        let mut first_run = true;
-        let value = Arc::new(RwLock::new(None::<T>));
-
-        Executor::spawn({
-            let value = Arc::clone(&value);
-            let subscriber = inner.to_any_subscriber();
-
-            async move {
-                while rx.next().await.is_some() {
-                    if first_run
-                        || subscriber
-                            .with_observer(|| subscriber.update_if_necessary())
-                    {
-                        first_run = false;
-                        subscriber.clear_sources(&subscriber);
-
-                        let old_value =
-                            mem::take(&mut *value.write().or_poisoned());
-                        let new_value = owner.with_cleanup(|| {
-                            subscriber.with_observer(|| fun(old_value))
-                        });
-                        *value.write().or_poisoned() = Some(new_value);
-                    }
-                }
-            }
-        });
+        let defined_at = Location::caller();
+        let (rx, owner, inner) =
+            effect_base(defined_at, EffectPriority::Normal);
+
+        Executor::spawn(run_effect(
+            rx,
+            owner,
+            &inner,
+            EffectPriority::Normal,
+            fun,
+        ));
 
         Self {
             inner: Some(StoredValue::new_with_storage(Some(inner))),
@@ -416,6 +517,7 @@ impl Effect<SyncStorage> {
     }
 
     /// This is to [`Effect::watch`] what [`Effect::new_sync`] is to [`Effect::new`].
+    #[track_caller]
     pub fn watch_sync<D, T>(
         mut dependency_fn: impl FnMut() -> D + Send + Sync + 'static,
         mut handler: impl FnMut(&D, Option<&D>, Option<T>) -> T
@@ -428,7 +530,9 @@ impl Effect<SyncStorage> {
         D: Send + Sync + 'static,
         T: Send + Sync + 'static,
     {
-        let (mut rx, owner, inner) = effect_base();
+        let defined_at = Location::caller();
+        let (mut rx, owner, inner) =
+            effect_base(defined_at, EffectPriority::Normal);
         let mut first_run = true;
         let dep_value = Arc::new(RwLock::new(None::<D>));
         let watch_value = Arc::new(RwLock::new(None::<T>));
@@ -517,6 +621,20 @@ where
     Effect::new(fun)
 }
 
+/// Creates an [`Effect`] that is scheduled according to the given [`EffectPriority`],
+/// equivalent to [`Effect::new_with_priority`].
+#[inline(always)]
+#[track_caller]
+pub fn create_effect_with_priority<T>(
+    priority: EffectPriority,
+    fun: impl FnMut(Option<T>) -> T + 'static,
+) -> Effect<LocalStorage>
+where
+    T: 'static,
+{
+    Effect::new_with_priority(priority, fun)
+}
+
 /// Creates an [`Effect`], equivalent to [Effect::watch].
 #[inline(always)]
 #[track_caller]
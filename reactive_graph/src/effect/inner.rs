@@ -1,12 +1,17 @@
 use crate::{
     channel::Sender,
+    effect::priority::PendingLayout,
     graph::{
         AnySource, AnySubscriber, ReactiveNode, SourceSet, Subscriber,
         ToAnySubscriber,
     },
+    traits::DefinedAt,
 };
 use or_poisoned::OrPoisoned;
-use std::sync::{Arc, RwLock, Weak};
+use std::{
+    panic::Location,
+    sync::{Arc, RwLock, Weak},
+};
 
 /// Handles internal subscription logic for effects.
 #[derive(Debug)]
@@ -14,6 +19,35 @@ pub struct EffectInner {
     pub(crate) dirty: bool,
     pub(crate) observer: Sender,
     pub(crate) sources: SourceSet,
+    /// Whether this is a layout effect, which normal effects wait for.
+    pub(crate) layout: bool,
+    pub(crate) pending_layout: Option<PendingLayout>,
+    #[cfg(debug_assertions)]
+    pub(crate) defined_at: &'static Location<'static>,
+}
+
+impl EffectInner {
+    /// Schedules the effect to run.
+    pub(crate) fn notify(&mut self) {
+        if self.layout && self.pending_layout.is_none() {
+            self.pending_layout = Some(PendingLayout::new());
+        }
+        self.observer.notify()
+    }
+}
+
+impl DefinedAt for EffectInner {
+    #[inline(always)]
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(debug_assertions)]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            None
+        }
+    }
 }
 
 impl ToAnySubscriber for Arc<RwLock<EffectInner>> {
@@ -48,13 +82,17 @@ impl ReactiveNode for RwLock<EffectInner> {
     }
 
     fn mark_check(&self) {
-        self.write().or_poisoned().observer.notify()
+        #[cfg(debug_assertions)]
+        crate::graph::cycle::effect_notified(self as *const Self as usize);
+        self.write().or_poisoned().notify()
     }
 
     fn mark_dirty(&self) {
+        #[cfg(debug_assertions)]
+        crate::graph::cycle::effect_notified(self as *const Self as usize);
         let mut lock = self.write().or_poisoned();
         lock.dirty = true;
-        lock.observer.notify()
+        lock.notify()
     }
 }
 
@@ -64,6 +102,11 @@ impl Subscriber for RwLock<EffectInner> {
     }
 
     fn clear_sources(&self, subscriber: &AnySubscriber) {
-        self.write().or_poisoned().sources.clear_sources(subscriber);
+        let mut lock = self.write().or_poisoned();
+        // sources are only cleared right before the effect runs again
+        crate::diagnostics::effect_ran(lock.defined_at(), &lock.sources);
+        #[cfg(debug_assertions)]
+        crate::graph::cycle::effect_started(subscriber, lock.defined_at());
+        lock.sources.clear_sources(subscriber);
     }
 }
@@ -0,0 +1,103 @@
+use crate::transition::AsyncTransition;
+use any_spawner::Executor;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// When an effect created with [`Effect::new_with_priority`](super::Effect::new_with_priority)
+/// runs, relative to other effects and to the browser's rendering.
+///
+/// Effects created with [`Effect::new`](super::Effect::new) use the default, `Normal`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum EffectPriority {
+    /// Runs as soon as possible once it has been notified, before the browser paints. This is
+    /// intended for effects that read from or write to the DOM, such as measuring an element or
+    /// focusing a node reference, and should be kept short.
+    Layout,
+    /// Runs on the next “tick” after it has been notified. If any layout effects are waiting to
+    /// run, it waits until they have. This is how most effects should run.
+    #[default]
+    Normal,
+    /// Runs when the browser is idle, using
+    /// [`requestIdleCallback`](https://developer.mozilla.org/en-US/docs/Web/API/Window/requestIdleCallback)
+    /// where it is supported. This is intended for work that the user does not need to see,
+    /// such as analytics or prefetching.
+    Idle,
+}
+
+impl EffectPriority {
+    /// Waits until an effect with this priority should run.
+    pub(crate) async fn wait(self) {
+        // work that is started during a transition can wait until it is done
+        let priority = if self == EffectPriority::Normal
+            && AsyncTransition::is_running()
+        {
+            EffectPriority::Idle
+        } else {
+            self
+        };
+
+        match priority {
+            EffectPriority::Layout => {}
+            EffectPriority::Normal => {
+                while PENDING_LAYOUT.load(Ordering::Acquire) > 0 {
+                    Executor::tick().await;
+                }
+            }
+            EffectPriority::Idle => {
+                Executor::tick().await;
+                idle().await;
+            }
+        }
+    }
+}
+
+// the number of layout effects that have been notified but have not run yet
+static PENDING_LAYOUT: AtomicUsize = AtomicUsize::new(0);
+
+/// Held by a layout effect from when it is notified until it runs, so that normal effects can
+/// wait for it.
+#[derive(Debug)]
+pub(crate) struct PendingLayout(());
+
+impl PendingLayout {
+    pub(crate) fn new() -> Self {
+        PENDING_LAYOUT.fetch_add(1, Ordering::AcqRel);
+        Self(())
+    }
+}
+
+impl Drop for PendingLayout {
+    fn drop(&mut self) {
+        PENDING_LAYOUT.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+async fn idle() {
+    use futures::channel::oneshot;
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    // the window and callback are dropped before waiting, so that sync effects can wait too
+    let rx = {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        let (tx, rx) = oneshot::channel();
+        let callback = Closure::once_into_js(move || {
+            _ = tx.send(());
+        });
+        let callback = callback.unchecked_ref::<js_sys::Function>();
+        // Safari does not support requestIdleCallback, so this falls back to a timeout
+        if window.request_idle_callback(callback).is_err() {
+            _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+                callback, 1,
+            );
+        }
+        rx
+    };
+    _ = rx.await;
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+async fn idle() {
+    Executor::tick().await;
+}
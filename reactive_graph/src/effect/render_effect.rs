@@ -6,6 +6,7 @@ use crate::{
         WithObserver,
     },
     owner::Owner,
+    traits::DefinedAt,
 };
 use any_spawner::Executor;
 use futures::StreamExt;
@@ -13,6 +14,7 @@ use or_poisoned::OrPoisoned;
 use std::{
     fmt::Debug,
     mem,
+    panic::Location,
     sync::{Arc, RwLock, Weak},
 };
 
@@ -53,18 +55,22 @@ where
     T: 'static,
 {
     /// Creates a new render effect, which immediately runs `fun`.
+    #[track_caller]
     pub fn new(fun: impl FnMut(Option<T>) -> T + 'static) -> Self {
         Self::new_with_value(fun, None)
     }
 
     /// Creates a new render effect with an initial value.
+    #[track_caller]
     pub fn new_with_value(
         fun: impl FnMut(Option<T>) -> T + 'static,
         initial_value: Option<T>,
     ) -> Self {
+        #[cfg_attr(not(debug_assertions), allow(unused_variables))]
         fn erased<T>(
             mut fun: Box<dyn FnMut(Option<T>) -> T + 'static>,
             initial_value: Option<T>,
+            defined_at: &'static Location<'static>,
         ) -> RenderEffect<T> {
             let (observer, mut rx) = channel();
             let value = Arc::new(RwLock::new(None::<T>));
@@ -73,9 +79,22 @@ where
                 dirty: false,
                 observer,
                 sources: SourceSet::new(),
+                layout: false,
+                pending_layout: None,
+                #[cfg(debug_assertions)]
+                defined_at,
             }));
+            #[cfg(feature = "introspection")]
+            crate::introspection::register_subscriber(
+                crate::introspection::NodeKind::Effect,
+                inner.to_any_subscriber(),
+            );
 
             let initial_value = cfg!(feature = "effects").then(|| {
+                crate::diagnostics::effect_ran(
+                    inner.read().or_poisoned().defined_at(),
+                    &SourceSet::new(),
+                );
                 owner.with(|| {
                     inner
                         .to_any_subscriber()
@@ -112,7 +131,7 @@ where
             RenderEffect { value, inner }
         }
 
-        erased(Box::new(fun), initial_value)
+        erased(Box::new(fun), initial_value, Location::caller())
     }
 
     /// Mutably accesses the current value.
@@ -134,6 +153,7 @@ where
     T: Send + Sync + 'static,
 {
     /// Creates a render effect that will run whether the `effects` feature is enabled or not.
+    #[track_caller]
     pub fn new_isomorphic(
         mut fun: impl FnMut(Option<T>) -> T + Send + 'static,
     ) -> Self {
@@ -146,7 +166,16 @@ where
             dirty: false,
             observer,
             sources: SourceSet::new(),
+            layout: false,
+            pending_layout: None,
+            #[cfg(debug_assertions)]
+            defined_at: Location::caller(),
         }));
+        #[cfg(feature = "introspection")]
+        crate::introspection::register_subscriber(
+            crate::introspection::NodeKind::Effect,
+            inner.to_any_subscriber(),
+        );
         let mut first_run = true;
 
         Executor::spawn({
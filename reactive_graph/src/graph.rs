@@ -1,6 +1,8 @@
 //! Types that define the reactive graph itself. These are mostly internal, but can be used to
 //! create custom reactive primitives.
 
+#[cfg(debug_assertions)]
+pub(crate) mod cycle;
 mod node;
 mod sets;
 mod source;
@@ -0,0 +1,211 @@
+//! Detects cycles in the reactive graph in debug mode, so that they cause a panic that explains
+//! which nodes are involved, rather than an infinite loop or a stack overflow.
+//!
+//! There are two kinds of cycle:
+//! 1. Effects that write to signals that cause themselves to run again, either directly or
+//!    through other effects. Each time an effect is notified while another effect is running,
+//!    its next run is added to a chain of runs that caused one another. If a chain grows longer
+//!    than [`MAX_EFFECT_CHAIN`] without anything outside the reactive system happening, it is
+//!    assumed to be an infinite loop.
+//! 2. Memos that read from themselves, directly or through other memos. These would otherwise
+//!    recurse until the stack overflows.
+
+use super::{AnySubscriber, Observer, Subscriber};
+use or_poisoned::OrPoisoned;
+use rustc_hash::FxHashMap;
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Write,
+    panic::Location,
+    sync::{Arc, Mutex, OnceLock, Weak},
+};
+
+/// The number of effect runs that can cause one another before this is treated as a cycle.
+pub(crate) const MAX_EFFECT_CHAIN: usize = 1000;
+
+type DefinedAt = Option<&'static Location<'static>>;
+
+/// A run of an effect that was caused by a write during the run of another effect.
+struct Link {
+    effect: usize,
+    defined_at: DefinedAt,
+    /// The signal written to by the previous effect in the chain, which caused this run.
+    wrote: DefinedAt,
+    depth: usize,
+    /// The run of the effect that wrote to the signal, or `None` if that run was not itself
+    /// caused by another effect.
+    parent: Option<Arc<Link>>,
+}
+
+struct Pending {
+    wrote: DefinedAt,
+    parent: Option<Arc<Link>>,
+}
+
+#[derive(Default)]
+struct Chains {
+    /// The chain that will cause each effect's next run.
+    pending: FxHashMap<usize, Pending>,
+    /// The chain that caused each effect's most recent run, for effects that were run by
+    /// another effect. Effects that run for any other reason are not stored.
+    running: FxHashMap<usize, (Weak<dyn Subscriber + Send + Sync>, Arc<Link>)>,
+    inserted_since_prune: usize,
+}
+
+fn chains() -> &'static Mutex<Chains> {
+    static CHAINS: OnceLock<Mutex<Chains>> = OnceLock::new();
+    CHAINS.get_or_init(Default::default)
+}
+
+thread_local! {
+    static LAST_WRITE: Cell<DefinedAt> = const { Cell::new(None) };
+    static MEMO_STACK: RefCell<Vec<(usize, DefinedAt)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Called when a signal notifies its subscribers, so that the effects it notifies know which
+/// signal caused them to run.
+pub(crate) fn signal_written(defined_at: DefinedAt) {
+    LAST_WRITE.set(defined_at);
+}
+
+/// Called when an effect is marked to run again.
+pub(crate) fn effect_notified(effect: usize) {
+    let mut chains = chains().lock().or_poisoned();
+    match Observer::get() {
+        // the effect was notified by a write during another node's run
+        Some(writer) => {
+            let pending = Pending {
+                wrote: LAST_WRITE.get(),
+                parent: chains
+                    .running
+                    .get(&writer.0)
+                    .map(|(_, link)| Arc::clone(link)),
+            };
+            chains.pending.insert(effect, pending);
+        }
+        None => {
+            chains.pending.remove(&effect);
+        }
+    }
+}
+
+/// Called just before an effect runs.
+///
+/// # Panics
+/// Panics if this run is part of a chain of runs longer than [`MAX_EFFECT_CHAIN`].
+pub(crate) fn effect_started(
+    subscriber: &AnySubscriber,
+    defined_at: DefinedAt,
+) {
+    let mut chains = chains().lock().or_poisoned();
+    let Some(pending) = chains.pending.remove(&subscriber.0) else {
+        chains.running.remove(&subscriber.0);
+        return;
+    };
+    let link = Arc::new(Link {
+        effect: subscriber.0,
+        defined_at,
+        wrote: pending.wrote,
+        depth: pending.parent.as_ref().map(|link| link.depth).unwrap_or(0) + 1,
+        parent: pending.parent,
+    });
+    if link.depth > MAX_EFFECT_CHAIN {
+        chains.pending.clear();
+        chains.running.clear();
+        drop(chains);
+        panic!("{}", describe_effect_cycle(&link));
+    }
+
+    // dropped effects are not removed, so they are cleared out every so often
+    chains.inserted_since_prune += 1;
+    if chains.inserted_since_prune > 1024 {
+        chains
+            .running
+            .retain(|_, (subscriber, _)| subscriber.strong_count() > 0);
+        chains.inserted_since_prune = 0;
+    }
+    chains
+        .running
+        .insert(subscriber.0, (subscriber.1.clone(), link));
+}
+
+fn describe_effect_cycle(last: &Link) -> String {
+    // walk back until the effect that ran last appears again, which closes the cycle
+    let mut cycle = Vec::new();
+    let mut current = Some(last);
+    while let Some(link) = current {
+        let parent = link.parent.as_deref();
+        cycle.push((
+            parent.and_then(|p| p.defined_at),
+            link.wrote,
+            link.defined_at,
+        ));
+        current = parent.filter(|parent| parent.effect != last.effect);
+    }
+    cycle.reverse();
+
+    let mut message = format!(
+        "A cycle was detected in the reactive graph: effects have caused \
+         each other to run {MAX_EFFECT_CHAIN} times in a row. This usually \
+         means that an effect writes to a signal that it reads from, either \
+         directly or through other effects.\n\nThe cycle is:\n"
+    );
+    for (writer, wrote, effect) in cycle {
+        _ = writeln!(
+            message,
+            "  the effect defined at {} wrote to the signal defined at {}, \
+             which ran the effect defined at {}",
+            display(writer),
+            display(wrote),
+            display(effect)
+        );
+    }
+    message
+}
+
+/// Marks a memo as running until the returned guard is dropped.
+///
+/// # Panics
+/// Panics if the memo is already running on this thread, which means that it has read from
+/// itself.
+pub(crate) fn enter_memo(memo: usize, defined_at: DefinedAt) -> MemoGuard {
+    MEMO_STACK.with_borrow_mut(|stack| {
+        if let Some(start) = stack.iter().position(|(id, _)| *id == memo) {
+            let mut message = String::from(
+                "A cycle was detected in the reactive graph: a memo reads \
+                 from itself.\n\nThe cycle is:\n",
+            );
+            for (_, defined_at) in &stack[start..] {
+                _ = writeln!(
+                    message,
+                    "  the memo defined at {}, which reads",
+                    display(*defined_at)
+                );
+            }
+            _ = writeln!(
+                message,
+                "  the memo defined at {}",
+                display(defined_at)
+            );
+            stack.clear();
+            panic!("{message}");
+        }
+        stack.push((memo, defined_at));
+    });
+    MemoGuard
+}
+
+/// Removes a memo from the stack of running memos when dropped.
+pub(crate) struct MemoGuard;
+
+impl Drop for MemoGuard {
+    fn drop(&mut self) {
+        MEMO_STACK.with_borrow_mut(Vec::pop);
+    }
+}
+
+fn display(defined_at: DefinedAt) -> String {
+    defined_at
+        .map(ToString::to_string)
+        .unwrap_or_else(|| String::from("(unknown)"))
+}
@@ -36,6 +36,8 @@ impl SourceSet {
     }
 
     pub fn clear_sources(&mut self, subscriber: &AnySubscriber) {
+        #[cfg(feature = "introspection")]
+        crate::introspection::start_run(subscriber);
         for source in self.take() {
             source.remove_subscriber(subscriber);
         }
@@ -60,11 +62,22 @@ impl<'a> IntoIterator for &'a SourceSet {
     }
 }
 #[derive(Default, Clone)]
-pub struct SubscriberSet(Vec<AnySubscriber>);
+pub struct SubscriberSet(Vec<AnySubscriber>, u64);
 
 impl SubscriberSet {
     pub fn new() -> Self {
-        Self(Vec::with_capacity(2))
+        Self(Vec::with_capacity(2), 0)
+    }
+
+    /// Takes the subscribers so that they can be notified of a change, and increases the version.
+    pub fn notify(&mut self) -> Vec<AnySubscriber> {
+        self.1 = self.1.wrapping_add(1);
+        self.take()
+    }
+
+    /// The number of times [`notify`](Self::notify) has been called.
+    pub fn version(&self) -> u64 {
+        self.1
     }
 
     pub fn subscribe(&mut self, subscriber: AnySubscriber) {
@@ -0,0 +1,553 @@
+//! Enumerates the nodes of the reactive graph, for debugging tools like a devtools panel.
+//!
+//! When the `introspection` feature is enabled, every signal, trigger, memo, async derived and
+//! effect is registered when it is created. [`nodes`] returns the ones that are still alive,
+//! along with the sources each memo or effect read from the last time it ran, and when each node
+//! was last updated.
+//!
+//! ```rust
+//! # use reactive_graph::{introspection::{nodes, NodeKind}, prelude::*, signal::RwSignal};
+//! let count = RwSignal::new(0);
+//! let signals = nodes()
+//!     .into_iter()
+//!     .filter(|node| node.kind == NodeKind::Signal)
+//!     .count();
+//! assert!(signals >= 1);
+//! ```
+//!
+//! Tools that follow the graph as it changes can call [`add_listener`] to be told about each
+//! [`GraphEvent`] as it happens, rather than polling [`nodes`].
+//!
+//! # Finding leaks
+//! Owners are tracked as well, along with the owner each node was created under. [`leaks`]
+//! reports nodes that are still alive after their owner was cleaned up, like a signal that has
+//! been moved into an event listener that outlives its component, and owners that are still
+//! alive after their parent was cleaned up. In debug builds, each leak includes the
+//! [component stack](crate::owner::component_stack) it was created in.
+//!
+//! ```rust
+//! # use reactive_graph::{introspection::leaks, owner::Owner, signal::ArcRwSignal};
+//! let owner = Owner::new();
+//! let kept = owner.with(|| ArcRwSignal::new(0));
+//! owner.cleanup();
+//! assert!(!leaks().nodes.is_empty());
+//! drop(kept);
+//! ```
+
+use crate::{
+    graph::{AnySource, AnySubscriber},
+    owner::{Owner, OwnerInner},
+};
+use or_poisoned::OrPoisoned;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::{
+    fmt::{self, Display},
+    panic::Location,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, OnceLock, RwLock, Weak,
+    },
+};
+use web_time::SystemTime;
+
+/// Identifies a node in the reactive graph.
+///
+/// This is the address of the node, so it may be reused by a new node once this one has been
+/// dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(pub usize);
+
+/// The kind of a node in the reactive graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    /// A signal, which holds a value.
+    Signal,
+    /// A trigger, which notifies subscribers without holding a value.
+    Trigger,
+    /// A memo, which is derived from other nodes.
+    Memo,
+    /// An async derived signal or resource.
+    AsyncDerived,
+    /// An effect or render effect.
+    Effect,
+}
+
+/// A live node in the reactive graph, as returned by [`nodes`].
+#[derive(Debug, Clone)]
+pub struct NodeInfo {
+    /// The node's ID, which is used to refer to it in [`sources`](NodeInfo::sources).
+    pub id: NodeId,
+    /// What kind of node this is.
+    pub kind: NodeKind,
+    /// Where the node was created, in debug mode.
+    pub defined_at: Option<&'static Location<'static>>,
+    /// For a signal or trigger, when it was last notified of a change. For a memo, async derived
+    /// or effect, when it last ran.
+    pub last_updated: Option<SystemTime>,
+    /// The nodes this node read from the last time it ran.
+    pub sources: Vec<NodeId>,
+}
+
+/// A change to the reactive graph, passed to each function registered with [`add_listener`].
+#[derive(Debug, Clone)]
+pub enum GraphEvent {
+    /// A node was created.
+    NodeCreated {
+        /// The new node.
+        id: NodeId,
+        /// What kind of node it is.
+        kind: NodeKind,
+        /// Where the node was created, in debug mode.
+        defined_at: Option<&'static Location<'static>>,
+    },
+    /// A node notified its subscribers that it has changed.
+    NodeUpdated {
+        /// The node that changed.
+        id: NodeId,
+    },
+    /// A memo, async derived or effect started running.
+    NodeRan {
+        /// The node that ran.
+        id: NodeId,
+        /// What kind of node it is.
+        kind: NodeKind,
+    },
+    /// An owner was cleaned up, disposing of the nodes and values it owns.
+    OwnerDisposed {
+        /// The [`Owner::debug_id`](crate::owner::Owner::debug_id) of the owner.
+        owner: usize,
+    },
+}
+
+/// Identifies a listener registered with [`add_listener`], so that it can be removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ListenerId(usize);
+
+type Listener = Arc<dyn Fn(&GraphEvent) + Send + Sync>;
+
+fn listeners() -> &'static RwLock<Vec<(ListenerId, Listener)>> {
+    static LISTENERS: OnceLock<RwLock<Vec<(ListenerId, Listener)>>> =
+        OnceLock::new();
+    LISTENERS.get_or_init(Default::default)
+}
+
+// checked before building an event, so that there is no cost when nothing is listening
+static HAS_LISTENERS: AtomicBool = AtomicBool::new(false);
+
+/// Calls `listener` with every [`GraphEvent`] from now on, until it is removed with
+/// [`remove_listener`].
+///
+/// The listener is called synchronously, on the thread where the event happened. It may read
+/// from the graph, but should avoid creating or updating nodes, as these will emit further
+/// events.
+pub fn add_listener(
+    listener: impl Fn(&GraphEvent) + Send + Sync + 'static,
+) -> ListenerId {
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+    let id = ListenerId(NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    listeners()
+        .write()
+        .or_poisoned()
+        .push((id, Arc::new(listener)));
+    HAS_LISTENERS.store(true, Ordering::Release);
+    id
+}
+
+/// Stops calling a listener registered with [`add_listener`].
+pub fn remove_listener(id: ListenerId) {
+    let mut listeners = listeners().write().or_poisoned();
+    listeners.retain(|(listener_id, _)| *listener_id != id);
+    HAS_LISTENERS.store(!listeners.is_empty(), Ordering::Release);
+}
+
+fn emit(event: impl FnOnce() -> Option<GraphEvent>) {
+    if !HAS_LISTENERS.load(Ordering::Acquire) {
+        return;
+    }
+    let Some(event) = event() else {
+        return;
+    };
+    // listeners are called outside the lock, so they can add or remove listeners
+    let listeners = listeners()
+        .read()
+        .or_poisoned()
+        .iter()
+        .map(|(_, listener)| Arc::clone(listener))
+        .collect::<Vec<_>>();
+    for listener in listeners {
+        listener(&event);
+    }
+}
+
+enum Handle {
+    Source(AnySource),
+    Subscriber(AnySubscriber),
+}
+
+impl Handle {
+    fn is_alive(&self) -> bool {
+        match self {
+            Handle::Source(source) => source.1.strong_count() > 0,
+            Handle::Subscriber(subscriber) => subscriber.1.strong_count() > 0,
+        }
+    }
+}
+
+struct Entry {
+    kind: NodeKind,
+    handle: Handle,
+    defined_at: Option<&'static Location<'static>>,
+    last_updated: Option<SystemTime>,
+    sources: FxHashSet<NodeId>,
+    /// The owner the node was created under, and its generation at the time.
+    owner: Option<(usize, usize)>,
+    component_stack: Arc<[&'static str]>,
+}
+
+struct OwnerEntry {
+    handle: Weak<RwLock<OwnerInner>>,
+    /// Changes whenever the owner is cleaned up, as an owner can be reused after that.
+    generation: usize,
+    /// The parent of the owner, and its generation when this owner was created.
+    parent: Option<(usize, usize)>,
+    component_stack: Arc<[&'static str]>,
+}
+
+#[derive(Default)]
+struct Registry {
+    nodes: FxHashMap<NodeId, Entry>,
+    registered_since_prune: usize,
+    owners: FxHashMap<usize, OwnerEntry>,
+    owners_since_prune: usize,
+}
+
+impl Registry {
+    /// Whether the owner has been cleaned up or dropped since it had the given generation.
+    fn is_disposed(&self, (owner, generation): (usize, usize)) -> bool {
+        !self
+            .owners
+            .get(&owner)
+            .is_some_and(|entry| entry.generation == generation)
+    }
+
+    fn current_owner(&self) -> Option<(usize, usize)> {
+        let id = Owner::current()?.debug_id();
+        self.owners.get(&id).map(|entry| (id, entry.generation))
+    }
+}
+
+// generations are unique across owners, so that an owner that reuses the address of a dropped
+// one does not look like it
+static NEXT_GENERATION: AtomicUsize = AtomicUsize::new(0);
+static NODES_CREATED: AtomicUsize = AtomicUsize::new(0);
+static OWNERS_CREATED: AtomicUsize = AtomicUsize::new(0);
+
+fn next_generation() -> usize {
+    NEXT_GENERATION.fetch_add(1, Ordering::Relaxed)
+}
+
+fn registry() -> &'static RwLock<Registry> {
+    static REGISTRY: OnceLock<RwLock<Registry>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Returns every node in the reactive graph that is still alive.
+pub fn nodes() -> Vec<NodeInfo> {
+    let mut registry = registry().write().or_poisoned();
+    registry.nodes.retain(|_, entry| entry.handle.is_alive());
+    registry.registered_since_prune = 0;
+    let mut nodes = registry
+        .nodes
+        .iter()
+        .map(|(id, entry)| {
+            let mut sources = entry
+                .sources
+                .iter()
+                .copied()
+                .filter(|source| registry.nodes.contains_key(source))
+                .collect::<Vec<_>>();
+            sources.sort();
+            NodeInfo {
+                id: *id,
+                kind: entry.kind,
+                defined_at: entry.defined_at,
+                last_updated: entry.last_updated,
+                sources,
+            }
+        })
+        .collect::<Vec<_>>();
+    nodes.sort_by_key(|node| node.id);
+    nodes
+}
+
+fn register(id: NodeId, kind: NodeKind, handle: Handle) {
+    let defined_at = match &handle {
+        #[cfg(debug_assertions)]
+        Handle::Source(source) => Some(source.2),
+        _ => None,
+    };
+    NODES_CREATED.fetch_add(1, Ordering::Relaxed);
+    let component_stack = crate::owner::component_stack();
+    {
+        let mut registry = registry().write().or_poisoned();
+        // dropped nodes are not unregistered, so they are cleared out every so often
+        registry.registered_since_prune += 1;
+        if registry.registered_since_prune > 1024 {
+            registry.nodes.retain(|_, entry| entry.handle.is_alive());
+            registry.registered_since_prune = 0;
+        }
+        let owner = registry.current_owner();
+        registry.nodes.insert(
+            id,
+            Entry {
+                kind,
+                handle,
+                defined_at,
+                last_updated: None,
+                sources: FxHashSet::default(),
+                owner,
+                component_stack,
+            },
+        );
+    }
+    emit(|| {
+        Some(GraphEvent::NodeCreated {
+            id,
+            kind,
+            defined_at,
+        })
+    });
+}
+
+pub(crate) fn register_source(kind: NodeKind, source: AnySource) {
+    register(NodeId(source.0), kind, Handle::Source(source));
+}
+
+pub(crate) fn register_subscriber(kind: NodeKind, subscriber: AnySubscriber) {
+    register(NodeId(subscriber.0), kind, Handle::Subscriber(subscriber));
+}
+
+pub(crate) fn add_dependency(source: &AnySource, subscriber: &AnySubscriber) {
+    if let Some(entry) = registry()
+        .write()
+        .or_poisoned()
+        .nodes
+        .get_mut(&NodeId(subscriber.0))
+    {
+        entry.sources.insert(NodeId(source.0));
+    }
+}
+
+/// Called when a memo or effect is about to run again, and its sources are cleared.
+pub(crate) fn start_run(subscriber: &AnySubscriber) {
+    let id = NodeId(subscriber.0);
+    let kind =
+        registry()
+            .write()
+            .or_poisoned()
+            .nodes
+            .get_mut(&id)
+            .map(|entry| {
+                entry.sources.clear();
+                entry.last_updated = Some(SystemTime::now());
+                entry.kind
+            });
+    emit(|| kind.map(|kind| GraphEvent::NodeRan { id, kind }));
+}
+
+pub(crate) fn mark_updated(id: usize) {
+    let id = NodeId(id);
+    let registered = registry()
+        .write()
+        .or_poisoned()
+        .nodes
+        .get_mut(&id)
+        .map(|entry| entry.last_updated = Some(SystemTime::now()))
+        .is_some();
+    emit(|| registered.then_some(GraphEvent::NodeUpdated { id }));
+}
+
+pub(crate) fn register_owner(owner: &Arc<RwLock<OwnerInner>>) {
+    OWNERS_CREATED.fetch_add(1, Ordering::Relaxed);
+    let id = Arc::as_ptr(owner) as usize;
+    let (parent, component_stack) = {
+        let owner = owner.read().or_poisoned();
+        (
+            owner
+                .parent
+                .as_ref()
+                .and_then(Weak::upgrade)
+                .map(|parent| Arc::as_ptr(&parent) as usize),
+            owner.component_stack(),
+        )
+    };
+    let mut registry = registry().write().or_poisoned();
+    // as with nodes, dropped owners are cleared out every so often
+    registry.owners_since_prune += 1;
+    if registry.owners_since_prune > 1024 {
+        registry
+            .owners
+            .retain(|_, entry| entry.handle.strong_count() > 0);
+        registry.owners_since_prune = 0;
+    }
+    let parent = parent.and_then(|parent| {
+        registry
+            .owners
+            .get(&parent)
+            .map(|entry| (parent, entry.generation))
+    });
+    registry.owners.insert(
+        id,
+        OwnerEntry {
+            handle: Arc::downgrade(owner),
+            generation: next_generation(),
+            parent,
+            component_stack,
+        },
+    );
+}
+
+pub(crate) fn owner_disposed(owner: usize) {
+    if let Some(entry) = registry().write().or_poisoned().owners.get_mut(&owner)
+    {
+        entry.generation = next_generation();
+    }
+    emit(|| Some(GraphEvent::OwnerDisposed { owner }));
+}
+
+/// Possible memory leaks in the reactive graph, as returned by [`leaks`].
+#[derive(Debug, Clone, Default)]
+pub struct LeakReport {
+    /// How many nodes have been created in total.
+    pub nodes_created: usize,
+    /// How many nodes are still alive.
+    pub live_nodes: usize,
+    /// How many owners have been created in total.
+    pub owners_created: usize,
+    /// How many owners are still alive.
+    pub live_owners: usize,
+    /// Nodes that are still alive, although the owner they were created under has been cleaned
+    /// up.
+    pub nodes: Vec<LeakedNode>,
+    /// Owners that are still alive, although their parent has been cleaned up.
+    pub owners: Vec<LeakedOwner>,
+}
+
+/// A node that outlived its owner, as listed in a [`LeakReport`].
+#[derive(Debug, Clone)]
+pub struct LeakedNode {
+    /// The node's ID.
+    pub id: NodeId,
+    /// What kind of node this is.
+    pub kind: NodeKind,
+    /// Where the node was created, in debug mode.
+    pub defined_at: Option<&'static Location<'static>>,
+    /// The components the node was created in, outermost first, in debug mode.
+    pub component_stack: Arc<[&'static str]>,
+}
+
+/// An owner that outlived its parent, as listed in a [`LeakReport`].
+#[derive(Debug, Clone)]
+pub struct LeakedOwner {
+    /// The [`Owner::debug_id`](crate::owner::Owner::debug_id) of the owner.
+    pub id: usize,
+    /// The components the owner was created in, outermost first, in debug mode.
+    pub component_stack: Arc<[&'static str]>,
+}
+
+impl LeakReport {
+    /// Whether no leaks were found.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty() && self.owners.is_empty()
+    }
+}
+
+impl Display for LeakReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} reactive nodes and {} of {} owners are alive",
+            self.live_nodes,
+            self.nodes_created,
+            self.live_owners,
+            self.owners_created
+        )?;
+        for node in &self.nodes {
+            write!(f, "\n- {:?} outlived its owner", node.kind)?;
+            if let Some(defined_at) = node.defined_at {
+                write!(f, ", created at {defined_at}")?;
+            }
+            write_components(f, &node.component_stack)?;
+        }
+        for owner in &self.owners {
+            write!(f, "\n- owner {:#x} outlived its parent", owner.id)?;
+            write_components(f, &owner.component_stack)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_components(
+    f: &mut fmt::Formatter<'_>,
+    components: &[&'static str],
+) -> fmt::Result {
+    for (idx, name) in components.iter().enumerate() {
+        let sep = if idx == 0 { " in " } else { " → " };
+        write!(f, "{sep}<{name}>")?;
+    }
+    Ok(())
+}
+
+/// Finds nodes that are still alive after the owner they were created under has been cleaned
+/// up, and owners that are still alive after their parent has been cleaned up.
+///
+/// Anything that is still holding onto them, like an event listener on `window` or a clone
+/// stored in a global, is keeping them in memory. As nodes may be dropped a little after their
+/// owner is cleaned up, this is best called some time after a part of the UI is removed.
+pub fn leaks() -> LeakReport {
+    let mut registry = registry().write().or_poisoned();
+    registry.nodes.retain(|_, entry| entry.handle.is_alive());
+    registry.registered_since_prune = 0;
+    registry
+        .owners
+        .retain(|_, entry| entry.handle.strong_count() > 0);
+    registry.owners_since_prune = 0;
+
+    let mut nodes = registry
+        .nodes
+        .iter()
+        .filter(|(_, entry)| {
+            entry.owner.is_some_and(|owner| registry.is_disposed(owner))
+        })
+        .map(|(id, entry)| LeakedNode {
+            id: *id,
+            kind: entry.kind,
+            defined_at: entry.defined_at,
+            component_stack: Arc::clone(&entry.component_stack),
+        })
+        .collect::<Vec<_>>();
+    nodes.sort_by_key(|node| node.id);
+    let mut owners = registry
+        .owners
+        .iter()
+        .filter(|(_, entry)| {
+            entry
+                .parent
+                .is_some_and(|parent| registry.is_disposed(parent))
+        })
+        .map(|(id, entry)| LeakedOwner {
+            id: *id,
+            component_stack: Arc::clone(&entry.component_stack),
+        })
+        .collect::<Vec<_>>();
+    owners.sort_by_key(|owner| owner.id);
+
+    LeakReport {
+        nodes_created: NODES_CREATED.load(Ordering::Relaxed),
+        live_nodes: registry.nodes.len(),
+        owners_created: OWNERS_CREATED.load(Ordering::Relaxed),
+        live_owners: registry.owners.len(),
+        nodes,
+        owners,
+    }
+}
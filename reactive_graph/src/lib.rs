@@ -79,6 +79,8 @@ pub mod computed;
 pub mod diagnostics;
 pub mod effect;
 pub mod graph;
+#[cfg(feature = "introspection")]
+pub mod introspection;
 pub mod owner;
 #[cfg(feature = "serde")]
 mod serde;
@@ -19,6 +19,8 @@ use self::arena::Arena;
 #[cfg(feature = "sandboxed-arenas")]
 pub use arena::sandboxed::Sandboxed;
 use arena::NodeId;
+#[cfg(feature = "sandboxed-arenas")]
+use arena::{SharedArena, WeakArena};
 pub use context::*;
 #[allow(deprecated)] // allow exporting deprecated fn
 pub use stored_value::{
@@ -37,9 +39,10 @@ pub use stored_value::{
 /// is marked as the current `Owner`. Whenever it re-runs, this `Owner` is cleared by calling
 /// [`Owner::with_cleanup`]. This runs cleanup functions, cancels any [`Effect`](crate::effect::Effect)s created during the
 /// last run, drops signals stored in the arena, and so on, because those effects and signals will
-/// be re-created as needed during the next run.
+/// be re-created as needed during the next run. Context provided to the `Owner` itself is kept.
 ///
-/// When the owner is ultimately dropped, it will clean up its owned resources in the same way.
+/// When the owner is ultimately dropped, it will clean up its owned resources in the same way,
+/// and then drop its context.
 ///
 /// The "current owner" is set on the thread-local basis: whenever one of these reactive nodes is
 /// running, it will set the current owner on its thread with [`Owner::with`] or [`Owner::set`],
@@ -59,6 +62,11 @@ thread_local! {
     static OWNER: RefCell<Option<Owner>> = Default::default();
 }
 
+#[cfg(debug_assertions)]
+thread_local! {
+    static COMPONENT_STACK: RefCell<Option<Arc<[&'static str]>>> = const { RefCell::new(None) };
+}
+
 impl Owner {
     /// Returns a unique identifier for this owner, which can be used to identify it for debugging
     /// purposes.
@@ -113,6 +121,12 @@ impl Owner {
                 contexts: Default::default(),
                 cleanups: Default::default(),
                 children: Default::default(),
+                #[cfg(feature = "sandboxed-arenas")]
+                arena: Arena::current().as_ref().map(Arc::downgrade),
+                #[cfg(feature = "sandboxed-arenas")]
+                root_arena: None,
+                #[cfg(debug_assertions)]
+                component_stack: entered_component_stack(),
             })),
             #[cfg(feature = "hydration")]
             shared_context,
@@ -124,6 +138,8 @@ impl Owner {
                 .children
                 .push(Arc::downgrade(&this.inner));
         }
+        #[cfg(feature = "introspection")]
+        crate::introspection::register_owner(&this.inner);
         this
     }
 
@@ -132,11 +148,18 @@ impl Owner {
     ///
     /// Only one `SharedContext` needs to be created per request, and will be automatically shared
     /// by any other `Owner`s created under this one.
+    ///
+    /// With the `sandboxed-arenas` feature, this also creates a new arena for the values owned
+    /// by this root and its descendants. That arena is made current whenever one of these owners
+    /// is used with [`Owner::with`], so several roots, such as one per request, can be used on
+    /// the same thread without affecting each other's values.
     #[cfg(feature = "hydration")]
     pub fn new_root(
         shared_context: Option<Arc<dyn SharedContext + Send + Sync>>,
     ) -> Self {
         Arena::enter_new();
+        #[cfg(feature = "sandboxed-arenas")]
+        let root_arena = Arena::current();
 
         let this = Self {
             inner: Arc::new(RwLock::new(OwnerInner {
@@ -145,11 +168,21 @@ impl Owner {
                 contexts: Default::default(),
                 cleanups: Default::default(),
                 children: Default::default(),
+                #[cfg(feature = "sandboxed-arenas")]
+                arena: root_arena.as_ref().map(Arc::downgrade),
+                #[cfg(feature = "sandboxed-arenas")]
+                root_arena,
+                #[cfg(debug_assertions)]
+                component_stack: entered_component_stack(),
             })),
             #[cfg(feature = "hydration")]
             shared_context,
         };
-        OWNER.with_borrow_mut(|owner| *owner = Some(this.clone()));
+        #[cfg(feature = "introspection")]
+        crate::introspection::register_owner(&this.inner);
+        // the previous owner is dropped after the borrow ends, as its cleanups may use it
+        let prev = OWNER.with_borrow_mut(|owner| owner.replace(this.clone()));
+        drop(prev);
         this
     }
 
@@ -163,6 +196,12 @@ impl Owner {
                 contexts: Default::default(),
                 cleanups: Default::default(),
                 children: Default::default(),
+                #[cfg(feature = "sandboxed-arenas")]
+                arena: self.inner.read().or_poisoned().arena.clone(),
+                #[cfg(feature = "sandboxed-arenas")]
+                root_arena: None,
+                #[cfg(debug_assertions)]
+                component_stack: entered_component_stack(),
             })),
             #[cfg(feature = "hydration")]
             shared_context: self.shared_context.clone(),
@@ -172,25 +211,51 @@ impl Owner {
             .or_poisoned()
             .children
             .push(Arc::downgrade(&child.inner));
+        #[cfg(feature = "introspection")]
+        crate::introspection::register_owner(&child.inner);
         child
     }
 
     /// Sets this as the current `Owner`.
     pub fn set(&self) {
-        OWNER.with_borrow_mut(|owner| *owner = Some(self.clone()));
+        let prev = OWNER.with_borrow_mut(|owner| owner.replace(self.clone()));
+        drop(prev);
+    }
+
+    /// Stops this from being the current `Owner`, if it is.
+    ///
+    /// The current `Owner` is kept alive by the thread it was set on, so this should be called
+    /// on an `Owner` created with [`Owner::new_root`] that will not be used again, so that it can
+    /// be dropped.
+    pub fn unset(&self) {
+        let prev = OWNER.with_borrow_mut(|owner| {
+            if owner
+                .as_ref()
+                .map(|owner| Arc::ptr_eq(&owner.inner, &self.inner))
+                .unwrap_or(false)
+            {
+                owner.take()
+            } else {
+                None
+            }
+        });
+        drop(prev);
     }
 
     /// Runs the given function with this as the current `Owner`.
     pub fn with<T>(&self, fun: impl FnOnce() -> T) -> T {
+        // values created while this is the current owner are stored in its own arena
+        #[cfg(feature = "sandboxed-arenas")]
+        let _arena =
+            Arena::enter(self.inner.read().or_poisoned().arena.as_ref());
         let prev = {
             OWNER.with(|o| {
                 mem::replace(&mut *o.borrow_mut(), Some(self.clone()))
             })
         };
         let val = fun();
-        OWNER.with(|o| {
-            *o.borrow_mut() = prev;
-        });
+        let replaced = OWNER.with(|o| mem::replace(&mut *o.borrow_mut(), prev));
+        drop(replaced);
         val
     }
 
@@ -201,11 +266,69 @@ impl Owner {
     }
 
     /// Cleans up this owner in the following order:
-    /// 1) Runs `cleanup` on all children,
-    /// 2) Runs all cleanup functions registered with [`Owner::on_cleanup`],
+    /// 1) Disposes of all children, which cleans them up and then drops any context provided to
+    ///    them,
+    /// 2) Runs all cleanup functions registered with [`Owner::on_cleanup`], with this as the
+    ///    current `Owner`,
     /// 3) Drops the values of any arena-allocated [`StoredValue`]s.
+    ///
+    /// Context provided to this owner itself is kept, because an owner is cleaned up each time
+    /// its effect re-runs. It is dropped when the owner is dropped, after everything else.
     pub fn cleanup(&self) {
-        self.inner.cleanup();
+        #[cfg(feature = "introspection")]
+        crate::introspection::owner_disposed(Arc::as_ptr(&self.inner) as usize);
+        let (cleanups, nodes, children) = {
+            let mut lock = self.inner.write().or_poisoned();
+            (
+                mem::take(&mut lock.cleanups),
+                mem::take(&mut lock.nodes),
+                mem::take(&mut lock.children),
+            )
+        };
+        #[cfg(feature = "sandboxed-arenas")]
+        let arena = self.inner.read().or_poisoned().arena.clone();
+        for child in children {
+            if let Some(inner) = child.upgrade() {
+                Owner {
+                    inner,
+                    #[cfg(feature = "hydration")]
+                    shared_context: self.shared_context.clone(),
+                }
+                .dispose();
+            }
+        }
+        self.run_cleanups(cleanups);
+
+        remove_nodes(
+            #[cfg(feature = "sandboxed-arenas")]
+            arena.as_ref(),
+            nodes,
+        );
+    }
+
+    /// Runs the cleanup functions with this as the current owner. If the owner is being dropped
+    /// along with the thread's storage, there is no current owner to set, and they are run as-is.
+    fn run_cleanups(&self, cleanups: Vec<Box<dyn FnOnce() + Send + Sync>>) {
+        if OWNER.try_with(|_| ()).is_ok() {
+            self.with(|| {
+                for cleanup in cleanups {
+                    cleanup();
+                }
+            });
+        } else {
+            for cleanup in cleanups {
+                cleanup();
+            }
+        }
+    }
+
+    /// Cleans up this owner, then drops any context provided to it.
+    fn dispose(&self) {
+        self.cleanup();
+        // context is dropped outside the lock, as dropping it may use the owner
+        let contexts =
+            mem::take(&mut self.inner.write().or_poisoned().contexts);
+        drop(contexts);
     }
 
     /// Registers a function to be run the next time the current owner is cleaned up.
@@ -311,6 +434,78 @@ pub fn on_cleanup(fun: impl FnOnce() + Send + Sync + 'static) {
     Owner::on_cleanup(fun)
 }
 
+/// Runs `fun` with the component called `name` at the top of the component stack.
+///
+/// In debug builds, the stack of components is built once when a component is entered, and
+/// every `Owner` created while its body runs keeps a reference to it. Owners created later, like
+/// those of the effects that update a view once it is rendered, use the stack of the nearest
+/// parent that has one. This allows errors to report which components they occurred in, with
+/// [`component_stack`]. In release builds, this just runs `fun`.
+pub fn with_component<T>(name: &'static str, fun: impl FnOnce() -> T) -> T {
+    #[cfg(debug_assertions)]
+    {
+        struct RestoreOnDrop(Option<Arc<[&'static str]>>);
+
+        impl Drop for RestoreOnDrop {
+            fn drop(&mut self) {
+                let entered = COMPONENT_STACK.with_borrow_mut(|stack| {
+                    mem::replace(stack, self.0.take())
+                });
+                drop(entered);
+            }
+        }
+
+        let stack = component_stack()
+            .iter()
+            .copied()
+            .chain([name])
+            .collect::<Arc<[_]>>();
+        let _restore = RestoreOnDrop(
+            COMPONENT_STACK.with_borrow_mut(|entered| entered.replace(stack)),
+        );
+        fun()
+    }
+    #[cfg(not(debug_assertions))]
+    {
+        _ = name;
+        fun()
+    }
+}
+
+/// Returns the names of the components the code that is currently running belongs to,
+/// outermost first, as recorded by [`with_component`].
+///
+/// This is always empty in release builds.
+pub fn component_stack() -> Arc<[&'static str]> {
+    #[cfg(debug_assertions)]
+    {
+        COMPONENT_STACK
+            .with_borrow(Clone::clone)
+            .or_else(|| {
+                OWNER.with_borrow(|owner| {
+                    owner.as_ref().map(|owner| {
+                        owner
+                            .inner
+                            .read()
+                            .or_poisoned()
+                            .inherited_component_stack()
+                    })
+                })
+            })
+            .unwrap_or_default()
+    }
+    #[cfg(not(debug_assertions))]
+    {
+        Arc::from([])
+    }
+}
+
+/// The stack of the component whose body is running, if any.
+#[cfg(debug_assertions)]
+fn entered_component_stack() -> Option<Arc<[&'static str]>> {
+    COMPONENT_STACK.with_borrow(Clone::clone)
+}
+
 #[derive(Default)]
 pub(crate) struct OwnerInner {
     pub parent: Option<Weak<RwLock<OwnerInner>>>,
@@ -318,6 +513,16 @@ pub(crate) struct OwnerInner {
     pub contexts: FxHashMap<TypeId, Box<dyn Any + Send + Sync>>,
     pub cleanups: Vec<Box<dyn FnOnce() + Send + Sync>>,
     pub children: Vec<Weak<RwLock<OwnerInner>>>,
+    /// The arena that values owned by this owner are stored in.
+    #[cfg(feature = "sandboxed-arenas")]
+    arena: Option<WeakArena>,
+    /// Keeps the arena created by [`Owner::new_root`] alive for as long as the root.
+    #[cfg(feature = "sandboxed-arenas")]
+    #[allow(dead_code)]
+    root_arena: Option<SharedArena>,
+    /// The components whose bodies were running when this owner was created, if any.
+    #[cfg(debug_assertions)]
+    component_stack: Option<Arc<[&'static str]>>,
 }
 
 impl Debug for OwnerInner {
@@ -331,58 +536,100 @@ impl Debug for OwnerInner {
     }
 }
 
-impl Drop for OwnerInner {
-    fn drop(&mut self) {
-        for child in std::mem::take(&mut self.children) {
-            if let Some(child) = child.upgrade() {
-                child.cleanup();
-            }
+impl OwnerInner {
+    /// The components this owner was created in, or nothing in release builds.
+    #[cfg(feature = "introspection")]
+    pub(crate) fn component_stack(&self) -> Arc<[&'static str]> {
+        #[cfg(debug_assertions)]
+        {
+            self.inherited_component_stack()
         }
-
-        for cleanup in mem::take(&mut self.cleanups) {
-            cleanup();
+        #[cfg(not(debug_assertions))]
+        {
+            Arc::from([])
         }
+    }
 
-        let nodes = mem::take(&mut self.nodes);
-        if !nodes.is_empty() {
-            Arena::with_mut(|arena| {
-                for node in nodes {
-                    _ = arena.remove(node);
-                }
-            });
+    /// The stack of the components this owner was created in, or else that of its nearest
+    /// parent that has one.
+    #[cfg(debug_assertions)]
+    fn inherited_component_stack(&self) -> Arc<[&'static str]> {
+        match &self.component_stack {
+            Some(stack) => Arc::clone(stack),
+            None => self
+                .parent
+                .as_ref()
+                .and_then(Weak::upgrade)
+                .map(|parent| {
+                    parent.read().or_poisoned().inherited_component_stack()
+                })
+                .unwrap_or_default(),
         }
     }
 }
 
-trait Cleanup {
-    fn cleanup(&self);
-}
-
-impl Cleanup for RwLock<OwnerInner> {
-    fn cleanup(&self) {
-        let (cleanups, nodes, children) = {
-            let mut lock = self.write().or_poisoned();
-            (
-                mem::take(&mut lock.cleanups),
-                mem::take(&mut lock.nodes),
-                mem::take(&mut lock.children),
-            )
-        };
-        for child in children {
-            if let Some(child) = child.upgrade() {
-                child.cleanup();
+impl Drop for OwnerInner {
+    fn drop(&mut self) {
+        for child in mem::take(&mut self.children) {
+            if let Some(inner) = child.upgrade() {
+                Owner {
+                    inner,
+                    #[cfg(feature = "hydration")]
+                    shared_context: None,
+                }
+                .dispose();
             }
         }
-        for cleanup in cleanups {
-            cleanup();
-        }
 
-        if !nodes.is_empty() {
-            Arena::with_mut(|arena| {
-                for node in nodes {
-                    _ = arena.remove(node);
-                }
-            });
+        let cleanups = mem::take(&mut self.cleanups);
+        if !cleanups.is_empty() {
+            // this owner can no longer be made current, so a stand-in that holds its context is
+            // current while the cleanup functions run
+            let stand_in = Owner {
+                inner: Arc::new(RwLock::new(OwnerInner {
+                    parent: self.parent.clone(),
+                    nodes: Default::default(),
+                    contexts: mem::take(&mut self.contexts),
+                    cleanups: Default::default(),
+                    children: Default::default(),
+                    #[cfg(feature = "sandboxed-arenas")]
+                    arena: self.arena.clone(),
+                    #[cfg(feature = "sandboxed-arenas")]
+                    root_arena: None,
+                    #[cfg(debug_assertions)]
+                    component_stack: self.component_stack.clone(),
+                })),
+                #[cfg(feature = "hydration")]
+                shared_context: None,
+            };
+            stand_in.run_cleanups(cleanups);
         }
+
+        remove_nodes(
+            #[cfg(feature = "sandboxed-arenas")]
+            self.arena.as_ref(),
+            mem::take(&mut self.nodes),
+        );
+    }
+}
+
+/// Removes the values owned by an owner from the arena.
+///
+/// With the `sandboxed-arenas` feature, each owner removes its values from the arena they were
+/// created in, so that cleaning up an owner from one arena does not affect the values in another.
+fn remove_nodes(
+    #[cfg(feature = "sandboxed-arenas")] arena: Option<&WeakArena>,
+    nodes: Vec<NodeId>,
+) {
+    if nodes.is_empty() {
+        return;
     }
+    #[cfg(feature = "sandboxed-arenas")]
+    Arena::remove_from(arena, nodes);
+    #[cfg(not(feature = "sandboxed-arenas"))]
+    Arena::with_mut(|arena| {
+        for node in nodes {
+            _ = arena.remove(node);
+        }
+    });
 }
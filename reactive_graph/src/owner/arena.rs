@@ -22,6 +22,14 @@ thread_local! {
     pub(crate) static MAP: RefCell<Option<Arc<RwLock<ArenaMap>>>> = RefCell::new(Some(Default::default()));
 }
 
+/// A sandboxed arena.
+#[cfg(feature = "sandboxed-arenas")]
+pub(crate) type SharedArena = Arc<RwLock<ArenaMap>>;
+
+/// A reference to a sandboxed arena, which does not keep it alive.
+#[cfg(feature = "sandboxed-arenas")]
+pub(crate) type WeakArena = std::sync::Weak<RwLock<ArenaMap>>;
+
 impl Arena {
     #[cfg(feature = "hydration")]
     #[inline(always)]
@@ -37,6 +45,12 @@ impl Arena {
         }
     }
 
+    /// Returns the arena that is currently active on this thread.
+    #[cfg(feature = "sandboxed-arenas")]
+    pub(crate) fn current() -> Option<SharedArena> {
+        MAP.with_borrow(Clone::clone)
+    }
+
     #[track_caller]
     pub fn with<U>(fun: impl FnOnce(&ArenaMap) -> U) -> U {
         #[cfg(not(feature = "sandboxed-arenas"))]
@@ -89,7 +103,7 @@ impl Arena {
 
 #[cfg(feature = "sandboxed-arenas")]
 pub mod sandboxed {
-    use super::{Arena, ArenaMap, MAP};
+    use super::{Arena, ArenaMap, NodeId, WeakArena, MAP};
     use futures::Stream;
     use pin_project_lite::pin_project;
     use std::{
@@ -106,6 +120,35 @@ pub mod sandboxed {
                 UnsetArenaOnDrop(mem::replace(arena, Some(new_arena)))
             })
         }
+
+        /// Makes `arena` the current arena until the returned guard is dropped, if it is still
+        /// alive.
+        pub(crate) fn enter(
+            arena: Option<&WeakArena>,
+        ) -> Option<UnsetArenaOnDrop> {
+            arena.and_then(WeakArena::upgrade).map(Arena::set)
+        }
+
+        /// Removes `nodes` from `arena`, which is the arena they were created in, rather than
+        /// whichever arena happens to be current.
+        pub(crate) fn remove_from(
+            arena: Option<&WeakArena>,
+            nodes: Vec<NodeId>,
+        ) {
+            let _entered = match arena {
+                Some(arena) => match arena.upgrade() {
+                    Some(arena) => Some(Arena::set(arena)),
+                    // the arena has already been dropped, along with everything in it
+                    None => return,
+                },
+                None => None,
+            };
+            Arena::with_mut(|arena| {
+                for node in nodes {
+                    _ = arena.remove(node);
+                }
+            });
+        }
     }
 
     pin_project! {
@@ -174,7 +217,7 @@ pub mod sandboxed {
     }
 
     #[derive(Debug)]
-    struct UnsetArenaOnDrop(Option<Arc<RwLock<ArenaMap>>>);
+    pub(crate) struct UnsetArenaOnDrop(Option<Arc<RwLock<ArenaMap>>>);
 
     impl Drop for UnsetArenaOnDrop {
         fn drop(&mut self) {
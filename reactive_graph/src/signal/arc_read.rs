@@ -1,6 +1,6 @@
 use super::{
     guards::{Plain, ReadGuard},
-    subscriber_traits::AsSubscriberSet,
+    subscriber_traits::{version_of, AsSubscriberSet},
 };
 use crate::{
     graph::SubscriberSet,
@@ -84,12 +84,18 @@ impl<T> Debug for ArcReadSignal<T> {
 impl<T: Default> Default for ArcReadSignal<T> {
     #[track_caller]
     fn default() -> Self {
-        Self {
+        let this = Self {
             #[cfg(debug_assertions)]
             defined_at: Location::caller(),
             value: Arc::new(RwLock::new(T::default())),
             inner: Arc::new(RwLock::new(SubscriberSet::new())),
-        }
+        };
+        #[cfg(feature = "introspection")]
+        crate::introspection::register_source(
+            crate::introspection::NodeKind::Signal,
+            crate::graph::ToAnySource::to_any_source(&this),
+        );
+        this
     }
 }
 
@@ -107,6 +113,17 @@ impl<T> Hash for ArcReadSignal<T> {
     }
 }
 
+impl<T> ArcReadSignal<T> {
+    /// Returns the number of times the signal has notified its subscribers of a change.
+    ///
+    /// This can be compared to an earlier version to check whether the signal has changed,
+    /// without subscribing to it. It is updated along with the subscribers, so it is consistent
+    /// across threads.
+    pub fn version(&self) -> u64 {
+        version_of(self).unwrap_or_default()
+    }
+}
+
 impl<T> DefinedAt for ArcReadSignal<T> {
     #[inline(always)]
     fn defined_at(&self) -> Option<&'static Location<'static>> {
@@ -1,6 +1,6 @@
 use super::{
     guards::{Plain, ReadGuard, UntrackedWriteGuard, WriteGuard},
-    subscriber_traits::AsSubscriberSet,
+    subscriber_traits::{version_of, AsSubscriberSet},
     ArcReadSignal, ArcWriteSignal,
 };
 use crate::{
@@ -24,6 +24,11 @@ use std::{
 /// This is a reference-counted signal, which is `Clone` but not `Copy`.
 /// For arena-allocated `Copy` signals, use [`RwSignal`](super::RwSignal).
 ///
+/// The value is stored behind an `Arc<RwLock<_>>`, so the signal is `Send + Sync` whenever `T`
+/// is, and can be cloned into background tasks and threads of a multi-threaded runtime. Its
+/// [`version`](ArcRwSignal::version) can be used to check whether it has changed since it was
+/// last seen.
+///
 /// ## Core Trait Implementations
 ///
 /// ### Reading the Value
@@ -153,12 +158,27 @@ impl<T> ArcRwSignal<T> {
     )]
     #[track_caller]
     pub fn new(value: T) -> Self {
-        Self {
+        let this = Self {
             #[cfg(debug_assertions)]
             defined_at: Location::caller(),
             value: Arc::new(RwLock::new(value)),
             inner: Arc::new(RwLock::new(SubscriberSet::new())),
-        }
+        };
+        #[cfg(feature = "introspection")]
+        crate::introspection::register_source(
+            crate::introspection::NodeKind::Signal,
+            crate::graph::ToAnySource::to_any_source(&this),
+        );
+        this
+    }
+
+    /// Returns the number of times the signal has notified its subscribers of a change.
+    ///
+    /// This can be compared to an earlier version to check whether the signal has changed,
+    /// without subscribing to it. It is updated along with the subscribers, so it is consistent
+    /// across threads.
+    pub fn version(&self) -> u64 {
+        version_of(self).unwrap_or_default()
     }
 
     /// Returns a read-only handle to the signal.
@@ -22,11 +22,17 @@ impl ArcTrigger {
     /// Creates a new trigger.
     #[track_caller]
     pub fn new() -> Self {
-        Self {
+        let this = Self {
             #[cfg(debug_assertions)]
             defined_at: Location::caller(),
             inner: Default::default(),
-        }
+        };
+        #[cfg(feature = "introspection")]
+        crate::introspection::register_source(
+            crate::introspection::NodeKind::Trigger,
+            crate::graph::ToAnySource::to_any_source(&this),
+        );
+        this
     }
 }
 
@@ -85,6 +91,6 @@ impl DefinedAt for ArcTrigger {
 
 impl Trigger for ArcTrigger {
     fn trigger(&self) {
-        self.inner.mark_dirty();
+        self.mark_dirty();
     }
 }
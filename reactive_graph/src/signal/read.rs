@@ -1,6 +1,6 @@
 use super::{
     guards::{Plain, ReadGuard},
-    subscriber_traits::AsSubscriberSet,
+    subscriber_traits::{version_of, AsSubscriberSet},
     ArcReadSignal,
 };
 use crate::{
@@ -122,6 +122,22 @@ impl<T, S> IsDisposed for ReadSignal<T, S> {
     }
 }
 
+impl<T, S> ReadSignal<T, S>
+where
+    S: Storage<ArcReadSignal<T>>,
+{
+    /// Returns the number of times the signal has notified its subscribers of a change.
+    ///
+    /// See [`ArcReadSignal::version`].
+    ///
+    /// # Panics
+    /// Panics if the signal has been disposed.
+    #[track_caller]
+    pub fn version(&self) -> u64 {
+        version_of(self).unwrap_or_else(unwrap_signal!(self))
+    }
+}
+
 impl<T, S> AsSubscriberSet for ReadSignal<T, S>
 where
     S: Storage<ArcReadSignal<T>>,
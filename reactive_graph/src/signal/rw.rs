@@ -1,6 +1,6 @@
 use super::{
     guards::{Plain, ReadGuard},
-    subscriber_traits::AsSubscriberSet,
+    subscriber_traits::{version_of, AsSubscriberSet},
     ArcReadSignal, ArcRwSignal, ArcWriteSignal, ReadSignal, WriteSignal,
 };
 use crate::{
@@ -313,6 +313,22 @@ impl<T: 'static, S> IsDisposed for RwSignal<T, S> {
     }
 }
 
+impl<T, S> RwSignal<T, S>
+where
+    S: Storage<ArcRwSignal<T>>,
+{
+    /// Returns the number of times the signal has notified its subscribers of a change.
+    ///
+    /// See [`ArcRwSignal::version`].
+    ///
+    /// # Panics
+    /// Panics if the signal has been disposed.
+    #[track_caller]
+    pub fn version(&self) -> u64 {
+        version_of(self).unwrap_or_else(unwrap_signal!(self))
+    }
+}
+
 impl<T, S> AsSubscriberSet for RwSignal<T, S>
 where
     S: Storage<ArcRwSignal<T>>,
@@ -48,6 +48,16 @@ impl DefinedAt for RwLock<SubscriberSet> {
 // Source>
 impl<T: AsSubscriberSet + DefinedAt> ReactiveNode for T {
     fn mark_dirty(&self) {
+        #[cfg(feature = "introspection")]
+        if let Some(inner) = self.as_subscriber_set() {
+            let inner: &RwLock<SubscriberSet> = inner.borrow();
+            crate::introspection::mark_updated(
+                inner as *const RwLock<SubscriberSet> as usize,
+            );
+        }
+        crate::diagnostics::signal_written(self.defined_at());
+        #[cfg(debug_assertions)]
+        crate::graph::cycle::signal_written(self.defined_at());
         self.mark_subscribers_check();
     }
 
@@ -55,7 +65,7 @@ impl<T: AsSubscriberSet + DefinedAt> ReactiveNode for T {
 
     fn mark_subscribers_check(&self) {
         if let Some(inner) = self.as_subscriber_set() {
-            let subs = inner.borrow().write().unwrap().take();
+            let subs = inner.borrow().write().unwrap().notify();
             for sub in subs {
                 sub.mark_dirty();
             }
@@ -93,6 +103,14 @@ impl<T: AsSubscriberSet + DefinedAt> Source for T {
     }
 }
 
+/// Returns the number of times a signal has notified its subscribers, or `None` if it has been
+/// disposed.
+pub(crate) fn version_of(signal: &impl AsSubscriberSet) -> Option<u64> {
+    signal
+        .as_subscriber_set()
+        .map(|inner| inner.borrow().read().or_poisoned().version())
+}
+
 impl<T: AsSubscriberSet + DefinedAt> ToAnySource for T
 where
     T::Output: Borrow<Arc<RwLock<SubscriberSet>>>,
@@ -114,13 +132,16 @@ where
 
 impl ReactiveNode for RwLock<SubscriberSet> {
     fn mark_dirty(&self) {
+        crate::diagnostics::signal_written(self.defined_at());
+        #[cfg(debug_assertions)]
+        crate::graph::cycle::signal_written(self.defined_at());
         self.mark_subscribers_check();
     }
 
     fn mark_check(&self) {}
 
     fn mark_subscribers_check(&self) {
-        let subs = self.write().unwrap().take();
+        let subs = self.write().unwrap().notify();
         for sub in subs {
             sub.mark_dirty();
         }
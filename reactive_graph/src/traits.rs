@@ -108,7 +108,10 @@ impl<T: Source + ToAnySource + DefinedAt> Track for T {
     #[track_caller]
     fn track(&self) {
         if let Some(subscriber) = Observer::get() {
-            subscriber.add_source(self.to_any_source());
+            let source = self.to_any_source();
+            #[cfg(feature = "introspection")]
+            crate::introspection::add_dependency(&source, &subscriber);
+            subscriber.add_source(source);
             self.add_subscriber(subscriber);
         } else {
             #[cfg(all(debug_assertions, feature = "effects"))]
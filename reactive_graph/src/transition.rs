@@ -54,6 +54,11 @@ impl AsyncTransition {
         value
     }
 
+    /// Whether a transition is currently running.
+    pub(crate) fn is_running() -> bool {
+        global_transition().read().or_poisoned().is_some()
+    }
+
     pub(crate) fn register(rx: oneshot::Receiver<()>) {
         if let Some(tx) = global_transition()
             .read()
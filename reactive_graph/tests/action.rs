@@ -0,0 +1,179 @@
+use any_spawner::Executor;
+use futures::channel::oneshot;
+use reactive_graph::{
+    actions::{ArcAction, ArcMultiAction},
+    computed::ArcAsyncDerived,
+    traits::{Get, GetUntracked, With},
+};
+use std::sync::{Arc, Mutex};
+
+#[tokio::test]
+async fn action_tracks_input_pending_value_and_version() {
+    _ = Executor::init_tokio();
+
+    let (tx, rx) = oneshot::channel::<()>();
+    let rx = Arc::new(Mutex::new(Some(rx)));
+    let action = ArcAction::new(move |n: &u8| {
+        let n = *n;
+        let rx = rx.lock().unwrap().take();
+        async move {
+            if let Some(rx) = rx {
+                _ = rx.await;
+            }
+            n * 2
+        }
+    });
+
+    assert!(!action.pending().get());
+    action.dispatch(3);
+    assert!(action.pending().get());
+    assert_eq!(action.input().get(), Some(3));
+    assert_eq!(action.value().get(), None);
+    assert_eq!(action.version().get(), 0);
+
+    tx.send(()).unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+
+    assert!(!action.pending().get());
+    assert_eq!(action.input().get(), None);
+    assert_eq!(action.value().get(), Some(6));
+    assert_eq!(action.version().get(), 1);
+}
+
+#[tokio::test]
+async fn aborted_dispatches_do_not_update_the_value() {
+    _ = Executor::init_tokio();
+
+    let action = ArcAction::new(|n: &u8| {
+        let n = *n;
+        async move {
+            Executor::tick().await;
+            n
+        }
+    });
+
+    action.dispatch(1).abort();
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+
+    assert!(!action.pending().get());
+    assert_eq!(action.value().get(), None);
+    assert_eq!(action.version().get(), 0);
+}
+
+#[tokio::test]
+async fn resources_keyed_on_version_keep_their_value_while_refetching() {
+    _ = Executor::init_tokio();
+
+    let todos = Arc::new(Mutex::new(vec!["a".to_string()]));
+    // holds back the next fetch until the test releases it
+    let gate = Arc::new(Mutex::new(None::<oneshot::Receiver<()>>));
+    let add_todo = ArcAction::new({
+        let todos = Arc::clone(&todos);
+        move |todo: &String| {
+            todos.lock().unwrap().push(todo.clone());
+            async {}
+        }
+    });
+    let list = ArcAsyncDerived::new({
+        let version = add_todo.version();
+        let todos = Arc::clone(&todos);
+        let gate = Arc::clone(&gate);
+        move || {
+            version.get();
+            let todos = todos.lock().unwrap().clone();
+            let gate = gate.lock().unwrap().take();
+            async move {
+                if let Some(gate) = gate {
+                    _ = gate.await;
+                }
+                todos
+            }
+        }
+    });
+
+    assert_eq!(list.clone().await, vec!["a"]);
+
+    let (tx, rx) = oneshot::channel();
+    *gate.lock().unwrap() = Some(rx);
+    add_todo.dispatch("b".to_string());
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    assert_eq!(add_todo.version().get_untracked(), 1);
+    // the resource is refetching, but still holds the previous list
+    assert_eq!(list.get_untracked(), Some(vec!["a".to_string()]));
+
+    tx.send(()).unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    assert_eq!(list.get_untracked(), Some(vec!["a".into(), "b".into()]));
+}
+
+// ticks the executor until `done` returns `true`, rather than waiting for a fixed time
+async fn tick_until(mut done: impl FnMut() -> bool) {
+    for _ in 0..1000 {
+        if done() {
+            return;
+        }
+        Executor::tick().await;
+    }
+    panic!("spawned tasks did not settle");
+}
+
+#[tokio::test]
+async fn multi_action_tracks_each_submission() {
+    _ = Executor::init_tokio();
+
+    let gates = Arc::new(Mutex::new(Vec::new()));
+    let add_todo = ArcMultiAction::new({
+        let gates = Arc::clone(&gates);
+        move |title: &String| {
+            let (tx, rx) = oneshot::channel::<()>();
+            gates.lock().unwrap().push(tx);
+            let len = title.len();
+            async move {
+                _ = rx.await;
+                len
+            }
+        }
+    });
+    let submissions = add_todo.submissions();
+
+    add_todo.dispatch("milk".to_string());
+    add_todo.dispatch("eggs!".to_string());
+    add_todo.dispatch("bread".to_string());
+
+    // every submission is pending, with its input available for optimistic rendering
+    let pending_inputs = || {
+        submissions.with(|subs| {
+            subs.iter()
+                .filter(|sub| sub.pending().get())
+                .filter_map(|sub| sub.input().get())
+                .collect::<Vec<_>>()
+        })
+    };
+    assert_eq!(pending_inputs(), vec!["milk", "eggs!", "bread"]);
+
+    // resolve the second one only
+    let second = gates.lock().unwrap().remove(1);
+    second.send(()).unwrap();
+    tick_until(|| add_todo.version().get_untracked() == 1).await;
+
+    assert_eq!(pending_inputs(), vec!["milk", "bread"]);
+    assert_eq!(add_todo.version().get(), 1);
+    let values = submissions.with(|subs| {
+        subs.iter().map(|sub| sub.value().get()).collect::<Vec<_>>()
+    });
+    assert_eq!(values, vec![None, Some(5), None]);
+
+    // a canceled submission does not store its result
+    submissions.with(|subs| subs[0].cancel());
+    for gate in gates.lock().unwrap().drain(..) {
+        gate.send(()).unwrap();
+    }
+    // the version counts every submission that has finished, including canceled ones
+    tick_until(|| add_todo.version().get_untracked() == 3).await;
+
+    assert!(pending_inputs().is_empty());
+    let values = submissions.with(|subs| {
+        subs.iter().map(|sub| sub.value().get()).collect::<Vec<_>>()
+    });
+    assert_eq!(values, vec![None, Some(5), Some(5)]);
+}
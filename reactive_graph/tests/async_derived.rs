@@ -2,9 +2,15 @@ use any_spawner::Executor;
 use reactive_graph::{
     computed::{ArcAsyncDerived, AsyncDerived},
     signal::RwSignal,
-    traits::{Get, Read, Set, With, WithUntracked},
+    traits::{Get, Read, Set, Update, With, WithUntracked},
+};
+use std::{
+    future::pending,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
 };
-use std::future::pending;
 
 #[tokio::test]
 async fn arc_async_derived_calculates_eagerly() {
@@ -90,3 +96,41 @@ async fn read_signal_traits_on_arena() {
     assert_eq!(value.with(|n| *n), None);
     assert_eq!(value.get(), None);
 }
+
+#[tokio::test]
+async fn async_derived_refetch_reruns_computation() {
+    _ = Executor::init_tokio();
+
+    let runs = Arc::new(AtomicUsize::new(0));
+    let value = AsyncDerived::new({
+        let runs = Arc::clone(&runs);
+        move || {
+            let runs = Arc::clone(&runs);
+            async move {
+                Executor::tick().await;
+                runs.fetch_add(1, Ordering::Relaxed) + 1
+            }
+        }
+    });
+
+    assert_eq!(value.await, 1);
+    value.refetch();
+    Executor::tick().await;
+    assert_eq!(value.await, 2);
+}
+
+#[tokio::test]
+async fn async_derived_can_be_set_and_updated() {
+    _ = Executor::init_tokio();
+
+    let value = AsyncDerived::new(|| async {
+        Executor::tick().await;
+        1
+    });
+
+    assert_eq!(value.await, 1);
+    value.set(Some(10));
+    assert_eq!(value.get(), Some(10));
+    value.update(|n| *n = n.map(|n| n + 1));
+    assert_eq!(value.get(), Some(11));
+}
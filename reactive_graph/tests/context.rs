@@ -0,0 +1,124 @@
+use reactive_graph::owner::{provide_context, use_context, Owner};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+struct Theme(&'static str);
+
+#[test]
+fn context_is_shadowed_in_nested_owners() {
+    let root = Owner::new();
+    root.with(|| {
+        provide_context(Theme("light"));
+
+        let child = Owner::new();
+        child.with(|| {
+            assert_eq!(use_context::<Theme>(), Some(Theme("light")));
+            provide_context(Theme("dark"));
+
+            let grandchild = Owner::new();
+            grandchild.with(|| {
+                assert_eq!(use_context::<Theme>(), Some(Theme("dark")));
+            });
+        });
+
+        // the child's value does not leak into its parent or siblings
+        assert_eq!(use_context::<Theme>(), Some(Theme("light")));
+        let sibling = Owner::new();
+        sibling.with(|| {
+            assert_eq!(use_context::<Theme>(), Some(Theme("light")));
+        });
+    });
+}
+
+#[derive(Clone)]
+struct DropCounter(Arc<AtomicUsize>);
+
+impl Drop for DropCounter {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn context_is_kept_when_owner_is_cleaned_up() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let owner = Owner::new();
+    owner.with(|| provide_context(DropCounter(Arc::clone(&drops))));
+    owner.with(|| assert!(use_context::<DropCounter>().is_some()));
+    // the clone returned by use_context was dropped
+    assert_eq!(drops.swap(0, Ordering::Relaxed), 1);
+
+    // as when an effect re-runs
+    owner.with_cleanup(|| assert!(use_context::<DropCounter>().is_some()));
+    assert_eq!(drops.swap(0, Ordering::Relaxed), 1);
+
+    drop(owner);
+    assert_eq!(drops.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn context_in_child_owner_is_dropped_with_it() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let parent = Owner::new();
+    let child = parent.child();
+    child.with(|| provide_context(DropCounter(Arc::clone(&drops))));
+
+    drop(child);
+    assert_eq!(drops.load(Ordering::Relaxed), 1);
+    parent.with(|| assert!(use_context::<DropCounter>().is_none()));
+}
+
+#[test]
+fn unset_owner_is_dropped_with_its_last_handle() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let owner = Owner::new();
+    owner.with(|| provide_context(DropCounter(Arc::clone(&drops))));
+
+    owner.set();
+    assert!(Owner::current().is_some());
+    owner.unset();
+    assert!(Owner::current().is_none());
+
+    drop(owner);
+    assert_eq!(drops.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn context_can_be_used_in_cleanup_functions() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let on_cleanup = |seen: &Arc<Mutex<Vec<_>>>| {
+        let seen = Arc::clone(seen);
+        Owner::on_cleanup(move || {
+            seen.lock().unwrap().push(use_context::<Theme>());
+        });
+    };
+
+    let parent = Owner::new();
+    parent.with(|| provide_context(Theme("light")));
+    let child = parent.child();
+    child.with(|| {
+        provide_context(Theme("dark"));
+        on_cleanup(&seen);
+    });
+    parent.with(|| on_cleanup(&seen));
+
+    // the child is disposed of before the parent's own cleanup functions run
+    parent.cleanup();
+    assert_eq!(
+        *seen.lock().unwrap(),
+        [Some(Theme("dark")), Some(Theme("light"))]
+    );
+    child.with(|| assert_eq!(use_context::<Theme>(), Some(Theme("light"))));
+
+    seen.lock().unwrap().clear();
+    let owner = parent.child();
+    owner.with(|| {
+        provide_context(Theme("dark"));
+        on_cleanup(&seen);
+    });
+    drop(owner);
+    assert_eq!(*seen.lock().unwrap(), [Some(Theme("dark"))]);
+}
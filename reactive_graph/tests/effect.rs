@@ -8,7 +8,7 @@ pub mod imports {
     };
     pub use std::{
         mem,
-        sync::{Arc, RwLock},
+        sync::{Arc, Mutex, RwLock},
     };
     pub use tokio::task;
 }
@@ -149,3 +149,169 @@ async fn dynamic_dependencies() {
         })
         .await
 }
+
+// locations are only reported in debug builds
+#[cfg(all(feature = "effects", debug_assertions))]
+#[tokio::test]
+async fn observer_is_told_about_writes_and_runs() {
+    use imports::*;
+    use reactive_graph::diagnostics::{ReactiveObserver, System};
+    use std::panic::Location;
+
+    // the line of each effect that ran, and the lines of its sources
+    type Run = (u32, Vec<u32>);
+
+    #[derive(Default, Clone)]
+    struct Recorder {
+        writes: Arc<Mutex<Vec<u32>>>,
+        runs: Arc<Mutex<Vec<Run>>>,
+    }
+
+    impl ReactiveObserver for Recorder {
+        fn signal_written(
+            &self,
+            defined_at: Option<&'static Location<'static>>,
+        ) {
+            if let Some(location) = defined_at {
+                self.writes.lock().unwrap().push(location.line());
+            }
+        }
+
+        fn effect_ran(
+            &self,
+            defined_at: Option<&'static Location<'static>>,
+            sources: &[Option<&'static Location<'static>>],
+        ) {
+            if let Some(location) = defined_at {
+                let sources =
+                    sources.iter().flatten().map(|loc| loc.line()).collect();
+                self.runs.lock().unwrap().push((location.line(), sources));
+            }
+        }
+    }
+
+    _ = Executor::init_tokio();
+    let recorder = Recorder::default();
+    System::set_observer(recorder.clone());
+
+    task::LocalSet::new()
+        .run_until(async {
+            let a = RwSignal::new(0);
+            let effect = RenderEffect::new(move |_| {
+                a.get();
+            });
+
+            a.set(1);
+            Executor::tick().await;
+            drop(effect);
+        })
+        .await;
+    System::clear_observer();
+
+    let signal_line = line!() - 12;
+    let effect_line = signal_line + 1;
+    assert!(recorder.writes.lock().unwrap().contains(&signal_line));
+    let runs = recorder.runs.lock().unwrap();
+    let runs = runs
+        .iter()
+        .filter(|(line, _)| *line == effect_line)
+        .collect::<Vec<_>>();
+    // the first run has no sources, and the second was caused by the signal
+    assert_eq!(runs.len(), 2);
+    assert!(runs[0].1.is_empty());
+    assert_eq!(runs[1].1, vec![signal_line]);
+}
+
+#[cfg(all(feature = "effects", debug_assertions))]
+#[tokio::test]
+async fn effect_writing_to_its_own_source_is_stopped() {
+    use imports::*;
+
+    _ = Executor::init_tokio();
+    task::LocalSet::new()
+        .run_until(async {
+            let a = RwSignal::new(0);
+            Effect::new(move |_| {
+                a.set(a.get() + 1);
+            });
+
+            for _ in 0..2000 {
+                Executor::tick().await;
+            }
+            // the effect panics once it has run itself 1000 times in a row
+            let runs = a.get_untracked();
+            assert!(runs > 1000 && runs < 1010, "ran {runs} times");
+        })
+        .await;
+}
+
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn effects_run_in_priority_order() {
+    use imports::*;
+    use reactive_graph::effect::EffectPriority;
+
+    _ = Executor::init_tokio();
+    task::LocalSet::new()
+        .run_until(async {
+            let a = RwSignal::new(0);
+            let order = Arc::new(RwLock::new(Vec::new()));
+
+            // created in the reverse of the order they should run in
+            for (priority, name) in [
+                (EffectPriority::Idle, "idle"),
+                (EffectPriority::Normal, "normal"),
+                (EffectPriority::Layout, "layout"),
+            ] {
+                Effect::new_with_priority(priority, {
+                    let order = Arc::clone(&order);
+                    move |_| {
+                        a.track();
+                        order.write().unwrap().push(name);
+                    }
+                });
+            }
+
+            for _ in 0..5 {
+                Executor::tick().await;
+            }
+            assert_eq!(*order.read().unwrap(), ["layout", "normal", "idle"]);
+
+            order.write().unwrap().clear();
+            a.set(1);
+            for _ in 0..5 {
+                Executor::tick().await;
+            }
+            assert_eq!(*order.read().unwrap(), ["layout", "normal", "idle"]);
+        })
+        .await;
+}
+
+#[cfg(feature = "effects")]
+#[tokio::test(flavor = "multi_thread")]
+async fn deferred_effects_wait_until_the_view_is_built() {
+    use imports::*;
+    use reactive_graph::effect::defer_effects;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    _ = Executor::init_tokio();
+    let ran = Arc::new(AtomicBool::new(false));
+    defer_effects(|| {
+        Effect::new_sync({
+            let ran = Arc::clone(&ran);
+            move |_| ran.store(true, Ordering::Relaxed)
+        });
+        // the other worker threads keep running tasks while this one is blocked
+        futures::executor::block_on(Executor::tick());
+        assert!(!ran.load(Ordering::Relaxed));
+    });
+
+    // the effect may be picked up by another worker, so give it a few ticks
+    for _ in 0..100 {
+        if ran.load(Ordering::Relaxed) {
+            break;
+        }
+        Executor::tick().await;
+    }
+    assert!(ran.load(Ordering::Relaxed));
+}
@@ -0,0 +1,149 @@
+#![cfg(feature = "introspection")]
+
+use reactive_graph::{
+    computed::ArcMemo,
+    introspection::{
+        add_listener, nodes, remove_listener, GraphEvent, NodeId, NodeKind,
+    },
+    owner::Owner,
+    prelude::*,
+    signal::ArcRwSignal,
+};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn lists_nodes_and_their_sources() {
+    let a = ArcRwSignal::new(1);
+    let b = ArcRwSignal::new(2);
+    let sum = ArcMemo::new({
+        let a = a.clone();
+        let b = b.clone();
+        move |_| a.get() + b.get()
+    });
+    assert_eq!(sum.get(), 3);
+
+    let id_of = |line: u32| {
+        nodes()
+            .into_iter()
+            .find(|node| {
+                node.defined_at.map(|loc| (loc.file(), loc.line()))
+                    == Some((file!(), line))
+            })
+            .expect("node should be registered")
+    };
+    let a_node = id_of(16);
+    let b_node = id_of(17);
+    let sum_node = id_of(18);
+    assert_eq!(a_node.kind, NodeKind::Signal);
+    assert_eq!(sum_node.kind, NodeKind::Memo);
+    assert!(sum_node.last_updated.is_some());
+    let mut expected: Vec<NodeId> = vec![a_node.id, b_node.id];
+    expected.sort();
+    assert_eq!(sum_node.sources, expected);
+
+    // nodes that have been dropped are no longer listed
+    drop(sum);
+    assert!(nodes().iter().all(|node| node.id != sum_node.id));
+
+    a.set(5);
+    assert!(id_of(16).last_updated.is_some());
+}
+
+#[test]
+fn listeners_are_told_about_graph_events() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let listener = add_listener({
+        let events = Arc::clone(&events);
+        move |event| events.lock().unwrap().push(event.clone())
+    });
+
+    let owner = Owner::new();
+    let (signal, memo) = owner.with(|| {
+        let signal = ArcRwSignal::new(1);
+        let memo = ArcMemo::new({
+            let signal = signal.clone();
+            move |_| signal.get() * 2
+        });
+        (signal, memo)
+    });
+    assert_eq!(memo.get(), 2);
+    signal.set(2);
+    owner.cleanup();
+    remove_listener(listener);
+
+    let id_of = |kind: NodeKind, line: u32| {
+        events
+            .lock()
+            .unwrap()
+            .iter()
+            .find_map(|event| match event {
+                GraphEvent::NodeCreated {
+                    id,
+                    kind: created,
+                    defined_at: Some(loc),
+                } if *created == kind && loc.line() == line => Some(*id),
+                _ => None,
+            })
+            .expect("node creation should be emitted")
+    };
+    let signal_id = id_of(NodeKind::Signal, 62);
+    let memo_id = id_of(NodeKind::Memo, 63);
+
+    let events = events.lock().unwrap();
+    assert!(events.iter().any(|event| matches!(
+        event,
+        GraphEvent::NodeRan { id, kind: NodeKind::Memo } if *id == memo_id
+    )));
+    assert!(events.iter().any(|event| matches!(
+        event,
+        GraphEvent::NodeUpdated { id } if *id == signal_id
+    )));
+    assert!(events.iter().any(|event| matches!(
+        event,
+        GraphEvent::OwnerDisposed { owner: id } if *id == owner.debug_id()
+    )));
+}
+
+#[test]
+fn reports_nodes_and_owners_that_outlive_their_owner() {
+    use reactive_graph::{introspection::leaks, owner::with_component};
+
+    let owner = Owner::new();
+    let (kept, dropped, child) = owner.with(|| {
+        with_component("Leaky", || {
+            let kept = ArcRwSignal::new(1);
+            let dropped = ArcRwSignal::new(2);
+            (kept, dropped, Owner::new())
+        })
+    });
+    let leaked_at = |line: u32| {
+        leaks().nodes.into_iter().find(|node| {
+            node.defined_at.map(|loc| (loc.file(), loc.line()))
+                == Some((file!(), line))
+        })
+    };
+    let owner_leaked =
+        |id: usize| leaks().owners.iter().any(|owner| owner.id == id);
+
+    // nothing has leaked while the owner is alive
+    assert!(leaked_at(114).is_none());
+    assert!(!owner_leaked(child.debug_id()));
+
+    owner.cleanup();
+    drop(dropped);
+    let node = leaked_at(114).expect("signal should outlive its owner");
+    assert_eq!(node.kind, NodeKind::Signal);
+    assert_eq!(&*node.component_stack, ["Leaky"]);
+    assert!(leaked_at(115).is_none());
+    assert!(owner_leaked(child.debug_id()));
+    assert!(leaks().to_string().contains("outlived its owner"));
+
+    let child_id = child.debug_id();
+    drop(kept);
+    drop(child);
+    assert!(leaked_at(114).is_none());
+    assert!(!owner_leaked(child_id));
+    let report = leaks();
+    assert!(report.live_nodes <= report.nodes_created);
+    assert!(report.owners_created >= 2);
+}
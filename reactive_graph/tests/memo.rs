@@ -401,3 +401,18 @@ fn unsync_derived_signal_and_memo() {
     assert_eq!(f.with(|n| *n), 6);
     assert_eq!(f.get_untracked(), 6);
 }
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "a memo reads from itself")]
+fn memo_reading_itself_panics() {
+    use std::sync::OnceLock;
+
+    let this = Arc::new(OnceLock::<ArcMemo<i32>>::new());
+    let memo = ArcMemo::new({
+        let this = Arc::clone(&this);
+        move |_| this.get().map(|memo| memo.get()).unwrap_or(0) + 1
+    });
+    _ = this.set(memo.clone());
+    memo.get();
+}
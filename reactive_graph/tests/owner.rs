@@ -0,0 +1,58 @@
+#[cfg(all(feature = "hydration", feature = "sandboxed-arenas"))]
+#[test]
+fn roots_on_the_same_thread_use_separate_arenas() {
+    use reactive_graph::{
+        owner::Owner, signal::RwSignal, traits::GetUntracked,
+    };
+
+    let first = Owner::new_root(None);
+    let first_signal = first.with(|| RwSignal::new("first"));
+    let second = Owner::new_root(None);
+    let second_signal = second.with(|| RwSignal::new("second"));
+
+    assert_eq!(first.with(|| first_signal.get_untracked()), "first");
+    assert_eq!(second.with(|| second_signal.get_untracked()), "second");
+
+    // the second arena is current, but cleaning up the first root only disposes of its own values
+    first.cleanup();
+    assert_eq!(first.with(|| first_signal.try_get_untracked()), None);
+    assert_eq!(second.with(|| second_signal.get_untracked()), "second");
+    second.unset();
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn owners_remember_the_components_they_were_created_in() {
+    use reactive_graph::owner::{component_stack, with_component, Owner};
+
+    let root = Owner::new();
+    let row = root.with(|| {
+        with_component("App", || {
+            with_component("PostList", || {
+                assert_eq!(&*component_stack(), ["App", "PostList"]);
+                // an owner created in a component body keeps its stack
+                with_component("PostRow", Owner::new)
+            })
+        })
+    });
+    assert!(component_stack().is_empty());
+    assert!(root.with(component_stack).is_empty());
+    assert_eq!(&*row.with(component_stack), ["App", "PostList", "PostRow"]);
+
+    // owners created under it once the bodies have run, like those of effects, inherit it
+    let effect = row.with(Owner::new);
+    assert_eq!(
+        &*effect.with(component_stack),
+        ["App", "PostList", "PostRow"]
+    );
+
+    // a component entered later starts from the stack of the current owner
+    effect.with(|| {
+        with_component("Comment", || {
+            assert_eq!(
+                &*component_stack(),
+                ["App", "PostList", "PostRow", "Comment"]
+            );
+        })
+    });
+}
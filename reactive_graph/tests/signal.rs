@@ -95,3 +95,30 @@ fn update_signal() {
     set_a.set(4);
     assert_eq!(a.get(), 4);
 }
+
+#[test]
+fn signals_can_be_shared_between_threads() {
+    let count = ArcRwSignal::new(0);
+    let read = count.read_only();
+    assert_eq!(read.version(), 0);
+
+    let threads = (0..4)
+        .map(|_| {
+            let count = count.clone();
+            std::thread::spawn(move || {
+                for _ in 0..100 {
+                    count.update(|n| *n += 1);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    assert_eq!(read.get_untracked(), 400);
+    assert_eq!(read.version(), 400);
+    // untracked updates do not notify, so they do not change the version
+    count.update_untracked(|n| *n += 1);
+    assert_eq!(count.version(), 400);
+}
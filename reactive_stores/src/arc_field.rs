@@ -1,12 +1,9 @@
 use crate::{
     path::{StorePath, StorePathSegment},
-    AtIndex, StoreField, Subfield,
+    AtIndex, AtKey, AtKeyed, KeyMap, StoreField, StoreFieldTrigger, Subfield,
 };
-use reactive_graph::{
-    signal::ArcTrigger,
-    traits::{
-        DefinedAt, IsDisposed, ReadUntracked, Track, Trigger, UntrackableGuard,
-    },
+use reactive_graph::traits::{
+    DefinedAt, IsDisposed, ReadUntracked, Track, Trigger, UntrackableGuard,
 };
 use std::{
     ops::{Deref, DerefMut, IndexMut},
@@ -21,8 +18,7 @@ where
     #[cfg(debug_assertions)]
     defined_at: &'static Location<'static>,
     path: StorePath,
-    trigger: ArcTrigger,
-    get_trigger: Arc<dyn Fn(StorePath) -> ArcTrigger + Send + Sync>,
+    get_trigger: Arc<dyn Fn(StorePath) -> StoreFieldTrigger + Send + Sync>,
     read: Arc<dyn Fn() -> Option<StoreFieldReader<T>> + Send + Sync>,
     write: Arc<dyn Fn() -> Option<StoreFieldWriter<T>> + Send + Sync>,
 }
@@ -76,7 +72,7 @@ impl<T> StoreField for ArcField<T> {
     type Reader = StoreFieldReader<T>;
     type Writer = StoreFieldWriter<T>;
 
-    fn get_trigger(&self, path: StorePath) -> ArcTrigger {
+    fn get_trigger(&self, path: StorePath) -> StoreFieldTrigger {
         (self.get_trigger)(path)
     }
 
@@ -93,20 +89,16 @@ impl<T> StoreField for ArcField<T> {
     }
 }
 
-impl<Inner, Prev, T> From<Subfield<Inner, Prev, T>> for ArcField<T>
-where
-    T: Send + Sync,
-    Subfield<Inner, Prev, T>: Clone,
-    Inner: StoreField<Value = Prev> + Send + Sync + 'static,
-    Prev: 'static,
-{
+impl<T> ArcField<T> {
     #[track_caller]
-    fn from(value: Subfield<Inner, Prev, T>) -> Self {
+    fn from_field<F>(value: F) -> Self
+    where
+        F: StoreField<Value = T> + Clone + Send + Sync + 'static,
+    {
         ArcField {
             #[cfg(debug_assertions)]
             defined_at: Location::caller(),
             path: value.path().into_iter().collect(),
-            trigger: value.get_trigger(value.path().into_iter().collect()),
             get_trigger: Arc::new({
                 let value = value.clone();
                 move |path| value.get_trigger(path)
@@ -123,6 +115,19 @@ where
     }
 }
 
+impl<Inner, Prev, T> From<Subfield<Inner, Prev, T>> for ArcField<T>
+where
+    T: Send + Sync,
+    Subfield<Inner, Prev, T>: Clone,
+    Inner: StoreField<Value = Prev> + Send + Sync + 'static,
+    Prev: 'static,
+{
+    #[track_caller]
+    fn from(value: Subfield<Inner, Prev, T>) -> Self {
+        ArcField::from_field(value)
+    }
+}
+
 impl<Inner, Prev> From<AtIndex<Inner, Prev>> for ArcField<Prev::Output>
 where
     AtIndex<Inner, Prev>: Clone,
@@ -132,24 +137,36 @@ where
 {
     #[track_caller]
     fn from(value: AtIndex<Inner, Prev>) -> Self {
-        ArcField {
-            #[cfg(debug_assertions)]
-            defined_at: Location::caller(),
-            path: value.path().into_iter().collect(),
-            trigger: value.get_trigger(value.path().into_iter().collect()),
-            get_trigger: Arc::new({
-                let value = value.clone();
-                move |path| value.get_trigger(path)
-            }),
-            read: Arc::new({
-                let value = value.clone();
-                move || value.reader().map(StoreFieldReader::new)
-            }),
-            write: Arc::new({
-                let value = value.clone();
-                move || value.writer().map(StoreFieldWriter::new)
-            }),
-        }
+        ArcField::from_field(value)
+    }
+}
+
+impl<Inner, Prev, T, K> From<AtKeyed<Inner, Prev, T, K>> for ArcField<T::Output>
+where
+    AtKeyed<Inner, Prev, T, K>: Clone,
+    Inner: StoreField<Value = Prev> + Send + Sync + 'static,
+    Prev: Send + Sync + 'static,
+    T: IndexMut<usize> + Send + Sync + 'static,
+    T::Output: Sized + Send + Sync,
+    K: Send + Sync + 'static,
+{
+    #[track_caller]
+    fn from(value: AtKeyed<Inner, Prev, T, K>) -> Self {
+        ArcField::from_field(value)
+    }
+}
+
+impl<Inner, Prev, K> From<AtKey<Inner, Prev, K>> for ArcField<Prev::Value>
+where
+    AtKey<Inner, Prev, K>: Clone,
+    Inner: StoreField<Value = Prev> + Send + Sync + 'static,
+    Prev: KeyMap<K> + Send + Sync + 'static,
+    Prev::Value: Send + Sync,
+    K: Clone + Send + Sync + 'static,
+{
+    #[track_caller]
+    fn from(value: AtKey<Inner, Prev, K>) -> Self {
+        ArcField::from_field(value)
     }
 }
 
@@ -159,7 +176,6 @@ impl<T> Clone for ArcField<T> {
             #[cfg(debug_assertions)]
             defined_at: self.defined_at,
             path: self.path.clone(),
-            trigger: self.trigger.clone(),
             get_trigger: Arc::clone(&self.get_trigger),
             read: Arc::clone(&self.read),
             write: Arc::clone(&self.write),
@@ -182,13 +198,13 @@ impl<T> DefinedAt for ArcField<T> {
 
 impl<T> Trigger for ArcField<T> {
     fn trigger(&self) {
-        self.trigger.trigger();
+        self.triggers_for_current_path().trigger();
     }
 }
 
 impl<T> Track for ArcField<T> {
     fn track(&self) {
-        self.trigger.track();
+        self.track_field();
     }
 }
 
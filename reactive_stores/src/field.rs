@@ -1,11 +1,11 @@
 use crate::{
     arc_field::{StoreFieldReader, StoreFieldWriter},
     path::{StorePath, StorePathSegment},
-    ArcField, AtIndex, StoreField, Subfield,
+    ArcField, AtIndex, AtKey, AtKeyed, KeyMap, StoreField, StoreFieldTrigger,
+    Subfield,
 };
 use reactive_graph::{
     owner::{Storage, StoredValue, SyncStorage},
-    signal::ArcTrigger,
     traits::{DefinedAt, IsDisposed, ReadUntracked, Track, Trigger},
     unwrap_signal,
 };
@@ -28,7 +28,7 @@ where
     type Reader = StoreFieldReader<T>;
     type Writer = StoreFieldWriter<T>;
 
-    fn get_trigger(&self, path: StorePath) -> ArcTrigger {
+    fn get_trigger(&self, path: StorePath) -> StoreFieldTrigger {
         self.inner
             .try_get_value()
             .map(|inner| inner.get_trigger(path))
@@ -87,6 +87,46 @@ where
     }
 }
 
+impl<Inner, Prev, T, K, S> From<AtKeyed<Inner, Prev, T, K>>
+    for Field<T::Output, S>
+where
+    AtKeyed<Inner, Prev, T, K>: Clone,
+    S: Storage<ArcField<T::Output>>,
+    Inner: StoreField<Value = Prev> + Send + Sync + 'static,
+    Prev: Send + Sync + 'static,
+    T: IndexMut<usize> + Send + Sync + 'static,
+    T::Output: Sized + Send + Sync,
+    K: Send + Sync + 'static,
+{
+    #[track_caller]
+    fn from(value: AtKeyed<Inner, Prev, T, K>) -> Self {
+        Field {
+            #[cfg(debug_assertions)]
+            defined_at: Location::caller(),
+            inner: StoredValue::new_with_storage(value.into()),
+        }
+    }
+}
+
+impl<Inner, Prev, K, S> From<AtKey<Inner, Prev, K>> for Field<Prev::Value, S>
+where
+    AtKey<Inner, Prev, K>: Clone,
+    S: Storage<ArcField<Prev::Value>>,
+    Inner: StoreField<Value = Prev> + Send + Sync + 'static,
+    Prev: KeyMap<K> + Send + Sync + 'static,
+    Prev::Value: Send + Sync,
+    K: Clone + Send + Sync + 'static,
+{
+    #[track_caller]
+    fn from(value: AtKey<Inner, Prev, K>) -> Self {
+        Field {
+            #[cfg(debug_assertions)]
+            defined_at: Location::caller(),
+            inner: StoredValue::new_with_storage(value.into()),
+        }
+    }
+}
+
 impl<T, S> Clone for Field<T, S> {
     fn clone(&self) -> Self {
         *self
@@ -1,12 +1,10 @@
 use crate::{
     path::{StorePath, StorePathSegment},
-    store_field::StoreField,
+    store_field::{StoreField, StoreFieldTriggers},
+    StoreFieldTrigger,
 };
 use reactive_graph::{
-    signal::{
-        guards::{MappedMutArc, WriteGuard},
-        ArcTrigger,
-    },
+    signal::guards::{MappedMutArc, WriteGuard},
     traits::{
         DefinedAt, IsDisposed, ReadUntracked, Track, Trigger, UntrackableGuard,
         Writeable,
@@ -75,8 +73,10 @@ where
 {
     type Value = Prev::Output;
     type Reader = MappedMutArc<Inner::Reader, Prev::Output>;
-    type Writer =
-        MappedMutArc<WriteGuard<ArcTrigger, Inner::Writer>, Prev::Output>;
+    type Writer = MappedMutArc<
+        WriteGuard<StoreFieldTriggers, Inner::Writer>,
+        Prev::Output,
+    >;
 
     fn path(&self) -> impl IntoIterator<Item = StorePathSegment> {
         self.inner
@@ -85,7 +85,7 @@ where
             .chain(iter::once(self.index.into()))
     }
 
-    fn get_trigger(&self, path: StorePath) -> ArcTrigger {
+    fn get_trigger(&self, path: StorePath) -> StoreFieldTrigger {
         self.inner.get_trigger(path)
     }
 
@@ -100,8 +100,11 @@ where
     }
 
     fn writer(&self) -> Option<Self::Writer> {
-        let trigger = self.get_trigger(self.path().into_iter().collect());
-        let inner = WriteGuard::new(trigger, self.inner.writer()?);
+        let trigger = self.triggers_for_current_path();
+        // the triggers for the fields this one is nested in are included above
+        let mut inner = self.inner.writer()?;
+        inner.untrack();
+        let inner = WriteGuard::new(trigger, inner);
         let index = self.index;
         Some(MappedMutArc::new(
             inner,
@@ -143,8 +146,7 @@ where
     Prev::Output: Sized,
 {
     fn trigger(&self) {
-        let trigger = self.get_trigger(self.path().into_iter().collect());
-        trigger.trigger();
+        self.triggers_for_current_path().trigger();
     }
 }
 
@@ -155,8 +157,7 @@ where
     Prev::Output: Sized + 'static,
 {
     fn track(&self) {
-        let trigger = self.get_trigger(self.path().into_iter().collect());
-        trigger.track();
+        self.track_field();
     }
 }
 
@@ -207,8 +208,7 @@ where
 {
     fn iter(self) -> StoreFieldIter<Inner, Prev> {
         // reactively track changes to this field
-        let trigger = self.get_trigger(self.path().into_iter().collect());
-        trigger.track();
+        self.track_field();
 
         // get the current length of the field by accessing slice
         let len = self.reader().map(|n| n.as_ref().len()).unwrap_or(0);
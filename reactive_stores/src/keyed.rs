@@ -0,0 +1,702 @@
+use crate::{
+    path::{StorePath, StorePathSegment},
+    store_field::{StoreField, StoreFieldTriggers},
+    StoreFieldTrigger,
+};
+use reactive_graph::{
+    signal::guards::{Mapped, MappedMut, MappedMutArc, WriteGuard},
+    traits::{
+        DefinedAt, IsDisposed, ReadUntracked, Track, Trigger, UntrackableGuard,
+        Writeable,
+    },
+};
+use rustc_hash::FxHasher;
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::{BuildHasher, Hash, Hasher},
+    iter,
+    marker::PhantomData,
+    ops::{DerefMut, IndexMut},
+    panic::Location,
+};
+
+/// The path segment for the item with the given key.
+///
+/// Items are tracked by their key, rather than by their position, so that inserting or removing
+/// other items does not change which item a field refers to.
+fn key_segment<K: Hash>(key: &K) -> StorePathSegment {
+    let mut hasher = FxHasher::default();
+    key.hash(&mut hasher);
+    (hasher.finish() as usize).into()
+}
+
+/// A field that holds a collection of items which each have a unique key, such as a `Vec` of
+/// rows with an `id`.
+///
+/// This is created by `#[derive(Store)]` for fields with a `#[store(key = <field>: <Type>)]`
+/// attribute, where `<field>` is the field of each item that holds its key. Items can be
+/// accessed by key with [`at_key`](KeyedField::at_key), or iterated over with
+/// [`iter_keyed`](KeyedField::iter_keyed).
+#[derive(Debug)]
+pub struct KeyedField<Inner, Prev, T, K>
+where
+    Inner: StoreField<Value = Prev>,
+{
+    #[cfg(debug_assertions)]
+    defined_at: &'static Location<'static>,
+    path_segment: StorePathSegment,
+    inner: Inner,
+    read: fn(&Prev) -> &T,
+    write: fn(&mut Prev) -> &mut T,
+    find: fn(&T, &K) -> Option<usize>,
+    keys: fn(&T) -> Vec<K>,
+    ty: PhantomData<T>,
+}
+
+impl<Inner, Prev, T, K> Clone for KeyedField<Inner, Prev, T, K>
+where
+    Inner: StoreField<Value = Prev> + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            #[cfg(debug_assertions)]
+            defined_at: self.defined_at,
+            path_segment: self.path_segment,
+            inner: self.inner.clone(),
+            read: self.read,
+            write: self.write,
+            find: self.find,
+            keys: self.keys,
+            ty: self.ty,
+        }
+    }
+}
+
+impl<Inner, Prev, T, K> Copy for KeyedField<Inner, Prev, T, K> where
+    Inner: StoreField<Value = Prev> + Copy
+{
+}
+
+impl<Inner, Prev, T, K> KeyedField<Inner, Prev, T, K>
+where
+    Inner: StoreField<Value = Prev>,
+{
+    /// Creates a keyed field. `find` returns the position of the item with a given key, and
+    /// `keys` returns the keys of all the items, in order.
+    #[track_caller]
+    pub fn new(
+        inner: Inner,
+        path_segment: StorePathSegment,
+        read: fn(&Prev) -> &T,
+        write: fn(&mut Prev) -> &mut T,
+        find: fn(&T, &K) -> Option<usize>,
+        keys: fn(&T) -> Vec<K>,
+    ) -> Self {
+        Self {
+            #[cfg(debug_assertions)]
+            defined_at: Location::caller(),
+            inner,
+            path_segment,
+            read,
+            write,
+            find,
+            keys,
+            ty: PhantomData,
+        }
+    }
+}
+
+impl<Inner, Prev, T, K> KeyedField<Inner, Prev, T, K>
+where
+    Inner: StoreField<Value = Prev> + Clone,
+    Prev: 'static,
+    T: IndexMut<usize> + 'static,
+    T::Output: Sized,
+    K: Hash + 'static,
+{
+    /// Returns the field for the item with the given key.
+    ///
+    /// Reading from it only subscribes to changes to that item, wherever it is in the
+    /// collection. If there is no item with that key, reading from it returns `None`.
+    #[track_caller]
+    pub fn at_key(self, key: K) -> AtKeyed<Inner, Prev, T, K> {
+        AtKeyed {
+            #[cfg(debug_assertions)]
+            defined_at: Location::caller(),
+            path_segment: key_segment(&key),
+            inner: self,
+            key,
+        }
+    }
+
+    /// Returns a field for each item in the collection, in order.
+    ///
+    /// This subscribes to changes to the collection as a whole, such as items being added,
+    /// removed or reordered, but not to changes to the items themselves.
+    pub fn iter_keyed(
+        self,
+    ) -> impl Iterator<Item = AtKeyed<Inner, Prev, T, K>> {
+        self.track_field();
+        let keys = self
+            .reader()
+            .map(|items| (self.keys)(&items))
+            .unwrap_or_default();
+        keys.into_iter().map(move |key| self.clone().at_key(key))
+    }
+}
+
+impl<Inner, Prev, T, K> StoreField for KeyedField<Inner, Prev, T, K>
+where
+    Inner: StoreField<Value = Prev>,
+    Prev: 'static,
+{
+    type Value = T;
+    type Reader = Mapped<Inner::Reader, T>;
+    type Writer = MappedMut<WriteGuard<StoreFieldTriggers, Inner::Writer>, T>;
+
+    fn path(&self) -> impl IntoIterator<Item = StorePathSegment> {
+        self.inner
+            .path()
+            .into_iter()
+            .chain(iter::once(self.path_segment))
+    }
+
+    fn get_trigger(&self, path: StorePath) -> StoreFieldTrigger {
+        self.inner.get_trigger(path)
+    }
+
+    fn reader(&self) -> Option<Self::Reader> {
+        let inner = self.inner.reader()?;
+        Some(Mapped::new_with_guard(inner, self.read))
+    }
+
+    fn writer(&self) -> Option<Self::Writer> {
+        let trigger = self.triggers_for_current_path();
+        // the triggers for the fields this one is nested in are included above
+        let mut inner = self.inner.writer()?;
+        inner.untrack();
+        let inner = WriteGuard::new(trigger, inner);
+        Some(MappedMut::new(inner, self.read, self.write))
+    }
+}
+
+impl<Inner, Prev, T, K> DefinedAt for KeyedField<Inner, Prev, T, K>
+where
+    Inner: StoreField<Value = Prev>,
+{
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(debug_assertions)]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            None
+        }
+    }
+}
+
+impl<Inner, Prev, T, K> IsDisposed for KeyedField<Inner, Prev, T, K>
+where
+    Inner: StoreField<Value = Prev> + IsDisposed,
+{
+    fn is_disposed(&self) -> bool {
+        self.inner.is_disposed()
+    }
+}
+
+impl<Inner, Prev, T, K> Trigger for KeyedField<Inner, Prev, T, K>
+where
+    Inner: StoreField<Value = Prev>,
+    Prev: 'static,
+{
+    fn trigger(&self) {
+        self.triggers_for_current_path().trigger();
+    }
+}
+
+impl<Inner, Prev, T, K> Track for KeyedField<Inner, Prev, T, K>
+where
+    Inner: StoreField<Value = Prev> + 'static,
+    Prev: 'static,
+    T: 'static,
+{
+    fn track(&self) {
+        self.track_field();
+    }
+}
+
+impl<Inner, Prev, T, K> ReadUntracked for KeyedField<Inner, Prev, T, K>
+where
+    Inner: StoreField<Value = Prev>,
+    Prev: 'static,
+{
+    type Value = <Self as StoreField>::Reader;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        self.reader()
+    }
+}
+
+impl<Inner, Prev, T, K> Writeable for KeyedField<Inner, Prev, T, K>
+where
+    T: 'static,
+    Inner: StoreField<Value = Prev>,
+    Prev: 'static,
+{
+    type Value = T;
+
+    fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
+        self.writer()
+    }
+
+    fn try_write_untracked(
+        &self,
+    ) -> Option<impl DerefMut<Target = Self::Value>> {
+        self.writer().map(|mut writer| {
+            writer.untrack();
+            writer
+        })
+    }
+}
+
+/// The field for a single item in a [`KeyedField`], as returned by
+/// [`at_key`](KeyedField::at_key).
+#[derive(Debug)]
+pub struct AtKeyed<Inner, Prev, T, K>
+where
+    Inner: StoreField<Value = Prev>,
+{
+    #[cfg(debug_assertions)]
+    defined_at: &'static Location<'static>,
+    path_segment: StorePathSegment,
+    inner: KeyedField<Inner, Prev, T, K>,
+    key: K,
+}
+
+impl<Inner, Prev, T, K> Clone for AtKeyed<Inner, Prev, T, K>
+where
+    Inner: StoreField<Value = Prev> + Clone,
+    K: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            #[cfg(debug_assertions)]
+            defined_at: self.defined_at,
+            path_segment: self.path_segment,
+            inner: self.inner.clone(),
+            key: self.key.clone(),
+        }
+    }
+}
+
+impl<Inner, Prev, T, K> Copy for AtKeyed<Inner, Prev, T, K>
+where
+    Inner: StoreField<Value = Prev> + Copy,
+    K: Copy,
+{
+}
+
+impl<Inner, Prev, T, K> AtKeyed<Inner, Prev, T, K>
+where
+    Inner: StoreField<Value = Prev>,
+{
+    /// The key of this item.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+}
+
+impl<Inner, Prev, T, K> StoreField for AtKeyed<Inner, Prev, T, K>
+where
+    Inner: StoreField<Value = Prev>,
+    Prev: 'static,
+    T: IndexMut<usize> + 'static,
+    T::Output: Sized,
+{
+    type Value = T::Output;
+    type Reader = MappedMutArc<Mapped<Inner::Reader, T>, T::Output>;
+    type Writer = MappedMutArc<
+        WriteGuard<
+            StoreFieldTriggers,
+            MappedMut<WriteGuard<StoreFieldTriggers, Inner::Writer>, T>,
+        >,
+        T::Output,
+    >;
+
+    fn path(&self) -> impl IntoIterator<Item = StorePathSegment> {
+        self.inner
+            .path()
+            .into_iter()
+            .chain(iter::once(self.path_segment))
+    }
+
+    fn get_trigger(&self, path: StorePath) -> StoreFieldTrigger {
+        self.inner.get_trigger(path)
+    }
+
+    fn reader(&self) -> Option<Self::Reader> {
+        let inner = self.inner.reader()?;
+        // the collection stays locked while the guard is held, so the index stays valid
+        let index = (self.inner.find)(&*inner, &self.key)?;
+        Some(MappedMutArc::new(
+            inner,
+            move |n| &n[index],
+            move |n| &mut n[index],
+        ))
+    }
+
+    fn writer(&self) -> Option<Self::Writer> {
+        let trigger = self.triggers_for_current_path();
+        let mut inner = self.inner.writer()?;
+        inner.untrack();
+        let index = (self.inner.find)(&*inner, &self.key)?;
+        let inner = WriteGuard::new(trigger, inner);
+        Some(MappedMutArc::new(
+            inner,
+            move |n| &n[index],
+            move |n| &mut n[index],
+        ))
+    }
+}
+
+impl<Inner, Prev, T, K> DefinedAt for AtKeyed<Inner, Prev, T, K>
+where
+    Inner: StoreField<Value = Prev>,
+{
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(debug_assertions)]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            None
+        }
+    }
+}
+
+impl<Inner, Prev, T, K> IsDisposed for AtKeyed<Inner, Prev, T, K>
+where
+    Inner: StoreField<Value = Prev> + IsDisposed,
+{
+    fn is_disposed(&self) -> bool {
+        self.inner.is_disposed()
+    }
+}
+
+impl<Inner, Prev, T, K> Trigger for AtKeyed<Inner, Prev, T, K>
+where
+    Inner: StoreField<Value = Prev>,
+    Prev: 'static,
+    T: IndexMut<usize> + 'static,
+    T::Output: Sized,
+{
+    fn trigger(&self) {
+        self.triggers_for_current_path().trigger();
+    }
+}
+
+impl<Inner, Prev, T, K> Track for AtKeyed<Inner, Prev, T, K>
+where
+    Inner: StoreField<Value = Prev> + 'static,
+    Prev: 'static,
+    T: IndexMut<usize> + 'static,
+    T::Output: Sized,
+    K: 'static,
+{
+    fn track(&self) {
+        self.track_field();
+    }
+}
+
+impl<Inner, Prev, T, K> ReadUntracked for AtKeyed<Inner, Prev, T, K>
+where
+    Inner: StoreField<Value = Prev>,
+    Prev: 'static,
+    T: IndexMut<usize> + 'static,
+    T::Output: Sized,
+{
+    type Value = <Self as StoreField>::Reader;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        self.reader()
+    }
+}
+
+impl<Inner, Prev, T, K> Writeable for AtKeyed<Inner, Prev, T, K>
+where
+    Inner: StoreField<Value = Prev>,
+    Prev: 'static,
+    T: IndexMut<usize> + 'static,
+    T::Output: Sized + 'static,
+{
+    type Value = T::Output;
+
+    fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
+        self.writer()
+    }
+
+    fn try_write_untracked(
+        &self,
+    ) -> Option<impl DerefMut<Target = Self::Value>> {
+        self.writer().map(|mut writer| {
+            writer.untrack();
+            writer
+        })
+    }
+}
+
+/// A map whose values can be looked up by key, such as a [`HashMap`] or [`BTreeMap`].
+pub trait KeyMap<K> {
+    /// The type of the values in the map.
+    type Value;
+
+    /// Returns a reference to the value with the given key.
+    fn get_by_key(&self, key: &K) -> Option<&Self::Value>;
+
+    /// Returns a mutable reference to the value with the given key.
+    fn get_by_key_mut(&mut self, key: &K) -> Option<&mut Self::Value>;
+}
+
+impl<K, V, S> KeyMap<K> for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    type Value = V;
+
+    fn get_by_key(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    fn get_by_key_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.get_mut(key)
+    }
+}
+
+impl<K, V> KeyMap<K> for BTreeMap<K, V>
+where
+    K: Ord,
+{
+    type Value = V;
+
+    fn get_by_key(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    fn get_by_key_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.get_mut(key)
+    }
+}
+
+/// Provides access to the values of a map field by key.
+pub trait StoreFieldMap<K, Prev>: StoreField<Value = Prev> {
+    /// Returns the field for the value with the given key.
+    ///
+    /// Reading from it only subscribes to changes to that value, or to the map as a whole being
+    /// replaced. Inserting or removing other keys through this field does not notify it. If
+    /// there is no value with that key, reading from it returns `None`.
+    fn at_key(self, key: K) -> AtKey<Self, Prev, K>;
+}
+
+impl<Inner, Prev, K> StoreFieldMap<K, Prev> for Inner
+where
+    Inner: StoreField<Value = Prev>,
+    Prev: KeyMap<K>,
+    K: Hash,
+{
+    #[track_caller]
+    fn at_key(self, key: K) -> AtKey<Inner, Prev, K> {
+        AtKey {
+            #[cfg(debug_assertions)]
+            defined_at: Location::caller(),
+            path_segment: key_segment(&key),
+            inner: self,
+            key,
+            ty: PhantomData,
+        }
+    }
+}
+
+/// The field for a single value in a map, as returned by [`at_key`](StoreFieldMap::at_key).
+#[derive(Debug)]
+pub struct AtKey<Inner, Prev, K>
+where
+    Inner: StoreField<Value = Prev>,
+{
+    #[cfg(debug_assertions)]
+    defined_at: &'static Location<'static>,
+    path_segment: StorePathSegment,
+    inner: Inner,
+    key: K,
+    ty: PhantomData<Prev>,
+}
+
+impl<Inner, Prev, K> Clone for AtKey<Inner, Prev, K>
+where
+    Inner: StoreField<Value = Prev> + Clone,
+    K: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            #[cfg(debug_assertions)]
+            defined_at: self.defined_at,
+            path_segment: self.path_segment,
+            inner: self.inner.clone(),
+            key: self.key.clone(),
+            ty: self.ty,
+        }
+    }
+}
+
+impl<Inner, Prev, K> Copy for AtKey<Inner, Prev, K>
+where
+    Inner: StoreField<Value = Prev> + Copy,
+    K: Copy,
+{
+}
+
+impl<Inner, Prev, K> AtKey<Inner, Prev, K>
+where
+    Inner: StoreField<Value = Prev>,
+{
+    /// The key of this value.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+}
+
+impl<Inner, Prev, K> StoreField for AtKey<Inner, Prev, K>
+where
+    Inner: StoreField<Value = Prev>,
+    Prev: KeyMap<K> + 'static,
+    K: Clone + 'static,
+{
+    type Value = Prev::Value;
+    type Reader = MappedMutArc<Inner::Reader, Prev::Value>;
+    type Writer = MappedMutArc<
+        WriteGuard<StoreFieldTriggers, Inner::Writer>,
+        Prev::Value,
+    >;
+
+    fn path(&self) -> impl IntoIterator<Item = StorePathSegment> {
+        self.inner
+            .path()
+            .into_iter()
+            .chain(iter::once(self.path_segment))
+    }
+
+    fn get_trigger(&self, path: StorePath) -> StoreFieldTrigger {
+        self.inner.get_trigger(path)
+    }
+
+    fn reader(&self) -> Option<Self::Reader> {
+        let inner = self.inner.reader()?;
+        inner.get_by_key(&self.key)?;
+        // the map stays locked while the guard is held, so the value cannot be removed
+        let (read_key, write_key) = (self.key.clone(), self.key.clone());
+        Some(MappedMutArc::new(
+            inner,
+            move |n| n.get_by_key(&read_key).expect("key was removed"),
+            move |n| n.get_by_key_mut(&write_key).expect("key was removed"),
+        ))
+    }
+
+    fn writer(&self) -> Option<Self::Writer> {
+        let trigger = self.triggers_for_current_path();
+        let mut inner = self.inner.writer()?;
+        inner.untrack();
+        inner.get_by_key(&self.key)?;
+        let inner = WriteGuard::new(trigger, inner);
+        let (read_key, write_key) = (self.key.clone(), self.key.clone());
+        Some(MappedMutArc::new(
+            inner,
+            move |n| n.get_by_key(&read_key).expect("key was removed"),
+            move |n| n.get_by_key_mut(&write_key).expect("key was removed"),
+        ))
+    }
+}
+
+impl<Inner, Prev, K> DefinedAt for AtKey<Inner, Prev, K>
+where
+    Inner: StoreField<Value = Prev>,
+{
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(debug_assertions)]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            None
+        }
+    }
+}
+
+impl<Inner, Prev, K> IsDisposed for AtKey<Inner, Prev, K>
+where
+    Inner: StoreField<Value = Prev> + IsDisposed,
+{
+    fn is_disposed(&self) -> bool {
+        self.inner.is_disposed()
+    }
+}
+
+impl<Inner, Prev, K> Trigger for AtKey<Inner, Prev, K>
+where
+    Inner: StoreField<Value = Prev>,
+    Prev: KeyMap<K> + 'static,
+    K: Clone + 'static,
+{
+    fn trigger(&self) {
+        self.triggers_for_current_path().trigger();
+    }
+}
+
+impl<Inner, Prev, K> Track for AtKey<Inner, Prev, K>
+where
+    Inner: StoreField<Value = Prev> + 'static,
+    Prev: KeyMap<K> + 'static,
+    K: Clone + 'static,
+{
+    fn track(&self) {
+        self.track_field();
+    }
+}
+
+impl<Inner, Prev, K> ReadUntracked for AtKey<Inner, Prev, K>
+where
+    Inner: StoreField<Value = Prev>,
+    Prev: KeyMap<K> + 'static,
+    K: Clone + 'static,
+{
+    type Value = <Self as StoreField>::Reader;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        self.reader()
+    }
+}
+
+impl<Inner, Prev, K> Writeable for AtKey<Inner, Prev, K>
+where
+    Inner: StoreField<Value = Prev>,
+    Prev: KeyMap<K> + 'static,
+    Prev::Value: 'static,
+    K: Clone + 'static,
+{
+    type Value = Prev::Value;
+
+    fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
+        self.writer()
+    }
+
+    fn try_write_untracked(
+        &self,
+    ) -> Option<impl DerefMut<Target = Self::Value>> {
+        self.writer().map(|mut writer| {
+            writer.untrack();
+            writer
+        })
+    }
+}
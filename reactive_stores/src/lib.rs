@@ -4,11 +4,15 @@ use reactive_graph::{
         guards::{Plain, ReadGuard},
         ArcTrigger,
     },
-    traits::{DefinedAt, IsDisposed, ReadUntracked, Track, Trigger},
+    traits::{
+        DefinedAt, IsDisposed, ReadUntracked, Track, Trigger, UntrackableGuard,
+        Writeable,
+    },
 };
 use rustc_hash::FxHashMap;
 use std::{
     fmt::Debug,
+    ops::DerefMut,
     panic::Location,
     sync::{Arc, RwLock},
 };
@@ -16,39 +20,54 @@ use std::{
 mod arc_field;
 mod field;
 mod iter;
+mod keyed;
 mod patch;
 mod path;
+#[cfg(feature = "serde")]
+mod serde;
 mod store_field;
 mod subfield;
 
 pub use arc_field::ArcField;
 pub use field::Field;
 pub use iter::*;
+pub use keyed::*;
 pub use patch::*;
-use path::StorePath;
-pub use store_field::StoreField;
+pub use path::{StorePath, StorePathSegment};
+pub use store_field::{StoreField, StoreFieldTriggers};
 pub use subfield::Subfield;
 
 #[derive(Debug, Default)]
-struct TriggerMap(FxHashMap<StorePath, ArcTrigger>);
+struct TriggerMap(FxHashMap<StorePath, StoreFieldTrigger>);
 
 impl TriggerMap {
-    fn get_or_insert(&mut self, key: StorePath) -> ArcTrigger {
+    fn get_or_insert(&mut self, key: StorePath) -> StoreFieldTrigger {
         if let Some(trigger) = self.0.get(&key) {
             trigger.clone()
         } else {
-            let new = ArcTrigger::new();
+            let new = StoreFieldTrigger::default();
             self.0.insert(key, new.clone());
             new
         }
     }
 
     #[allow(unused)]
-    fn remove(&mut self, key: &StorePath) -> Option<ArcTrigger> {
+    fn remove(&mut self, key: &StorePath) -> Option<StoreFieldTrigger> {
         self.0.remove(key)
     }
 }
 
+/// The pair of triggers kept for each path in a store.
+///
+/// `this` is notified whenever the value at the path changes, including when one of its own
+/// fields is written to. `children` is only notified when the value at the path is written to
+/// as a whole, which means that every field nested inside it may have changed.
+#[derive(Debug, Clone, Default)]
+pub struct StoreFieldTrigger {
+    pub(crate) this: ArcTrigger,
+    pub(crate) children: ArcTrigger,
+}
+
 pub struct ArcStore<T> {
     #[cfg(debug_assertions)]
     defined_at: &'static Location<'static>,
@@ -123,14 +142,30 @@ where
 
 impl<T: 'static> Track for ArcStore<T> {
     fn track(&self) {
-        self.get_trigger(Default::default()).trigger();
+        self.track_field();
     }
 }
 
 impl<T: 'static> Trigger for ArcStore<T> {
     fn trigger(&self) {
-        self.get_trigger(self.path().into_iter().collect())
-            .trigger();
+        self.triggers_for_current_path().trigger();
+    }
+}
+
+impl<T: 'static> Writeable for ArcStore<T> {
+    type Value = T;
+
+    fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
+        self.writer()
+    }
+
+    fn try_write_untracked(
+        &self,
+    ) -> Option<impl DerefMut<Target = Self::Value>> {
+        self.writer().map(|mut writer| {
+            writer.untrack();
+            writer
+        })
     }
 }
 
@@ -247,17 +282,44 @@ where
     }
 }
 
+impl<T, S> Writeable for Store<T, S>
+where
+    T: 'static,
+    S: Storage<ArcStore<T>>,
+{
+    type Value = T;
+
+    fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
+        self.writer()
+    }
+
+    fn try_write_untracked(
+        &self,
+    ) -> Option<impl DerefMut<Target = Self::Value>> {
+        self.writer().map(|mut writer| {
+            writer.untrack();
+            writer
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{self as reactive_stores, Patch, Store, StoreFieldIterator};
+    use crate::{
+        self as reactive_stores, Patch, Store, StoreFieldIterator,
+        StoreFieldMap,
+    };
     use reactive_graph::{
         effect::Effect,
         traits::{Read, ReadUntracked, Set, Update, Writeable},
     };
     use reactive_stores_macro::{Patch, Store};
-    use std::sync::{
-        atomic::{AtomicUsize, Ordering},
-        Arc,
+    use std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
     };
 
     pub async fn tick() {
@@ -285,6 +347,38 @@ mod tests {
         }
     }
 
+    #[derive(Debug, Store)]
+    struct Rows {
+        #[store(key = id: usize)]
+        rows: Vec<Row>,
+        names: HashMap<String, String>,
+    }
+
+    #[derive(Debug, Store)]
+    struct Row {
+        id: usize,
+        label: String,
+    }
+
+    fn rows() -> Rows {
+        Rows {
+            rows: vec![
+                Row {
+                    id: 1,
+                    label: "one".to_string(),
+                },
+                Row {
+                    id: 2,
+                    label: "two".to_string(),
+                },
+            ],
+            names: HashMap::from([
+                ("a".to_string(), "Alice".to_string()),
+                ("b".to_string(), "Bob".to_string()),
+            ]),
+        }
+    }
+
     fn data() -> Todos {
         Todos {
             user: "Bob".to_string(),
@@ -463,4 +557,177 @@ mod tests {
         tick().await;
         assert_eq!(combined_count.load(Ordering::Relaxed), 2);
     }
+
+    #[tokio::test]
+    async fn writing_a_field_only_notifies_fields_it_affects() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let combined_count = Arc::new(AtomicUsize::new(0));
+
+        let store = Store::new(data());
+        let first = store.todos().iter().next().unwrap();
+
+        Effect::new_sync({
+            let combined_count = Arc::clone(&combined_count);
+            move |_| {
+                println!("{:?}", *first.label().read());
+                combined_count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        tick().await;
+        let second = store.todos().iter().nth(1).unwrap();
+        second.label().set("Something else".into());
+        tick().await;
+        second.completed().set(true);
+        tick().await;
+        store.user().set("Greg".into());
+        tick().await;
+        // none of these are the first todo, or a field it is nested in
+        assert_eq!(combined_count.load(Ordering::Relaxed), 1);
+
+        // replacing the whole list may have changed every item in it
+        store.todos().write().insert(0, Todo::new("Start over"));
+        tick().await;
+        assert_eq!(combined_count.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn keyed_field_tracks_items_by_key() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let combined_count = Arc::new(AtomicUsize::new(0));
+
+        let store = Store::new(rows());
+
+        Effect::new_sync({
+            let combined_count = Arc::clone(&combined_count);
+            move |_| {
+                println!("{:?}", *store.rows().at_key(2).label().read());
+                combined_count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        tick().await;
+        store.rows().at_key(1).label().set("uno".into());
+        tick().await;
+        assert_eq!(combined_count.load(Ordering::Relaxed), 1);
+
+        store.rows().at_key(2).label().set("dos".into());
+        tick().await;
+        assert_eq!(combined_count.load(Ordering::Relaxed), 2);
+
+        // the item is found by its key, wherever it has moved to
+        store.rows().write().insert(
+            0,
+            Row {
+                id: 0,
+                label: "zero".into(),
+            },
+        );
+        tick().await;
+        assert_eq!(
+            store.rows().at_key(2).label().read_untracked().as_str(),
+            "dos"
+        );
+        assert_eq!(
+            store
+                .rows()
+                .iter_keyed()
+                .map(|row| *row.key())
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+        assert!(store
+            .rows()
+            .at_key(3)
+            .label()
+            .try_read_untracked()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn map_field_tracks_values_by_key() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let combined_count = Arc::new(AtomicUsize::new(0));
+
+        let store = Store::new(rows());
+
+        Effect::new_sync({
+            let combined_count = Arc::clone(&combined_count);
+            move |_| {
+                println!("{:?}", *store.names().at_key("a".to_string()).read());
+                combined_count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        tick().await;
+        store.names().at_key("b".to_string()).set("Carol".into());
+        tick().await;
+        assert_eq!(combined_count.load(Ordering::Relaxed), 1);
+
+        store
+            .names()
+            .at_key("a".to_string())
+            .update(|name| name.push_str("!!!"));
+        tick().await;
+        assert_eq!(combined_count.load(Ordering::Relaxed), 2);
+        assert_eq!(
+            store
+                .names()
+                .at_key("a".to_string())
+                .read_untracked()
+                .as_str(),
+            "Alice!!!"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[tokio::test]
+    async fn restoring_a_snapshot_notifies_fields() {
+        _ = any_spawner::Executor::init_tokio();
+
+        #[derive(Debug, Store, serde::Serialize, serde::Deserialize)]
+        struct Settings {
+            theme: String,
+            volume: u8,
+        }
+
+        let combined_count = Arc::new(AtomicUsize::new(0));
+
+        let store = Store::new(Settings {
+            theme: "dark".into(),
+            volume: 5,
+        });
+        let snapshot = store.snapshot().unwrap();
+        assert_eq!(
+            snapshot,
+            serde_json::json!({ "theme": "dark", "volume": 5 })
+        );
+
+        Effect::new_sync({
+            let combined_count = Arc::clone(&combined_count);
+            move |_| {
+                println!("{:?}", *store.theme().read());
+                combined_count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        tick().await;
+        store.theme().set("light".into());
+        store.volume().set(11);
+        tick().await;
+        assert_eq!(combined_count.load(Ordering::Relaxed), 2);
+
+        store.restore(snapshot).unwrap();
+        tick().await;
+        assert_eq!(combined_count.load(Ordering::Relaxed), 3);
+        assert_eq!(store.theme().read_untracked().as_str(), "dark");
+        assert_eq!(*store.volume().read_untracked(), 5);
+
+        // an invalid snapshot leaves the store unchanged
+        assert!(store.restore(serde_json::json!({ "theme": 1 })).is_err());
+        assert_eq!(store.theme().read_untracked().as_str(), "dark");
+
+        // a value that cannot be represented as JSON returns an error
+        let map = std::collections::HashMap::from([((1, 2), 3)]);
+        assert!(crate::ArcStore::new(map).snapshot().is_err());
+    }
 }
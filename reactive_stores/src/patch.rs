@@ -27,13 +27,12 @@ where
     type Value = T::Value;
 
     fn patch(&self, new: Self::Value) {
-        let path = StorePath::default();
+        let path = self.path().into_iter().collect::<StorePath>();
         if let Some(mut writer) = self.writer() {
-            // don't track the writer for the whole store
+            // don't notify for the whole field, only for the paths that have changed
             writer.untrack();
             let mut notify = |path: &StorePath| {
-                println!("notifying on {path:?}");
-                self.get_trigger(path.to_owned()).trigger();
+                self.triggers_for_path(path.to_owned()).trigger();
             };
             writer.patch_field(new, &path, &mut notify);
         }
@@ -0,0 +1,130 @@
+use crate::{ArcStore, Store, StoreField};
+use reactive_graph::{
+    owner::{Storage, SyncStorage},
+    traits::{ReadUntracked, With},
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+impl<T> Serialize for ArcStore<T>
+where
+    T: Serialize + 'static,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.with(|value| value.serialize(serializer))
+    }
+}
+
+impl<T, St> Serialize for Store<T, St>
+where
+    T: Serialize + 'static,
+    St: Storage<ArcStore<T>>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.with(|value| value.serialize(serializer))
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for ArcStore<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(ArcStore::new)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Store<T, SyncStorage>
+where
+    T: Send + Sync + Deserialize<'de> + 'static,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(Store::new)
+    }
+}
+
+impl<T> ArcStore<T>
+where
+    T: 'static,
+{
+    /// Returns a JSON snapshot of the current state of the store, without tracking it.
+    ///
+    /// This can be embedded in a server-rendered page and passed to [`restore`](Self::restore)
+    /// during hydration, or saved so that the state can be recovered later.
+    ///
+    /// Returns an error if the value cannot be represented as JSON, such as a map with keys that
+    /// are not strings or numbers.
+    pub fn snapshot(&self) -> Result<serde_json::Value, serde_json::Error>
+    where
+        T: Serialize,
+    {
+        serde_json::to_value(&*self.read_untracked())
+    }
+
+    /// Replaces the state of the store with a snapshot taken by [`snapshot`](Self::snapshot),
+    /// notifying every field.
+    ///
+    /// If the snapshot cannot be deserialized, the store is left unchanged.
+    pub fn restore(
+        &self,
+        snapshot: serde_json::Value,
+    ) -> Result<(), serde_json::Error>
+    where
+        T: DeserializeOwned,
+    {
+        restore(self, snapshot)
+    }
+}
+
+impl<T, S> Store<T, S>
+where
+    T: 'static,
+    S: Storage<ArcStore<T>>,
+{
+    /// Returns a JSON snapshot of the current state of the store, without tracking it.
+    ///
+    /// See [`ArcStore::snapshot`].
+    pub fn snapshot(&self) -> Result<serde_json::Value, serde_json::Error>
+    where
+        T: Serialize,
+    {
+        serde_json::to_value(&*self.read_untracked())
+    }
+
+    /// Replaces the state of the store with a snapshot taken by [`snapshot`](Self::snapshot),
+    /// notifying every field.
+    ///
+    /// See [`ArcStore::restore`].
+    pub fn restore(
+        &self,
+        snapshot: serde_json::Value,
+    ) -> Result<(), serde_json::Error>
+    where
+        T: DeserializeOwned,
+    {
+        restore(self, snapshot)
+    }
+}
+
+fn restore<F>(
+    store: &F,
+    snapshot: serde_json::Value,
+) -> Result<(), serde_json::Error>
+where
+    F: StoreField,
+    F::Value: DeserializeOwned,
+{
+    let value = serde_json::from_value(snapshot)?;
+    if let Some(mut writer) = store.writer() {
+        *writer = value;
+    }
+    Ok(())
+}
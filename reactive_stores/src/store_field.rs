@@ -1,6 +1,6 @@
 use crate::{
     path::{StorePath, StorePathSegment},
-    ArcStore, Store,
+    ArcStore, Store, StoreFieldTrigger,
 };
 use guardian::ArcRwLockWriteGuardian;
 use or_poisoned::OrPoisoned;
@@ -10,7 +10,7 @@ use reactive_graph::{
         guards::{Plain, WriteGuard},
         ArcTrigger,
     },
-    traits::{DefinedAt, UntrackableGuard},
+    traits::{DefinedAt, Track, Trigger, UntrackableGuard},
     unwrap_signal,
 };
 use std::{iter, ops::Deref, sync::Arc};
@@ -20,13 +20,55 @@ pub trait StoreField: Sized {
     type Reader: Deref<Target = Self::Value>;
     type Writer: UntrackableGuard<Target = Self::Value>;
 
-    fn get_trigger(&self, path: StorePath) -> ArcTrigger;
+    fn get_trigger(&self, path: StorePath) -> StoreFieldTrigger;
 
     fn path(&self) -> impl IntoIterator<Item = StorePathSegment>;
 
     fn reader(&self) -> Option<Self::Reader>;
 
     fn writer(&self) -> Option<Self::Writer>;
+
+    /// Subscribes to changes to this field.
+    ///
+    /// This tracks the field itself, which is notified when it or any of its own fields are
+    /// written to, and each of the fields it is nested in, but only for when those are replaced
+    /// as a whole. Writing to a sibling of this field does not notify it.
+    fn track_field(&self) {
+        let mut path = self.path().into_iter().collect::<StorePath>();
+        self.get_trigger(path.clone()).this.track();
+        while path.pop().is_some() {
+            self.get_trigger(path.clone()).children.track();
+        }
+    }
+
+    /// Returns the triggers that should be notified when the value at `path` is written to.
+    fn triggers_for_path(&self, path: StorePath) -> StoreFieldTriggers {
+        let mut path = path;
+        let trigger = self.get_trigger(path.clone());
+        let mut triggers = vec![trigger.this, trigger.children];
+        while path.pop().is_some() {
+            triggers.push(self.get_trigger(path.clone()).this);
+        }
+        StoreFieldTriggers(triggers)
+    }
+
+    /// Returns the triggers that should be notified when this field is written to.
+    fn triggers_for_current_path(&self) -> StoreFieldTriggers {
+        self.triggers_for_path(self.path().into_iter().collect())
+    }
+}
+
+/// The set of triggers notified when a field is written to: the field itself, every field nested
+/// inside it, and each of the fields it is nested in.
+#[derive(Debug, Clone)]
+pub struct StoreFieldTriggers(Vec<ArcTrigger>);
+
+impl Trigger for StoreFieldTriggers {
+    fn trigger(&self) {
+        for trigger in &self.0 {
+            trigger.trigger();
+        }
+    }
 }
 
 impl<T> StoreField for ArcStore<T>
@@ -35,9 +77,9 @@ where
 {
     type Value = T;
     type Reader = Plain<T>;
-    type Writer = WriteGuard<ArcTrigger, ArcRwLockWriteGuardian<T>>;
+    type Writer = WriteGuard<StoreFieldTriggers, ArcRwLockWriteGuardian<T>>;
 
-    fn get_trigger(&self, path: StorePath) -> ArcTrigger {
+    fn get_trigger(&self, path: StorePath) -> StoreFieldTrigger {
         let triggers = &self.signals;
         let trigger = triggers.write().or_poisoned().get_or_insert(path);
         trigger
@@ -52,7 +94,7 @@ where
     }
 
     fn writer(&self) -> Option<Self::Writer> {
-        let trigger = self.get_trigger(Default::default());
+        let trigger = self.triggers_for_current_path();
         let guard =
             ArcRwLockWriteGuardian::take(Arc::clone(&self.value)).ok()?;
         Some(WriteGuard::new(trigger, guard))
@@ -66,9 +108,9 @@ where
 {
     type Value = T;
     type Reader = Plain<T>;
-    type Writer = WriteGuard<ArcTrigger, ArcRwLockWriteGuardian<T>>;
+    type Writer = WriteGuard<StoreFieldTriggers, ArcRwLockWriteGuardian<T>>;
 
-    fn get_trigger(&self, path: StorePath) -> ArcTrigger {
+    fn get_trigger(&self, path: StorePath) -> StoreFieldTrigger {
         self.inner
             .try_get_value()
             .map(|n| n.get_trigger(path))
@@ -1,12 +1,10 @@
 use crate::{
     path::{StorePath, StorePathSegment},
-    store_field::StoreField,
+    store_field::{StoreField, StoreFieldTriggers},
+    StoreFieldTrigger,
 };
 use reactive_graph::{
-    signal::{
-        guards::{Mapped, MappedMut, WriteGuard},
-        ArcTrigger,
-    },
+    signal::guards::{Mapped, MappedMut, WriteGuard},
     traits::{
         DefinedAt, IsDisposed, ReadUntracked, Track, Trigger, UntrackableGuard,
         Writeable,
@@ -80,7 +78,7 @@ where
 {
     type Value = T;
     type Reader = Mapped<Inner::Reader, T>;
-    type Writer = MappedMut<WriteGuard<ArcTrigger, Inner::Writer>, T>;
+    type Writer = MappedMut<WriteGuard<StoreFieldTriggers, Inner::Writer>, T>;
 
     fn path(&self) -> impl IntoIterator<Item = StorePathSegment> {
         self.inner
@@ -89,7 +87,7 @@ where
             .chain(iter::once(self.path_segment))
     }
 
-    fn get_trigger(&self, path: StorePath) -> ArcTrigger {
+    fn get_trigger(&self, path: StorePath) -> StoreFieldTrigger {
         self.inner.get_trigger(path)
     }
 
@@ -99,8 +97,11 @@ where
     }
 
     fn writer(&self) -> Option<Self::Writer> {
-        let trigger = self.get_trigger(self.path().into_iter().collect());
-        let inner = WriteGuard::new(trigger, self.inner.writer()?);
+        let trigger = self.triggers_for_current_path();
+        // the triggers for the fields this one is nested in are included above
+        let mut inner = self.inner.writer()?;
+        inner.untrack();
+        let inner = WriteGuard::new(trigger, inner);
         Some(MappedMut::new(inner, self.read, self.write))
     }
 }
@@ -136,8 +137,7 @@ where
     Prev: 'static,
 {
     fn trigger(&self) {
-        let trigger = self.get_trigger(self.path().into_iter().collect());
-        trigger.trigger();
+        self.triggers_for_current_path().trigger();
     }
 }
 
@@ -148,8 +148,7 @@ where
     T: 'static,
 {
     fn track(&self) {
-        let trigger = self.get_trigger(self.path().into_iter().collect());
-        trigger.track();
+        self.track_field();
     }
 }
 
@@ -110,15 +110,21 @@ fn field_to_tokens(
         if modes.len() == 1 {
             let mode = &modes[0];
             // Can replace with a match if additional modes added
-            // TODO keyed_by
-            let SubfieldMode::Keyed(_keyed_by, key_ty) = mode;
+            let SubfieldMode::Keyed(keyed_by, key_ty) = mode;
             let signature = quote! {
                 fn #ident(self) ->  #library_path::KeyedField<#any_store_field, #struct_name #generics, #ty, #key_ty>
             };
             return if include_body {
                 quote! {
                     #signature {
-                        todo!()
+                        #library_path::KeyedField::new(
+                            self,
+                            #idx.into(),
+                            |prev| &prev.#locator,
+                            |prev| &mut prev.#locator,
+                            |items, key| items.iter().position(|item| &item.#keyed_by == key),
+                            |items| items.iter().map(|item| item.#keyed_by.clone()).collect(),
+                        )
                     }
                 }
             } else {
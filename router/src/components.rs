@@ -1,20 +1,23 @@
-pub use super::{form::*, link::*};
+pub use super::{error_page::*, form::*, link::*};
 #[cfg(feature = "ssr")]
 use crate::location::RequestUrl;
 pub use crate::nested_router::Outlet;
 use crate::{
     flat_router::FlatRoutesView,
-    hooks::use_navigate,
+    hooks::RoutePrefetcher,
     location::{
         BrowserUrl, Location, LocationChange, LocationProvider, State, Url,
     },
     navigate::NavigateOptions,
     nested_router::NestedRoutesView,
     resolve_path::resolve_path,
-    ChooseView, MatchNestedRoutes, NestedRoute, Routes, SsrMode,
+    ChooseView, MatchNestedRoutes, NestedRoute, PrefetchedLoaders, Routes,
+    SitemapRoute, SsrMode, StaticRoute, TrailingSlash,
 };
+use any_spawner::Executor;
 use either_of::Either;
-use leptos::prelude::*;
+use leptos::{prelude::*, view_transition::start_view_transition};
+use or_poisoned::OrPoisoned;
 use reactive_graph::{
     owner::{provide_context, use_context, Owner},
     signal::ArcRwSignal,
@@ -23,9 +26,10 @@ use reactive_graph::{
 };
 use std::{
     borrow::Cow,
+    collections::HashSet,
     fmt::{Debug, Display},
     marker::PhantomData,
-    sync::Arc,
+    sync::{Arc, Mutex},
     time::Duration,
 };
 use tachys::{renderer::dom::Dom, view::any_view::AnyView};
@@ -56,10 +60,28 @@ pub fn Router<Chil>(
     /// A signal that will be set while the navigation process is underway.
     #[prop(optional, into)]
     set_is_routing: Option<SignalSetter<bool>>,
-    // TODO trailing slashes
-    ///// How trailing slashes should be handled in [`Route`] paths.
-    //#[prop(optional)]
-    //trailing_slash: TrailingSlash,
+    /// Whether to wrap each navigation in a [view transition](leptos::view_transition), so that
+    /// the browser animates from the old page to the new one. Defaults to `false`.
+    #[prop(optional)]
+    transition: bool,
+    /// If `true`, the router does not restore the scroll position when going back or forward in
+    /// the browser's history, so that the app can do it itself. Otherwise, the position is
+    /// remembered for each of the 100 most recently scrolled history entries, and in browsers
+    /// with the Navigation API, two entries with the same URL each restore their own. Defaults
+    /// to `false`.
+    #[prop(optional)]
+    manual_scroll_restoration: bool,
+    /// Whether paths that end with a slash, or paths that do not, are redirected to the other
+    /// form. See [`TrailingSlash`]. Defaults to [`TrailingSlash::Ignore`].
+    #[prop(optional)]
+    trailing_slash: TrailingSlash,
+    /// The URL at which the site is served, like `https://leptos.dev`, which is used by
+    /// [`use_canonical_url`](crate::hooks::use_canonical_url).
+    #[prop(optional, into)]
+    site_url: Option<Cow<'static, str>>,
+    /// The page shown when an error reaches a [`RouteErrorBoundary`]. See [`ErrorTemplate`].
+    #[prop(optional, into)]
+    error_template: Option<ErrorTemplate>,
     /// The `<Router/>` should usually wrap your whole page. It can contain
     /// any elements, and should include a [`Routes`](crate::Routes) component somewhere
     /// to define and display [`Route`](crate::Route)s.
@@ -69,37 +91,104 @@ where
     Chil: IntoView,
 {
     #[cfg(feature = "ssr")]
-    let current_url = {
+    let (current_url, state) = {
+        _ = manual_scroll_restoration;
         let req = use_context::<RequestUrl>().expect("no RequestUrl provided");
         let parsed = req.parse().expect("could not parse RequestUrl");
-        ArcRwSignal::new(parsed)
+        (ArcRwSignal::new(parsed), ArcRwSignal::new(State::new(None)))
     };
 
     #[cfg(not(feature = "ssr"))]
-    let current_url = {
-        let location =
+    let (current_url, state) = {
+        let mut location =
             BrowserUrl::new().expect("could not access browser navigation"); // TODO options here
+        location.transition = transition;
+        location.manual_scroll_restoration = manual_scroll_restoration;
         location.init(base.clone());
         provide_context(location.clone());
-        location.as_url().clone()
+        (location.as_url().clone(), location.state.clone())
     };
     // provide router context
-    let state = ArcRwSignal::new(State::new(None));
     let location = Location::new(current_url.read_only(), state.read_only());
 
-    // TODO server function redirect hook
-    provide_context(RouterContext {
+    let router = RouterContext {
         base,
         current_url,
         location,
         state,
         set_is_routing,
-    });
+        transition,
+        trailing_slash,
+        site_url,
+    };
+
+    // redirects to the canonical form of the path
+    if trailing_slash != TrailingSlash::Ignore {
+        #[cfg(feature = "ssr")]
+        {
+            let url = router.current_url.read_untracked();
+            let path = trailing_slash.normalize(url.path());
+            if path != url.path() {
+                redirect(&canonical_location(&path, &url));
+            }
+        }
+        #[cfg(not(feature = "ssr"))]
+        {
+            let router = router.clone();
+            Effect::new(move |_| {
+                let url = router.current_url.read();
+                let path = trailing_slash.normalize(url.path());
+                if path != url.path() {
+                    let location = canonical_location(&path, &url);
+                    drop(url);
+                    router.navigate(
+                        &location,
+                        NavigateOptions {
+                            resolve: false,
+                            replace: true,
+                            scroll: false,
+                            ..Default::default()
+                        },
+                    );
+                }
+            });
+        }
+    }
+
+    // server functions that redirect from the browser navigate with the router
+    #[cfg(not(feature = "ssr"))]
+    {
+        let router = router.clone();
+        _ = leptos::server_fn::redirect::set_redirect_hook(move |loc: &str| {
+            router.navigate(loc, Default::default())
+        });
+    }
+
+    provide_context(router);
+    if let Some(error_template) = error_template {
+        provide_error_template(error_template);
+    }
 
     let children = children.into_inner();
     children()
 }
 
+/// The path, with the query and hash of the current URL.
+fn canonical_location(path: &str, url: &Url) -> String {
+    let mut location = path.to_string();
+    if !url.search().is_empty() {
+        location.push('?');
+        location.push_str(url.search());
+    }
+    if !url.hash().is_empty() {
+        if !url.hash().starts_with('#') {
+            location.push('#');
+        }
+        location.push_str(url.hash());
+    }
+    location
+}
+
 #[derive(Clone)]
 pub(crate) struct RouterContext {
     pub base: Option<Cow<'static, str>>,
@@ -107,6 +196,9 @@ pub(crate) struct RouterContext {
     pub location: Location,
     pub state: ArcRwSignal<State>,
     pub set_is_routing: Option<SignalSetter<bool>>,
+    pub transition: bool,
+    pub trailing_slash: TrailingSlash,
+    pub site_url: Option<Cow<'static, str>>,
 }
 
 impl RouterContext {
@@ -135,15 +227,28 @@ impl RouterContext {
             }
         };
 
-        // update state signal, if necessary
-        if options.state != self.state.get_untracked() {
-            self.state.set(options.state.clone());
-        }
+        let update = {
+            let state = self.state.clone();
+            let current_url = self.current_url.clone();
+            let new_state = options.state.clone();
+            let url_changed = *current != url;
+            move || {
+                // update state signal, if necessary
+                if new_state != state.get_untracked() {
+                    state.set(new_state);
+                }
 
-        // update URL signal, if necessary
-        if current != url {
-            drop(current);
-            self.current_url.set(url);
+                // update URL signal, if necessary
+                if url_changed {
+                    current_url.set(url);
+                }
+            }
+        };
+        drop(current);
+        if self.transition {
+            start_view_transition(update);
+        } else {
+            update();
         }
 
         BrowserUrl::complete_navigation(&LocationChange {
@@ -194,6 +299,8 @@ where
 
 #[component]
 pub fn Routes<Defs, FallbackFn, Fallback>(
+    /// The view shown when no route matches the path. When it is rendered on the server, the
+    /// response has a `404` status code.
     fallback: FallbackFn,
     children: RouteChildren<Defs>,
 ) -> impl IntoView
@@ -216,6 +323,7 @@ where
         base
     });
     let routes = Routes::new(children.into_inner());
+    provide_context(route_prefetcher(routes.clone(), current_url.clone()));
     let outer_owner =
         Owner::current().expect("creating Routes, but no Owner was found");
     move || {
@@ -238,6 +346,8 @@ where
 
 #[component]
 pub fn FlatRoutes<Defs, FallbackFn, Fallback>(
+    /// The view shown when no route matches the path. When it is rendered on the server, the
+    /// response has a `404` status code.
     fallback: FallbackFn,
     children: RouteChildren<Defs>,
 ) -> impl IntoView
@@ -263,6 +373,7 @@ where
         base
     });
     let routes = Routes::new(children.into_inner());
+    provide_context(route_prefetcher(routes.clone(), current_url.clone()));
 
     let outer_owner =
         Owner::current().expect("creating Router, but no Owner was found");
@@ -283,16 +394,58 @@ where
     }
 }
 
+fn route_prefetcher<Defs>(
+    routes: Routes<Defs, Dom>,
+    current_url: ArcRwSignal<Url>,
+) -> RoutePrefetcher
+where
+    Defs: MatchNestedRoutes<Dom> + Send + 'static,
+{
+    provide_context(PrefetchedLoaders::default());
+    // routes are prefetched in children of an owner that lives as long as the routes do, so that
+    // the data returned by their loaders can be used when they are entered
+    let owner = Owner::new();
+    let routes = Mutex::new(routes);
+    let prefetched = Mutex::new((String::new(), HashSet::new()));
+    RoutePrefetcher(Arc::new(move |path: &str| {
+        if cfg!(feature = "ssr") {
+            return;
+        }
+        {
+            let mut prefetched = prefetched.lock().or_poisoned();
+            let (page, paths) = &mut *prefetched;
+            // the data loaded by prefetching a link is used up when its route is entered, so
+            // links are prefetched again after navigating
+            let current = current_url.read_untracked();
+            if page != current.path() {
+                *page = current.path().to_string();
+                paths.clear();
+            }
+            if !paths.insert(path.to_string()) {
+                return;
+            }
+        }
+        let prefetch =
+            owner.with(|| routes.lock().or_poisoned().prefetch(path));
+        Executor::spawn_local(prefetch);
+    }))
+}
+
 #[component]
 pub fn Route<Segments, View>(
     path: Segments,
     view: View,
     #[prop(optional)] ssr: SsrMode,
+    #[prop(optional, into)] static_route: Option<StaticRoute>,
+    #[prop(optional, into)] sitemap: Option<SitemapRoute>,
 ) -> NestedRoute<Segments, (), (), View, Dom>
 where
     View: ChooseView<Dom>,
 {
-    NestedRoute::new(path, view).ssr_mode(ssr)
+    NestedRoute::new(path, view)
+        .ssr_mode(ssr)
+        .static_route(static_route)
+        .sitemap(sitemap)
 }
 
 #[component]
@@ -301,12 +454,18 @@ pub fn ParentRoute<Segments, View, Children>(
     view: View,
     children: RouteChildren<Children>,
     #[prop(optional)] ssr: SsrMode,
+    #[prop(optional, into)] static_route: Option<StaticRoute>,
+    #[prop(optional, into)] sitemap: Option<SitemapRoute>,
 ) -> NestedRoute<Segments, Children, (), View, Dom>
 where
     View: ChooseView<Dom>,
 {
     let children = children.into_inner();
-    NestedRoute::new(path, view).ssr_mode(ssr).child(children)
+    NestedRoute::new(path, view)
+        .ssr_mode(ssr)
+        .static_route(static_route)
+        .sitemap(sitemap)
+        .child(children)
 }
 
 #[component]
@@ -417,23 +576,105 @@ pub fn Redirect<P>(
 {
     // TODO resolve relative path
     let path = path.to_string();
+    redirect_with_options(&path, options.unwrap_or_default());
+}
+
+/// A page for paths that do not exist, which sets a `404` status code when it is rendered on
+/// the server.
+///
+/// The `fallback` of [`Routes`] and [`FlatRoutes`] sets the status on its own, so this is for
+/// pages that are matched by a route but still do not exist, like a catch-all route, or a user
+/// whose id is not in the database. It shows its children, or `"Not found."` if it has none. In
+/// the browser, navigating to the page renders it like any other, without reloading.
+///
+/// ```rust
+/// use leptos::prelude::*;
+/// use leptos_router::{
+///     components::{NotFound, Route, Router, Routes},
+///     path,
+/// };
+///
+/// #[component]
+/// fn App() -> impl IntoView {
+///     let not_found = || view! { <NotFound><h1>"Page not found"</h1></NotFound> };
+///     view! {
+///         <Router>
+///             <Routes fallback=not_found>
+///                 <Route path=path!("/") view=|| "Home"/>
+///                 <Route path=path!("/docs/*any") view=not_found/>
+///             </Routes>
+///         </Router>
+///     }
+/// }
+/// ```
+#[component]
+pub fn NotFound(
+    /// The contents of the page.
+    #[prop(optional)]
+    children: Option<Children>,
+) -> impl IntoView {
+    set_not_found_status();
+    match children {
+        Some(children) => Either::Left(children()),
+        None => Either::Right("Not found."),
+    }
+}
+
+/// Sets a `404` status code on the response, unless another status, like that of a redirect,
+/// has already been set.
+pub(crate) fn set_not_found_status() {
+    if let Some(res) = leptos::response::use_response_meta() {
+        if res.status().is_none() {
+            res.set_status(404);
+        }
+    }
+}
+
+/// Redirects the user to a new URL from a server function, a route loader, or any other code
+/// that runs with access to the reactive context.
+///
+/// On the server, this uses the function provided by [`provide_server_redirect`], which the
+/// server integrations provide both while rendering and while running a server function. When a
+/// server function that redirects is called from the browser, the client follows the redirect
+/// with the router rather than reloading the page. In the browser, this navigates with the
+/// router, or by setting `window.location` if it is called outside a [`Router`].
+///
+/// Server functions can call it anywhere in their body, including after an `.await`, because
+/// the reactive owner of the request, and with it the context, is carried across `.await`s.
+///
+/// ```rust
+/// use leptos::prelude::*;
+/// use leptos_router::components::redirect;
+///
+/// #[server]
+/// pub async fn log_out() -> Result<(), ServerFnError> {
+///     redirect("/login");
+///     Ok(())
+/// }
+/// ```
+pub fn redirect(path: &str) {
+    redirect_with_options(path, NavigateOptions::default())
+}
 
+fn redirect_with_options(path: &str, options: NavigateOptions) {
     // redirect on the server
     if let Some(redirect_fn) = use_context::<ServerRedirectFunction>() {
-        (redirect_fn.f)(&path);
+        (redirect_fn.f)(path);
     }
     // redirect on the client
     else {
         if cfg!(feature = "ssr") {
             #[cfg(feature = "tracing")]
             tracing::warn!(
-                "Calling <Redirect/> without a ServerRedirectFunction \
+                "Calling redirect() without a ServerRedirectFunction \
                  provided, in SSR mode."
             );
             return;
         }
-        let navigate = use_navigate();
-        navigate(&path, options.unwrap_or_default());
+        match use_context::<RouterContext>() {
+            Some(router) => router.navigate(path, options),
+            None => _ = window().location().set_href(path),
+        }
     }
 }
 
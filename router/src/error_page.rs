@@ -0,0 +1,126 @@
+use leptos::{
+    component,
+    error::{ErrorBoundary, Errors},
+    prelude::*,
+};
+use reactive_graph::owner::{provide_context, use_context};
+use std::sync::Arc;
+use tachys::{renderer::dom::Dom, view::any_view::AnyView};
+
+/// A page that is shown when an error reaches a [`RouteErrorBoundary`].
+///
+/// It is shown in place of the route, both in the browser and during server rendering, so the
+/// rest of the app (including anything it adds to the `<head>`) is rendered around it as usual.
+/// It can be set with the `error_template` of the [`Router`](crate::components::Router), or
+/// with [`provide_error_template`].
+#[derive(Clone)]
+pub struct ErrorTemplate(Arc<dyn Fn(Errors) -> AnyView<Dom> + Send + Sync>);
+
+impl ErrorTemplate {
+    /// Creates a template from a function that renders the errors.
+    pub fn new<F, V>(template: F) -> Self
+    where
+        F: Fn(Errors) -> V + Send + Sync + 'static,
+        V: IntoView + 'static,
+    {
+        Self(Arc::new(move |errors| template(errors).into_any()))
+    }
+
+    /// Renders the page for the errors.
+    pub fn render(&self, errors: Errors) -> AnyView<Dom> {
+        (self.0)(errors)
+    }
+}
+
+impl core::fmt::Debug for ErrorTemplate {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("ErrorTemplate").finish()
+    }
+}
+
+/// Provides the [`ErrorTemplate`] used by any [`RouteErrorBoundary`] below this point.
+pub fn provide_error_template(template: ErrorTemplate) {
+    provide_context(template);
+}
+
+/// An [`ErrorBoundary`] for a whole route, which shows the [`ErrorTemplate`] in context when an
+/// error is thrown inside it, or a list of the errors if there is none.
+///
+/// During server rendering, it also sets a `500` status code, unless another status has already
+/// been set.
+///
+/// Errors thrown while a [`Suspense`](leptos::suspense::Suspense) inside the boundary is
+/// loading may be thrown after the boundary has been rendered and the status code has been sent,
+/// in which case they do not reach it on the server. To show the template in place of content
+/// that is loaded in a `Suspense`, put a `RouteErrorBoundary` inside the `Suspense` as well; the
+/// status code will not change, as it has already been sent.
+///
+/// The template is rendered again whenever the errors change, for example when another error is
+/// thrown or one of them is cleared.
+///
+/// ```rust
+/// use leptos::prelude::*;
+/// use leptos_router::{
+///     components::{ErrorTemplate, Route, RouteErrorBoundary, Router, Routes},
+///     path,
+/// };
+///
+/// #[component]
+/// fn App() -> impl IntoView {
+///     let error_template = ErrorTemplate::new(|errors: Errors| {
+///         view! {
+///             <h1>"Something went wrong"</h1>
+///             <p>{errors.iter().count()} " errors"</p>
+///         }
+///     });
+///     view! {
+///         <Router error_template>
+///             <Routes fallback=|| "Not found.">
+///                 <Route path=path!("/") view=Home/>
+///             </Routes>
+///         </Router>
+///     }
+/// }
+///
+/// #[component]
+/// fn Home() -> impl IntoView {
+///     let count: Result<i32, std::num::ParseIntError> = "four".parse();
+///     view! {
+///         <RouteErrorBoundary>
+///             <p>"Count: " {count}</p>
+///         </RouteErrorBoundary>
+///     }
+/// }
+/// ```
+#[component]
+pub fn RouteErrorBoundary(
+    /// The contents of the route.
+    children: Children,
+) -> impl IntoView {
+    let template = use_context::<ErrorTemplate>();
+    let res = leptos::response::use_response_meta();
+    let fallback = move |errors: ArcRwSignal<Errors>| {
+        if let Some(res) = &res {
+            if res.status().is_none() {
+                res.set_status(500);
+            }
+        }
+        let template = template.clone();
+        move || {
+            let errors = errors.get();
+            match &template {
+                Some(template) => template.render(errors),
+                None => view! {
+                    <ul>
+                        {errors
+                            .into_iter()
+                            .map(|(_, error)| view! { <li>{error.to_string()}</li> })
+                            .collect::<Vec<_>>()}
+                    </ul>
+                }
+                .into_any(),
+            }
+        }
+    };
+    view! { <ErrorBoundary fallback>{children()}</ErrorBoundary> }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    components::set_not_found_status,
     location::{LocationProvider, Url},
     matching::Routes,
     params::ParamsMap,
@@ -440,7 +441,10 @@ where
                 .unwrap_or_default(),
         );
         let view = match new_match {
-            None => Either::Left((self.fallback)()),
+            None => {
+                self.outer_owner.with(set_not_found_status);
+                Either::Left((self.fallback)())
+            }
             Some(matched) => {
                 let (view, _) = matched.into_view_and_child();
                 let view = owner
@@ -511,9 +515,9 @@ where
                         data.ssr_mode,
                         // TODO methods
                         [Method::Get],
-                        // TODO static data
-                        None,
+                        data.static_route,
                     )
+                    .with_sitemap(data.sitemap)
                 })
                 .collect::<Vec<_>>();
 
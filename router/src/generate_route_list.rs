@@ -1,5 +1,6 @@
 use crate::{
-    matching::PathSegment, Method, SsrMode, StaticDataMap, StaticMode,
+    matching::PathSegment, Method, SitemapRoute, SsrMode, StaticDataMap,
+    StaticMode, StaticRoute,
 };
 use std::{
     cell::{Cell, RefCell},
@@ -13,7 +14,8 @@ pub struct RouteListing {
     path: Vec<PathSegment>,
     mode: SsrMode,
     methods: HashSet<Method>,
-    static_mode: Option<(StaticMode, StaticDataMap)>,
+    static_route: Option<StaticRoute>,
+    sitemap: Option<SitemapRoute>,
 }
 
 impl RouteListing {
@@ -22,16 +24,23 @@ impl RouteListing {
         path: impl IntoIterator<Item = PathSegment>,
         mode: SsrMode,
         methods: impl IntoIterator<Item = Method>,
-        static_mode: Option<(StaticMode, StaticDataMap)>,
+        static_route: Option<StaticRoute>,
     ) -> Self {
         Self {
             path: path.into_iter().collect(),
             mode,
             methods: methods.into_iter().collect(),
-            static_mode,
+            static_route,
+            sitemap: None,
         }
     }
 
+    /// Sets how this route is listed in the sitemap.
+    pub fn with_sitemap(mut self, sitemap: Option<SitemapRoute>) -> Self {
+        self.sitemap = sitemap;
+        self
+    }
+
     /// Create a route listing from a path, with the other fields set to default values.
     pub fn from_path(path: impl IntoIterator<Item = PathSegment>) -> Self {
         Self::new(path, SsrMode::Async, [], None)
@@ -55,50 +64,46 @@ impl RouteListing {
     /// Whether this route is statically rendered.
     #[inline(always)]
     pub fn static_mode(&self) -> Option<StaticMode> {
-        self.static_mode.as_ref().map(|n| n.0)
+        self.static_route.as_ref().map(StaticRoute::mode)
     }
 
-    /// Whether this route is statically rendered.
+    /// How this route is statically rendered, if it is.
+    pub fn static_route(&self) -> Option<&StaticRoute> {
+        self.static_route.as_ref()
+    }
+
+    /// The static data for this route, if it is statically rendered.
+    #[deprecated = "Static routes are now described by a `StaticRoute`. Use \
+                    `RouteListing::static_route` instead."]
     #[inline(always)]
     pub fn static_data_map(&self) -> Option<&StaticDataMap> {
-        self.static_mode.as_ref().map(|n| &n.1)
+        self.static_route.as_ref().map(|_| &StaticDataMap)
     }
 
-    pub fn into_static_parts(self) -> Option<(StaticMode, StaticDataMap)> {
-        self.static_mode
-    }
-
-    /*
-    /// Build a route statically, will return `Ok(true)` on success or `Ok(false)` when the route
-    /// is not marked as statically rendered. All route parameters to use when resolving all paths
-    /// to render should be passed in the `params` argument.
-    pub async fn build_static<IV>(
-        &self,
-        options: &LeptosOptions,
-        app_fn: impl Fn() -> IV + Send + 'static + Clone,
-        additional_context: impl Fn() + Send + 'static + Clone,
-        params: &StaticParamsMap,
-    ) -> Result<bool, std::io::Error>
-    where
-        IV: IntoView + 'static,
-    {
-        match self.static_mode {
-            None => Ok(false),
-            Some(_) => {
-                let mut path = StaticPath::new(&self.leptos_path);
-                path.add_params(params);
-                for path in path.into_paths() {
-                    path.write(
-                        options,
-                        app_fn.clone(),
-                        additional_context.clone(),
-                    )
-                    .await?;
-                }
-                Ok(true)
-            }
+    /// Returns every URL path that should be rendered to a static file for this route, or an
+    /// empty list if the route is not static.
+    pub async fn static_paths(&self) -> Vec<String> {
+        match &self.static_route {
+            Some(static_route) => static_route.to_paths(&self.path).await,
+            None => Vec::new(),
         }
-    }*/
+    }
+
+    /// How this route is listed in the sitemap, if it has been set.
+    pub fn sitemap(&self) -> Option<&SitemapRoute> {
+        self.sitemap.as_ref()
+    }
+
+    pub fn into_static_route(self) -> Option<StaticRoute> {
+        self.static_route
+    }
+
+    #[deprecated = "Static routes are now described by a `StaticRoute`. Use \
+                    `RouteListing::into_static_route` instead."]
+    pub fn into_static_parts(self) -> Option<(StaticMode, StaticDataMap)> {
+        self.static_route
+            .map(|static_route| (static_route.mode(), StaticDataMap::new()))
+    }
 }
 
 #[derive(Debug, Default)]
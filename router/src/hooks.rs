@@ -4,15 +4,19 @@ use crate::{
     navigate::NavigateOptions,
     params::{Params, ParamsError, ParamsMap},
 };
-use leptos::oco::Oco;
+use leptos::{
+    focus::focus_page_heading, leptos_dom::helpers::request_animation_frame,
+    oco::Oco,
+};
 use reactive_graph::{
     computed::{ArcMemo, Memo},
+    effect::Effect,
     owner::use_context,
     signal::{ArcRwSignal, ReadSignal},
-    traits::{Get, GetUntracked, With},
-    wrappers::write::SignalSetter,
+    traits::{Get, GetUntracked, Track, With, WithUntracked},
+    wrappers::{read::Signal, write::SignalSetter},
 };
-use std::str::FromStr;
+use std::{str::FromStr, sync::Arc};
 
 #[track_caller]
 #[deprecated = "This has been renamed to `query_signal` to match Rust naming \
@@ -155,6 +159,59 @@ pub fn use_location() -> Location {
     location
 }
 
+/// Returns the canonical URL of the current page: the site URL followed by the path, with the
+/// `trailing_slash` setting of the `<Router/>` applied and without the query or hash.
+///
+/// The site URL is the `site_url` of the `<Router/>`. Setting it is recommended, because
+/// otherwise the origin is taken from the `Host` header of the request during server rendering,
+/// which can be set by the client. In the browser it falls back to the origin of the page.
+///
+/// This can be used for the `<link rel="canonical">` of the page, which is rendered into the
+/// `<head>` during server rendering:
+///
+/// ```rust,ignore
+/// use leptos::prelude::*;
+/// use leptos_meta::Link;
+/// use leptos_router::hooks::use_canonical_url;
+///
+/// #[component]
+/// fn Canonical() -> impl IntoView {
+///     let url = use_canonical_url();
+///     move || view! { <Link rel="canonical" href=url.get()/> }
+/// }
+/// ```
+#[track_caller]
+pub fn use_canonical_url() -> Signal<String> {
+    let RouterContext {
+        current_url,
+        trailing_slash,
+        site_url,
+        ..
+    } = use_context()
+        .expect("Tried to access the canonical URL outside a <Router>.");
+    let origin = match site_url {
+        Some(site_url) => site_url.trim_end_matches('/').to_string(),
+        None => request_origin().unwrap_or_else(|| {
+            current_url.with_untracked(|url| url.origin().to_string())
+        }),
+    };
+    Signal::derive(move || {
+        current_url.with(|url| {
+            format!("{origin}{}", trailing_slash.normalize(url.path()))
+        })
+    })
+}
+
+/// The origin of the current request, from its `Host` header.
+fn request_origin() -> Option<String> {
+    let req = leptos::request::use_request_meta()?;
+    let host = req
+        .header("x-forwarded-host")
+        .or_else(|| req.header("host"))?;
+    let scheme = req.header("x-forwarded-proto").unwrap_or("http");
+    Some(format!("{scheme}://{host}"))
+}
+
 #[track_caller]
 fn use_params_raw() -> ArcRwSignal<ParamsMap> {
     use_context().expect(
@@ -171,6 +228,31 @@ pub fn use_params_map() -> Memo<ParamsMap> {
 }
 
 /// Returns the current route params, parsed into the given type, or an error.
+///
+/// The type is usually a struct that derives [`Params`]. Because the result is reactive, a view
+/// can return it directly, and a param that is missing or cannot be parsed is shown by the
+/// nearest `<ErrorBoundary/>`.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos::Params;
+/// # use leptos_router::hooks::use_params;
+/// #[derive(Params, Clone, PartialEq)]
+/// struct PostParams {
+///     id: u64,
+///     tab: Option<String>,
+/// }
+///
+/// #[component]
+/// fn Post() -> impl IntoView {
+///     let params = use_params::<PostParams>();
+///     view! {
+///         <ErrorBoundary fallback=|_| "Invalid post.">
+///             {move || params.get().map(|params| format!("Post #{}", params.id))}
+///         </ErrorBoundary>
+///     }
+/// }
+/// ```
 #[track_caller]
 pub fn use_params<T>() -> Memo<Result<T, ParamsError>>
 where
@@ -258,6 +340,43 @@ pub fn use_navigate() -> impl Fn(&str, NavigateOptions) + Clone {
     move |path: &str, options: NavigateOptions| cx.navigate(path, options)
 }
 
+/// Moves focus to the heading of the new page and announces it to screen readers whenever the
+/// path changes, using [`focus_page_heading`](leptos::focus::focus_page_heading).
+///
+/// Without this, focus stays on the link that was clicked, or is lost when that link is
+/// removed, and screen reader users are not told that the page has changed. Call it once,
+/// inside the `<Router/>`. Focus is not moved when the page first loads.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// # use leptos_router::{components::Router, hooks::use_focus_on_navigate};
+/// #[component]
+/// fn App() -> impl IntoView {
+///     view! {
+///         <Router>
+///             <FocusOnNavigate/>
+///             // <Routes/> ...
+///         </Router>
+///     }
+/// }
+///
+/// #[component]
+/// fn FocusOnNavigate() -> impl IntoView {
+///     use_focus_on_navigate();
+/// }
+/// ```
+#[track_caller]
+pub fn use_focus_on_navigate() {
+    let pathname = use_location().pathname;
+    Effect::new(move |prev: Option<()>| {
+        pathname.track();
+        if prev.is_some() {
+            // waits for the new page to be rendered
+            request_animation_frame(focus_page_heading);
+        }
+    });
+}
+
 /*
 /// Returns a signal that tells you whether you are currently navigating backwards.
 pub(crate) fn use_is_back_navigation() -> ReadSignal<bool> {
@@ -292,3 +411,19 @@ pub(crate) fn resolve_redirect_url(loc: &str) -> Option<web_sys::Url> {
     }
 }
 */
+
+/// Prefetches routes for [`prefetch_route`]. Provided by `<Routes/>` and `<FlatRoutes/>`.
+#[derive(Clone)]
+pub(crate) struct RoutePrefetcher(pub Arc<dyn Fn(&str) + Send + Sync>);
+
+/// Starts loading the code for any [`Lazy`](crate::Lazy) routes that match `path`, and calls
+/// their [data loaders](crate::with_loader), so that navigating to it is faster.
+///
+/// The data returned by a loader is kept until its route is entered with the same params. Each
+/// path is only prefetched once per page. This does nothing on the server, or outside of
+/// `<Routes/>` or `<FlatRoutes/>`.
+pub fn prefetch_route(path: &str) {
+    if let Some(RoutePrefetcher(prefetch)) = use_context() {
+        prefetch(path);
+    }
+}
@@ -1,8 +1,65 @@
+//! A router for Leptos applications, with nested routes that render into `<Outlet/>`s.
+//!
+//! Routes are defined as a tree inside [`Routes`](components::Routes). Each route's path is made
+//! of segments: static segments (`"users"`), params (`":id"`), and a trailing wildcard
+//! (`"*rest"`) that matches the rest of the URL. A [`ParentRoute`](components::ParentRoute)
+//! renders its own view, which shows the view of its matched child wherever it places an
+//! [`Outlet`](components::Outlet).
+//!
+//! ```
+//! use leptos::prelude::*;
+//! use leptos_router::{
+//!     components::{Outlet, ParentRoute, Route, Router, Routes},
+//!     hooks::use_params_map,
+//!     path,
+//! };
+//!
+//! #[component]
+//! fn App() -> impl IntoView {
+//!     view! {
+//!         <Router>
+//!             <Routes fallback=|| "Not found.">
+//!                 <ParentRoute path=path!("/users") view=Users>
+//!                     <Route path=path!("") view=|| "Pick a user."/>
+//!                     <Route path=path!(":id") view=User/>
+//!                 </ParentRoute>
+//!             </Routes>
+//!         </Router>
+//!     }
+//! }
+//!
+//! #[component]
+//! fn Users() -> impl IntoView {
+//!     view! {
+//!         <h1>"Users"</h1>
+//!         <Outlet/>
+//!     }
+//! }
+//!
+//! #[component]
+//! fn User() -> impl IntoView {
+//!     let params = use_params_map();
+//!     view! { <p>"User " {move || params.read().get("id")}</p> }
+//! }
+//! ```
+//!
+//! On the server (with the `ssr` feature), the route is matched against the
+//! [`RequestUrl`](location::RequestUrl) in context, and the matched views are rendered as part of
+//! the page, so out-of-order and in-order streaming work per route. In the browser, navigating
+//! only replaces the outlets whose matched route has changed. Views that are still matched are
+//! kept, and only the params and URL they read are updated.
+//!
+//! In an islands app, the router only runs on the server. Passing `islands_router=true` to
+//! [`HydrationScripts`](leptos::prelude::HydrationScripts) enables server-driven navigation:
+//! links fetch the new page's HTML and patch it into the document, keeping alive any islands
+//! that are in the same place on both pages, rather than doing a full page load.
+
 #![forbid(unsafe_code)]
 #![cfg_attr(feature = "nightly", feature(auto_traits))]
 #![cfg_attr(feature = "nightly", feature(negative_impls))]
 
 pub mod components;
+mod error_page;
 pub mod flat_router;
 mod form;
 mod generate_route_list;
@@ -15,8 +72,10 @@ mod navigate;
 pub mod nested_router;
 pub mod params;
 //mod router;
+mod sitemap;
 mod ssr_mode;
 mod static_route;
+mod trailing_slash;
 
 pub use generate_route_list::*;
 pub use leptos_router_macro::path;
@@ -24,5 +83,7 @@ pub use matching::*;
 pub use method::*;
 pub use navigate::*;
 //pub use router::*;
+pub use sitemap::*;
 pub use ssr_mode::*;
 pub use static_route::*;
+pub use trailing_slash::*;
@@ -1,8 +1,11 @@
 use crate::{
-    components::RouterContext, hooks::use_resolved_path, location::State,
+    components::RouterContext,
+    hooks::{prefetch_route, use_resolved_path},
+    location::State,
 };
 use leptos::{children::Children, oco::Oco, prelude::*, *};
 use reactive_graph::{computed::ArcMemo, owner::use_context};
+use send_wrapper::SendWrapper;
 use std::borrow::Cow;
 
 /// Describes a value that is either a static or a reactive URL, i.e.,
@@ -50,6 +53,18 @@ where
     }
 }
 
+/// When an [`<A/>`](A) should [prefetch](crate::hooks::prefetch_route) the route it links to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Prefetch {
+    /// The route is not prefetched.
+    #[default]
+    Never,
+    /// The route is prefetched when the pointer enters the link, or it is focused.
+    Hover,
+    /// The route is prefetched when the link first scrolls into view.
+    Visible,
+}
+
 /// An HTML [`a`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/a)
 /// progressively enhanced to use client-side routing.
 ///
@@ -60,6 +75,9 @@ where
 /// 2) Sets the `aria-current` attribute if this link is the active link (i.e., it’s a link to the page you’re on).
 ///    This is helpful for accessibility and for styling. For example, maybe you want to set the link a
 ///    different color if it’s a link to the page you’re currently on.
+///
+/// It can also [`prefetch`](Prefetch) the route before the link is clicked, which loads the code
+/// for a [`Lazy`](crate::Lazy) route and calls its [data loader](crate::with_loader).
 #[component]
 pub fn A<H>(
     /// Used to calculate the link's `href` attribute. Will be resolved relative
@@ -79,6 +97,9 @@ pub fn A<H>(
     /// will skip this page.)
     #[prop(optional)]
     replace: bool,
+    /// When to prefetch the linked route. Defaults to [`Prefetch::Never`].
+    #[prop(optional)]
+    prefetch: Prefetch,
     /// The nodes or elements to be shown inside the link.
     children: Children,
 ) -> impl IntoView
@@ -91,6 +112,7 @@ where
         exact: bool,
         #[allow(unused)] state: Option<State>,
         #[allow(unused)] replace: bool,
+        prefetch: Prefetch,
         children: Children,
     ) -> impl IntoView {
         let RouterContext { current_url, .. } =
@@ -113,8 +135,29 @@ where
             }
         });
 
+        let node_ref = NodeRef::<html::A>::new();
+        if prefetch == Prefetch::Visible {
+            prefetch_when_visible(node_ref, href.clone());
+        }
+        let on_hover = {
+            let href = href.clone();
+            move || {
+                if prefetch == Prefetch::Hover {
+                    if let Some(href) = href.get_untracked() {
+                        prefetch_route(&href);
+                    }
+                }
+            }
+        };
+
         view! {
             <a
+                node_ref=node_ref
+                on:pointerenter={
+                    let on_hover = on_hover.clone();
+                    move |_| on_hover()
+                }
+                on:focus=move |_| on_hover()
                 href=move || href.get().unwrap_or_default()
                 target=target
                 prop:state=state.map(|s| s.to_js_value())
@@ -131,5 +174,44 @@ where
     }
 
     let href = use_resolved_path(move || href.to_href()());
-    inner(href, target, exact, state, replace, children)
+    inner(href, target, exact, state, replace, prefetch, children)
+}
+
+fn prefetch_when_visible(
+    node_ref: NodeRef<html::A>,
+    href: ArcMemo<Option<String>>,
+) {
+    use wasm_bindgen::{closure::Closure, JsCast};
+    use web_sys::{IntersectionObserver, IntersectionObserverEntry};
+
+    node_ref.on_load(move |el| {
+        let callback =
+            Closure::<dyn Fn(js_sys::Array, IntersectionObserver)>::new(
+                move |entries: js_sys::Array,
+                      observer: IntersectionObserver| {
+                    let visible = entries.iter().any(|entry| {
+                        entry
+                            .unchecked_into::<IntersectionObserverEntry>()
+                            .is_intersecting()
+                    });
+                    if visible {
+                        observer.disconnect();
+                        if let Some(href) = href.get_untracked() {
+                            prefetch_route(&href);
+                        }
+                    }
+                },
+            );
+        let Ok(observer) =
+            IntersectionObserver::new(callback.as_ref().unchecked_ref())
+        else {
+            return;
+        };
+        observer.observe(&el);
+        let observer = SendWrapper::new((observer, callback));
+        Owner::on_cleanup(move || {
+            let (observer, _callback) = observer.take();
+            observer.disconnect();
+        });
+    });
 }
@@ -1,9 +1,11 @@
-use super::{handle_anchor_click, LocationChange, LocationProvider, Url};
+use super::{
+    handle_anchor_click, LocationChange, LocationProvider, State, Url,
+};
 use crate::params::ParamsMap;
 use core::fmt;
 use futures::channel::oneshot;
-use js_sys::{try_iter, Array, JsString};
-use or_poisoned::OrPoisoned;
+use js_sys::{try_iter, Array, JsString, Reflect};
+use leptos::view_transition::start_view_transition;
 use reactive_graph::{
     signal::ArcRwSignal,
     traits::{ReadUntracked, Set},
@@ -11,17 +13,63 @@ use reactive_graph::{
 use std::{
     borrow::Cow,
     boxed::Box,
+    cell::{Cell, RefCell},
+    collections::{HashMap, VecDeque},
     string::String,
-    sync::{Arc, Mutex},
 };
 use tachys::dom::{document, window};
 use wasm_bindgen::{closure::Closure, JsCast, JsValue};
-use web_sys::{Event, UrlSearchParams};
+use web_sys::{Event, ScrollRestoration, UrlSearchParams};
+
+thread_local! {
+    // the last scroll position of each history entry that has been visited, by its key
+    static SCROLL_POSITIONS: RefCell<ScrollPositions> = Default::default();
+    // the key of the history entry that is currently shown
+    static CURRENT_KEY: RefCell<String> = Default::default();
+    // whether a back/forward navigation is waiting to restore its scroll position
+    static RESTORE_PENDING: Cell<bool> = const { Cell::new(false) };
+    // completes a link navigation by updating the address bar, once its route has loaded
+    static PENDING_NAVIGATION: RefCell<Option<oneshot::Sender<()>>> = Default::default();
+}
+
+// the number of history entries whose scroll positions are remembered, after which the entry that
+// was scrolled the longest time ago is forgotten
+const MAX_SCROLL_POSITIONS: usize = 100;
+
+#[derive(Default)]
+struct ScrollPositions {
+    positions: HashMap<String, (f64, f64)>,
+    // the keys in `positions`, from the least to the most recently saved
+    order: VecDeque<String>,
+}
+
+impl ScrollPositions {
+    fn insert(&mut self, key: String, position: (f64, f64)) {
+        if self.positions.insert(key.clone(), position).is_some() {
+            self.order.retain(|k| k != &key);
+        } else if self.order.len() >= MAX_SCROLL_POSITIONS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.positions.remove(&oldest);
+            }
+        }
+        self.order.push_back(key);
+    }
+
+    fn get(&self, key: &str) -> Option<(f64, f64)> {
+        self.positions.get(key).copied()
+    }
+}
 
 #[derive(Clone)]
 pub struct BrowserUrl {
     url: ArcRwSignal<Url>,
-    pending_navigation: Arc<Mutex<Option<oneshot::Sender<()>>>>,
+    /// The history state of the current entry.
+    pub(crate) state: ArcRwSignal<State>,
+    /// Whether URL changes from links and back/forward navigation run in a view transition.
+    pub(crate) transition: bool,
+    /// Whether scroll positions are left to the app, rather than restored on back/forward
+    /// navigation.
+    pub(crate) manual_scroll_restoration: bool,
 }
 
 impl fmt::Debug for BrowserUrl {
@@ -31,6 +79,42 @@ impl fmt::Debug for BrowserUrl {
 }
 
 impl BrowserUrl {
+    fn set_url(url: &ArcRwSignal<Url>, new_url: Url, transition: bool) {
+        if transition {
+            let url = url.clone();
+            start_view_transition(move || url.set(new_url));
+        } else {
+            url.set(new_url);
+        }
+    }
+
+    fn save_scroll_position() {
+        // while a back/forward navigation is rendering, the page can scroll because its content
+        // changes, which should not overwrite the position that is about to be restored
+        if RESTORE_PENDING.get() {
+            return;
+        }
+        let window = window();
+        if let (Ok(x), Ok(y)) = (window.scroll_x(), window.scroll_y()) {
+            let key = CURRENT_KEY.with_borrow(Clone::clone);
+            SCROLL_POSITIONS.with_borrow_mut(|positions| {
+                positions.insert(key, (x, y));
+            });
+        }
+    }
+
+    fn restore_scroll_position() {
+        if !RESTORE_PENDING.replace(false) {
+            return;
+        }
+        let (x, y) = CURRENT_KEY
+            .with_borrow(|key| {
+                SCROLL_POSITIONS.with_borrow(|positions| positions.get(key))
+            })
+            .unwrap_or_default();
+        window().scroll_to_with_x_and_y(x, y);
+    }
+
     fn scroll_to_el(loc_scroll: bool) {
         if let Ok(hash) = window().location().hash() {
             if !hash.is_empty() {
@@ -58,10 +142,13 @@ impl LocationProvider for BrowserUrl {
 
     fn new() -> Result<Self, JsValue> {
         let url = ArcRwSignal::new(Self::current()?);
-        let pending_navigation = Default::default();
+        CURRENT_KEY.set(current_entry_key());
+        let state = ArcRwSignal::new(current_state()?);
         Ok(Self {
             url,
-            pending_navigation,
+            state,
+            transition: false,
+            manual_scroll_restoration: false,
         })
     }
 
@@ -107,21 +194,22 @@ impl LocationProvider for BrowserUrl {
         let window = window();
         let navigate = {
             let url = self.url.clone();
-            let pending = Arc::clone(&self.pending_navigation);
-            move |new_url: Url, loc| {
+            let state = self.state.clone();
+            let transition = self.transition;
+            move |new_url: Url, loc: LocationChange| {
+                state.set(loc.state.clone());
                 let same_path = {
                     let curr = url.read_untracked();
                     curr.origin() == new_url.origin()
                         && curr.path() == new_url.path()
                 };
-                url.set(new_url);
+                Self::set_url(&url, new_url, transition);
                 if same_path {
                     Self::complete_navigation(&loc);
                 }
-                let pending = Arc::clone(&pending);
                 let (tx, rx) = oneshot::channel::<()>();
                 if !same_path {
-                    *pending.lock().or_poisoned() = Some(tx);
+                    PENDING_NAVIGATION.set(Some(tx));
                 }
                 async move {
                     if !same_path {
@@ -156,11 +244,44 @@ impl LocationProvider for BrowserUrl {
                  clicks",
             );
 
+        if !self.manual_scroll_restoration {
+            if let Ok(history) = window.history() {
+                _ = history.set_scroll_restoration(ScrollRestoration::Manual);
+            }
+            let closure = Closure::wrap(
+                Box::new(Self::save_scroll_position) as Box<dyn Fn()>
+            )
+            .into_js_value();
+            window
+                .add_event_listener_with_callback(
+                    "scroll",
+                    closure.as_ref().unchecked_ref(),
+                )
+                .expect("couldn't add `scroll` listener to `window`");
+        }
+
         // handle popstate event (forward/back navigation)
         let cb = {
             let url = self.url.clone();
+            let state = self.state.clone();
+            let transition = self.transition;
+            let restore_scroll = !self.manual_scroll_restoration;
             move || match Self::current() {
-                Ok(new_url) => url.set(new_url),
+                Ok(new_url) => {
+                    CURRENT_KEY.set(current_entry_key());
+                    if let Ok(history_state) = current_state() {
+                        state.set(history_state);
+                    }
+                    let same_path =
+                        url.read_untracked().path() == new_url.path();
+                    RESTORE_PENDING.set(restore_scroll);
+                    Self::set_url(&url, new_url, transition);
+                    // the routes are only rendered again, and call ready_to_complete(), if the
+                    // path has changed
+                    if same_path {
+                        Self::restore_scroll_position();
+                    }
+                }
                 Err(e) => {
                     #[cfg(feature = "tracing")]
                     tracing::error!("{e:?}");
@@ -180,13 +301,17 @@ impl LocationProvider for BrowserUrl {
     }
 
     fn ready_to_complete(&self) {
-        if let Some(tx) = self.pending_navigation.lock().or_poisoned().take() {
+        if let Some(tx) = PENDING_NAVIGATION.take() {
             _ = tx.send(());
         }
+        Self::restore_scroll_position();
     }
 
     fn complete_navigation(loc: &LocationChange) {
         let history = window().history().unwrap();
+        // a new navigation replaces any link or back/forward navigation that was still loading
+        RESTORE_PENDING.set(false);
+        PENDING_NAVIGATION.take();
 
         if loc.replace {
             history
@@ -198,16 +323,44 @@ impl LocationProvider for BrowserUrl {
                 .unwrap();
         } else {
             // push the "forward direction" marker
-            let state = &loc.state.to_js_value();
             history
-                .push_state_with_url(state, "", Some(&loc.value))
+                .push_state_with_url(
+                    &loc.state.to_js_value(),
+                    "",
+                    Some(&loc.value),
+                )
                 .unwrap();
         }
+        CURRENT_KEY.set(current_entry_key());
         // scroll to el
         Self::scroll_to_el(loc.scroll);
     }
 }
 
+// scroll positions are keyed by the key the Navigation API gives each history entry, so that two
+// entries for the same URL each restore their own position, without storing anything in
+// `history.state`, which is left to the app
+//
+// browsers without the Navigation API fall back to the URL of the entry
+fn current_entry_key() -> String {
+    let win = window();
+    Reflect::get(&win, &"navigation".into())
+        .ok()
+        .filter(JsValue::is_object)
+        .and_then(|navigation| {
+            Reflect::get(&navigation, &"currentEntry".into()).ok()
+        })
+        .filter(JsValue::is_object)
+        .and_then(|entry| Reflect::get(&entry, &"key".into()).ok())
+        .and_then(|key| key.as_string())
+        .or_else(|| win.location().href().ok())
+        .unwrap_or_default()
+}
+
+fn current_state() -> Result<State, JsValue> {
+    Ok(State::new(Some(window().history()?.state()?)))
+}
+
 fn search_params_from_web_url(
     params: &web_sys::UrlSearchParams,
 ) -> Result<ParamsMap, JsValue> {
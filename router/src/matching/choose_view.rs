@@ -1,5 +1,25 @@
+use crate::{
+    components::{Redirect, RedirectProps},
+    hooks::use_navigate,
+    nested_router::PreviousUrl,
+    params::ParamsMap,
+    NavigateOptions,
+};
 use either_of::*;
-use std::{future::Future, marker::PhantomData};
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    effect::Effect,
+    owner::{provide_context, use_context, Owner},
+    signal::ArcRwSignal,
+    traits::{Get, GetUntracked, Set, WithUntracked},
+};
+use std::{
+    any::{Any, TypeId},
+    collections::{HashMap, VecDeque},
+    future::Future,
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+};
 use tachys::{
     renderer::Renderer,
     view::{any_view::AnyView, Render},
@@ -15,6 +35,14 @@ where
     fn choose(self) -> impl Future<Output = Self::Output>;
 
     fn preload(&self) -> impl Future<Output = ()>;
+
+    /// Starts loading anything this view needs before its route has been matched, such as the
+    /// code for a [`Lazy`] route, so that navigating to it later is faster.
+    ///
+    /// Does nothing by default.
+    fn prefetch(&self) -> impl Future<Output = ()> {
+        async {}
+    }
 }
 
 impl<F, View, R> ChooseView<R> for F
@@ -46,17 +74,64 @@ where
     async fn preload(&self) {
         T::data().view().await;
     }
+
+    async fn prefetch(&self) {
+        T::data().view().await;
+    }
 }
 
+/// A route whose view is loaded asynchronously. Used as the view of a route with [`Lazy`].
+///
+/// When the WASM binary is split into chunks at build time, the code that is only reachable from
+/// an `async fn view` is placed in its own chunk, which is only fetched the first time the route
+/// is matched.
 pub trait LazyRoute<R>: Send + 'static
 where
     R: Renderer,
 {
+    /// Creates the route's data. This is called each time the route is matched.
     fn data() -> Self;
 
+    /// Loads and renders the route's view.
     fn view(self) -> impl Future<Output = AnyView<R>>;
 }
 
+/// Marks a route's view as lazy-loaded, by wrapping a type that implements [`LazyRoute`].
+///
+/// During client-side navigation, the current page stays on screen while a lazy route is loading,
+/// and the router's `set_is_routing` signal is `true` until it has loaded, so it can be used to
+/// show a loading indicator. A lazy route can be fetched before it is needed with
+/// [`prefetch_route`](crate::hooks::prefetch_route), or by an [`<A/>`](crate::components::A) link
+/// with its `prefetch` prop set. Lazy routes are always loaded synchronously during server-side
+/// rendering and hydration.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos::tachys::{renderer::dom::Dom, view::any_view::AnyView};
+/// # use leptos_router::{components::*, path, Lazy, LazyRoute};
+/// struct Settings;
+///
+/// impl LazyRoute<Dom> for Settings {
+///     fn data() -> Self {
+///         Settings
+///     }
+///
+///     async fn view(self) -> AnyView<Dom> {
+///         view! { <h1>"Settings"</h1> }.into_any()
+///     }
+/// }
+///
+/// # if false {
+/// view! {
+///     <Router>
+///         <Routes fallback=|| "Not found.">
+///             <Route path=path!("/settings") view={Lazy::<Settings>::new()}/>
+///         </Routes>
+///     </Router>
+/// }
+/// # ;
+/// # }
+/// ```
 #[derive(Debug)]
 pub struct Lazy<T> {
     ty: PhantomData<T>,
@@ -82,6 +157,484 @@ impl<T> Default for Lazy<T> {
     }
 }
 
+/// A route view with a data loader, created by [`with_loader`] or
+/// [`NestedRoute::loader`](crate::NestedRoute::loader).
+#[derive(Debug, Clone)]
+pub struct WithLoader<L, V> {
+    loader: L,
+    view: V,
+}
+
+/// Attaches a data loader to a route's view.
+///
+/// When the route is matched, `loader` is called and the value it returns is provided to the
+/// view, which can read it by passing the same loader to [`use_loader_data`] or
+/// [`expect_loader_data`]. Because the data is looked up by its loader rather than by its type,
+/// loaders for different routes can return the same type. Loaders run in the route's own reactive
+/// scope, so they can read the route's params with [`use_params`](crate::hooks::use_params) and
+/// the data loaded by any parent route.
+///
+/// In a nested router, the loaders of every matched route are called when the URL is matched,
+/// before any of the route views are rendered, and before a client-side navigation completes. A
+/// loader that returns a resource therefore starts loading the data for a child route at the same
+/// time as the data for its parents, rather than waiting for the parent to render its
+/// `<Outlet/>`. Loaders are called again when a different route is matched, but not when only the
+/// params of the same route change, so data that depends on the params should be loaded by a
+/// resource that reads them.
+///
+/// When a link to the route is [prefetched](crate::hooks::prefetch_route), the loader is called
+/// ahead of time with the params of that link. The value it returns is used the next time the
+/// route is entered with the same params, instead of calling the loader again.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_router::{
+/// #     components::*, expect_loader_data, hooks::use_params_map, path, with_loader,
+/// # };
+/// #[derive(Clone)]
+/// struct UserData(ArcResource<String>);
+///
+/// fn load_user() -> UserData {
+///     let params = use_params_map();
+///     UserData(ArcResource::new(
+///         move || params.read().get("id").unwrap_or_default(),
+///         |id| async move { format!("User {id}") },
+///     ))
+/// }
+///
+/// #[component]
+/// fn User() -> impl IntoView {
+///     let UserData(user) = expect_loader_data(load_user);
+///     view! {
+///         <Suspense>
+///             {move || {
+///                 let user = user.clone();
+///                 Suspend::new(async move { user.await })
+///             }}
+///         </Suspense>
+///     }
+/// }
+///
+/// # if false {
+/// view! {
+///     <Router>
+///         <Routes fallback=|| "Not found.">
+///             <Route path=path!("/users/:id") view=with_loader(load_user, User)/>
+///         </Routes>
+///     </Router>
+/// }
+/// # ;
+/// # }
+/// ```
+pub fn with_loader<L, T, V>(loader: L, view: V) -> WithLoader<L, V>
+where
+    L: Fn() -> T + Send + Clone + 'static,
+    T: Clone + Send + Sync + 'static,
+{
+    WithLoader { loader, view }
+}
+
+// the data provided by a loader, which is keyed by the loader so that loaders returning the same
+// type do not replace each other's data
+struct LoaderData<L, T> {
+    data: T,
+    // the owner the data was provided in, so that a child route that uses the same loader still
+    // calls it, rather than finding its parent's data
+    owner: usize,
+    ty: PhantomData<fn() -> L>,
+}
+
+impl<L, T: Clone> Clone for LoaderData<L, T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            owner: self.owner,
+            ty: PhantomData,
+        }
+    }
+}
+
+/// Returns the data provided by `loader` for the current route, or for the nearest parent route
+/// that uses it, if it has been loaded. See [`with_loader`].
+pub fn use_loader_data<L, T>(loader: L) -> Option<T>
+where
+    L: Fn() -> T + 'static,
+    T: Clone + Send + Sync + 'static,
+{
+    _ = loader;
+    use_context::<LoaderData<L, T>>().map(|loaded| loaded.data)
+}
+
+/// Returns the data provided by `loader` for the current route, or for the nearest parent route
+/// that uses it. See [`with_loader`].
+///
+/// ## Panics
+/// Panics if the data has not been loaded, because no matched route uses `loader`.
+#[track_caller]
+pub fn expect_loader_data<L, T>(loader: L) -> T
+where
+    L: Fn() -> T + 'static,
+    T: Clone + Send + Sync + 'static,
+{
+    use_loader_data(loader).unwrap_or_else(|| {
+        panic!(
+            "no data was loaded by {} for this route",
+            std::any::type_name::<L>()
+        )
+    })
+}
+
+// the data returned by loaders while prefetching links, keyed by the loader and the params it
+// was called with, until their route is entered
+#[derive(Clone, Default)]
+pub(crate) struct PrefetchedLoaders(Arc<Mutex<Prefetched>>);
+
+// how many prefetched loaders keep their data before the least recently prefetched is dropped
+const MAX_PREFETCHED: usize = 32;
+
+type PrefetchKey = (TypeId, ParamsMap);
+
+#[derive(Default)]
+struct Prefetched {
+    data: HashMap<PrefetchKey, PrefetchedData>,
+    // least recently prefetched first
+    order: VecDeque<PrefetchKey>,
+}
+
+impl Prefetched {
+    fn take(&mut self, key: &PrefetchKey) -> Option<PrefetchedData> {
+        let data = self.data.remove(key)?;
+        self.order.retain(|k| k != key);
+        Some(data)
+    }
+
+    // marks the data as recently prefetched, returning whether there is any
+    fn touch(&mut self, key: &PrefetchKey) -> bool {
+        if !self.data.contains_key(key) {
+            return false;
+        }
+        if let Some(idx) = self.order.iter().position(|k| k == key) {
+            if let Some(key) = self.order.remove(idx) {
+                self.order.push_back(key);
+            }
+        }
+        true
+    }
+
+    // returns the data that was dropped to make room, if any
+    fn insert(
+        &mut self,
+        key: PrefetchKey,
+        data: PrefetchedData,
+    ) -> Option<PrefetchedData> {
+        let evicted = if self.data.len() >= MAX_PREFETCHED {
+            self.order
+                .pop_front()
+                .and_then(|oldest| self.data.remove(&oldest))
+        } else {
+            None
+        };
+        self.order.push_back(key.clone());
+        self.data.insert(key, data);
+        evicted
+    }
+}
+
+struct PrefetchedData {
+    data: Box<dyn Any + Send + Sync>,
+    params: ArcRwSignal<ParamsMap>,
+    // anything the loader created belongs to the owner it was called in, so the owner is kept
+    // alive for as long as the data is
+    owner: Owner,
+}
+
+impl<L, T, V> WithLoader<L, V>
+where
+    L: Fn() -> T + Send + Clone + 'static,
+    T: Clone + Send + Sync + 'static,
+{
+    fn load(&self) {
+        let owner = Owner::current().map(|owner| owner.debug_id());
+        if use_context::<LoaderData<L, T>>()
+            .is_some_and(|loaded| Some(loaded.owner) == owner)
+        {
+            return;
+        }
+        let data = match self.take_prefetched() {
+            Some(data) => data,
+            None => (self.loader)(),
+        };
+        provide_context(LoaderData::<L, T> {
+            data,
+            owner: owner.unwrap_or_default(),
+            ty: PhantomData,
+        });
+    }
+
+    fn take_prefetched(&self) -> Option<T> {
+        let PrefetchedLoaders(prefetched) = use_context()?;
+        let params = use_context::<ArcRwSignal<ParamsMap>>()?;
+        let key = (TypeId::of::<L>(), params.get_untracked());
+        let PrefetchedData {
+            data,
+            params: prefetched_params,
+            owner,
+        } = prefetched.lock().or_poisoned().take(&key)?;
+        let data = *data.downcast::<T>().ok()?;
+
+        Owner::on_cleanup(move || drop(owner));
+        // the loader read the params it was prefetched with, which now follow the route's own
+        Effect::new(move |_| {
+            let params = params.get();
+            if prefetched_params.with_untracked(|prev| prev != &params) {
+                prefetched_params.set(params);
+            }
+        });
+        Some(data)
+    }
+
+    fn prefetch_data(&self) {
+        let Some(PrefetchedLoaders(prefetched)) = use_context() else {
+            return;
+        };
+        let Some(params) = use_context::<ArcRwSignal<ParamsMap>>() else {
+            return;
+        };
+        let key = (TypeId::of::<L>(), params.get_untracked());
+        if prefetched.lock().or_poisoned().touch(&key) {
+            return;
+        }
+        // the lock is not held while the loader runs, in case it prefetches anything itself
+        let data = Box::new((self.loader)());
+        let owner = Owner::current().unwrap_or_default();
+        let evicted = prefetched.lock().or_poisoned().insert(
+            key,
+            PrefetchedData {
+                data,
+                params,
+                owner,
+            },
+        );
+        // anything the dropped loader created is disposed of, outside the lock
+        if let Some(evicted) = evicted {
+            evicted.owner.cleanup();
+        }
+    }
+}
+
+impl<L, T, V, R> ChooseView<R> for WithLoader<L, V>
+where
+    L: Fn() -> T + Send + Clone + 'static,
+    T: Clone + Send + Sync + 'static,
+    V: ChooseView<R>,
+    R: Renderer + 'static,
+{
+    type Output = V::Output;
+
+    async fn choose(self) -> Self::Output {
+        // routers that do not preload views still need the data
+        self.load();
+        self.view.choose().await
+    }
+
+    async fn preload(&self) {
+        self.load();
+        self.view.preload().await;
+    }
+
+    async fn prefetch(&self) {
+        self.prefetch_data();
+        self.view.prefetch().await;
+    }
+}
+
+/// The outcome of a route guard added with [`before_enter`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Guard {
+    /// The route is rendered as normal.
+    Allow,
+    /// The user is sent to another path instead, which is resolved relative to the current
+    /// route like the `path` of [`<Redirect/>`](crate::components::Redirect).
+    Redirect(String),
+    /// The navigation is canceled, and the user stays on the page they were on. On the server,
+    /// the route renders nothing and the response has a `403` status.
+    Block,
+}
+
+/// A route view with a guard, created by [`before_enter`] or
+/// [`NestedRoute::before_enter`](crate::NestedRoute::before_enter).
+#[derive(Debug, Clone)]
+pub struct WithGuard<G, V> {
+    guard: G,
+    view: V,
+}
+
+/// Adds a guard to a route's view, which decides whether the route can be entered.
+///
+/// `guard` is called in the route's own reactive scope each time the route is matched, so it can
+/// read context provided by the app (such as the current user) and the route's params, and it can
+/// `await` data before it decides. The route's view is only rendered if it returns
+/// [`Guard::Allow`].
+///
+/// During client-side navigation, the current page stays on screen while the guard and the
+/// route's loaders are running. If the guard returns [`Guard::Redirect`], the router navigates to
+/// the new path instead, replacing the current history entry. If it returns [`Guard::Block`], the
+/// router returns to the URL it was showing before the navigation started.
+///
+/// During server-side rendering, the guard is checked when the route's view is rendered. A
+/// redirect sets a `302` status and `Location` header on the response, through the function
+/// provided by the server integration (see
+/// [`provide_server_redirect`](crate::components::provide_server_redirect)). A blocked route
+/// renders nothing, and sets a `403 Forbidden` status through
+/// [`ResponseMeta`](leptos::response::ResponseMeta), unless another status has already been set.
+/// A guard that would rather send the user to a login page should return [`Guard::Redirect`]
+/// instead. Either status can only be set before the response has started streaming, so a guard
+/// that has to wait for data should be used on a route with
+/// [`SsrMode::Async`](crate::SsrMode::Async).
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_router::{components::*, path, before_enter, Guard};
+/// #[derive(Clone)]
+/// struct CurrentUser(Option<String>);
+///
+/// async fn require_login() -> Guard {
+///     match use_context::<CurrentUser>() {
+///         Some(CurrentUser(Some(_))) => Guard::Allow,
+///         _ => Guard::Redirect("/login".to_string()),
+///     }
+/// }
+///
+/// # if false {
+/// view! {
+///     <Router>
+///         <Routes fallback=|| "Not found.">
+///             <Route path=path!("/login") view=|| "Log in"/>
+///             <Route
+///                 path=path!("/account")
+///                 view=before_enter(require_login, || "Your account")
+///             />
+///         </Routes>
+///     </Router>
+/// }
+/// # ;
+/// # }
+/// ```
+pub fn before_enter<G, Fut, V>(guard: G, view: V) -> WithGuard<G, V>
+where
+    G: Fn() -> Fut + Send + Clone + 'static,
+    Fut: Future<Output = Guard> + 'static,
+{
+    WithGuard { guard, view }
+}
+
+// stores the outcome of a guard that has already been checked in the current route's scope
+struct Checked<G>(Guard, PhantomData<fn() -> G>);
+
+impl<G> Clone for Checked<G> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), PhantomData)
+    }
+}
+
+impl<G, Fut, V> WithGuard<G, V>
+where
+    G: Fn() -> Fut + Send + Clone + 'static,
+    Fut: Future<Output = Guard> + 'static,
+{
+    async fn check(&self) -> Guard {
+        if let Some(Checked(outcome, _)) = use_context::<Checked<G>>() {
+            return outcome;
+        }
+        let outcome = (self.guard)().await;
+        provide_context(Checked::<G>(outcome.clone(), PhantomData));
+        outcome
+    }
+
+    fn leave(outcome: Guard) {
+        let options = NavigateOptions {
+            replace: true,
+            ..Default::default()
+        };
+        match outcome {
+            Guard::Allow => {}
+            Guard::Redirect(path) => Redirect(
+                RedirectProps::builder().path(path).options(options).build(),
+            ),
+            // there is no page to stay on when rendering on the server, so the response says
+            // that the route cannot be entered instead
+            Guard::Block if cfg!(feature = "ssr") => {
+                if let Some(res) = leptos::response::use_response_meta() {
+                    if res.status().is_none() {
+                        res.set_status(403);
+                    }
+                }
+            }
+            Guard::Block => {
+                // there is nothing to go back to on the first page load
+                if let Some(PreviousUrl(url)) = use_context::<PreviousUrl>() {
+                    let search = if url.search().is_empty() { "" } else { "?" };
+                    let path = format!(
+                        "{}{search}{}{}",
+                        url.path(),
+                        url.search(),
+                        url.hash()
+                    );
+                    use_navigate()(
+                        &path,
+                        NavigateOptions {
+                            resolve: false,
+                            scroll: false,
+                            ..options
+                        },
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl<G, Fut, V, R> ChooseView<R> for WithGuard<G, V>
+where
+    G: Fn() -> Fut + Send + Clone + 'static,
+    Fut: Future<Output = Guard> + 'static,
+    V: ChooseView<R>,
+    R: Renderer + 'static,
+{
+    type Output = Either<V::Output, ()>;
+
+    async fn choose(self) -> Self::Output {
+        match self.check().await {
+            Guard::Allow => Either::Left(self.view.choose().await),
+            outcome => {
+                Self::leave(outcome);
+                Either::Right(())
+            }
+        }
+    }
+
+    async fn preload(&self) {
+        // routes are rendered synchronously on the server and during hydration, so the guard is
+        // checked when the view is chosen instead
+        let hydrating = Owner::current_shared_context()
+            .map(|sc| sc.during_hydration())
+            .unwrap_or(false);
+        if cfg!(feature = "ssr") || hydrating {
+            return;
+        }
+        match self.check().await {
+            Guard::Allow => self.view.preload().await,
+            // leaving navigates away, so the router drops this navigation rather than showing
+            // the route, and the current page stays on screen
+            outcome => Self::leave(outcome),
+        }
+    }
+
+    async fn prefetch(&self) {
+        self.view.prefetch().await;
+    }
+}
+
 impl<R> ChooseView<R> for ()
 where
     R: Renderer + 'static,
@@ -114,6 +667,12 @@ where
             Either::Right(f) => f.preload().await,
         }
     }
+    async fn prefetch(&self) {
+        match self {
+            Either::Left(f) => f.prefetch().await,
+            Either::Right(f) => f.prefetch().await,
+        }
+    }
 }
 
 macro_rules! tuples {
@@ -136,6 +695,12 @@ macro_rules! tuples {
                     $($either::$ty(f) => f.preload().await,)*
                 }
             }
+
+            async fn prefetch(&self) {
+                match self {
+                    $($either::$ty(f) => f.prefetch().await,)*
+                }
+            }
         }
     };
 }
@@ -6,10 +6,16 @@ pub use path_segment::*;
 mod horizontal;
 mod nested;
 mod vertical;
-use crate::SsrMode;
+use crate::{params::ParamsMap, SitemapRoute, SsrMode, StaticRoute};
+use futures::future::join_all;
 pub use horizontal::*;
 pub use nested::*;
-use std::{borrow::Cow, marker::PhantomData};
+use reactive_graph::{
+    computed::ScopedFuture,
+    owner::{provide_context, Owner},
+    signal::ArcRwSignal,
+};
+use std::{borrow::Cow, future::Future, marker::PhantomData, pin::Pin};
 use tachys::{
     renderer::Renderer,
     view::{Render, RenderHtml},
@@ -88,6 +94,43 @@ where
         }
     }
 
+    /// Prefetches the views of every route that matches `path`, if any, and calls their data
+    /// loaders.
+    ///
+    /// Each route is prefetched in a new child of the current [`Owner`], with its params
+    /// provided as context. The owner is dropped once the route has been prefetched, unless the
+    /// data returned by its loader is being kept for when the route is entered.
+    pub fn prefetch(&self, path: &str) -> impl Future<Output = ()> {
+        fn prefetch_match<M, R>(
+            matched: M,
+            views: &mut Vec<Pin<Box<dyn Future<Output = ()>>>>,
+        ) where
+            M: MatchInterface<R> + MatchParams,
+            R: Renderer + 'static,
+        {
+            let params: ParamsMap = matched.to_params().into_iter().collect();
+            let (view, child) = matched.into_view_and_child();
+            let owner = Owner::new();
+            views.push(Box::pin(owner.with(|| {
+                ScopedFuture::new(async move {
+                    provide_context(ArcRwSignal::new(params));
+                    view.prefetch().await
+                })
+            })));
+            if let Some(child) = child {
+                prefetch_match(child, views);
+            }
+        }
+
+        let mut views = Vec::new();
+        if let Some(matched) = self.match_route(path) {
+            prefetch_match(matched, &mut views);
+        }
+        async move {
+            join_all(views).await;
+        }
+    }
+
     pub fn generate_routes(
         &self,
     ) -> (
@@ -145,6 +188,8 @@ where
 pub struct GeneratedRouteData {
     pub segments: Vec<PathSegment>,
     pub ssr_mode: SsrMode,
+    pub static_route: Option<StaticRoute>,
+    pub sitemap: Option<SitemapRoute>,
 }
 
 #[cfg(test)]
@@ -316,6 +361,144 @@ mod tests {
         let params = matched.to_params().collect::<Vec<_>>();
         assert_eq!(params, vec![("any".into(), "foobar".into())]);
     }
+
+    #[test]
+    pub fn prefetches_lazy_routes_that_match() {
+        use crate::{Lazy, LazyRoute};
+        use futures::FutureExt;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tachys::view::any_view::{AnyView, IntoAny};
+
+        static LOADS: AtomicUsize = AtomicUsize::new(0);
+
+        struct Settings;
+
+        impl LazyRoute<Dom> for Settings {
+            fn data() -> Self {
+                Settings
+            }
+
+            async fn view(self) -> AnyView<Dom> {
+                LOADS.fetch_add(1, Ordering::Relaxed);
+                ().into_any()
+            }
+        }
+
+        let routes: Routes<_, Dom> = Routes::new((
+            NestedRoute::new(StaticSegment("/"), || ()),
+            NestedRoute::new(
+                StaticSegment("/settings"),
+                Lazy::<Settings>::new(),
+            ),
+        ));
+
+        routes.prefetch("/").now_or_never().unwrap();
+        assert_eq!(LOADS.load(Ordering::Relaxed), 0);
+        routes.prefetch("/settings").now_or_never().unwrap();
+        assert_eq!(LOADS.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    pub fn prefetches_route_loaders_and_reuses_their_data() {
+        use crate::{
+            expect_loader_data, hooks::use_params_map, params::ParamsMap,
+            with_loader, ChooseView, PrefetchedLoaders,
+        };
+        use futures::FutureExt;
+        use reactive_graph::{
+            owner::{provide_context, Owner},
+            signal::ArcRwSignal,
+            traits::Read,
+        };
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Clone)]
+        struct Post(String);
+
+        fn load_post() -> Post {
+            CALLS.fetch_add(1, Ordering::Relaxed);
+            Post(use_params_map().read().get("id").unwrap_or_default())
+        }
+
+        // entering a prefetched route creates an effect, if effects are enabled
+        _ = any_spawner::Executor::init_futures_executor();
+        let owner = Owner::new();
+        owner.set();
+        provide_context(PrefetchedLoaders::default());
+        let routes: Routes<_, Dom> = Routes::new(NestedRoute::new(
+            (StaticSegment("post"), ParamSegment("id")),
+            with_loader(load_post, || ()),
+        ));
+
+        routes.prefetch("/post/1").now_or_never().unwrap();
+        routes.prefetch("/post/1").now_or_never().unwrap();
+        assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+
+        // entering the route uses the data that was prefetched for the same params
+        let enter = |path: &str| {
+            let matched = routes.match_route(path).unwrap();
+            let params: ParamsMap = matched.to_params().collect();
+            let (view, _) = MatchInterface::<Dom>::into_view_and_child(matched);
+            owner.child().with(|| {
+                provide_context(ArcRwSignal::new(params));
+                view.preload().now_or_never().unwrap();
+                expect_loader_data(load_post).0
+            })
+        };
+        assert_eq!(enter("/post/1"), "1");
+        assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+        assert_eq!(enter("/post/2"), "2");
+        assert_eq!(CALLS.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    pub fn drops_the_least_recently_prefetched_loaders() {
+        use crate::{with_loader, PrefetchedLoaders};
+        use futures::FutureExt;
+        use reactive_graph::owner::{provide_context, Owner};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        static DISPOSED: AtomicUsize = AtomicUsize::new(0);
+
+        fn load_post() {
+            CALLS.fetch_add(1, Ordering::Relaxed);
+            Owner::on_cleanup(|| {
+                DISPOSED.fetch_add(1, Ordering::Relaxed);
+            });
+        }
+
+        let owner = Owner::new();
+        owner.set();
+        provide_context(PrefetchedLoaders::default());
+        let routes: Routes<_, Dom> = Routes::new(NestedRoute::new(
+            (StaticSegment("post"), ParamSegment("id")),
+            with_loader(load_post, || ()),
+        ));
+
+        for id in 0..32 {
+            routes
+                .prefetch(&format!("/post/{id}"))
+                .now_or_never()
+                .unwrap();
+        }
+        assert_eq!(CALLS.load(Ordering::Relaxed), 32);
+        assert_eq!(DISPOSED.load(Ordering::Relaxed), 0);
+
+        // prefetching a link again keeps its data around for longer
+        routes.prefetch("/post/0").now_or_never().unwrap();
+        routes.prefetch("/post/32").now_or_never().unwrap();
+        assert_eq!(CALLS.load(Ordering::Relaxed), 33);
+        assert_eq!(DISPOSED.load(Ordering::Relaxed), 1);
+
+        routes.prefetch("/post/0").now_or_never().unwrap();
+        assert_eq!(CALLS.load(Ordering::Relaxed), 33);
+        routes.prefetch("/post/1").now_or_never().unwrap();
+        assert_eq!(CALLS.load(Ordering::Relaxed), 34);
+        assert_eq!(DISPOSED.load(Ordering::Relaxed), 2);
+    }
 }
 
 #[derive(Debug)]
@@ -2,11 +2,16 @@ use super::{
     MatchInterface, MatchNestedRoutes, PartialPathMatch, PathSegment,
     PossibleRouteMatch, RouteMatchId,
 };
-use crate::{ChooseView, GeneratedRouteData, MatchParams, SsrMode};
+use crate::{
+    before_enter, with_loader, ChooseView, GeneratedRouteData, Guard,
+    MatchParams, SitemapRoute, SsrMode, StaticRoute, WithGuard, WithLoader,
+};
 use core::{fmt, iter};
 use either_of::Either;
+use reactive_graph::owner::StoredValue;
 use std::{
     borrow::Cow,
+    future::Future,
     marker::PhantomData,
     sync::atomic::{AtomicU16, Ordering},
 };
@@ -28,6 +33,9 @@ pub struct NestedRoute<Segments, Children, Data, View, R> {
     view: View,
     rndr: PhantomData<R>,
     ssr_mode: SsrMode,
+    // stored, rather than held directly, so that the route stays `Copy`
+    static_route: Option<StoredValue<StaticRoute>>,
+    sitemap: Option<StoredValue<SitemapRoute>>,
 }
 
 impl<Segments, Children, Data, View, R> Clone
@@ -47,6 +55,8 @@ where
             view: self.view.clone(),
             rndr: PhantomData,
             ssr_mode: self.ssr_mode,
+            static_route: self.static_route,
+            sitemap: self.sitemap,
         }
     }
 }
@@ -65,6 +75,8 @@ impl<Segments, View, R> NestedRoute<Segments, (), (), View, R> {
             view,
             rndr: PhantomData,
             ssr_mode: Default::default(),
+            static_route: None,
+            sitemap: None,
         }
     }
 }
@@ -81,6 +93,8 @@ impl<Segments, Data, View, R> NestedRoute<Segments, (), Data, View, R> {
             view,
             rndr,
             ssr_mode,
+            static_route,
+            sitemap,
             ..
         } = self;
         NestedRoute {
@@ -90,6 +104,8 @@ impl<Segments, Data, View, R> NestedRoute<Segments, (), Data, View, R> {
             data,
             view,
             ssr_mode,
+            static_route,
+            sitemap,
             rndr,
         }
     }
@@ -98,6 +114,91 @@ impl<Segments, Data, View, R> NestedRoute<Segments, (), Data, View, R> {
         self.ssr_mode = ssr_mode;
         self
     }
+
+    /// Marks this route to be rendered to a static HTML file. See [`StaticRoute`].
+    pub fn static_route(
+        mut self,
+        static_route: impl Into<Option<StaticRoute>>,
+    ) -> Self {
+        self.static_route = static_route.into().map(StoredValue::new);
+        self
+    }
+
+    /// Sets how this route is listed in the sitemap. See [`SitemapRoute`].
+    pub fn sitemap(mut self, sitemap: impl Into<Option<SitemapRoute>>) -> Self {
+        self.sitemap = sitemap.into().map(StoredValue::new);
+        self
+    }
+}
+
+impl<Segments, Children, Data, View, R>
+    NestedRoute<Segments, Children, Data, View, R>
+{
+    /// Attaches a data loader to this route's view. See [`with_loader`].
+    pub fn loader<L, T>(
+        self,
+        loader: L,
+    ) -> NestedRoute<Segments, Children, Data, WithLoader<L, View>, R>
+    where
+        L: Fn() -> T + Send + Clone + 'static,
+        T: Clone + Send + Sync + 'static,
+    {
+        let Self {
+            id,
+            segments,
+            children,
+            data,
+            view,
+            rndr,
+            ssr_mode,
+            static_route,
+            sitemap,
+        } = self;
+        NestedRoute {
+            id,
+            segments,
+            children,
+            data,
+            view: with_loader(loader, view),
+            rndr,
+            ssr_mode,
+            static_route,
+            sitemap,
+        }
+    }
+
+    /// Adds a guard that decides whether this route can be entered. See [`before_enter`].
+    pub fn before_enter<G, Fut>(
+        self,
+        guard: G,
+    ) -> NestedRoute<Segments, Children, Data, WithGuard<G, View>, R>
+    where
+        G: Fn() -> Fut + Send + Clone + 'static,
+        Fut: Future<Output = Guard> + 'static,
+    {
+        let Self {
+            id,
+            segments,
+            children,
+            data,
+            view,
+            rndr,
+            ssr_mode,
+            static_route,
+            sitemap,
+        } = self;
+        NestedRoute {
+            id,
+            segments,
+            children,
+            data,
+            view: before_enter(guard, view),
+            rndr,
+            ssr_mode,
+            static_route,
+            sitemap,
+        }
+    }
 }
 
 #[derive(PartialEq, Eq)]
@@ -250,25 +351,27 @@ where
         self.segments.generate_path(&mut segment_routes);
         let children = self.children.as_ref();
         let ssr_mode = self.ssr_mode;
+        let static_route =
+            self.static_route.and_then(|route| route.try_get_value());
+        let sitemap = self.sitemap.and_then(|sitemap| sitemap.try_get_value());
 
         match children {
             None => Either::Left(iter::once(GeneratedRouteData {
                 segments: segment_routes,
-                ssr_mode
+                ssr_mode,
+                static_route,
+                sitemap,
             })),
             Some(children) => {
                 Either::Right(children.generate_routes().into_iter().map(move |child| {
                     let segments = segment_routes.clone().into_iter().chain(child.segments).collect();
-                    if child.ssr_mode > ssr_mode {
-                        GeneratedRouteData {
-                            segments,
-                            ssr_mode: child.ssr_mode,
-                        }
-                    } else {
-                        GeneratedRouteData {
-                            segments,
-                            ssr_mode,
-                        }
+                    GeneratedRouteData {
+                        segments,
+                        ssr_mode: child.ssr_mode.max(ssr_mode),
+                        // a child route can override whether its parent is static
+                        static_route: child.static_route.or_else(|| static_route.clone()),
+                        // and how it is listed in the sitemap
+                        sitemap: child.sitemap.or_else(|| sitemap.clone()),
                     }
                 }))
             }
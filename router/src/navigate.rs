@@ -13,6 +13,9 @@ pub struct NavigateOptions {
     pub scroll: bool,
     /// [State](https://developer.mozilla.org/en-US/docs/Web/API/History/state) that should be pushed
     /// onto the history stack during navigation.
+    ///
+    /// The destination route can read it from [`Location::state`](crate::location::Location),
+    /// which is also updated to the state of each history entry when going back or forward.
     pub state: State,
 }
 
@@ -1,4 +1,5 @@
 use crate::{
+    components::set_not_found_status,
     hooks::Matched,
     location::{LocationProvider, Url},
     matching::Routes,
@@ -49,7 +50,6 @@ pub(crate) struct NestedRoutesView<Loc, Defs, FalFn, R> {
     pub current_url: ArcRwSignal<Url>,
     pub base: Option<Oco<'static, str>>,
     pub fallback: FalFn,
-    #[allow(unused)] // TODO
     pub set_is_routing: Option<SignalSetter<bool>>,
     pub rndr: PhantomData<R>,
 }
@@ -60,6 +60,8 @@ where
     R: Renderer + 'static,
 {
     path: String,
+    // the URL that was last routed, so a route guard can return to it
+    url: Url,
     current_url: ArcRwSignal<Url>,
     outlets: Vec<RouteContext<R>>,
     // TODO loading fallback
@@ -131,6 +133,7 @@ where
 
         NestedRouteViewState {
             path: url.path().to_string(),
+            url: url.to_owned(),
             current_url,
             outlets,
             view,
@@ -139,6 +142,7 @@ where
 
     fn rebuild(self, state: &mut Self::State) {
         let url_snapshot = self.current_url.get_untracked();
+        let previous_url = mem::replace(&mut state.url, url_snapshot.clone());
 
         // if the path is the same, we do not need to re-route
         // we can just update the search query and go about our day
@@ -163,6 +167,11 @@ where
                 state.outlets.clear();
             }
             Some(route) => {
+                if let Some(set_is_routing) = self.set_is_routing {
+                    set_is_routing.set(true);
+                }
+                self.outer_owner
+                    .with(|| provide_context(PreviousUrl(previous_url)));
                 let mut loaders = Vec::new();
                 route.rebuild_nested_route(
                     &self.current_url.read_untracked(),
@@ -174,12 +183,23 @@ where
                 );
 
                 let location = self.location.clone();
+                let set_is_routing = self.set_is_routing;
+                let current_url = self.current_url.clone();
+                let path = state.path.clone();
                 Executor::spawn_local(async move {
                     let triggers = join_all(loaders).await;
+                    // if the router navigated somewhere else while loading, for example because a
+                    // route guard redirected, the newer navigation shows its own routes instead
+                    if current_url.read_untracked().path() != path {
+                        return;
+                    }
                     // tell each one of the outlet triggers that it's ready
                     for trigger in triggers {
                         trigger.trigger();
                     }
+                    if let Some(set_is_routing) = set_is_routing {
+                        set_is_routing.set(false);
+                    }
                     if let Some(loc) = location {
                         loc.ready_to_complete();
                     }
@@ -269,9 +289,9 @@ where
                         data.ssr_mode,
                         // TODO methods
                         [Method::Get],
-                        // TODO static data
-                        None,
+                        data.static_route,
                     )
+                    .with_sitemap(data.sitemap)
                 })
                 .collect::<Vec<_>>();
 
@@ -307,7 +327,10 @@ where
             let mut outlets = Vec::new();
             let new_match = routes.match_route(current_url.path());
             let view = match new_match {
-                None => Either::Left(fallback()),
+                None => {
+                    outer_owner.with(set_not_found_status);
+                    Either::Left(fallback())
+                }
                 Some(route) => {
                     let mut loaders = Vec::new();
                     route.build_nested_route(
@@ -358,7 +381,10 @@ where
         let mut outlets = Vec::new();
         let new_match = routes.match_route(current_url.path());
         let view = match new_match {
-            None => Either::Left(fallback()),
+            None => {
+                outer_owner.with(set_not_found_status);
+                Either::Left(fallback())
+            }
             Some(route) => {
                 let mut loaders = Vec::new();
                 route.build_nested_route(
@@ -440,6 +466,7 @@ where
 
         NestedRouteViewState {
             path: url.path().to_string(),
+            url: url.to_owned(),
             current_url,
             outlets,
             view,
@@ -447,6 +474,11 @@ where
     }
 }
 
+/// The URL that was shown before the current navigation, which a route guard that blocks the
+/// navigation returns to.
+#[derive(Debug, Clone)]
+pub(crate) struct PreviousUrl(pub Url);
+
 type OutletViewFn<R> = Box<
     dyn Fn() -> Suspend<Pin<Box<dyn Future<Output = AnyView<R>> + Send>>>
         + Send,
@@ -106,10 +106,18 @@ impl Params for () {
     }
 }
 
+/// Converts a single value from a [`ParamsMap`] into a field of a type that implements
+/// [`Params`].
+///
+/// `Option<T>` is `None` if the param is missing. Any other type returns
+/// [`ParamsError::MissingParam`] if the param is missing. On stable Rust, this is implemented for
+/// `String`, `bool`, `char`, and the primitive number types; with the `nightly` feature, it is
+/// implemented for every type that implements [`FromStr`].
 pub trait IntoParam
 where
     Self: Sized,
 {
+    /// Parses the value of the param called `name`, which is `None` if it is missing.
     fn into_param(value: Option<&str>, name: &str)
         -> Result<Self, ParamsError>;
 }
@@ -133,7 +141,38 @@ where
     }
 }
 
-// TODO can we support Option<T> and T in a non-nightly way?
+// on stable, a required (non-`Option`) param is supported for the standard library types that
+// implement `FromStr`; on nightly, it is supported for any type that implements `FromStr`
+#[cfg(not(feature = "nightly"))]
+mod required_param {
+    use super::{IntoParam, ParamsError};
+    use std::{str::FromStr, sync::Arc};
+
+    macro_rules! required_param {
+        ($($ty:ty),*) => {
+            $(
+                impl IntoParam for $ty {
+                    fn into_param(
+                        value: Option<&str>,
+                        name: &str,
+                    ) -> Result<Self, ParamsError> {
+                        let value = value.ok_or_else(|| {
+                            ParamsError::MissingParam(name.to_string())
+                        })?;
+                        <$ty>::from_str(value)
+                            .map_err(|e| ParamsError::Params(Arc::new(e)))
+                    }
+                }
+            )*
+        };
+    }
+
+    required_param!(
+        String, bool, char, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64,
+        i128, isize, f32, f64
+    );
+}
+
 #[cfg(feature = "nightly")]
 mod option_param {
     use super::{IntoParam, ParamsError};
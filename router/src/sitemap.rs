@@ -0,0 +1,297 @@
+use crate::{PathSegment, StaticRoute};
+use std::fmt::Write;
+
+/// How often the page at a URL is likely to change, as a hint to search engines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeFrequency {
+    /// The page changes each time it is accessed.
+    Always,
+    /// The page changes about once an hour.
+    Hourly,
+    /// The page changes about once a day.
+    Daily,
+    /// The page changes about once a week.
+    Weekly,
+    /// The page changes about once a month.
+    Monthly,
+    /// The page changes about once a year.
+    Yearly,
+    /// The page is archived, and will not change.
+    Never,
+}
+
+impl ChangeFrequency {
+    /// The value of the `<changefreq>` element.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeFrequency::Always => "always",
+            ChangeFrequency::Hourly => "hourly",
+            ChangeFrequency::Daily => "daily",
+            ChangeFrequency::Weekly => "weekly",
+            ChangeFrequency::Monthly => "monthly",
+            ChangeFrequency::Yearly => "yearly",
+            ChangeFrequency::Never => "never",
+        }
+    }
+}
+
+/// Sets how a route is listed in the [`Sitemap`].
+///
+/// Every route without parameters is listed by default. A route with parameters is listed once
+/// for each path that is prerendered for it with a [`StaticRoute`], as the router does not know
+/// the values of its parameters otherwise. The options set on a parent route apply to its
+/// children, unless they set their own.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// use leptos_router::{
+///     components::{Route, Router, Routes},
+///     path, ChangeFrequency, SitemapRoute,
+/// };
+///
+/// # fn Home() -> impl IntoView {}
+/// # fn Admin() -> impl IntoView {}
+/// # #[component]
+/// # fn App() -> impl IntoView {
+/// view! {
+///     <Router>
+///         <Routes fallback=|| "Not found.">
+///             <Route
+///                 path=path!("/")
+///                 view=Home
+///                 sitemap=SitemapRoute::new()
+///                     .priority(1.0)
+///                     .change_frequency(ChangeFrequency::Daily)
+///             />
+///             <Route path=path!("/admin") view=Admin sitemap=SitemapRoute::disallow()/>
+///         </Routes>
+///     </Router>
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SitemapRoute {
+    /// In tenths.
+    priority: Option<u8>,
+    change_frequency: Option<ChangeFrequency>,
+    last_modified: Option<String>,
+    exclude: bool,
+    disallow: bool,
+}
+
+impl SitemapRoute {
+    /// Lists the route in the sitemap, without any further information.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Leaves the route out of the sitemap.
+    pub fn exclude() -> Self {
+        Self {
+            exclude: true,
+            ..Self::default()
+        }
+    }
+
+    /// Leaves the route out of the sitemap, and asks crawlers not to visit it in `robots.txt`.
+    pub fn disallow() -> Self {
+        Self {
+            exclude: true,
+            disallow: true,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the priority of the route relative to the other pages of the site, from `0.0` to
+    /// `1.0`, rounded to one decimal place.
+    pub fn priority(mut self, priority: f32) -> Self {
+        let tenths = (priority * 10.0).round().clamp(0.0, 10.0);
+        self.priority = Some(if tenths.is_nan() { 5 } else { tenths as u8 });
+        self
+    }
+
+    /// Sets how often the route is likely to change.
+    pub fn change_frequency(
+        mut self,
+        change_frequency: ChangeFrequency,
+    ) -> Self {
+        self.change_frequency = Some(change_frequency);
+        self
+    }
+
+    /// Sets the date the route last changed, in the [W3C Datetime](https://www.w3.org/TR/NOTE-datetime)
+    /// format, like `2024-07-01`.
+    pub fn last_modified(mut self, date: impl Into<String>) -> Self {
+        self.last_modified = Some(date.into());
+        self
+    }
+
+    /// Whether the route is left out of the sitemap.
+    pub fn is_excluded(&self) -> bool {
+        self.exclude
+    }
+
+    /// Whether crawlers are asked not to visit the route.
+    pub fn is_disallowed(&self) -> bool {
+        self.disallow
+    }
+}
+
+/// A list of the pages of a site, which can be written as a
+/// [`sitemap.xml`](https://www.sitemaps.org/protocol.html) and a `robots.txt` that points to it.
+///
+/// This is usually created from the route list of the app by a server integration (for
+/// example, `leptos_axum::build_sitemap`), but paths can also be added to it by hand.
+#[derive(Debug, Clone)]
+pub struct Sitemap {
+    base_url: String,
+    urls: Vec<(String, SitemapRoute)>,
+    disallowed: Vec<String>,
+}
+
+impl Sitemap {
+    /// Creates an empty sitemap for the site at `base_url`, like `https://leptos.dev`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        let mut base_url = base_url.into();
+        while base_url.ends_with('/') {
+            base_url.pop();
+        }
+        Self {
+            base_url,
+            urls: Vec::new(),
+            disallowed: Vec::new(),
+        }
+    }
+
+    /// Adds the paths of a route, as described in [`SitemapRoute`].
+    pub async fn add_route(
+        &mut self,
+        path: &[PathSegment],
+        static_route: Option<&StaticRoute>,
+        sitemap: Option<&SitemapRoute>,
+    ) {
+        let sitemap = sitemap.cloned().unwrap_or_default();
+        if sitemap.disallow {
+            let prefix = disallowed_prefix(path);
+            if !self.disallowed.contains(&prefix) {
+                self.disallowed.push(prefix);
+            }
+        }
+        if sitemap.exclude {
+            return;
+        }
+        let paths = match static_route {
+            Some(static_route) => static_route.to_paths(path).await,
+            None => StaticRoute::new().to_paths(path).await,
+        };
+        for path in paths {
+            self.add_path(path, sitemap.clone());
+        }
+    }
+
+    /// Adds a path, unless it has already been added.
+    pub fn add_path(&mut self, path: impl Into<String>, sitemap: SitemapRoute) {
+        let path = path.into();
+        if !self.urls.iter().any(|(existing, _)| *existing == path) {
+            self.urls.push((path, sitemap));
+        }
+    }
+
+    /// The paths in the sitemap.
+    pub fn paths(&self) -> impl Iterator<Item = &str> + '_ {
+        self.urls.iter().map(|(path, _)| path.as_str())
+    }
+
+    /// The URL at which the sitemap is served.
+    pub fn url(&self) -> String {
+        format!("{}/sitemap.xml", self.base_url)
+    }
+
+    /// Renders the sitemap as XML.
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset \
+             xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+        );
+        for (path, sitemap) in &self.urls {
+            xml.push_str("<url><loc>");
+            escape_xml(&mut xml, &self.base_url);
+            escape_xml(&mut xml, path);
+            xml.push_str("</loc>");
+            if let Some(last_modified) = &sitemap.last_modified {
+                xml.push_str("<lastmod>");
+                escape_xml(&mut xml, last_modified);
+                xml.push_str("</lastmod>");
+            }
+            if let Some(change_frequency) = sitemap.change_frequency {
+                _ = write!(
+                    xml,
+                    "<changefreq>{}</changefreq>",
+                    change_frequency.as_str()
+                );
+            }
+            if let Some(priority) = sitemap.priority {
+                _ = write!(
+                    xml,
+                    "<priority>{}.{}</priority>",
+                    priority / 10,
+                    priority % 10
+                );
+            }
+            xml.push_str("</url>\n");
+        }
+        xml.push_str("</urlset>\n");
+        xml
+    }
+
+    /// Renders a `robots.txt` that allows every crawler to visit the site, except for the
+    /// routes marked with [`SitemapRoute::disallow`], and points them to the sitemap.
+    pub fn robots_txt(&self) -> String {
+        let mut robots = String::from("User-agent: *\n");
+        if self.disallowed.is_empty() {
+            robots.push_str("Allow: /\n");
+        }
+        for path in &self.disallowed {
+            _ = writeln!(robots, "Disallow: {path}");
+        }
+        _ = writeln!(robots, "\nSitemap: {}", self.url());
+        robots
+    }
+}
+
+/// The part of the path before its first parameter, which matches every path of the route.
+fn disallowed_prefix(path: &[PathSegment]) -> String {
+    let mut prefix = String::new();
+    for segment in path {
+        match segment {
+            PathSegment::Unit => {}
+            PathSegment::Static(s) => {
+                if !s.is_empty() && !s.starts_with('/') {
+                    prefix.push('/');
+                }
+                prefix.push_str(s);
+            }
+            PathSegment::Param(_) | PathSegment::Splat(_) => {
+                prefix.push('/');
+                return prefix;
+            }
+        }
+    }
+    if prefix.is_empty() {
+        prefix.push('/');
+    }
+    prefix
+}
+
+fn escape_xml(buf: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => buf.push_str("&amp;"),
+            '<' => buf.push_str("&lt;"),
+            '>' => buf.push_str("&gt;"),
+            '"' => buf.push_str("&quot;"),
+            '\'' => buf.push_str("&apos;"),
+            c => buf.push(c),
+        }
+    }
+}
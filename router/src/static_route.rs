@@ -1,5 +1,9 @@
+use crate::PathSegment;
+use futures::future::BoxFuture;
+use std::{fmt, future::Future, sync::Arc, time::Duration};
+
 /// The mode to use when rendering the route statically.
-/// On mode `Upfront`, the route will be built with the server is started using the provided static
+/// On mode `Upfront`, the route will be built when the server is started using the provided static
 /// data. On mode `Incremental`, the route will be built on the first request to it and then cached
 /// and returned statically for subsequent requests.
 #[derive(Default, Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -19,3 +23,239 @@ impl StaticDataMap {
         Self
     }
 }
+
+/// The values to use for each route parameter when prerendering a route with a [`StaticRoute`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StaticParamsMap(pub Vec<(String, Vec<String>)>);
+
+impl StaticParamsMap {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the values for a parameter, replacing any previous values.
+    pub fn insert(
+        &mut self,
+        key: impl Into<String>,
+        values: impl IntoIterator<Item = impl Into<String>>,
+    ) {
+        let key = key.into();
+        let values = values.into_iter().map(Into::into).collect();
+        match self.0.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, existing)) => *existing = values,
+            None => self.0.push((key, values)),
+        }
+    }
+
+    /// The values for a parameter, if any have been set.
+    pub fn get(&self, key: &str) -> Option<&[String]> {
+        self.0
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, values)| values.as_slice())
+    }
+}
+
+impl<K, V, I> FromIterator<(K, I)> for StaticParamsMap
+where
+    K: Into<String>,
+    V: Into<String>,
+    I: IntoIterator<Item = V>,
+{
+    fn from_iter<T: IntoIterator<Item = (K, I)>>(iter: T) -> Self {
+        let mut map = Self::new();
+        for (key, values) in iter {
+            map.insert(key, values);
+        }
+        map
+    }
+}
+
+type ParamsFn =
+    Arc<dyn Fn() -> BoxFuture<'static, StaticParamsMap> + Send + Sync>;
+
+/// Marks a route to be rendered to a static HTML file, rather than being rendered on each
+/// request.
+///
+/// Static routes are rendered once, after all of their async data has loaded, and written into
+/// the site root by the server integration (for example, `leptos_axum::build_static_routes`).
+/// A route with parameters is rendered once for each combination of the values returned by
+/// [`prerender_params`](StaticRoute::prerender_params); a parameter without any values means
+/// that the route is not prerendered.
+///
+/// Pages can also be regenerated while the server is running. A route marked as
+/// [`incremental`](StaticRoute::incremental) is rendered on the first request to each path,
+/// rather than up front, and a route with a [`revalidate`](StaticRoute::revalidate) interval is
+/// rendered again in the background on the first request after the cached page has expired.
+/// Until the new page is ready, the expired one continues to be served.
+///
+/// Only the paths generated from [`prerender_params`](StaticRoute::prerender_params) are
+/// stored, so that requests for arbitrary parameters cannot fill the cache. Other paths that the
+/// route matches are rendered on each request, unless the route opts in with
+/// [`cache_any_path`](StaticRoute::cache_any_path). Pages that set a status other than `2xx`, or
+/// redirect, are never stored.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// use leptos_router::{
+///     components::{Route, Router, Routes},
+///     path, StaticParamsMap, StaticRoute,
+/// };
+///
+/// # fn Post() -> impl IntoView {}
+/// # #[component]
+/// # fn App() -> impl IntoView {
+/// view! {
+///     <Router>
+///         <Routes fallback=|| "Not found.">
+///             <Route
+///                 path=path!("/posts/:slug")
+///                 view=Post
+///                 static_route=StaticRoute::new().prerender_params(|| async {
+///                     [("slug", ["hello-world", "static-sites"])]
+///                         .into_iter()
+///                         .collect::<StaticParamsMap>()
+///                 })
+///             />
+///         </Routes>
+///     </Router>
+/// }
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct StaticRoute {
+    mode: StaticMode,
+    prerender_params: Option<ParamsFn>,
+    revalidate: Option<Duration>,
+    cache_any_path: bool,
+}
+
+impl StaticRoute {
+    /// Creates a static route that is rendered up front, with no route parameters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the function that returns the values of each route parameter that should be
+    /// prerendered.
+    pub fn prerender_params<Fut>(
+        mut self,
+        params: impl Fn() -> Fut + Send + Sync + 'static,
+    ) -> Self
+    where
+        Fut: Future<Output = StaticParamsMap> + Send + 'static,
+    {
+        self.prerender_params = Some(Arc::new(move || Box::pin(params())));
+        self
+    }
+
+    /// Renders each path on the first request to it, rather than when the server starts.
+    pub fn incremental(mut self) -> Self {
+        self.mode = StaticMode::Incremental;
+        self
+    }
+
+    /// Renders a page again once it is older than `ttl`.
+    ///
+    /// The page is regenerated in the background on the first request after it expires, and the
+    /// expired page is served in the meantime.
+    pub fn revalidate(mut self, ttl: Duration) -> Self {
+        self.revalidate = Some(ttl);
+        self
+    }
+
+    /// Stores a page for any path this route matches, when it is rendered on request, rather than
+    /// only for the paths generated from [`prerender_params`](StaticRoute::prerender_params).
+    ///
+    /// Every distinct path that is requested is stored, so this should only be used when the
+    /// values of the route's parameters are limited in some other way.
+    pub fn cache_any_path(mut self) -> Self {
+        self.cache_any_path = true;
+        self
+    }
+
+    /// When this route will be rendered.
+    pub fn mode(&self) -> StaticMode {
+        self.mode
+    }
+
+    /// How long a rendered page can be served before it is regenerated, if it ever is.
+    pub fn ttl(&self) -> Option<Duration> {
+        self.revalidate
+    }
+
+    /// Whether the page rendered for a request to `path` should be stored, for a route with the
+    /// given path segments.
+    pub async fn caches_path(
+        &self,
+        segments: &[PathSegment],
+        path: &str,
+    ) -> bool {
+        self.cache_any_path
+            || self.to_paths(segments).await.iter().any(|p| p == path)
+    }
+
+    /// Returns every URL path that should be prerendered for a route with the given path.
+    pub async fn to_paths(&self, path: &[PathSegment]) -> Vec<String> {
+        let params = match &self.prerender_params {
+            Some(params) => params().await,
+            None => StaticParamsMap::new(),
+        };
+        let mut paths = vec![String::new()];
+        for segment in path {
+            let values = match segment {
+                PathSegment::Unit => continue,
+                PathSegment::Static(s) => vec![s.to_string()],
+                PathSegment::Param(name) | PathSegment::Splat(name) => {
+                    params.get(name).map(<[_]>::to_vec).unwrap_or_default()
+                }
+            };
+            paths = paths
+                .iter()
+                .flat_map(|path| {
+                    values.iter().map(move |value| {
+                        let mut path = path.clone();
+                        if !value.is_empty() && !value.starts_with('/') {
+                            path.push('/');
+                        }
+                        path.push_str(value);
+                        path
+                    })
+                })
+                .collect();
+        }
+        for path in &mut paths {
+            if path.is_empty() {
+                path.push('/');
+            }
+        }
+        paths
+    }
+}
+
+impl fmt::Debug for StaticRoute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StaticRoute")
+            .field("mode", &self.mode)
+            .field("prerender_params", &self.prerender_params.is_some())
+            .field("revalidate", &self.revalidate)
+            .field("cache_any_path", &self.cache_any_path)
+            .finish()
+    }
+}
+
+impl PartialEq for StaticRoute {
+    fn eq(&self, other: &Self) -> bool {
+        self.mode == other.mode
+            && self.revalidate == other.revalidate
+            && self.cache_any_path == other.cache_any_path
+            && match (&self.prerender_params, &other.prerender_params) {
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                (None, None) => true,
+                _ => false,
+            }
+    }
+}
+
+impl Eq for StaticRoute {}
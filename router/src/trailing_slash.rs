@@ -0,0 +1,58 @@
+use std::borrow::Cow;
+
+/// How the [`Router`](crate::components::Router) handles a trailing slash at the end of the path
+/// of a URL.
+///
+/// Routes match a path whether or not it ends with a slash, so `/about` and `/about/` show the
+/// same page. As search engines treat them as two different pages, a site usually picks one form,
+/// and redirects from the other to it. Both forms are kept as they are by default.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TrailingSlash {
+    /// Paths are used as they are.
+    #[default]
+    Ignore,
+    /// Paths that end with a slash are redirected to the path without it, like `/about/` to
+    /// `/about`.
+    Remove,
+    /// Paths that do not end with a slash are redirected to the path with one, like `/about` to
+    /// `/about/`.
+    Add,
+}
+
+impl TrailingSlash {
+    /// Returns the canonical form of the path. The root path, `/`, is always left as it is.
+    pub fn normalize<'a>(&self, path: &'a str) -> Cow<'a, str> {
+        match self {
+            TrailingSlash::Ignore => path.into(),
+            TrailingSlash::Remove => {
+                let trimmed = path.trim_end_matches('/');
+                if trimmed.is_empty() {
+                    "/".into()
+                } else {
+                    trimmed.into()
+                }
+            }
+            TrailingSlash::Add => {
+                if path.ends_with('/') {
+                    path.into()
+                } else {
+                    format!("{path}/").into()
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TrailingSlash;
+
+    #[test]
+    fn normalizes_trailing_slashes() {
+        assert_eq!(TrailingSlash::Ignore.normalize("/about/"), "/about/");
+        assert_eq!(TrailingSlash::Remove.normalize("/about//"), "/about");
+        assert_eq!(TrailingSlash::Remove.normalize("/"), "/");
+        assert_eq!(TrailingSlash::Add.normalize("/about"), "/about/");
+        assert_eq!(TrailingSlash::Add.normalize("/about/"), "/about/");
+    }
+}
@@ -0,0 +1,43 @@
+use leptos::Params;
+use leptos_router::params::{Params, ParamsError, ParamsMap};
+
+#[derive(Params, Debug, PartialEq)]
+struct PostParams {
+    id: u64,
+    tab: Option<String>,
+}
+
+#[test]
+fn parses_required_and_optional_params() {
+    let map = [("id", "42"), ("tab", "comments")]
+        .into_iter()
+        .collect::<ParamsMap>();
+    assert_eq!(
+        PostParams::from_map(&map),
+        Ok(PostParams {
+            id: 42,
+            tab: Some("comments".to_string())
+        })
+    );
+
+    let map = [("id", "42")].into_iter().collect::<ParamsMap>();
+    assert_eq!(
+        PostParams::from_map(&map),
+        Ok(PostParams { id: 42, tab: None })
+    );
+}
+
+#[test]
+fn missing_or_invalid_params_are_errors() {
+    let map = [("tab", "comments")].into_iter().collect::<ParamsMap>();
+    assert_eq!(
+        PostParams::from_map(&map),
+        Err(ParamsError::MissingParam("id".to_string()))
+    );
+
+    let map = [("id", "forty-two")].into_iter().collect::<ParamsMap>();
+    assert!(matches!(
+        PostParams::from_map(&map),
+        Err(ParamsError::Params(_))
+    ));
+}
@@ -0,0 +1,646 @@
+#![cfg(feature = "ssr")]
+
+use leptos::{form::ActionForm, prelude::*};
+use leptos_router::{
+    before_enter,
+    components::{
+        provide_server_redirect, NotFound, Outlet, ParentRoute, Prefetch,
+        Route, Router, Routes, A,
+    },
+    expect_loader_data,
+    hooks::{use_canonical_url, use_params, use_params_map},
+    location::RequestUrl,
+    path, with_loader, ChangeFrequency, Guard, RouteList, Sitemap,
+    SitemapRoute, StaticMode, StaticParamsMap, StaticRoute, TrailingSlash,
+};
+use server_fn::error::ServerFnErrorSerde;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+
+#[component]
+fn App() -> impl IntoView {
+    view! {
+        <Router>
+            <main>
+                <Routes fallback=|| "Not found.">
+                    <ParentRoute path=path!("/users") view=Users>
+                        <Route path=path!("") view=|| "Pick a user."/>
+                        <Route path=path!(":id") view=User/>
+                    </ParentRoute>
+                    <Route path=path!("/files/*rest") view=File/>
+                </Routes>
+            </main>
+        </Router>
+    }
+}
+
+#[component]
+fn Users() -> impl IntoView {
+    view! {
+        <h1>"Users"</h1>
+        <Outlet/>
+    }
+}
+
+#[component]
+fn User() -> impl IntoView {
+    let params = use_params_map();
+    view! { <p>"User " {move || params.read().get("id")}</p> }
+}
+
+#[component]
+fn File() -> impl IntoView {
+    let params = use_params_map();
+    view! { <p>"File " {move || params.read().get("rest")}</p> }
+}
+
+fn render(path: &str) -> String {
+    let owner = Owner::new();
+    owner.with(|| {
+        provide_context(RequestUrl::new(path));
+        view! { <App/> }.to_html()
+    })
+}
+
+#[test]
+fn renders_matched_nested_routes_on_the_server() {
+    assert_eq!(render("/users"), "<main><h1>Users</h1>Pick a user.</main>");
+    assert_eq!(
+        render("/users/42"),
+        "<main><h1>Users</h1><p>User <!>42</p></main>"
+    );
+}
+
+#[test]
+fn wildcard_segment_matches_the_rest_of_the_path() {
+    assert_eq!(
+        render("/files/a/b.txt"),
+        "<main><p>File <!>a/b.txt</p></main>"
+    );
+}
+
+#[test]
+fn renders_fallback_when_no_route_matches() {
+    assert_eq!(render("/nope"), "<main>Not found.</main>");
+}
+
+#[derive(leptos::Params, Clone, PartialEq)]
+struct PostParams {
+    id: u64,
+}
+
+#[component]
+fn Post() -> impl IntoView {
+    let params = use_params::<PostParams>();
+    view! {
+        <ErrorBoundary fallback=|_| "Invalid post.">
+            {move || params.get().map(|params| format!("Post #{}", params.id))}
+        </ErrorBoundary>
+    }
+}
+
+fn render_post(path: &str) -> String {
+    let owner = Owner::new();
+    owner.with(|| {
+        provide_context(RequestUrl::new(path));
+        view! {
+            <Router>
+                <Routes fallback=|| "Not found.">
+                    <Route path=path!("/posts/:id") view=Post/>
+                </Routes>
+            </Router>
+        }
+        .to_html()
+    })
+}
+
+#[test]
+fn invalid_typed_params_are_caught_by_error_boundary() {
+    assert_eq!(render_post("/posts/7"), "Post #7");
+    assert_eq!(render_post("/posts/seven"), "Invalid post.");
+}
+
+static TEAM_LOADS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone)]
+struct Team(String);
+
+fn load_team() -> Team {
+    TEAM_LOADS.fetch_add(1, Ordering::Relaxed);
+    let params = use_params_map();
+    Team(params.read_untracked().get("team").unwrap_or_default())
+}
+
+#[derive(Clone)]
+struct Member(String);
+
+fn load_member() -> Member {
+    // the parent's data is available to its children's loaders
+    let Team(team) = expect_loader_data(load_team);
+    let params = use_params_map();
+    Member(format!(
+        "{} of {team}",
+        params.read_untracked().get("member").unwrap_or_default()
+    ))
+}
+
+#[component]
+fn TeamPage() -> impl IntoView {
+    let Team(team) = expect_loader_data(load_team);
+    view! {
+        <h1>{team}</h1>
+        <Outlet/>
+    }
+}
+
+#[component]
+fn MemberPage() -> impl IntoView {
+    let Member(member) = expect_loader_data(load_member);
+    view! { <p>{member}</p> }
+}
+
+#[test]
+fn route_loaders_provide_data_to_matched_views() {
+    let html = Owner::new().with(|| {
+        provide_context(RequestUrl::new("/teams/rust/ferris"));
+        view! {
+            <Router>
+                <Routes fallback=|| "Not found.">
+                    <ParentRoute
+                        path=path!("/teams/:team")
+                        view=with_loader(load_team, TeamPage)
+                    >
+                        <Route
+                            path=path!(":member")
+                            view=with_loader(load_member, MemberPage)
+                        />
+                    </ParentRoute>
+                </Routes>
+            </Router>
+        }
+        .to_html()
+    });
+    assert_eq!(html, "<h1>rust</h1><p>ferris of rust</p>");
+    assert_eq!(TEAM_LOADS.load(Ordering::Relaxed), 1);
+}
+
+#[derive(Clone)]
+struct Title(String);
+
+fn load_section() -> Title {
+    Title(
+        use_params_map()
+            .read_untracked()
+            .get("section")
+            .unwrap_or_default(),
+    )
+}
+
+fn load_page() -> Title {
+    Title(
+        use_params_map()
+            .read_untracked()
+            .get("page")
+            .unwrap_or_default(),
+    )
+}
+
+static VISITS: AtomicUsize = AtomicUsize::new(0);
+
+fn load_visit() -> usize {
+    VISITS.fetch_add(1, Ordering::Relaxed) + 1
+}
+
+#[component]
+fn Section() -> impl IntoView {
+    view! { <Outlet/> }
+}
+
+#[component]
+fn Page() -> impl IntoView {
+    // both loaders return a `Title`, and each can still be read
+    let Title(section) = expect_loader_data(load_section);
+    let Title(page) = expect_loader_data(load_page);
+    view! {
+        <h1>{format!("{section} / {page}")}</h1>
+        <Outlet/>
+    }
+}
+
+#[component]
+fn Visits() -> impl IntoView {
+    let visit = expect_loader_data(load_visit);
+    view! {
+        <p>{visit}</p>
+        <Outlet/>
+    }
+}
+
+#[component]
+fn Visit() -> impl IntoView {
+    let visit = expect_loader_data(load_visit);
+    view! { <p>{visit}</p> }
+}
+
+#[test]
+fn route_loaders_are_keyed_by_loader_and_route() {
+    let html = Owner::new().with(|| {
+        provide_context(RequestUrl::new("/docs/guide/routing/visits"));
+        view! {
+            <Router>
+                <Routes fallback=|| "Not found.">
+                    <ParentRoute
+                        path=path!("/docs/:section")
+                        view=with_loader(load_section, Section)
+                    >
+                        <ParentRoute
+                            path=path!(":page")
+                            view=with_loader(load_page, Page)
+                        >
+                            // a child route that uses the same loader as its parent still calls it
+                            <ParentRoute
+                                path=path!("visits")
+                                view=with_loader(load_visit, Visits)
+                            >
+                                <Route path=path!("") view=with_loader(load_visit, Visit)/>
+                            </ParentRoute>
+                        </ParentRoute>
+                    </ParentRoute>
+                </Routes>
+            </Router>
+        }
+        .to_html()
+    });
+    assert_eq!(html, "<h1>guide / routing</h1><p>1</p><p>2</p>");
+}
+
+#[test]
+fn links_mark_the_current_page() {
+    let html = Owner::new().with(|| {
+        provide_context(RequestUrl::new("/users/42"));
+        view! {
+            <Router>
+                <nav>
+                    <A href="/users" prefetch=Prefetch::Hover>"Users"</A>
+                    <A href="/users" exact=true>"All users"</A>
+                    <A href="/files">"Files"</A>
+                </nav>
+            </Router>
+        }
+        .to_html()
+    });
+    assert_eq!(
+        html,
+        "<nav><a href=\"/users\" aria-current=\"page\">Users</a><a \
+         href=\"/users\">All users</a><a href=\"/files\">Files</a></nav>"
+    );
+}
+
+#[derive(Clone)]
+struct LoggedIn(bool);
+
+async fn require_login() -> Guard {
+    if expect_context::<LoggedIn>().0 {
+        Guard::Allow
+    } else {
+        Guard::Redirect("/login".to_string())
+    }
+}
+
+fn render_guarded(logged_in: bool) -> (String, Option<String>) {
+    let redirected = Arc::new(Mutex::new(None));
+    let html = Owner::new().with(|| {
+        provide_context(RequestUrl::new("/account"));
+        provide_context(LoggedIn(logged_in));
+        provide_server_redirect({
+            let redirected = Arc::clone(&redirected);
+            move |path| *redirected.lock().unwrap() = Some(path.to_string())
+        });
+        view! {
+            <Router>
+                <Routes fallback=|| "Not found.">
+                    <Route path=path!("/login") view=|| "Log in"/>
+                    <Route
+                        path=path!("/account")
+                        view=before_enter(require_login, || "Your account")
+                    />
+                </Routes>
+            </Router>
+        }
+        .to_html()
+    });
+    let redirected = redirected.lock().unwrap().take();
+    (html, redirected)
+}
+
+#[test]
+fn route_guards_allow_or_redirect_on_the_server() {
+    assert_eq!(render_guarded(true), ("Your account".to_string(), None));
+    assert_eq!(
+        render_guarded(false),
+        ("<!>".to_string(), Some("/login".to_string()))
+    );
+}
+
+async fn block() -> Guard {
+    Guard::Block
+}
+
+#[test]
+fn route_guards_that_block_set_a_403_status_on_the_server() {
+    let (html, status) = Owner::new().with(|| {
+        provide_context(RequestUrl::new("/admin"));
+        let res = leptos::response::provide_response_meta();
+        let html = view! {
+            <Router>
+                <Routes fallback=|| "Not found.">
+                    <Route path=path!("/admin") view=before_enter(block, || "Admin")/>
+                </Routes>
+            </Router>
+        }
+        .to_html();
+        (html, res.status())
+    });
+    assert_eq!((html.as_str(), status), ("<!>", Some(403)));
+}
+
+#[server(endpoint = "save_name")]
+async fn save_name(name: String) -> Result<(), ServerFnError> {
+    if name.is_empty() {
+        Err(ServerFnError::ServerError("a name is required".into()))
+    } else {
+        Ok(())
+    }
+}
+
+#[component]
+fn NameForm() -> impl IntoView {
+    let save = ServerAction::<SaveName>::new();
+    let error = move || {
+        save.value()
+            .get()
+            .and_then(Result::err)
+            .map(|err| err.to_string())
+            .unwrap_or_else(|| "No errors.".to_string())
+    };
+    view! {
+        <ActionForm action=save>
+            <input type="text" name="name"/>
+        </ActionForm>
+        <p>{error}</p>
+    }
+}
+
+fn render_name_form(path: &str) -> String {
+    Owner::new().with(|| {
+        provide_context(RequestUrl::new(path));
+        view! {
+            <Router>
+                <Routes fallback=|| "Not found.">
+                    <Route path=path!("/name") view=NameForm/>
+                </Routes>
+            </Router>
+        }
+        .to_html()
+    })
+}
+
+#[test]
+fn action_forms_post_to_the_server_fn_and_show_its_errors() {
+    let form = "<form action=\"/api/save_name\" method=\"post\"><input \
+                type=\"text\" name=\"name\"></form>";
+    assert_eq!(
+        render_name_form("/name"),
+        format!("{form}<p>No errors.</p>")
+    );
+
+    // after a form submission without JS/WASM, the server redirects back to this page with the
+    // error encoded in the URL
+    let err = ServerFnError::<server_fn::error::NoCustomError>::ServerError(
+        "a name is required".into(),
+    )
+    .ser()
+    .unwrap();
+    let path = format!(
+        "/name?__path=/api/save_name&__err={}",
+        leptos_router::location::Url::escape(&err)
+    );
+    assert_eq!(
+        render_name_form(&path),
+        format!(
+            "{form}<p>error running server function: a name is \
+             required</p>"
+        )
+    );
+}
+
+#[component]
+fn StaticApp() -> impl IntoView {
+    view! {
+        <Router>
+            <Routes fallback=|| "Not found.">
+                <Route path=path!("/") view=|| "Home" static_route=StaticRoute::new()/>
+                <Route path=path!("/about") view=|| "About"/>
+                <Route
+                    path=path!("/:lang/posts/:slug")
+                    view=|| "Post"
+                    static_route=StaticRoute::new()
+                        .prerender_params(|| async {
+                            [("lang", vec!["en", "fr"]), ("slug", vec!["hello"])]
+                                .into_iter()
+                                .collect::<StaticParamsMap>()
+                        })
+                />
+                <Route path=path!("/drafts/:id") view=|| "Draft" static_route=StaticRoute::new()/>
+            </Routes>
+        </Router>
+    }
+}
+
+#[test]
+fn static_routes_are_expanded_with_their_params() {
+    let routes = Owner::new().with(|| {
+        provide_context(RequestUrl::new(""));
+        RouteList::generate(|| view! { <StaticApp/> })
+    });
+    let routes = routes.unwrap().into_inner();
+    assert_eq!(routes.len(), 4);
+
+    let paths = routes
+        .iter()
+        .map(|route| {
+            futures::executor::block_on(async {
+                match route.static_route() {
+                    Some(_) => Some(route.static_paths().await),
+                    None => None,
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        [
+            Some(vec!["/".to_string()]),
+            None,
+            Some(vec![
+                "/en/posts/hello".to_string(),
+                "/fr/posts/hello".into()
+            ]),
+            // a parameter without any values can't be prerendered
+            Some(vec![]),
+        ]
+    );
+    assert_eq!(routes[0].static_mode(), Some(StaticMode::Upfront));
+    assert_eq!(routes[1].static_mode(), None);
+}
+
+#[component]
+fn SitemapApp() -> impl IntoView {
+    view! {
+        <Router>
+            <Routes fallback=|| "Not found.">
+                <Route
+                    path=path!("/")
+                    view=|| "Home"
+                    sitemap=SitemapRoute::new()
+                        .priority(1.0)
+                        .change_frequency(ChangeFrequency::Daily)
+                        .last_modified("2024-07-01")
+                />
+                <Route path=path!("/search") view=|| "Search" sitemap=SitemapRoute::exclude()/>
+                <ParentRoute path=path!("/admin") view=|| view! { <Outlet/> } sitemap=SitemapRoute::disallow()>
+                    <Route path=path!("") view=|| "Dashboard"/>
+                    <Route path=path!(":id") view=|| "User"/>
+                </ParentRoute>
+                <Route path=path!("/users/:id") view=|| "User"/>
+                <Route
+                    path=path!("/posts/:slug")
+                    view=|| "Post"
+                    static_route=StaticRoute::new()
+                        .prerender_params(|| async {
+                            [("slug", vec!["a&b"])].into_iter().collect::<StaticParamsMap>()
+                        })
+                />
+            </Routes>
+        </Router>
+    }
+}
+
+#[test]
+fn sitemaps_list_routes_with_their_options() {
+    let routes = Owner::new().with(|| {
+        provide_context(RequestUrl::new(""));
+        RouteList::generate(|| view! { <SitemapApp/> })
+    });
+    let mut sitemap = Sitemap::new("https://leptos.dev/");
+    futures::executor::block_on(async {
+        for route in routes.unwrap().into_inner() {
+            sitemap
+                .add_route(route.path(), route.static_route(), route.sitemap())
+                .await;
+        }
+    });
+    assert_eq!(
+        sitemap.to_xml(),
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset \
+         xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n\
+         <url><loc>https://leptos.dev/</loc><lastmod>2024-07-01</lastmod>\
+         <changefreq>daily</changefreq><priority>1.0</priority></url>\n\
+         <url><loc>https://leptos.dev/posts/a&amp;b</loc></url>\n\
+         </urlset>\n"
+    );
+    assert_eq!(
+        sitemap.robots_txt(),
+        "User-agent: *\nDisallow: /admin\nDisallow: /admin/\n\nSitemap: \
+         https://leptos.dev/sitemap.xml\n"
+    );
+}
+
+#[component]
+fn CanonicalUrl() -> impl IntoView {
+    let url = use_canonical_url();
+    view! { <p>{move || url.get()}</p> }
+}
+
+fn render_with_trailing_slash(
+    path: &str,
+    trailing_slash: TrailingSlash,
+) -> (String, Option<String>) {
+    let redirected = Arc::new(Mutex::new(None));
+    let html = Owner::new().with(|| {
+        provide_context(RequestUrl::new(path));
+        provide_server_redirect({
+            let redirected = Arc::clone(&redirected);
+            move |path| *redirected.lock().unwrap() = Some(path.to_string())
+        });
+        view! {
+            <Router trailing_slash site_url="https://leptos.dev/">
+                <CanonicalUrl/>
+            </Router>
+        }
+        .to_html()
+    });
+    let redirected = redirected.lock().unwrap().take();
+    (html, redirected)
+}
+
+#[test]
+fn trailing_slashes_are_redirected_to_the_canonical_url() {
+    assert_eq!(
+        render_with_trailing_slash("/about/?x=1", TrailingSlash::Remove),
+        (
+            "<p>https://leptos.dev/about</p>".to_string(),
+            Some("/about?x=1".to_string())
+        )
+    );
+    assert_eq!(
+        render_with_trailing_slash("/about", TrailingSlash::Add),
+        (
+            "<p>https://leptos.dev/about/</p>".to_string(),
+            Some("/about/".to_string())
+        )
+    );
+    assert_eq!(
+        render_with_trailing_slash("/about/", TrailingSlash::Ignore),
+        ("<p>https://leptos.dev/about/</p>".to_string(), None)
+    );
+    assert_eq!(
+        render_with_trailing_slash("/", TrailingSlash::Remove),
+        ("<p>https://leptos.dev/</p>".to_string(), None)
+    );
+}
+
+fn render_status(path: &str) -> (String, Option<u16>) {
+    Owner::new().with(|| {
+        provide_context(RequestUrl::new(path));
+        let res = leptos::response::provide_response_meta();
+        let html = view! {
+            <Router>
+                <Routes fallback=|| "Not found.">
+                    <Route path=path!("/") view=|| "Home"/>
+                    <Route
+                        path=path!("/docs/*any")
+                        view=|| view! { <NotFound>"No such page."</NotFound> }
+                    />
+                </Routes>
+            </Router>
+        }
+        .to_html();
+        (html, res.status())
+    })
+}
+
+#[test]
+fn unmatched_paths_render_the_fallback_with_a_404_status() {
+    assert_eq!(render_status("/"), ("Home".to_string(), None));
+    assert_eq!(
+        render_status("/missing"),
+        ("Not found.".to_string(), Some(404))
+    );
+    assert_eq!(
+        render_status("/docs/missing"),
+        ("No such page.".to_string(), Some(404))
+    );
+}
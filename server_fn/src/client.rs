@@ -39,11 +39,18 @@ pub mod browser {
     use super::Client;
     use crate::{
         error::ServerFnError,
-        request::browser::{BrowserRequest, RequestInner},
+        request::browser::{BrowserRequest, OnProgress, RequestInner},
         response::browser::BrowserResponse,
     };
+    use futures::channel::oneshot;
+    use gloo_net::http::{Headers, Request, Response};
     use send_wrapper::SendWrapper;
-    use std::future::Future;
+    use std::{cell::RefCell, future::Future, rc::Rc};
+    use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+    use web_sys::{
+        Blob, FormData, ProgressEvent, XmlHttpRequest,
+        XmlHttpRequestResponseType,
+    };
 
     /// Implements [`Client`] for a `fetch` request in the browser.    
     pub struct BrowserClient;
@@ -61,12 +68,22 @@ pub mod browser {
                 let RequestInner {
                     request,
                     mut abort_ctrl,
+                    upload,
                 } = req;
-                let res = request
-                    .send()
-                    .await
-                    .map(|res| BrowserResponse(SendWrapper::new(res)))
-                    .map_err(|e| ServerFnError::Request(e.to_string()));
+                let res = match upload {
+                    Some((body, on_progress)) => {
+                        send_with_progress(request, body, on_progress)
+                            .await
+                            .map_err(|e| {
+                                ServerFnError::Request(format!("{e:?}"))
+                            })
+                    }
+                    None => request
+                        .send()
+                        .await
+                        .map_err(|e| ServerFnError::Request(e.to_string())),
+                }
+                .map(|res| BrowserResponse(SendWrapper::new(res)));
 
                 // at this point, the future has successfully resolved without being dropped, so we
                 // can prevent the `AbortController` from firing
@@ -77,6 +94,83 @@ pub mod browser {
             })
         }
     }
+
+    // aborts an upload if its future is dropped before it has completed
+    struct AbortXhrOnDrop(Option<XmlHttpRequest>);
+
+    impl Drop for AbortXhrOnDrop {
+        fn drop(&mut self) {
+            if let Some(xhr) = self.0.take() {
+                _ = xhr.abort();
+            }
+        }
+    }
+
+    // `fetch` cannot report the progress of an upload, so multipart requests that do are sent
+    // with `XMLHttpRequest` instead
+    async fn send_with_progress(
+        request: Request,
+        body: FormData,
+        on_progress: OnProgress,
+    ) -> Result<Response, JsValue> {
+        let xhr = XmlHttpRequest::new()?;
+        xhr.open_with_async(request.method().as_ref(), &request.url(), true)?;
+        for (name, value) in request.headers().entries() {
+            xhr.set_request_header(&name, &value)?;
+        }
+        xhr.set_response_type(XmlHttpRequestResponseType::Blob);
+
+        let on_progress =
+            Closure::<dyn Fn(ProgressEvent)>::new(move |ev: ProgressEvent| {
+                if ev.length_computable() {
+                    (on_progress.0)(ev.loaded() as u64, ev.total() as u64);
+                }
+            });
+        xhr.upload()?
+            .set_onprogress(Some(on_progress.as_ref().unchecked_ref()));
+
+        let (tx, rx) = oneshot::channel();
+        let tx = Rc::new(RefCell::new(Some(tx)));
+        let on_done = |loaded: bool| {
+            let tx = Rc::clone(&tx);
+            Closure::<dyn Fn()>::new(move || {
+                if let Some(tx) = tx.take() {
+                    _ = tx.send(loaded);
+                }
+            })
+        };
+        let on_load = on_done(true);
+        let on_error = on_done(false);
+        xhr.set_onload(Some(on_load.as_ref().unchecked_ref()));
+        xhr.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+
+        xhr.send_with_opt_form_data(Some(&body))?;
+        let mut pending = AbortXhrOnDrop(Some(xhr.clone()));
+        let loaded = rx.await.unwrap_or(false);
+        pending.0.take();
+        if !loaded {
+            return Err(JsValue::from_str("the upload failed"));
+        }
+
+        let headers = Headers::new();
+        let raw_headers = xhr.get_all_response_headers()?;
+        for line in raw_headers.split("\r\n") {
+            if let Some((name, value)) = line.split_once(':') {
+                headers.append(name.trim(), value.trim());
+            }
+        }
+        let status = xhr.status()?;
+        // responses with these statuses cannot be constructed with a body
+        let body = match status {
+            204 | 205 | 304 => None,
+            _ => xhr.response()?.dyn_into::<Blob>().ok(),
+        };
+        Response::builder()
+            .status(status)
+            .headers(headers)
+            .body(body.as_ref())
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
 }
 
 #[cfg(feature = "reqwest")]
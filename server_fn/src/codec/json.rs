@@ -6,10 +6,11 @@ use crate::{
     IntoReq, IntoRes,
 };
 use bytes::Bytes;
-use futures::{Stream, StreamExt};
+use futures::{stream, Stream, StreamExt};
 use http::Method;
 use serde::{de::DeserializeOwned, Serialize};
-use std::pin::Pin;
+use std::{mem, pin::Pin};
+
 /// Pass arguments and receive responses as JSON in the body of a `POST` request.
 pub struct Json;
 
@@ -72,7 +73,10 @@ where
 
 /// An encoding that represents a stream of JSON data.
 ///
-/// A server function that uses this as its output encoding should return [`StreamingJson`]
+/// A server function that uses this as its output encoding should return [`JsonStream`].
+///
+/// Each item is sent as a JSON value followed by a newline, so that the receiver can decode
+/// each item as soon as it has arrived, however the stream has been split up in transit.
 ///
 /// ## Browser Support for Streaming Input
 ///
@@ -85,14 +89,27 @@ where
 pub struct StreamingJson;
 
 impl Encoding for StreamingJson {
-    // Each chunk is encoded as a JSON object, but the overall stream is not valid JSON so this uses the default stream content type
+    // Each item is encoded as a JSON value, but the overall stream is not valid JSON so this uses the default stream content type
     const CONTENT_TYPE: &'static str = Streaming::CONTENT_TYPE;
     const METHOD: Method = Streaming::METHOD;
 }
 
 /// A stream of typed data encoded as JSON.
 ///
-/// A server function can return this type if its output encoding is [`StreamingJson`].
+/// A server function can return this type if its output encoding is [`StreamingJson`]. On the
+/// client, [`JsonStream::into_inner`] returns a stream that yields each item as it arrives.
+///
+/// ```rust,ignore
+/// #[server(output = StreamingJson)]
+/// pub async fn progress() -> Result<JsonStream<u8>, ServerFnError> {
+///     Ok(JsonStream::from(futures::stream::iter(0..=100)))
+/// }
+///
+/// let mut progress = progress().await?.into_inner();
+/// while let Some(percent) = progress.next().await {
+///     println!("{}%", percent?);
+/// }
+/// ```
 ///
 /// ## Browser Support for Streaming Input
 ///
@@ -113,7 +130,7 @@ impl<T, CustErr> std::fmt::Debug for JsonStream<T, CustErr> {
 }
 
 impl<T> JsonStream<T> {
-    /// Creates a new `ByteStream` from the given stream.
+    /// Creates a new `JsonStream` from the given stream.
     pub fn new(
         value: impl Stream<Item = Result<T, ServerFnError>> + Send + 'static,
     ) -> Self {
@@ -122,7 +139,7 @@ impl<T> JsonStream<T> {
 }
 
 impl<T, CustErr> JsonStream<T, CustErr> {
-    /// Consumes the wrapper, returning a stream of text.
+    /// Consumes the wrapper, returning a stream of values.
     pub fn into_inner(
         self,
     ) -> impl Stream<Item = Result<T, ServerFnError<CustErr>>> + Send {
@@ -156,9 +173,9 @@ where
             accepts,
             Streaming::CONTENT_TYPE,
             data.0.map(|chunk| {
-                serde_json::to_vec(&chunk)
-                    .unwrap_or_else(|_| Vec::new())
-                    .into()
+                chunk
+                    .and_then(|value| to_json_line(&value))
+                    .unwrap_or_default()
             }),
         )
     }
@@ -172,7 +189,7 @@ where
     T: DeserializeOwned + 'static,
 {
     async fn from_req(req: Request) -> Result<Self, ServerFnError<CustErr>> {
-        let data = req.try_into_stream()?;
+        let data = split_lines(req.try_into_stream()?);
         let s = JsonStream::new(data.map(|chunk| {
             chunk.and_then(|bytes| {
                 serde_json::from_slice(bytes.as_ref())
@@ -193,11 +210,7 @@ where
     async fn into_res(self) -> Result<Response, ServerFnError<CustErr>> {
         Response::try_from_stream(
             Streaming::CONTENT_TYPE,
-            self.into_inner().map(|value| {
-                serde_json::to_vec(&value?)
-                    .map(Bytes::from)
-                    .map_err(|e| ServerFnError::Serialization(e.to_string()))
-            }),
+            self.into_inner().map(|value| to_json_line(&value?)),
         )
     }
 }
@@ -209,7 +222,7 @@ where
     T: DeserializeOwned,
 {
     async fn from_res(res: Response) -> Result<Self, ServerFnError<CustErr>> {
-        let stream = res.try_into_stream()?;
+        let stream = split_lines(res.try_into_stream()?);
         Ok(JsonStream::new(stream.map(|chunk| {
             chunk.and_then(|bytes| {
                 serde_json::from_slice(bytes.as_ref())
@@ -218,3 +231,61 @@ where
         })))
     }
 }
+
+fn to_json_line<CustErr>(
+    value: &impl Serialize,
+) -> Result<Bytes, ServerFnError<CustErr>> {
+    let mut data = serde_json::to_vec(value)
+        .map_err(|e| ServerFnError::Serialization(e.to_string()))?;
+    data.push(b'\n');
+    Ok(data.into())
+}
+
+// the chunks of a streaming body do not line up with the items that were sent, so they are
+// buffered and split into one chunk per line
+fn split_lines(
+    stream: impl Stream<Item = Result<Bytes, ServerFnError>> + Send + 'static,
+) -> impl Stream<Item = Result<Bytes, ServerFnError>> + Send + 'static {
+    stream::unfold(
+        (Box::pin(stream), Vec::new(), false),
+        |(mut stream, mut buf, mut done)| async move {
+            loop {
+                if let Some(end) = buf.iter().position(|byte| *byte == b'\n') {
+                    let line = buf.drain(..=end).collect::<Vec<_>>();
+                    return Some((Ok(line.into()), (stream, buf, done)));
+                }
+                if done {
+                    if buf.iter().all(u8::is_ascii_whitespace) {
+                        return None;
+                    }
+                    let line = mem::take(&mut buf);
+                    return Some((Ok(line.into()), (stream, buf, done)));
+                }
+                match stream.next().await {
+                    Some(Ok(chunk)) => buf.extend_from_slice(&chunk),
+                    Some(Err(e)) => return Some((Err(e), (stream, buf, done))),
+                    None => done = true,
+                }
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_lines;
+    use bytes::Bytes;
+    use futures::{executor::block_on, stream, StreamExt};
+
+    #[test]
+    fn split_lines_reassembles_items_across_chunks() {
+        let chunks = ["{\"a\":", "1}\n{\"a\":2}\n{\"a", "\":3}"]
+            .map(|chunk| Ok(Bytes::from(chunk)));
+        let lines = block_on(
+            split_lines(stream::iter(chunks))
+                .map(|line| String::from_utf8(line.unwrap().to_vec()).unwrap())
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(lines, ["{\"a\":1}\n", "{\"a\":2}\n", "{\"a\":3}"]);
+    }
+}
@@ -39,6 +39,24 @@ impl MultipartData {
         }
     }
 
+    /// Reports the progress of the upload on the client, by calling `on_progress` with the number
+    /// of bytes that have been sent and the total size of the upload each time the browser
+    /// reports progress. This can be used to update a signal that drives a progress bar.
+    ///
+    /// `fetch` cannot report the progress of an upload, so the request is sent with
+    /// `XMLHttpRequest` instead. On the server, this does nothing.
+    pub fn with_progress(
+        self,
+        on_progress: impl Fn(u64, u64) + 'static,
+    ) -> Self {
+        match self {
+            MultipartData::Client(data) => {
+                MultipartData::Client(data.with_progress(on_progress))
+            }
+            MultipartData::Server(data) => MultipartData::Server(data),
+        }
+    }
+
     /// Extracts the inner form data on the client side.
     ///
     /// On the server side, this always returns `None`. On the client side, always returns `Some(_)`.
@@ -1,4 +1,4 @@
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{
     fmt,
     fmt::{Display, Write},
@@ -52,6 +52,74 @@ impl FromStr for NoCustomError {
     }
 }
 
+/// A custom server function error type that is sent to the client as JSON, so that any type that
+/// implements [`Serialize`] and [`Deserialize`] can be used as the custom error of a
+/// [`ServerFnError`], without implementing [`FromStr`] and [`Display`] for it.
+///
+/// The client receives the same value that the server function returned, so it can match on it,
+/// for example to show a different message for each variant of an error enum. Because the
+/// [`Display`] implementation of this type is the JSON representation of the error, it is
+/// usually better to match on the inner value than to show it directly. A
+/// `ServerFnError<TypedError<E>>` implements [`Error`](trait@std::error::Error), so it can also be
+/// thrown to an error boundary, which can get it back with `downcast_ref`.
+///
+/// ```rust
+/// # use serde::{Deserialize, Serialize};
+/// # use server_fn::{error::TypedError, ServerFnError};
+/// #[derive(Debug, Clone, Serialize, Deserialize)]
+/// pub enum LoginError {
+///     UnknownUser,
+///     WrongPassword { attempts_left: u8 },
+/// }
+///
+/// // in a server function that returns `Result<_, ServerFnError<TypedError<LoginError>>>`,
+/// // a `LoginError` can be returned with `?`
+/// fn check_password() -> Result<(), ServerFnError<TypedError<LoginError>>> {
+///     Err(LoginError::WrongPassword { attempts_left: 2 })?
+/// }
+///
+/// match check_password() {
+///     Err(ServerFnError::WrappedServerError(TypedError(
+///         LoginError::WrongPassword { attempts_left },
+///     ))) => assert_eq!(attempts_left, 2),
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct TypedError<E>(pub E);
+
+impl<E> Display for TypedError<E>
+where
+    E: Serialize,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let data = serde_json::to_string(&self.0).map_err(|_| fmt::Error)?;
+        f.write_str(&data)
+    }
+}
+
+impl<E> FromStr for TypedError<E>
+where
+    E: DeserializeOwned,
+{
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s).map(TypedError)
+    }
+}
+
+impl<E> std::error::Error for TypedError<E> where E: fmt::Debug + Serialize {}
+
+impl<E> From<E> for ServerFnError<TypedError<E>> {
+    fn from(value: E) -> Self {
+        ServerFnError::WrappedServerError(TypedError(value))
+    }
+}
+
 /// Wraps some error type, which may implement any of [`Error`](trait@std::error::Error), [`Clone`], or
 /// [`Display`].
 #[derive(Debug)]
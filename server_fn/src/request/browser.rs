@@ -5,7 +5,11 @@ use futures::{Stream, StreamExt};
 pub use gloo_net::http::Request;
 use js_sys::{Reflect, Uint8Array};
 use send_wrapper::SendWrapper;
-use std::ops::{Deref, DerefMut};
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+    rc::Rc,
+};
 use wasm_bindgen::JsValue;
 use wasm_streams::ReadableStream;
 use web_sys::{
@@ -21,6 +25,8 @@ pub struct BrowserRequest(pub(crate) SendWrapper<RequestInner>);
 pub(crate) struct RequestInner {
     pub(crate) request: Request,
     pub(crate) abort_ctrl: Option<AbortOnDrop>,
+    /// A multipart body whose upload progress should be reported.
+    pub(crate) upload: Option<(FormData, OnProgress)>,
 }
 
 #[derive(Debug)]
@@ -69,11 +75,39 @@ impl DerefMut for BrowserRequest {
 
 /// The `FormData` type available in the browser.
 #[derive(Debug)]
-pub struct BrowserFormData(pub(crate) SendWrapper<FormData>);
+pub struct BrowserFormData(
+    pub(crate) SendWrapper<FormData>,
+    pub(crate) Option<OnProgress>,
+);
+
+impl BrowserFormData {
+    /// Sends this form data with `XMLHttpRequest` rather than `fetch`, and calls `on_progress`
+    /// with the number of bytes that have been sent and the total size of the upload each time
+    /// the browser reports progress.
+    pub fn with_progress(
+        self,
+        on_progress: impl Fn(u64, u64) + 'static,
+    ) -> Self {
+        Self(
+            self.0,
+            Some(OnProgress(SendWrapper::new(Rc::new(on_progress)))),
+        )
+    }
+}
 
 impl From<FormData> for BrowserFormData {
     fn from(value: FormData) -> Self {
-        Self(SendWrapper::new(value))
+        Self(SendWrapper::new(value), None)
+    }
+}
+
+/// Called with the number of bytes of an upload that have been sent, and its total size.
+#[derive(Clone)]
+pub(crate) struct OnProgress(pub(crate) SendWrapper<Rc<dyn Fn(u64, u64)>>);
+
+impl fmt::Debug for OnProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OnProgress").finish_non_exhaustive()
     }
 }
 
@@ -109,6 +143,7 @@ impl<CustErr> ClientReq<CustErr> for BrowserRequest {
                 .build()
                 .map_err(|e| ServerFnError::Request(e.to_string()))?,
             abort_ctrl,
+            upload: None,
         })))
     }
 
@@ -131,6 +166,7 @@ impl<CustErr> ClientReq<CustErr> for BrowserRequest {
                 .body(body)
                 .map_err(|e| ServerFnError::Request(e.to_string()))?,
             abort_ctrl,
+            upload: None,
         })))
     }
 
@@ -155,6 +191,7 @@ impl<CustErr> ClientReq<CustErr> for BrowserRequest {
                 .body(body)
                 .map_err(|e| ServerFnError::Request(e.to_string()))?,
             abort_ctrl,
+            upload: None,
         })))
     }
 
@@ -168,13 +205,16 @@ impl<CustErr> ClientReq<CustErr> for BrowserRequest {
         let mut url = String::with_capacity(server_url.len() + path.len());
         url.push_str(server_url);
         url.push_str(path);
+        let BrowserFormData(form_data, on_progress) = body;
+        let form_data = form_data.take();
         Ok(Self(SendWrapper::new(RequestInner {
             request: Request::post(&url)
                 .header("Accept", accepts)
                 .abort_signal(abort_signal.as_ref())
-                .body(body.0.take())
+                .body(form_data.clone())
                 .map_err(|e| ServerFnError::Request(e.to_string()))?,
             abort_ctrl,
+            upload: on_progress.map(|on_progress| (form_data, on_progress)),
         })))
     }
 
@@ -204,6 +244,7 @@ impl<CustErr> ClientReq<CustErr> for BrowserRequest {
                 .body(url_params)
                 .map_err(|e| ServerFnError::Request(e.to_string()))?,
             abort_ctrl,
+            upload: None,
         })))
     }
 
@@ -220,6 +261,7 @@ impl<CustErr> ClientReq<CustErr> for BrowserRequest {
         Ok(Self(SendWrapper::new(RequestInner {
             request,
             abort_ctrl,
+            upload: None,
         })))
     }
 }
@@ -47,10 +47,17 @@ where
 /// Helper function to extract `event.target.value` from an event.
 ///
 /// This is useful in the `on:input` or `on:change` listeners for an `<input>` element.
+///
+/// Outside the browser, this reads the `value` attribute of the target of the event that is
+/// being dispatched in the [`MockDom`](crate::renderer::mock_dom::MockDom).
 pub fn event_target_value<T>(event: &T) -> String
 where
     T: JsCast,
 {
+    #[cfg(all(feature = "testing", not(target_arch = "wasm32")))]
+    if let Some(target) = crate::renderer::mock_dom::current_event_target() {
+        return target.attribute("value").unwrap_or_default();
+    }
     event
         .unchecked_ref::<web_sys::Event>()
         .target()
@@ -62,7 +69,14 @@ where
 /// Helper function to extract `event.target.checked` from an event.
 ///
 /// This is useful in the `on:change` listeners for an `<input type="checkbox">` element.
+///
+/// Outside the browser, this checks for a `checked` attribute on the target of the event that
+/// is being dispatched in the [`MockDom`](crate::renderer::mock_dom::MockDom).
 pub fn event_target_checked(ev: &web_sys::Event) -> bool {
+    #[cfg(all(feature = "testing", not(target_arch = "wasm32")))]
+    if let Some(target) = crate::renderer::mock_dom::current_event_target() {
+        return target.attribute("checked").is_some();
+    }
     ev.target()
         .unwrap()
         .unchecked_into::<web_sys::HtmlInputElement>()
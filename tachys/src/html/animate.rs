@@ -0,0 +1,451 @@
+use super::{
+    attribute::Attribute,
+    element::{CreateElement, ElementState, ElementType, HtmlElement},
+};
+use crate::{
+    hydration::Cursor,
+    renderer::{DomRenderer, RemoveEventHandler},
+    ssr::StreamBuilder,
+    view::{
+        add_attr::AddAnyAttr, Mountable, Position, PositionState, Render,
+        RenderHtml,
+    },
+};
+use futures::{
+    channel::oneshot,
+    future::{self, Either},
+};
+use std::{cell::RefCell, pin::pin, rc::Rc, time::Duration};
+
+// how long an exit waits for its animation or transition to end by default
+const EXIT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Wraps an element so that it animates in when it is mounted and out when it is unmounted.
+///
+/// The `enter` class is added each time the element is mounted, and removed again when its
+/// animation or transition ends. When the element is unmounted, the `exit` class is added, and
+/// the element is only removed once the animation or transition it triggers has ended. If that
+/// has not happened after the [`exit_timeout`](Animated::exit_timeout), which is one second by
+/// default, the element is removed anyway, so an `exit` class that starts no animation or
+/// transition only delays the removal.
+///
+/// If the element is mounted again before its exit animation has finished, the exit is cancelled.
+/// Only animations and transitions on the element itself count: those that bubble up from its
+/// children are ignored.
+pub fn animated<E, At, Ch, R>(
+    el: HtmlElement<E, At, Ch, R>,
+    enter: &'static str,
+    exit: &'static str,
+) -> Animated<E, At, Ch, R> {
+    Animated {
+        el,
+        enter,
+        exit,
+        exit_timeout: EXIT_TIMEOUT,
+    }
+}
+
+/// An element with enter and exit animations. See [`animated`].
+pub struct Animated<E, At, Ch, R> {
+    el: HtmlElement<E, At, Ch, R>,
+    enter: &'static str,
+    exit: &'static str,
+    exit_timeout: Duration,
+}
+
+impl<E, At, Ch, R> Animated<E, At, Ch, R> {
+    /// Sets how long to wait for the exit animation or transition to end before the element is
+    /// removed anyway. This should be longer than the exit animation.
+    pub fn exit_timeout(mut self, timeout: Duration) -> Self {
+        self.exit_timeout = timeout;
+        self
+    }
+}
+
+/// The view state for an [`Animated`] element.
+pub struct AnimatedState<At, Ch, R>
+where
+    R: DomRenderer,
+{
+    state: ElementState<At, Ch, R>,
+    enter: &'static str,
+    exit: &'static str,
+    exit_timeout: Duration,
+    exit_done: Rc<RefCell<Option<oneshot::Sender<()>>>>,
+    handlers: Vec<RemoveEventHandler<R::Element>>,
+    // listeners handed over to a pending exit when the state is dropped before it finishes
+    orphaned_handlers: Rc<RefCell<Vec<RemoveEventHandler<R::Element>>>>,
+}
+
+impl<At, Ch, R> AnimatedState<At, Ch, R>
+where
+    R: DomRenderer,
+{
+    fn new(
+        state: ElementState<At, Ch, R>,
+        enter: &'static str,
+        exit: &'static str,
+        exit_timeout: Duration,
+    ) -> Self {
+        let exit_done = Rc::new(RefCell::new(None::<oneshot::Sender<()>>));
+        let handlers = ["animationend", "transitionend"]
+            .into_iter()
+            .map(|event| {
+                let el = state.clone();
+                let exit_done = Rc::clone(&exit_done);
+                R::add_event_listener(
+                    &state,
+                    event,
+                    Box::new(move |ev| {
+                        if !R::is_event_target(&ev, &el) {
+                            return;
+                        }
+                        R::remove_class(&R::class_list(&el), enter);
+                        if let Some(done) = exit_done.borrow_mut().take() {
+                            _ = done.send(());
+                        }
+                    }),
+                )
+            })
+            .collect();
+        Self {
+            state,
+            enter,
+            exit,
+            exit_timeout,
+            exit_done,
+            handlers,
+            orphaned_handlers: Default::default(),
+        }
+    }
+}
+
+impl<At, Ch, R> Drop for AnimatedState<At, Ch, R>
+where
+    R: DomRenderer,
+{
+    fn drop(&mut self) {
+        let handlers = self.handlers.drain(..);
+        if self.exit_done.borrow().is_some() {
+            // the listeners are still needed to finish the exit, which removes them afterwards
+            self.orphaned_handlers.borrow_mut().extend(handlers);
+        } else {
+            for handler in handlers {
+                (handler.into_inner())(&self.state);
+            }
+        }
+    }
+}
+
+impl<E, At, Ch, R> Render<R> for Animated<E, At, Ch, R>
+where
+    E: ElementType + CreateElement<R>,
+    At: Attribute<R>,
+    Ch: Render<R>,
+    R: DomRenderer,
+{
+    type State = AnimatedState<At::State, Ch::State, R>;
+
+    fn build(self) -> Self::State {
+        AnimatedState::new(
+            self.el.build(),
+            self.enter,
+            self.exit,
+            self.exit_timeout,
+        )
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        self.el.rebuild(&mut state.state);
+    }
+}
+
+impl<At, Ch, R> Mountable<R> for AnimatedState<At, Ch, R>
+where
+    R: DomRenderer,
+{
+    fn unmount(&mut self) {
+        let class_list = R::class_list(&self.state);
+        R::remove_class(&class_list, self.enter);
+        R::add_class(&class_list, self.exit);
+
+        let (tx, rx) = oneshot::channel();
+        // replacing an earlier sender cancels its removal
+        *self.exit_done.borrow_mut() = Some(tx);
+        let el = self.state.clone();
+        let orphaned_handlers = Rc::clone(&self.orphaned_handlers);
+        let timeout = R::sleep(self.exit_timeout);
+        R::remove_after(&self.state, async move {
+            // an error means the exit was cancelled by mounting the element again, while the
+            // timeout removes the element if its exit never starts an animation or transition
+            let done = match future::select(rx, pin!(timeout)).await {
+                Either::Left((res, _)) => res.is_ok(),
+                Either::Right(_) => true,
+            };
+            for handler in orphaned_handlers.take() {
+                (handler.into_inner())(&el);
+            }
+            done
+        });
+    }
+
+    fn mount(&mut self, parent: &R::Element, marker: Option<&R::Node>) {
+        self.exit_done.borrow_mut().take();
+        let class_list = R::class_list(&self.state);
+        R::remove_class(&class_list, self.exit);
+        self.state.mount(parent, marker);
+        R::add_class(&class_list, self.enter);
+    }
+
+    fn insert_before_this(&self, child: &mut dyn Mountable<R>) -> bool {
+        self.state.insert_before_this(child)
+    }
+}
+
+impl<E, At, Ch, R> AddAnyAttr<R> for Animated<E, At, Ch, R>
+where
+    E: ElementType + CreateElement<R> + Send,
+    At: Attribute<R> + Send,
+    Ch: RenderHtml<R> + Send,
+    R: DomRenderer,
+{
+    type Output<SomeNewAttr: Attribute<R>> = Animated<
+        E,
+        <At as crate::html::attribute::NextAttribute<R>>::Output<SomeNewAttr>,
+        Ch,
+        R,
+    >;
+
+    fn add_any_attr<NewAttr: Attribute<R>>(
+        self,
+        attr: NewAttr,
+    ) -> Self::Output<NewAttr>
+    where
+        Self::Output<NewAttr>: RenderHtml<R>,
+    {
+        Animated {
+            el: self.el.add_any_attr(attr),
+            enter: self.enter,
+            exit: self.exit,
+            exit_timeout: self.exit_timeout,
+        }
+    }
+}
+
+impl<E, At, Ch, R> RenderHtml<R> for Animated<E, At, Ch, R>
+where
+    E: ElementType + CreateElement<R> + Send,
+    At: Attribute<R> + Send,
+    Ch: RenderHtml<R> + Send,
+    R: DomRenderer,
+{
+    type AsyncOutput = Animated<E, At::AsyncOutput, Ch::AsyncOutput, R>;
+
+    const MIN_LENGTH: usize = HtmlElement::<E, At, Ch, R>::MIN_LENGTH;
+
+    fn html_len(&self) -> usize {
+        self.el.html_len()
+    }
+
+    fn dry_resolve(&mut self) {
+        self.el.dry_resolve();
+    }
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        Animated {
+            el: self.el.resolve().await,
+            enter: self.enter,
+            exit: self.exit,
+            exit_timeout: self.exit_timeout,
+        }
+    }
+
+    fn to_html_with_buf(
+        self,
+        buf: &mut String,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: bool,
+    ) {
+        self.el
+            .to_html_with_buf(buf, position, escape, mark_branches);
+    }
+
+    fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+        self,
+        buf: &mut StreamBuilder,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: bool,
+    ) {
+        self.el.to_html_async_with_buf::<OUT_OF_ORDER>(
+            buf,
+            position,
+            escape,
+            mark_branches,
+        );
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        cursor: &Cursor<R>,
+        position: &PositionState,
+    ) -> Self::State {
+        // an element that was rendered on the server is already visible, so it does not animate in
+        AnimatedState::new(
+            self.el.hydrate::<FROM_SERVER>(cursor, position),
+            self.enter,
+            self.exit,
+            self.exit_timeout,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::animated;
+    use crate::{
+        html::element::{div, p, Div, ElementChild},
+        renderer::{
+            mock_dom::{Element, MockDom},
+            CastFrom, Renderer,
+        },
+        view::{Mountable, Render},
+    };
+    use any_spawner::Executor;
+
+    #[tokio::test]
+    async fn element_stays_mounted_until_exit_animation_ends() {
+        _ = Executor::init_tokio();
+        tokio::task::LocalSet::new()
+            .run_until(async {
+                let parent = MockDom::create_element(Div);
+                let mut state =
+                    animated::<_, _, _, MockDom>(p().child("hi"), "in", "out")
+                        .build();
+                state.mount(&parent, None);
+                assert_eq!(
+                    parent.to_debug_html(),
+                    "<div><p class=\"in\">hi</p></div>"
+                );
+
+                MockDom::dispatch_event(&state.state, "animationend");
+                assert_eq!(parent.to_debug_html(), "<div><p>hi</p></div>");
+
+                state.unmount();
+                tokio::task::yield_now().await;
+                assert_eq!(
+                    parent.to_debug_html(),
+                    "<div><p class=\"out\">hi</p></div>"
+                );
+
+                MockDom::dispatch_event(&state.state, "animationend");
+                tokio::task::yield_now().await;
+                assert_eq!(parent.to_debug_html(), "<div></div>");
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn mounting_again_cancels_the_exit() {
+        _ = Executor::init_tokio();
+        tokio::task::LocalSet::new()
+            .run_until(async {
+                let parent = MockDom::create_element(Div);
+                let mut state =
+                    animated::<_, _, _, MockDom>(p().child("hi"), "in", "out")
+                        .build();
+                state.mount(&parent, None);
+                state.unmount();
+                state.mount(&parent, None);
+                tokio::task::yield_now().await;
+
+                MockDom::dispatch_event(&state.state, "animationend");
+                tokio::task::yield_now().await;
+                assert_eq!(parent.to_debug_html(), "<div><p>hi</p></div>");
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn dropping_the_state_during_the_exit_still_removes_the_element() {
+        _ = Executor::init_tokio();
+        tokio::task::LocalSet::new()
+            .run_until(async {
+                let parent = MockDom::create_element(Div);
+                let mut state =
+                    animated::<_, _, _, MockDom>(p().child("hi"), "in", "out")
+                        .build();
+                state.mount(&parent, None);
+                state.unmount();
+                let el = state.state.clone();
+                drop(state);
+                tokio::task::yield_now().await;
+                assert_eq!(
+                    parent.to_debug_html(),
+                    "<div><p class=\"out\">hi</p></div>"
+                );
+
+                MockDom::dispatch_event(&el, "animationend");
+                tokio::task::yield_now().await;
+                assert_eq!(parent.to_debug_html(), "<div></div>");
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn element_is_removed_if_its_exit_never_ends() {
+        _ = Executor::init_tokio();
+        tokio::task::LocalSet::new()
+            .run_until(async {
+                let parent = MockDom::create_element(Div);
+                let mut state =
+                    animated::<_, _, _, MockDom>(p().child("hi"), "in", "out")
+                        .build();
+                state.mount(&parent, None);
+                state.unmount();
+                tokio::task::yield_now().await;
+                assert_eq!(
+                    parent.to_debug_html(),
+                    "<div><p class=\"out\">hi</p></div>"
+                );
+
+                MockDom::run_timers();
+                tokio::task::yield_now().await;
+                assert_eq!(parent.to_debug_html(), "<div></div>");
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn animations_of_children_are_ignored() {
+        _ = Executor::init_tokio();
+        tokio::task::LocalSet::new()
+            .run_until(async {
+                let parent = MockDom::create_element(Div);
+                let mut state = animated::<_, _, _, MockDom>(
+                    div().child(p().child("hi")),
+                    "in",
+                    "out",
+                )
+                .build();
+                state.mount(&parent, None);
+                let child = MockDom::first_child(state.state.as_ref())
+                    .and_then(Element::cast_from)
+                    .unwrap();
+                state.unmount();
+
+                MockDom::dispatch_event(&child, "animationend");
+                tokio::task::yield_now().await;
+                assert_eq!(
+                    parent.to_debug_html(),
+                    "<div><div class=\"out\"><p>hi</p></div></div>"
+                );
+
+                MockDom::dispatch_event(&state.state, "animationend");
+                tokio::task::yield_now().await;
+                assert_eq!(parent.to_debug_html(), "<div></div>");
+            })
+            .await;
+    }
+}
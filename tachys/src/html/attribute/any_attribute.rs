@@ -1,5 +1,5 @@
-use super::{Attribute, NextAttribute};
-use crate::renderer::Renderer;
+use super::{Attribute, NamedAttributeKey, NextAttribute};
+use crate::renderer::{DomRenderer, Renderer};
 use std::{
     any::{Any, TypeId},
     fmt::Debug,
@@ -9,10 +9,15 @@ use std::{
 use std::{future::Future, pin::Pin};
 
 /// A type-erased container for any [`Attribute`].
+///
+/// The attribute is stored in its [cloneable, owned](Attribute::CloneableOwned) form, so an
+/// `AnyAttribute` can itself be cloned.
 pub struct AnyAttribute<R: Renderer> {
     type_id: TypeId,
     html_len: usize,
+    keys: Vec<NamedAttributeKey>,
     value: Box<dyn Any + Send>,
+    clone: fn(&(dyn Any + Send)) -> Box<dyn Any + Send>,
     #[cfg(feature = "ssr")]
     to_html:
         fn(Box<dyn Any>, &mut String, &mut String, &mut String, &mut String),
@@ -32,6 +37,33 @@ pub struct AnyAttribute<R: Renderer> {
     dry_resolve: fn(&mut Box<dyn Any + Send>),
 }
 
+impl<R> Clone for AnyAttribute<R>
+where
+    R: Renderer,
+{
+    fn clone(&self) -> Self {
+        Self {
+            type_id: self.type_id,
+            html_len: self.html_len,
+            keys: self.keys.clone(),
+            value: (self.clone)(&*self.value),
+            clone: self.clone,
+            #[cfg(feature = "ssr")]
+            to_html: self.to_html,
+            build: self.build,
+            rebuild: self.rebuild,
+            #[cfg(feature = "hydrate")]
+            hydrate_from_server: self.hydrate_from_server,
+            #[cfg(feature = "hydrate")]
+            hydrate_from_template: self.hydrate_from_template,
+            #[cfg(feature = "ssr")]
+            resolve: self.resolve,
+            #[cfg(feature = "ssr")]
+            dry_resolve: self.dry_resolve,
+        }
+    }
+}
+
 impl<R> Debug for AnyAttribute<R>
 where
     R: Renderer,
@@ -47,6 +79,7 @@ where
     R: Renderer,
 {
     type_id: TypeId,
+    keys: Vec<NamedAttributeKey>,
     state: Box<dyn Any>,
     el: R::Element,
     rndr: PhantomData<R>,
@@ -63,9 +96,8 @@ where
 
 impl<T, R> IntoAnyAttribute<R> for T
 where
-    Self: Send,
-    T: Attribute<R> + 'static,
-    T::State: 'static,
+    T: Attribute<R>,
+    <T::CloneableOwned as Attribute<R>>::State: 'static,
     R: Renderer + 'static,
     R::Element: Clone,
 {
@@ -73,10 +105,20 @@ where
     // i.e., doesn't ship HTML-generating code that isn't used
     #[inline(always)]
     fn into_any_attr(self) -> AnyAttribute<R> {
+        type C<T, R> = <T as Attribute<R>>::CloneableOwned;
+
         let html_len = self.html_len();
+        let keys = self.keys();
 
-        let value = Box::new(self) as Box<dyn Any + Send>;
+        let value =
+            Box::new(self.into_cloneable_owned()) as Box<dyn Any + Send>;
 
+        let clone = |value: &(dyn Any + Send)| {
+            let value = value
+                .downcast_ref::<C<T, R>>()
+                .expect("AnyAttribute::clone couldn't downcast");
+            Box::new(value.clone()) as Box<dyn Any + Send>
+        };
         #[cfg(feature = "ssr")]
         let to_html = |value: Box<dyn Any>,
                        buf: &mut String,
@@ -84,18 +126,19 @@ where
                        style: &mut String,
                        inner_html: &mut String| {
             let value = value
-                .downcast::<T>()
+                .downcast::<C<T, R>>()
                 .expect("AnyAttribute::to_html could not be downcast");
             value.to_html(buf, class, style, inner_html);
         };
         let build = |value: Box<dyn Any>, el: &R::Element| {
             let value = value
-                .downcast::<T>()
+                .downcast::<C<T, R>>()
                 .expect("AnyAttribute::build couldn't downcast");
             let state = Box::new(value.build(el));
 
             AnyAttributeState {
-                type_id: TypeId::of::<T>(),
+                type_id: TypeId::of::<C<T, R>>(),
+                keys: Vec::new(),
                 state,
                 el: el.clone(),
                 rndr: PhantomData,
@@ -104,12 +147,13 @@ where
         #[cfg(feature = "hydrate")]
         let hydrate_from_server = |value: Box<dyn Any>, el: &R::Element| {
             let value = value
-                .downcast::<T>()
+                .downcast::<C<T, R>>()
                 .expect("AnyAttribute::hydrate_from_server couldn't downcast");
             let state = Box::new(value.hydrate::<true>(el));
 
             AnyAttributeState {
-                type_id: TypeId::of::<T>(),
+                type_id: TypeId::of::<C<T, R>>(),
+                keys: Vec::new(),
                 state,
                 el: el.clone(),
                 rndr: PhantomData,
@@ -118,12 +162,13 @@ where
         #[cfg(feature = "hydrate")]
         let hydrate_from_template = |value: Box<dyn Any>, el: &R::Element| {
             let value = value
-                .downcast::<T>()
+                .downcast::<C<T, R>>()
                 .expect("AnyAttribute::hydrate_from_server couldn't downcast");
             let state = Box::new(value.hydrate::<true>(el));
 
             AnyAttributeState {
-                type_id: TypeId::of::<T>(),
+                type_id: TypeId::of::<C<T, R>>(),
+                keys: Vec::new(),
                 state,
                 el: el.clone(),
                 rndr: PhantomData,
@@ -133,7 +178,7 @@ where
                        value: Box<dyn Any>,
                        state: &mut AnyAttributeState<R>| {
             let value = value
-                .downcast::<T>()
+                .downcast::<C<T, R>>()
                 .expect("AnyAttribute::rebuild couldn't downcast value");
             if new_type_id == state.type_id {
                 let state = state
@@ -142,14 +187,14 @@ where
                     .expect("AnyAttribute::rebuild couldn't downcast state");
                 value.rebuild(state);
             } else {
-                let new = value.into_any_attr().build(&state.el);
-                *state = new;
+                state.type_id = TypeId::of::<C<T, R>>();
+                state.state = Box::new(value.build(&state.el));
             }
         };
         #[cfg(feature = "ssr")]
         let dry_resolve = |value: &mut Box<dyn Any + Send>| {
             let value = value
-                .downcast_mut::<T>()
+                .downcast_mut::<C<T, R>>()
                 .expect("AnyView::resolve could not be downcast");
             value.dry_resolve();
         };
@@ -157,15 +202,17 @@ where
         #[cfg(feature = "ssr")]
         let resolve = |value: Box<dyn Any>| {
             let value = value
-                .downcast::<T>()
+                .downcast::<C<T, R>>()
                 .expect("AnyView::resolve could not be downcast");
             Box::pin(async move { value.resolve().await.into_any_attr() })
                 as Pin<Box<dyn Future<Output = AnyAttribute<R>> + Send>>
         };
         AnyAttribute {
-            type_id: TypeId::of::<T>(),
+            type_id: TypeId::of::<C<T, R>>(),
             html_len,
+            keys,
             value,
+            clone,
             #[cfg(feature = "ssr")]
             to_html,
             build,
@@ -204,8 +251,8 @@ where
 
     type AsyncOutput = AnyAttribute<R>;
     type State = AnyAttributeState<R>;
-    type Cloneable = ();
-    type CloneableOwned = ();
+    type Cloneable = AnyAttribute<R>;
+    type CloneableOwned = AnyAttribute<R>;
 
     fn html_len(&self) -> usize {
         self.html_len
@@ -235,10 +282,14 @@ where
         el: &<R as Renderer>::Element,
     ) -> Self::State {
         #[cfg(feature = "hydrate")]
-        if FROM_SERVER {
-            (self.hydrate_from_server)(self.value, el)
-        } else {
-            (self.hydrate_from_template)(self.value, el)
+        {
+            let mut state = if FROM_SERVER {
+                (self.hydrate_from_server)(self.value, el)
+            } else {
+                (self.hydrate_from_template)(self.value, el)
+            };
+            state.keys = self.keys;
+            state
         }
         #[cfg(not(feature = "hydrate"))]
         {
@@ -251,19 +302,22 @@ where
     }
 
     fn build(self, el: &<R as Renderer>::Element) -> Self::State {
-        (self.build)(self.value, el)
+        let mut state = (self.build)(self.value, el);
+        state.keys = self.keys;
+        state
     }
 
     fn rebuild(self, state: &mut Self::State) {
-        (self.rebuild)(self.type_id, self.value, state)
+        (self.rebuild)(self.type_id, self.value, state);
+        state.keys = self.keys;
     }
 
     fn into_cloneable(self) -> Self::Cloneable {
-        todo!()
+        self
     }
 
     fn into_cloneable_owned(self) -> Self::CloneableOwned {
-        todo!()
+        self
     }
 
     fn dry_resolve(&mut self) {
@@ -289,4 +343,139 @@ where
              enabled."
         );
     }
+
+    fn keys(&self) -> Vec<NamedAttributeKey> {
+        self.keys.clone()
+    }
+}
+
+/// A dynamically-sized set of type-erased attributes.
+///
+/// This allows a component to accept an arbitrary set of attributes from its caller, and spread
+/// them onto one of its inner elements with [`HtmlElement::spread`] or `{..attributes}` in the
+/// `view` macro.
+///
+/// When the list is rebuilt, any attribute or class that was set by the old list but is not set
+/// by the new one is removed from the element. (See [`Attribute::keys`] for what can be removed.)
+///
+/// [`HtmlElement::spread`]: crate::html::element::HtmlElement::spread
+impl<R> Attribute<R> for Vec<AnyAttribute<R>>
+where
+    R: DomRenderer,
+{
+    const MIN_LENGTH: usize = 0;
+
+    type AsyncOutput = Vec<AnyAttribute<R>>;
+    type State = AnyAttributesState<R>;
+    type Cloneable = Vec<AnyAttribute<R>>;
+    type CloneableOwned = Vec<AnyAttribute<R>>;
+
+    fn html_len(&self) -> usize {
+        self.iter().map(|attr| attr.html_len() + 1).sum()
+    }
+
+    fn to_html(
+        self,
+        buf: &mut String,
+        class: &mut String,
+        style: &mut String,
+        inner_html: &mut String,
+    ) {
+        for attr in self {
+            attr.to_html(buf, class, style, inner_html);
+        }
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        el: &<R as Renderer>::Element,
+    ) -> Self::State {
+        AnyAttributesState {
+            el: el.clone(),
+            states: self
+                .into_iter()
+                .map(|attr| attr.hydrate::<FROM_SERVER>(el))
+                .collect(),
+        }
+    }
+
+    fn build(self, el: &<R as Renderer>::Element) -> Self::State {
+        AnyAttributesState {
+            el: el.clone(),
+            states: self.into_iter().map(|attr| attr.build(el)).collect(),
+        }
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        let AnyAttributesState { el, states } = state;
+        let keys = self.keys();
+        for key in states.iter().flat_map(|state| &state.keys) {
+            if !keys.contains(key) {
+                match key {
+                    NamedAttributeKey::Attribute(name) => {
+                        R::remove_attribute(el, name)
+                    }
+                    NamedAttributeKey::Class(name) => {
+                        R::remove_class(&R::class_list(el), name)
+                    }
+                }
+            }
+        }
+
+        states.truncate(self.len());
+        for (idx, attr) in self.into_iter().enumerate() {
+            match states.get_mut(idx) {
+                Some(state) => attr.rebuild(state),
+                None => states.push(attr.build(el)),
+            }
+        }
+    }
+
+    fn into_cloneable(self) -> Self::Cloneable {
+        self.into_iter().map(Attribute::into_cloneable).collect()
+    }
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {
+        self.into_iter()
+            .map(Attribute::into_cloneable_owned)
+            .collect()
+    }
+
+    fn dry_resolve(&mut self) {
+        for attr in self.iter_mut() {
+            attr.dry_resolve();
+        }
+    }
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        futures::future::join_all(self.into_iter().map(Attribute::resolve))
+            .await
+    }
+
+    fn keys(&self) -> Vec<NamedAttributeKey> {
+        self.iter().flat_map(Attribute::keys).collect()
+    }
+}
+
+impl<R> NextAttribute<R> for Vec<AnyAttribute<R>>
+where
+    R: DomRenderer,
+{
+    type Output<NewAttr: Attribute<R>> = (Self, NewAttr);
+
+    fn add_any_attr<NewAttr: Attribute<R>>(
+        self,
+        new_attr: NewAttr,
+    ) -> Self::Output<NewAttr> {
+        (self, new_attr)
+    }
+}
+
+/// View state for a list of [`AnyAttribute`]s.
+pub struct AnyAttributesState<R>
+where
+    R: Renderer,
+{
+    el: R::Element,
+    states: Vec<AnyAttributeState<R>>,
 }
@@ -1,6 +1,6 @@
 use super::NextAttribute;
 use crate::{
-    html::attribute::{Attribute, AttributeValue},
+    html::attribute::{Attribute, AttributeValue, NamedAttributeKey},
     renderer::DomRenderer,
     view::{add_attr::AddAnyAttr, Position, ToTemplate},
 };
@@ -118,6 +118,12 @@ where
             rndr: self.rndr,
         }
     }
+
+    fn keys(&self) -> Vec<NamedAttributeKey> {
+        vec![NamedAttributeKey::Attribute(
+            self.key.as_ref().to_owned().into(),
+        )]
+    }
 }
 
 impl<K, V, R> NextAttribute<R> for CustomAttr<K, V, R>
@@ -13,7 +13,7 @@ use crate::{
     view::{Position, ToTemplate},
 };
 pub use key::*;
-use std::{fmt::Debug, future::Future, marker::PhantomData};
+use std::{borrow::Cow, fmt::Debug, future::Future, marker::PhantomData};
 pub use value::*;
 
 /// Defines an attribute: anything that can modify an element.
@@ -70,6 +70,24 @@ pub trait Attribute<R: Renderer>: NextAttribute<R> + Send {
 
     /// “Resolves” this into a type that is not waiting for any asynchronous data.
     fn resolve(self) -> impl Future<Output = Self::AsyncOutput> + Send;
+
+    /// The attributes and classes this sets on an element, so that they can be removed again if
+    /// it is replaced by a different attribute.
+    ///
+    /// Event listeners, properties, and classes or attributes whose names are only known once they
+    /// have been built, are not included.
+    fn keys(&self) -> Vec<NamedAttributeKey> {
+        Vec::new()
+    }
+}
+
+/// Something set on an element by an [`Attribute`], which can be removed again.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum NamedAttributeKey {
+    /// An HTML attribute, with this name.
+    Attribute(Cow<'static, str>),
+    /// A single class in the `class` attribute.
+    Class(Cow<'static, str>),
 }
 
 /// Adds another attribute to this one, returning a new attribute.
@@ -234,6 +252,10 @@ where
     async fn resolve(self) -> Self::AsyncOutput {
         Attr(self.0, self.1.resolve().await, PhantomData)
     }
+
+    fn keys(&self) -> Vec<NamedAttributeKey> {
+        vec![NamedAttributeKey::Attribute(K::KEY.into())]
+    }
 }
 
 impl<K, V, R> NextAttribute<R> for Attr<K, V, R>
@@ -325,6 +347,14 @@ macro_rules! impl_attr_for_tuples {
                 )
             }
 
+            fn keys(&self) -> Vec<NamedAttributeKey> {
+                #[allow(non_snake_case)]
+                let ($first, $($ty,)*) = self;
+                let mut keys = $first.keys();
+                $(keys.extend($ty.keys());)*
+                keys
+            }
+
             fn dry_resolve(&mut self) {
                 #[allow(non_snake_case)]
                 let ($first, $($ty,)*) = self;
@@ -435,6 +465,14 @@ macro_rules! impl_attr_for_tuples_truncate_additional {
                 )
             }
 
+            fn keys(&self) -> Vec<NamedAttributeKey> {
+                #[allow(non_snake_case)]
+                let ($first, $($ty,)*) = self;
+                let mut keys = $first.keys();
+                $(keys.extend($ty.keys());)*
+                keys
+            }
+
             fn dry_resolve(&mut self) {
                 #[allow(non_snake_case)]
                 let ($first, $($ty,)*) = self;
@@ -527,6 +565,10 @@ where
     async fn resolve(self) -> Self::AsyncOutput {
         (self.0.resolve().await,)
     }
+
+    fn keys(&self) -> Vec<NamedAttributeKey> {
+        self.0.keys()
+    }
 }
 
 impl<A, Rndr> NextAttribute<Rndr> for (A,)
@@ -1,9 +1,9 @@
-use super::attribute::{Attribute, NextAttribute};
+use super::attribute::{Attribute, NamedAttributeKey, NextAttribute};
 use crate::{
     renderer::DomRenderer,
     view::{Position, ToTemplate},
 };
-use std::{future::Future, marker::PhantomData, sync::Arc};
+use std::{borrow::Cow, future::Future, marker::PhantomData, sync::Arc};
 
 /// Adds a CSS class.
 #[inline(always)]
@@ -100,6 +100,14 @@ where
             rndr: self.rndr,
         }
     }
+
+    fn keys(&self) -> Vec<NamedAttributeKey> {
+        self.class
+            .class_names()
+            .into_iter()
+            .map(NamedAttributeKey::Class)
+            .collect()
+    }
 }
 
 impl<C, R> NextAttribute<R> for Class<C, R>
@@ -171,6 +179,36 @@ pub trait IntoClass<R: DomRenderer>: Send {
     /// Updates the value.
     fn rebuild(self, state: &mut Self::State);
 
+    /// Like [`hydrate`](IntoClass::hydrate), for an entry in a tuple of classes.
+    ///
+    /// Classes that would otherwise set the whole `class` attribute, like strings, only add their
+    /// own class names here, so that they don't replace the classes of the other entries.
+    fn hydrate_entry<const FROM_SERVER: bool>(
+        self,
+        el: &R::Element,
+    ) -> Self::State
+    where
+        Self: Sized,
+    {
+        self.hydrate::<FROM_SERVER>(el)
+    }
+
+    /// Like [`build`](IntoClass::build), for an entry in a tuple of classes.
+    fn build_entry(self, el: &R::Element) -> Self::State
+    where
+        Self: Sized,
+    {
+        self.build(el)
+    }
+
+    /// Like [`rebuild`](IntoClass::rebuild), for an entry in a tuple of classes.
+    fn rebuild_entry(self, state: &mut Self::State)
+    where
+        Self: Sized,
+    {
+        self.rebuild(state)
+    }
+
     /// Converts this to a cloneable type.
     fn into_cloneable(self) -> Self::Cloneable;
 
@@ -184,6 +222,44 @@ pub trait IntoClass<R: DomRenderer>: Send {
 
     /// “Resolves” this into a type that is not waiting for any asynchronous data.
     fn resolve(self) -> impl Future<Output = Self::AsyncOutput> + Send;
+
+    /// The names of the classes this can add, if they are known before it is built.
+    fn class_names(&self) -> Vec<Cow<'static, str>> {
+        Vec::new()
+    }
+}
+
+pub(crate) fn split_class_names(class: &str) -> Vec<Cow<'static, str>> {
+    class
+        .split_whitespace()
+        .map(|name| Cow::Owned(name.to_owned()))
+        .collect()
+}
+
+/// Adds each of the class names in `class` to the element's class list.
+pub(crate) fn add_class_names<R: DomRenderer>(el: &R::Element, class: &str) {
+    let class_list = R::class_list(el);
+    for name in class.split_whitespace() {
+        R::add_class(&class_list, name);
+    }
+}
+
+/// Removes the class names in `prev` that are not in `next` from the element's class list, and
+/// adds those in `next`.
+pub(crate) fn replace_class_names<R: DomRenderer>(
+    el: &R::Element,
+    prev: &str,
+    next: &str,
+) {
+    let class_list = R::class_list(el);
+    for name in prev.split_whitespace() {
+        if !next.split_whitespace().any(|next| next == name) {
+            R::remove_class(&class_list, name);
+        }
+    }
+    for name in next.split_whitespace() {
+        R::add_class(&class_list, name);
+    }
 }
 
 impl<'a, R> IntoClass<R> for &'a str
@@ -223,6 +299,29 @@ where
         *prev = self;
     }
 
+    fn hydrate_entry<const FROM_SERVER: bool>(
+        self,
+        el: &R::Element,
+    ) -> Self::State {
+        if !FROM_SERVER {
+            add_class_names::<R>(el, self);
+        }
+        (el.clone(), self)
+    }
+
+    fn build_entry(self, el: &R::Element) -> Self::State {
+        add_class_names::<R>(el, self);
+        (el.clone(), self)
+    }
+
+    fn rebuild_entry(self, state: &mut Self::State) {
+        let (el, prev) = state;
+        if self != *prev {
+            replace_class_names::<R>(el, prev, self);
+        }
+        *prev = self;
+    }
+
     fn into_cloneable(self) -> Self::Cloneable {
         self
     }
@@ -236,6 +335,10 @@ where
     async fn resolve(self) -> Self::AsyncOutput {
         self
     }
+
+    fn class_names(&self) -> Vec<Cow<'static, str>> {
+        split_class_names(self)
+    }
 }
 
 impl<R> IntoClass<R> for String
@@ -275,6 +378,29 @@ where
         *prev = self;
     }
 
+    fn hydrate_entry<const FROM_SERVER: bool>(
+        self,
+        el: &R::Element,
+    ) -> Self::State {
+        if !FROM_SERVER {
+            add_class_names::<R>(el, &self);
+        }
+        (el.clone(), self)
+    }
+
+    fn build_entry(self, el: &R::Element) -> Self::State {
+        add_class_names::<R>(el, &self);
+        (el.clone(), self)
+    }
+
+    fn rebuild_entry(self, state: &mut Self::State) {
+        let (el, prev) = state;
+        if self != *prev {
+            replace_class_names::<R>(el, prev, &self);
+        }
+        *prev = self;
+    }
+
     fn into_cloneable(self) -> Self::Cloneable {
         self.into()
     }
@@ -288,6 +414,10 @@ where
     async fn resolve(self) -> Self::AsyncOutput {
         self
     }
+
+    fn class_names(&self) -> Vec<Cow<'static, str>> {
+        split_class_names(self)
+    }
 }
 
 impl<R> IntoClass<R> for Arc<str>
@@ -327,6 +457,29 @@ where
         *prev = self;
     }
 
+    fn hydrate_entry<const FROM_SERVER: bool>(
+        self,
+        el: &R::Element,
+    ) -> Self::State {
+        if !FROM_SERVER {
+            add_class_names::<R>(el, &self);
+        }
+        (el.clone(), self)
+    }
+
+    fn build_entry(self, el: &R::Element) -> Self::State {
+        add_class_names::<R>(el, &self);
+        (el.clone(), self)
+    }
+
+    fn rebuild_entry(self, state: &mut Self::State) {
+        let (el, prev) = state;
+        if !Arc::ptr_eq(&self, prev) {
+            replace_class_names::<R>(el, prev, &self);
+        }
+        *prev = self;
+    }
+
     fn into_cloneable(self) -> Self::Cloneable {
         self
     }
@@ -340,6 +493,10 @@ where
     async fn resolve(self) -> Self::AsyncOutput {
         self
     }
+
+    fn class_names(&self) -> Vec<Cow<'static, str>> {
+        split_class_names(self)
+    }
 }
 
 impl<R> IntoClass<R> for (&'static str, bool)
@@ -406,6 +563,10 @@ where
     async fn resolve(self) -> Self::AsyncOutput {
         self
     }
+
+    fn class_names(&self) -> Vec<Cow<'static, str>> {
+        vec![Cow::Borrowed(self.0)]
+    }
 }
 
 #[cfg(feature = "nightly")]
@@ -457,6 +618,168 @@ where
     }
 }
 
+impl<R> IntoClass<R> for ()
+where
+    R: DomRenderer,
+{
+    type AsyncOutput = Self;
+    type State = ();
+    type Cloneable = Self;
+    type CloneableOwned = Self;
+
+    fn html_len(&self) -> usize {
+        0
+    }
+
+    fn to_html(self, _class: &mut String) {}
+
+    fn hydrate<const FROM_SERVER: bool>(self, _el: &R::Element) -> Self::State {
+    }
+
+    fn build(self, _el: &R::Element) -> Self::State {}
+
+    fn rebuild(self, _state: &mut Self::State) {}
+
+    fn into_cloneable(self) -> Self::Cloneable {}
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {}
+
+    fn dry_resolve(&mut self) {}
+
+    async fn resolve(self) -> Self::AsyncOutput {}
+}
+
+/// Renders one entry of a tuple of classes, separating it from any previous class names.
+fn entry_to_html<C, R>(entry: C, class: &mut String)
+where
+    C: IntoClass<R>,
+    R: DomRenderer,
+{
+    let start = class.len();
+    if !class.is_empty() && !class.ends_with(' ') {
+        class.push(' ');
+    }
+    let before = class.len();
+    entry.to_html(class);
+    if class.len() == before {
+        class.truncate(start);
+    }
+}
+
+// A tuple of classes acts like a class list: each entry is applied to the element separately, so
+// that toggling one entry on rebuild only adds or removes that class. Entries that are strings
+// add their class names to the list, rather than replacing the whole `class` attribute.
+//
+// Every arity is generated by `impl_class_for_tuples!`. A generic implementation for 2-tuples
+// would overlap with toggles like `(&'static str, bool)`, so the pair arm only matches pairs
+// whose first entry is itself a tuple, as in `(("active", is_active), ("disabled", disabled))`.
+// The async and cloneable forms of that first entry are not known to be pairs, so those of the
+// pair are padded with a trailing `()` to use the flat 3-tuple implementation.
+macro_rules! impl_class_for_tuples {
+    (($a0:ident, $a1:ident), $b:ident) => {
+        impl_class_for_tuples!(@impl [$a0, $a1, $b] [A: ($a0, $a1), $b: $b] ());
+    };
+    ($($ty:ident),+ $(,)?) => {
+        impl_class_for_tuples!(@impl [$($ty),+] [$($ty: $ty),+]);
+    };
+    (@impl [$($gen:ident),+] [$($name:ident: $ty:ty),+] $($pad:tt)?) => {
+        impl<$($gen,)+ Rndr> IntoClass<Rndr> for ($($ty,)+)
+        where
+            $($ty: IntoClass<Rndr>,)+
+            Rndr: DomRenderer,
+        {
+            type AsyncOutput =
+                ($(<$ty as IntoClass<Rndr>>::AsyncOutput,)+ $($pad)?);
+            type State = ($(<$ty as IntoClass<Rndr>>::State,)+);
+            type Cloneable =
+                ($(<$ty as IntoClass<Rndr>>::Cloneable,)+ $($pad)?);
+            type CloneableOwned =
+                ($(<$ty as IntoClass<Rndr>>::CloneableOwned,)+ $($pad)?);
+
+            fn html_len(&self) -> usize {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+                0 $(+ 1 + $name.html_len())+
+            }
+
+            fn to_html(self, class: &mut String) {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+                $(entry_to_html($name, class);)+
+            }
+
+            fn hydrate<const FROM_SERVER: bool>(
+                self,
+                el: &Rndr::Element,
+            ) -> Self::State {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+                ($($name.hydrate_entry::<FROM_SERVER>(el),)+)
+            }
+
+            fn build(self, el: &Rndr::Element) -> Self::State {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+                ($($name.build_entry(el),)+)
+            }
+
+            fn rebuild(self, state: &mut Self::State) {
+                paste::paste! {
+                    let ($([<$name:lower>],)+) = self;
+                    let ($([<view_ $name:lower>],)+) = state;
+                    $([<$name:lower>].rebuild_entry([<view_ $name:lower>]);)+
+                }
+            }
+
+            fn into_cloneable(self) -> Self::Cloneable {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+                ($($name.into_cloneable(),)+ $($pad)?)
+            }
+
+            fn into_cloneable_owned(self) -> Self::CloneableOwned {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+                ($($name.into_cloneable_owned(),)+ $($pad)?)
+            }
+
+            fn dry_resolve(&mut self) {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+                $($name.dry_resolve();)+
+            }
+
+            async fn resolve(self) -> Self::AsyncOutput {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+                #[allow(non_snake_case)]
+                let ($($name,)+) = futures::join!($($name.resolve()),+);
+                ($($name,)+ $($pad)?)
+            }
+
+            fn class_names(&self) -> Vec<Cow<'static, str>> {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+                let mut names = Vec::new();
+                $(names.extend($name.class_names());)+
+                names
+            }
+        }
+    };
+}
+
+impl_class_for_tuples!((A0, A1), B);
+impl_class_for_tuples!(A, B, C);
+impl_class_for_tuples!(A, B, C, D);
+impl_class_for_tuples!(A, B, C, D, E);
+impl_class_for_tuples!(A, B, C, D, E, F);
+impl_class_for_tuples!(A, B, C, D, E, F, G);
+impl_class_for_tuples!(A, B, C, D, E, F, G, H);
+impl_class_for_tuples!(A, B, C, D, E, F, G, H, I);
+impl_class_for_tuples!(A, B, C, D, E, F, G, H, I, J);
+impl_class_for_tuples!(A, B, C, D, E, F, G, H, I, J, K);
+impl_class_for_tuples!(A, B, C, D, E, F, G, H, I, J, K, L);
+
 /* #[cfg(test)]
 mod tests {
     use crate::{
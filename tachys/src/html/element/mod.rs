@@ -1,6 +1,9 @@
 use crate::{
     html::attribute::Attribute,
-    hydration::Cursor,
+    hydration::{
+        enter_element, exit_element, failed_to_cast_node, validate_element,
+        Cursor,
+    },
     renderer::{CastFrom, Renderer},
     ssr::StreamBuilder,
     view::{
@@ -119,6 +122,40 @@ where
     }
 }
 
+impl<E, At, Ch, Rndr> HtmlElement<E, At, Ch, Rndr>
+where
+    At: Attribute<Rndr>,
+    Rndr: Renderer,
+{
+    /// Spreads a set of attributes onto this element.
+    ///
+    /// `attrs` can be a single attribute, a tuple of attributes, or a
+    /// `Vec<`[`AnyAttribute`](crate::html::attribute::any_attribute::AnyAttribute)`>`
+    /// that was passed in by the caller of a component.
+    pub fn spread<NewAttr: Attribute<Rndr>>(
+        self,
+        attrs: NewAttr,
+    ) -> HtmlElement<E, <At as NextAttribute<Rndr>>::Output<NewAttr>, Ch, Rndr>
+    {
+        let HtmlElement {
+            tag,
+            attributes,
+            children,
+            rndr,
+            #[cfg(debug_assertions)]
+            defined_at,
+        } = self;
+        HtmlElement {
+            tag,
+            attributes: attributes.add_any_attr(attrs),
+            children,
+            rndr,
+            #[cfg(debug_assertions)]
+            defined_at,
+        }
+    }
+}
+
 /// Adds a child to the element.
 pub trait ElementChild<Rndr, NewChild>
 where
@@ -362,7 +399,19 @@ where
         } else if curr_position != Position::Current {
             cursor.sibling();
         }
-        let el = Rndr::Element::cast_from(cursor.current()).unwrap();
+        #[cfg(debug_assertions)]
+        let defined_at = Some(self.defined_at);
+        #[cfg(not(debug_assertions))]
+        let defined_at = None;
+        let node = cursor.current();
+        let el = Rndr::Element::cast_from(node.clone()).unwrap_or_else(|| {
+            failed_to_cast_node::<Rndr>(
+                &format!("a <{}> element", self.tag.tag()),
+                &node,
+                defined_at,
+            )
+        });
+        validate_element::<Rndr>(self.tag.tag(), &el, defined_at);
 
         let attrs = self.attributes.hydrate::<FROM_SERVER>(&el);
 
@@ -371,7 +420,11 @@ where
             None
         } else {
             position.set(Position::FirstChild);
-            Some(self.children.hydrate::<FROM_SERVER>(cursor, position))
+            enter_element(self.tag.tag());
+            let children =
+                self.children.hydrate::<FROM_SERVER>(cursor, position);
+            exit_element();
+            Some(children)
         };
 
         // go to next sibling
@@ -407,7 +460,15 @@ where
     let mut inner_html = String::new();
 
     // inject regular attributes, and fill class and style
+    #[cfg(feature = "reactive_graph")]
+    let attrs_start = buf.len();
     attr.to_html(buf, &mut class, &mut style, &mut inner_html);
+    #[cfg(feature = "reactive_graph")]
+    crate::reactive_graph::bind::finish_attributes_html(
+        buf,
+        attrs_start,
+        &mut inner_html,
+    );
 
     if !class.is_empty() {
         buf.push(' ');
@@ -558,11 +619,18 @@ mod tests {
     use super::{main, p, HtmlElement};
     use crate::{
         html::{
-            attribute::global::GlobalAttributes,
-            element::{em, ElementChild, Main},
+            attribute::{
+                any_attribute::IntoAnyAttribute,
+                custom::custom_attribute,
+                global::{ClassAttribute, GlobalAttributes, StyleAttribute},
+                Attribute,
+            },
+            class::{class, IntoClass},
+            element::{button, div, em, span, Div, ElementChild, Main, P},
         },
+        hydration::Cursor,
         renderer::mock_dom::MockDom,
-        view::{Render, RenderHtml},
+        view::{Position, PositionState, Render, RenderHtml},
     };
 
     #[test]
@@ -590,6 +658,138 @@ mod tests {
         );
     }
 
+    #[test]
+    fn spread_applies_dynamic_attributes() {
+        let el: HtmlElement<Div, _, _, MockDom> =
+            div().child(button().spread(vec![
+                custom_attribute("data-test", "y").into_any_attr(),
+                class("x").into_any_attr(),
+            ]));
+        let mut state = el.build();
+        // the mock DOM does not preserve attribute order
+        let html = state.el.to_debug_html();
+        assert!(html.contains("data-test=\"y\""));
+        assert!(html.contains("class=\"x\""));
+
+        let el: HtmlElement<Div, _, _, MockDom> =
+            div().child(button().spread(vec![
+                custom_attribute("data-test", "z").into_any_attr(),
+            ]));
+        el.rebuild(&mut state);
+        assert_eq!(
+            state.el.to_debug_html(),
+            "<div><button data-test=\"z\"><!></button></div>"
+        );
+
+        let el: HtmlElement<Div, _, _, MockDom> =
+            div().child(button().spread(vec![class("y").into_any_attr()]));
+        el.rebuild(&mut state);
+        assert_eq!(
+            state.el.to_debug_html(),
+            "<div><button class=\"y\"><!></button></div>"
+        );
+    }
+
+    #[test]
+    fn spread_attributes_can_be_cloned() {
+        let attrs = Attribute::<MockDom>::into_cloneable(vec![
+            custom_attribute("data-test", "y").into_any_attr(),
+            class(String::from("x")).into_any_attr(),
+        ]);
+        for _ in 0..2 {
+            let el: HtmlElement<Div, _, _, MockDom> =
+                div().spread(attrs.clone());
+            let html = el.build().el.to_debug_html();
+            assert!(html.contains("data-test=\"y\""));
+            assert!(html.contains("class=\"x\""));
+        }
+    }
+
+    #[test]
+    fn class_tuples_toggle_individual_classes() {
+        let view = |a: bool, b: bool| -> HtmlElement<P, _, (), MockDom> {
+            p().class((("a", a), ("b", b)))
+        };
+        let mut state = view(true, false).build();
+        assert_eq!(state.el.to_debug_html(), "<p class=\"a\"><!></p>");
+        view(false, true).rebuild(&mut state);
+        assert_eq!(state.el.to_debug_html(), "<p class=\"b\"><!></p>");
+
+        let el: HtmlElement<P, _, (), MockDom> =
+            p().class((("a", true), ("b", false), ("c", true)));
+        assert_eq!(el.to_html(), "<p class=\"a c\"></p>");
+
+        let pair =
+            IntoClass::<MockDom>::into_cloneable((("a", true), ("b", true)));
+        let el: HtmlElement<P, _, (), MockDom> = p().class(pair);
+        assert_eq!(el.to_html(), "<p class=\"a b\"></p>");
+    }
+
+    #[test]
+    fn class_tuples_keep_toggled_classes_after_string_entries() {
+        let view = |s: &'static str,
+                    a: bool,
+                    b: bool|
+         -> HtmlElement<P, _, (), MockDom> {
+            p().class((s, ("a", a), ("b", b)))
+        };
+        let mut state = view("btn", true, false).build();
+        assert_eq!(state.el.to_debug_html(), "<p class=\"btn a\"><!></p>");
+        view("btn", false, true).rebuild(&mut state);
+        assert_eq!(state.el.to_debug_html(), "<p class=\"btn b\"><!></p>");
+        view("big", false, true).rebuild(&mut state);
+        assert_eq!(state.el.to_debug_html(), "<p class=\"b big\"><!></p>");
+    }
+
+    #[test]
+    fn style_tuples_keep_properties_after_string_entries() {
+        let view = |s: &'static str,
+                    color: &'static str|
+         -> HtmlElement<P, _, (), MockDom> {
+            p().style((s, ("color", color), ()))
+        };
+        let mut state = view("margin: 0; padding: 1px", "red").build();
+        assert_eq!(
+            state.el.to_debug_html(),
+            "<p style=\"margin: 0; padding: 1px; color: red;\"><!></p>"
+        );
+        view("margin: 0; padding: 1px", "blue").rebuild(&mut state);
+        assert_eq!(
+            state.el.to_debug_html(),
+            "<p style=\"margin: 0; padding: 1px; color: blue;\"><!></p>"
+        );
+        view("margin: 0", "blue").rebuild(&mut state);
+        assert_eq!(
+            state.el.to_debug_html(),
+            "<p style=\"color: blue; margin: 0;\"><!></p>"
+        );
+    }
+
+    #[test]
+    fn style_tuples_render_each_property() {
+        let el: HtmlElement<P, _, (), MockDom> =
+            p().style((("color", "red"), ("font-weight", "bold")));
+        assert_eq!(
+            el.to_html(),
+            "<p style=\"color:red;font-weight:bold;\"></p>"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "expected to find a <span> element")]
+    fn hydration_mismatch_reports_expected_element() {
+        let server: HtmlElement<Div, _, _, MockDom> =
+            div().child(main().child(p().child("Hello, world!")));
+        let server = server.build();
+
+        let client: HtmlElement<Main, _, _, MockDom> =
+            main().child(span().child("Hello, world!"));
+        client.hydrate::<true>(
+            &Cursor::new(server.el),
+            &PositionState::new(Position::FirstChild),
+        );
+    }
+
     #[cfg(feature = "nightly")]
     #[test]
     fn html_render_allocates_appropriate_buffer() {
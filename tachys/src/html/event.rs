@@ -426,6 +426,54 @@ impl<E: FromWasmAbi> Custom<E> {
     }
 }
 
+/// Wraps an event so that its listener is attached directly to the element, even if the event
+/// bubbles and event delegation is enabled.
+///
+/// Delegated listeners only run once the event has bubbled up to the document, so this is useful
+/// when a handler needs to run before listeners on its ancestors, for example to call
+/// `stop_propagation()`.
+///
+/// ```rust
+/// # use tachys::prelude::*;
+/// # use tachys::html;
+/// # use tachys::html::event as ev;
+/// # fn undelegated_click() -> impl Render<Dom> {
+/// let button = html::element::button().on(
+///     ev::undelegated(ev::click),
+///     |e: ev::MouseEvent| e.stop_propagation(),
+/// );
+/// # button
+/// # }
+/// ```
+pub fn undelegated<E: EventDescriptor>(event: E) -> Undelegated<E> {
+    Undelegated(event)
+}
+
+/// An event whose listener will not be delegated. See [`undelegated`].
+#[derive(Copy, Clone, Debug)]
+pub struct Undelegated<E>(E);
+
+impl<E: EventDescriptor> EventDescriptor for Undelegated<E> {
+    type EventType = E::EventType;
+
+    const BUBBLES: bool = false;
+
+    #[inline(always)]
+    fn name(&self) -> Cow<'static, str> {
+        self.0.name()
+    }
+
+    #[inline(always)]
+    fn event_delegation_key(&self) -> Cow<'static, str> {
+        self.0.event_delegation_key()
+    }
+
+    #[inline(always)]
+    fn options(&self) -> Option<&web_sys::AddEventListenerOptions> {
+        self.0.options()
+    }
+}
+
 macro_rules! generate_event_types {
   {$(
     $( #[$does_not_bubble:ident] )?
@@ -12,18 +12,52 @@ use std::marker::PhantomData;
 pub struct Island<Rndr, View> {
     component: &'static str,
     props_json: String,
+    strategy: HydrationStrategy,
     view: View,
     rndr: PhantomData<Rndr>,
 }
 const ISLAND_TAG: &str = "leptos-island";
 const ISLAND_CHILDREN_TAG: &str = "leptos-children";
 
+/// Determines when the client-side islands runtime hydrates an [`Island`].
+///
+/// The strategy is encoded in the island's `data-hydrate` attribute in the server-rendered HTML.
+/// Islands nested inside a deferred island are not hydrated until their parent has been.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HydrationStrategy {
+    /// Hydrate as soon as the WASM bundle has loaded.
+    #[default]
+    Load,
+    /// Hydrate once the browser is idle, using `requestIdleCallback` where it is available.
+    Idle,
+    /// Hydrate once the island scrolls into the viewport, using an `IntersectionObserver`.
+    Visible,
+    /// Hydrate on the first pointer, focus, or keyboard event within the island.
+    ///
+    /// Note that the event that triggers hydration is not replayed, so handlers added by the
+    /// island will only receive subsequent events.
+    Interaction,
+}
+
+impl HydrationStrategy {
+    /// The value of the `data-hydrate` attribute for this strategy.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HydrationStrategy::Load => "load",
+            HydrationStrategy::Idle => "idle",
+            HydrationStrategy::Visible => "visible",
+            HydrationStrategy::Interaction => "interaction",
+        }
+    }
+}
+
 impl<Rndr, View> Island<Rndr, View> {
     /// Creates a new island with the given component name.
     pub fn new(component: &'static str, view: View) -> Self {
         Island {
             component,
             props_json: String::new(),
+            strategy: HydrationStrategy::default(),
             view,
             rndr: PhantomData,
         }
@@ -35,13 +69,32 @@ impl<Rndr, View> Island<Rndr, View> {
         self
     }
 
-    fn open_tag(component: &'static str, props: &str, buf: &mut String) {
+    /// Sets when the island will be hydrated on the client.
+    pub fn with_hydration_strategy(
+        mut self,
+        strategy: HydrationStrategy,
+    ) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    fn open_tag(
+        component: &'static str,
+        props: &str,
+        strategy: HydrationStrategy,
+        buf: &mut String,
+    ) {
         buf.push('<');
         buf.push_str(ISLAND_TAG);
         buf.push(' ');
         buf.push_str("data-component=\"");
         buf.push_str(component);
         buf.push('"');
+        if strategy != HydrationStrategy::Load {
+            buf.push_str(" data-hydrate=\"");
+            buf.push_str(strategy.as_str());
+            buf.push('"');
+        }
         if !props.is_empty() {
             buf.push_str(" data-props=\"");
             buf.push_str(&html_escape::encode_double_quoted_attribute(&props));
@@ -91,12 +144,14 @@ where
         let Island {
             component,
             props_json,
+            strategy,
             view,
             rndr,
         } = self;
         Island {
             component,
             props_json,
+            strategy,
             view: view.add_any_attr(attr),
             rndr,
         }
@@ -124,12 +179,14 @@ where
         let Island {
             component,
             props_json,
+            strategy,
             view,
             rndr,
         } = self;
         Island {
             component,
             props_json,
+            strategy,
             view: view.resolve().await,
             rndr,
         }
@@ -142,7 +199,7 @@ where
         escape: bool,
         mark_branches: bool,
     ) {
-        Self::open_tag(self.component, &self.props_json, buf);
+        Self::open_tag(self.component, &self.props_json, self.strategy, buf);
         self.view
             .to_html_with_buf(buf, position, escape, mark_branches);
         Self::close_tag(buf);
@@ -159,7 +216,12 @@ where
     {
         // insert the opening tag synchronously
         let mut tag = String::new();
-        Self::open_tag(self.component, &self.props_json, &mut tag);
+        Self::open_tag(
+            self.component,
+            &self.props_json,
+            self.strategy,
+            &mut tag,
+        );
         buf.push_sync(&tag);
 
         // streaming render for the view
@@ -186,8 +248,16 @@ where
         } else if position.get() == Position::NextChild {
             cursor.sibling();
         }
+        let island = cursor.current();
         position.set(Position::FirstChild);
-        self.view.hydrate::<FROM_SERVER>(cursor, position)
+        let state = self.view.hydrate::<FROM_SERVER>(cursor, position);
+
+        // the island's view only walks its own contents, so return to the
+        // island's root node so that any siblings are hydrated correctly
+        cursor.set(island);
+        position.set(Position::NextChild);
+
+        state
     }
 }
 
@@ -333,5 +403,62 @@ where
         } else if curr_position != Position::Current {
             cursor.sibling();
         }
+        position.set(Position::NextChild);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HydrationStrategy, Island, IslandChildren};
+    use crate::{
+        html::element::{
+            button, custom, div, p, span, Div, ElementChild, HtmlElement,
+        },
+        hydration::Cursor,
+        renderer::mock_dom::MockDom,
+        view::{Position, PositionState, Render, RenderHtml},
+    };
+
+    #[test]
+    fn hydrates_the_siblings_of_an_island() {
+        let server: HtmlElement<Div, _, _, MockDom> = div().child(
+            div().child((
+                custom("leptos-island")
+                    .child((button(), custom("leptos-children").child(span()))),
+                p().child("after"),
+            )),
+        );
+        let server = server.build();
+
+        let client: HtmlElement<Div, _, _, MockDom> = div().child((
+            Island::new("Counter", (button(), IslandChildren::new(span()))),
+            p().child("after"),
+        ));
+        let state = client.hydrate::<true>(
+            &Cursor::new(server.el),
+            &PositionState::new(Position::FirstChild),
+        );
+        let (_, after) = state.children.as_ref().unwrap();
+        assert_eq!(after.el.to_debug_html(), "<p>after</p>");
+    }
+
+    #[test]
+    fn encodes_the_hydration_strategy() {
+        let island = |strategy| {
+            Island::<MockDom, _>::new("Counter", button())
+                .with_props("{}".to_string())
+                .with_hydration_strategy(strategy)
+                .to_html()
+        };
+        assert_eq!(
+            island(HydrationStrategy::Load),
+            "<leptos-island data-component=\"Counter\" \
+             data-props=\"{}\"><button></button></leptos-island>"
+        );
+        assert_eq!(
+            island(HydrationStrategy::Visible),
+            "<leptos-island data-component=\"Counter\" \
+             data-hydrate=\"visible\" data-props=\"{}\"><button></button></leptos-island>"
+        );
     }
 }
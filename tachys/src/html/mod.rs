@@ -5,6 +5,8 @@ use crate::{
 };
 use std::marker::PhantomData;
 
+/// Types for animating elements as they are mounted and unmounted.
+pub mod animate;
 /// Types for HTML attributes.
 pub mod attribute;
 /// Types for manipulating the `class` attribute and `classList`.
@@ -158,6 +158,15 @@ where
     }
 }
 
+// renderers that run outside a JavaScript environment are never passed the actual value
+fn js_value<R: DomRenderer>(value: impl FnOnce() -> JsValue) -> JsValue {
+    if R::JS_PROPERTIES {
+        value()
+    } else {
+        JsValue::UNDEFINED
+    }
+}
+
 /// A possible value for a DOM property.
 pub trait IntoProperty<R: DomRenderer> {
     /// The view state retained between building and rebuilding.
@@ -202,20 +211,20 @@ macro_rules! prop_type {
                 el: &R::Element,
                 key: &str,
             ) -> Self::State {
-                let value = self.into();
+                let value = js_value::<R>(|| self.into());
                 R::set_property(el, key, &value);
                 (el.clone(), value)
             }
 
             fn build(self, el: &R::Element, key: &str) -> Self::State {
-                let value = self.into();
+                let value = js_value::<R>(|| self.into());
                 R::set_property(el, key, &value);
                 (el.clone(), value)
             }
 
             fn rebuild(self, state: &mut Self::State, key: &str) {
                 let (el, prev) = state;
-                let value = self.into();
+                let value = js_value::<R>(|| self.into());
                 R::set_property(el, key, &value);
                 *prev = value;
             }
@@ -243,7 +252,7 @@ macro_rules! prop_type {
                 key: &str,
             ) -> Self::State {
                 let was_some = self.is_some();
-                let value = self.into();
+                let value = js_value::<R>(|| self.into());
                 if was_some {
                     R::set_property(el, key, &value);
                 }
@@ -252,7 +261,7 @@ macro_rules! prop_type {
 
             fn build(self, el: &R::Element, key: &str) -> Self::State {
                 let was_some = self.is_some();
-                let value = self.into();
+                let value = js_value::<R>(|| self.into());
                 if was_some {
                     R::set_property(el, key, &value);
                 }
@@ -261,7 +270,7 @@ macro_rules! prop_type {
 
             fn rebuild(self, state: &mut Self::State, key: &str) {
                 let (el, prev) = state;
-                let value = self.into();
+                let value = js_value::<R>(|| self.into());
                 R::set_property(el, key, &value);
                 *prev = value;
             }
@@ -292,20 +301,20 @@ macro_rules! prop_type_str {
                 el: &R::Element,
                 key: &str,
             ) -> Self::State {
-                let value = JsValue::from(&*self);
+                let value = js_value::<R>(|| JsValue::from(&*self));
                 R::set_property(el, key, &value);
                 (el.clone(), value)
             }
 
             fn build(self, el: &R::Element, key: &str) -> Self::State {
-                let value = JsValue::from(&*self);
+                let value = js_value::<R>(|| JsValue::from(&*self));
                 R::set_property(el, key, &value);
                 (el.clone(), value)
             }
 
             fn rebuild(self, state: &mut Self::State, key: &str) {
                 let (el, prev) = state;
-                let value = JsValue::from(&*self);
+                let value = js_value::<R>(|| JsValue::from(&*self));
                 R::set_property(el, key, &value);
                 *prev = value;
             }
@@ -335,7 +344,9 @@ macro_rules! prop_type_str {
                 key: &str,
             ) -> Self::State {
                 let was_some = self.is_some();
-                let value = JsValue::from(self.map(|n| JsValue::from_str(&n)));
+                let value = js_value::<R>(|| {
+                    JsValue::from(self.map(|n| JsValue::from_str(&n)))
+                });
                 if was_some {
                     R::set_property(el, key, &value);
                 }
@@ -344,7 +355,9 @@ macro_rules! prop_type_str {
 
             fn build(self, el: &R::Element, key: &str) -> Self::State {
                 let was_some = self.is_some();
-                let value = JsValue::from(self.map(|n| JsValue::from_str(&n)));
+                let value = js_value::<R>(|| {
+                    JsValue::from(self.map(|n| JsValue::from_str(&n)))
+                });
                 if was_some {
                     R::set_property(el, key, &value);
                 }
@@ -353,7 +366,9 @@ macro_rules! prop_type_str {
 
             fn rebuild(self, state: &mut Self::State, key: &str) {
                 let (el, prev) = state;
-                let value = JsValue::from(self.map(|n| JsValue::from_str(&n)));
+                let value = js_value::<R>(|| {
+                    JsValue::from(self.map(|n| JsValue::from_str(&n)))
+                });
                 R::set_property(el, key, &value);
                 *prev = value;
             }
@@ -388,20 +403,20 @@ where
         el: &R::Element,
         key: &str,
     ) -> Self::State {
-        let value = JsValue::from_str(self.as_ref());
+        let value = js_value::<R>(|| JsValue::from_str(self.as_ref()));
         R::set_property(el, key, &value);
         (el.clone(), value)
     }
 
     fn build(self, el: &R::Element, key: &str) -> Self::State {
-        let value = JsValue::from_str(self.as_ref());
+        let value = js_value::<R>(|| JsValue::from_str(self.as_ref()));
         R::set_property(el, key, &value);
         (el.clone(), value)
     }
 
     fn rebuild(self, state: &mut Self::State, key: &str) {
         let (el, prev) = state;
-        let value = JsValue::from_str(self.as_ref());
+        let value = js_value::<R>(|| JsValue::from_str(self.as_ref()));
         R::set_property(el, key, &value);
         *prev = value;
     }
@@ -429,7 +444,9 @@ where
         key: &str,
     ) -> Self::State {
         let was_some = self.is_some();
-        let value = JsValue::from(self.map(|n| JsValue::from_str(&n)));
+        let value = js_value::<R>(|| {
+            JsValue::from(self.map(|n| JsValue::from_str(&n)))
+        });
         if was_some {
             R::set_property(el, key, &value);
         }
@@ -438,7 +455,9 @@ where
 
     fn build(self, el: &R::Element, key: &str) -> Self::State {
         let was_some = self.is_some();
-        let value = JsValue::from(self.map(|n| JsValue::from_str(&n)));
+        let value = js_value::<R>(|| {
+            JsValue::from(self.map(|n| JsValue::from_str(&n)))
+        });
         if was_some {
             R::set_property(el, key, &value);
         }
@@ -447,7 +466,9 @@ where
 
     fn rebuild(self, state: &mut Self::State, key: &str) {
         let (el, prev) = state;
-        let value = JsValue::from(self.map(|n| JsValue::from_str(&n)));
+        let value = js_value::<R>(|| {
+            JsValue::from(self.map(|n| JsValue::from_str(&n)))
+        });
         R::set_property(el, key, &value);
         *prev = value;
     }
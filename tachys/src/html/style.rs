@@ -161,6 +161,36 @@ pub trait IntoStyle<R: DomRenderer>: Send {
     /// Updates the value.
     fn rebuild(self, state: &mut Self::State);
 
+    /// Like [`hydrate`](IntoStyle::hydrate), for an entry in a tuple of styles.
+    ///
+    /// Styles that would otherwise set the whole `style` attribute, like strings, only set their
+    /// own properties here, so that they don't replace the properties of the other entries.
+    fn hydrate_entry<const FROM_SERVER: bool>(
+        self,
+        el: &R::Element,
+    ) -> Self::State
+    where
+        Self: Sized,
+    {
+        self.hydrate::<FROM_SERVER>(el)
+    }
+
+    /// Like [`build`](IntoStyle::build), for an entry in a tuple of styles.
+    fn build_entry(self, el: &R::Element) -> Self::State
+    where
+        Self: Sized,
+    {
+        self.build(el)
+    }
+
+    /// Like [`rebuild`](IntoStyle::rebuild), for an entry in a tuple of styles.
+    fn rebuild_entry(self, state: &mut Self::State)
+    where
+        Self: Sized,
+    {
+        self.rebuild(state)
+    }
+
     /// Converts this to a cloneable type.
     fn into_cloneable(self) -> Self::Cloneable;
 
@@ -176,6 +206,40 @@ pub trait IntoStyle<R: DomRenderer>: Send {
     fn resolve(self) -> impl Future<Output = Self::AsyncOutput> + Send;
 }
 
+/// The property names and values of the declarations in a `style` attribute.
+fn declarations(style: &str) -> impl Iterator<Item = (&str, &str)> {
+    style.split(';').filter_map(|declaration| {
+        let (name, value) = declaration.split_once(':')?;
+        Some((name.trim(), value.trim()))
+    })
+}
+
+/// Sets each of the properties declared in `style` on the element.
+fn set_declarations<R: DomRenderer>(el: &R::Element, style: &str) {
+    let css = R::style(el);
+    for (name, value) in declarations(style) {
+        R::set_css_property(&css, name, value);
+    }
+}
+
+/// Removes the properties declared in `prev` that are not declared in `next` from the element,
+/// and sets those in `next`.
+fn replace_declarations<R: DomRenderer>(
+    el: &R::Element,
+    prev: &str,
+    next: &str,
+) {
+    let css = R::style(el);
+    for (name, _) in declarations(prev) {
+        if !declarations(next).any(|(next, _)| next == name) {
+            R::set_css_property(&css, name, "");
+        }
+    }
+    for (name, value) in declarations(next) {
+        R::set_css_property(&css, name, value);
+    }
+}
+
 impl<'a, R> IntoStyle<R> for &'a str
 where
     R: DomRenderer,
@@ -207,6 +271,29 @@ where
         *prev = self;
     }
 
+    fn hydrate_entry<const FROM_SERVER: bool>(
+        self,
+        el: &R::Element,
+    ) -> Self::State {
+        if !FROM_SERVER {
+            set_declarations::<R>(el, self);
+        }
+        (el.clone(), self)
+    }
+
+    fn build_entry(self, el: &R::Element) -> Self::State {
+        set_declarations::<R>(el, self);
+        (el.clone(), self)
+    }
+
+    fn rebuild_entry(self, state: &mut Self::State) {
+        let (el, prev) = state;
+        if self != *prev {
+            replace_declarations::<R>(el, prev, self);
+        }
+        *prev = self;
+    }
+
     fn into_cloneable(self) -> Self::Cloneable {
         self
     }
@@ -253,6 +340,29 @@ where
         *prev = self;
     }
 
+    fn hydrate_entry<const FROM_SERVER: bool>(
+        self,
+        el: &R::Element,
+    ) -> Self::State {
+        if !FROM_SERVER {
+            set_declarations::<R>(el, &self);
+        }
+        (el.clone(), self)
+    }
+
+    fn build_entry(self, el: &R::Element) -> Self::State {
+        set_declarations::<R>(el, &self);
+        (el.clone(), self)
+    }
+
+    fn rebuild_entry(self, state: &mut Self::State) {
+        let (el, prev) = state;
+        if self != *prev {
+            replace_declarations::<R>(el, prev, &self);
+        }
+        *prev = self;
+    }
+
     fn into_cloneable(self) -> Self::Cloneable {
         self
     }
@@ -299,6 +409,29 @@ where
         *prev = self;
     }
 
+    fn hydrate_entry<const FROM_SERVER: bool>(
+        self,
+        el: &R::Element,
+    ) -> Self::State {
+        if !FROM_SERVER {
+            set_declarations::<R>(el, &self);
+        }
+        (el.clone(), self)
+    }
+
+    fn build_entry(self, el: &R::Element) -> Self::State {
+        set_declarations::<R>(el, &self);
+        (el.clone(), self)
+    }
+
+    fn rebuild_entry(self, state: &mut Self::State) {
+        let (el, prev) = state;
+        if self != *prev {
+            replace_declarations::<R>(el, prev, &self);
+        }
+        *prev = self;
+    }
+
     fn into_cloneable(self) -> Self::Cloneable {
         self.into()
     }
@@ -319,7 +452,7 @@ where
     R: DomRenderer,
 {
     type AsyncOutput = Self;
-    type State = (R::CssStyleDeclaration, Arc<str>);
+    type State = (R::CssStyleDeclaration, Arc<str>, Arc<str>);
     type Cloneable = Self;
     type CloneableOwned = Self;
 
@@ -333,23 +466,29 @@ where
 
     fn hydrate<const FROM_SERVER: bool>(self, el: &R::Element) -> Self::State {
         let style = R::style(el);
-        (style, self.1)
+        (style, self.0, self.1)
     }
 
     fn build(self, el: &R::Element) -> Self::State {
         let (name, value) = self;
         let style = R::style(el);
         R::set_css_property(&style, &name, &value);
-        (style, value)
+        (style, name, value)
     }
 
     fn rebuild(self, state: &mut Self::State) {
         let (name, value) = self;
-        let (style, prev) = state;
-        if value != *prev {
+        let (style, prev_name, prev_value) = state;
+        // the name can change as well, for example when a logical property is mapped to a
+        // physical one for the current text direction
+        if name != *prev_name {
+            R::set_css_property(style, prev_name, "");
+            R::set_css_property(style, &name, &value);
+        } else if value != *prev_value {
             R::set_css_property(style, &name, &value);
         }
-        *prev = value;
+        *prev_name = name;
+        *prev_value = value;
     }
 
     fn into_cloneable(self) -> Self::Cloneable {
@@ -604,6 +743,133 @@ where
     }
 }
 
+impl<R> IntoStyle<R> for ()
+where
+    R: DomRenderer,
+{
+    type AsyncOutput = Self;
+    type State = ();
+    type Cloneable = Self;
+    type CloneableOwned = Self;
+
+    fn to_html(self, _style: &mut String) {}
+
+    fn hydrate<const FROM_SERVER: bool>(self, _el: &R::Element) -> Self::State {
+    }
+
+    fn build(self, _el: &R::Element) -> Self::State {}
+
+    fn rebuild(self, _state: &mut Self::State) {}
+
+    fn into_cloneable(self) -> Self::Cloneable {}
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {}
+
+    fn dry_resolve(&mut self) {}
+
+    async fn resolve(self) -> Self::AsyncOutput {}
+}
+
+// A tuple of styles acts like a style object: each entry is applied to the element separately, so
+// that changing one entry on rebuild only sets that property. Entries that are strings set the
+// properties they declare, rather than replacing the whole `style` attribute.
+//
+// Every arity is generated by `impl_style_for_tuples!`. A generic implementation for 2-tuples
+// would overlap with name-value pairs like `(&str, &str)`, so the pair arm only matches pairs
+// whose first entry is itself a tuple, as in `(("color", color), ("font-weight", weight))`.
+// The async and cloneable forms of that first entry are not known to be pairs, so those of the
+// pair are padded with a trailing `()` to use the flat 3-tuple implementation.
+macro_rules! impl_style_for_tuples {
+    (($a0:ident, $a1:ident), $b:ident) => {
+        impl_style_for_tuples!(@impl [$a0, $a1, $b] [A: ($a0, $a1), $b: $b] ());
+    };
+    ($($ty:ident),+ $(,)?) => {
+        impl_style_for_tuples!(@impl [$($ty),+] [$($ty: $ty),+]);
+    };
+    (@impl [$($gen:ident),+] [$($name:ident: $ty:ty),+] $($pad:tt)?) => {
+        impl<$($gen,)+ Rndr> IntoStyle<Rndr> for ($($ty,)+)
+        where
+            $($ty: IntoStyle<Rndr>,)+
+            Rndr: DomRenderer,
+        {
+            type AsyncOutput =
+                ($(<$ty as IntoStyle<Rndr>>::AsyncOutput,)+ $($pad)?);
+            type State = ($(<$ty as IntoStyle<Rndr>>::State,)+);
+            type Cloneable =
+                ($(<$ty as IntoStyle<Rndr>>::Cloneable,)+ $($pad)?);
+            type CloneableOwned =
+                ($(<$ty as IntoStyle<Rndr>>::CloneableOwned,)+ $($pad)?);
+
+            fn to_html(self, style: &mut String) {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+                $($name.to_html(style);)+
+            }
+
+            fn hydrate<const FROM_SERVER: bool>(
+                self,
+                el: &Rndr::Element,
+            ) -> Self::State {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+                ($($name.hydrate_entry::<FROM_SERVER>(el),)+)
+            }
+
+            fn build(self, el: &Rndr::Element) -> Self::State {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+                ($($name.build_entry(el),)+)
+            }
+
+            fn rebuild(self, state: &mut Self::State) {
+                paste::paste! {
+                    let ($([<$name:lower>],)+) = self;
+                    let ($([<view_ $name:lower>],)+) = state;
+                    $([<$name:lower>].rebuild_entry([<view_ $name:lower>]);)+
+                }
+            }
+
+            fn into_cloneable(self) -> Self::Cloneable {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+                ($($name.into_cloneable(),)+ $($pad)?)
+            }
+
+            fn into_cloneable_owned(self) -> Self::CloneableOwned {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+                ($($name.into_cloneable_owned(),)+ $($pad)?)
+            }
+
+            fn dry_resolve(&mut self) {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+                $($name.dry_resolve();)+
+            }
+
+            async fn resolve(self) -> Self::AsyncOutput {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+                #[allow(non_snake_case)]
+                let ($($name,)+) = futures::join!($($name.resolve()),+);
+                ($($name,)+ $($pad)?)
+            }
+        }
+    };
+}
+
+impl_style_for_tuples!((A0, A1), B);
+impl_style_for_tuples!(A, B, C);
+impl_style_for_tuples!(A, B, C, D);
+impl_style_for_tuples!(A, B, C, D, E);
+impl_style_for_tuples!(A, B, C, D, E, F);
+impl_style_for_tuples!(A, B, C, D, E, F, G);
+impl_style_for_tuples!(A, B, C, D, E, F, G, H);
+impl_style_for_tuples!(A, B, C, D, E, F, G, H, I);
+impl_style_for_tuples!(A, B, C, D, E, F, G, H, I, J);
+impl_style_for_tuples!(A, B, C, D, E, F, G, H, I, J, K);
+impl_style_for_tuples!(A, B, C, D, E, F, G, H, I, J, K, L);
+
 /*
 #[cfg(test)]
 mod tests {
@@ -2,7 +2,7 @@ use crate::{
     renderer::{CastFrom, Renderer},
     view::{Position, PositionState},
 };
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, panic::Location, rc::Rc};
 
 /// Hydration works by walking over the DOM, adding interactivity as needed.
 ///
@@ -90,13 +90,177 @@ where
         }
         let marker = self.current();
         position.set(Position::NextChild);
-        R::Placeholder::cast_from(marker)
-            .expect("could not convert current node into marker node")
-        /*let marker2 = marker.clone();
-        R::Placeholder::cast_from(marker).unwrap_or_else(|| {
-            crate::dom::log("expecting to find a marker. instead, found");
-            R::log_node(&marker2);
-            panic!("oops.");
-        })*/
+        R::Placeholder::cast_from(marker.clone()).unwrap_or_else(|| {
+            failed_to_cast_node::<R>("a marker node", &marker, None)
+        })
+    }
+
+    /// Removes the marker that opens or closes an out-of-order chunk, if it is the next node to
+    /// be hydrated. The cursor itself is not moved.
+    ///
+    /// If the page is hydrated before an out-of-order chunk has streamed in, the fallback for that
+    /// chunk is still in the document, wrapped in these markers. Removing them registers the chunk
+    /// as pending in the hydrated view: when the chunk arrives, its inline script does not find the
+    /// markers, and discards the server-rendered HTML rather than swapping it in underneath the
+    /// live view, which renders the resolved content itself.
+    ///
+    /// Returns `true` if a marker was removed.
+    pub fn take_chunk_marker(
+        &self,
+        position: &PositionState,
+        opening: bool,
+    ) -> bool {
+        let current = self.current();
+        let next = if position.get() == Position::FirstChild {
+            R::first_child(&current)
+        } else {
+            R::next_sibling(&current)
+        };
+        let suffix = if opening { "-o" } else { "-c" };
+        match next {
+            Some(node)
+                if R::comment_text(&node).is_some_and(|text| {
+                    text.starts_with("s-") && text.ends_with(suffix)
+                }) =>
+            {
+                R::remove(&node);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(debug_assertions)]
+thread_local! {
+    static HYDRATION_PATH: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Records that hydration has descended into an element with the given tag.
+///
+/// In debug mode, the path of elements from the root to the node currently being hydrated is
+/// included in hydration mismatch diagnostics. Every call should be paired with a call to
+/// [`exit_element`].
+#[inline(always)]
+pub fn enter_element(tag: &str) {
+    #[cfg(debug_assertions)]
+    HYDRATION_PATH.with(|path| path.borrow_mut().push(tag.to_string()));
+    #[cfg(not(debug_assertions))]
+    {
+        _ = tag;
+    }
+}
+
+/// Records that hydration has finished with the element most recently passed to
+/// [`enter_element`].
+#[inline(always)]
+pub fn exit_element() {
+    #[cfg(debug_assertions)]
+    HYDRATION_PATH.with(|path| {
+        path.borrow_mut().pop();
+    });
+}
+
+/// Returns the path of elements from the root to the node currently being hydrated, like
+/// `<main> > <ul> > <li>`.
+///
+/// This is only tracked in debug mode, and is always empty in release mode.
+pub fn hydration_path() -> String {
+    #[cfg(debug_assertions)]
+    {
+        HYDRATION_PATH.with(|path| {
+            let path = path.borrow();
+            let mut buf = String::new();
+            for (idx, tag) in path.iter().enumerate() {
+                if idx > 0 {
+                    buf.push_str(" > ");
+                }
+                buf.push('<');
+                buf.push_str(tag);
+                buf.push('>');
+            }
+            buf
+        })
+    }
+    #[cfg(not(debug_assertions))]
+    {
+        String::new()
+    }
+}
+
+/// Checks that an element found while hydrating has the expected tag.
+///
+/// In debug mode, logs the actual DOM node and panics with the element path and the expected
+/// tag if the renderer reports a different tag, because hydrating the wrong element would
+/// silently attach attributes, event listeners, and children to the wrong part of the page.
+/// Does nothing in release mode, or if the renderer cannot report tag names.
+#[track_caller]
+#[inline(always)]
+pub fn validate_element<R: Renderer>(
+    expected_tag: &str,
+    el: &R::Element,
+    defined_at: Option<&'static Location<'static>>,
+) where
+    R::Element: AsRef<R::Node>,
+{
+    #[cfg(debug_assertions)]
+    {
+        // custom elements in templates have an empty tag and can't be checked
+        if expected_tag.is_empty() {
+            return;
+        }
+        if let Some(actual) = R::tag_name(el) {
+            if !actual.eq_ignore_ascii_case(expected_tag) {
+                failed_to_cast_node::<R>(
+                    &format!("a <{expected_tag}> element"),
+                    el.as_ref(),
+                    defined_at,
+                );
+            }
+        }
+    }
+    #[cfg(not(debug_assertions))]
+    {
+        _ = (expected_tag, el, defined_at);
+    }
+}
+
+/// Reports that the node found while hydrating does not match the view being hydrated, and
+/// panics.
+///
+/// In debug mode, this logs the actual DOM node and includes the path of elements leading to it
+/// and, if known, the location at which the expected view was defined.
+#[cold]
+#[track_caller]
+pub fn failed_to_cast_node<R: Renderer>(
+    expected: &str,
+    actual: &R::Node,
+    defined_at: Option<&'static Location<'static>>,
+) -> ! {
+    #[cfg(debug_assertions)]
+    {
+        R::log_node(actual);
+        let path = hydration_path();
+        let path = if path.is_empty() {
+            String::from("the root")
+        } else {
+            path
+        };
+        let defined_at = defined_at
+            .map(|loc| format!(" (defined at {loc})"))
+            .unwrap_or_default();
+        panic!(
+            "Hydration error: expected to find {expected}{defined_at} inside \
+             {path}, but found the node logged above instead. This usually \
+             means the HTML rendered on the server does not match the view \
+             rendered on the client, for example because of invalid HTML \
+             that the browser corrected, or because the view depends on \
+             data that differs between server and client."
+        );
+    }
+    #[cfg(not(debug_assertions))]
+    {
+        _ = (actual, defined_at);
+        panic!("Hydration error: expected to find {expected}.");
     }
 }
@@ -12,6 +12,7 @@ pub mod prelude {
     pub use crate::{
         html::{
             attribute::{
+                any_attribute::IntoAnyAttribute,
                 aria::AriaAttributes,
                 custom::CustomAttribute,
                 global::{
@@ -30,6 +31,8 @@ pub mod prelude {
             RenderHtml,
         },
     };
+    #[cfg(feature = "reactive_graph")]
+    pub use crate::reactive_graph::bind::BindAttribute;
 }
 
 use wasm_bindgen::JsValue;
@@ -43,12 +46,17 @@ pub mod html;
 pub mod hydration;
 /// Types for MathML.
 pub mod mathml;
+/// Patches existing DOM trees to match new HTML.
+pub mod morph;
 /// Defines various backends that can render views.
 pub mod renderer;
 /// Rendering views to HTML.
 pub mod ssr;
 /// Types for SVG.
 pub mod svg;
+/// Helpers for testing views natively, using a mock DOM.
+#[cfg(feature = "testing")]
+pub mod testing;
 /// Core logic for manipulating views.
 pub mod view;
 
@@ -0,0 +1,610 @@
+use crate::dom::document;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    Document, Element, HtmlElement, HtmlInputElement, HtmlTemplateElement, Node,
+};
+
+const ISLAND_TAG: &str = "LEPTOS-ISLAND";
+
+/// The attribute that marks an element which was inserted into the page by the client, like the
+/// container of a `<Portal/>`. These elements do not appear in server-rendered HTML, so they are
+/// never patched or removed.
+pub const CLIENT_ONLY_ATTR: &str = "data-leptos-client-only";
+
+/// Patches the children of `from` so that they match the HTML in `to_html`.
+///
+/// Rather than replacing the whole tree, this walks the existing nodes and the new ones side by
+/// side, keeping any node that is unchanged, updating the attributes and text of nodes that are
+/// of the same kind, and only inserting or removing nodes where the structure differs. Elements
+/// with an `id` are moved into place if they appear later among their siblings.
+///
+/// This means that the rest of the page is left alone:
+/// - the focused element keeps focus, along with its text selection
+/// - form controls keep the values the user has entered, because only their attributes are
+///   updated
+/// - islands whose component and props have not changed are not touched, so their state and
+///   event listeners are kept
+/// - elements marked with [`CLIENT_ONLY_ATTR`] are kept as they are
+///
+/// It can be used to apply new server-rendered HTML to a page, for example when hot-reloading a
+/// view or navigating between pages in an islands app.
+///
+/// Any other node that is patched or removed loses whatever the client-side view tree expected
+/// of it, so this should not be used on parts of the page that have been hydrated outside of an
+/// island.
+pub fn morph(from: &Element, to_html: &str) {
+    let to = parse(to_html);
+    let focused = Focus::save();
+    morph_children::<Node>(from, &to);
+    if let Some(focused) = focused {
+        focused.restore();
+    }
+}
+
+/// Patches the children of `from` like [`morph`], but only if the islands in `to_html` are the
+/// same as the ones on the page, with the same components and props in the same order. Returns
+/// `false` without changing anything otherwise.
+///
+/// In an islands app, everything outside of the islands is static HTML, so this patches the
+/// page without touching any state. An island that was added, removed or given new props would
+/// need to be hydrated again, which only a reload does.
+pub fn morph_static(from: &Element, to_html: &str) -> bool {
+    let to = parse(to_html);
+    if islands::<Node>(from) != islands::<Node>(&to) {
+        return false;
+    }
+    let focused = Focus::save();
+    morph_children::<Node>(from, &to);
+    if let Some(focused) = focused {
+        focused.restore();
+    }
+    true
+}
+
+/// Patches the current page to match `doc`, a whole page of server-rendered HTML, like
+/// [`morph`]: the attributes of `<html>` and `<body>`, and the children of `<head>` and
+/// `<body>`, are updated in place.
+///
+/// This is how an islands app navigates between pages, keeping any islands that are in the same
+/// place on both pages with the same props. `<script>` elements that are inserted are not run.
+pub fn morph_document(doc: &Document) {
+    let current = document();
+    let focused = Focus::save();
+    if let (Some(old), Some(new)) =
+        (current.document_element(), doc.document_element())
+    {
+        morph_attributes::<Node>(&old.into(), &new.into());
+    }
+    if let (Some(old), Some(new)) = (current.head(), doc.head()) {
+        morph_children::<Node>(&old.into(), &new.into());
+    }
+    if let (Some(old), Some(new)) = (current.body(), doc.body()) {
+        let (old, new): (Node, Node) = (old.into(), new.into());
+        morph_attributes(&old, &new);
+        morph_children(&old, &new);
+    }
+    if let Some(focused) = focused {
+        focused.restore();
+    }
+}
+
+fn parse(html: &str) -> Node {
+    let tpl = document()
+        .create_element("template")
+        .unwrap()
+        .unchecked_into::<HtmlTemplateElement>();
+    tpl.set_inner_html(html);
+    tpl.content().into()
+}
+
+/// The DOM operations that patching a tree relies on.
+trait MorphNode: Clone {
+    fn first_child(&self) -> Option<Self>;
+    fn next_sibling(&self) -> Option<Self>;
+    fn is_equal_node(&self, other: &Self) -> bool;
+    fn node_type(&self) -> u16;
+    fn node_value(&self) -> Option<String>;
+    fn set_node_value(&self, value: Option<&str>);
+    /// The tag name, if this is an element.
+    fn tag_name(&self) -> Option<String>;
+    fn attribute(&self, name: &str) -> Option<String>;
+    fn attribute_names(&self) -> Vec<String>;
+    fn set_attribute(&self, name: &str, value: &str);
+    fn remove_attribute(&self, name: &str);
+    fn insert_before(&self, node: &Self, reference: Option<&Self>);
+    fn remove_child(&self, child: &Self);
+}
+
+impl MorphNode for Node {
+    fn first_child(&self) -> Option<Self> {
+        Node::first_child(self)
+    }
+
+    fn next_sibling(&self) -> Option<Self> {
+        Node::next_sibling(self)
+    }
+
+    fn is_equal_node(&self, other: &Self) -> bool {
+        Node::is_equal_node(self, Some(other))
+    }
+
+    fn node_type(&self) -> u16 {
+        Node::node_type(self)
+    }
+
+    fn node_value(&self) -> Option<String> {
+        Node::node_value(self)
+    }
+
+    fn set_node_value(&self, value: Option<&str>) {
+        Node::set_node_value(self, value)
+    }
+
+    fn tag_name(&self) -> Option<String> {
+        self.dyn_ref::<Element>().map(Element::tag_name)
+    }
+
+    fn attribute(&self, name: &str) -> Option<String> {
+        self.dyn_ref::<Element>()?.get_attribute(name)
+    }
+
+    fn attribute_names(&self) -> Vec<String> {
+        self.dyn_ref::<Element>()
+            .map(|el| {
+                el.get_attribute_names()
+                    .iter()
+                    .filter_map(|name| name.as_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn set_attribute(&self, name: &str, value: &str) {
+        if let Some(el) = self.dyn_ref::<Element>() {
+            _ = el.set_attribute(name, value);
+        }
+    }
+
+    fn remove_attribute(&self, name: &str) {
+        if let Some(el) = self.dyn_ref::<Element>() {
+            _ = el.remove_attribute(name);
+        }
+    }
+
+    fn insert_before(&self, node: &Self, reference: Option<&Self>) {
+        _ = Node::insert_before(self, node, reference);
+    }
+
+    fn remove_child(&self, child: &Self) {
+        _ = Node::remove_child(self, child);
+    }
+}
+
+fn morph_children<N: MorphNode>(from: &N, to: &N) {
+    let mut prev = skip_client_only(from.first_child());
+    let mut next = to.first_child();
+    while let Some(new) = next {
+        // get the next node first, because `new` may be moved into `from`
+        next = new.next_sibling();
+        match prev {
+            Some(old) if old.is_equal_node(&new) => {
+                prev = skip_client_only(old.next_sibling());
+            }
+            Some(old) if same_kind(&old, &new) => {
+                morph_node(&old, &new);
+                prev = skip_client_only(old.next_sibling());
+            }
+            _ => match find_by_id(prev.as_ref(), &new) {
+                Some(keyed) => {
+                    from.insert_before(&keyed, prev.as_ref());
+                    morph_node(&keyed, &new);
+                }
+                None => {
+                    from.insert_before(&new, prev.as_ref());
+                }
+            },
+        }
+    }
+    while let Some(stale) = prev {
+        prev = skip_client_only(stale.next_sibling());
+        from.remove_child(&stale);
+    }
+}
+
+/// Skips over any nodes that were inserted by the client, which are kept where they are.
+fn skip_client_only<N: MorphNode>(mut node: Option<N>) -> Option<N> {
+    while let Some(current) = node
+        .as_ref()
+        .filter(|n| n.attribute(CLIENT_ONLY_ATTR).is_some())
+    {
+        node = current.next_sibling();
+    }
+    node
+}
+
+fn morph_node<N: MorphNode>(old: &N, new: &N) {
+    match (old.tag_name(), new.tag_name()) {
+        (Some(tag), Some(_)) => {
+            // an island that has the same component and props keeps its state
+            if tag == ISLAND_TAG {
+                return;
+            }
+            morph_attributes(old, new);
+            // the value of a <textarea> is its text, which the user may have edited
+            if tag != "TEXTAREA" {
+                morph_children(old, new);
+            }
+        }
+        _ => {
+            let value = new.node_value();
+            if old.node_value() != value {
+                old.set_node_value(value.as_deref());
+            }
+        }
+    }
+}
+
+fn morph_attributes<N: MorphNode>(old: &N, new: &N) {
+    for name in old.attribute_names() {
+        if new.attribute(&name).is_none() {
+            old.remove_attribute(&name);
+        }
+    }
+    for name in new.attribute_names() {
+        let value = new.attribute(&name).unwrap_or_default();
+        if old.attribute(&name).as_deref() != Some(value.as_str()) {
+            old.set_attribute(&name, &value);
+        }
+    }
+}
+
+fn same_kind<N: MorphNode>(old: &N, new: &N) -> bool {
+    if old.node_type() != new.node_type() {
+        return false;
+    }
+    match (old.tag_name(), new.tag_name()) {
+        (Some(old_tag), Some(new_tag)) => {
+            old_tag == new_tag
+                && old.attribute("id") == new.attribute("id")
+                && (old_tag != ISLAND_TAG || island(old) == island(new))
+        }
+        _ => true,
+    }
+}
+
+fn find_by_id<N: MorphNode>(start: Option<&N>, new: &N) -> Option<N> {
+    new.tag_name()?;
+    let id = new.attribute("id")?;
+    let mut current = start.cloned();
+    while let Some(node) = current {
+        if node.attribute("id").as_deref() == Some(id.as_str())
+            && same_kind(&node, new)
+        {
+            return Some(node);
+        }
+        current = node.next_sibling();
+    }
+    None
+}
+
+/// The component and props of an island.
+fn island<N: MorphNode>(node: &N) -> (Option<String>, Option<String>) {
+    (
+        node.attribute("data-component"),
+        node.attribute("data-props"),
+    )
+}
+
+/// The outermost islands under `root`, in document order.
+fn islands<N: MorphNode>(root: &N) -> Vec<(Option<String>, Option<String>)> {
+    let mut found = Vec::new();
+    let mut child = root.first_child();
+    while let Some(node) = child {
+        if node.tag_name().as_deref() == Some(ISLAND_TAG) {
+            found.push(island(&node));
+        } else if node.attribute(CLIENT_ONLY_ATTR).is_none() {
+            found.extend(islands(&node));
+        }
+        child = node.next_sibling();
+    }
+    found
+}
+
+/// The focused element, which loses focus if it is moved.
+struct Focus {
+    el: HtmlElement,
+    selection: Option<(u32, u32)>,
+}
+
+impl Focus {
+    fn save() -> Option<Self> {
+        let el = document()
+            .active_element()?
+            .dyn_into::<HtmlElement>()
+            .ok()?;
+        let selection = el.dyn_ref::<HtmlInputElement>().and_then(|input| {
+            Some((input.selection_start().ok()??, input.selection_end().ok()??))
+        });
+        Some(Self { el, selection })
+    }
+
+    fn restore(self) {
+        let still_focused = document()
+            .active_element()
+            .is_some_and(|active| active == *self.el);
+        if still_focused || !self.el.is_connected() {
+            return;
+        }
+        _ = self.el.focus();
+        if let (Some(input), Some((start, end))) =
+            (self.el.dyn_ref::<HtmlInputElement>(), self.selection)
+        {
+            _ = input.set_selection_range(start, end);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        islands, morph_children, MorphNode, CLIENT_ONLY_ATTR, ISLAND_TAG,
+    };
+    use std::{
+        cell::RefCell,
+        rc::{Rc, Weak},
+    };
+
+    #[derive(Default)]
+    struct NodeData {
+        tag: Option<String>,
+        value: Option<String>,
+        attrs: Vec<(String, String)>,
+        children: Vec<TestNode>,
+        parent: Weak<RefCell<NodeData>>,
+    }
+
+    /// A minimal DOM tree, so that patching can be tested without a browser.
+    #[derive(Clone)]
+    struct TestNode(Rc<RefCell<NodeData>>);
+
+    fn el(
+        tag: &str,
+        attrs: &[(&str, &str)],
+        children: Vec<TestNode>,
+    ) -> TestNode {
+        let node = TestNode(Rc::new(RefCell::new(NodeData {
+            tag: Some(tag.to_ascii_uppercase()),
+            attrs: attrs
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            ..Default::default()
+        })));
+        for child in children {
+            node.insert_before(&child, None);
+        }
+        node
+    }
+
+    fn text(value: &str) -> TestNode {
+        TestNode(Rc::new(RefCell::new(NodeData {
+            value: Some(value.to_string()),
+            ..Default::default()
+        })))
+    }
+
+    impl TestNode {
+        fn parent(&self) -> Option<TestNode> {
+            self.0.borrow().parent.upgrade().map(TestNode)
+        }
+
+        fn index(&self) -> Option<usize> {
+            let parent = self.parent()?;
+            let index = parent
+                .0
+                .borrow()
+                .children
+                .iter()
+                .position(|child| Rc::ptr_eq(&child.0, &self.0));
+            index
+        }
+
+        fn to_html(&self) -> String {
+            let data = self.0.borrow();
+            match &data.tag {
+                Some(tag) => {
+                    let tag = tag.to_ascii_lowercase();
+                    let attrs = data
+                        .attrs
+                        .iter()
+                        .map(|(name, value)| format!(" {name}=\"{value}\""))
+                        .collect::<String>();
+                    let children = data
+                        .children
+                        .iter()
+                        .map(TestNode::to_html)
+                        .collect::<String>();
+                    format!("<{tag}{attrs}>{children}</{tag}>")
+                }
+                None => data.value.clone().unwrap_or_default(),
+            }
+        }
+    }
+
+    impl MorphNode for TestNode {
+        fn first_child(&self) -> Option<Self> {
+            self.0.borrow().children.first().cloned()
+        }
+
+        fn next_sibling(&self) -> Option<Self> {
+            let index = self.index()?;
+            let parent = self.parent()?;
+            let sibling = parent.0.borrow().children.get(index + 1).cloned();
+            sibling
+        }
+
+        fn is_equal_node(&self, other: &Self) -> bool {
+            self.to_html() == other.to_html()
+        }
+
+        fn node_type(&self) -> u16 {
+            if self.0.borrow().tag.is_some() {
+                1
+            } else {
+                3
+            }
+        }
+
+        fn node_value(&self) -> Option<String> {
+            self.0.borrow().value.clone()
+        }
+
+        fn set_node_value(&self, value: Option<&str>) {
+            self.0.borrow_mut().value = value.map(str::to_string);
+        }
+
+        fn tag_name(&self) -> Option<String> {
+            self.0.borrow().tag.clone()
+        }
+
+        fn attribute(&self, name: &str) -> Option<String> {
+            self.0
+                .borrow()
+                .attrs
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, value)| value.clone())
+        }
+
+        fn attribute_names(&self) -> Vec<String> {
+            self.0
+                .borrow()
+                .attrs
+                .iter()
+                .map(|(n, _)| n.clone())
+                .collect()
+        }
+
+        fn set_attribute(&self, name: &str, value: &str) {
+            self.remove_attribute(name);
+            self.0
+                .borrow_mut()
+                .attrs
+                .push((name.to_string(), value.to_string()));
+        }
+
+        fn remove_attribute(&self, name: &str) {
+            self.0.borrow_mut().attrs.retain(|(n, _)| n != name);
+        }
+
+        fn insert_before(&self, node: &Self, reference: Option<&Self>) {
+            // like the DOM, inserting a node moves it from wherever it was
+            if let Some(parent) = node.parent() {
+                parent.remove_child(node);
+            }
+            let index = reference
+                .and_then(TestNode::index)
+                .unwrap_or(self.0.borrow().children.len());
+            node.0.borrow_mut().parent = Rc::downgrade(&self.0);
+            self.0.borrow_mut().children.insert(index, node.clone());
+        }
+
+        fn remove_child(&self, child: &Self) {
+            if let Some(index) = child.index() {
+                self.0.borrow_mut().children.remove(index);
+                child.0.borrow_mut().parent = Weak::new();
+            }
+        }
+    }
+
+    fn island(
+        component: &str,
+        props: &str,
+        children: Vec<TestNode>,
+    ) -> TestNode {
+        el(
+            ISLAND_TAG,
+            &[("data-component", component), ("data-props", props)],
+            children,
+        )
+    }
+
+    #[test]
+    fn static_html_is_patched_in_place() {
+        let heading = el("h1", &[("class", "old")], vec![text("Old title")]);
+        let page = el("body", &[], vec![heading.clone(), el("p", &[], vec![])]);
+        let new = el(
+            "body",
+            &[],
+            vec![el("h1", &[("class", "new")], vec![text("New title")])],
+        );
+
+        morph_children(&page, &new);
+        assert_eq!(
+            page.to_html(),
+            "<body><h1 class=\"new\">New title</h1></body>"
+        );
+        // the heading was updated rather than replaced
+        assert!(Rc::ptr_eq(&page.first_child().unwrap().0, &heading.0));
+    }
+
+    #[test]
+    fn unchanged_islands_keep_their_contents() {
+        let counter = island("Counter", "{}", vec![text("clicked 3 times")]);
+        let page = el(
+            "body",
+            &[],
+            vec![el("p", &[], vec![text("Intro")]), counter.clone()],
+        );
+        let new = el(
+            "body",
+            &[],
+            vec![
+                el("p", &[], vec![text("New intro")]),
+                island("Counter", "{}", vec![text("clicked 0 times")]),
+            ],
+        );
+
+        assert_eq!(islands(&page), islands(&new));
+        morph_children(&page, &new);
+        assert_eq!(
+            counter.to_html(),
+            island("Counter", "{}", vec![text("clicked 3 times")]).to_html()
+        );
+        assert!(page.to_html().contains("New intro"));
+    }
+
+    #[test]
+    fn changed_islands_are_detected() {
+        let page = el("body", &[], vec![island("Counter", "{}", vec![])]);
+        let new_props = el(
+            "body",
+            &[],
+            vec![island("Counter", "{\"start\":1}", vec![])],
+        );
+        let removed = el("body", &[], vec![el("p", &[], vec![])]);
+
+        assert_ne!(islands(&page), islands(&new_props));
+        assert_ne!(islands(&page), islands(&removed));
+    }
+
+    #[test]
+    fn client_only_elements_are_kept() {
+        let portal = el("div", &[(CLIENT_ONLY_ATTR, "")], vec![text("Modal")]);
+        let page = el(
+            "body",
+            &[],
+            vec![el("main", &[], vec![text("Old")]), portal.clone()],
+        );
+        let new = el("body", &[], vec![el("main", &[], vec![text("New")])]);
+
+        morph_children(&page, &new);
+        assert_eq!(
+            page.to_html(),
+            format!(
+                "<body><main>New</main><div {CLIENT_ONLY_ATTR}=\"\">Modal</div></body>"
+            )
+        );
+        assert!(Rc::ptr_eq(&portal.parent().unwrap().0, &page.0));
+    }
+}
@@ -1,5 +1,10 @@
 use crate::{
-    html::{attribute::AttributeValue, class::IntoClass},
+    html::{
+        attribute::AttributeValue,
+        class::{
+            add_class_names, replace_class_names, split_class_names, IntoClass,
+        },
+    },
     hydration::Cursor,
     no_attrs,
     prelude::{Mountable, Render, RenderHtml},
@@ -7,6 +12,7 @@ use crate::{
     view::{strings::StrState, Position, PositionState, ToTemplate},
 };
 use oco_ref::Oco;
+use std::borrow::Cow;
 
 /// Retained view state for [`Oco`].
 pub struct OcoStrState<R: Renderer> {
@@ -211,6 +217,29 @@ where
         *prev = self;
     }
 
+    fn hydrate_entry<const FROM_SERVER: bool>(
+        self,
+        el: &R::Element,
+    ) -> Self::State {
+        if !FROM_SERVER {
+            add_class_names::<R>(el, &self);
+        }
+        (el.clone(), self)
+    }
+
+    fn build_entry(self, el: &R::Element) -> Self::State {
+        add_class_names::<R>(el, &self);
+        (el.clone(), self)
+    }
+
+    fn rebuild_entry(self, state: &mut Self::State) {
+        let (el, prev) = state;
+        if self != *prev {
+            replace_class_names::<R>(el, prev, &self);
+        }
+        *prev = self;
+    }
+
     fn into_cloneable(mut self) -> Self::Cloneable {
         // ensure it's reference-counted
         self.upgrade_inplace();
@@ -228,4 +257,8 @@ where
     async fn resolve(self) -> Self::AsyncOutput {
         self
     }
+
+    fn class_names(&self) -> Vec<Cow<'static, str>> {
+        split_class_names(self.as_str())
+    }
 }
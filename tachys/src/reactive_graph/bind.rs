@@ -0,0 +1,814 @@
+use crate::{
+    html::{
+        attribute::{escape_attr, Attribute, NextAttribute},
+        element::{ElementType, HtmlElement},
+    },
+    renderer::{dom::Dom, DomRenderer, RemoveEventHandler},
+    view::{add_attr::AddAnyAttr, Render},
+};
+use js_sys::Reflect;
+use reactive_graph::{
+    effect::RenderEffect,
+    signal::{
+        ArcReadSignal, ArcRwSignal, ArcWriteSignal, ReadSignal, RwSignal,
+        WriteSignal,
+    },
+    traits::{Get, GetUntracked, Set},
+};
+use std::{cell::RefCell, marker::PhantomData, ops::Range};
+use wasm_bindgen::JsValue;
+use web_sys::Element;
+
+/// Binds the `value` of an `<input>`, `<textarea>`, or `<select>` to a signal.
+///
+/// A `String` signal is updated on every `input` event, except while an IME composition is in
+/// progress; the value is written once the composition ends. A `Vec<String>` signal binds the
+/// selected options of a `<select multiple>`, and is updated on `change`.
+///
+/// When rendering a `<select>` on the server, only options with a `value` attribute are selected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Value;
+
+/// Binds the `checked` state of a checkbox to a `bool` signal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checked;
+
+/// Binds a group of radio buttons or checkboxes to a single signal.
+///
+/// Each element in the group is bound to the same signal. For radio buttons, the signal holds the
+/// `value` of the checked button as a `String`. For checkboxes, it holds the values of every
+/// checked box as a `Vec<String>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Group;
+
+/// Describes how a bound value is read from and written to an element.
+pub trait BindKey<T>: Copy + Send + 'static {
+    /// The event after which the value is read back from the element.
+    const EVENT: &'static str;
+
+    /// Whether updates should wait for IME composition to finish.
+    const COMPOSITION: bool = false;
+
+    /// Renders the initial value into the HTML of the element, once its other attributes have
+    /// been rendered.
+    fn to_html(value: &T, el: &mut ElementHtml<'_>);
+
+    /// Updates the element to reflect the value.
+    fn update_dom(el: &Element, value: &T);
+
+    /// Reads the new value from the element, given the current value of the signal.
+    fn from_dom(el: &Element, current: T) -> Option<T>;
+}
+
+/// The HTML of an element whose attributes are being rendered, into which a [`BindKey`] renders
+/// its value.
+#[derive(Debug)]
+pub struct ElementHtml<'a> {
+    buf: &'a mut String,
+    tag: Range<usize>,
+    attrs_start: usize,
+    inner_html: &'a mut String,
+}
+
+impl ElementHtml<'_> {
+    /// The tag name of the element.
+    pub fn tag(&self) -> &str {
+        &self.buf[self.tag.clone()]
+    }
+
+    /// Whether the element has an attribute `name` with the given value.
+    pub fn has_attribute(&self, name: &str, value: &str) -> bool {
+        let attrs = &self.buf[self.attrs_start..];
+        let key = format!(" {name}=\"");
+        attrs.match_indices(&key).any(|(idx, _)| {
+            let rest = &attrs[idx + key.len()..];
+            let raw = rest.find('"').map_or(rest, |end| &rest[..end]);
+            // static attribute values are rendered without escaping them
+            raw == escape_attr(value) || raw == value
+        })
+    }
+
+    /// Adds an attribute to the element, or a boolean attribute if `value` is `None`.
+    pub fn push_attribute(&mut self, name: &str, value: Option<&str>) {
+        self.buf.push(' ');
+        self.buf.push_str(name);
+        if let Some(value) = value {
+            self.buf.push_str("=\"");
+            self.buf.push_str(&escape_attr(value));
+            self.buf.push('"');
+        }
+    }
+
+    /// Sets the content of the element to `text`, replacing its children.
+    pub fn set_text(&mut self, text: &str) {
+        self.inner_html.clear();
+        self.inner_html.push_str(&html_escape::encode_text(text));
+    }
+}
+
+type PendingHtml = Box<dyn FnOnce(&mut ElementHtml<'_>)>;
+
+thread_local! {
+    // bound values of the element being rendered to HTML, which depend on its tag and its other
+    // attributes, so can only be rendered once they are all known
+    static PENDING_HTML: RefCell<Vec<PendingHtml>> = const { RefCell::new(Vec::new()) };
+    // the values of the options to select in the `<select>` being rendered to HTML
+    static SELECTED_OPTIONS: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+fn render_later<K, T>(value: T)
+where
+    K: BindKey<T>,
+    T: 'static,
+{
+    PENDING_HTML.with_borrow_mut(|pending| {
+        pending.push(Box::new(move |el| K::to_html(&value, el)))
+    });
+}
+
+/// Selects the `<option>`s with the given values in the `<select>` being rendered to HTML.
+///
+/// Only options with a `value` attribute can be selected this way.
+fn select_options(values: Vec<String>) {
+    SELECTED_OPTIONS.set(Some(values));
+}
+
+/// Renders the bound values of an element, whose opening tag and attributes have been pushed to
+/// `buf` from `attrs_start` on.
+pub(crate) fn finish_attributes_html(
+    buf: &mut String,
+    attrs_start: usize,
+    inner_html: &mut String,
+) {
+    let pending = PENDING_HTML.take();
+    let selected = SELECTED_OPTIONS.with_borrow(Option::is_some);
+    if pending.is_empty() && !selected {
+        return;
+    }
+
+    let tag = buf[..attrs_start]
+        .rfind('<')
+        .map_or(attrs_start..attrs_start, |idx| idx + 1..attrs_start);
+    let mut el = ElementHtml {
+        buf,
+        tag,
+        attrs_start,
+        inner_html,
+    };
+    if selected {
+        match el.tag() {
+            "option" => {
+                let is_selected = SELECTED_OPTIONS.with_borrow(|values| {
+                    values
+                        .iter()
+                        .flatten()
+                        .any(|v| el.has_attribute("value", v))
+                });
+                if is_selected {
+                    el.push_attribute("selected", None);
+                }
+            }
+            "optgroup" | "hr" => {}
+            // any other element is outside the `<select>`
+            _ => SELECTED_OPTIONS.set(None),
+        }
+    }
+    for render in pending {
+        render(&mut el);
+    }
+}
+
+fn get_prop(el: &JsValue, key: &str) -> JsValue {
+    Reflect::get(el, &JsValue::from_str(key)).unwrap_or(JsValue::UNDEFINED)
+}
+
+fn set_prop(el: &JsValue, key: &str, value: &JsValue) {
+    _ = Reflect::set(el, &JsValue::from_str(key), value);
+}
+
+fn element_value(el: &Element) -> String {
+    get_prop(el, "value").as_string().unwrap_or_default()
+}
+
+fn element_checked(el: &Element) -> bool {
+    get_prop(el, "checked").is_truthy()
+}
+
+/// Calls `f` with each `<option>` of a `<select>` element.
+fn for_each_option(el: &Element, mut f: impl FnMut(&JsValue)) {
+    let options = get_prop(el, "options");
+    let len = get_prop(&options, "length").as_f64().unwrap_or_default() as u32;
+    for idx in 0..len {
+        if let Ok(option) = Reflect::get_u32(&options, idx) {
+            f(&option);
+        }
+    }
+}
+
+impl BindKey<String> for Value {
+    const EVENT: &'static str = "input";
+    const COMPOSITION: bool = true;
+
+    fn to_html(value: &String, el: &mut ElementHtml<'_>) {
+        match el.tag() {
+            "textarea" => el.set_text(value),
+            "select" => select_options(vec![value.clone()]),
+            _ => el.push_attribute("value", Some(value)),
+        }
+    }
+
+    fn update_dom(el: &Element, value: &String) {
+        // setting the same value again would move the cursor to the end of the input
+        if element_value(el) != *value {
+            set_prop(el, "value", &JsValue::from_str(value));
+        }
+    }
+
+    fn from_dom(el: &Element, _current: String) -> Option<String> {
+        Some(element_value(el))
+    }
+}
+
+impl BindKey<Vec<String>> for Value {
+    const EVENT: &'static str = "change";
+
+    fn to_html(value: &Vec<String>, _el: &mut ElementHtml<'_>) {
+        select_options(value.clone());
+    }
+
+    fn update_dom(el: &Element, value: &Vec<String>) {
+        for_each_option(el, |option| {
+            let option_value =
+                get_prop(option, "value").as_string().unwrap_or_default();
+            set_prop(
+                option,
+                "selected",
+                &JsValue::from_bool(value.contains(&option_value)),
+            );
+        });
+    }
+
+    fn from_dom(el: &Element, _current: Vec<String>) -> Option<Vec<String>> {
+        let mut selected = Vec::new();
+        for_each_option(el, |option| {
+            if get_prop(option, "selected").is_truthy() {
+                selected.push(
+                    get_prop(option, "value").as_string().unwrap_or_default(),
+                );
+            }
+        });
+        Some(selected)
+    }
+}
+
+impl BindKey<bool> for Checked {
+    const EVENT: &'static str = "change";
+
+    fn to_html(value: &bool, el: &mut ElementHtml<'_>) {
+        if *value {
+            el.push_attribute("checked", None);
+        }
+    }
+
+    fn update_dom(el: &Element, value: &bool) {
+        set_prop(el, "checked", &JsValue::from_bool(*value));
+    }
+
+    fn from_dom(el: &Element, _current: bool) -> Option<bool> {
+        Some(element_checked(el))
+    }
+}
+
+impl BindKey<String> for Group {
+    const EVENT: &'static str = "change";
+
+    fn to_html(value: &String, el: &mut ElementHtml<'_>) {
+        if el.has_attribute("value", value) {
+            el.push_attribute("checked", None);
+        }
+    }
+
+    fn update_dom(el: &Element, value: &String) {
+        set_prop(
+            el,
+            "checked",
+            &JsValue::from_bool(element_value(el) == *value),
+        );
+    }
+
+    fn from_dom(el: &Element, _current: String) -> Option<String> {
+        // unchecking one radio button always checks another, which will write the value
+        element_checked(el).then(|| element_value(el))
+    }
+}
+
+impl BindKey<Vec<String>> for Group {
+    const EVENT: &'static str = "change";
+
+    fn to_html(value: &Vec<String>, el: &mut ElementHtml<'_>) {
+        if value.iter().any(|v| el.has_attribute("value", v)) {
+            el.push_attribute("checked", None);
+        }
+    }
+
+    fn update_dom(el: &Element, value: &Vec<String>) {
+        set_prop(
+            el,
+            "checked",
+            &JsValue::from_bool(value.contains(&element_value(el))),
+        );
+    }
+
+    fn from_dom(el: &Element, mut current: Vec<String>) -> Option<Vec<String>> {
+        let value = element_value(el);
+        let checked = element_checked(el);
+        match current.iter().position(|v| *v == value) {
+            Some(idx) if !checked => {
+                current.remove(idx);
+            }
+            None if checked => current.push(value),
+            _ => return None,
+        }
+        Some(current)
+    }
+}
+
+/// A signal that can be split into a readable and a writable half, which can be bound to an
+/// element with [`bind`].
+pub trait IntoSplitSignal {
+    /// The type of the value contained in the signal.
+    type Value;
+    /// The readable half of the signal.
+    type Read: Get<Value = Self::Value>
+        + GetUntracked<Value = Self::Value>
+        + Clone
+        + Send
+        + Sync
+        + 'static;
+    /// The writable half of the signal.
+    type Write: Set<Value = Self::Value> + Clone + Send + Sync + 'static;
+
+    /// Splits the signal into its readable and writable halves.
+    fn into_split_signal(self) -> (Self::Read, Self::Write);
+}
+
+impl<T> IntoSplitSignal for RwSignal<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    type Value = T;
+    type Read = ReadSignal<T>;
+    type Write = WriteSignal<T>;
+
+    fn into_split_signal(self) -> (Self::Read, Self::Write) {
+        self.split()
+    }
+}
+
+impl<T> IntoSplitSignal for ArcRwSignal<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    type Value = T;
+    type Read = ArcReadSignal<T>;
+    type Write = ArcWriteSignal<T>;
+
+    fn into_split_signal(self) -> (Self::Read, Self::Write) {
+        self.split()
+    }
+}
+
+impl<T, Rd, Wr> IntoSplitSignal for (Rd, Wr)
+where
+    Rd: Get<Value = T>
+        + GetUntracked<Value = T>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+    Wr: Set<Value = T> + Clone + Send + Sync + 'static,
+{
+    type Value = T;
+    type Read = Rd;
+    type Write = Wr;
+
+    fn into_split_signal(self) -> (Self::Read, Self::Write) {
+        self
+    }
+}
+
+/// Creates an [`Attribute`] that keeps a property of a form element and a signal in sync.
+///
+/// The element is updated whenever the signal changes, and the signal is set whenever the user
+/// changes the element. The `key` is one of [`Value`], [`Checked`], or [`Group`].
+///
+/// When hydrating, the signal wins: anything the user typed before the page became interactive is
+/// replaced by the signal's value. Use [`uncontrolled`] to keep that input instead, or
+/// [`controlled`] to decide which changes are accepted.
+pub fn bind<K, S>(_key: K, signal: S) -> Bind<K, S::Read, S::Write>
+where
+    S: IntoSplitSignal,
+    K: BindKey<S::Value>,
+{
+    let (read, write) = signal.into_split_signal();
+    Bind {
+        key: PhantomData,
+        read,
+        write,
+    }
+}
+
+/// An [`Attribute`] that binds a property of a form element to a signal.
+#[derive(Debug, Clone)]
+pub struct Bind<K, Rd, Wr> {
+    key: PhantomData<K>,
+    read: Rd,
+    write: Wr,
+}
+
+/// The state for a [`Bind`], [`Controlled`], or [`Uncontrolled`] attribute.
+pub struct BindState {
+    el: Element,
+    _effect: Option<RenderEffect<()>>,
+    handlers: Vec<RemoveEventHandler<Element>>,
+}
+
+impl BindState {
+    fn remove_handlers(&mut self) {
+        for handler in self.handlers.drain(..) {
+            (handler.into_inner())(&self.el);
+        }
+    }
+}
+
+/// The parts of [`Bind`], [`Controlled`], and [`Uncontrolled`] that differ between them.
+#[doc(hidden)]
+pub trait Binding: Clone + Send + 'static {
+    /// Renders the initial value, once the element's other attributes have been rendered.
+    fn to_html(&self);
+
+    /// Keeps the element and the value in sync.
+    fn attach(self, el: &Element) -> BindState;
+
+    /// Attaches to an element that was rendered on the server.
+    fn hydrate(self, el: &Element) -> BindState {
+        self.attach(el)
+    }
+
+    /// Attaches to an element that has just been created.
+    fn build(self, el: &Element) -> BindState {
+        self.attach(el)
+    }
+}
+
+// implements `Attribute` and `NextAttribute` for a `Binding`
+macro_rules! binding_attribute {
+    ($ty:ident<$($param:ident),+>) => {
+        impl<$($param),+> Attribute<Dom> for $ty<$($param),+>
+        where
+            Self: Binding,
+        {
+            const MIN_LENGTH: usize = 0;
+
+            type AsyncOutput = Self;
+            type State = BindState;
+            type Cloneable = Self;
+            type CloneableOwned = Self;
+
+            fn html_len(&self) -> usize {
+                0
+            }
+
+            fn to_html(
+                self,
+                _buf: &mut String,
+                _class: &mut String,
+                _style: &mut String,
+                _inner_html: &mut String,
+            ) {
+                Binding::to_html(&self);
+            }
+
+            fn hydrate<const FROM_SERVER: bool>(
+                self,
+                el: &Element,
+            ) -> Self::State {
+                Binding::hydrate(self, el)
+            }
+
+            fn build(self, el: &Element) -> Self::State {
+                Binding::build(self, el)
+            }
+
+            fn rebuild(self, state: &mut Self::State) {
+                // an uncontrolled element is not reset to the signal, as it is owned by the user
+                state.remove_handlers();
+                let el = state.el.clone();
+                *state = self.attach(&el);
+            }
+
+            fn into_cloneable(self) -> Self::Cloneable {
+                self
+            }
+
+            fn into_cloneable_owned(self) -> Self::CloneableOwned {
+                self
+            }
+
+            fn dry_resolve(&mut self) {}
+
+            async fn resolve(self) -> Self::AsyncOutput {
+                self
+            }
+        }
+
+        impl<$($param),+> NextAttribute<Dom> for $ty<$($param),+>
+        where
+            Self: Binding,
+        {
+            type Output<NewAttr: Attribute<Dom>> = (Self, NewAttr);
+
+            fn add_any_attr<NewAttr: Attribute<Dom>>(
+                self,
+                new_attr: NewAttr,
+            ) -> Self::Output<NewAttr> {
+                (self, new_attr)
+            }
+        }
+    };
+}
+
+/// Calls `read_back` whenever the user changes the bound property of the element.
+fn listen<K, T>(
+    el: &Element,
+    read_back: impl Fn() + Clone + 'static,
+) -> Vec<RemoveEventHandler<Element>>
+where
+    K: BindKey<T>,
+{
+    if K::COMPOSITION {
+        vec![
+            Dom::add_event_listener(
+                el,
+                K::EVENT,
+                Box::new({
+                    let read_back = read_back.clone();
+                    move |ev: JsValue| {
+                        // the value is incomplete while an IME composition is in progress
+                        if !get_prop(&ev, "isComposing").is_truthy() {
+                            read_back();
+                        }
+                    }
+                }),
+            ),
+            Dom::add_event_listener(
+                el,
+                "compositionend",
+                Box::new(move |_| read_back()),
+            ),
+        ]
+    } else {
+        vec![Dom::add_event_listener(
+            el,
+            K::EVENT,
+            Box::new(move |_| read_back()),
+        )]
+    }
+}
+
+impl<K, Rd, Wr> Binding for Bind<K, Rd, Wr>
+where
+    Rd: Get
+        + GetUntracked<Value = <Rd as Get>::Value>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+    Wr: Set<Value = <Rd as Get>::Value> + Clone + Send + Sync + 'static,
+    K: BindKey<<Rd as Get>::Value>,
+{
+    fn to_html(&self) {
+        render_later::<K, _>(self.read.get_untracked());
+    }
+
+    fn attach(self, el: &Element) -> BindState {
+        let Bind {
+            read: rd, write, ..
+        } = self;
+        let effect = RenderEffect::new({
+            let el = el.clone();
+            let read = rd.clone();
+            move |_| K::update_dom(&el, &read.get())
+        });
+
+        let handlers = listen::<K, _>(el, {
+            let el = el.clone();
+            move || {
+                if let Some(value) = K::from_dom(&el, rd.get_untracked()) {
+                    write.set(value);
+                }
+            }
+        });
+
+        BindState {
+            el: el.clone(),
+            _effect: Some(effect),
+            handlers,
+        }
+    }
+}
+
+binding_attribute!(Bind<K, Rd, Wr>);
+
+/// Creates an [`Attribute`] for a controlled form element, whose property always reflects `value`.
+///
+/// When the user changes the element, the new value is passed to `on_change`. If `on_change`
+/// does not update `value` to accept it, the element is immediately reverted to the current
+/// value of `value`. The signal also wins when hydrating.
+pub fn controlled<K, Rd, F>(
+    _key: K,
+    value: Rd,
+    on_change: F,
+) -> Controlled<K, Rd, F>
+where
+    Rd: Get + GetUntracked<Value = <Rd as Get>::Value>,
+    K: BindKey<<Rd as Get>::Value>,
+    F: Fn(<Rd as Get>::Value),
+{
+    Controlled {
+        key: PhantomData,
+        value,
+        on_change,
+    }
+}
+
+/// An [`Attribute`] that drives a property of a form element from a signal, and reports changes
+/// made by the user to a callback.
+#[derive(Debug, Clone)]
+pub struct Controlled<K, Rd, F> {
+    key: PhantomData<K>,
+    value: Rd,
+    on_change: F,
+}
+
+impl<K, Rd, F> Binding for Controlled<K, Rd, F>
+where
+    Rd: Get
+        + GetUntracked<Value = <Rd as Get>::Value>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+    F: Fn(<Rd as Get>::Value) + Clone + Send + Sync + 'static,
+    K: BindKey<<Rd as Get>::Value>,
+{
+    fn to_html(&self) {
+        render_later::<K, _>(self.value.get_untracked());
+    }
+
+    fn attach(self, el: &Element) -> BindState {
+        let Controlled {
+            value, on_change, ..
+        } = self;
+        let effect = RenderEffect::new({
+            let el = el.clone();
+            let value = value.clone();
+            move |_| K::update_dom(&el, &value.get())
+        });
+
+        let handlers = listen::<K, _>(el, {
+            let el = el.clone();
+            move || {
+                if let Some(new_value) = K::from_dom(&el, value.get_untracked())
+                {
+                    on_change(new_value);
+                }
+                // if the change was not accepted, the signal still holds the old value
+                K::update_dom(&el, &value.get_untracked());
+            }
+        });
+
+        BindState {
+            el: el.clone(),
+            _effect: Some(effect),
+            handlers,
+        }
+    }
+}
+
+binding_attribute!(Controlled<K, Rd, F>);
+
+/// Creates an [`Attribute`] for an uncontrolled form element, which is initialized from `signal`
+/// once and then owned by the user.
+///
+/// The signal is set whenever the user changes the element, but later changes to the signal are
+/// not reflected in the element. When hydrating, the element wins: if the user changed it before
+/// the page became interactive, that value is written to the signal.
+pub fn uncontrolled<K, S>(
+    _key: K,
+    signal: S,
+) -> Uncontrolled<K, S::Read, S::Write>
+where
+    S: IntoSplitSignal,
+    K: BindKey<S::Value>,
+{
+    let (read, write) = signal.into_split_signal();
+    Uncontrolled {
+        key: PhantomData,
+        read,
+        write,
+    }
+}
+
+/// An [`Attribute`] that initializes a property of a form element from a signal, and sets the
+/// signal when the user changes it.
+#[derive(Debug, Clone)]
+pub struct Uncontrolled<K, Rd, Wr> {
+    key: PhantomData<K>,
+    read: Rd,
+    write: Wr,
+}
+
+impl<K, Rd, Wr> Binding for Uncontrolled<K, Rd, Wr>
+where
+    Rd: Get
+        + GetUntracked<Value = <Rd as Get>::Value>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+    Wr: Set<Value = <Rd as Get>::Value> + Clone + Send + Sync + 'static,
+    <Rd as Get>::Value: PartialEq,
+    K: BindKey<<Rd as Get>::Value>,
+{
+    fn to_html(&self) {
+        render_later::<K, _>(self.read.get_untracked());
+    }
+
+    fn attach(self, el: &Element) -> BindState {
+        let Uncontrolled { read, write, .. } = self;
+        let handlers = listen::<K, _>(el, {
+            let el = el.clone();
+            move || {
+                if let Some(value) = K::from_dom(&el, read.get_untracked()) {
+                    write.set(value);
+                }
+            }
+        });
+
+        BindState {
+            el: el.clone(),
+            _effect: None,
+            handlers,
+        }
+    }
+
+    fn hydrate(self, el: &Element) -> BindState {
+        // keep anything the user entered before hydration, rather than resetting it
+        let current = self.read.get_untracked();
+        if let Some(value) = K::from_dom(el, self.read.get_untracked()) {
+            if value != current {
+                self.write.set(value);
+            }
+        }
+        self.attach(el)
+    }
+
+    fn build(self, el: &Element) -> BindState {
+        K::update_dom(el, &self.read.get_untracked());
+        self.attach(el)
+    }
+}
+
+binding_attribute!(Uncontrolled<K, Rd, Wr>);
+
+/// Adds two-way binding to a form element.
+pub trait BindAttribute<K, S>
+where
+    S: IntoSplitSignal,
+    K: BindKey<S::Value>,
+{
+    /// Keeps a property of this element and a signal in sync. See [`bind`].
+    fn bind(
+        self,
+        key: K,
+        signal: S,
+    ) -> <Self as AddAnyAttr<Dom>>::Output<Bind<K, S::Read, S::Write>>
+    where
+        Self: Sized + AddAnyAttr<Dom>,
+        <Self as AddAnyAttr<Dom>>::Output<Bind<K, S::Read, S::Write>>:
+            Render<Dom>,
+    {
+        self.add_any_attr(bind(key, signal))
+    }
+}
+
+impl<E, At, Ch, K, S> BindAttribute<K, S> for HtmlElement<E, At, Ch, Dom>
+where
+    E: ElementType,
+    At: Attribute<Dom>,
+    Ch: Render<Dom>,
+    S: IntoSplitSignal,
+    K: BindKey<S::Value>,
+{
+}
@@ -4,7 +4,11 @@ use any_spawner::Executor;
 use futures::FutureExt;
 use reactive_graph::{effect::RenderEffect, signal::guards::ReadGuard};
 use std::{
-    borrow::Borrow, cell::RefCell, future::Future, ops::Deref, rc::Rc,
+    borrow::{Borrow, Cow},
+    cell::RefCell,
+    future::Future,
+    ops::Deref,
+    rc::Rc,
     sync::Arc,
 };
 
@@ -75,6 +79,51 @@ where
         );
     }
 
+    fn hydrate_entry<const FROM_SERVER: bool>(
+        mut self,
+        el: &R::Element,
+    ) -> Self::State {
+        let el = el.clone();
+        RenderEffect::new(move |prev| {
+            let value = self.invoke();
+            if let Some(mut state) = prev {
+                value.rebuild_entry(&mut state);
+                state
+            } else {
+                value.hydrate_entry::<FROM_SERVER>(&el)
+            }
+        })
+    }
+
+    fn build_entry(mut self, el: &R::Element) -> Self::State {
+        let el = el.to_owned();
+        RenderEffect::new(move |prev| {
+            let value = self.invoke();
+            if let Some(mut state) = prev {
+                value.rebuild_entry(&mut state);
+                state
+            } else {
+                value.build_entry(&el)
+            }
+        })
+    }
+
+    fn rebuild_entry(mut self, state: &mut Self::State) {
+        let prev_value = state.take_value();
+        *state = RenderEffect::new_with_value(
+            move |prev| {
+                let value = self.invoke();
+                if let Some(mut state) = prev {
+                    value.rebuild_entry(&mut state);
+                    state
+                } else {
+                    unreachable!()
+                }
+            },
+            prev_value,
+        );
+    }
+
     fn into_cloneable(self) -> Self::Cloneable {
         self.into_shared()
     }
@@ -204,6 +253,10 @@ where
     async fn resolve(mut self) -> Self::AsyncOutput {
         (self.0, *self.1.invoke().borrow())
     }
+
+    fn class_names(&self) -> Vec<Cow<'static, str>> {
+        vec![Cow::Borrowed(self.0)]
+    }
 }
 
 // TODO this needs a non-reactive form too to be restored
@@ -492,6 +545,21 @@ mod stable {
                     (move || self.get()).rebuild(state)
                 }
 
+                fn hydrate_entry<const FROM_SERVER: bool>(
+                    self,
+                    el: &R::Element,
+                ) -> Self::State {
+                    (move || self.get()).hydrate_entry::<FROM_SERVER>(el)
+                }
+
+                fn build_entry(self, el: &R::Element) -> Self::State {
+                    (move || self.get()).build_entry(el)
+                }
+
+                fn rebuild_entry(self, state: &mut Self::State) {
+                    (move || self.get()).rebuild_entry(state)
+                }
+
                 fn into_cloneable(self) -> Self::Cloneable {
                     self
                 }
@@ -565,6 +633,10 @@ mod stable {
                 async fn resolve(self) -> Self::AsyncOutput {
                     self
                 }
+
+                fn class_names(&self) -> Vec<Cow<'static, str>> {
+                    vec![Cow::Borrowed(self.0)]
+                }
             }
         };
     }
@@ -607,6 +679,21 @@ mod stable {
                     (move || self.get()).rebuild(state)
                 }
 
+                fn hydrate_entry<const FROM_SERVER: bool>(
+                    self,
+                    el: &R::Element,
+                ) -> Self::State {
+                    (move || self.get()).hydrate_entry::<FROM_SERVER>(el)
+                }
+
+                fn build_entry(self, el: &R::Element) -> Self::State {
+                    (move || self.get()).build_entry(el)
+                }
+
+                fn rebuild_entry(self, state: &mut Self::State) {
+                    (move || self.get()).rebuild_entry(state)
+                }
+
                 fn into_cloneable(self) -> Self::Cloneable {
                     self
                 }
@@ -678,6 +765,10 @@ mod stable {
                 async fn resolve(self) -> Self::AsyncOutput {
                     self
                 }
+
+                fn class_names(&self) -> Vec<Cow<'static, str>> {
+                    vec![Cow::Borrowed(self.0)]
+                }
             }
         };
     }
@@ -691,6 +782,7 @@ mod stable {
         traits::Get,
         wrappers::read::{ArcSignal, MaybeSignal, Signal},
     };
+    use std::borrow::Cow;
 
     class_signal_arena!(RwSignal);
     class_signal_arena!(ReadSignal);
@@ -9,7 +9,7 @@ use crate::{
     },
 };
 use any_spawner::Executor;
-use reactive_graph::effect::RenderEffect;
+use reactive_graph::effect::{defer_effects, RenderEffect};
 use std::{
     cell::RefCell,
     future::Future,
@@ -17,6 +17,8 @@ use std::{
     sync::{Arc, Mutex},
 };
 
+/// Two-way binding between form elements and signals.
+pub mod bind;
 mod class;
 mod guards;
 mod inner_html;
@@ -60,13 +62,19 @@ where
     #[track_caller]
     fn build(mut self) -> Self::State {
         RenderEffect::new(move |prev| {
-            let value = self.invoke();
-            if let Some(mut state) = prev {
-                value.rebuild(&mut state);
-                state
-            } else {
-                value.build()
-            }
+            // a rebuilt view has been mounted by the time this returns, so its effects are
+            // released once it is in the DOM. a view that is built for the first time is
+            // mounted by the caller, so its effects are only held back until it has been mounted
+            // if the caller is inside `defer_effects` as well, as the `mount` functions are
+            defer_effects(|| {
+                let value = self.invoke();
+                if let Some(mut state) = prev {
+                    value.rebuild(&mut state);
+                    state
+                } else {
+                    value.build()
+                }
+            })
         })
         .into()
     }
@@ -176,13 +184,16 @@ where
         let cursor = cursor.clone();
         let position = position.clone();
         RenderEffect::new(move |prev| {
-            let value = self.invoke();
-            if let Some(mut state) = prev {
-                value.rebuild(&mut state);
-                state
-            } else {
-                value.hydrate::<FROM_SERVER>(&cursor, &position)
-            }
+            // see build()
+            defer_effects(|| {
+                let value = self.invoke();
+                if let Some(mut state) = prev {
+                    value.rebuild(&mut state);
+                    state
+                } else {
+                    value.hydrate::<FROM_SERVER>(&cursor, &position)
+                }
+            })
         })
         .into()
     }
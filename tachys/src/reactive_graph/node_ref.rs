@@ -3,10 +3,13 @@ use crate::{
     renderer::{dom::Dom, Renderer},
 };
 use reactive_graph::{
+    effect::Effect,
+    owner::LocalStorage,
     signal::RwSignal,
-    traits::{DefinedAt, Set, Track, WithUntracked},
+    traits::{DefinedAt, Get, Set, Track, WithUntracked},
 };
 use send_wrapper::SendWrapper;
+use std::{cell::Cell, rc::Rc};
 use wasm_bindgen::JsCast;
 
 /// A reactive reference to a DOM node that can be used with the `node_ref` attribute.
@@ -26,6 +29,48 @@ where
     pub fn new() -> Self {
         Self(RwSignal::new(None))
     }
+
+    /// Runs the provided closure once, when the element has been loaded into this reference
+    /// and the view that contains it has been mounted.
+    ///
+    /// This is useful for work that needs the element to be in the document, like focusing an
+    /// input or handing the element to a third-party library. It never runs on the server.
+    pub fn on_load<F>(self, f: F)
+    where
+        E: 'static,
+        F: FnOnce(E::Output) + 'static,
+        E::Output: JsCast + Clone,
+    {
+        on_first_load(move || self.get(), f);
+    }
+}
+
+/// Calls `f` with the first value that `load` returns, once effects run.
+///
+/// `load` is only tracked until then: the effect stops once `f` has been called.
+fn on_first_load<T>(
+    load: impl Fn() -> Option<T> + 'static,
+    f: impl FnOnce(T) + 'static,
+) where
+    T: 'static,
+{
+    let effect = Rc::new(Cell::new(None::<Effect<LocalStorage>>));
+    let mut f = Some(f);
+    // effects run after the current render has been mounted
+    let handle = Effect::new({
+        let effect = Rc::clone(&effect);
+        move |_| {
+            if let Some(value) = load() {
+                if let Some(f) = f.take() {
+                    f(value);
+                }
+                if let Some(effect) = effect.take() {
+                    effect.stop();
+                }
+            }
+        }
+    });
+    effect.set(Some(handle));
 }
 
 impl<E> Default for NodeRef<E>
@@ -103,3 +148,75 @@ where
         self.0.track();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::on_first_load;
+    use crate::{
+        html::{
+            element::{input, Input},
+            node_ref::{NodeRefAttribute, NodeRefContainer},
+        },
+        renderer::mock_dom::{Element, MockDom},
+        testing::{mount_to, test_dom, tick},
+    };
+    use any_spawner::Executor;
+    use reactive_graph::{
+        owner::Owner,
+        signal::RwSignal,
+        traits::{Get, Set},
+    };
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+
+    // a node reference for the mock DOM, as a `NodeRef` only holds browser elements
+    #[derive(Clone, Copy)]
+    struct MockRef(RwSignal<Option<Element>>);
+
+    impl NodeRefContainer<Input, MockDom> for MockRef {
+        fn load(self, el: &Element) {
+            self.0.set(Some(el.clone()));
+        }
+    }
+
+    #[tokio::test]
+    async fn on_load_runs_once_the_element_is_loaded() {
+        _ = Executor::init_tokio();
+        tokio::task::LocalSet::new()
+            .run_until(async {
+                let owner = Owner::new();
+                owner.set();
+                let el = MockRef(RwSignal::new(None));
+                let loaded = Rc::new(RefCell::new(Vec::new()));
+                let reads = Rc::new(Cell::new(0));
+                let load = {
+                    let reads = Rc::clone(&reads);
+                    move || {
+                        reads.set(reads.get() + 1);
+                        el.0.get()
+                    }
+                };
+                on_first_load(load, {
+                    let loaded = Rc::clone(&loaded);
+                    move |el: Element| loaded.borrow_mut().push(el)
+                });
+                tick().await;
+                assert!(loaded.borrow().is_empty());
+
+                let first =
+                    mount_to(&test_dom(), input::<MockDom>().node_ref(el));
+                tick().await;
+                assert_eq!(*loaded.borrow(), first.root().child_elements());
+
+                // the reference is no longer tracked once it has loaded
+                let _second =
+                    mount_to(&test_dom(), input::<MockDom>().node_ref(el));
+                tick().await;
+                assert_eq!(loaded.borrow().len(), 1);
+                assert_eq!(reads.get(), 2);
+            })
+            .await;
+    }
+}
@@ -178,6 +178,51 @@ where
         );
     }
 
+    fn hydrate_entry<const FROM_SERVER: bool>(
+        mut self,
+        el: &R::Element,
+    ) -> Self::State {
+        let el = el.clone();
+        RenderEffect::new(move |prev| {
+            let value = self.invoke();
+            if let Some(mut state) = prev {
+                value.rebuild_entry(&mut state);
+                state
+            } else {
+                value.hydrate_entry::<FROM_SERVER>(&el)
+            }
+        })
+    }
+
+    fn build_entry(mut self, el: &R::Element) -> Self::State {
+        let el = el.clone();
+        RenderEffect::new(move |prev| {
+            let value = self.invoke();
+            if let Some(mut state) = prev {
+                value.rebuild_entry(&mut state);
+                state
+            } else {
+                value.build_entry(&el)
+            }
+        })
+    }
+
+    fn rebuild_entry(mut self, state: &mut Self::State) {
+        let prev_value = state.take_value();
+        *state = RenderEffect::new_with_value(
+            move |prev| {
+                let value = self.invoke();
+                if let Some(mut state) = prev {
+                    value.rebuild_entry(&mut state);
+                    state
+                } else {
+                    unreachable!()
+                }
+            },
+            prev_value,
+        );
+    }
+
     fn into_cloneable(self) -> Self::Cloneable {
         self.into_shared()
     }
@@ -231,6 +276,21 @@ mod stable {
                     (move || self.get()).rebuild(state)
                 }
 
+                fn hydrate_entry<const FROM_SERVER: bool>(
+                    self,
+                    el: &R::Element,
+                ) -> Self::State {
+                    (move || self.get()).hydrate_entry::<FROM_SERVER>(el)
+                }
+
+                fn build_entry(self, el: &R::Element) -> Self::State {
+                    (move || self.get()).build_entry(el)
+                }
+
+                fn rebuild_entry(self, state: &mut Self::State) {
+                    (move || self.get()).rebuild_entry(state)
+                }
+
                 fn into_cloneable(self) -> Self::Cloneable {
                     self
                 }
@@ -339,6 +399,21 @@ mod stable {
                     (move || self.get()).rebuild(state)
                 }
 
+                fn hydrate_entry<const FROM_SERVER: bool>(
+                    self,
+                    el: &R::Element,
+                ) -> Self::State {
+                    (move || self.get()).hydrate_entry::<FROM_SERVER>(el)
+                }
+
+                fn build_entry(self, el: &R::Element) -> Self::State {
+                    (move || self.get()).build_entry(el)
+                }
+
+                fn rebuild_entry(self, state: &mut Self::State) {
+                    (move || self.get()).rebuild_entry(state)
+                }
+
                 fn into_cloneable(self) -> Self::Cloneable {
                     self
                 }
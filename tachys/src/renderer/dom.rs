@@ -4,10 +4,13 @@ use crate::{
     ok_or_debug, or_debug,
     view::{Mountable, ToTemplate},
 };
+use futures::channel::oneshot;
 use linear_map::LinearMap;
 use once_cell::unsync::Lazy;
 use rustc_hash::FxHashSet;
-use std::{any::TypeId, borrow::Cow, cell::RefCell};
+use std::{
+    any::TypeId, borrow::Cow, cell::RefCell, future::Future, time::Duration,
+};
 use wasm_bindgen::{intern, prelude::Closure, JsCast, JsValue};
 use web_sys::{
     Comment, CssStyleDeclaration, DocumentFragment, DomTokenList, Element,
@@ -103,6 +106,16 @@ impl Renderer for Dom {
         web_sys::console::log_1(node);
     }
 
+    fn tag_name(el: &Self::Element) -> Option<String> {
+        Some(el.tag_name())
+    }
+
+    fn comment_text(node: &Self::Node) -> Option<String> {
+        (node.node_type() == Node::COMMENT_NODE)
+            .then(|| node.text_content())
+            .flatten()
+    }
+
     #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace"))]
     fn clear_children(parent: &Self::Element) {
         parent.set_text_content(Some(""));
@@ -174,6 +187,14 @@ impl DomRenderer for Dom {
         T::cast_from(el).expect("incorrect element type")
     }
 
+    fn is_event_target(ev: &Self::Event, el: &Self::Element) -> bool {
+        ev.unchecked_ref::<Event>().target().is_some_and(|target| {
+            let target: &JsValue = target.as_ref();
+            let el: &JsValue = el.as_ref();
+            target == el
+        })
+    }
+
     fn add_event_listener_delegated(
         el: &Self::Element,
         name: Cow<'static, str>,
@@ -348,6 +369,18 @@ impl DomRenderer for Dom {
             .unwrap()
             .unchecked_into()
     }
+
+    fn sleep(duration: Duration) -> impl Future<Output = ()> + 'static {
+        let (tx, rx) = oneshot::channel::<()>();
+        let cb = Closure::once_into_js(move || _ = tx.send(()));
+        _ = window().set_timeout_with_callback_and_timeout_and_arguments_0(
+            cb.unchecked_ref(),
+            duration.as_millis().try_into().unwrap_or(i32::MAX),
+        );
+        async move {
+            _ = rx.await;
+        }
+    }
 }
 
 impl Mountable<Dom> for Node {
@@ -2,6 +2,11 @@
 
 //! A stupidly-simple mock DOM implementation that can be used for testing.
 //!
+//! Views can be built, rebuilt, and unmounted natively, and the resulting tree can be inspected
+//! with [`Element::to_debug_html`]. Event listeners are stored on the mock nodes, and can be
+//! triggered with [`MockDom::dispatch_event`]. Properties cannot hold JavaScript values outside a
+//! browser, so only their names are recorded, and can be checked with [`Element::has_property`].
+//!
 //! Do not use this for anything real.
 
 use super::{CastFrom, DomRenderer, RemoveEventHandler, Renderer};
@@ -9,8 +14,17 @@ use crate::{
     html::element::{CreateElement, ElementType},
     view::Mountable,
 };
+use futures::channel::oneshot;
 use slotmap::{new_key_type, SlotMap};
-use std::{borrow::Cow, cell::RefCell, collections::HashMap, rc::Rc};
+use std::{
+    any::TypeId,
+    borrow::Cow,
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    future::Future,
+    rc::Rc,
+    time::Duration,
+};
 use wasm_bindgen::JsValue;
 
 /// A [`Renderer`] that uses a mock DOM structure running in Rust code.
@@ -95,6 +109,56 @@ impl Element {
         self.debug_html(&mut buf);
         buf
     }
+
+    /// Returns the value of the attribute with the given name, if it is set.
+    pub fn attribute(&self, name: &str) -> Option<String> {
+        Document::with_node(self.0 .0, |node| match &node.ty {
+            NodeType::Element { attrs, .. } => attrs.get(name).cloned(),
+            _ => None,
+        })
+        .flatten()
+    }
+
+    /// Returns whether a property with the given name has been set on the element.
+    pub fn has_property(&self, key: &str) -> bool {
+        PROPERTIES.with_borrow(|props| {
+            props
+                .get(&self.0 .0)
+                .is_some_and(|props| props.contains(key))
+        })
+    }
+
+    /// Returns the text of the element and all of its descendants.
+    pub fn text_content(&self) -> String {
+        let mut buf = String::new();
+        text_content(self.0 .0, &mut buf);
+        buf
+    }
+
+    /// Returns the children of the element that are elements, in order.
+    pub fn child_elements(&self) -> Vec<Element> {
+        Document::with_node(self.0 .0, |node| match &node.ty {
+            NodeType::Element { children, .. } => children
+                .iter()
+                .cloned()
+                .filter_map(Element::cast_from)
+                .collect(),
+            _ => Vec::new(),
+        })
+        .unwrap_or_default()
+    }
+}
+
+fn text_content(id: NodeId, buf: &mut String) {
+    Document::with_node(id, |node| match &node.ty {
+        NodeType::Text(text) => buf.push_str(text),
+        NodeType::Element { children, .. } => {
+            for child in children {
+                text_content(child.0, buf);
+            }
+        }
+        NodeType::Placeholder => {}
+    });
 }
 
 /// The DOM data associated with a particular node.
@@ -199,6 +263,9 @@ impl Document {
     /// Resets the document's contents.
     pub fn reset(&self) {
         self.0.borrow_mut().clear();
+        LISTENERS.with(|listeners| listeners.borrow_mut().clear());
+        PROPERTIES.with(|props| props.borrow_mut().clear());
+        TEMPLATES.with(|templates| templates.borrow_mut().clear());
     }
 
     fn create_element(&self, tag: &str) -> Element {
@@ -225,17 +292,196 @@ impl Document {
             ty: NodeType::Placeholder,
         })))
     }
+
+    // like `cloneNode(true)`, this copies attributes and children, but not listeners or properties
+    fn clone_node(&self, node: &Node) -> Node {
+        let (ty, children) =
+            Document::with_node(node.0, |node| match &node.ty {
+                NodeType::Text(text) => {
+                    (NodeType::Text(text.clone()), Vec::new())
+                }
+                NodeType::Element {
+                    tag,
+                    attrs,
+                    children,
+                } => (
+                    NodeType::Element {
+                        tag: tag.clone(),
+                        attrs: attrs.clone(),
+                        children: Vec::new(),
+                    },
+                    children.clone(),
+                ),
+                NodeType::Placeholder => (NodeType::Placeholder, Vec::new()),
+            })
+            .expect("tried to clone a node that does not exist");
+        let clone = Element(Node(
+            self.0.borrow_mut().insert(NodeData { parent: None, ty }),
+        ));
+        for child in children {
+            let child = self.clone_node(&child);
+            MockDom::insert_node(&clone, &child, None);
+        }
+        clone.0
+    }
+
+    // the mock DOM has no HTML parser, but templates only contain elements with quoted
+    // attributes, text, and `<!>` comments, so they can be read with a much simpler one
+    fn parse_template(&self, html: &str, parent: &Element) {
+        let mut open = vec![parent.clone()];
+        let mut rest = html;
+        while !rest.is_empty() {
+            let parent = open.last().expect("template closes an unopened tag");
+            if let Some(tag) = rest.strip_prefix("</") {
+                let (_, after) = tag.split_once('>').expect("unclosed tag");
+                rest = after;
+                open.pop();
+            } else if let Some(comment) = rest.strip_prefix("<!") {
+                let (_, after) =
+                    comment.split_once('>').expect("unclosed comment");
+                rest = after;
+                let placeholder = self.create_placeholder();
+                MockDom::insert_node(parent, placeholder.as_ref(), None);
+            } else if let Some(tag) = rest.strip_prefix('<') {
+                let name_end = tag
+                    .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+                    .expect("unclosed tag");
+                let el = self.create_element(&tag[..name_end]);
+                MockDom::insert_node(parent, el.as_ref(), None);
+                rest = &tag[name_end..];
+                loop {
+                    rest = rest.trim_start();
+                    if let Some(after) = rest.strip_prefix("/>") {
+                        rest = after;
+                        break;
+                    } else if let Some(after) = rest.strip_prefix('>') {
+                        rest = after;
+                        open.push(el);
+                        break;
+                    }
+                    let name_end = rest
+                        .find(|c: char| {
+                            c.is_whitespace() || matches!(c, '=' | '>' | '/')
+                        })
+                        .expect("unclosed tag");
+                    let (name, after) = rest.split_at(name_end);
+                    let value = match after.strip_prefix("=\"") {
+                        Some(value) => {
+                            let (value, after) = value
+                                .split_once('"')
+                                .expect("unclosed attribute value");
+                            rest = after;
+                            value
+                        }
+                        None => {
+                            rest = after;
+                            ""
+                        }
+                    };
+                    MockDom::set_attribute(&el, name, value);
+                }
+            } else {
+                let end = rest.find('<').unwrap_or(rest.len());
+                let text = self.create_text_node(&rest[..end]);
+                MockDom::insert_node(parent, text.as_ref(), None);
+                rest = &rest[end..];
+            }
+        }
+    }
+}
+
+type Listener = (
+    Cow<'static, str>,
+    usize,
+    Rc<RefCell<Box<dyn FnMut(JsValue)>>>,
+);
+
+thread_local! {
+    static LISTENERS: RefCell<HashMap<NodeId, Vec<Listener>>> =
+        Default::default();
+    static NEXT_LISTENER_ID: RefCell<usize> = const { RefCell::new(0) };
+    static EVENT_TARGETS: RefCell<Vec<Element>> = const { RefCell::new(Vec::new()) };
+    static TIMERS: RefCell<Vec<oneshot::Sender<()>>> = const { RefCell::new(Vec::new()) };
+    static PROPERTIES: RefCell<HashMap<NodeId, HashSet<String>>> = Default::default();
+    static TEMPLATES: RefCell<HashMap<TypeId, Element>> = Default::default();
+}
+
+impl MockDom {
+    /// Dispatches a synthetic event with the given name to the element.
+    ///
+    /// Listeners on the element itself run first, followed by listeners on each of its
+    /// ancestors, as if the event bubbled. The event passed to them is `undefined`, but its
+    /// target is available from [`MockDom::event_target`](DomRenderer::event_target) and
+    /// [`current_event_target`] while they run, so helpers like
+    /// [`event_target_value`](crate::dom::event_target_value) work in listeners.
+    pub fn dispatch_event(el: &Element, name: &str) {
+        EVENT_TARGETS.with(|targets| targets.borrow_mut().push(el.clone()));
+        let mut node = Some(el.0.clone());
+        while let Some(curr) = node {
+            let listeners = LISTENERS.with(|listeners| {
+                listeners
+                    .borrow()
+                    .get(&curr.0)
+                    .map(|listeners| {
+                        listeners
+                            .iter()
+                            .filter(|(ev, _, _)| ev == name)
+                            .map(|(_, _, cb)| Rc::clone(cb))
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default()
+            });
+            for cb in listeners {
+                (cb.borrow_mut())(JsValue::UNDEFINED);
+            }
+            node = MockDom::get_parent(&curr);
+        }
+        EVENT_TARGETS.with(|targets| targets.borrow_mut().pop());
+    }
+
+    /// Fires every timer started with [`MockDom::sleep`](DomRenderer::sleep) that has not fired
+    /// yet, however long it was set for.
+    pub fn run_timers() {
+        for timer in TIMERS.take() {
+            _ = timer.send(());
+        }
+    }
+
+    fn update_attribute(
+        el: &Element,
+        name: &str,
+        fun: impl FnOnce(&mut String),
+    ) {
+        Document::with_node_mut(el.0 .0, |node| {
+            if let NodeType::Element { ref mut attrs, .. } = node.ty {
+                let value = attrs.entry(name.to_string()).or_default();
+                fun(value);
+                if value.is_empty() {
+                    attrs.remove(name);
+                }
+            }
+        });
+    }
+}
+
+/// The target of the event that is currently being dispatched with [`MockDom::dispatch_event`],
+/// if any.
+pub fn current_event_target() -> Option<Element> {
+    EVENT_TARGETS.with(|targets| targets.borrow().last().cloned())
 }
 
-// TODO!
 impl DomRenderer for MockDom {
-    type Event = ();
-    type ClassList = ();
-    type CssStyleDeclaration = ();
-    type TemplateElement = ();
+    type Event = JsValue;
+    type ClassList = Element;
+    type CssStyleDeclaration = Element;
+    type TemplateElement = Element;
+
+    const JS_PROPERTIES: bool = false;
 
-    fn set_property(el: &Self::Element, key: &str, value: &JsValue) {
-        todo!()
+    fn set_property(el: &Self::Element, key: &str, _value: &JsValue) {
+        PROPERTIES.with_borrow_mut(|props| {
+            props.entry(el.0 .0).or_default().insert(key.to_string());
+        });
     }
 
     fn add_event_listener(
@@ -243,32 +489,67 @@ impl DomRenderer for MockDom {
         name: &str,
         cb: Box<dyn FnMut(Self::Event)>,
     ) -> RemoveEventHandler<Self::Element> {
-        todo!()
+        let id = NEXT_LISTENER_ID.with(|next| {
+            let mut next = next.borrow_mut();
+            *next += 1;
+            *next
+        });
+        let name = Cow::Owned(name.to_string());
+        LISTENERS.with(|listeners| {
+            listeners.borrow_mut().entry(el.0 .0).or_default().push((
+                name,
+                id,
+                Rc::new(RefCell::new(cb)),
+            ))
+        });
+        RemoveEventHandler::new(move |el: &Element| {
+            LISTENERS.with(|listeners| {
+                if let Some(listeners) =
+                    listeners.borrow_mut().get_mut(&el.0 .0)
+                {
+                    listeners.retain(|(_, other, _)| *other != id);
+                }
+            })
+        })
     }
 
     fn add_event_listener_delegated(
         el: &Self::Element,
         name: Cow<'static, str>,
-        delegation_key: Cow<'static, str>,
+        _delegation_key: Cow<'static, str>,
         cb: Box<dyn FnMut(Self::Event)>,
     ) -> RemoveEventHandler<Self::Element> {
-        todo!()
+        // dispatch_event() always bubbles, so there's no need for a separate delegated listener
+        Self::add_event_listener(el, &name, cb)
     }
 
     fn class_list(el: &Self::Element) -> Self::ClassList {
-        todo!()
+        el.clone()
     }
 
     fn add_class(class_list: &Self::ClassList, name: &str) {
-        todo!()
+        Self::update_attribute(class_list, "class", |classes| {
+            if !classes.split_ascii_whitespace().any(|class| class == name) {
+                if !classes.is_empty() {
+                    classes.push(' ');
+                }
+                classes.push_str(name);
+            }
+        });
     }
 
     fn remove_class(class_list: &Self::ClassList, name: &str) {
-        todo!()
+        Self::update_attribute(class_list, "class", |classes| {
+            *classes = classes
+                .split_ascii_whitespace()
+                .filter(|class| *class != name)
+                .collect::<Vec<_>>()
+                .join(" ");
+        });
     }
 
     fn style(el: &Self::Element) -> Self::CssStyleDeclaration {
-        todo!()
+        el.clone()
     }
 
     fn set_css_property(
@@ -276,29 +557,82 @@ impl DomRenderer for MockDom {
         name: &str,
         value: &str,
     ) {
-        todo!()
+        Self::update_attribute(style, "style", |styles| {
+            let mut props = styles
+                .split(';')
+                .map(str::trim)
+                .filter(|prop| {
+                    !prop.is_empty()
+                        && prop.split(':').next().map(str::trim) != Some(name)
+                })
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            // as in the DOM, setting a property to an empty value removes it
+            if !value.is_empty() {
+                props.push(format!("{name}: {value}"));
+            }
+            *styles = props.join("; ");
+            if !styles.is_empty() {
+                styles.push(';');
+            }
+        });
     }
 
     fn set_inner_html(el: &Self::Element, html: &str) {
-        todo!()
+        // the mock DOM doesn't parse HTML, so the markup is stored as a text node
+        MockDom::clear_children(el);
+        let text = MockDom::create_text_node(html);
+        MockDom::insert_node(el, text.as_ref(), None);
     }
 
     fn event_target<T>(ev: &Self::Event) -> T
     where
         T: CastFrom<Self::Element>,
     {
-        todo!()
+        let el = current_event_target().expect("event.target not found");
+        T::cast_from(el).expect("incorrect element type")
+    }
+
+    fn is_event_target(_ev: &Self::Event, el: &Self::Element) -> bool {
+        current_event_target().as_ref() == Some(el)
+    }
+
+    fn sleep(_duration: Duration) -> impl Future<Output = ()> + 'static {
+        let (tx, rx) = oneshot::channel();
+        TIMERS.with_borrow_mut(|timers| timers.push(tx));
+        async move {
+            _ = rx.await;
+        }
     }
 
     fn get_template<V>() -> Self::TemplateElement
     where
         V: crate::view::ToTemplate + 'static,
     {
-        todo!()
+        TEMPLATES.with_borrow_mut(|templates| {
+            templates
+                .entry(TypeId::of::<V>())
+                .or_insert_with(|| {
+                    let mut buf = String::new();
+                    V::to_template(
+                        &mut buf,
+                        &mut String::new(),
+                        &mut String::new(),
+                        &mut String::new(),
+                        &mut Default::default(),
+                    );
+                    let document = document();
+                    let tpl = document.create_element("template");
+                    document.parse_template(&buf, &tpl);
+                    tpl
+                })
+                .clone()
+        })
     }
 
     fn clone_template(tpl: &Self::TemplateElement) -> Self::Element {
-        todo!()
+        // the clone stands in for the template's content, which the nodes are hydrated from
+        Element(document().clone_node(tpl.as_ref()))
     }
 }
 
@@ -337,7 +671,9 @@ pub enum NodeType {
 
 impl Mountable<MockDom> for Node {
     fn unmount(&mut self) {
-        todo!()
+        if let Some(parent) = MockDom::get_parent(self) {
+            MockDom::remove_node(&Element(parent), self);
+        }
     }
 
     fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
@@ -356,7 +692,7 @@ impl Mountable<MockDom> for Node {
 
 impl Mountable<MockDom> for Text {
     fn unmount(&mut self) {
-        todo!()
+        self.as_ref().clone().unmount();
     }
 
     fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
@@ -376,7 +712,7 @@ impl Mountable<MockDom> for Text {
 
 impl Mountable<MockDom> for Element {
     fn unmount(&mut self) {
-        todo!()
+        self.as_ref().clone().unmount();
     }
 
     fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
@@ -396,7 +732,7 @@ impl Mountable<MockDom> for Element {
 
 impl Mountable<MockDom> for Placeholder {
     fn unmount(&mut self) {
-        todo!()
+        self.as_ref().clone().unmount();
     }
 
     fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
@@ -574,6 +910,14 @@ impl Renderer for MockDom {
         println!("{node:?}");
     }
 
+    fn tag_name(el: &Self::Element) -> Option<String> {
+        Document::with_node(el.0 .0, |node| match &node.ty {
+            NodeType::Element { tag, .. } => Some(tag.to_string()),
+            _ => None,
+        })
+        .flatten()
+    }
+
     fn clear_children(parent: &Self::Element) {
         let prev_children =
             Document::with_node_mut(parent.0 .0, |node| match node.ty {
@@ -623,8 +967,9 @@ mod tests {
     use super::MockDom;
     use crate::{
         html::element,
-        renderer::{mock_dom::node_eq, Renderer},
+        renderer::{mock_dom::node_eq, DomRenderer, Renderer},
     };
+    use std::{cell::RefCell, rc::Rc};
 
     #[test]
     fn html_debugging_works() {
@@ -741,4 +1086,107 @@ mod tests {
             Some(text.as_ref())
         );
     }
+
+    #[test]
+    fn dispatched_events_bubble_to_ancestors() {
+        let main = MockDom::create_element(element::Main);
+        let button = MockDom::create_element(element::Button);
+        MockDom::insert_node(&main, button.as_ref(), None);
+
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let on_button = MockDom::add_event_listener(&button, "click", {
+            let calls = Rc::clone(&calls);
+            Box::new(move |_| calls.borrow_mut().push("button"))
+        });
+        let _on_main = MockDom::add_event_listener(&main, "click", {
+            let calls = Rc::clone(&calls);
+            Box::new(move |_| calls.borrow_mut().push("main"))
+        });
+
+        MockDom::dispatch_event(&button, "click");
+        MockDom::dispatch_event(&button, "input");
+        assert_eq!(*calls.borrow(), ["button", "main"]);
+
+        (on_button.into_inner())(&button);
+        MockDom::dispatch_event(&button, "click");
+        assert_eq!(*calls.borrow(), ["button", "main", "main"]);
+    }
+
+    #[test]
+    fn classes_can_be_added_and_removed() {
+        let p = MockDom::create_element(element::P);
+        let class_list = MockDom::class_list(&p);
+        MockDom::add_class(&class_list, "foo");
+        MockDom::add_class(&class_list, "bar");
+        MockDom::add_class(&class_list, "foo");
+        assert_eq!(p.to_debug_html(), "<p class=\"foo bar\"></p>");
+        MockDom::remove_class(&class_list, "foo");
+        assert_eq!(p.to_debug_html(), "<p class=\"bar\"></p>");
+        MockDom::remove_class(&class_list, "bar");
+        assert_eq!(p.to_debug_html(), "<p></p>");
+    }
+
+    #[test]
+    fn style_properties_can_be_renamed() {
+        use crate::html::style::IntoStyle;
+        use std::sync::Arc;
+
+        let p = MockDom::create_element(element::P);
+        let style = |name: &str, value: &str| -> (Arc<str>, Arc<str>) {
+            (name.into(), value.into())
+        };
+        let mut state =
+            IntoStyle::<MockDom>::build(style("margin-left", "1rem"), &p);
+        assert_eq!(p.to_debug_html(), "<p style=\"margin-left: 1rem;\"></p>");
+        IntoStyle::<MockDom>::rebuild(
+            style("margin-right", "1rem"),
+            &mut state,
+        );
+        assert_eq!(p.to_debug_html(), "<p style=\"margin-right: 1rem;\"></p>");
+        MockDom::set_css_property(&MockDom::style(&p), "margin-right", "");
+        assert_eq!(p.to_debug_html(), "<p></p>");
+    }
+
+    #[test]
+    fn templates_are_cloned_for_each_view() {
+        use crate::{
+            html::{
+                attribute::global::ClassAttribute,
+                element::{p, ElementChild, HtmlElement, P},
+            },
+            view::{template::ViewTemplate, Mountable, Render},
+        };
+
+        let main = MockDom::create_element(element::Main);
+        let view = |text: &'static str| {
+            let el: HtmlElement<P, _, _, MockDom> =
+                p().class("greeting").child(text);
+            ViewTemplate::new(el)
+        };
+        let mut hello = view("Hello").build();
+        hello.mount(&main, None);
+        let mut world = view("world").build();
+        world.mount(&main, None);
+        assert_eq!(
+            main.to_debug_html(),
+            "<main><p class=\"greeting\">Hello</p><p \
+             class=\"greeting\">world</p></main>"
+        );
+    }
+
+    #[test]
+    fn property_names_are_recorded() {
+        use crate::{
+            html::{
+                attribute::global::PropAttribute,
+                element::{input, HtmlElement, Input},
+            },
+            view::Render,
+        };
+
+        let el: HtmlElement<Input, _, (), MockDom> = input().prop("value", "a");
+        let state = el.build();
+        assert!(state.el.has_property("value"));
+        assert!(!state.el.has_property("checked"));
+    }
 }
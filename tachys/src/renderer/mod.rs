@@ -2,7 +2,8 @@ use crate::{
     html::element::CreateElement,
     view::{Mountable, ToTemplate},
 };
-use std::{borrow::Cow, fmt::Debug};
+use any_spawner::Executor;
+use std::{borrow::Cow, fmt::Debug, future::Future, time::Duration};
 use wasm_bindgen::JsValue;
 
 /// A DOM renderer.
@@ -13,6 +14,8 @@ pub mod mock_dom;
 /// A DOM renderer optimized for element creation.
 #[cfg(feature = "sledgehammer")]
 pub mod sledgehammer;
+/// A renderer that does nothing, for server-only builds.
+pub mod ssr;
 
 /// Implements the instructions necessary to render an interface on some platform.
 /// By default, this is implemented for the Document Object Model (DOM) in a Web
@@ -129,6 +132,20 @@ pub trait Renderer: Send + Sized + Debug + 'static {
 
     /// Logs the given node in a platform-appropriate way.
     fn log_node(node: &Self::Node);
+
+    /// Returns the tag name of the given element, if the renderer can provide it.
+    ///
+    /// This is only used for diagnostics, for example to detect hydration mismatches.
+    fn tag_name(_el: &Self::Element) -> Option<String> {
+        None
+    }
+
+    /// Returns the text of the given node if it is a comment, and the renderer can provide it.
+    ///
+    /// This is used during hydration to find the markers around out-of-order chunks.
+    fn comment_text(_node: &Self::Node) -> Option<String> {
+        None
+    }
 }
 
 /// A function that can be called to remove an event handler from an element after it has been added.
@@ -160,6 +177,12 @@ pub trait DomRenderer: Renderer {
     /// The type of a `<template>` element.
     type TemplateElement;
 
+    /// Whether this renderer is passed the actual values of properties.
+    ///
+    /// A [`JsValue`] can only be created in a JavaScript environment, so renderers that run
+    /// outside of one set this to `false`, and are passed [`JsValue::UNDEFINED`] instead.
+    const JS_PROPERTIES: bool = true;
+
     /// Sets a JavaScript object property on a DOM element.
     fn set_property(el: &Self::Element, key: &str, value: &JsValue);
 
@@ -187,6 +210,14 @@ pub trait DomRenderer: Renderer {
     where
         T: CastFrom<Self::Element>;
 
+    /// Whether `el` is the `event.target`, rather than an ancestor the event has bubbled up to.
+    ///
+    /// By default, every event is treated as targeting `el`, for renderers that cannot tell.
+    fn is_event_target(ev: &Self::Event, el: &Self::Element) -> bool {
+        _ = (ev, el);
+        true
+    }
+
     /// The list of CSS classes for an element.
     fn class_list(el: &Self::Element) -> Self::ClassList;
 
@@ -215,6 +246,30 @@ pub trait DomRenderer: Renderer {
         V: ToTemplate + 'static;
     /// Deeply clones a template.
     fn clone_template(tpl: &Self::TemplateElement) -> Self::Element;
+
+    /// Removes an element from its parent once `done` resolves to `true`, rather than
+    /// immediately. If it resolves to `false`, the element is left where it is.
+    ///
+    /// This allows an element to stay in the document while an exit animation runs.
+    fn remove_after(
+        el: &Self::Element,
+        done: impl Future<Output = bool> + 'static,
+    ) {
+        let el = el.clone();
+        Executor::spawn_local(async move {
+            if done.await {
+                Self::remove(el.as_ref());
+            }
+        });
+    }
+
+    /// Returns a future that resolves once `duration` has passed.
+    ///
+    /// By default it never resolves, for renderers that have no timers.
+    fn sleep(duration: Duration) -> impl Future<Output = ()> + 'static {
+        _ = duration;
+        futures::future::pending()
+    }
 }
 
 /// Attempts to cast from one type to another.
@@ -0,0 +1,215 @@
+//! A renderer that does nothing, for server-only builds.
+//!
+//! Views are rendered to HTML on the server with [`RenderHtml`](crate::view::RenderHtml), which
+//! does not touch the renderer at all. Using [`Ssr`] as the renderer type means that the rest of
+//! the view machinery, such as [`Render::build`](crate::view::Render::build), still compiles and
+//! runs on native targets without touching `wasm-bindgen`, which is useful for pure SSR binaries
+//! and for benchmarking view construction on the server.
+
+use super::{CastFrom, DomRenderer, RemoveEventHandler, Renderer};
+use crate::{
+    html::element::{CreateElement, ElementType},
+    view::{Mountable, ToTemplate},
+};
+use std::borrow::Cow;
+use wasm_bindgen::JsValue;
+
+/// A [`Renderer`] whose node types are all zero-sized, and whose operations are all no-ops.
+#[derive(Debug)]
+pub struct Ssr;
+
+/// A node created by the [`Ssr`] renderer, which has no contents.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SsrNode;
+
+impl AsRef<SsrNode> for SsrNode {
+    fn as_ref(&self) -> &SsrNode {
+        self
+    }
+}
+
+impl CastFrom<SsrNode> for SsrNode {
+    fn cast_from(source: SsrNode) -> Option<Self> {
+        Some(source)
+    }
+}
+
+impl Mountable<Ssr> for SsrNode {
+    fn unmount(&mut self) {}
+
+    fn mount(&mut self, _parent: &SsrNode, _marker: Option<&SsrNode>) {}
+
+    fn insert_before_this(&self, _child: &mut dyn Mountable<Ssr>) -> bool {
+        false
+    }
+}
+
+impl<E: ElementType> CreateElement<Ssr> for E {
+    fn create_element(&self) -> SsrNode {
+        SsrNode
+    }
+}
+
+impl Renderer for Ssr {
+    type Node = SsrNode;
+    type Element = SsrNode;
+    type Text = SsrNode;
+    type Placeholder = SsrNode;
+
+    fn intern(text: &str) -> &str {
+        text
+    }
+
+    fn create_text_node(_text: &str) -> Self::Text {
+        SsrNode
+    }
+
+    fn create_placeholder() -> Self::Placeholder {
+        SsrNode
+    }
+
+    fn set_text(_node: &Self::Text, _text: &str) {}
+
+    fn set_attribute(_node: &Self::Element, _name: &str, _value: &str) {}
+
+    fn remove_attribute(_node: &Self::Element, _name: &str) {}
+
+    fn insert_node(
+        _parent: &Self::Element,
+        _new_child: &Self::Node,
+        _marker: Option<&Self::Node>,
+    ) {
+    }
+
+    fn mount_before<M>(_new_child: &mut M, _before: &Self::Node)
+    where
+        M: Mountable<Self>,
+    {
+    }
+
+    fn remove_node(
+        _parent: &Self::Element,
+        _child: &Self::Node,
+    ) -> Option<Self::Node> {
+        None
+    }
+
+    fn clear_children(_parent: &Self::Element) {}
+
+    fn remove(_node: &Self::Node) {}
+
+    fn get_parent(_node: &Self::Node) -> Option<Self::Node> {
+        None
+    }
+
+    fn first_child(_node: &Self::Node) -> Option<Self::Node> {
+        None
+    }
+
+    fn next_sibling(_node: &Self::Node) -> Option<Self::Node> {
+        None
+    }
+
+    // there is nothing to log, as nodes have no contents
+    fn log_node(_node: &Self::Node) {}
+}
+
+impl DomRenderer for Ssr {
+    type Event = JsValue;
+    type ClassList = ();
+    type CssStyleDeclaration = ();
+    type TemplateElement = ();
+
+    const JS_PROPERTIES: bool = false;
+
+    fn set_property(_el: &Self::Element, _key: &str, _value: &JsValue) {}
+
+    fn add_event_listener(
+        _el: &Self::Element,
+        _name: &str,
+        _cb: Box<dyn FnMut(Self::Event)>,
+    ) -> RemoveEventHandler<Self::Element> {
+        RemoveEventHandler::new(|_| {})
+    }
+
+    fn add_event_listener_delegated(
+        _el: &Self::Element,
+        _name: Cow<'static, str>,
+        _delegation_key: Cow<'static, str>,
+        _cb: Box<dyn FnMut(Self::Event)>,
+    ) -> RemoveEventHandler<Self::Element> {
+        RemoveEventHandler::new(|_| {})
+    }
+
+    fn event_target<T>(_ev: &Self::Event) -> T
+    where
+        T: CastFrom<Self::Element>,
+    {
+        // events are never dispatched, but every element is the same empty node anyway
+        T::cast_from(SsrNode)
+            .expect("the event target could not be cast from an SsrNode")
+    }
+
+    fn class_list(_el: &Self::Element) -> Self::ClassList {}
+
+    fn add_class(_class_list: &Self::ClassList, _name: &str) {}
+
+    fn remove_class(_class_list: &Self::ClassList, _name: &str) {}
+
+    fn style(_el: &Self::Element) -> Self::CssStyleDeclaration {}
+
+    fn set_css_property(
+        _style: &Self::CssStyleDeclaration,
+        _name: &str,
+        _value: &str,
+    ) {
+    }
+
+    fn set_inner_html(_el: &Self::Element, _html: &str) {}
+
+    fn get_template<V>() -> Self::TemplateElement
+    where
+        V: ToTemplate + 'static,
+    {
+    }
+
+    fn clone_template(_tpl: &Self::TemplateElement) -> Self::Element {
+        SsrNode
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Ssr;
+    use crate::{
+        html::{
+            attribute::global::{
+                ClassAttribute, GlobalAttributes, OnAttribute, PropAttribute,
+            },
+            element::{
+                button, input, main, p, ElementChild, HtmlElement, Main,
+            },
+            event,
+        },
+        view::{Render, RenderHtml},
+    };
+
+    #[test]
+    fn views_can_be_built_and_rendered() {
+        fn view() -> HtmlElement<Main, (), impl RenderHtml<Ssr>, Ssr> {
+            main().child((
+                p().id("test").class("foo").child("Hello, world!"),
+                button().on(event::click, |_| {}).child("Click"),
+                input().prop("value", "typed"),
+            ))
+        }
+
+        let mut state = view().build();
+        view().rebuild(&mut state);
+        assert_eq!(
+            view().to_html(),
+            "<main><p id=\"test\" class=\"foo\">Hello, \
+             world!</p><button>Click</button><input></main>"
+        );
+    }
+}
@@ -12,7 +12,17 @@ use std::{
     task::{Context, Poll},
 };
 
+/// Renders views to normalized HTML for snapshot tests.
+pub mod snapshot;
+
 /// Manages streaming HTML rendering for the response to a single request.
+///
+/// `StreamBuilder` implements [`Stream<Item = String>`](Stream), so HTML is only rendered as
+/// quickly as the consumer polls for it. By default, any synchronously-available HTML is yielded
+/// as soon as the stream reaches an async chunk; setting a
+/// [minimum flush size](StreamBuilder::with_min_flush_size) instead keeps buffering chunks that
+/// are already ready until at least that many bytes are available, which avoids sending many
+/// tiny packets for fine-grained views.
 #[derive(Default)]
 pub struct StreamBuilder {
     pub(crate) sync_buf: String,
@@ -20,6 +30,7 @@ pub struct StreamBuilder {
     pending: Option<ChunkFuture>,
     pending_ooo: VecDeque<PinnedFuture<OooChunk>>,
     id: Option<Vec<u16>>,
+    min_flush_size: usize,
 }
 
 type PinnedFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
@@ -40,6 +51,32 @@ impl StreamBuilder {
         }
     }
 
+    /// Sets the minimum number of bytes that will be buffered before a chunk is yielded.
+    ///
+    /// HTML is still yielded below this size whenever the stream would otherwise have to wait
+    /// for an async chunk, or when the stream is finished, so this never delays content that
+    /// could already be sent.
+    pub fn with_min_flush_size(mut self, min_flush_size: usize) -> Self {
+        self.min_flush_size = min_flush_size;
+        self
+    }
+
+    /// Sets the minimum number of bytes that will be buffered before a chunk is yielded.
+    ///
+    /// See [`StreamBuilder::with_min_flush_size`].
+    pub fn set_min_flush_size(&mut self, min_flush_size: usize) {
+        self.min_flush_size = min_flush_size;
+    }
+
+    /// Returns the minimum number of bytes that will be buffered before a chunk is yielded.
+    pub fn min_flush_size(&self) -> usize {
+        self.min_flush_size
+    }
+
+    fn should_flush(&self) -> bool {
+        !self.sync_buf.is_empty() && self.sync_buf.len() >= self.min_flush_size
+    }
+
     /// Reserves additional space in the synchronous buffer.
     pub fn reserve(&mut self, additional: usize) {
         self.sync_buf.reserve(additional);
@@ -213,6 +250,7 @@ impl Debug for StreamBuilder {
             .field("sync_buf", &self.sync_buf)
             .field("chunks", &self.chunks)
             .field("pending", &self.pending.is_some())
+            .field("min_flush_size", &self.min_flush_size)
             .finish()
     }
 }
@@ -265,7 +303,13 @@ impl OooChunk {
              {if(walker.currentNode.textContent == `s-${id}o`){ \
              open=walker.currentNode; } else \
              if(walker.currentNode.textContent == `s-${id}c`) { close = \
-             walker.currentNode;}}let range = new Range(); \
+             walker.currentNode;}}",
+        );
+        // if the page was hydrated before this chunk arrived, the hydrated view has removed the
+        // markers and will render the resolved content itself, so the chunk is discarded
+        buf.push_str(
+            "if(!open){document.getElementById(`${id}f`).remove();if(close){\
+             close.remove();}return;}let range = new Range(); \
              range.setStartBefore(open); range.setEndBefore(close);",
         );
         if replace {
@@ -309,7 +353,13 @@ impl Stream for StreamBuilder {
             match pending.as_mut().poll(cx) {
                 Poll::Pending => {
                     this.pending = Some(pending);
-                    Poll::Pending
+                    // anything held back to reach the minimum flush size
+                    // should be sent rather than waiting on the async chunk
+                    if this.sync_buf.is_empty() {
+                        Poll::Pending
+                    } else {
+                        Poll::Ready(Some(mem::take(&mut this.sync_buf)))
+                    }
                 }
                 Poll::Ready(chunks) => {
                     for chunk in chunks.into_iter().rev() {
@@ -425,18 +475,18 @@ impl Stream for StreamBuilder {
                 }
                 Some(StreamChunk::Async { chunks, .. }) => {
                     this.pending = Some(chunks);
-                    if this.sync_buf.is_empty() {
-                        self.poll_next(cx)
-                    } else {
+                    if this.should_flush() {
                         Poll::Ready(Some(mem::take(&mut this.sync_buf)))
+                    } else {
+                        self.poll_next(cx)
                     }
                 }
                 Some(StreamChunk::OutOfOrder { chunks, .. }) => {
                     this.pending_ooo.push_back(chunks);
-                    if this.sync_buf.is_empty() {
-                        self.poll_next(cx)
-                    } else {
+                    if this.should_flush() {
                         Poll::Ready(Some(mem::take(&mut this.sync_buf)))
+                    } else {
+                        self.poll_next(cx)
                     }
                 }
             }
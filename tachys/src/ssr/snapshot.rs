@@ -0,0 +1,253 @@
+//! Renders views to HTML that is stable enough to compare in snapshot tests.
+//!
+//! The HTML rendered on the server includes comments that mark where text nodes, branches and
+//! async chunks begin and end, for the client to use when it hydrates the page. These change
+//! whenever the structure of a view changes, even if the page looks the same, so
+//! [`HtmlSnapshot`] strips them by default. It also sorts the attributes of each element by
+//! name, so that the order in which they are added to a view does not matter.
+//!
+//! ```rust
+//! # use tachys::{html::element::p, prelude::*, ssr::snapshot::HtmlSnapshot};
+//! let view = p::<Dom>().attr("id", "greeting").attr("class", "big").child("Hello");
+//! assert_eq!(
+//!     HtmlSnapshot::new().render(view),
+//!     r#"<p class="big" id="greeting">Hello</p>"#
+//! );
+//! ```
+
+use crate::{renderer::Renderer, view::RenderHtml};
+use futures::StreamExt;
+
+/// Renders views to normalized HTML for snapshot tests, as described in the
+/// [module documentation](self).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HtmlSnapshot {
+    keep_markers: bool,
+}
+
+impl HtmlSnapshot {
+    /// Creates a snapshot renderer that strips hydration markers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the comments used as hydration markers are kept, which is useful to catch
+    /// changes to the structure that the client will expect when it hydrates.
+    pub fn keep_markers(mut self, keep: bool) -> Self {
+        self.keep_markers = keep;
+        self
+    }
+
+    /// Renders the view to normalized HTML.
+    pub fn render<V, R>(self, view: V) -> String
+    where
+        V: RenderHtml<R>,
+        R: Renderer,
+    {
+        self.normalize(&view.to_html())
+    }
+
+    /// Streams the view, in order or out of order, and returns each chunk of normalized HTML
+    /// in the order it was sent.
+    ///
+    /// Comparing the chunks, rather than only the whole page, catches changes to when each
+    /// part of the page is sent, like a resource that is no longer streamed out of order.
+    /// Chunks that are empty once normalized are left out.
+    pub async fn render_stream<V, R>(
+        self,
+        view: V,
+        out_of_order: bool,
+    ) -> Vec<String>
+    where
+        V: RenderHtml<R>,
+        R: Renderer,
+    {
+        let stream = if out_of_order {
+            view.to_html_stream_out_of_order()
+        } else {
+            view.to_html_stream_in_order()
+        };
+        stream
+            .map(|chunk| self.normalize(&chunk))
+            .filter(|chunk| std::future::ready(!chunk.is_empty()))
+            .collect()
+            .await
+    }
+
+    /// Normalizes HTML that has already been rendered.
+    pub fn normalize(self, html: &str) -> String {
+        let mut out = String::with_capacity(html.len());
+        let mut rest = html;
+        while let Some(start) = rest.find('<') {
+            out.push_str(&rest[..start]);
+            rest = &rest[start..];
+            if let Some(after) = rest.strip_prefix("<!--") {
+                let end = after.find("-->").map_or(after.len(), |end| end + 3);
+                if self.keep_markers {
+                    out.push_str(&rest[..end + 4]);
+                }
+                rest = &after[end..];
+            } else if let Some(after) = rest.strip_prefix("<!>") {
+                if self.keep_markers {
+                    out.push_str("<!>");
+                }
+                rest = after;
+            } else if rest.starts_with("</") || rest.starts_with("<!") {
+                let end = rest.find('>').map_or(rest.len(), |end| end + 1);
+                out.push_str(&rest[..end]);
+                rest = &rest[end..];
+            } else {
+                let Some((tag, len)) = parse_start_tag(rest) else {
+                    // not a tag, or it is cut off
+                    out.push('<');
+                    rest = &rest[1..];
+                    continue;
+                };
+                // the contents of raw text elements are not HTML
+                let raw_text =
+                    matches!(tag.name, "script" | "style") && !tag.self_closing;
+                let close = format!("</{}", tag.name);
+                tag.write(&mut out);
+                rest = &rest[len..];
+                if raw_text {
+                    let end = rest.find(&close).unwrap_or(rest.len());
+                    out.push_str(&rest[..end]);
+                    rest = &rest[end..];
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+struct StartTag<'a> {
+    name: &'a str,
+    attrs: Vec<(&'a str, Option<&'a str>)>,
+    self_closing: bool,
+}
+
+impl StartTag<'_> {
+    fn write(mut self, buf: &mut String) {
+        self.attrs.sort_by_key(|(name, _)| *name);
+        buf.push('<');
+        buf.push_str(self.name);
+        for (name, value) in self.attrs {
+            buf.push(' ');
+            buf.push_str(name);
+            if let Some(value) = value {
+                buf.push_str("=\"");
+                buf.push_str(&value.replace('"', "&quot;"));
+                buf.push('"');
+            }
+        }
+        buf.push_str(if self.self_closing { "/>" } else { ">" });
+    }
+}
+
+/// Parses the start tag at the beginning of `html`, returning it and its length.
+fn parse_start_tag(html: &str) -> Option<(StartTag<'_>, usize)> {
+    let is_name_end =
+        |c: char| c.is_ascii_whitespace() || matches!(c, '>' | '/' | '=');
+    let body = html.strip_prefix('<')?;
+    let name_len = body.find(is_name_end)?;
+    if name_len == 0 || !body.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let name = &body[..name_len];
+    let mut rest = &body[name_len..];
+    let mut attrs = Vec::new();
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix("/>") {
+            let len = html.len() - after.len();
+            let tag = StartTag {
+                name,
+                attrs,
+                self_closing: true,
+            };
+            return Some((tag, len));
+        } else if let Some(after) = rest.strip_prefix('>') {
+            let len = html.len() - after.len();
+            let tag = StartTag {
+                name,
+                attrs,
+                self_closing: false,
+            };
+            return Some((tag, len));
+        } else if let Some(after) = rest.strip_prefix('/') {
+            rest = after;
+            continue;
+        }
+
+        let attr_len = rest.find(is_name_end)?;
+        if attr_len == 0 {
+            return None;
+        }
+        let attr = &rest[..attr_len];
+        rest = rest[attr_len..].trim_start();
+        let Some(after) = rest.strip_prefix('=') else {
+            attrs.push((attr, None));
+            continue;
+        };
+        let after = after.trim_start();
+        let (value, after) = match after.chars().next()? {
+            quote @ ('"' | '\'') => {
+                let end = after[1..].find(quote)? + 1;
+                (&after[1..end], &after[end + 1..])
+            }
+            _ => {
+                let end = after
+                    .find(|c: char| c.is_ascii_whitespace() || c == '>')?;
+                (&after[..end], &after[end..])
+            }
+        };
+        attrs.push((attr, Some(value)));
+        rest = after;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HtmlSnapshot;
+    use crate::{
+        html::element::{div, input, p},
+        prelude::*,
+    };
+
+    #[test]
+    fn sorts_attributes_and_strips_markers() {
+        let html = r#"<!DOCTYPE html><div id="a" class='x "y"' hidden><!>text<!--bo-1--><input value=1 type="text"/><!--bc-1--></div><script>if (a < b) {}</script>"#;
+        assert_eq!(
+            HtmlSnapshot::new().normalize(html),
+            r#"<!DOCTYPE html><div class="x &quot;y&quot;" hidden id="a">text<input type="text" value="1"/></div><script>if (a < b) {}</script>"#
+        );
+        assert_eq!(
+            HtmlSnapshot::new()
+                .keep_markers(true)
+                .normalize("<p b a><!>x<!--[--></p>"),
+            "<p a b><!>x<!--[--></p>"
+        );
+        assert_eq!(HtmlSnapshot::new().normalize("1 < 2 <"), "1 < 2 <");
+    }
+
+    #[test]
+    fn renders_views_and_streams() {
+        let view = || {
+            div::<Dom>()
+                .attr("id", "main")
+                .class("a")
+                .child((p().child("one"), input().attr("type", "text")))
+        };
+        let html = HtmlSnapshot::new().render(view());
+        assert_eq!(
+            html,
+            r#"<div class="a" id="main"><p>one</p><input type="text"></div>"#
+        );
+
+        let chunks = futures::executor::block_on(
+            HtmlSnapshot::new().render_stream(view(), false),
+        );
+        assert_eq!(chunks, [html]);
+    }
+}
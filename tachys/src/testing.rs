@@ -0,0 +1,301 @@
+//! Helpers for testing views natively, without a browser.
+//!
+//! Views that are generic over their renderer can be mounted into the [`MockDom`], queried for
+//! elements by their text or role, and sent synthetic events, all in a plain `#[test]`.
+//!
+//! ```rust
+//! # use std::{cell::Cell, rc::Rc};
+//! # use tachys::{
+//! #     html::{element::{button, p}, event as ev},
+//! #     prelude::*,
+//! #     renderer::mock_dom::MockDom,
+//! #     testing::{click, find_by_role, find_by_text, mount_to, test_dom},
+//! # };
+//! let clicks = Rc::new(Cell::new(0));
+//! let view = (
+//!     p::<MockDom>().child("Hello!"),
+//!     button().child("Click me").on(ev::click, {
+//!         let clicks = Rc::clone(&clicks);
+//!         move |_| clicks.set(clicks.get() + 1)
+//!     }),
+//! );
+//! let mounted = mount_to(&test_dom(), view);
+//!
+//! assert!(find_by_text(mounted.root(), "Hello!").is_some());
+//! click(&find_by_role(mounted.root(), "button").unwrap());
+//! assert_eq!(clicks.get(), 1);
+//! ```
+//!
+//! The output of `view!` is generic over its renderer, so it can be mounted in the same way.
+//! Components that return `impl IntoView` are always rendered with the browser DOM, so the
+//! logic that should be tested natively can be written in a function that returns the `view!`
+//! itself, and is called by the component.
+//!
+//! The events passed to listeners are `undefined`, but
+//! [`event_target_value`](crate::dom::event_target_value) and
+//! [`event_target_checked`](crate::dom::event_target_checked) read from the element the event
+//! was dispatched to, so listeners that use them can be tested too.
+
+use crate::{
+    html::element::Body,
+    renderer::{
+        mock_dom::{Element, MockDom},
+        Renderer,
+    },
+    view::{Mountable, Render},
+};
+
+/// Creates an empty `<body>` element to mount views into.
+pub fn test_dom() -> Element {
+    MockDom::create_element(Body)
+}
+
+/// A view that has been mounted with [`mount_to`].
+///
+/// The view is unmounted when this is dropped.
+#[must_use = "the view is unmounted when this is dropped"]
+pub struct Mounted<S>
+where
+    S: Mountable<MockDom>,
+{
+    root: Element,
+    state: S,
+}
+
+impl<S> Mounted<S>
+where
+    S: Mountable<MockDom>,
+{
+    /// The element the view was mounted into.
+    pub fn root(&self) -> &Element {
+        &self.root
+    }
+
+    /// The HTML of the element the view was mounted into.
+    pub fn html(&self) -> String {
+        self.root.to_debug_html()
+    }
+
+    /// The state of the mounted view.
+    pub fn state(&mut self) -> &mut S {
+        &mut self.state
+    }
+}
+
+impl<S> Drop for Mounted<S>
+where
+    S: Mountable<MockDom>,
+{
+    fn drop(&mut self) {
+        self.state.unmount();
+    }
+}
+
+/// Builds the view and mounts it at the end of the given element.
+pub fn mount_to<V>(test_dom: &Element, view: V) -> Mounted<V::State>
+where
+    V: Render<MockDom>,
+{
+    let mut state = view.build();
+    state.mount(test_dom, None);
+    Mounted {
+        root: test_dom.clone(),
+        state,
+    }
+}
+
+/// Finds the innermost element below `root` whose text, trimmed, is `text`.
+pub fn find_by_text(root: &Element, text: &str) -> Option<Element> {
+    find_all_by_text(root, text).into_iter().next()
+}
+
+/// Finds every element below `root` whose text, trimmed, is `text`, skipping elements that
+/// only match because one of their children does.
+pub fn find_all_by_text(root: &Element, text: &str) -> Vec<Element> {
+    let mut found = Vec::new();
+    for child in root.child_elements() {
+        let before = found.len();
+        found.extend(find_all_by_text(&child, text));
+        if found.len() == before && child.text_content().trim() == text {
+            found.push(child);
+        }
+    }
+    found
+}
+
+/// Finds the first element below `root` with the given [ARIA role], either set with the `role`
+/// attribute or implied by the element, like `button` for a `<button>` or `link` for an `<a>`
+/// with an `href`.
+///
+/// [ARIA role]: https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Roles
+pub fn find_by_role(root: &Element, role: &str) -> Option<Element> {
+    find_all_by_role(root, role).into_iter().next()
+}
+
+/// Finds every element below `root` with the given ARIA role, in document order.
+///
+/// See [`find_by_role`].
+pub fn find_all_by_role(root: &Element, role: &str) -> Vec<Element> {
+    let mut found = Vec::new();
+    for child in root.child_elements() {
+        if role_of(&child).as_deref() == Some(role) {
+            found.push(child.clone());
+        }
+        found.extend(find_all_by_role(&child, role));
+    }
+    found
+}
+
+fn role_of(el: &Element) -> Option<String> {
+    if let Some(role) = el.attribute("role") {
+        return Some(role);
+    }
+    let tag = MockDom::tag_name(el)?.to_ascii_lowercase();
+    let role = match tag.as_str() {
+        "a" | "area" if el.attribute("href").is_some() => "link",
+        "article" => "article",
+        "aside" => "complementary",
+        "button" => "button",
+        "dialog" => "dialog",
+        "footer" => "contentinfo",
+        "form" => "form",
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => "heading",
+        "header" => "banner",
+        "hr" => "separator",
+        "img" => "img",
+        "input" => match el.attribute("type").as_deref() {
+            Some("button" | "image" | "reset" | "submit") => "button",
+            Some("checkbox") => "checkbox",
+            Some("radio") => "radio",
+            Some("range") => "slider",
+            Some("number") => "spinbutton",
+            Some("search") => "searchbox",
+            _ => "textbox",
+        },
+        "li" => "listitem",
+        "main" => "main",
+        "nav" => "navigation",
+        "ol" | "ul" => "list",
+        "option" => "option",
+        "progress" => "progressbar",
+        "section" => "region",
+        "select" => "combobox",
+        "table" => "table",
+        "td" => "cell",
+        "textarea" => "textbox",
+        "th" => "columnheader",
+        "tr" => "row",
+        _ => return None,
+    };
+    Some(role.to_string())
+}
+
+/// Dispatches a synthetic `click` event to the element, which bubbles to its ancestors.
+pub fn click(el: &Element) {
+    MockDom::dispatch_event(el, "click");
+}
+
+/// Sets the `value` attribute of the element, as if the user had typed it, and dispatches an
+/// `input` event.
+pub fn input(el: &Element, value: &str) {
+    MockDom::set_attribute(el, "value", value);
+    MockDom::dispatch_event(el, "input");
+}
+
+/// Sets or removes the `checked` attribute of the element, as if the user had clicked it, and
+/// dispatches a `change` event.
+pub fn check(el: &Element, checked: bool) {
+    if checked {
+        MockDom::set_attribute(el, "checked", "");
+    } else {
+        MockDom::remove_attribute(el, "checked");
+    }
+    MockDom::dispatch_event(el, "change");
+}
+
+/// Waits until the async executor has run the tasks that were ready, like effects that were
+/// scheduled by updating a signal.
+///
+/// This requires an [`Executor`](any_spawner::Executor) to have been initialized.
+pub async fn tick() {
+    any_spawner::Executor::tick().await;
+}
+
+/// Runs `action`, and then waits until the resources read while it ran have resolved, followed
+/// by a [`tick`] so that the view can update.
+///
+/// This requires an [`Executor`](any_spawner::Executor) to have been initialized.
+#[cfg(feature = "reactive_graph")]
+pub async fn resolve_resources<T>(action: impl FnOnce() -> T) -> T {
+    let value = reactive_graph::transition::AsyncTransition::run(|| {
+        let value = action();
+        async move { value }
+    })
+    .await;
+    tick().await;
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        html::{
+            element::{a, button, div, h2, input as input_el, li, p, ul},
+            event as ev,
+        },
+        prelude::*,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    #[test]
+    fn finds_elements_by_text_and_role() {
+        let view = div::<MockDom>().child((
+            h2().child("Posts"),
+            ul().child((li().child("First"), li().child(p().child("Second")))),
+            a().attr("href", "/more").child("More"),
+        ));
+        let mounted = mount_to(&test_dom(), view);
+
+        let second = find_by_text(mounted.root(), "Second").unwrap();
+        assert_eq!(second.to_debug_html(), "<p>Second</p>");
+        assert!(find_by_text(mounted.root(), "Third").is_none());
+        assert_eq!(
+            find_by_role(mounted.root(), "heading")
+                .unwrap()
+                .text_content(),
+            "Posts"
+        );
+        assert_eq!(find_all_by_role(mounted.root(), "listitem").len(), 2);
+        assert_eq!(
+            find_by_role(mounted.root(), "link").unwrap().text_content(),
+            "More"
+        );
+    }
+
+    #[test]
+    fn dispatches_events_to_listeners() {
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let view = div::<MockDom>().class("form").child((
+            button().child("Save").on(ev::click, {
+                let events = Rc::clone(&events);
+                move |_| events.borrow_mut().push("click")
+            }),
+            input_el().on(ev::input, {
+                let events = Rc::clone(&events);
+                move |_| events.borrow_mut().push("input")
+            }),
+        ));
+        let mounted = mount_to(&test_dom(), view);
+
+        click(&find_by_text(mounted.root(), "Save").unwrap());
+        let field = find_by_role(mounted.root(), "textbox").unwrap();
+        input(&field, "hello");
+        assert_eq!(*events.borrow(), ["click", "input"]);
+        assert_eq!(field.attribute("value").as_deref(), Some("hello"));
+
+        let root = mounted.root().clone();
+        drop(mounted);
+        assert_eq!(root.to_debug_html(), "<body></body>");
+    }
+}
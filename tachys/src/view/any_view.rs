@@ -26,6 +26,11 @@ use std::{future::Future, pin::Pin};
 /// Generally speaking, using `AnyView` restricts the amount of information available to the
 /// compiler and should be limited to situations in which it is necessary to preserve the maximum
 /// amount of type information possible.
+///
+/// An `AnyView` can be rendered to HTML, hydrated, and rebuilt like any other view. Rebuilding
+/// with a view of the same underlying type updates the existing view in place; rebuilding with a
+/// different type replaces it.
+#[doc(alias = "DynChild")]
 pub struct AnyView<R>
 where
     R: Renderer,
@@ -492,27 +497,56 @@ where
         (self.insert_before_this)(&*self.state, child)
     }
 }
-/*
 #[cfg(test)]
 mod tests {
     use super::IntoAny;
     use crate::{
-        html::element::{p, span},
-        renderer::mock_dom::MockDom,
-        view::{any_view::AnyView, RenderHtml},
+        html::element::{p, span, Div, ElementChild},
+        renderer::{
+            mock_dom::{node_eq, MockDom},
+            Renderer,
+        },
+        view::{any_view::AnyView, Mountable, Render},
     };
 
+    fn either(x: i32) -> AnyView<MockDom> {
+        if x == 0 {
+            p().child("foo").into_any()
+        } else {
+            span().child(format!("bar {x}")).into_any()
+        }
+    }
+
+    #[test]
+    fn rebuilds_in_place_or_replaces_the_view() {
+        let parent = MockDom::create_element(Div);
+        let mut state = either(0).build();
+        state.mount(&parent, None);
+        assert_eq!(parent.to_debug_html(), "<div><p>foo</p></div>");
+
+        // a different type of view replaces the old one
+        either(1).rebuild(&mut state);
+        assert_eq!(parent.to_debug_html(), "<div><span>bar 1</span></div>");
+
+        // the same type of view is updated in place
+        let span = MockDom::first_child(parent.as_ref()).unwrap();
+        either(2).rebuild(&mut state);
+        assert_eq!(parent.to_debug_html(), "<div><span>bar 2</span></div>");
+        assert!(node_eq(
+            MockDom::first_child(parent.as_ref()).unwrap(),
+            span
+        ));
+
+        state.unmount();
+        assert_eq!(parent.to_debug_html(), "<div></div>");
+    }
+
+    #[cfg(feature = "ssr")]
     #[test]
     fn should_handle_html_creation() {
-        let x = 1;
-        let mut buf = String::new();
-        let view: AnyView<MockDom> = if x == 0 {
-            p((), "foo").into_any()
-        } else {
-            span((), "bar").into_any()
-        };
-        view.to_html(&mut buf, &Default::default());
-        assert_eq!(buf, "<span>bar</span><!>");
+        use crate::view::RenderHtml;
+
+        assert_eq!(either(0).to_html(), "<p>foo</p>");
+        assert_eq!(either(1).to_html(), "<span>bar 1</span>");
     }
 }
- */
@@ -19,6 +19,11 @@ use std::{
 type FxIndexSet<T> = IndexSet<T, BuildHasherDefault<FxHasher>>;
 
 /// Creates a keyed list of views.
+///
+/// When the list is rebuilt, items are matched to the previous items by the key returned from
+/// `key_fn`. The view state for each existing key is kept and, if its position has changed, moved
+/// in the DOM using [`Mountable::insert_before_this`]. Views are only created for new keys, so
+/// items do not need to be `Clone`.
 pub fn keyed<T, I, K, KF, VF, V, Rndr>(
     items: I,
     key_fn: KF,
@@ -668,116 +673,173 @@ fn unpack_moves(diff: &Diff) -> (Vec<DiffOpMove>, Vec<DiffOpAdd>) {
 
     (moves, adds)
 }
-/*
 #[cfg(test)]
 mod tests {
     use crate::{
-        html::element::{li, ul, HtmlElement, Li},
-        renderer::mock_dom::MockDom,
+        html::element::{li, ul, ElementChild, HtmlElement, Li},
+        renderer::{
+            mock_dom::{node_eq, Element, MockDom, Node},
+            Renderer,
+        },
         view::{keyed::keyed, Render},
     };
 
-    fn item(key: usize) -> HtmlElement<Li, (), String, MockDom> {
-        li((), key.to_string())
+    fn item(key: usize) -> HtmlElement<Li, (), (String,), MockDom> {
+        li().child(key.to_string())
+    }
+
+    fn row(list: &Element, at: usize) -> Node {
+        let mut node = MockDom::first_child(list.as_ref()).unwrap();
+        for _ in 0..at {
+            node = MockDom::next_sibling(&node).unwrap();
+        }
+        node
     }
 
     #[test]
     fn keyed_creates_list() {
-        let el = ul((), keyed(1..=3, |k| *k, item));
+        let el = ul().child(keyed(1..=3, |k| *k, item));
         let el_state = el.build();
         assert_eq!(
             el_state.el.to_debug_html(),
-            "<ul><li>1</li><li>2</li><li>3</li></ul>"
+            "<ul><li>1</li><li>2</li><li>3</li><!></ul>"
         );
     }
 
     #[test]
     fn adding_items_updates_list() {
-        let el = ul((), keyed(1..=3, |k| *k, item));
+        let el = ul().child(keyed(1..=3, |k| *k, item));
         let mut el_state = el.build();
-        let el = ul((), keyed(1..=5, |k| *k, item));
+        let el = ul().child(keyed(1..=5, |k| *k, item));
         el.rebuild(&mut el_state);
         assert_eq!(
             el_state.el.to_debug_html(),
-            "<ul><li>1</li><li>2</li><li>3</li><li>4</li><li>5</li></ul>"
+            "<ul><li>1</li><li>2</li><li>3</li><li>4</li><li>5</li><!></ul>"
         );
     }
 
     #[test]
     fn removing_items_updates_list() {
-        let el = ul((), keyed(1..=3, |k| *k, item));
+        let el = ul().child(keyed(1..=3, |k| *k, item));
         let mut el_state = el.build();
-        let el = ul((), keyed(1..=2, |k| *k, item));
+        let el = ul().child(keyed(1..=2, |k| *k, item));
         el.rebuild(&mut el_state);
         assert_eq!(
             el_state.el.to_debug_html(),
-            "<ul><li>1</li><li>2</li></ul>"
+            "<ul><li>1</li><li>2</li><!></ul>"
         );
     }
 
     #[test]
     fn swapping_items_updates_list() {
-        let el = ul((), keyed([1, 2, 3, 4, 5], |k| *k, item));
+        let el = ul().child(keyed([1, 2, 3, 4, 5], |k| *k, item));
         let mut el_state = el.build();
-        let el = ul((), keyed([1, 4, 3, 2, 5], |k| *k, item));
+        let el = ul().child(keyed([1, 4, 3, 2, 5], |k| *k, item));
         el.rebuild(&mut el_state);
         assert_eq!(
             el_state.el.to_debug_html(),
-            "<ul><li>1</li><li>4</li><li>3</li><li>2</li><li>5</li></ul>"
+            "<ul><li>1</li><li>4</li><li>3</li><li>2</li><li>5</li><!></ul>"
         );
     }
 
+    #[test]
+    fn swapping_moves_existing_rows() {
+        let el = ul().child(keyed([1, 2, 3], |k| *k, item));
+        let mut el_state = el.build();
+        let first = row(&el_state.el, 0);
+        let last = row(&el_state.el, 2);
+
+        let el = ul().child(keyed([3, 2, 1], |k| *k, item));
+        el.rebuild(&mut el_state);
+        assert_eq!(
+            el_state.el.to_debug_html(),
+            "<ul><li>3</li><li>2</li><li>1</li><!></ul>"
+        );
+        assert!(node_eq(row(&el_state.el, 0), last));
+        assert!(node_eq(row(&el_state.el, 2), first));
+    }
+
     #[test]
     fn swapping_and_removing_orders_correctly() {
-        let el = ul((), keyed([1, 2, 3, 4, 5], |k| *k, item));
+        let el = ul().child(keyed([1, 2, 3, 4, 5], |k| *k, item));
         let mut el_state = el.build();
-        let el = ul((), keyed([1, 4, 3, 5], |k| *k, item));
+        let el = ul().child(keyed([1, 4, 3, 5], |k| *k, item));
         el.rebuild(&mut el_state);
         assert_eq!(
             el_state.el.to_debug_html(),
-            "<ul><li>1</li><li>4</li><li>3</li><li>5</li></ul>"
+            "<ul><li>1</li><li>4</li><li>3</li><li>5</li><!></ul>"
         );
     }
 
     #[test]
     fn arbitrarily_hard_adjustment() {
-        let el = ul((), keyed([1, 2, 3, 4, 5], |k| *k, item));
+        let el = ul().child(keyed([1, 2, 3, 4, 5], |k| *k, item));
         let mut el_state = el.build();
-        let el = ul((), keyed([2, 4, 3], |k| *k, item));
+        let el = ul().child(keyed([2, 4, 3], |k| *k, item));
         el.rebuild(&mut el_state);
         assert_eq!(
             el_state.el.to_debug_html(),
-            "<ul><li>2</li><li>4</li><li>3</li></ul>"
+            "<ul><li>2</li><li>4</li><li>3</li><!></ul>"
         );
     }
 
     #[test]
     fn a_series_of_moves() {
-        let el = ul((), keyed([1, 2, 3, 4, 5], |k| *k, item));
+        let el = ul().child(keyed(vec![1, 2, 3, 4, 5], |k| *k, item));
         let mut el_state = el.build();
-        let el = ul((), keyed([2, 4, 3], |k| *k, item));
-        el.rebuild(&mut el_state);
-        let el = ul((), keyed([1, 7, 5, 11, 13, 17], |k| *k, item));
-        el.rebuild(&mut el_state);
-        let el = ul((), keyed([2, 6, 8, 7, 13], |k| *k, item));
-        el.rebuild(&mut el_state);
-        let el = ul((), keyed([13, 4, 5, 3], |k| *k, item));
-        el.rebuild(&mut el_state);
-        let el = ul((), keyed([1, 2, 3, 4], |k| *k, item));
-        el.rebuild(&mut el_state);
+        for items in [
+            vec![2, 4, 3],
+            vec![1, 7, 5, 11, 13, 17],
+            vec![2, 6, 8, 7, 13],
+            vec![13, 4, 5, 3],
+            vec![1, 2, 3, 4],
+        ] {
+            let el = ul().child(keyed(items, |k| *k, item));
+            el.rebuild(&mut el_state);
+        }
         assert_eq!(
             el_state.el.to_debug_html(),
-            "<ul><li>1</li><li>2</li><li>3</li><li>4</li></ul>"
+            "<ul><li>1</li><li>2</li><li>3</li><li>4</li><!></ul>"
         );
     }
 
     #[test]
     fn clearing_works() {
-        let el = ul((), keyed([1, 2, 3, 4, 5], |k| *k, item));
+        let el = ul().child(keyed(vec![1, 2, 3, 4, 5], |k| *k, item));
         let mut el_state = el.build();
-        let el = ul((), keyed([], |k| *k, item));
+        let el = ul().child(keyed(vec![], |k| *k, item));
         el.rebuild(&mut el_state);
-        assert_eq!(el_state.el.to_debug_html(), "<ul></ul>");
+        assert_eq!(el_state.el.to_debug_html(), "<ul><!></ul>");
+    }
+
+    #[cfg(feature = "reactive_graph")]
+    #[test]
+    fn removing_items_cleans_up_their_owners() {
+        use crate::reactive_graph::OwnedView;
+        use reactive_graph::owner::{on_cleanup, Owner};
+        use std::sync::{Arc, Mutex};
+
+        let cleaned_up = Arc::new(Mutex::new(Vec::new()));
+        let owned_item = |key: usize| {
+            let owner = Owner::new();
+            owner.with(|| {
+                let cleaned_up = Arc::clone(&cleaned_up);
+                on_cleanup(move || cleaned_up.lock().unwrap().push(key));
+            });
+            OwnedView::new_with_owner(item(key), owner)
+        };
+
+        let el = ul().child(keyed(1..=4, |k| *k, owned_item));
+        let mut el_state = el.build();
+        let el = ul().child(keyed([1, 3], |k| *k, owned_item));
+        el.rebuild(&mut el_state);
+        let mut removed = cleaned_up.lock().unwrap().clone();
+        removed.sort();
+        assert_eq!(removed, [2, 4]);
+
+        drop(el_state);
+        let mut removed = cleaned_up.lock().unwrap().clone();
+        removed.sort();
+        assert_eq!(removed, [1, 2, 3, 4]);
     }
 }
-*/
@@ -17,6 +17,8 @@ pub mod fragment;
 pub mod iterators;
 /// Keyed list iteration.
 pub mod keyed;
+/// Rendering children into another part of the DOM.
+pub mod portal;
 mod primitives;
 /// Optimized types for static strings known at compile time.
 #[cfg(feature = "nightly")]
@@ -0,0 +1,275 @@
+use super::{
+    add_attr::AddAnyAttr, Mountable, Position, PositionState, Render,
+    RenderHtml,
+};
+use crate::{
+    html::attribute::Attribute, hydration::Cursor, renderer::Renderer,
+    ssr::StreamBuilder,
+};
+use std::marker::PhantomData;
+
+/// Renders its children into some other element, rather than where the portal appears in the
+/// view tree.
+///
+/// The children are built in the current reactive scope, so they can use context and signals from
+/// the surrounding view as normal. They are mounted into the `target` element when the portal is
+/// built, and removed from it when the portal is unmounted or its state is dropped, for example
+/// because the owner that created it has been disposed.
+///
+/// The target is provided as a function so that it is only accessed when the portal is built.
+/// Where the portal appears in the view tree, it only renders a placeholder, which keeps its place
+/// among its siblings. During server-side rendering, this placeholder is all that is rendered:
+/// the children are built from scratch rather than hydrated in the browser.
+pub struct Portal<T, V, R> {
+    target: T,
+    view: V,
+    rndr: PhantomData<R>,
+}
+
+/// Creates a [`Portal`] that mounts `view` into the element returned by `target`.
+pub fn portal<T, V, R>(target: T, view: V) -> Portal<T, V, R>
+where
+    T: FnOnce() -> R::Element,
+    V: Render<R>,
+    R: Renderer,
+{
+    Portal {
+        target,
+        view,
+        rndr: PhantomData,
+    }
+}
+
+/// The view state for a [`Portal`].
+pub struct PortalState<S, R>
+where
+    S: Mountable<R>,
+    R: Renderer,
+{
+    // keeps the portal's place in its parent
+    marker: R::Placeholder,
+    target: R::Element,
+    state: S,
+    mounted: bool,
+}
+
+impl<T, V, R> Render<R> for Portal<T, V, R>
+where
+    T: FnOnce() -> R::Element,
+    V: Render<R>,
+    R: Renderer,
+{
+    type State = PortalState<V::State, R>;
+
+    fn build(self) -> Self::State {
+        self.build_with_marker(R::create_placeholder())
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        self.view.rebuild(&mut state.state);
+    }
+}
+
+impl<T, V, R> Portal<T, V, R>
+where
+    T: FnOnce() -> R::Element,
+    V: Render<R>,
+    R: Renderer,
+{
+    fn build_with_marker(
+        self,
+        marker: R::Placeholder,
+    ) -> PortalState<V::State, R> {
+        let target = (self.target)();
+        let mut state = self.view.build();
+        state.mount(&target, None);
+        PortalState {
+            marker,
+            target,
+            state,
+            mounted: true,
+        }
+    }
+}
+
+impl<T, V, R> AddAnyAttr<R> for Portal<T, V, R>
+where
+    T: FnOnce() -> R::Element + Send,
+    V: RenderHtml<R>,
+    R: Renderer,
+{
+    type Output<SomeNewAttr: Attribute<R>> =
+        Portal<T, <V as AddAnyAttr<R>>::Output<SomeNewAttr>, R>;
+
+    fn add_any_attr<NewAttr: Attribute<R>>(
+        self,
+        attr: NewAttr,
+    ) -> Self::Output<NewAttr>
+    where
+        Self::Output<NewAttr>: RenderHtml<R>,
+    {
+        Portal {
+            target: self.target,
+            view: self.view.add_any_attr(attr),
+            rndr: PhantomData,
+        }
+    }
+}
+
+impl<T, V, R> RenderHtml<R> for Portal<T, V, R>
+where
+    T: FnOnce() -> R::Element + Send,
+    V: RenderHtml<R>,
+    R: Renderer,
+{
+    type AsyncOutput = Self;
+
+    const MIN_LENGTH: usize = 3;
+    const EXISTS: bool = false;
+
+    fn dry_resolve(&mut self) {}
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        self
+    }
+
+    fn to_html_with_buf(
+        self,
+        buf: &mut String,
+        position: &mut Position,
+        _escape: bool,
+        _mark_branches: bool,
+    ) {
+        buf.push_str("<!>");
+        *position = Position::NextChild;
+    }
+
+    fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+        self,
+        buf: &mut StreamBuilder,
+        position: &mut Position,
+        _escape: bool,
+        _mark_branches: bool,
+    ) {
+        buf.push_sync("<!>");
+        *position = Position::NextChild;
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        cursor: &Cursor<R>,
+        position: &PositionState,
+    ) -> Self::State {
+        // only the placeholder was rendered on the server, so the children are built from scratch
+        let marker = cursor.next_placeholder(position);
+        self.build_with_marker(marker)
+    }
+}
+
+impl<S, R> Mountable<R> for PortalState<S, R>
+where
+    S: Mountable<R>,
+    R: Renderer,
+{
+    fn unmount(&mut self) {
+        self.marker.unmount();
+        if self.mounted {
+            self.state.unmount();
+            self.mounted = false;
+        }
+    }
+
+    fn mount(&mut self, parent: &R::Element, marker: Option<&R::Node>) {
+        self.marker.mount(parent, marker);
+        if !self.mounted {
+            self.state.mount(&self.target, None);
+            self.mounted = true;
+        }
+    }
+
+    fn insert_before_this(&self, child: &mut dyn Mountable<R>) -> bool {
+        self.marker.insert_before_this(child)
+    }
+}
+
+impl<S, R> Drop for PortalState<S, R>
+where
+    S: Mountable<R>,
+    R: Renderer,
+{
+    fn drop(&mut self) {
+        // the portal's children are not inside its parent, so removing the parent from the DOM
+        // does not remove them
+        self.unmount();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::portal;
+    use crate::{
+        html::element::{p, span, Body, Div, ElementChild},
+        renderer::{mock_dom::MockDom, Renderer},
+        view::{Mountable, Render, RenderHtml},
+    };
+
+    #[test]
+    fn portal_mounts_into_target_and_cleans_up() {
+        let target = MockDom::create_element(Body);
+        let view = p().child((
+            "before",
+            portal::<_, _, MockDom>(
+                {
+                    let target = target.clone();
+                    move || target
+                },
+                span().child("portaled"),
+            ),
+            "after",
+        ));
+        let parent = MockDom::create_element(Div);
+        let mut state = view.build();
+        state.mount(&parent, None);
+
+        assert_eq!(parent.to_debug_html(), "<div><p>before<!>after</p></div>");
+        assert_eq!(
+            target.to_debug_html(),
+            "<body><span>portaled</span></body>"
+        );
+
+        drop(state);
+        assert_eq!(target.to_debug_html(), "<body></body>");
+    }
+
+    #[test]
+    fn portal_renders_nothing_on_the_server() {
+        let view = p().child(portal::<_, _, MockDom>(
+            || MockDom::create_element(Body),
+            span().child("portaled"),
+        ));
+        assert_eq!(view.to_html(), "<p><!></p>");
+    }
+
+    #[test]
+    fn siblings_can_be_inserted_before_a_portal() {
+        let target = MockDom::create_element(Body);
+        let parent = MockDom::create_element(Div);
+        let mut state = portal::<_, _, MockDom>(
+            {
+                let target = target.clone();
+                move || target
+            },
+            span().child("portaled"),
+        )
+        .build();
+        state.mount(&parent, None);
+
+        let mut sibling = p().child("sibling").build();
+        assert!(state.insert_before_this(&mut sibling));
+        assert_eq!(parent.to_debug_html(), "<div><p>sibling</p><!></div>");
+        assert_eq!(
+            target.to_debug_html(),
+            "<body><span>portaled</span></body>"
+        );
+    }
+}
@@ -116,6 +116,10 @@ where
     async fn resolve(self) -> Self::AsyncOutput {
         self
     }
+
+    fn keys(&self) -> Vec<NamedAttributeKey> {
+        vec![NamedAttributeKey::Attribute(K::KEY.into())]
+    }
 }
 
 impl<K, const V: &'static str, R> NextAttribute<R> for StaticAttr<K, V>
@@ -93,8 +93,13 @@ where
         }
 
         let node = cursor.current();
-        let node = R::Text::cast_from(node)
-            .expect("couldn't cast text node from node");
+        let node = R::Text::cast_from(node.clone()).unwrap_or_else(|| {
+            crate::hydration::failed_to_cast_node::<R>(
+                &format!("a text node containing \"{self}\""),
+                &node,
+                None,
+            )
+        });
 
         if !FROM_SERVER {
             R::set_text(&node, self);